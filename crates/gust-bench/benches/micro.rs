@@ -0,0 +1,89 @@
+//! Criterion microbenchmarks for the gust-core hot paths
+//!
+//! Covers the per-request work the serve loops repeat millions of
+//! times: route matching, HTTP/1.1 header parsing, JWT verification,
+//! and response compression. Run with `cargo bench -p gust-bench`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use gust_core::middleware::compress::Compress;
+use gust_core::middleware::jwt::{Claims, Jwt, JwtConfig};
+use gust_core::parser::{parse_request, HeaderOffsets, MAX_HEADERS};
+use gust_core::{Method, Middleware, Request, Response, Router};
+use std::hint::black_box;
+
+fn bench_router_find(c: &mut Criterion) {
+    let mut router = Router::new();
+    router.insert("GET", "/", 1);
+    router.insert("GET", "/users/:id", 2);
+    router.insert("GET", "/users/:id/posts/:post", 3);
+    router.insert("POST", "/users", 4);
+    router.insert("GET", "/static/*path", 5);
+    router.insert("GET", "/api/v1/items", 6);
+
+    c.bench_function("router_find_static", |b| {
+        b.iter(|| black_box(router.find(black_box("GET"), black_box("/api/v1/items"))))
+    });
+    c.bench_function("router_find_params", |b| {
+        b.iter(|| black_box(router.find(black_box("GET"), black_box("/users/42/posts/7"))))
+    });
+    c.bench_function("router_find_wildcard", |b| {
+        b.iter(|| black_box(router.find(black_box("GET"), black_box("/static/css/app.css"))))
+    });
+}
+
+fn bench_parse_request(c: &mut Criterion) {
+    let request = b"GET /users/42?page=2 HTTP/1.1\r\n\
+        host: localhost:3000\r\n\
+        user-agent: bench/1.0\r\n\
+        accept: application/json\r\n\
+        accept-encoding: gzip, br\r\n\
+        connection: keep-alive\r\n\r\n";
+
+    c.bench_function("parse_request_headers", |b| {
+        let mut offsets: HeaderOffsets = [0; MAX_HEADERS * 4];
+        b.iter(|| black_box(parse_request(black_box(request), &mut offsets)))
+    });
+}
+
+fn bench_jwt_verify(c: &mut Criterion) {
+    let jwt = Jwt::new(JwtConfig::new("bench-secret"));
+    let token = jwt.encode(
+        &Claims::new()
+            .sub("user-42")
+            .claim("role", "admin")
+            .exp_in(3600),
+    );
+
+    c.bench_function("jwt_verify", |b| {
+        b.iter(|| black_box(jwt.decode(black_box(&token))).unwrap())
+    });
+}
+
+fn bench_compress(c: &mut Criterion) {
+    let compress = Compress::new();
+    let body = "The quick brown fox jumps over the lazy dog. ".repeat(200);
+    let mut request = Request::new(Method::Get, "/data");
+    request
+        .headers
+        .push(("accept-encoding".to_string(), "gzip".to_string()));
+
+    c.bench_function("compress_gzip_9k", |b| {
+        b.iter(|| {
+            let mut response = Response::text(body.clone());
+            response
+                .headers
+                .push(("content-type".to_string(), "text/plain".to_string()));
+            compress.after(&request, &mut response);
+            black_box(response)
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_router_find,
+    bench_parse_request,
+    bench_jwt_verify,
+    bench_compress
+);
+criterion_main!(benches);
@@ -0,0 +1,293 @@
+//! gust-bench: end-to-end load testing with a comparable JSON report
+//!
+//! Drives `wrk` against either an external URL or a built-in gust
+//! static server, and condenses the run into a stable JSON document so
+//! CI can diff throughput and latency percentiles between PRs. The
+//! criterion microbenchmarks live under `benches/`.
+
+use gust_core::{tokio, Response, Server};
+use std::net::SocketAddr;
+use std::process::{Command, ExitCode};
+
+const USAGE: &str = "\
+gust-bench - end-to-end load test orchestration
+
+USAGE:
+    gust-bench loadtest [OPTIONS]
+
+OPTIONS:
+    --url <url>          Target URL (default: spawn a built-in server)
+    --duration <secs>    Test duration in seconds (default: 10)
+    --connections <n>    Open connections (default: 64)
+    --threads <n>        wrk threads (default: 4)
+    --wrk <path>         wrk binary to invoke (default: wrk)
+    --out <file>         Write the JSON report to a file (default: stdout)
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("loadtest") => match loadtest(&args[1..]) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("gust-bench: {}", message);
+                ExitCode::FAILURE
+            }
+        },
+        Some("help") | Some("--help") | Some("-h") => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        _ => {
+            eprint!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct LoadTestOptions {
+    url: Option<String>,
+    duration_secs: u32,
+    connections: u32,
+    threads: u32,
+    wrk: String,
+    out: Option<String>,
+}
+
+fn parse_options(args: &[String]) -> Result<LoadTestOptions, String> {
+    let mut options = LoadTestOptions {
+        url: None,
+        duration_secs: 10,
+        connections: 64,
+        threads: 4,
+        wrk: "wrk".to_string(),
+        out: None,
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .map(|s| s.to_string())
+                .ok_or_else(|| format!("{} expects a value", flag))
+        };
+        match arg.as_str() {
+            "--url" => options.url = Some(value("--url")?),
+            "--duration" => {
+                options.duration_secs = value("--duration")?
+                    .parse()
+                    .map_err(|_| "--duration expects a number of seconds".to_string())?;
+            }
+            "--connections" => {
+                options.connections = value("--connections")?
+                    .parse()
+                    .map_err(|_| "--connections expects a number".to_string())?;
+            }
+            "--threads" => {
+                options.threads = value("--threads")?
+                    .parse()
+                    .map_err(|_| "--threads expects a number".to_string())?;
+            }
+            "--wrk" => options.wrk = value("--wrk")?,
+            "--out" => options.out = Some(value("--out")?),
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+    Ok(options)
+}
+
+fn loadtest(args: &[String]) -> Result<(), String> {
+    let options = parse_options(args)?;
+
+    // Spawn the built-in target unless an external URL was given; the
+    // runtime stays alive for the duration of the run
+    let (url, _runtime) = match &options.url {
+        Some(url) => (url.clone(), None),
+        None => {
+            let (url, runtime) = spawn_builtin_server()?;
+            (url, Some(runtime))
+        }
+    };
+
+    let output = Command::new(&options.wrk)
+        .arg("--latency")
+        .arg("-d")
+        .arg(format!("{}s", options.duration_secs))
+        .arg("-c")
+        .arg(options.connections.to_string())
+        .arg("-t")
+        .arg(options.threads.to_string())
+        .arg(&url)
+        .output()
+        .map_err(|e| format!("failed to run '{}': {} (is wrk installed?)", options.wrk, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} exited with {}: {}",
+            options.wrk,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let summary = parse_wrk_output(&stdout)
+        .ok_or_else(|| format!("could not parse wrk output:\n{}", stdout))?;
+    let report = render_report(&url, &options, &summary);
+
+    match &options.out {
+        Some(path) => std::fs::write(path, report)
+            .map_err(|e| format!("failed to write {}: {}", path, e)),
+        None => {
+            println!("{}", report);
+            Ok(())
+        }
+    }
+}
+
+/// Boot a static JSON responder on an OS-assigned port
+fn spawn_builtin_server() -> Result<(String, tokio::runtime::Runtime), String> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("failed to start runtime: {}", e))?;
+
+    let server = Server::builder()
+        .get("/", |_req| async { Response::json(r#"{"hello":"world"}"#) })
+        .build();
+
+    // Bind on port 0 first to learn the port, then serve on it
+    let listener = runtime
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .map_err(|e| format!("failed to bind: {}", e))?;
+    let addr: SocketAddr = listener
+        .local_addr()
+        .map_err(|e| format!("failed to read local addr: {}", e))?;
+
+    runtime.spawn(async move {
+        let _ = server.serve_on(listener, std::future::pending::<()>()).await;
+    });
+
+    Ok((format!("http://{}/", addr), runtime))
+}
+
+struct WrkSummary {
+    requests_per_sec: f64,
+    latency_p50_ms: f64,
+    latency_p75_ms: f64,
+    latency_p90_ms: f64,
+    latency_p99_ms: f64,
+}
+
+/// Extract throughput and latency percentiles from `wrk --latency` output
+fn parse_wrk_output(stdout: &str) -> Option<WrkSummary> {
+    let mut requests_per_sec = None;
+    let mut percentiles = [None; 4];
+
+    for line in stdout.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("Requests/sec:") {
+            requests_per_sec = rest.trim().parse::<f64>().ok();
+        }
+        for (i, label) in ["50%", "75%", "90%", "99%"].iter().enumerate() {
+            if let Some(rest) = line.strip_prefix(label) {
+                percentiles[i] = parse_duration_ms(rest.trim());
+            }
+        }
+    }
+
+    Some(WrkSummary {
+        requests_per_sec: requests_per_sec?,
+        latency_p50_ms: percentiles[0]?,
+        latency_p75_ms: percentiles[1]?,
+        latency_p90_ms: percentiles[2]?,
+        latency_p99_ms: percentiles[3]?,
+    })
+}
+
+/// Parse wrk duration values (`634.00us`, `1.23ms`, `2.50s`) into ms
+fn parse_duration_ms(value: &str) -> Option<f64> {
+    let (number, scale) = if let Some(n) = value.strip_suffix("us") {
+        (n, 0.001)
+    } else if let Some(n) = value.strip_suffix("ms") {
+        (n, 1.0)
+    } else if let Some(n) = value.strip_suffix('m') {
+        (n, 60_000.0)
+    } else if let Some(n) = value.strip_suffix('s') {
+        (n, 1000.0)
+    } else {
+        return None;
+    };
+    number.trim().parse::<f64>().ok().map(|n| n * scale)
+}
+
+/// Render the report with a stable key order so runs diff cleanly
+fn render_report(url: &str, options: &LoadTestOptions, summary: &WrkSummary) -> String {
+    format!(
+        concat!(
+            "{{\"target\":{},\"durationSecs\":{},\"connections\":{},\"threads\":{},",
+            "\"requestsPerSec\":{:.2},\"latencyMs\":{{\"p50\":{:.3},\"p75\":{:.3},",
+            "\"p90\":{:.3},\"p99\":{:.3}}}}}"
+        ),
+        {
+            let mut quoted = String::new();
+            gust_core::pure::json::write_json_string(url, &mut quoted);
+            quoted
+        },
+        options.duration_secs,
+        options.connections,
+        options.threads,
+        summary.requests_per_sec,
+        summary.latency_p50_ms,
+        summary.latency_p75_ms,
+        summary.latency_p90_ms,
+        summary.latency_p99_ms,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WRK_OUTPUT: &str = "\
+Running 10s test @ http://127.0.0.1:3000/
+  4 threads and 64 connections
+  Thread Stats   Avg      Stdev     Max   +/- Stdev
+    Latency   634.00us    1.02ms  23.45ms   91.23%
+    Req/Sec    28.12k     3.45k   41.00k    70.12%
+  Latency Distribution
+     50%  512.00us
+     75%    0.89ms
+     90%    1.45ms
+     99%    5.67ms
+  1123456 requests in 10.02s, 158.44MB read
+Requests/sec: 112345.67
+Transfer/sec:     15.81MB
+";
+
+    #[test]
+    fn test_parse_duration_ms() {
+        assert_eq!(parse_duration_ms("500.00us"), Some(0.5));
+        assert_eq!(parse_duration_ms("1.50ms"), Some(1.5));
+        assert_eq!(parse_duration_ms("2s"), Some(2000.0));
+        assert_eq!(parse_duration_ms("nope"), None);
+    }
+
+    #[test]
+    fn test_parse_wrk_output() {
+        let summary = parse_wrk_output(WRK_OUTPUT).unwrap();
+        assert!((summary.requests_per_sec - 112_345.67).abs() < f64::EPSILON);
+        assert!((summary.latency_p50_ms - 0.512).abs() < 1e-9);
+        assert!((summary.latency_p99_ms - 5.67).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_render_report_is_stable_json() {
+        let options = parse_options(&[]).unwrap();
+        let summary = parse_wrk_output(WRK_OUTPUT).unwrap();
+        let report = render_report("http://127.0.0.1:3000/", &options, &summary);
+        assert!(report.starts_with(r#"{"target":"http://127.0.0.1:3000/""#));
+        assert!(report.contains(r#""requestsPerSec":112345.67"#));
+        assert!(report.contains(r#""p50":0.512"#));
+    }
+}
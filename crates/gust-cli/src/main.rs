@@ -0,0 +1,644 @@
+//! gust CLI: serve static sites and reverse-proxy to upstreams
+//!
+//! Thin command-line front end over the gust-core native server:
+//! `gust serve` wires StaticFiles (with optional SPA fallback,
+//! compression, and TLS) into a [`Server`], and `gust proxy` forwards
+//! every request to an upstream over HTTP/1.1.
+
+use gust_core::handlers::{StaticFileConfig, StaticFiles};
+use gust_core::middleware::compress::{
+    accepts_encoding, compress_data, decompress_data, Compress, CompressionLevel, Encoding,
+};
+use gust_core::{
+    bytes::Bytes, http_body_util, hyper, tokio, Method, Request, Response,
+    ResponseBuilder, Server, ServerBuilder, StatusCode,
+};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+const USAGE: &str = "\
+gust - high-performance HTTP server
+
+USAGE:
+    gust serve <dir> [OPTIONS]               Serve a static site from <dir>
+    gust proxy <listen> <upstream> [OPTIONS] Reverse-proxy <listen> to <upstream>
+    gust help                                Show this message
+
+SERVE OPTIONS:
+    --port <port>      Port to listen on (default: 3000)
+    --host <host>      Address to bind (default: 0.0.0.0)
+    --spa              Fall back to index.html for unknown paths
+    --compress         Compress responses (gzip/brotli, by Accept-Encoding)
+    --tls-cert <file>  PEM certificate chain (requires --tls-key)
+    --tls-key <file>   PEM private key (requires --tls-cert)
+    --tls-auto         HTTPS with a generated self-signed certificate
+    --plugin <file>    Load a GustPlugin cdylib (repeatable, unix only)
+
+PROXY OPTIONS:
+    --compress         Compress responses (gzip/brotli, by Accept-Encoding)
+
+<listen> is [host]:port (e.g. :8080 or 127.0.0.1:8080); <upstream> is an
+http:// URL.
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("gust: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("serve") => serve_command(&args[1..]),
+        Some("proxy") => proxy_command(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Some(other) => Err(format!(
+            "unknown command '{}' (try `gust help`)",
+            other
+        )),
+        None => Err("missing command (try `gust help`)".to_string()),
+    }
+}
+
+// ============================================================================
+// serve
+// ============================================================================
+
+enum TlsMode {
+    Off,
+    Files { cert: String, key: String },
+    Auto,
+}
+
+struct ServeOptions {
+    dir: String,
+    port: u16,
+    host: String,
+    spa: bool,
+    compress: bool,
+    tls: TlsMode,
+    plugins: Vec<String>,
+}
+
+fn parse_serve_options(args: &[String]) -> Result<ServeOptions, String> {
+    let mut dir = None;
+    let mut port = 3000u16;
+    let mut host = "0.0.0.0".to_string();
+    let mut spa = false;
+    let mut compress = false;
+    let mut tls_cert = None;
+    let mut tls_key = None;
+    let mut tls_auto = false;
+    let mut plugins = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--port" => {
+                port = required_value(&mut iter, "--port")?
+                    .parse()
+                    .map_err(|_| "--port expects a number between 1 and 65535".to_string())?;
+            }
+            "--host" => host = required_value(&mut iter, "--host")?,
+            "--spa" => spa = true,
+            "--compress" => compress = true,
+            "--tls-cert" => tls_cert = Some(required_value(&mut iter, "--tls-cert")?),
+            "--tls-key" => tls_key = Some(required_value(&mut iter, "--tls-key")?),
+            "--tls-auto" => tls_auto = true,
+            "--plugin" => plugins.push(required_value(&mut iter, "--plugin")?),
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option '{}' (try `gust help`)", flag));
+            }
+            value if dir.is_none() => dir = Some(value.to_string()),
+            value => return Err(format!("unexpected argument '{}'", value)),
+        }
+    }
+
+    let tls = match (tls_cert, tls_key, tls_auto) {
+        (None, None, false) => TlsMode::Off,
+        (None, None, true) => TlsMode::Auto,
+        (Some(cert), Some(key), false) => TlsMode::Files { cert, key },
+        (Some(_), Some(_), true) => {
+            return Err("--tls-auto conflicts with --tls-cert/--tls-key".to_string());
+        }
+        _ => return Err("--tls-cert and --tls-key must be given together".to_string()),
+    };
+
+    Ok(ServeOptions {
+        dir: dir.ok_or_else(|| "serve expects a directory (try `gust help`)".to_string())?,
+        port,
+        host,
+        spa,
+        compress,
+        tls,
+        plugins,
+    })
+}
+
+fn serve_command(args: &[String]) -> Result<(), String> {
+    let options = parse_serve_options(args)?;
+
+    if !std::path::Path::new(&options.dir).is_dir() {
+        return Err(format!("'{}' is not a directory", options.dir));
+    }
+
+    let mut config = StaticFileConfig::new(&options.dir).etag(true);
+    if options.spa {
+        config = config.fallback("index.html");
+    }
+    let files = Arc::new(StaticFiles::new(config));
+
+    let mut builder = Server::builder();
+    if options.compress {
+        builder = builder.middleware(Compress::new());
+    }
+    for path in &options.plugins {
+        let plugin = load_plugin(path)?;
+        println!("gust: loaded plugin '{}' from {}", plugin.name(), path);
+        builder = builder.plugin(plugin);
+    }
+    for method in [Method::Get, Method::Head] {
+        for path in ["/", "/*"] {
+            let files = Arc::clone(&files);
+            builder = builder.route(method, path, move |req: Request| {
+                let files = Arc::clone(&files);
+                async move { files.handle(&req).await }
+            });
+        }
+    }
+    let server = builder.build();
+
+    let addr = resolve_addr(&options.host, options.port)?;
+    let runtime = build_runtime()?;
+    match options.tls {
+        TlsMode::Off => {
+            println!("gust: serving {} on http://{}", options.dir, addr);
+            runtime.block_on(server.serve(addr))
+        }
+        TlsMode::Files { cert, key } => {
+            let tls = gust_core::TlsConfig::new(cert, key)
+                .build_server_config()
+                .map_err(|e| e.to_string())?;
+            println!("gust: serving {} on https://{}", options.dir, addr);
+            runtime.block_on(server.serve_tls(addr, tls))
+        }
+        TlsMode::Auto => {
+            let certified = rcgen::generate_simple_self_signed(vec![
+                options.host.clone(),
+                "localhost".to_string(),
+            ])
+            .map_err(|e| format!("failed to generate certificate: {}", e))?;
+            let tls = gust_core::server_config_from_der(
+                vec![certified.cert.der().to_vec()],
+                certified.signing_key.serialize_der(),
+            )
+            .map_err(|e| e.to_string())?;
+            println!(
+                "gust: serving {} on https://{} (self-signed certificate)",
+                options.dir, addr
+            );
+            runtime.block_on(server.serve_tls(addr, tls))
+        }
+    }
+    .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// plugins
+// ============================================================================
+
+/// Load a `GustPlugin` cdylib declared with `gust_core::declare_plugin!`
+///
+/// Resolves the two exported plugin symbols, checks the ABI version,
+/// and takes ownership of the constructed plugin. The library itself
+/// is never unloaded: its code backs the plugin's middleware and
+/// handlers for the life of the process.
+#[cfg(unix)]
+fn load_plugin(path: &str) -> Result<Box<dyn gust_core::GustPlugin>, String> {
+    use std::ffi::{CStr, CString};
+
+    let c_path =
+        CString::new(path).map_err(|_| format!("invalid plugin path '{}'", path))?;
+    let version_name = CString::new("gust_plugin_abi_version").expect("no NUL");
+    let create_name = CString::new("gust_plugin_create").expect("no NUL");
+
+    // SAFETY: dlopen/dlsym follow the plugin ABI documented in
+    // gust_core::plugin; the version check rejects plugins built
+    // against a different gust-core before any plugin code runs
+    unsafe {
+        let handle = libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+        if handle.is_null() {
+            let err = libc::dlerror();
+            let detail = if err.is_null() {
+                "unknown error".to_string()
+            } else {
+                CStr::from_ptr(err).to_string_lossy().into_owned()
+            };
+            return Err(format!("cannot load plugin '{}': {}", path, detail));
+        }
+
+        let version_sym = libc::dlsym(handle, version_name.as_ptr());
+        let create_sym = libc::dlsym(handle, create_name.as_ptr());
+        if version_sym.is_null() || create_sym.is_null() {
+            return Err(format!(
+                "'{}' is not a gust plugin (missing plugin symbols)",
+                path
+            ));
+        }
+
+        let abi_version: extern "C" fn() -> u32 = std::mem::transmute(version_sym);
+        let found = abi_version();
+        if found != gust_core::PLUGIN_ABI_VERSION {
+            return Err(format!(
+                "plugin '{}' targets ABI version {} but this gust speaks {}",
+                path,
+                found,
+                gust_core::PLUGIN_ABI_VERSION
+            ));
+        }
+
+        let create: extern "C" fn() -> *mut Box<dyn gust_core::GustPlugin> =
+            std::mem::transmute(create_sym);
+        Ok(*Box::from_raw(create()))
+    }
+}
+
+#[cfg(not(unix))]
+fn load_plugin(path: &str) -> Result<Box<dyn gust_core::GustPlugin>, String> {
+    let _ = path;
+    Err("--plugin is only supported on unix".to_string())
+}
+
+// ============================================================================
+// proxy
+// ============================================================================
+
+struct ProxyOptions {
+    listen: SocketAddr,
+    host: String,
+    port: u16,
+    compress: bool,
+}
+
+fn parse_proxy_options(args: &[String]) -> Result<ProxyOptions, String> {
+    let mut listen = None;
+    let mut upstream = None;
+    let mut compress = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--compress" => compress = true,
+            flag if flag.starts_with("--") => {
+                return Err(format!("unknown option '{}' (try `gust help`)", flag));
+            }
+            value if listen.is_none() => listen = Some(parse_listen(value)?),
+            value if upstream.is_none() => upstream = Some(parse_upstream(value)?),
+            value => return Err(format!("unexpected argument '{}'", value)),
+        }
+    }
+
+    let listen = listen.ok_or_else(|| "proxy expects a listen address".to_string())?;
+    let (host, port) =
+        upstream.ok_or_else(|| "proxy expects an upstream http:// URL".to_string())?;
+    Ok(ProxyOptions {
+        listen,
+        host,
+        port,
+        compress,
+    })
+}
+
+fn proxy_command(args: &[String]) -> Result<(), String> {
+    let options = parse_proxy_options(args)?;
+
+    let mut builder = Server::builder();
+    if options.compress {
+        builder = builder.middleware(Compress::new());
+    }
+    builder = proxy_routes(builder, options.host.clone(), options.port);
+    let server = builder.build();
+
+    let runtime = build_runtime()?;
+    println!(
+        "gust: proxying {} -> http://{}:{}",
+        options.listen, options.host, options.port
+    );
+    runtime
+        .block_on(server.serve(options.listen))
+        .map_err(|e| e.to_string())
+}
+
+fn proxy_routes(mut builder: ServerBuilder, host: String, port: u16) -> ServerBuilder {
+    const METHODS: [Method; 7] = [
+        Method::Get,
+        Method::Post,
+        Method::Put,
+        Method::Delete,
+        Method::Patch,
+        Method::Head,
+        Method::Options,
+    ];
+    for method in METHODS {
+        for path in ["/", "/*"] {
+            let host = host.clone();
+            builder = builder.route(method, path, move |req: Request| {
+                let host = host.clone();
+                async move { proxy_request(&host, port, req).await }
+            });
+        }
+    }
+    builder
+}
+
+async fn proxy_request(host: &str, port: u16, req: Request) -> Response {
+    match forward(host, port, req).await {
+        Ok(response) => response,
+        Err(err) => ResponseBuilder::new(StatusCode::BAD_GATEWAY)
+            .body(format!("upstream error: {}", err))
+            .build(),
+    }
+}
+
+/// Hop-by-hop headers never forwarded in either direction
+fn is_hop_by_hop(name: &str) -> bool {
+    name.eq_ignore_ascii_case("connection")
+        || name.eq_ignore_ascii_case("keep-alive")
+        || name.eq_ignore_ascii_case("transfer-encoding")
+        || name.eq_ignore_ascii_case("upgrade")
+        || name.eq_ignore_ascii_case("proxy-connection")
+}
+
+async fn forward(host: &str, port: u16, req: Request) -> Result<Response, String> {
+    let accept_encoding = req.header("accept-encoding").unwrap_or("").to_string();
+
+    let uri = match &req.query {
+        Some(query) => format!("{}?{}", req.path, query),
+        None => req.path.clone(),
+    };
+    let mut request = hyper::Request::builder().method(req.method.as_str()).uri(uri);
+    for (name, value) in &req.headers {
+        if !is_hop_by_hop(name) && !name.eq_ignore_ascii_case("host") {
+            request = request.header(name.as_str(), value.as_str());
+        }
+    }
+    request = request.header("host", format!("{}:{}", host, port));
+    let request = request
+        .body(http_body_util::Full::new(req.body.clone()))
+        .map_err(|e| e.to_string())?;
+
+    let key = format!("http://{}:{}", host, port);
+    let dial = || async {
+        let stream = gust_core::default_resolver().connect(host, port).await?;
+        gust_core::upstream::handshake(stream).await
+    };
+    let (status, headers, body) = gust_core::upstream::default_pool()
+        .request(&key, dial, request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut response = Response::new(StatusCode(status));
+    for (name, value) in headers {
+        if !is_hop_by_hop(&name) {
+            response.headers.push((name, value));
+        }
+    }
+    response.body = body;
+    adapt_content_encoding(&accept_encoding, &mut response);
+    Ok(response)
+}
+
+/// Re-encode or decompress an upstream body the client cannot accept
+///
+/// The client's Accept-Encoding is honored on both legs: it is
+/// forwarded to the upstream untouched, and when the upstream replies
+/// with an encoding the client did not offer the body is decompressed
+/// and re-encoded to the client's preference (or identity). Responses
+/// the client can accept pass through unchanged.
+fn adapt_content_encoding(accept: &str, response: &mut Response) {
+    let current = response
+        .headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-encoding"))
+        .and_then(|(_, v)| Encoding::from_token(v));
+    let Some(current) = current else {
+        return; // No encoding, or one we cannot transcode
+    };
+    if current == Encoding::Identity || accepts_encoding(accept, current) {
+        return;
+    }
+    let Some(decoded) = decompress_data(current, &response.body) else {
+        return; // Body does not match its declared encoding; pass through
+    };
+
+    let target = Encoding::from_accept_encoding(accept);
+    response
+        .headers
+        .retain(|(k, _)| !k.eq_ignore_ascii_case("content-encoding"));
+    if target == Encoding::Identity {
+        response.body = Bytes::from(decoded);
+    } else {
+        response.body = Bytes::from(compress_data(target, CompressionLevel::Default, &decoded));
+        response
+            .headers
+            .push(("Content-Encoding".to_string(), target.as_str().to_string()));
+    }
+    response
+        .headers
+        .retain(|(k, _)| !k.eq_ignore_ascii_case("content-length"));
+    response
+        .headers
+        .push(("Content-Length".to_string(), response.body.len().to_string()));
+}
+
+// ============================================================================
+// Shared helpers
+// ============================================================================
+
+fn build_runtime() -> Result<tokio::runtime::Runtime, String> {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("failed to start runtime: {}", e))
+}
+
+fn required_value(iter: &mut std::slice::Iter<'_, String>, flag: &str) -> Result<String, String> {
+    iter.next()
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("{} expects a value", flag))
+}
+
+fn resolve_addr(host: &str, port: u16) -> Result<SocketAddr, String> {
+    (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("could not resolve {}:{}: {}", host, port, e))?
+        .next()
+        .ok_or_else(|| format!("could not resolve {}:{}", host, port))
+}
+
+/// Parse `[host]:port` (`:8080` binds all interfaces)
+fn parse_listen(spec: &str) -> Result<SocketAddr, String> {
+    let (host, port) = spec
+        .rsplit_once(':')
+        .ok_or_else(|| format!("listen address '{}' must be [host]:port", spec))?;
+    let port: u16 = port
+        .parse()
+        .map_err(|_| format!("invalid port in listen address '{}'", spec))?;
+    let host = if host.is_empty() { "0.0.0.0" } else { host };
+    resolve_addr(host, port)
+}
+
+/// Parse `http://host[:port]` into host and port (path must be empty)
+fn parse_upstream(url: &str) -> Result<(String, u16), String> {
+    if url.starts_with("https://") {
+        return Err("https upstreams are not supported; use http://".to_string());
+    }
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("upstream '{}' must be an http:// URL", url))?;
+    let rest = rest.strip_suffix('/').unwrap_or(rest);
+    if rest.contains('/') {
+        return Err(format!("upstream '{}' must not include a path", url));
+    }
+    match rest.rsplit_once(':') {
+        Some((host, port)) => {
+            let port: u16 = port
+                .parse()
+                .map_err(|_| format!("invalid port in upstream '{}'", url))?;
+            Ok((host.to_string(), port))
+        }
+        None => Ok((rest.to_string(), 80)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_listen() {
+        assert_eq!(
+            parse_listen(":8080").unwrap(),
+            "0.0.0.0:8080".parse().unwrap()
+        );
+        assert_eq!(
+            parse_listen("127.0.0.1:9000").unwrap(),
+            "127.0.0.1:9000".parse().unwrap()
+        );
+        assert!(parse_listen("8080").is_err());
+    }
+
+    #[test]
+    fn test_parse_upstream() {
+        assert_eq!(
+            parse_upstream("http://localhost:3000").unwrap(),
+            ("localhost".to_string(), 3000)
+        );
+        assert_eq!(
+            parse_upstream("http://backend").unwrap(),
+            ("backend".to_string(), 80)
+        );
+        assert!(parse_upstream("https://secure").is_err());
+        assert!(parse_upstream("http://host/path").is_err());
+    }
+
+    #[test]
+    fn test_adapt_content_encoding() {
+        let plain = b"hello hello hello hello".repeat(20);
+        let gzipped = compress_data(Encoding::Gzip, CompressionLevel::Default, &plain);
+        let make_response = || {
+            let mut response = Response::new(StatusCode::OK);
+            response
+                .headers
+                .push(("Content-Encoding".to_string(), "gzip".to_string()));
+            response.body = Bytes::from(gzipped.clone());
+            response
+        };
+
+        // Client accepts gzip: untouched
+        let mut response = make_response();
+        adapt_content_encoding("gzip, br", &mut response);
+        assert_eq!(response.body, Bytes::from(gzipped.clone()));
+
+        // Client accepts nothing: decompressed to identity
+        let mut response = make_response();
+        adapt_content_encoding("", &mut response);
+        assert_eq!(response.body, Bytes::from(plain.clone()));
+        assert!(!response
+            .headers
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case("content-encoding")));
+
+        // Client accepts only brotli: re-encoded
+        let mut response = make_response();
+        adapt_content_encoding("br", &mut response);
+        assert!(response
+            .headers
+            .iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case("content-encoding") && v == "br"));
+        assert_eq!(
+            gust_core::middleware::compress::decompress_data(Encoding::Brotli, &response.body)
+                .unwrap(),
+            plain
+        );
+    }
+
+    #[test]
+    fn test_parse_serve_options() {
+        let options = parse_serve_options(&args(&[
+            "./dist",
+            "--spa",
+            "--compress",
+            "--port",
+            "8080",
+        ]))
+        .unwrap();
+        assert_eq!(options.dir, "./dist");
+        assert!(options.spa && options.compress);
+        assert_eq!(options.port, 8080);
+        assert!(matches!(options.tls, TlsMode::Off));
+
+        let options = parse_serve_options(&args(&["./dist", "--tls-auto"])).unwrap();
+        assert!(matches!(options.tls, TlsMode::Auto));
+
+        assert!(parse_serve_options(&args(&["./dist", "--tls-cert", "c.pem"])).is_err());
+        assert!(parse_serve_options(&args(&["--port", "70000", "./dist"])).is_err());
+
+        let options = parse_serve_options(&args(&[
+            "./dist",
+            "--plugin",
+            "a.so",
+            "--plugin",
+            "b.so",
+        ]))
+        .unwrap();
+        assert_eq!(options.plugins, vec!["a.so", "b.so"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_plugin_rejects_non_plugins() {
+        // Missing file
+        let err = match load_plugin("/nonexistent/libplugin.so") {
+            Err(err) => err,
+            Ok(_) => panic!("expected load failure"),
+        };
+        assert!(err.contains("cannot load plugin"));
+
+        // A loadable shared object without the plugin symbols
+        let lib = std::env::current_exe().unwrap();
+        assert!(load_plugin(lib.to_str().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! Consistent-hash (ring) load balancing
+//!
+//! Routes requests to upstream members by hashing an affinity key —
+//! a header, cookie, or the client IP — onto a ring of virtual nodes,
+//! so the same session or cache key keeps landing on the same member
+//! while membership changes only remap a small slice of keys.
+//! Bounded-load rebalancing (consistent hashing with bounded loads)
+//! spills a key to the next ring position when its member is already
+//! carrying more than its fair share of in-flight requests.
+
+use crate::middleware::cookie::CookieJar;
+use crate::pure::client_ip::parse_client_ip;
+use crate::Request;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+/// Where the affinity key for a request comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AffinityKey {
+    /// A request header value (e.g. `x-session-id`)
+    Header(String),
+    /// A cookie value (e.g. a session cookie)
+    Cookie(String),
+    /// The client IP (first X-Forwarded-For hop, then X-Real-IP, then
+    /// the socket address)
+    ClientIp,
+}
+
+impl AffinityKey {
+    /// Extract the key from a request; `remote_addr` is the socket
+    /// peer used as the final client-IP fallback
+    pub fn extract(&self, req: &Request, remote_addr: Option<&str>) -> Option<String> {
+        match self {
+            AffinityKey::Header(name) => req.header(name).map(str::to_string),
+            AffinityKey::Cookie(name) => req
+                .header("cookie")
+                .and_then(|header| CookieJar::parse(header).get_value(name).map(str::to_string)),
+            AffinityKey::ClientIp => Some(parse_client_ip(
+                req.header("x-forwarded-for"),
+                req.header("x-real-ip"),
+                remote_addr,
+            )),
+        }
+    }
+}
+
+/// FNV-1a, stable across processes so ring placement survives restarts
+fn hash_key(key: &str) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for byte in key.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+struct Member {
+    addr: SocketAddr,
+    /// In-flight requests currently assigned to this member
+    active: u64,
+}
+
+struct RingState {
+    members: Vec<Member>,
+    /// (hash, member index), sorted by hash
+    ring: Vec<(u64, usize)>,
+}
+
+struct Inner {
+    state: Mutex<RingState>,
+    replicas: usize,
+    load_factor: f64,
+}
+
+/// Consistent-hash balancer over a set of upstream members
+///
+/// Cloning is cheap and shares the ring and load counters.
+pub struct HashRing {
+    inner: Arc<Inner>,
+}
+
+impl Clone for HashRing {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl HashRing {
+    /// `replicas` virtual nodes per member smooth the distribution
+    /// (100 is a good default); `load_factor` bounds how far above the
+    /// mean in-flight load a member may sit before keys spill to the
+    /// next ring position (1.25 per the bounded-loads paper, values
+    /// below 1 are clamped)
+    pub fn new(replicas: usize, load_factor: f64) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                state: Mutex::new(RingState {
+                    members: Vec::new(),
+                    ring: Vec::new(),
+                }),
+                replicas: replicas.max(1),
+                load_factor: load_factor.max(1.0),
+            }),
+        }
+    }
+
+    /// Replace the member set, rebuilding the ring
+    ///
+    /// Load counters of members that remain in the set are preserved.
+    pub fn set_members(&self, addrs: Vec<SocketAddr>) {
+        let Ok(mut state) = self.inner.state.lock() else {
+            return;
+        };
+        let members: Vec<Member> = addrs
+            .into_iter()
+            .map(|addr| Member {
+                addr,
+                active: state
+                    .members
+                    .iter()
+                    .find(|m| m.addr == addr)
+                    .map(|m| m.active)
+                    .unwrap_or(0),
+            })
+            .collect();
+
+        let mut ring = Vec::with_capacity(members.len() * self.inner.replicas);
+        for (index, member) in members.iter().enumerate() {
+            for replica in 0..self.inner.replicas {
+                ring.push((hash_key(&format!("{}#{}", member.addr, replica)), index));
+            }
+        }
+        ring.sort_unstable();
+
+        state.members = members;
+        state.ring = ring;
+    }
+
+    /// Current members, in insertion order
+    pub fn members(&self) -> Vec<SocketAddr> {
+        self.inner
+            .state
+            .lock()
+            .map(|state| state.members.iter().map(|m| m.addr).collect())
+            .unwrap_or_default()
+    }
+
+    /// Pick the member for a key, walking past overloaded members
+    ///
+    /// The returned guard counts as in-flight load on the member until
+    /// dropped; `None` while the ring is empty.
+    pub fn pick(&self, key: &str) -> Option<RingCheckout> {
+        let mut state = self.inner.state.lock().ok()?;
+        if state.ring.is_empty() {
+            return None;
+        }
+
+        // Bounded load: ceil(load_factor * (total + 1) / members)
+        let total: u64 = state.members.iter().map(|m| m.active).sum();
+        let limit = (self.inner.load_factor * (total + 1) as f64
+            / state.members.len() as f64)
+            .ceil() as u64;
+
+        let hash = hash_key(key);
+        let start = state
+            .ring
+            .partition_point(|(node, _)| *node < hash);
+        let mut chosen = None;
+        for step in 0..state.ring.len() {
+            let (_, index) = state.ring[(start + step) % state.ring.len()];
+            if state.members[index].active < limit {
+                chosen = Some(index);
+                break;
+            }
+        }
+        // Every member at the bound can only happen transiently; fall
+        // back to the natural ring position
+        let index = chosen.unwrap_or(state.ring[start % state.ring.len()].1);
+
+        state.members[index].active += 1;
+        Some(RingCheckout {
+            addr: state.members[index].addr,
+            ring: self.clone(),
+        })
+    }
+}
+
+impl Default for HashRing {
+    fn default() -> Self {
+        Self::new(100, 1.25)
+    }
+}
+
+/// Guard holding one unit of in-flight load on a member
+pub struct RingCheckout {
+    addr: SocketAddr,
+    ring: HashRing,
+}
+
+impl RingCheckout {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for RingCheckout {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.ring.inner.state.lock() {
+            if let Some(member) = state.members.iter_mut().find(|m| m.addr == self.addr) {
+                member.active = member.active.saturating_sub(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, RequestBuilder};
+
+    fn addrs(n: usize) -> Vec<SocketAddr> {
+        (0..n)
+            .map(|i| format!("10.0.0.{}:8080", i + 1).parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_same_key_maps_to_same_member() {
+        let ring = HashRing::default();
+        ring.set_members(addrs(4));
+
+        let first = ring.pick("session-abc").unwrap().addr();
+        for _ in 0..10 {
+            assert_eq!(ring.pick("session-abc").unwrap().addr(), first);
+        }
+    }
+
+    #[test]
+    fn test_membership_change_remaps_few_keys() {
+        let ring = HashRing::default();
+        ring.set_members(addrs(5));
+
+        let keys: Vec<String> = (0..200).map(|i| format!("key-{}", i)).collect();
+        let before: Vec<SocketAddr> =
+            keys.iter().map(|k| ring.pick(k).unwrap().addr()).collect();
+
+        // Drop one member; most keys must keep their assignment
+        ring.set_members(addrs(4));
+        let moved = keys
+            .iter()
+            .zip(&before)
+            .filter(|(k, prev)| ring.pick(k).unwrap().addr() != **prev)
+            .count();
+        assert!(moved < 100, "{} of 200 keys moved", moved);
+    }
+
+    #[test]
+    fn test_bounded_load_spills_hot_key() {
+        let ring = HashRing::new(100, 1.25);
+        ring.set_members(addrs(3));
+
+        // Hold many checkouts for one hot key; the bound forces later
+        // checkouts onto other members
+        let checkouts: Vec<RingCheckout> =
+            (0..30).map(|_| ring.pick("hot-key").unwrap()).collect();
+        let distinct: std::collections::HashSet<SocketAddr> =
+            checkouts.iter().map(|c| c.addr()).collect();
+        assert!(distinct.len() > 1);
+
+        // Releasing the load restores plain consistent hashing
+        drop(checkouts);
+        let a = ring.pick("hot-key").unwrap().addr();
+        let b = ring.pick("other").unwrap();
+        drop(b);
+        assert_eq!(ring.pick("hot-key").unwrap().addr(), a);
+    }
+
+    #[test]
+    fn test_affinity_key_extraction() {
+        let req = RequestBuilder::new(Method::Get, "/")
+            .header("x-session-id", "abc123")
+            .header("cookie", "theme=dark; sid=s-42")
+            .header("x-forwarded-for", "203.0.113.9, 10.0.0.1")
+            .build();
+
+        assert_eq!(
+            AffinityKey::Header("x-session-id".to_string()).extract(&req, None),
+            Some("abc123".to_string())
+        );
+        assert_eq!(
+            AffinityKey::Cookie("sid".to_string()).extract(&req, None),
+            Some("s-42".to_string())
+        );
+        assert_eq!(
+            AffinityKey::ClientIp.extract(&req, Some("10.9.9.9")),
+            Some("203.0.113.9".to_string())
+        );
+        assert_eq!(
+            AffinityKey::Cookie("missing".to_string()).extract(&req, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_empty_ring_and_member_order() {
+        let ring = HashRing::default();
+        assert!(ring.pick("any").is_none());
+        ring.set_members(addrs(2));
+        assert_eq!(ring.members(), addrs(2));
+    }
+}
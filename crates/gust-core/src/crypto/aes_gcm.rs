@@ -0,0 +1,284 @@
+//! AES-256-GCM authenticated encryption
+//!
+//! Minimal implementation without external dependencies: AES-256 in
+//! counter mode with GHASH authentication per NIST SP 800-38D. Used
+//! for JWE content encryption.
+//!
+//! GHASH and the GF(2^8) arithmetic are branch-free and tags are
+//! compared in constant time, but the AES rounds use table-indexed
+//! S-box lookups, which can leak key material through cache timing to
+//! a co-resident attacker. See the note on the `Jwe` middleware.
+
+use super::constant_time_eq;
+
+/// AES S-box
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+/// Round constants for key expansion
+const RCON: [u8; 15] = [
+    0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36, 0x6c, 0xd8, 0xab, 0x4d, 0x9a,
+];
+
+/// Expanded AES-256 key schedule (15 round keys)
+struct Aes256 {
+    round_keys: [[u8; 16]; 15],
+}
+
+impl Aes256 {
+    fn new(key: &[u8; 32]) -> Self {
+        // Nk = 8 words, Nr = 14 rounds, 60 words total
+        let mut w = [[0u8; 4]; 60];
+        for (i, word) in w.iter_mut().take(8).enumerate() {
+            word.copy_from_slice(&key[i * 4..i * 4 + 4]);
+        }
+        for i in 8..60 {
+            let mut temp = w[i - 1];
+            if i % 8 == 0 {
+                temp.rotate_left(1);
+                for byte in &mut temp {
+                    *byte = SBOX[*byte as usize];
+                }
+                temp[0] ^= RCON[i / 8 - 1];
+            } else if i % 8 == 4 {
+                for byte in &mut temp {
+                    *byte = SBOX[*byte as usize];
+                }
+            }
+            for j in 0..4 {
+                w[i][j] = w[i - 8][j] ^ temp[j];
+            }
+        }
+
+        let mut round_keys = [[0u8; 16]; 15];
+        for (round, rk) in round_keys.iter_mut().enumerate() {
+            for j in 0..4 {
+                rk[j * 4..j * 4 + 4].copy_from_slice(&w[round * 4 + j]);
+            }
+        }
+        Self { round_keys }
+    }
+
+    /// Encrypt a single 16-byte block
+    fn encrypt_block(&self, block: &[u8; 16]) -> [u8; 16] {
+        let mut state = *block;
+        add_round_key(&mut state, &self.round_keys[0]);
+        for round in 1..14 {
+            sub_bytes(&mut state);
+            shift_rows(&mut state);
+            mix_columns(&mut state);
+            add_round_key(&mut state, &self.round_keys[round]);
+        }
+        sub_bytes(&mut state);
+        shift_rows(&mut state);
+        add_round_key(&mut state, &self.round_keys[14]);
+        state
+    }
+}
+
+fn add_round_key(state: &mut [u8; 16], round_key: &[u8; 16]) {
+    for (s, k) in state.iter_mut().zip(round_key) {
+        *s ^= k;
+    }
+}
+
+fn sub_bytes(state: &mut [u8; 16]) {
+    for byte in state.iter_mut() {
+        *byte = SBOX[*byte as usize];
+    }
+}
+
+fn shift_rows(state: &mut [u8; 16]) {
+    // State is column-major: row r, column c lives at 4c + r
+    let copy = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[4 * c + r] = copy[4 * ((c + r) % 4) + r];
+        }
+    }
+}
+
+/// Multiply by x in GF(2^8), branch-free
+fn xtime(b: u8) -> u8 {
+    (b << 1) ^ (((b >> 7) & 1) * 0x1b)
+}
+
+fn mix_columns(state: &mut [u8; 16]) {
+    for c in 0..4 {
+        let col = [state[4 * c], state[4 * c + 1], state[4 * c + 2], state[4 * c + 3]];
+        let all = col[0] ^ col[1] ^ col[2] ^ col[3];
+        for r in 0..4 {
+            state[4 * c + r] = col[r] ^ all ^ xtime(col[r] ^ col[(r + 1) % 4]);
+        }
+    }
+}
+
+/// Multiply two 128-bit values in GF(2^128) per SP 800-38D
+///
+/// Branch-free: bits select through arithmetic masks so the hash-key
+/// operand does not leak through timing.
+fn ghash_mul(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xE1 << 120;
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        let x_mask = ((x >> (127 - i)) & 1).wrapping_neg();
+        z ^= v & x_mask;
+        let r_mask = (v & 1).wrapping_neg();
+        v = (v >> 1) ^ (R & r_mask);
+    }
+    z
+}
+
+/// GHASH over AAD and ciphertext with length block
+fn ghash(h: u128, aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+    let mut y = 0u128;
+    let mut absorb = |data: &[u8]| {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            y = ghash_mul(y ^ u128::from_be_bytes(block), h);
+        }
+    };
+    absorb(aad);
+    absorb(ciphertext);
+    let lengths = ((aad.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+    y = ghash_mul(y ^ lengths, h);
+    y.to_be_bytes()
+}
+
+/// CTR-mode keystream application starting at counter block 2
+fn ctr_apply(cipher: &Aes256, nonce: &[u8; 12], data: &mut [u8]) {
+    let mut counter_block = [0u8; 16];
+    counter_block[..12].copy_from_slice(nonce);
+    let mut counter: u32 = 2;
+    for chunk in data.chunks_mut(16) {
+        counter_block[12..].copy_from_slice(&counter.to_be_bytes());
+        let keystream = cipher.encrypt_block(&counter_block);
+        for (byte, k) in chunk.iter_mut().zip(keystream.iter()) {
+            *byte ^= k;
+        }
+        counter = counter.wrapping_add(1);
+    }
+}
+
+/// Tag computation: GHASH masked with the J0 counter block
+fn compute_tag(cipher: &Aes256, nonce: &[u8; 12], aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+    let h = u128::from_be_bytes(cipher.encrypt_block(&[0u8; 16]));
+    let mut j0 = [0u8; 16];
+    j0[..12].copy_from_slice(nonce);
+    j0[15] = 1;
+    let mask = cipher.encrypt_block(&j0);
+    let mut tag = ghash(h, aad, ciphertext);
+    for (t, m) in tag.iter_mut().zip(mask.iter()) {
+        *t ^= m;
+    }
+    tag
+}
+
+/// Encrypt with AES-256-GCM (96-bit nonce), returning ciphertext and tag
+pub fn aes256_gcm_encrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> (Vec<u8>, [u8; 16]) {
+    let cipher = Aes256::new(key);
+    let mut ciphertext = plaintext.to_vec();
+    ctr_apply(&cipher, nonce, &mut ciphertext);
+    let tag = compute_tag(&cipher, nonce, aad, &ciphertext);
+    (ciphertext, tag)
+}
+
+/// Decrypt with AES-256-GCM; None when the tag does not authenticate
+pub fn aes256_gcm_decrypt(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    aad: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8; 16],
+) -> Option<Vec<u8>> {
+    let cipher = Aes256::new(key);
+    let expected = compute_tag(&cipher, nonce, aad, ciphertext);
+    if !constant_time_eq(&expected, tag) {
+        return None;
+    }
+    let mut plaintext = ciphertext.to_vec();
+    ctr_apply(&cipher, nonce, &mut plaintext);
+    Some(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // GCM spec test case 13: zero key, zero nonce, empty plaintext
+    #[test]
+    fn test_gcm_empty() {
+        let (ct, tag) = aes256_gcm_encrypt(&[0; 32], &[0; 12], b"", b"");
+        assert!(ct.is_empty());
+        assert_eq!(tag.to_vec(), hex("530f8afbc74536b9a963b4f1c4cb738b"));
+    }
+
+    // GCM spec test case 14: zero key, zero nonce, one zero block
+    #[test]
+    fn test_gcm_single_block() {
+        let (ct, tag) = aes256_gcm_encrypt(&[0; 32], &[0; 12], b"", &[0; 16]);
+        assert_eq!(ct, hex("cea7403d4d606b6e074ec5d3baf39d18"));
+        assert_eq!(tag.to_vec(), hex("d0d1c8a799996bf0265b98b5d48ab919"));
+    }
+
+    // GCM spec test case 16: AAD and a 60-byte plaintext
+    #[test]
+    fn test_gcm_with_aad() {
+        let key: [u8; 32] = hex(
+            "feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308",
+        )
+        .try_into()
+        .unwrap();
+        let nonce: [u8; 12] = hex("cafebabefacedbaddecaf888").try_into().unwrap();
+        let plaintext = hex(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        );
+        let aad = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+
+        let (ct, tag) = aes256_gcm_encrypt(&key, &nonce, &aad, &plaintext);
+        assert_eq!(
+            ct,
+            hex(
+                "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa\
+                 8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662",
+            )
+        );
+        assert_eq!(tag.to_vec(), hex("76fc6ece0f4e1768cddf8853bb2d551b"));
+
+        // Round-trip and tamper detection
+        let decrypted = aes256_gcm_decrypt(&key, &nonce, &aad, &ct, &tag).unwrap();
+        assert_eq!(decrypted, plaintext);
+        assert!(aes256_gcm_decrypt(&key, &nonce, b"wrong aad", &ct, &tag).is_none());
+    }
+}
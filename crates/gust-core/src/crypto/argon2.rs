@@ -0,0 +1,402 @@
+//! Argon2id password hashing (RFC 9106, version 0x13)
+//!
+//! Minimal single-threaded implementation without external
+//! dependencies. The parallelism parameter shapes the memory layout
+//! exactly as specified (so hashes interoperate), but lanes are
+//! filled sequentially.
+
+use super::blake2b::blake2b;
+
+/// Cost parameters for [`argon2id`]
+///
+/// Defaults follow the OWASP recommendation: 19 MiB, 2 passes, 1
+/// lane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes over memory
+    pub iterations: u32,
+    /// Number of lanes
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+impl Argon2Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the memory cost in KiB (minimum 8 per lane)
+    pub fn memory_kib(mut self, kib: u32) -> Self {
+        self.memory_kib = kib;
+        self
+    }
+
+    /// Set the number of passes over memory
+    pub fn iterations(mut self, passes: u32) -> Self {
+        self.iterations = passes.max(1);
+        self
+    }
+
+    /// Set the number of lanes
+    pub fn parallelism(mut self, lanes: u32) -> Self {
+        self.parallelism = lanes.max(1);
+        self
+    }
+}
+
+/// One 1024-byte memory block as 128 little-endian words
+type Block = [u64; 128];
+
+const ZERO_BLOCK: Block = [0u64; 128];
+const VERSION: u32 = 0x13;
+const ARGON2ID: u32 = 2;
+const SYNC_POINTS: usize = 4;
+
+/// Derive `out_len` bytes from a password and salt with Argon2id
+pub fn argon2id(password: &[u8], salt: &[u8], params: Argon2Params, out_len: usize) -> Vec<u8> {
+    let lanes = params.parallelism.max(1) as usize;
+    // At least 8 blocks total, rounded down to a multiple of 4*lanes
+    let memory_blocks = (params.memory_kib as usize).max(8 * lanes) / (4 * lanes) * (4 * lanes);
+    let segment_length = memory_blocks / (lanes * SYNC_POINTS);
+    let lane_length = segment_length * SYNC_POINTS;
+    let passes = params.iterations.max(1) as usize;
+
+    // H0 commits to every parameter (RFC 9106 §3.2); secret and
+    // associated data are unused here, encoded as zero-length
+    let mut h0_input = Vec::with_capacity(password.len() + salt.len() + 40);
+    for value in [
+        params.parallelism.max(1),
+        out_len as u32,
+        memory_blocks as u32,
+        passes as u32,
+        VERSION,
+        ARGON2ID,
+        password.len() as u32,
+    ] {
+        h0_input.extend_from_slice(&value.to_le_bytes());
+    }
+    h0_input.extend_from_slice(password);
+    h0_input.extend_from_slice(&(salt.len() as u32).to_le_bytes());
+    h0_input.extend_from_slice(salt);
+    h0_input.extend_from_slice(&0u32.to_le_bytes()); // secret
+    h0_input.extend_from_slice(&0u32.to_le_bytes()); // associated data
+    let h0 = blake2b(64, &h0_input);
+
+    // First two blocks of each lane come straight from H0
+    let mut memory = vec![ZERO_BLOCK; memory_blocks];
+    for lane in 0..lanes {
+        for i in 0..2 {
+            let mut input = Vec::with_capacity(72);
+            input.extend_from_slice(&h0);
+            input.extend_from_slice(&(i as u32).to_le_bytes());
+            input.extend_from_slice(&(lane as u32).to_le_bytes());
+            memory[lane * lane_length + i] = bytes_to_block(&h_prime(1024, &input));
+        }
+    }
+
+    for pass in 0..passes {
+        for slice in 0..SYNC_POINTS {
+            for lane in 0..lanes {
+                fill_segment(
+                    &mut memory,
+                    pass,
+                    slice,
+                    lane,
+                    lanes,
+                    segment_length,
+                    lane_length,
+                    passes,
+                );
+            }
+        }
+    }
+
+    // XOR the last block of every lane and hash down to the tag
+    let mut last = memory[lane_length - 1];
+    for lane in 1..lanes {
+        let block = &memory[lane * lane_length + lane_length - 1];
+        for (a, b) in last.iter_mut().zip(block.iter()) {
+            *a ^= b;
+        }
+    }
+    h_prime(out_len, &block_to_bytes(&last))
+}
+
+/// Variable-length hash H' (RFC 9106 §3.3)
+fn h_prime(out_len: usize, input: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(input.len() + 4);
+    message.extend_from_slice(&(out_len as u32).to_le_bytes());
+    message.extend_from_slice(input);
+
+    if out_len <= 64 {
+        return blake2b(out_len, &message);
+    }
+
+    // Chain 64-byte digests, keeping the first 32 bytes of each, and
+    // finish with one digest sized to the remainder
+    let r = out_len.div_ceil(32) - 2;
+    let mut output = Vec::with_capacity(out_len);
+    let mut v = blake2b(64, &message);
+    for _ in 1..r {
+        output.extend_from_slice(&v[..32]);
+        v = blake2b(64, &v);
+    }
+    output.extend_from_slice(&v[..32]);
+    output.extend_from_slice(&blake2b(out_len - 32 * r, &v));
+    output
+}
+
+#[allow(clippy::too_many_arguments)]
+fn fill_segment(
+    memory: &mut [Block],
+    pass: usize,
+    slice: usize,
+    lane: usize,
+    lanes: usize,
+    segment_length: usize,
+    lane_length: usize,
+    _passes: usize,
+) {
+    // Argon2id: data-independent addressing for the first half of the
+    // first pass, data-dependent afterwards
+    let independent = pass == 0 && slice < 2;
+    let mut address_block = ZERO_BLOCK;
+    let mut input_block = ZERO_BLOCK;
+    if independent {
+        input_block[0] = pass as u64;
+        input_block[1] = lane as u64;
+        input_block[2] = slice as u64;
+        input_block[3] = memory.len() as u64;
+        input_block[4] = _passes as u64;
+        input_block[5] = ARGON2ID as u64;
+    }
+
+    // The first two blocks of the first segment are pre-filled from
+    // H0; their address block still has to be generated up front
+    let start = if pass == 0 && slice == 0 { 2 } else { 0 };
+    if independent && start == 2 {
+        input_block[6] += 1;
+        address_block = g_block(&ZERO_BLOCK, &g_block(&ZERO_BLOCK, &input_block));
+    }
+
+    for index in start..segment_length {
+        let cur = lane * lane_length + slice * segment_length + index;
+        let prev = lane * lane_length + (cur % lane_length + lane_length - 1) % lane_length;
+
+        let (j1, j2) = if independent {
+            // A fresh address block every 128 references
+            if index % 128 == 0 {
+                input_block[6] += 1;
+                address_block = g_block(&ZERO_BLOCK, &g_block(&ZERO_BLOCK, &input_block));
+            }
+            let word = address_block[index % 128];
+            (word as u32 as u64, (word >> 32) as u32 as u64)
+        } else {
+            let word = memory[prev][0];
+            (word as u32 as u64, (word >> 32) as u32 as u64)
+        };
+
+        let ref_lane = if pass == 0 && slice == 0 {
+            lane
+        } else {
+            (j2 as usize) % lanes
+        };
+        let ref_index = index_alpha(
+            pass,
+            slice,
+            index,
+            j1,
+            ref_lane == lane,
+            segment_length,
+            lane_length,
+        );
+
+        let new = g_block(&memory[prev], &memory[ref_lane * lane_length + ref_index]);
+        if pass == 0 {
+            memory[cur] = new;
+        } else {
+            // Version 0x13 XORs over the previous pass's block
+            for (a, b) in memory[cur].iter_mut().zip(new.iter()) {
+                *a ^= b;
+            }
+        }
+    }
+}
+
+/// Map J1 onto the reference window (RFC 9106 §3.4.2)
+fn index_alpha(
+    pass: usize,
+    slice: usize,
+    index: usize,
+    j1: u64,
+    same_lane: bool,
+    segment_length: usize,
+    lane_length: usize,
+) -> usize {
+    let reference_area = if pass == 0 {
+        if slice == 0 {
+            index - 1
+        } else if same_lane {
+            slice * segment_length + index - 1
+        } else {
+            slice * segment_length - if index == 0 { 1 } else { 0 }
+        }
+    } else if same_lane {
+        lane_length - segment_length + index - 1
+    } else {
+        lane_length - segment_length - if index == 0 { 1 } else { 0 }
+    } as u64;
+
+    // Non-uniform mapping biased towards recent blocks
+    let mut rel = (j1 * j1) >> 32;
+    rel = (reference_area * rel) >> 32;
+    let position = reference_area - 1 - rel;
+
+    let start = if pass == 0 {
+        0
+    } else {
+        (slice + 1) % SYNC_POINTS * segment_length
+    };
+    ((start as u64 + position) % lane_length as u64) as usize
+}
+
+/// Compression function G over two 1024-byte blocks (RFC 9106 §3.5)
+fn g_block(x: &Block, y: &Block) -> Block {
+    let mut r = [0u64; 128];
+    for i in 0..128 {
+        r[i] = x[i] ^ y[i];
+    }
+    let mut z = r;
+
+    // Rows, then columns, of the 8x8 matrix of 16-byte registers
+    for row in 0..8 {
+        let mut v = [0u64; 16];
+        v.copy_from_slice(&z[row * 16..row * 16 + 16]);
+        permute(&mut v);
+        z[row * 16..row * 16 + 16].copy_from_slice(&v);
+    }
+    for col in 0..8 {
+        let mut v = [0u64; 16];
+        for i in 0..8 {
+            v[i * 2] = z[col * 2 + i * 16];
+            v[i * 2 + 1] = z[col * 2 + i * 16 + 1];
+        }
+        permute(&mut v);
+        for i in 0..8 {
+            z[col * 2 + i * 16] = v[i * 2];
+            z[col * 2 + i * 16 + 1] = v[i * 2 + 1];
+        }
+    }
+
+    for i in 0..128 {
+        z[i] ^= r[i];
+    }
+    z
+}
+
+/// BLAKE2b round with the BlaMka multiply-add
+fn permute(v: &mut [u64; 16]) {
+    gb(v, 0, 4, 8, 12);
+    gb(v, 1, 5, 9, 13);
+    gb(v, 2, 6, 10, 14);
+    gb(v, 3, 7, 11, 15);
+    gb(v, 0, 5, 10, 15);
+    gb(v, 1, 6, 11, 12);
+    gb(v, 2, 7, 8, 13);
+    gb(v, 3, 4, 9, 14);
+}
+
+#[inline(always)]
+fn gb(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize) {
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+/// a + b + 2 * lo32(a) * lo32(b)
+#[inline(always)]
+fn blamka(a: u64, b: u64) -> u64 {
+    let product = (a as u32 as u64).wrapping_mul(b as u32 as u64);
+    a.wrapping_add(b).wrapping_add(product.wrapping_mul(2))
+}
+
+fn bytes_to_block(bytes: &[u8]) -> Block {
+    let mut block = ZERO_BLOCK;
+    for (i, word) in block.iter_mut().enumerate() {
+        *word = u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap());
+    }
+    block
+}
+
+fn block_to_bytes(block: &Block) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1024);
+    for word in block {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Vectors from the reference implementation's test suite
+    // (phc-winner-argon2, Argon2id v=0x13)
+
+    #[test]
+    fn test_argon2id_m256_t2_p1() {
+        let params = Argon2Params::new()
+            .memory_kib(256)
+            .iterations(2)
+            .parallelism(1);
+        let tag = argon2id(b"password", b"somesalt", params, 32);
+        assert_eq!(
+            hex(&tag),
+            "9dfeb910e80bad0311fee20f9c0e2b12c17987b4cac90c2ef54d5b3021c68bfe"
+        );
+    }
+
+    #[test]
+    fn test_argon2id_m256_t2_p2() {
+        let params = Argon2Params::new()
+            .memory_kib(256)
+            .iterations(2)
+            .parallelism(2);
+        let tag = argon2id(b"password", b"somesalt", params, 32);
+        assert_eq!(
+            hex(&tag),
+            "6d093c501fd5999645e0ea3bf620d7b8be7fd2db59c20d9fff9539da2bf57037"
+        );
+    }
+
+    #[test]
+    fn test_argon2id_salt_sensitivity() {
+        let params = Argon2Params::new()
+            .memory_kib(64)
+            .iterations(1)
+            .parallelism(1);
+        let a = argon2id(b"password", b"salt-one", params, 32);
+        let b = argon2id(b"password", b"salt-two", params, 32);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,90 @@
+//! Base32 encoding for OTP shared secrets (RFC 4648)
+//!
+//! Minimal implementation without external dependencies.
+//! Authenticator apps exchange TOTP secrets in Base32.
+
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encode bytes to Base32 string (unpadded, as used in otpauth URIs)
+pub fn base32_encode(input: &[u8]) -> String {
+    let mut output = String::with_capacity(input.len().div_ceil(5) * 8);
+    let mut buffer: u64 = 0;
+    let mut bits = 0u32;
+
+    for &byte in input {
+        buffer = (buffer << 8) | byte as u64;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(ALPHABET[((buffer >> bits) & 0x1F) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize] as char);
+    }
+    output
+}
+
+/// Decode a Base32 string to bytes
+///
+/// Tolerant of lowercase, spaces, and trailing padding, since secrets
+/// are often hand-copied. Returns None on any other character.
+pub fn base32_decode(input: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u64 = 0;
+    let mut bits = 0u32;
+
+    for c in input.chars() {
+        let value = match c {
+            'A'..='Z' => c as u64 - 'A' as u64,
+            'a'..='z' => c as u64 - 'a' as u64,
+            '2'..='7' => c as u64 - '2' as u64 + 26,
+            ' ' | '-' => continue,
+            '=' => break,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push(((buffer >> bits) & 0xFF) as u8);
+        }
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc4648_vectors() {
+        // RFC 4648 §10 test vectors, padding stripped
+        assert_eq!(base32_encode(b""), "");
+        assert_eq!(base32_encode(b"f"), "MY");
+        assert_eq!(base32_encode(b"fo"), "MZXQ");
+        assert_eq!(base32_encode(b"foo"), "MZXW6");
+        assert_eq!(base32_encode(b"foob"), "MZXW6YQ");
+        assert_eq!(base32_encode(b"fooba"), "MZXW6YTB");
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let data = b"12345678901234567890";
+        assert_eq!(base32_decode(&base32_encode(data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_tolerant() {
+        assert_eq!(base32_decode("mzxw6ytboi").unwrap(), b"foobar");
+        assert_eq!(base32_decode("MZXW 6YTB OI").unwrap(), b"foobar");
+        assert_eq!(base32_decode("MZXW6YTBOI======").unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert!(base32_decode("MZXW1").is_none()); // '1' not in alphabet
+        assert!(base32_decode("MZ!W6").is_none());
+    }
+}
@@ -34,6 +34,54 @@ pub fn base64_encode(input: &[u8]) -> String {
     output
 }
 
+/// Decode a Base64 string to bytes
+///
+/// Accepts standard alphabet with optional padding; returns None on
+/// invalid characters.
+pub fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim_end_matches('=');
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0u32;
+
+    for &b in input.as_bytes() {
+        let value = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        } as u32;
+
+        buffer = (buffer << 6) | value;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+/// Encode bytes to unpadded URL-safe Base64 (RFC 4648 §5)
+///
+/// For tokens carried in URLs, cookies, and headers: `-` and `_`
+/// replace `+` and `/`, and padding is dropped.
+pub fn base64url_encode(input: &[u8]) -> String {
+    base64_encode(input)
+        .trim_end_matches('=')
+        .chars()
+        .map(|c| match c {
+            '+' => '-',
+            '/' => '_',
+            c => c,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,4 +120,17 @@ mod tests {
     fn test_base64_foobar() {
         assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
     }
+
+    #[test]
+    fn test_base64_decode_roundtrip() {
+        for input in [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"] {
+            let encoded = base64_encode(input);
+            assert_eq!(base64_decode(&encoded).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_base64_decode_invalid() {
+        assert!(base64_decode("not valid!").is_none());
+    }
 }
@@ -0,0 +1,151 @@
+//! ChaCha20 stream cipher (RFC 8439) and the XChaCha20 variant
+//!
+//! Minimal implementation without external dependencies.
+//! Building block for PASETO v4.local encryption.
+
+/// "expand 32-byte k" constants
+const SIGMA: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+#[inline(always)]
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+fn rounds(state: &mut [u32; 16]) {
+    for _ in 0..10 {
+        quarter_round(state, 0, 4, 8, 12);
+        quarter_round(state, 1, 5, 9, 13);
+        quarter_round(state, 2, 6, 10, 14);
+        quarter_round(state, 3, 7, 11, 15);
+        quarter_round(state, 0, 5, 10, 15);
+        quarter_round(state, 1, 6, 11, 12);
+        quarter_round(state, 2, 7, 8, 13);
+        quarter_round(state, 3, 4, 9, 14);
+    }
+}
+
+/// One 64-byte keystream block for the given counter
+fn block(key: &[u8; 32], nonce: &[u8; 12], counter: u32) -> [u8; 64] {
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&SIGMA);
+    for i in 0..8 {
+        state[4 + i] = u32::from_le_bytes(key[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+    state[12] = counter;
+    for i in 0..3 {
+        state[13 + i] = u32::from_le_bytes(nonce[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+
+    let initial = state;
+    rounds(&mut state);
+
+    let mut out = [0u8; 64];
+    for i in 0..16 {
+        let word = state[i].wrapping_add(initial[i]);
+        out[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+    }
+    out
+}
+
+/// XOR data with the ChaCha20 keystream (encrypts and decrypts)
+pub fn chacha20_xor(key: &[u8; 32], nonce: &[u8; 12], counter: u32, data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    for (i, chunk) in data.chunks(64).enumerate() {
+        let keystream = block(key, nonce, counter.wrapping_add(i as u32));
+        out.extend(chunk.iter().zip(keystream.iter()).map(|(d, k)| d ^ k));
+    }
+    out
+}
+
+/// Derive an XChaCha20 subkey from a 16-byte nonce prefix
+///
+/// HChaCha20: the ChaCha20 rounds without the final state addition,
+/// keeping only the first and last rows.
+pub fn hchacha20(key: &[u8; 32], nonce: &[u8; 16]) -> [u8; 32] {
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&SIGMA);
+    for i in 0..8 {
+        state[4 + i] = u32::from_le_bytes(key[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+    for i in 0..4 {
+        state[12 + i] = u32::from_le_bytes(nonce[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+
+    rounds(&mut state);
+
+    let mut out = [0u8; 32];
+    for i in 0..4 {
+        out[i * 4..i * 4 + 4].copy_from_slice(&state[i].to_le_bytes());
+        out[16 + i * 4..16 + i * 4 + 4].copy_from_slice(&state[12 + i].to_le_bytes());
+    }
+    out
+}
+
+/// XOR data with the XChaCha20 keystream (24-byte nonce)
+pub fn xchacha20_xor(key: &[u8; 32], nonce: &[u8; 24], data: &[u8]) -> Vec<u8> {
+    let subkey = hchacha20(key, nonce[..16].try_into().unwrap());
+    let mut subnonce = [0u8; 12];
+    subnonce[4..].copy_from_slice(&nonce[16..]);
+    chacha20_xor(&subkey, &subnonce, 0, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn key() -> [u8; 32] {
+        core::array::from_fn(|i| i as u8)
+    }
+
+    #[test]
+    fn test_chacha20_block() {
+        // RFC 8439 §2.3.2 block function test vector
+        let nonce = [0, 0, 0, 9, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+        assert_eq!(
+            hex(&block(&key(), &nonce, 1)),
+            "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e\
+             d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"
+        );
+    }
+
+    #[test]
+    fn test_chacha20_roundtrip() {
+        let nonce = [7u8; 12];
+        let plaintext = b"Attack at dawn, but quietly this time".to_vec();
+        let ciphertext = chacha20_xor(&key(), &nonce, 0, &plaintext);
+        assert_ne!(ciphertext, plaintext);
+        assert_eq!(chacha20_xor(&key(), &nonce, 0, &ciphertext), plaintext);
+    }
+
+    #[test]
+    fn test_hchacha20() {
+        // draft-irtf-cfrg-xchacha §2.2.1 test vector
+        let nonce = [
+            0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00, 0x31, 0x41,
+            0x59, 0x27,
+        ];
+        assert_eq!(
+            hex(&hchacha20(&key(), &nonce)),
+            "82413b4227b27bfed30e42508a877d73a0f9e4d58a74a853c12ec41326d3ecdc"
+        );
+    }
+
+    #[test]
+    fn test_xchacha20_roundtrip() {
+        let nonce = [9u8; 24];
+        let plaintext = vec![0xabu8; 200];
+        let ciphertext = xchacha20_xor(&key(), &nonce, &plaintext);
+        assert_eq!(xchacha20_xor(&key(), &nonce, &ciphertext), plaintext);
+    }
+}
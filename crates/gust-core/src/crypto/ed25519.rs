@@ -0,0 +1,571 @@
+//! Ed25519 signatures (RFC 8032)
+//!
+//! Minimal implementation without external dependencies.
+//! Building block for PASETO v4.public tokens. Field elements are
+//! four 64-bit limbs reduced modulo 2^255 - 19; points use extended
+//! twisted Edwards coordinates, and scalar multiplication is
+//! branch-free so signing does not leak key bits through timing.
+
+use super::sha512::sha512;
+
+/// Field element: 256-bit little-endian limbs, kept below 2^256 and
+/// fully reduced only when encoding or comparing
+type Fe = [u64; 4];
+
+/// p = 2^255 - 19
+const P: Fe = [
+    0xffff_ffff_ffff_ffed,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0x7fff_ffff_ffff_ffff,
+];
+
+/// Curve constant d = -121665/121666
+const D: Fe = [
+    0x75eb_4dca_1359_78a3,
+    0x0070_0a4d_4141_d8ab,
+    0x8cc7_4079_7779_e898,
+    0x5203_6cee_2b6f_fe73,
+];
+
+/// sqrt(-1) = 2^((p-1)/4)
+const SQRT_M1: Fe = [
+    0xc4ee_1b27_4a0e_a0b0,
+    0x2f43_1806_ad2f_e478,
+    0x2b4d_0099_3dfb_d7a7,
+    0x2b83_2480_4fc1_df0b,
+];
+
+/// Group order L = 2^252 + 27742317777372353535851937790883648493
+const L: [u64; 4] = [
+    0x5812_631a_5cf5_d3ed,
+    0x14de_f9de_a2f7_9cd6,
+    0x0000_0000_0000_0000,
+    0x1000_0000_0000_0000,
+];
+
+const FE_ZERO: Fe = [0, 0, 0, 0];
+const FE_ONE: Fe = [1, 0, 0, 0];
+
+#[inline(always)]
+fn adc(a: u64, b: u64, carry: &mut u64) -> u64 {
+    let t = a as u128 + b as u128 + *carry as u128;
+    *carry = (t >> 64) as u64;
+    t as u64
+}
+
+#[inline(always)]
+fn sbb(a: u64, b: u64, borrow: &mut u64) -> u64 {
+    let t = (a as u128).wrapping_sub(b as u128 + *borrow as u128);
+    *borrow = ((t >> 64) as u64) & 1;
+    t as u64
+}
+
+fn geq(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+fn sub4(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut borrow = 0;
+    let mut out = [0u64; 4];
+    for i in 0..4 {
+        out[i] = sbb(a[i], b[i], &mut borrow);
+    }
+    out
+}
+
+/// Add a small constant, propagating the carry
+fn add_small(a: &mut [u64; 4], value: u64) {
+    let mut carry = value;
+    for limb in a.iter_mut() {
+        let t = *limb as u128 + carry as u128;
+        *limb = t as u64;
+        carry = (t >> 64) as u64;
+        if carry == 0 {
+            break;
+        }
+    }
+    // A final wrap past 2^256 folds back in as 38 (2^256 = 38 mod p)
+    if carry != 0 {
+        add_small(a, 38);
+    }
+}
+
+fn fe_add(a: &Fe, b: &Fe) -> Fe {
+    let mut carry = 0;
+    let mut out = [0u64; 4];
+    for i in 0..4 {
+        out[i] = adc(a[i], b[i], &mut carry);
+    }
+    if carry != 0 {
+        add_small(&mut out, 38);
+    }
+    out
+}
+
+fn fe_sub(a: &Fe, b: &Fe) -> Fe {
+    let mut borrow = 0;
+    let mut out = [0u64; 4];
+    for i in 0..4 {
+        out[i] = sbb(a[i], b[i], &mut borrow);
+    }
+    // Wrapped past zero: the wrap added 2^256, correct it by -38
+    if borrow != 0 {
+        let mut b2 = 0;
+        let mut fixed = [0u64; 4];
+        for i in 0..4 {
+            fixed[i] = sbb(out[i], if i == 0 { 38 } else { 0 }, &mut b2);
+        }
+        if b2 != 0 {
+            // Can only happen for values below 38; fold once more
+            let mut b3 = 0;
+            for (i, limb) in fixed.iter_mut().enumerate() {
+                *limb = sbb(*limb, if i == 0 { 38 } else { 0 }, &mut b3);
+            }
+        }
+        return fixed;
+    }
+    out
+}
+
+fn fe_mul(a: &Fe, b: &Fe) -> Fe {
+    // Schoolbook 4x4 into a 512-bit product
+    let mut wide = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u64;
+        for j in 0..4 {
+            let t = a[i] as u128 * b[j] as u128 + wide[i + j] as u128 + carry as u128;
+            wide[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        wide[i + 4] = carry;
+    }
+    reduce_wide(&wide)
+}
+
+fn fe_square(a: &Fe) -> Fe {
+    fe_mul(a, a)
+}
+
+/// Reduce a 512-bit product: 2^256 = 38 (mod p)
+fn reduce_wide(wide: &[u64; 8]) -> Fe {
+    let mut out = [wide[0], wide[1], wide[2], wide[3]];
+    let mut carry = 0u64;
+    for i in 0..4 {
+        let t = wide[4 + i] as u128 * 38 + out[i] as u128 + carry as u128;
+        out[i] = t as u64;
+        carry = (t >> 64) as u64;
+    }
+    if carry != 0 {
+        add_small(&mut out, carry.wrapping_mul(38));
+    }
+    out
+}
+
+/// Fully reduce into [0, p)
+fn fe_normalize(a: &Fe) -> Fe {
+    let mut out = *a;
+    while geq(&out, &P) {
+        out = sub4(&out, &P);
+    }
+    out
+}
+
+fn fe_eq(a: &Fe, b: &Fe) -> bool {
+    fe_normalize(a) == fe_normalize(b)
+}
+
+/// Raise to a 256-bit exponent (square-and-multiply)
+fn fe_pow(base: &Fe, exp: &[u64; 4]) -> Fe {
+    let mut result = FE_ONE;
+    for i in (0..256).rev() {
+        result = fe_square(&result);
+        if (exp[i / 64] >> (i % 64)) & 1 == 1 {
+            result = fe_mul(&result, base);
+        }
+    }
+    result
+}
+
+fn fe_invert(a: &Fe) -> Fe {
+    // Fermat: a^(p-2)
+    let mut exp = P;
+    exp[0] -= 2;
+    fe_pow(a, &exp)
+}
+
+/// Square root via a^((p+3)/8), fixed up by sqrt(-1) (RFC 8032 §5.1.3)
+fn fe_sqrt(a: &Fe) -> Option<Fe> {
+    // (p+3)/8 = 2^252 - 2
+    let exp = [
+        0xffff_ffff_ffff_fffe,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+        0x0fff_ffff_ffff_ffff,
+    ];
+    let candidate = fe_pow(a, &exp);
+    if fe_eq(&fe_square(&candidate), a) {
+        return Some(candidate);
+    }
+    let candidate = fe_mul(&candidate, &SQRT_M1);
+    if fe_eq(&fe_square(&candidate), a) {
+        return Some(candidate);
+    }
+    None
+}
+
+fn fe_from_bytes(bytes: &[u8; 32]) -> Fe {
+    let mut out = [0u64; 4];
+    for i in 0..4 {
+        out[i] = u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap());
+    }
+    out
+}
+
+fn fe_to_bytes(a: &Fe) -> [u8; 32] {
+    let a = fe_normalize(a);
+    let mut out = [0u8; 32];
+    for i in 0..4 {
+        out[i * 8..i * 8 + 8].copy_from_slice(&a[i].to_le_bytes());
+    }
+    out
+}
+
+/// Point in extended twisted Edwards coordinates (x = X/Z, y = Y/Z, T = XY/Z)
+#[derive(Clone, Copy)]
+struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+    t: Fe,
+}
+
+const IDENTITY: Point = Point {
+    x: FE_ZERO,
+    y: FE_ONE,
+    z: FE_ONE,
+    t: FE_ZERO,
+};
+
+/// Base point B = (x, 4/5)
+fn base_point() -> Point {
+    let x: Fe = [
+        0xc956_2d60_8f25_d51a,
+        0x692c_c760_9525_a7b2,
+        0xc0a4_e231_fdd6_dc5c,
+        0x2169_36d3_cd6e_53fe,
+    ];
+    let y: Fe = [
+        0x6666_6666_6666_6658,
+        0x6666_6666_6666_6666,
+        0x6666_6666_6666_6666,
+        0x6666_6666_6666_6666,
+    ];
+    Point {
+        x,
+        y,
+        z: FE_ONE,
+        t: fe_mul(&x, &y),
+    }
+}
+
+/// Unified point addition (add-2008-hwcd-3, complete for a = -1)
+fn point_add(p: &Point, q: &Point) -> Point {
+    let a = fe_mul(&fe_sub(&p.y, &p.x), &fe_sub(&q.y, &q.x));
+    let b = fe_mul(&fe_add(&p.y, &p.x), &fe_add(&q.y, &q.x));
+    let two_d = fe_add(&D, &D);
+    let c = fe_mul(&fe_mul(&p.t, &two_d), &q.t);
+    let d = fe_mul(&fe_add(&p.z, &p.z), &q.z);
+    let e = fe_sub(&b, &a);
+    let f = fe_sub(&d, &c);
+    let g = fe_add(&d, &c);
+    let h = fe_add(&b, &a);
+    Point {
+        x: fe_mul(&e, &f),
+        y: fe_mul(&g, &h),
+        z: fe_mul(&f, &g),
+        t: fe_mul(&e, &h),
+    }
+}
+
+/// Constant-time conditional assignment: `a = b` where `mask` is all-ones
+fn fe_select(a: &mut Fe, b: &Fe, mask: u64) {
+    for (x, y) in a.iter_mut().zip(b.iter()) {
+        *x = (*x & !mask) | (y & mask);
+    }
+}
+
+/// Scalar multiplication over a 256-bit scalar
+///
+/// Branch-free double-and-always-add: the addition is computed every
+/// iteration and kept or discarded through a masked select, so the
+/// sequence of field operations does not depend on secret scalar
+/// bits (the scalar is the long-term signing key when issuing
+/// PASETO v4.public tokens).
+fn point_mul(scalar: &[u8; 32], point: &Point) -> Point {
+    let mut result = IDENTITY;
+    for i in (0..256).rev() {
+        result = point_add(&result, &result);
+        let added = point_add(&result, point);
+        let mask = (((scalar[i / 8] >> (i % 8)) & 1) as u64).wrapping_neg();
+        fe_select(&mut result.x, &added.x, mask);
+        fe_select(&mut result.y, &added.y, mask);
+        fe_select(&mut result.z, &added.z, mask);
+        fe_select(&mut result.t, &added.t, mask);
+    }
+    result
+}
+
+/// Compress to 32 bytes: y with the sign of x in the top bit
+fn point_compress(p: &Point) -> [u8; 32] {
+    let zinv = fe_invert(&p.z);
+    let x = fe_mul(&p.x, &zinv);
+    let y = fe_mul(&p.y, &zinv);
+    let mut out = fe_to_bytes(&y);
+    out[31] |= (fe_to_bytes(&x)[0] & 1) << 7;
+    out
+}
+
+/// Decompress per RFC 8032 §5.1.3; None for invalid encodings
+fn point_decompress(bytes: &[u8; 32]) -> Option<Point> {
+    let mut y_bytes = *bytes;
+    let sign = (y_bytes[31] >> 7) & 1;
+    y_bytes[31] &= 0x7f;
+    let y = fe_from_bytes(&y_bytes);
+    if geq(&y, &P) {
+        return None;
+    }
+
+    // x^2 = (y^2 - 1) / (d y^2 + 1)
+    let y2 = fe_square(&y);
+    let u = fe_sub(&y2, &FE_ONE);
+    let v = fe_add(&fe_mul(&D, &y2), &FE_ONE);
+    let x2 = fe_mul(&u, &fe_invert(&v));
+    let mut x = fe_sqrt(&x2)?;
+
+    let x_bytes = fe_to_bytes(&x);
+    if x_bytes == [0u8; 32] && sign == 1 {
+        return None;
+    }
+    if x_bytes[0] & 1 != sign {
+        x = fe_sub(&FE_ZERO, &x);
+    }
+
+    Some(Point {
+        x,
+        y,
+        z: FE_ONE,
+        t: fe_mul(&x, &y),
+    })
+}
+
+/// Reduce a little-endian integer modulo L by double-and-add
+fn scalar_reduce(bytes: &[u8]) -> [u64; 4] {
+    let mut r = [0u64; 4];
+    for i in (0..bytes.len() * 8).rev() {
+        // r = r * 2 + bit, then conditionally subtract L
+        let mut carry = 0;
+        for limb in r.iter_mut() {
+            let t = (*limb as u128) << 1 | carry as u128;
+            *limb = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        r[0] |= ((bytes[i / 8] >> (i % 8)) & 1) as u64;
+        if geq(&r, &L) {
+            r = sub4(&r, &L);
+        }
+    }
+    r
+}
+
+/// (a * b + c) mod L
+fn scalar_muladd(a: &[u64; 4], b: &[u64; 4], c: &[u64; 4]) -> [u64; 4] {
+    let mut wide = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u64;
+        for j in 0..4 {
+            let t = a[i] as u128 * b[j] as u128 + wide[i + j] as u128 + carry as u128;
+            wide[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        wide[i + 4] = carry;
+    }
+    let mut wide_bytes = [0u8; 64];
+    for i in 0..8 {
+        wide_bytes[i * 8..i * 8 + 8].copy_from_slice(&wide[i].to_le_bytes());
+    }
+    let mut r = scalar_reduce(&wide_bytes);
+
+    let mut carry = 0;
+    for i in 0..4 {
+        r[i] = adc(r[i], c[i], &mut carry);
+    }
+    if geq(&r, &L) {
+        r = sub4(&r, &L);
+    }
+    r
+}
+
+fn scalar_to_bytes(a: &[u64; 4]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for i in 0..4 {
+        out[i * 8..i * 8 + 8].copy_from_slice(&a[i].to_le_bytes());
+    }
+    out
+}
+
+/// Clamp the secret scalar per RFC 8032 §5.1.5
+fn clamp(bytes: &mut [u8; 32]) {
+    bytes[0] &= 0xf8;
+    bytes[31] &= 0x7f;
+    bytes[31] |= 0x40;
+}
+
+/// Derive the public key from a 32-byte seed
+pub fn ed25519_public_key(seed: &[u8; 32]) -> [u8; 32] {
+    let h = sha512(seed);
+    let mut scalar: [u8; 32] = h[..32].try_into().unwrap();
+    clamp(&mut scalar);
+    point_compress(&point_mul(&scalar, &base_point()))
+}
+
+/// Sign a message with a 32-byte seed (RFC 8032 §5.1.6)
+pub fn ed25519_sign(seed: &[u8; 32], message: &[u8]) -> [u8; 64] {
+    let h = sha512(seed);
+    let mut scalar: [u8; 32] = h[..32].try_into().unwrap();
+    clamp(&mut scalar);
+    let prefix = &h[32..];
+    let public = point_compress(&point_mul(&scalar, &base_point()));
+
+    let mut r_input = prefix.to_vec();
+    r_input.extend_from_slice(message);
+    let r = scalar_reduce(&sha512(&r_input));
+    let r_point = point_compress(&point_mul(&scalar_to_bytes(&r), &base_point()));
+
+    let mut k_input = r_point.to_vec();
+    k_input.extend_from_slice(&public);
+    k_input.extend_from_slice(message);
+    let k = scalar_reduce(&sha512(&k_input));
+
+    let a = scalar_reduce(&scalar);
+    let s = scalar_muladd(&k, &a, &r);
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r_point);
+    signature[32..].copy_from_slice(&scalar_to_bytes(&s));
+    signature
+}
+
+/// Verify a signature (RFC 8032 §5.1.7)
+pub fn ed25519_verify(public: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let r_bytes: [u8; 32] = signature[..32].try_into().unwrap();
+    let s_bytes: [u8; 32] = signature[32..].try_into().unwrap();
+
+    // Reject malleable signatures: s must already be below L
+    let mut s = [0u64; 4];
+    for i in 0..4 {
+        s[i] = u64::from_le_bytes(s_bytes[i * 8..i * 8 + 8].try_into().unwrap());
+    }
+    if geq(&s, &L) {
+        return false;
+    }
+
+    let a = match point_decompress(public) {
+        Some(p) => p,
+        None => return false,
+    };
+    let r = match point_decompress(&r_bytes) {
+        Some(p) => p,
+        None => return false,
+    };
+
+    let mut k_input = r_bytes.to_vec();
+    k_input.extend_from_slice(public);
+    k_input.extend_from_slice(message);
+    let k = scalar_reduce(&sha512(&k_input));
+
+    // s*B == R + k*A
+    let lhs = point_mul(&s_bytes, &base_point());
+    let rhs = point_add(&r, &point_mul(&scalar_to_bytes(&k), &a));
+    point_compress(&lhs) == point_compress(&rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn unhex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_ed25519_rfc8032_test1() {
+        // RFC 8032 §7.1 TEST 1 (empty message)
+        let seed: [u8; 32] =
+            unhex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                .try_into()
+                .unwrap();
+        let public = ed25519_public_key(&seed);
+        assert_eq!(
+            hex(&public),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        let signature = ed25519_sign(&seed, b"");
+        assert_eq!(
+            hex(&signature),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+        assert!(ed25519_verify(&public, b"", &signature));
+    }
+
+    #[test]
+    fn test_ed25519_rfc8032_test2() {
+        // RFC 8032 §7.1 TEST 2 (one-byte message)
+        let seed: [u8; 32] =
+            unhex("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb")
+                .try_into()
+                .unwrap();
+        let public = ed25519_public_key(&seed);
+        assert_eq!(
+            hex(&public),
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"
+        );
+        let signature = ed25519_sign(&seed, &[0x72]);
+        assert_eq!(
+            hex(&signature),
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+        );
+        assert!(ed25519_verify(&public, &[0x72], &signature));
+    }
+
+    #[test]
+    fn test_ed25519_rejects_tampering() {
+        let seed = [42u8; 32];
+        let public = ed25519_public_key(&seed);
+        let signature = ed25519_sign(&seed, b"hello");
+
+        assert!(ed25519_verify(&public, b"hello", &signature));
+        assert!(!ed25519_verify(&public, b"hellO", &signature));
+
+        let mut bad = signature;
+        bad[0] ^= 1;
+        assert!(!ed25519_verify(&public, b"hello", &bad));
+
+        let other = ed25519_public_key(&[43u8; 32]);
+        assert!(!ed25519_verify(&other, b"hello", &signature));
+    }
+}
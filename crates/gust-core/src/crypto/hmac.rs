@@ -0,0 +1,124 @@
+//! HMAC-SHA256 and HMAC-SHA1 implementations (RFC 2104)
+//!
+//! Keyed hashing for request signing (AWS SigV4, webhooks) and
+//! one-time passwords (HOTP/TOTP).
+
+use super::sha1;
+use super::sha256;
+
+const BLOCK_SIZE: usize = 64;
+
+/// Compute HMAC-SHA256 of a message under a key
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    // Keys longer than the block size are hashed first
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block_key[..32].copy_from_slice(&sha256(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Vec::with_capacity(BLOCK_SIZE + message.len());
+    for b in &block_key {
+        inner.push(b ^ 0x36);
+    }
+    inner.extend_from_slice(message);
+    let inner_hash = sha256(&inner);
+
+    let mut outer = Vec::with_capacity(BLOCK_SIZE + 32);
+    for b in &block_key {
+        outer.push(b ^ 0x5c);
+    }
+    outer.extend_from_slice(&inner_hash);
+    sha256(&outer)
+}
+
+/// Compute HMAC-SHA1 of a message under a key
+///
+/// SHA-1 is fine here: HMAC-SHA1 is not affected by SHA-1 collision
+/// attacks and remains the default algorithm for TOTP (RFC 6238).
+pub fn hmac_sha1(key: &[u8], message: &[u8]) -> [u8; 20] {
+    // Keys longer than the block size are hashed first
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block_key[..20].copy_from_slice(&sha1(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Vec::with_capacity(BLOCK_SIZE + message.len());
+    for b in &block_key {
+        inner.push(b ^ 0x36);
+    }
+    inner.extend_from_slice(message);
+    let inner_hash = sha1(&inner);
+
+    let mut outer = Vec::with_capacity(BLOCK_SIZE + 20);
+    for b in &block_key {
+        outer.push(b ^ 0x5c);
+    }
+    outer.extend_from_slice(&inner_hash);
+    sha1(&outer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_rfc4231_case_1() {
+        let digest = hmac_sha256(&[0x0b; 20], b"Hi There");
+        assert_eq!(
+            hex(&digest),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+    }
+
+    #[test]
+    fn test_rfc4231_case_2() {
+        let digest = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            hex(&digest),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_rfc4231_long_key() {
+        // Case 6: key longer than the block size is hashed first
+        let digest = hmac_sha256(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!(
+            hex(&digest),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_rfc2202_sha1_case_1() {
+        let digest = hmac_sha1(&[0x0b; 20], b"Hi There");
+        assert_eq!(hex(&digest), "b617318655057264e28bc0b6fb378c8ef146be00");
+    }
+
+    #[test]
+    fn test_rfc2202_sha1_case_2() {
+        let digest = hmac_sha1(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(hex(&digest), "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79");
+    }
+
+    #[test]
+    fn test_rfc2202_sha1_long_key() {
+        // Case 6: key longer than the block size is hashed first
+        let digest = hmac_sha1(
+            &[0xaa; 80],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!(hex(&digest), "aa4ae5e15272d00e95705637ce8a3b55ed402112");
+    }
+}
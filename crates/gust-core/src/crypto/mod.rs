@@ -3,11 +3,67 @@
 //! These implementations are used by WebSocket handlers in both
 //! native and WASM builds.
 
+mod aes_gcm;
+mod blake2b;
+mod chacha20;
+mod ed25519;
 mod sha1;
+mod sha256;
+mod sha512;
+mod md5;
+mod base32;
 mod base64;
+mod hmac;
+mod rand;
+pub mod sigv4;
+mod totp;
 
+#[cfg(feature = "password-hash")]
+mod argon2;
+#[cfg(feature = "password-hash")]
+mod password;
+#[cfg(feature = "password-hash")]
+mod scrypt;
+
+pub use aes_gcm::{aes256_gcm_decrypt, aes256_gcm_encrypt};
+pub use blake2b::{blake2b, blake2b_keyed};
+pub use chacha20::{chacha20_xor, hchacha20, xchacha20_xor};
+pub use ed25519::{ed25519_public_key, ed25519_sign, ed25519_verify};
 pub use sha1::sha1;
-pub use base64::base64_encode;
+pub use sha256::{sha256, sha256_hex};
+pub use sha512::sha512;
+pub use md5::md5;
+pub use base32::{base32_encode, base32_decode};
+pub use base64::{base64_encode, base64_decode, base64url_encode};
+pub use hmac::{hmac_sha1, hmac_sha256};
+pub use rand::{
+    fill_random, fill_random_or_fallback, random_base64url, random_bytes, random_hex,
+    try_fill_random, uuid_v4, uuid_v4_from_bytes, uuid_v7, uuid_v7_from_parts,
+};
+pub use totp::{hotp, OtpAlgorithm, Totp};
+
+#[cfg(feature = "password-hash")]
+pub use argon2::{argon2id, Argon2Params};
+#[cfg(feature = "password-hash")]
+pub use password::{hash_password, hash_password_scrypt, verify_password, PasswordError};
+#[cfg(feature = "password-hash")]
+pub use scrypt::{pbkdf2_sha256, scrypt, ScryptParams};
+
+/// Compare two byte slices without early exit on mismatch
+///
+/// For secrets (signatures, tokens, password hashes): the run time
+/// depends only on the lengths, so timing does not leak how much of
+/// a guess matched.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
 
 /// Generate WebSocket accept key from client key (RFC 6455)
 pub fn websocket_accept_key(client_key: &str) -> String {
@@ -0,0 +1,243 @@
+//! Password hashing in PHC string format
+//!
+//! Wraps [`argon2id`](super::argon2id) (default) and
+//! [`scrypt`](super::scrypt) (fallback) behind hash/verify functions
+//! producing self-describing `$argon2id$...` / `$scrypt$...` strings,
+//! so stored hashes survive parameter changes and algorithm
+//! migrations.
+
+use super::argon2::{argon2id, Argon2Params};
+use super::base64::{base64_decode, base64_encode};
+use super::constant_time_eq;
+use super::scrypt::{scrypt, ScryptParams};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Length of generated salts and hashes, in bytes
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+
+/// Errors from [`verify_password`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordError {
+    /// The encoded hash is not a recognized PHC string
+    MalformedHash,
+    /// The algorithm identifier is not supported
+    UnknownAlgorithm,
+}
+
+impl std::fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasswordError::MalformedHash => write!(f, "Malformed password hash"),
+            PasswordError::UnknownAlgorithm => write!(f, "Unknown password hash algorithm"),
+        }
+    }
+}
+
+impl std::error::Error for PasswordError {}
+
+/// Hash a password with argon2id into a PHC string
+///
+/// # Example
+/// ```
+/// use gust_core::crypto::{hash_password, verify_password, Argon2Params};
+///
+/// let params = Argon2Params::new().memory_kib(64).iterations(1);
+/// let encoded = hash_password("hunter2", params);
+/// assert!(verify_password("hunter2", &encoded).unwrap());
+/// assert!(!verify_password("hunter3", &encoded).unwrap());
+/// ```
+pub fn hash_password(password: &str, params: Argon2Params) -> String {
+    let salt = generate_salt();
+    let hash = argon2id(password.as_bytes(), &salt, params, HASH_LEN);
+    format!(
+        "$argon2id$v=19$m={},t={},p={}${}${}",
+        params.memory_kib,
+        params.iterations.max(1),
+        params.parallelism.max(1),
+        b64(&salt),
+        b64(&hash),
+    )
+}
+
+/// Hash a password with scrypt into a PHC string
+pub fn hash_password_scrypt(password: &str, params: ScryptParams) -> String {
+    let salt = generate_salt();
+    let hash = scrypt(password.as_bytes(), &salt, params, HASH_LEN);
+    format!(
+        "$scrypt$ln={},r={},p={}${}${}",
+        params.log_n.clamp(1, 24),
+        params.r.max(1),
+        params.p.max(1),
+        b64(&salt),
+        b64(&hash),
+    )
+}
+
+/// Verify a password against a PHC string from either algorithm
+///
+/// The parameters come from the stored hash, so old hashes keep
+/// verifying after defaults change. The comparison is constant-time.
+pub fn verify_password(password: &str, encoded: &str) -> Result<bool, PasswordError> {
+    let mut parts = encoded.strip_prefix('$').ok_or(PasswordError::MalformedHash)?.split('$');
+    let algorithm = parts.next().ok_or(PasswordError::MalformedHash)?;
+
+    match algorithm {
+        "argon2id" => {
+            // Version field is optional in PHC strings
+            let mut field = parts.next().ok_or(PasswordError::MalformedHash)?;
+            if field.starts_with("v=") {
+                field = parts.next().ok_or(PasswordError::MalformedHash)?;
+            }
+            let (m, t, p) = parse_costs(field, ["m", "t", "p"])?;
+            let (salt, hash) = parse_salt_hash(&mut parts)?;
+            let params = Argon2Params::new()
+                .memory_kib(m)
+                .iterations(t)
+                .parallelism(p);
+            let computed = argon2id(password.as_bytes(), &salt, params, hash.len());
+            Ok(constant_time_eq(&computed, &hash))
+        }
+        "scrypt" => {
+            let field = parts.next().ok_or(PasswordError::MalformedHash)?;
+            let (ln, r, p) = parse_costs(field, ["ln", "r", "p"])?;
+            let (salt, hash) = parse_salt_hash(&mut parts)?;
+            let params = ScryptParams::new().log_n(ln).r(r).p(p);
+            let computed = scrypt(password.as_bytes(), &salt, params, hash.len());
+            Ok(constant_time_eq(&computed, &hash))
+        }
+        _ => Err(PasswordError::UnknownAlgorithm),
+    }
+}
+
+/// Parse a `key=value,key=value,key=value` cost field
+fn parse_costs(field: &str, keys: [&str; 3]) -> Result<(u32, u32, u32), PasswordError> {
+    let mut values = [0u32; 3];
+    let mut seen = [false; 3];
+    for pair in field.split(',') {
+        let (key, value) = pair.split_once('=').ok_or(PasswordError::MalformedHash)?;
+        let slot = keys
+            .iter()
+            .position(|k| *k == key)
+            .ok_or(PasswordError::MalformedHash)?;
+        values[slot] = value.parse().map_err(|_| PasswordError::MalformedHash)?;
+        seen[slot] = true;
+    }
+    if seen != [true; 3] {
+        return Err(PasswordError::MalformedHash);
+    }
+    Ok((values[0], values[1], values[2]))
+}
+
+/// Decode the trailing salt and hash segments
+fn parse_salt_hash<'a>(
+    parts: &mut impl Iterator<Item = &'a str>,
+) -> Result<(Vec<u8>, Vec<u8>), PasswordError> {
+    let salt = parts.next().ok_or(PasswordError::MalformedHash)?;
+    let hash = parts.next().ok_or(PasswordError::MalformedHash)?;
+    if parts.next().is_some() {
+        return Err(PasswordError::MalformedHash);
+    }
+    let salt = b64_decode(salt).ok_or(PasswordError::MalformedHash)?;
+    let hash = b64_decode(hash).ok_or(PasswordError::MalformedHash)?;
+    if salt.is_empty() || hash.is_empty() {
+        return Err(PasswordError::MalformedHash);
+    }
+    Ok((salt, hash))
+}
+
+/// Unpadded Base64, as PHC strings use
+fn b64(bytes: &[u8]) -> String {
+    let mut encoded = base64_encode(bytes);
+    while encoded.ends_with('=') {
+        encoded.pop();
+    }
+    encoded
+}
+
+fn b64_decode(input: &str) -> Option<Vec<u8>> {
+    let mut padded = input.to_string();
+    while !padded.len().is_multiple_of(4) {
+        padded.push('=');
+    }
+    base64_decode(&padded)
+}
+
+/// Generate a random salt
+///
+/// In production, this should use a proper CSPRNG; the time-seeded
+/// PRNG matches what the rest of the crate uses for token generation.
+fn generate_salt() -> Vec<u8> {
+    use std::time::SystemTime;
+
+    // Counter for uniqueness within the same nanosecond
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let seed = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+        ^ COUNTER.fetch_add(1, Ordering::Relaxed).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+    let mut state = seed;
+    let mut salt = Vec::with_capacity(SALT_LEN);
+    for _ in 0..SALT_LEN {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        salt.push((state & 0xFF) as u8);
+    }
+    salt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Low-cost parameters keep the tests fast; defaults are for
+    // production use
+    fn test_params() -> Argon2Params {
+        Argon2Params::new().memory_kib(64).iterations(1)
+    }
+
+    #[test]
+    fn test_argon2id_round_trip() {
+        let encoded = hash_password("correct horse battery staple", test_params());
+        assert!(encoded.starts_with("$argon2id$"));
+        assert!(verify_password("correct horse battery staple", &encoded).unwrap());
+        assert!(!verify_password("incorrect horse", &encoded).unwrap());
+    }
+
+    #[test]
+    fn test_scrypt_round_trip() {
+        let params = ScryptParams::new().log_n(4).r(4).p(1);
+        let encoded = hash_password_scrypt("hunter2", params);
+        assert!(encoded.starts_with("$scrypt$"));
+        assert!(verify_password("hunter2", &encoded).unwrap());
+        assert!(!verify_password("hunter3", &encoded).unwrap());
+    }
+
+    #[test]
+    fn test_salts_differ() {
+        let a = hash_password("same password", test_params());
+        let b = hash_password("same password", test_params());
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_malformed_hashes() {
+        assert_eq!(
+            verify_password("x", "not a phc string").unwrap_err(),
+            PasswordError::MalformedHash
+        );
+        assert_eq!(
+            verify_password("x", "$argon2id$m=64$short").unwrap_err(),
+            PasswordError::MalformedHash
+        );
+        assert_eq!(
+            verify_password("x", "$bcrypt$whatever$y$z").unwrap_err(),
+            PasswordError::UnknownAlgorithm
+        );
+    }
+}
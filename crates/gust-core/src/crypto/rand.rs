@@ -0,0 +1,200 @@
+//! Random tokens and UUIDs
+//!
+//! Session tokens, API keys, and request IDs from OS entropy
+//! (/dev/urandom, opened once and cached so fd exhaustion after
+//! startup cannot cut it off). Key and nonce material never degrades
+//! silently: [`fill_random`] and the `random_*` helpers panic when no
+//! OS source exists, and [`try_fill_random`] surfaces the error.
+//! Only request-ID-grade callers may opt into the time-seeded
+//! xorshift64 fallback via [`fill_random_or_fallback`]. The WASM
+//! layer passes its own entropy through the `*_from_*` variants
+//! instead.
+
+use super::base64::base64url_encode;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Fill a buffer from the OS entropy source
+///
+/// The urandom fd is opened on first use and cached for the process
+/// lifetime. Errors are returned, never papered over — a predictable
+/// key or a repeated AES-GCM nonce is worse than failing the request.
+pub fn try_fill_random(buf: &mut [u8]) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Read;
+        use std::sync::OnceLock;
+        static URANDOM: OnceLock<std::fs::File> = OnceLock::new();
+        if URANDOM.get().is_none() {
+            let file = std::fs::File::open("/dev/urandom")?;
+            let _ = URANDOM.set(file);
+        }
+        let mut urandom = URANDOM.get().expect("urandom fd just cached");
+        urandom.read_exact(buf)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = buf;
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "no OS entropy source on this target",
+        ))
+    }
+}
+
+/// Fill a buffer with OS entropy, panicking if none is available
+///
+/// For key and nonce material, aborting beats silently degrading to
+/// a predictable generator; callers that can surface the error use
+/// [`try_fill_random`] instead.
+pub fn fill_random(buf: &mut [u8]) {
+    try_fill_random(buf).expect("OS entropy source unavailable");
+}
+
+/// Best-effort fill for request-ID-grade material only
+///
+/// Falls back to a time-seeded xorshift64 when no OS source exists
+/// (WASM, exotic targets) — fine for trace IDs, never for keys.
+pub fn fill_random_or_fallback(buf: &mut [u8]) {
+    if try_fill_random(buf).is_err() {
+        fill_fallback(buf);
+    }
+}
+
+/// `len` random bytes
+pub fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    fill_random(&mut bytes);
+    bytes
+}
+
+/// `len` random bytes as lowercase hex (2·len characters)
+pub fn random_hex(len: usize) -> String {
+    let mut output = String::with_capacity(len * 2);
+    for byte in random_bytes(len) {
+        output.push_str(&format!("{:02x}", byte));
+    }
+    output
+}
+
+/// `len` random bytes as unpadded URL-safe Base64
+pub fn random_base64url(len: usize) -> String {
+    base64url_encode(&random_bytes(len))
+}
+
+/// Generate a random UUIDv4 (RFC 9562)
+pub fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+    fill_random_or_fallback(&mut bytes);
+    uuid_v4_from_bytes(bytes)
+}
+
+/// Format 16 caller-supplied random bytes as a UUIDv4
+pub fn uuid_v4_from_bytes(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0F) | 0x40;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+    format_uuid(&bytes)
+}
+
+/// Generate a time-ordered UUIDv7 (RFC 9562)
+///
+/// The leading 48 bits are the Unix timestamp in milliseconds, so
+/// v7 IDs sort by creation time — better index locality than v4 for
+/// database keys.
+pub fn uuid_v7() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let mut random = [0u8; 10];
+    fill_random_or_fallback(&mut random);
+    uuid_v7_from_parts(unix_ms, random)
+}
+
+/// Format a timestamp plus 10 caller-supplied random bytes as a UUIDv7
+pub fn uuid_v7_from_parts(unix_ms: u64, random: [u8; 10]) -> String {
+    let mut bytes = [0u8; 16];
+    bytes[..6].copy_from_slice(&unix_ms.to_be_bytes()[2..]);
+    bytes[6..].copy_from_slice(&random);
+    bytes[6] = (bytes[6] & 0x0F) | 0x70;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+    format_uuid(&bytes)
+}
+
+/// Standard 8-4-4-4-12 hex formatting
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let mut output = String::with_capacity(36);
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            output.push('-');
+        }
+        output.push_str(&format!("{:02x}", byte));
+    }
+    output
+}
+
+/// Time-seeded xorshift64, for targets without an OS entropy source
+///
+/// In production, this path should be backed by a proper CSPRNG.
+fn fill_fallback(buf: &mut [u8]) {
+    use std::time::SystemTime;
+
+    // Counter for uniqueness within the same nanosecond
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let seed = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+        ^ COUNTER.fetch_add(1, Ordering::Relaxed).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+    let mut state = seed;
+    for byte in buf {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        *byte = (state & 0xFF) as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_tokens() {
+        let hex = random_hex(32);
+        assert_eq!(hex.len(), 64);
+        assert!(hex.bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_ne!(random_hex(32), random_hex(32));
+
+        let token = random_base64url(32);
+        assert_eq!(token.len(), 43); // ceil(32 * 8 / 6), unpadded
+        assert!(!token.contains(['+', '/', '=']));
+    }
+
+    #[test]
+    fn test_uuid_v4_shape() {
+        let id = uuid_v4();
+        assert_eq!(id.len(), 36);
+        assert_eq!(id.as_bytes()[14], b'4'); // version nibble
+        assert!(matches!(id.as_bytes()[19], b'8' | b'9' | b'a' | b'b')); // variant
+        assert_ne!(uuid_v4(), uuid_v4());
+    }
+
+    #[test]
+    fn test_uuid_v7_time_ordering() {
+        let earlier = uuid_v7_from_parts(1_700_000_000_000, [0xFF; 10]);
+        let later = uuid_v7_from_parts(1_700_000_000_001, [0x00; 10]);
+        assert!(earlier < later);
+        assert_eq!(earlier.as_bytes()[14], b'7');
+    }
+
+    #[test]
+    fn test_uuid_v7_known_timestamp() {
+        // 0x017F22E279B0 = 2022-02-22T19:22:22Z from the RFC example
+        let id = uuid_v7_from_parts(0x017F_22E2_79B0, [0; 10]);
+        assert!(id.starts_with("017f22e2-79b0-7"));
+    }
+}
@@ -0,0 +1,218 @@
+//! scrypt password hashing (RFC 7914)
+//!
+//! Minimal implementation without external dependencies. Fallback
+//! for environments standardized on scrypt; argon2id is the default.
+
+use super::hmac::hmac_sha256;
+
+/// Cost parameters for [`scrypt`]
+///
+/// Defaults: N = 2^15 (32 MiB with r = 8), suitable for interactive
+/// logins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptParams {
+    /// CPU/memory cost exponent; N = 2^log_n
+    pub log_n: u32,
+    /// Block size factor
+    pub r: u32,
+    /// Parallelization factor
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        Self { log_n: 15, r: 8, p: 1 }
+    }
+}
+
+impl ScryptParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the cost exponent (N = 2^log_n, clamped to 1-24)
+    pub fn log_n(mut self, log_n: u32) -> Self {
+        self.log_n = log_n.clamp(1, 24);
+        self
+    }
+
+    /// Set the block size factor
+    pub fn r(mut self, r: u32) -> Self {
+        self.r = r.max(1);
+        self
+    }
+
+    /// Set the parallelization factor
+    pub fn p(mut self, p: u32) -> Self {
+        self.p = p.max(1);
+        self
+    }
+}
+
+/// Derive `out_len` bytes from a password and salt with scrypt
+pub fn scrypt(password: &[u8], salt: &[u8], params: ScryptParams, out_len: usize) -> Vec<u8> {
+    let n = 1usize << params.log_n.clamp(1, 24);
+    let r = params.r.max(1) as usize;
+    let p = params.p.max(1) as usize;
+    let block_bytes = 128 * r;
+
+    let mut blocks = pbkdf2_sha256(password, salt, 1, p * block_bytes);
+    for chunk in blocks.chunks_mut(block_bytes) {
+        ro_mix(chunk, n, r);
+    }
+    pbkdf2_sha256(password, &blocks, 1, out_len)
+}
+
+/// Sequential memory-hard mixing of one block (RFC 7914 §5)
+fn ro_mix(block: &mut [u8], n: usize, r: usize) {
+    let words = 32 * r;
+    let mut x = vec![0u32; words];
+    for (i, word) in x.iter_mut().enumerate() {
+        *word = u32::from_le_bytes(block[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+
+    let mut v = vec![0u32; words * n];
+    let mut y = vec![0u32; words];
+    for i in 0..n {
+        v[i * words..(i + 1) * words].copy_from_slice(&x);
+        block_mix(&x, &mut y, r);
+        std::mem::swap(&mut x, &mut y);
+    }
+    for _ in 0..n {
+        // Integerify: low word of the last 64-byte sub-block
+        let j = (x[words - 16] as usize) % n;
+        for (a, b) in x.iter_mut().zip(&v[j * words..(j + 1) * words]) {
+            *a ^= b;
+        }
+        block_mix(&x, &mut y, r);
+        std::mem::swap(&mut x, &mut y);
+    }
+
+    for (i, word) in x.iter().enumerate() {
+        block[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+    }
+}
+
+/// Shuffle 64-byte sub-blocks through Salsa20/8 (RFC 7914 §4)
+fn block_mix(input: &[u32], output: &mut [u32], r: usize) {
+    let mut x: [u32; 16] = input[input.len() - 16..].try_into().unwrap();
+    for i in 0..2 * r {
+        for (a, b) in x.iter_mut().zip(&input[i * 16..(i + 1) * 16]) {
+            *a ^= b;
+        }
+        salsa20_8(&mut x);
+        // Even sub-blocks first, then odd
+        let dest = if i % 2 == 0 { i / 2 } else { r + i / 2 };
+        output[dest * 16..(dest + 1) * 16].copy_from_slice(&x);
+    }
+}
+
+/// Salsa20/8 core permutation
+fn salsa20_8(block: &mut [u32; 16]) {
+    let input = *block;
+    for _ in 0..4 {
+        // Column round
+        block[4] ^= block[0].wrapping_add(block[12]).rotate_left(7);
+        block[8] ^= block[4].wrapping_add(block[0]).rotate_left(9);
+        block[12] ^= block[8].wrapping_add(block[4]).rotate_left(13);
+        block[0] ^= block[12].wrapping_add(block[8]).rotate_left(18);
+        block[9] ^= block[5].wrapping_add(block[1]).rotate_left(7);
+        block[13] ^= block[9].wrapping_add(block[5]).rotate_left(9);
+        block[1] ^= block[13].wrapping_add(block[9]).rotate_left(13);
+        block[5] ^= block[1].wrapping_add(block[13]).rotate_left(18);
+        block[14] ^= block[10].wrapping_add(block[6]).rotate_left(7);
+        block[2] ^= block[14].wrapping_add(block[10]).rotate_left(9);
+        block[6] ^= block[2].wrapping_add(block[14]).rotate_left(13);
+        block[10] ^= block[6].wrapping_add(block[2]).rotate_left(18);
+        block[3] ^= block[15].wrapping_add(block[11]).rotate_left(7);
+        block[7] ^= block[3].wrapping_add(block[15]).rotate_left(9);
+        block[11] ^= block[7].wrapping_add(block[3]).rotate_left(13);
+        block[15] ^= block[11].wrapping_add(block[7]).rotate_left(18);
+        // Row round
+        block[1] ^= block[0].wrapping_add(block[3]).rotate_left(7);
+        block[2] ^= block[1].wrapping_add(block[0]).rotate_left(9);
+        block[3] ^= block[2].wrapping_add(block[1]).rotate_left(13);
+        block[0] ^= block[3].wrapping_add(block[2]).rotate_left(18);
+        block[6] ^= block[5].wrapping_add(block[4]).rotate_left(7);
+        block[7] ^= block[6].wrapping_add(block[5]).rotate_left(9);
+        block[4] ^= block[7].wrapping_add(block[6]).rotate_left(13);
+        block[5] ^= block[4].wrapping_add(block[7]).rotate_left(18);
+        block[11] ^= block[10].wrapping_add(block[9]).rotate_left(7);
+        block[8] ^= block[11].wrapping_add(block[10]).rotate_left(9);
+        block[9] ^= block[8].wrapping_add(block[11]).rotate_left(13);
+        block[10] ^= block[9].wrapping_add(block[8]).rotate_left(18);
+        block[12] ^= block[15].wrapping_add(block[14]).rotate_left(7);
+        block[13] ^= block[12].wrapping_add(block[15]).rotate_left(9);
+        block[14] ^= block[13].wrapping_add(block[12]).rotate_left(13);
+        block[15] ^= block[14].wrapping_add(block[13]).rotate_left(18);
+    }
+    for (word, original) in block.iter_mut().zip(&input) {
+        *word = word.wrapping_add(*original);
+    }
+}
+
+/// PBKDF2-HMAC-SHA256 (RFC 2898); scrypt only ever uses c = 1
+pub fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32, dk_len: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(dk_len.div_ceil(32) * 32);
+    let mut block_index = 1u32;
+    while output.len() < dk_len {
+        let mut message = Vec::with_capacity(salt.len() + 4);
+        message.extend_from_slice(salt);
+        message.extend_from_slice(&block_index.to_be_bytes());
+        let mut u = hmac_sha256(password, &message);
+        let mut t = u;
+        for _ in 1..iterations {
+            u = hmac_sha256(password, &u);
+            for (a, b) in t.iter_mut().zip(&u) {
+                *a ^= b;
+            }
+        }
+        output.extend_from_slice(&t);
+        block_index += 1;
+    }
+    output.truncate(dk_len);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_rfc7914_empty() {
+        // RFC 7914 §12, first vector (N=16, r=1, p=1)
+        let dk = scrypt(b"", b"", ScryptParams::new().log_n(4).r(1).p(1), 64);
+        assert_eq!(
+            hex(&dk),
+            "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+             fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"
+        );
+    }
+
+    #[test]
+    fn test_rfc7914_password_nacl() {
+        // RFC 7914 §12, second vector (N=1024, r=8, p=16)
+        let dk = scrypt(
+            b"password",
+            b"NaCl",
+            ScryptParams::new().log_n(10).r(8).p(16),
+            64,
+        );
+        assert_eq!(
+            hex(&dk),
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+             2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+        );
+    }
+
+    #[test]
+    fn test_pbkdf2_salt_sensitivity() {
+        let a = pbkdf2_sha256(b"password", b"salt-one", 1, 32);
+        let b = pbkdf2_sha256(b"password", b"salt-two", 1, 32);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,268 @@
+//! HOTP and TOTP one-time passwords (RFC 4226, RFC 6238)
+//!
+//! HMAC-based one-time passwords for 2FA endpoints, without external
+//! dependencies. Supports HMAC-SHA1 (the authenticator-app default)
+//! and HMAC-SHA256, a configurable drift window for clock skew, and
+//! otpauth:// provisioning URIs for QR-code enrollment.
+
+use super::base32::base32_encode;
+use super::constant_time_eq;
+use super::hmac::{hmac_sha1, hmac_sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// HMAC algorithm used for OTP generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtpAlgorithm {
+    /// HMAC-SHA1 (RFC 6238 default, what authenticator apps expect)
+    Sha1,
+    /// HMAC-SHA256
+    Sha256,
+}
+
+impl OtpAlgorithm {
+    /// Algorithm name as spelled in otpauth:// URIs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OtpAlgorithm::Sha1 => "SHA1",
+            OtpAlgorithm::Sha256 => "SHA256",
+        }
+    }
+}
+
+/// Generate an HOTP code for a counter value (RFC 4226)
+///
+/// `digits` is clamped to 6-10; authenticator apps use 6.
+pub fn hotp(secret: &[u8], counter: u64, algorithm: OtpAlgorithm, digits: u32) -> String {
+    let message = counter.to_be_bytes();
+    let digest: Vec<u8> = match algorithm {
+        OtpAlgorithm::Sha1 => hmac_sha1(secret, &message).to_vec(),
+        OtpAlgorithm::Sha256 => hmac_sha256(secret, &message).to_vec(),
+    };
+
+    // Dynamic truncation: the low nibble of the last byte picks a
+    // 31-bit big-endian word out of the digest
+    let offset = (digest[digest.len() - 1] & 0x0F) as usize;
+    let value = ((digest[offset] as u32 & 0x7F) << 24)
+        | ((digest[offset + 1] as u32) << 16)
+        | ((digest[offset + 2] as u32) << 8)
+        | digest[offset + 3] as u32;
+
+    let digits = digits.clamp(6, 10);
+    let code = value as u64 % 10u64.pow(digits);
+    format!("{:0width$}", code, width = digits as usize)
+}
+
+/// TOTP generator/verifier for one shared secret (RFC 6238)
+///
+/// Defaults match what authenticator apps expect: SHA-1, 6 digits,
+/// 30-second steps, and one step of drift tolerance either way.
+///
+/// # Example
+/// ```
+/// use gust_core::crypto::Totp;
+///
+/// let totp = Totp::new(b"12345678901234567890".to_vec());
+/// let code = totp.generate();
+/// assert!(totp.verify(&code));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Totp {
+    secret: Vec<u8>,
+    algorithm: OtpAlgorithm,
+    digits: u32,
+    period: u64,
+    skew: u64,
+}
+
+impl Totp {
+    pub fn new(secret: Vec<u8>) -> Self {
+        Self {
+            secret,
+            algorithm: OtpAlgorithm::Sha1,
+            digits: 6,
+            period: 30,
+            skew: 1,
+        }
+    }
+
+    /// Set the HMAC algorithm (default: SHA-1)
+    pub fn algorithm(mut self, algorithm: OtpAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Set the code length (default: 6, clamped to 6-10)
+    pub fn digits(mut self, digits: u32) -> Self {
+        self.digits = digits.clamp(6, 10);
+        self
+    }
+
+    /// Set the time step in seconds (default: 30)
+    pub fn period(mut self, seconds: u64) -> Self {
+        self.period = seconds.max(1);
+        self
+    }
+
+    /// Set the drift window: codes up to this many steps in the past
+    /// or future are accepted (default: 1)
+    pub fn skew(mut self, steps: u64) -> Self {
+        self.skew = steps;
+        self
+    }
+
+    /// Generate the code for a given Unix timestamp
+    pub fn generate_at(&self, unix_secs: u64) -> String {
+        hotp(
+            &self.secret,
+            unix_secs / self.period,
+            self.algorithm,
+            self.digits,
+        )
+    }
+
+    /// Generate the code for the current time
+    pub fn generate(&self) -> String {
+        self.generate_at(now_unix_secs())
+    }
+
+    /// Verify a code against a given Unix timestamp, accepting the
+    /// drift window either way
+    ///
+    /// Every candidate step is checked with a constant-time compare so
+    /// timing does not leak how close a guess was.
+    pub fn verify_at(&self, code: &str, unix_secs: u64) -> bool {
+        let step = unix_secs / self.period;
+        let mut matched = false;
+        for candidate in step.saturating_sub(self.skew)..=step.saturating_add(self.skew) {
+            let expected = hotp(&self.secret, candidate, self.algorithm, self.digits);
+            matched |= constant_time_eq(code.as_bytes(), expected.as_bytes());
+        }
+        matched
+    }
+
+    /// Verify a code against the current time
+    pub fn verify(&self, code: &str) -> bool {
+        self.verify_at(code, now_unix_secs())
+    }
+
+    /// Build the otpauth:// provisioning URI authenticator apps scan
+    ///
+    /// # Example
+    /// ```
+    /// use gust_core::crypto::Totp;
+    ///
+    /// let totp = Totp::new(b"12345678901234567890".to_vec());
+    /// let uri = totp.provisioning_uri("Example", "alice@example.com");
+    /// assert!(uri.starts_with("otpauth://totp/Example:alice%40example.com?secret="));
+    /// ```
+    pub fn provisioning_uri(&self, issuer: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm={}&digits={}&period={}",
+            uri_escape(issuer),
+            uri_escape(account),
+            base32_encode(&self.secret),
+            uri_escape(issuer),
+            self.algorithm.as_str(),
+            self.digits,
+            self.period,
+        )
+    }
+}
+
+/// Seconds since the Unix epoch
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Percent-encode everything outside the URI unreserved set
+fn uri_escape(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                output.push(byte as char)
+            }
+            _ => output.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4226/6238 shared secrets ("12345678901234567890" repeated
+    // to the digest length for SHA-256)
+    const SECRET_SHA1: &[u8] = b"12345678901234567890";
+    const SECRET_SHA256: &[u8] = b"12345678901234567890123456789012";
+
+    #[test]
+    fn test_rfc4226_hotp_vectors() {
+        // RFC 4226 Appendix D
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(
+                hotp(SECRET_SHA1, counter as u64, OtpAlgorithm::Sha1, 6),
+                *code
+            );
+        }
+    }
+
+    #[test]
+    fn test_rfc6238_totp_vectors() {
+        // RFC 6238 Appendix B (8-digit codes)
+        let sha1 = Totp::new(SECRET_SHA1.to_vec()).digits(8);
+        assert_eq!(sha1.generate_at(59), "94287082");
+        assert_eq!(sha1.generate_at(1111111109), "07081804");
+        assert_eq!(sha1.generate_at(20000000000), "65353130");
+
+        let sha256 = Totp::new(SECRET_SHA256.to_vec())
+            .algorithm(OtpAlgorithm::Sha256)
+            .digits(8);
+        assert_eq!(sha256.generate_at(59), "46119246");
+        assert_eq!(sha256.generate_at(1111111109), "68084774");
+    }
+
+    #[test]
+    fn test_verify_drift_window() {
+        let totp = Totp::new(SECRET_SHA1.to_vec());
+        let now = 1_700_000_015;
+        let code = totp.generate_at(now);
+
+        // Accepted one step either side, rejected beyond the window
+        assert!(totp.verify_at(&code, now));
+        assert!(totp.verify_at(&code, now - 30));
+        assert!(totp.verify_at(&code, now + 30));
+        assert!(!totp.verify_at(&code, now + 90));
+
+        // skew(0) only accepts the exact step
+        let strict = Totp::new(SECRET_SHA1.to_vec()).skew(0);
+        let code = strict.generate_at(now);
+        assert!(strict.verify_at(&code, now));
+        assert!(!strict.verify_at(&code, now + 30));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_code() {
+        let totp = Totp::new(SECRET_SHA1.to_vec());
+        assert!(!totp.verify_at("000000", 1_700_000_015));
+        assert!(!totp.verify_at("12345", 1_700_000_015)); // wrong length
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        let totp = Totp::new(b"foobar".to_vec());
+        assert_eq!(
+            totp.provisioning_uri("My App", "alice@example.com"),
+            "otpauth://totp/My%20App:alice%40example.com?secret=MZXW6YTBOI\
+             &issuer=My%20App&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! DNS-based service discovery for upstream pools
+//!
+//! A [`ServiceSet`] defines an upstream by DNS name instead of a fixed
+//! address list: SRV records (or plain A/AAAA records with a fallback
+//! port) are re-resolved periodically, members that disappear from DNS
+//! are drained from the connection pool, and members that appear start
+//! receiving traffic — so Kubernetes headless services work without
+//! manual configuration.
+
+use crate::dns::Resolver;
+use crate::upstream::UpstreamPool;
+use crate::Result;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A DNS-defined set of upstream members
+///
+/// [`refresh`](Self::refresh) re-resolves the name and reconciles the
+/// member list against the pool; [`spawn_refresh`](Self::spawn_refresh)
+/// does so on an interval. [`pick`](Self::pick) round-robins over the
+/// current members.
+pub struct ServiceSet {
+    service: String,
+    /// Port used for plain A/AAAA members (SRV records carry their own)
+    fallback_port: u16,
+    /// Scheme prefix for pool keys (default `http`)
+    scheme: String,
+    resolver: Arc<Resolver>,
+    pool: Arc<UpstreamPool>,
+    members: Mutex<Vec<SocketAddr>>,
+    cursor: AtomicUsize,
+    /// Optional consistent-hash ring kept in sync with the members
+    ring: Option<crate::balance::HashRing>,
+    /// Optional health checker; unhealthy members are skipped by pick
+    health: Option<Arc<crate::outlier::HealthChecker>>,
+}
+
+impl ServiceSet {
+    /// Discover members of `service`; A/AAAA members connect on
+    /// `fallback_port` unless SRV records supply ports
+    pub fn new(
+        service: impl Into<String>,
+        fallback_port: u16,
+        resolver: Arc<Resolver>,
+        pool: Arc<UpstreamPool>,
+    ) -> Self {
+        Self {
+            service: service.into(),
+            fallback_port,
+            scheme: "http".to_string(),
+            resolver,
+            pool,
+            members: Mutex::new(Vec::new()),
+            cursor: AtomicUsize::new(0),
+            ring: None,
+            health: None,
+        }
+    }
+
+    /// Keep a consistent-hash ring's members in sync with each refresh
+    /// (see [`crate::balance::HashRing`])
+    pub fn ring(mut self, ring: crate::balance::HashRing) -> Self {
+        self.ring = Some(ring);
+        self
+    }
+
+    /// Pause traffic to members the checker marks unhealthy; the
+    /// checker's probed set follows each refresh (see
+    /// [`crate::outlier::HealthChecker`])
+    pub fn health(mut self, checker: Arc<crate::outlier::HealthChecker>) -> Self {
+        self.health = Some(checker);
+        self
+    }
+
+    /// Scheme used when deriving pool keys (e.g. `https`)
+    pub fn scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.scheme = scheme.into();
+        self
+    }
+
+    /// Pool key for a member, matching the keys callers dial with
+    pub fn member_key(&self, addr: SocketAddr) -> String {
+        format!("{}://{}", self.scheme, addr)
+    }
+
+    /// Current members (last successful refresh)
+    pub fn members(&self) -> Vec<SocketAddr> {
+        self.members.lock().map(|m| m.clone()).unwrap_or_default()
+    }
+
+    /// Next member, round-robin, skipping members an attached health
+    /// checker marks unhealthy; `None` while no member is eligible
+    pub fn pick(&self) -> Option<SocketAddr> {
+        let members = self.members.lock().ok()?;
+        if members.is_empty() {
+            return None;
+        }
+        for _ in 0..members.len() {
+            let index = self.cursor.fetch_add(1, Ordering::Relaxed) % members.len();
+            let member = members[index];
+            match &self.health {
+                Some(checker) if !checker.is_healthy(member) => continue,
+                _ => return Some(member),
+            }
+        }
+        None
+    }
+
+    /// Re-resolve the service and reconcile members with the pool
+    ///
+    /// Removed members are drained (idle connections dropped, running
+    /// requests allowed to finish); added or returning members are
+    /// restored. A resolution failure keeps the previous member list
+    /// so a flaky DNS server cannot empty a healthy set.
+    pub async fn refresh(&self) -> Result<()> {
+        let fresh = self.resolve_members().await?;
+
+        let previous = self.members();
+        for member in &previous {
+            if !fresh.contains(member) {
+                self.pool.drain(&self.member_key(*member));
+            }
+        }
+        for member in &fresh {
+            if !previous.contains(member) {
+                self.pool.restore(&self.member_key(*member));
+            }
+        }
+
+        if let Some(ring) = &self.ring {
+            ring.set_members(fresh.clone());
+        }
+        if let Some(checker) = &self.health {
+            checker.set_members(fresh.clone());
+        }
+        if let Ok(mut members) = self.members.lock() {
+            *members = fresh;
+        }
+        Ok(())
+    }
+
+    /// Refresh on an interval until the handle is aborted or the set
+    /// is dropped
+    pub fn spawn_refresh(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let set = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval.max(Duration::from_millis(100)));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                // Failures keep the previous members; retried next tick
+                let _ = set.refresh().await;
+            }
+        })
+    }
+
+    /// SRV records when present (each target resolved to addresses),
+    /// otherwise plain A/AAAA records on the fallback port
+    async fn resolve_members(&self) -> Result<Vec<SocketAddr>> {
+        let mut members = Vec::new();
+
+        if let Ok(srv_records) = self.resolver.resolve_srv(&self.service).await {
+            for record in srv_records {
+                if let Ok(addrs) = self.resolver.resolve(&record.target).await {
+                    for addr in addrs {
+                        let member = SocketAddr::new(addr, record.port);
+                        if !members.contains(&member) {
+                            members.push(member);
+                        }
+                    }
+                }
+            }
+        }
+
+        if members.is_empty() {
+            for addr in self.resolver.resolve(&self.service).await? {
+                let member = SocketAddr::new(addr, self.fallback_port);
+                if !members.contains(&member) {
+                    members.push(member);
+                }
+            }
+        }
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::DnsConfig;
+    use crate::upstream::UpstreamConfig;
+    use std::net::IpAddr;
+
+    /// DNS server answering every query with the given A records and
+    /// no SRV records
+    async fn a_record_server(ips: Vec<IpAddr>) -> SocketAddr {
+        let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            while let Ok((len, peer)) = socket.recv_from(&mut buf).await {
+                let query = &buf[..len];
+                let qtype = u16::from_be_bytes([buf[len - 4], buf[len - 3]]);
+                let mut packet = query.to_vec();
+                packet[2] = 0x81;
+                packet[3] = 0x80;
+                // A queries get the records; AAAA/SRV get empty answers
+                let answers: Vec<IpAddr> = if qtype == 1 { ips.clone() } else { Vec::new() };
+                packet[6..8].copy_from_slice(&(answers.len() as u16).to_be_bytes());
+                for ip in answers {
+                    let IpAddr::V4(v4) = ip else { continue };
+                    packet.extend_from_slice(&[0xC0, 0x0C]);
+                    packet.extend_from_slice(&1u16.to_be_bytes());
+                    packet.extend_from_slice(&1u16.to_be_bytes());
+                    packet.extend_from_slice(&5u32.to_be_bytes());
+                    packet.extend_from_slice(&4u16.to_be_bytes());
+                    packet.extend_from_slice(&v4.octets());
+                }
+                let _ = socket.send_to(&packet, peer).await;
+            }
+        });
+        addr
+    }
+
+    fn service_set(dns: SocketAddr) -> ServiceSet {
+        let resolver = Arc::new(Resolver::new(
+            DnsConfig::default()
+                .servers(vec![dns])
+                .timeout(Duration::from_millis(300))
+                // Force a fresh lookup on every refresh
+                .max_ttl(Duration::ZERO),
+        ));
+        let pool = Arc::new(UpstreamPool::new(UpstreamConfig::default()));
+        ServiceSet::new("backend.test", 8080, resolver, pool)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_tracks_membership_changes() {
+        let dns = a_record_server(vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()])
+            .await;
+        let set = service_set(dns);
+
+        set.refresh().await.unwrap();
+        assert_eq!(
+            set.members(),
+            vec![
+                "10.0.0.1:8080".parse::<SocketAddr>().unwrap(),
+                "10.0.0.2:8080".parse::<SocketAddr>().unwrap(),
+            ]
+        );
+
+        // Round-robin alternates between the members
+        let first = set.pick().unwrap();
+        let second = set.pick().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(set.pick().unwrap(), first);
+
+        // One member leaves, another joins
+        let dns = a_record_server(vec!["10.0.0.2".parse().unwrap(), "10.0.0.3".parse().unwrap()])
+            .await;
+        let set = ServiceSet {
+            resolver: Arc::new(Resolver::new(
+                DnsConfig::default()
+                    .servers(vec![dns])
+                    .timeout(Duration::from_millis(300))
+                    .max_ttl(Duration::ZERO),
+            )),
+            members: Mutex::new(set.members()),
+            ..set
+        };
+        set.refresh().await.unwrap();
+        assert_eq!(
+            set.members(),
+            vec![
+                "10.0.0.2:8080".parse::<SocketAddr>().unwrap(),
+                "10.0.0.3:8080".parse::<SocketAddr>().unwrap(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pick_skips_unhealthy_members() {
+        use crate::outlier::{HealthCheckConfig, HealthChecker};
+
+        let dns = a_record_server(vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()])
+            .await;
+        let checker = Arc::new(HealthChecker::new(
+            HealthCheckConfig::new().unhealthy_threshold(1),
+        ));
+        let set = service_set(dns).health(Arc::clone(&checker));
+        set.refresh().await.unwrap();
+
+        let sick: SocketAddr = "10.0.0.1:8080".parse().unwrap();
+        let well: SocketAddr = "10.0.0.2:8080".parse().unwrap();
+        checker.record(sick, false);
+        for _ in 0..4 {
+            assert_eq!(set.pick(), Some(well));
+        }
+
+        // Both unhealthy: nothing is eligible
+        checker.record(well, false);
+        assert_eq!(set.pick(), None);
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_keeps_previous_members() {
+        let dns = a_record_server(vec!["10.0.0.1".parse().unwrap()]).await;
+        let set = service_set(dns);
+        set.refresh().await.unwrap();
+        let before = set.members();
+        assert_eq!(before.len(), 1);
+
+        // Point the set at a dead DNS server; refresh fails but the
+        // member list survives
+        let set = ServiceSet {
+            resolver: Arc::new(Resolver::new(
+                DnsConfig::default()
+                    .servers(vec!["127.0.0.1:1".parse().unwrap()])
+                    .timeout(Duration::from_millis(50))
+                    .max_ttl(Duration::ZERO),
+            )),
+            members: Mutex::new(before.clone()),
+            ..set
+        };
+        assert!(set.refresh().await.is_err());
+        assert_eq!(set.members(), before);
+    }
+}
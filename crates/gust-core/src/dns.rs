@@ -0,0 +1,607 @@
+//! Async DNS resolver with TTL caching and happy-eyeballs dialing
+//!
+//! Speaks the DNS wire format directly over UDP so outbound connections
+//! (the CLI proxy, the S3 client) never fall back to blocking std
+//! resolution. A and AAAA records are queried concurrently, cached for
+//! their TTL, and [`Resolver::connect`] dials the candidate addresses
+//! with staggered happy-eyeballs attempts (RFC 8305), interleaving
+//! address families so one broken family cannot stall the dial.
+
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::net::{TcpStream, UdpSocket};
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const TYPE_SRV: u16 = 33;
+const CLASS_IN: u16 = 1;
+
+/// One SRV record (RFC 2782)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrvRecord {
+    pub priority: u16,
+    pub weight: u16,
+    pub port: u16,
+    /// Target hostname, to be resolved to addresses separately
+    pub target: String,
+    pub ttl: u32,
+}
+
+/// Resolver configuration
+#[derive(Debug, Clone)]
+pub struct DnsConfig {
+    /// DNS servers tried in order (default: `/etc/resolv.conf`, falling
+    /// back to well-known public resolvers)
+    pub servers: Vec<SocketAddr>,
+    /// Per-server query timeout
+    pub timeout: Duration,
+    /// Cap on how long a record is cached, regardless of its TTL
+    pub max_ttl: Duration,
+    /// Delay between staggered happy-eyeballs connection attempts
+    pub connect_delay: Duration,
+}
+
+impl Default for DnsConfig {
+    fn default() -> Self {
+        Self {
+            servers: system_servers(),
+            timeout: Duration::from_secs(2),
+            max_ttl: Duration::from_secs(300),
+            connect_delay: Duration::from_millis(250),
+        }
+    }
+}
+
+impl DnsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the server list
+    pub fn servers(mut self, servers: Vec<SocketAddr>) -> Self {
+        self.servers = servers;
+        self
+    }
+
+    /// Per-server query timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Cap cached TTLs
+    pub fn max_ttl(mut self, max_ttl: Duration) -> Self {
+        self.max_ttl = max_ttl;
+        self
+    }
+}
+
+/// Read nameservers from `/etc/resolv.conf`, falling back to public
+/// resolvers when none are configured
+fn system_servers() -> Vec<SocketAddr> {
+    let mut servers = Vec::new();
+    if let Ok(contents) = std::fs::read_to_string("/etc/resolv.conf") {
+        for line in contents.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("nameserver") {
+                if let Ok(ip) = rest.trim().parse::<IpAddr>() {
+                    servers.push(SocketAddr::new(ip, 53));
+                }
+            }
+        }
+    }
+    if servers.is_empty() {
+        servers.push("1.1.1.1:53".parse().unwrap());
+        servers.push("8.8.8.8:53".parse().unwrap());
+    }
+    servers
+}
+
+struct CacheEntry {
+    addrs: Vec<IpAddr>,
+    expires: Instant,
+}
+
+/// Async caching DNS resolver
+///
+/// Cheap to share behind an `Arc`; [`default_resolver`] provides a
+/// process-wide instance with default configuration.
+pub struct Resolver {
+    config: DnsConfig,
+    cache: Mutex<HashMap<String, CacheEntry>>,
+    next_id: AtomicU32,
+}
+
+impl Resolver {
+    pub fn new(config: DnsConfig) -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        Self {
+            config,
+            cache: Mutex::new(HashMap::new()),
+            next_id: AtomicU32::new(seed),
+        }
+    }
+
+    /// Resolve a hostname to its addresses (A and AAAA, queried
+    /// concurrently), consulting the TTL cache first
+    pub async fn resolve(&self, host: &str) -> Result<Vec<IpAddr>> {
+        // Literal addresses never hit the network
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![ip]);
+        }
+
+        let key = host.to_ascii_lowercase();
+        if let Ok(cache) = self.cache.lock() {
+            if let Some(entry) = cache.get(&key) {
+                if entry.expires > Instant::now() {
+                    return Ok(entry.addrs.clone());
+                }
+            }
+        }
+
+        let (v4, v6) = tokio::join!(self.lookup(host, TYPE_A), self.lookup(host, TYPE_AAAA));
+        let mut addrs = Vec::new();
+        let mut ttl = self.config.max_ttl.as_secs().min(u32::MAX as u64) as u32;
+        for result in [v6, v4] {
+            if let Ok(answers) = &result {
+                for (ip, answer_ttl) in answers {
+                    ttl = ttl.min((*answer_ttl).max(1));
+                    addrs.push(*ip);
+                }
+            }
+        }
+        if addrs.is_empty() {
+            return Err(Error::Dns(format!("no addresses found for '{}'", host)));
+        }
+
+        if let Ok(mut cache) = self.cache.lock() {
+            // Keep the cache bounded by evicting expired entries first
+            if cache.len() >= 1024 {
+                let now = Instant::now();
+                cache.retain(|_, entry| entry.expires > now);
+            }
+            cache.insert(
+                key,
+                CacheEntry {
+                    addrs: addrs.clone(),
+                    expires: Instant::now() + Duration::from_secs(ttl as u64),
+                },
+            );
+        }
+        Ok(addrs)
+    }
+
+    /// Resolve and dial with happy-eyeballs: candidate addresses are
+    /// interleaved by family and attempted with a staggered delay; the
+    /// first connection to complete wins and the rest are aborted
+    pub async fn connect(&self, host: &str, port: u16) -> Result<TcpStream> {
+        let addrs = self.resolve(host).await?;
+        let ordered = interleave_addresses(&addrs);
+
+        let mut attempts = tokio::task::JoinSet::new();
+        for (index, addr) in ordered.into_iter().enumerate() {
+            let delay = self.config.connect_delay * index as u32;
+            attempts.spawn(async move {
+                tokio::time::sleep(delay).await;
+                TcpStream::connect(SocketAddr::new(addr, port)).await
+            });
+        }
+
+        let mut last_error = None;
+        while let Some(joined) = attempts.join_next().await {
+            match joined {
+                Ok(Ok(stream)) => return Ok(stream),
+                Ok(Err(e)) => last_error = Some(e),
+                Err(_) => {}
+            }
+        }
+        match last_error {
+            Some(e) => Err(Error::Io(e)),
+            None => Err(Error::Dns(format!("no addresses found for '{}'", host))),
+        }
+    }
+
+    /// Query SRV records for a service name (e.g.
+    /// `_http._tcp.backend.default.svc.cluster.local`), sorted by
+    /// priority then descending weight
+    pub async fn resolve_srv(&self, name: &str) -> Result<Vec<SrvRecord>> {
+        let id = (self.next_id.fetch_add(1, Ordering::Relaxed) & 0xFFFF) as u16;
+        let query = encode_query(id, name, TYPE_SRV)?;
+        let packet = self.query_servers(&query).await?;
+        let mut records = parse_srv_answers(&packet, id).map_err(Error::Dns)?;
+        records.sort_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then(b.weight.cmp(&a.weight))
+        });
+        Ok(records)
+    }
+
+    /// Query each configured server in order until one answers
+    async fn lookup(&self, host: &str, qtype: u16) -> Result<Vec<(IpAddr, u32)>> {
+        let id = (self.next_id.fetch_add(1, Ordering::Relaxed) & 0xFFFF) as u16;
+        let query = encode_query(id, host, qtype)?;
+        let packet = self.query_servers(&query).await?;
+        parse_answers(&packet, id).map_err(Error::Dns)
+    }
+
+    async fn query_servers(&self, query: &[u8]) -> Result<Vec<u8>> {
+        let mut last_error = None;
+        for server in &self.config.servers {
+            match self.query_server(*server, query).await {
+                Ok(packet) => return Ok(packet),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| Error::Dns("no DNS servers configured".to_string())))
+    }
+
+    async fn query_server(&self, server: SocketAddr, query: &[u8]) -> Result<Vec<u8>> {
+        let bind: SocketAddr = if server.is_ipv4() {
+            "0.0.0.0:0".parse().unwrap()
+        } else {
+            "[::]:0".parse().unwrap()
+        };
+        let socket = UdpSocket::bind(bind).await?;
+        socket.send_to(query, server).await?;
+
+        let mut buf = [0u8; 1500];
+        let len = tokio::time::timeout(self.config.timeout, socket.recv(&mut buf))
+            .await
+            .map_err(|_| Error::Dns(format!("DNS query to {} timed out", server)))??;
+        Ok(buf[..len].to_vec())
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new(DnsConfig::default())
+    }
+}
+
+/// Process-wide resolver with default configuration
+pub fn default_resolver() -> &'static Resolver {
+    static RESOLVER: OnceLock<Resolver> = OnceLock::new();
+    RESOLVER.get_or_init(Resolver::default)
+}
+
+/// Interleave address families, IPv6 first (RFC 8305 §4)
+pub fn interleave_addresses(addrs: &[IpAddr]) -> Vec<IpAddr> {
+    let v6: Vec<IpAddr> = addrs.iter().filter(|a| a.is_ipv6()).copied().collect();
+    let v4: Vec<IpAddr> = addrs.iter().filter(|a| a.is_ipv4()).copied().collect();
+    let mut ordered = Vec::with_capacity(addrs.len());
+    let mut v6 = v6.into_iter();
+    let mut v4 = v4.into_iter();
+    loop {
+        match (v6.next(), v4.next()) {
+            (None, None) => break,
+            (a, b) => {
+                ordered.extend(a);
+                ordered.extend(b);
+            }
+        }
+    }
+    ordered
+}
+
+// ============================================================================
+// Wire format
+// ============================================================================
+
+/// Encode a single-question query with recursion desired
+fn encode_query(id: u16, name: &str, qtype: u16) -> Result<Vec<u8>> {
+    if name.is_empty() || name.len() > 253 {
+        return Err(Error::Dns(format!("invalid hostname '{}'", name)));
+    }
+
+    let mut packet = Vec::with_capacity(17 + name.len());
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&0x0100u16.to_be_bytes()); // RD
+    packet.extend_from_slice(&1u16.to_be_bytes()); // QDCOUNT
+    packet.extend_from_slice(&[0; 6]); // AN/NS/ARCOUNT
+
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(Error::Dns(format!("invalid hostname '{}'", name)));
+        }
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+
+    packet.extend_from_slice(&qtype.to_be_bytes());
+    packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(packet)
+}
+
+/// Skip over a (possibly compressed) name, returning the next offset
+fn skip_name(packet: &[u8], mut offset: usize) -> std::result::Result<usize, String> {
+    loop {
+        let len = *packet.get(offset).ok_or("truncated name")? as usize;
+        if len == 0 {
+            return Ok(offset + 1);
+        }
+        if len & 0xC0 == 0xC0 {
+            // Compression pointer terminates the name
+            return Ok(offset + 2);
+        }
+        offset += 1 + len;
+    }
+}
+
+fn read_u16(packet: &[u8], offset: usize) -> std::result::Result<u16, String> {
+    let bytes = packet
+        .get(offset..offset + 2)
+        .ok_or("truncated response")?;
+    Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+/// Decode a (possibly compressed) name into its dotted form
+fn decode_name(packet: &[u8], mut offset: usize) -> std::result::Result<String, String> {
+    let mut name = String::new();
+    let mut jumps = 0;
+    loop {
+        let len = *packet.get(offset).ok_or("truncated name")? as usize;
+        if len == 0 {
+            return Ok(name);
+        }
+        if len & 0xC0 == 0xC0 {
+            jumps += 1;
+            if jumps > 16 {
+                return Err("compression pointer loop".to_string());
+            }
+            let low = *packet.get(offset + 1).ok_or("truncated pointer")? as usize;
+            offset = ((len & 0x3F) << 8) | low;
+            continue;
+        }
+        let label = packet
+            .get(offset + 1..offset + 1 + len)
+            .ok_or("truncated label")?;
+        if !name.is_empty() {
+            name.push('.');
+        }
+        name.push_str(&String::from_utf8_lossy(label));
+        offset += 1 + len;
+    }
+}
+
+/// Validate a response header and skip to the answer section,
+/// returning (offset, answer count)
+fn answer_section(packet: &[u8], id: u16) -> std::result::Result<(usize, usize), String> {
+    if packet.len() < 12 {
+        return Err("response too short".to_string());
+    }
+    if read_u16(packet, 0)? != id {
+        return Err("response ID mismatch".to_string());
+    }
+    let flags = read_u16(packet, 2)?;
+    if flags & 0x8000 == 0 {
+        return Err("not a response".to_string());
+    }
+    let rcode = flags & 0x000F;
+    if rcode != 0 {
+        return Err(format!("server returned RCODE {}", rcode));
+    }
+    let qdcount = read_u16(packet, 4)? as usize;
+    let ancount = read_u16(packet, 6)? as usize;
+
+    let mut offset = 12;
+    for _ in 0..qdcount {
+        offset = skip_name(packet, offset)? + 4;
+    }
+    Ok((offset, ancount))
+}
+
+/// Parse address answers out of a response packet
+fn parse_answers(packet: &[u8], id: u16) -> std::result::Result<Vec<(IpAddr, u32)>, String> {
+    let (mut offset, ancount) = answer_section(packet, id)?;
+    let mut answers = Vec::new();
+    for _ in 0..ancount {
+        offset = skip_name(packet, offset)?;
+        let rtype = read_u16(packet, offset)?;
+        let ttl_bytes = packet
+            .get(offset + 4..offset + 8)
+            .ok_or("truncated answer")?;
+        let ttl = u32::from_be_bytes([ttl_bytes[0], ttl_bytes[1], ttl_bytes[2], ttl_bytes[3]]);
+        let rdlen = read_u16(packet, offset + 8)? as usize;
+        let rdata = packet
+            .get(offset + 10..offset + 10 + rdlen)
+            .ok_or("truncated answer")?;
+
+        match (rtype, rdlen) {
+            (TYPE_A, 4) => {
+                let ip: [u8; 4] = rdata.try_into().unwrap();
+                answers.push((IpAddr::from(ip), ttl));
+            }
+            (TYPE_AAAA, 16) => {
+                let ip: [u8; 16] = rdata.try_into().unwrap();
+                answers.push((IpAddr::from(ip), ttl));
+            }
+            // CNAMEs and anything else are skipped; the resolver
+            // follows the address records appended after them
+            _ => {}
+        }
+        offset += 10 + rdlen;
+    }
+    Ok(answers)
+}
+
+/// Parse SRV answers out of a response packet
+fn parse_srv_answers(packet: &[u8], id: u16) -> std::result::Result<Vec<SrvRecord>, String> {
+    let (mut offset, ancount) = answer_section(packet, id)?;
+    let mut records = Vec::new();
+    for _ in 0..ancount {
+        offset = skip_name(packet, offset)?;
+        let rtype = read_u16(packet, offset)?;
+        let ttl_bytes = packet
+            .get(offset + 4..offset + 8)
+            .ok_or("truncated answer")?;
+        let ttl = u32::from_be_bytes([ttl_bytes[0], ttl_bytes[1], ttl_bytes[2], ttl_bytes[3]]);
+        let rdlen = read_u16(packet, offset + 8)? as usize;
+        let rdata_start = offset + 10;
+        if packet.len() < rdata_start + rdlen {
+            return Err("truncated answer".to_string());
+        }
+
+        if rtype == TYPE_SRV && rdlen >= 7 {
+            records.push(SrvRecord {
+                priority: read_u16(packet, rdata_start)?,
+                weight: read_u16(packet, rdata_start + 2)?,
+                port: read_u16(packet, rdata_start + 4)?,
+                target: decode_name(packet, rdata_start + 6)?,
+                ttl,
+            });
+        }
+        offset = rdata_start + rdlen;
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a response answering `query` with the given records
+    fn canned_response(query: &[u8], answers: &[(IpAddr, u32)]) -> Vec<u8> {
+        let mut packet = query.to_vec();
+        packet[2] = 0x81; // QR + RD
+        packet[3] = 0x80; // RA
+        packet[6..8].copy_from_slice(&(answers.len() as u16).to_be_bytes());
+        for (ip, ttl) in answers {
+            packet.extend_from_slice(&[0xC0, 0x0C]); // pointer to question name
+            let (rtype, rdata): (u16, Vec<u8>) = match ip {
+                IpAddr::V4(v4) => (TYPE_A, v4.octets().to_vec()),
+                IpAddr::V6(v6) => (TYPE_AAAA, v6.octets().to_vec()),
+            };
+            packet.extend_from_slice(&rtype.to_be_bytes());
+            packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+            packet.extend_from_slice(&ttl.to_be_bytes());
+            packet.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            packet.extend_from_slice(&rdata);
+        }
+        packet
+    }
+
+    #[test]
+    fn test_encode_query() {
+        let query = encode_query(0x1234, "example.com", TYPE_A).unwrap();
+        assert_eq!(&query[..2], &[0x12, 0x34]);
+        assert_eq!(read_u16(&query, 4).unwrap(), 1); // one question
+        let name_start = 12;
+        assert_eq!(query[name_start], 7);
+        assert_eq!(&query[name_start + 1..name_start + 8], b"example");
+        assert_eq!(query[name_start + 8], 3);
+        assert!(encode_query(0, "", TYPE_A).is_err());
+        assert!(encode_query(0, &"a".repeat(300), TYPE_A).is_err());
+    }
+
+    #[test]
+    fn test_parse_answers_with_compression() {
+        let query = encode_query(7, "example.com", TYPE_A).unwrap();
+        let response = canned_response(
+            &query,
+            &[("93.184.216.34".parse().unwrap(), 60), ("2606:2800:220:1:248:1893:25c8:1946".parse().unwrap(), 30)],
+        );
+        let answers = parse_answers(&response, 7).unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0], ("93.184.216.34".parse().unwrap(), 60));
+
+        // Mismatched ID and error RCODEs are rejected
+        assert!(parse_answers(&response, 8).is_err());
+        let mut nxdomain = response.clone();
+        nxdomain[3] |= 0x03;
+        assert!(parse_answers(&nxdomain, 7).is_err());
+    }
+
+    #[test]
+    fn test_parse_srv_answers() {
+        let query = encode_query(9, "_http._tcp.backend.test", TYPE_SRV).unwrap();
+        let mut response = query.clone();
+        response[2] = 0x81;
+        response[3] = 0x80;
+        response[6..8].copy_from_slice(&1u16.to_be_bytes());
+        response.extend_from_slice(&[0xC0, 0x0C]); // question name
+        response.extend_from_slice(&TYPE_SRV.to_be_bytes());
+        response.extend_from_slice(&CLASS_IN.to_be_bytes());
+        response.extend_from_slice(&30u32.to_be_bytes());
+        // RDATA: priority 10, weight 5, port 8080, target "pod-0" +
+        // compression pointer back into the question name
+        let target_offset = 12 + 1 + "_http".len(); // points at "_tcp.backend.test"
+        let rdata_len = 6 + 1 + "pod-0".len() + 2;
+        response.extend_from_slice(&(rdata_len as u16).to_be_bytes());
+        response.extend_from_slice(&10u16.to_be_bytes());
+        response.extend_from_slice(&5u16.to_be_bytes());
+        response.extend_from_slice(&8080u16.to_be_bytes());
+        response.push(5);
+        response.extend_from_slice(b"pod-0");
+        response.extend_from_slice(&[0xC0, target_offset as u8]);
+
+        let records = parse_srv_answers(&response, 9).unwrap();
+        assert_eq!(
+            records,
+            vec![SrvRecord {
+                priority: 10,
+                weight: 5,
+                port: 8080,
+                target: "pod-0._tcp.backend.test".to_string(),
+                ttl: 30,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_interleave_addresses() {
+        let addrs: Vec<IpAddr> = vec![
+            "1.1.1.1".parse().unwrap(),
+            "2.2.2.2".parse().unwrap(),
+            "::1".parse().unwrap(),
+        ];
+        let ordered = interleave_addresses(&addrs);
+        assert_eq!(ordered[0], "::1".parse::<IpAddr>().unwrap());
+        assert_eq!(ordered[1], "1.1.1.1".parse::<IpAddr>().unwrap());
+        assert_eq!(ordered[2], "2.2.2.2".parse::<IpAddr>().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_literal_ip_skips_network() {
+        let resolver = Resolver::new(DnsConfig::default().servers(vec![]));
+        let addrs = resolver.resolve("127.0.0.1").await.unwrap();
+        assert_eq!(addrs, vec!["127.0.0.1".parse::<IpAddr>().unwrap()]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_against_local_server_and_cache() {
+        // A one-shot UDP server answering whatever query arrives
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            let (len, peer) = socket.recv_from(&mut buf).await.unwrap();
+            let response =
+                canned_response(&buf[..len], &[("10.1.2.3".parse().unwrap(), 120)]);
+            socket.send_to(&response, peer).await.unwrap();
+            // Second query (AAAA) goes unanswered; the resolver still
+            // succeeds with the A records
+            let _ = socket.recv_from(&mut buf).await;
+        });
+
+        let resolver = Resolver::new(
+            DnsConfig::default()
+                .servers(vec![server])
+                .timeout(Duration::from_millis(300)),
+        );
+        let addrs = resolver.resolve("cache.test").await.unwrap();
+        assert_eq!(addrs, vec!["10.1.2.3".parse::<IpAddr>().unwrap()]);
+
+        // Served from cache: no server is listening anymore
+        let cached = resolver.resolve("CACHE.TEST").await.unwrap();
+        assert_eq!(cached, addrs);
+    }
+}
@@ -42,11 +42,32 @@ pub enum Error {
     #[error("HTTP error: {0}")]
     Hyper(String),
 
+    /// DNS resolution error (native only)
+    #[cfg(feature = "native")]
+    #[error("DNS error: {0}")]
+    Dns(String),
+
+    /// Outbound call cut off by the surrounding request's deadline
+    /// (native only)
+    #[cfg(feature = "native")]
+    #[error("Request deadline exceeded")]
+    DeadlineExceeded,
+
+    /// S3 error (native only)
+    #[cfg(feature = "native")]
+    #[error("S3 error ({status}): {message}")]
+    S3 { status: u16, message: String },
+
     /// TLS error
     #[cfg(feature = "tls")]
     #[error("TLS error: {0}")]
     Tls(String),
 
+    /// Plugin error (native only)
+    #[cfg(feature = "native")]
+    #[error("Plugin error: {0}")]
+    Plugin(String),
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
@@ -0,0 +1,375 @@
+//! Pluggable storage backends for static file serving
+//!
+//! [`StaticFiles`](super::StaticFiles) reads through a [`FileSource`]
+//! so assets can come from the local filesystem, an in-memory bundle
+//! embedded at build time, or S3-compatible object storage.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+/// Metadata for one entry in a file source
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileEntry {
+    /// Whether the entry is a directory
+    pub is_dir: bool,
+    /// Size in bytes (0 for directories)
+    pub size: u64,
+    /// Modification time (Unix seconds), if the backend tracks one
+    pub mtime: Option<u64>,
+}
+
+/// Storage backend for static assets
+///
+/// Paths are relative, slash-separated, and already sanitized by the
+/// caller (no leading slash, no `..` components).
+pub trait FileSource: Send + Sync {
+    /// Look up entry metadata; `None` if the path does not exist
+    fn entry(&self, path: &str) -> Option<FileEntry>;
+
+    /// Read a file's contents; `None` for missing paths or directories
+    fn read(&self, path: &str) -> Option<Vec<u8>>;
+
+    /// List a directory as (name, is_dir) pairs; `None` if not a directory
+    fn list(&self, path: &str) -> Option<Vec<(String, bool)>>;
+}
+
+/// Shared sources (e.g. a metadata cache that a watcher task also
+/// revalidates) can be handed to `StaticFiles` via `Arc`
+impl<S: FileSource + ?Sized> FileSource for std::sync::Arc<S> {
+    fn entry(&self, path: &str) -> Option<FileEntry> {
+        (**self).entry(path)
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        (**self).read(path)
+    }
+
+    fn list(&self, path: &str) -> Option<Vec<(String, bool)>> {
+        (**self).list(path)
+    }
+}
+
+/// Local filesystem source rooted at a directory
+pub struct LocalFs {
+    root: PathBuf,
+}
+
+impl LocalFs {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn full_path(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+}
+
+impl FileSource for LocalFs {
+    fn entry(&self, path: &str) -> Option<FileEntry> {
+        let meta = std::fs::metadata(self.full_path(path)).ok()?;
+        let mtime = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        Some(FileEntry {
+            is_dir: meta.is_dir(),
+            size: meta.len(),
+            mtime,
+        })
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        std::fs::read(self.full_path(path)).ok()
+    }
+
+    fn list(&self, path: &str) -> Option<Vec<(String, bool)>> {
+        let dir = std::fs::read_dir(self.full_path(path)).ok()?;
+        let mut entries = Vec::new();
+        for entry in dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            entries.push((name, is_dir));
+        }
+        Some(entries)
+    }
+}
+
+/// In-memory source for embedded asset bundles
+///
+/// Directories are inferred from file paths, so `insert("css/app.css", …)`
+/// makes `css` listable.
+#[derive(Default)]
+pub struct MemorySource {
+    files: HashMap<String, Vec<u8>>,
+    /// Bundle build time, used as mtime for every file
+    mtime: Option<u64>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the mtime reported for all files (e.g. the build timestamp)
+    pub fn mtime(mut self, epoch_secs: u64) -> Self {
+        self.mtime = Some(epoch_secs);
+        self
+    }
+
+    /// Add a file to the bundle
+    pub fn insert(&mut self, path: impl Into<String>, contents: impl Into<Vec<u8>>) {
+        let path = path.into();
+        self.files
+            .insert(path.trim_start_matches('/').to_string(), contents.into());
+    }
+
+    /// Build a bundle from (path, contents) pairs
+    pub fn from_entries<P, C>(entries: impl IntoIterator<Item = (P, C)>) -> Self
+    where
+        P: Into<String>,
+        C: Into<Vec<u8>>,
+    {
+        let mut source = Self::new();
+        for (path, contents) in entries {
+            source.insert(path, contents);
+        }
+        source
+    }
+
+    fn is_dir(&self, path: &str) -> bool {
+        let prefix = format!("{}/", path);
+        self.files.keys().any(|k| k.starts_with(&prefix))
+    }
+}
+
+impl FileSource for MemorySource {
+    fn entry(&self, path: &str) -> Option<FileEntry> {
+        if path.is_empty() {
+            // Root directory
+            return Some(FileEntry {
+                is_dir: true,
+                size: 0,
+                mtime: self.mtime,
+            });
+        }
+        if let Some(contents) = self.files.get(path) {
+            return Some(FileEntry {
+                is_dir: false,
+                size: contents.len() as u64,
+                mtime: self.mtime,
+            });
+        }
+        if self.is_dir(path) {
+            return Some(FileEntry {
+                is_dir: true,
+                size: 0,
+                mtime: self.mtime,
+            });
+        }
+        None
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        self.files.get(path).cloned()
+    }
+
+    fn list(&self, path: &str) -> Option<Vec<(String, bool)>> {
+        if !path.is_empty() && !self.is_dir(path) {
+            return None;
+        }
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", path)
+        };
+
+        let mut entries: Vec<(String, bool)> = Vec::new();
+        for key in self.files.keys() {
+            let rest = match key.strip_prefix(&prefix) {
+                Some(r) => r,
+                None => continue,
+            };
+            let (name, is_dir) = match rest.split_once('/') {
+                Some((dir, _)) => (dir.to_string(), true),
+                None => (rest.to_string(), false),
+            };
+            if !entries.iter().any(|(n, _)| n == &name) {
+                entries.push((name, is_dir));
+            }
+        }
+        Some(entries)
+    }
+}
+
+/// S3-backed source for serving assets from object storage
+///
+/// Object keys are the request paths (optionally under a prefix).
+/// Listings are not supported; enable directory listing only with
+/// filesystem-backed sources.
+#[cfg(feature = "native")]
+pub struct S3Source {
+    client: crate::s3::S3Client,
+    prefix: String,
+}
+
+#[cfg(feature = "native")]
+impl S3Source {
+    pub fn new(config: crate::s3::S3Config) -> Self {
+        Self {
+            client: crate::s3::S3Client::new(config),
+            prefix: String::new(),
+        }
+    }
+
+    /// Serve objects under a key prefix (e.g. `assets/`)
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        let mut prefix = prefix.into();
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        self.prefix = prefix;
+        self
+    }
+
+    fn key(&self, path: &str) -> String {
+        format!("{}{}", self.prefix, path)
+    }
+
+    /// Bridge the async client into the sync trait
+    fn block_on<F: std::future::Future>(&self, future: F) -> Option<F::Output> {
+        let handle = tokio::runtime::Handle::try_current().ok()?;
+        Some(tokio::task::block_in_place(|| handle.block_on(future)))
+    }
+}
+
+#[cfg(feature = "native")]
+impl FileSource for S3Source {
+    fn entry(&self, path: &str) -> Option<FileEntry> {
+        let info = self.block_on(self.client.head_object(&self.key(path)))?.ok()??;
+        Some(FileEntry {
+            is_dir: false,
+            size: info.content_length,
+            mtime: None,
+        })
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        let body = self.block_on(self.client.get_object(&self.key(path)))?.ok()?;
+        Some(body.to_vec())
+    }
+
+    fn list(&self, _path: &str) -> Option<Vec<(String, bool)>> {
+        None
+    }
+}
+
+/// Embed assets into the binary at build time
+///
+/// Takes `"request/path" => "relative/file/path"` pairs (resolved like
+/// `include_bytes!`, relative to the invoking file) and produces a
+/// [`MemorySource`] so [`StaticFiles`](super::StaticFiles) can serve
+/// them with zero filesystem access:
+///
+/// ```ignore
+/// let source = embedded_assets! {
+///     "index.html" => "../assets/index.html",
+///     "css/app.css" => "../assets/css/app.css",
+/// };
+/// let files = StaticFiles::with_source(StaticFileConfig::default(), source);
+/// ```
+#[macro_export]
+macro_rules! embedded_assets {
+    ( $( $path:literal => $file:literal ),* $(,)? ) => {
+        $crate::handlers::MemorySource::from_entries([
+            $( ($path, &include_bytes!($file)[..]) ),*
+        ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> MemorySource {
+        MemorySource::from_entries([
+            ("index.html", "<html></html>"),
+            ("css/app.css", "body{}"),
+            ("css/print.css", "@media print{}"),
+            ("js/app.js", "void 0"),
+        ])
+    }
+
+    #[test]
+    fn test_memory_source_files() {
+        let source = bundle();
+        let entry = source.entry("index.html").unwrap();
+        assert!(!entry.is_dir);
+        assert_eq!(entry.size, 13);
+        assert_eq!(source.read("css/app.css").unwrap(), b"body{}");
+        assert!(source.entry("missing.txt").is_none());
+    }
+
+    #[test]
+    fn test_memory_source_inferred_dirs() {
+        let source = bundle();
+        assert!(source.entry("css").unwrap().is_dir);
+        assert!(source.read("css").is_none());
+
+        let mut listing = source.list("").unwrap();
+        listing.sort();
+        assert_eq!(
+            listing,
+            vec![
+                ("css".to_string(), true),
+                ("index.html".to_string(), false),
+                ("js".to_string(), true),
+            ]
+        );
+
+        let mut css = source.list("css").unwrap();
+        css.sort();
+        assert_eq!(
+            css,
+            vec![
+                ("app.css".to_string(), false),
+                ("print.css".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_embedded_assets_macro() {
+        // Embeds this source file itself
+        let source = crate::embedded_assets! {
+            "code/file_source.rs" => "file_source.rs",
+        };
+        let entry = source.entry("code/file_source.rs").unwrap();
+        assert!(!entry.is_dir);
+        assert!(entry.size > 0);
+        assert!(source.entry("code").unwrap().is_dir);
+    }
+
+    #[test]
+    fn test_local_fs_roundtrip() {
+        let dir = std::env::temp_dir().join(format!(
+            "gust-file-source-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.txt"), b"hello").unwrap();
+
+        let source = LocalFs::new(&dir);
+        assert!(!source.entry("a.txt").unwrap().is_dir);
+        assert!(source.entry("sub").unwrap().is_dir);
+        assert_eq!(source.read("a.txt").unwrap(), b"hello");
+        assert!(source.list("a.txt").is_none());
+        assert!(source
+            .list("")
+            .unwrap()
+            .contains(&("sub".to_string(), true)));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,477 @@
+//! GraphQL-over-HTTP protocol adapter
+//!
+//! Implements the transport-level details of the GraphQL-over-HTTP spec
+//! (GET query param extraction, POST body parsing, batching, APQ hash
+//! handling, status codes) so runtimes only have to execute the
+//! already-validated operation. Execution itself stays in the host
+//! (JS resolver or Rust callback).
+
+use crate::crypto::sha256_hex;
+use crate::middleware::validate::Value;
+use crate::pure::json::{parse_json, serialize_json, write_json_string};
+
+/// A single parsed GraphQL operation
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GraphQLRequest {
+    /// The GraphQL document (may be absent for APQ hash-only requests)
+    pub query: Option<String>,
+    /// Operation name for multi-operation documents
+    pub operation_name: Option<String>,
+    /// Variables as raw JSON (object), if provided
+    pub variables: Option<Value>,
+    /// Extensions as raw JSON (object), if provided
+    pub extensions: Option<Value>,
+}
+
+/// A request payload: single operation or a batch
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphQLPayload {
+    Single(GraphQLRequest),
+    Batch(Vec<GraphQLRequest>),
+}
+
+/// Transport-level error with the HTTP status the spec mandates
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphQLHttpError {
+    /// HTTP status code to respond with
+    pub status: u16,
+    /// Error message for the errors array
+    pub message: String,
+    /// Machine-readable error code (extensions.code)
+    pub code: &'static str,
+}
+
+impl GraphQLHttpError {
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: 400,
+            message: message.into(),
+            code: "BAD_REQUEST",
+        }
+    }
+
+    pub fn method_not_allowed() -> Self {
+        Self {
+            status: 405,
+            message: "GraphQL only supports GET and POST requests".to_string(),
+            code: "METHOD_NOT_ALLOWED",
+        }
+    }
+
+    /// APQ: registry does not know the hash yet; client should retry with full query
+    pub fn persisted_query_not_found() -> Self {
+        Self {
+            status: 200,
+            message: "PersistedQueryNotFound".to_string(),
+            code: "PERSISTED_QUERY_NOT_FOUND",
+        }
+    }
+
+    /// Render as a spec-shaped `{"errors": [...]}` JSON body
+    pub fn to_body(&self) -> String {
+        let mut out = String::new();
+        out.push_str("{\"errors\":[{\"message\":");
+        write_json_string(&self.message, &mut out);
+        out.push_str(",\"extensions\":{\"code\":\"");
+        out.push_str(self.code);
+        out.push_str("\"}}]}");
+        out
+    }
+}
+
+/// Parse a GraphQL GET request from the raw query string
+///
+/// Recognized params: `query`, `operationName`, `variables` (JSON),
+/// `extensions` (JSON).
+pub fn parse_get_request(query_string: &str) -> Result<GraphQLRequest, GraphQLHttpError> {
+    let mut req = GraphQLRequest::default();
+
+    for pair in query_string.split('&') {
+        let (key, value) = match pair.split_once('=') {
+            Some(kv) => kv,
+            None => continue,
+        };
+        let value = url_decode(value);
+
+        match key {
+            "query" => req.query = Some(value),
+            "operationName" if !value.is_empty() => {
+                req.operation_name = Some(value);
+            }
+            "variables" if !value.is_empty() => {
+                let parsed = parse_json(&value).map_err(|e| {
+                    GraphQLHttpError::bad_request(format!("Invalid variables: {}", e))
+                })?;
+                req.variables = Some(parsed);
+            }
+            "extensions" if !value.is_empty() => {
+                let parsed = parse_json(&value).map_err(|e| {
+                    GraphQLHttpError::bad_request(format!("Invalid extensions: {}", e))
+                })?;
+                req.extensions = Some(parsed);
+            }
+            _ => {}
+        }
+    }
+
+    if req.query.is_none() && req.extensions.is_none() {
+        return Err(GraphQLHttpError::bad_request(
+            "Missing query parameter",
+        ));
+    }
+
+    Ok(req)
+}
+
+/// Parse a GraphQL POST request body
+///
+/// `application/json` bodies may be a single request object or an array
+/// (batch). `application/graphql` bodies are the bare document.
+pub fn parse_post_request(
+    content_type: Option<&str>,
+    body: &str,
+) -> Result<GraphQLPayload, GraphQLHttpError> {
+    let content_type = content_type.unwrap_or("application/json");
+
+    if content_type.starts_with("application/graphql") {
+        if body.trim().is_empty() {
+            return Err(GraphQLHttpError::bad_request("Empty GraphQL document"));
+        }
+        return Ok(GraphQLPayload::Single(GraphQLRequest {
+            query: Some(body.to_string()),
+            ..Default::default()
+        }));
+    }
+
+    if !content_type.starts_with("application/json") {
+        return Err(GraphQLHttpError::bad_request(format!(
+            "Unsupported content type: {}",
+            content_type
+        )));
+    }
+
+    let value = parse_json(body)
+        .map_err(|e| GraphQLHttpError::bad_request(format!("Invalid JSON body: {}", e)))?;
+
+    match value {
+        Value::Object(_) => Ok(GraphQLPayload::Single(request_from_value(&value)?)),
+        Value::Array(items) => {
+            if items.is_empty() {
+                return Err(GraphQLHttpError::bad_request("Empty batch"));
+            }
+            let mut requests = Vec::with_capacity(items.len());
+            for item in &items {
+                requests.push(request_from_value(item)?);
+            }
+            Ok(GraphQLPayload::Batch(requests))
+        }
+        _ => Err(GraphQLHttpError::bad_request(
+            "Request body must be a JSON object or array",
+        )),
+    }
+}
+
+fn request_from_value(value: &Value) -> Result<GraphQLRequest, GraphQLHttpError> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| GraphQLHttpError::bad_request("Each request must be a JSON object"))?;
+
+    let query = match obj.get("query") {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Null) | None => None,
+        Some(_) => return Err(GraphQLHttpError::bad_request("'query' must be a string")),
+    };
+
+    let operation_name = match obj.get("operationName") {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Null) | None => None,
+        Some(_) => {
+            return Err(GraphQLHttpError::bad_request(
+                "'operationName' must be a string",
+            ))
+        }
+    };
+
+    let variables = match obj.get("variables") {
+        Some(Value::Null) | None => None,
+        Some(v @ Value::Object(_)) => Some(v.clone()),
+        Some(_) => return Err(GraphQLHttpError::bad_request("'variables' must be an object")),
+    };
+
+    let extensions = match obj.get("extensions") {
+        Some(Value::Null) | None => None,
+        Some(v @ Value::Object(_)) => Some(v.clone()),
+        Some(_) => {
+            return Err(GraphQLHttpError::bad_request(
+                "'extensions' must be an object",
+            ))
+        }
+    };
+
+    Ok(GraphQLRequest {
+        query,
+        operation_name,
+        variables,
+        extensions,
+    })
+}
+
+/// Extract the APQ sha256 hash from `extensions.persistedQuery`, if present
+pub fn persisted_query_hash(req: &GraphQLRequest) -> Option<String> {
+    let extensions = req.extensions.as_ref()?.as_object()?;
+    let persisted = extensions.get("persistedQuery")?.as_object()?;
+
+    // Only version 1 is defined
+    if let Some(version) = persisted.get("version").and_then(|v| v.as_f64()) {
+        if version != 1.0 {
+            return None;
+        }
+    }
+
+    persisted
+        .get("sha256Hash")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_lowercase())
+}
+
+/// Compute the APQ hash of a query document
+pub fn apq_hash(query: &str) -> String {
+    sha256_hex(query.as_bytes())
+}
+
+/// Serialize a request to the normalized JSON passed to the executor
+pub fn to_execute_json(req: &GraphQLRequest) -> String {
+    let mut out = String::new();
+    write_request_json(req, &mut out);
+    out
+}
+
+/// Serialize a payload (single object or batch array) to executor JSON
+pub fn payload_to_execute_json(payload: &GraphQLPayload) -> String {
+    match payload {
+        GraphQLPayload::Single(req) => to_execute_json(req),
+        GraphQLPayload::Batch(requests) => {
+            let mut out = String::from("[");
+            for (i, req) in requests.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_request_json(req, &mut out);
+            }
+            out.push(']');
+            out
+        }
+    }
+}
+
+fn write_request_json(req: &GraphQLRequest, out: &mut String) {
+    out.push_str("{\"query\":");
+    match &req.query {
+        Some(q) => write_json_string(q, out),
+        None => out.push_str("null"),
+    }
+    out.push_str(",\"operationName\":");
+    match &req.operation_name {
+        Some(n) => write_json_string(n, out),
+        None => out.push_str("null"),
+    }
+    out.push_str(",\"variables\":");
+    match &req.variables {
+        Some(v) => out.push_str(&serialize_json(v)),
+        None => out.push_str("null"),
+    }
+    out.push('}');
+}
+
+/// Minimal GraphiQL page pointing at the given endpoint
+pub fn graphiql_html(endpoint: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>GraphiQL</title>
+  <style>body {{ margin: 0; }} #graphiql {{ height: 100vh; }}</style>
+  <link rel="stylesheet" href="https://unpkg.com/graphiql/graphiql.min.css" />
+</head>
+<body>
+  <div id="graphiql">Loading GraphiQL...</div>
+  <script crossorigin src="https://unpkg.com/react/umd/react.production.min.js"></script>
+  <script crossorigin src="https://unpkg.com/react-dom/umd/react-dom.production.min.js"></script>
+  <script crossorigin src="https://unpkg.com/graphiql/graphiql.min.js"></script>
+  <script>
+    const fetcher = GraphiQL.createFetcher({{ url: '{}' }});
+    ReactDOM.render(
+      React.createElement(GraphiQL, {{ fetcher }}),
+      document.getElementById('graphiql')
+    );
+  </script>
+</body>
+</html>"#,
+        endpoint
+    )
+}
+
+/// URL-decode a query string component
+fn url_decode(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut bytes = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            let hex: String = chars.by_ref().take(2).collect();
+            if hex.len() == 2 {
+                if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                    bytes.push(byte);
+                    continue;
+                }
+            }
+            flush_bytes(&mut bytes, &mut result);
+            result.push('%');
+            result.push_str(&hex);
+        } else {
+            flush_bytes(&mut bytes, &mut result);
+            if c == '+' {
+                result.push(' ');
+            } else {
+                result.push(c);
+            }
+        }
+    }
+    flush_bytes(&mut bytes, &mut result);
+    result
+}
+
+fn flush_bytes(bytes: &mut Vec<u8>, out: &mut String) {
+    if !bytes.is_empty() {
+        out.push_str(&String::from_utf8_lossy(bytes));
+        bytes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_get_request() {
+        let req = parse_get_request("query=%7B%20hero%20%7D&operationName=Op").unwrap();
+        assert_eq!(req.query.as_deref(), Some("{ hero }"));
+        assert_eq!(req.operation_name.as_deref(), Some("Op"));
+    }
+
+    #[test]
+    fn test_parse_get_missing_query() {
+        let err = parse_get_request("foo=bar").unwrap_err();
+        assert_eq!(err.status, 400);
+    }
+
+    #[test]
+    fn test_parse_get_variables() {
+        let req = parse_get_request("query=q&variables=%7B%22id%22%3A1%7D").unwrap();
+        let vars = req.variables.unwrap();
+        assert_eq!(
+            vars.as_object().unwrap().get("id").unwrap().as_f64(),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_post_single() {
+        let payload = parse_post_request(
+            Some("application/json"),
+            r#"{"query": "{ hero }", "variables": {"a": 1}}"#,
+        )
+        .unwrap();
+        match payload {
+            GraphQLPayload::Single(req) => {
+                assert_eq!(req.query.as_deref(), Some("{ hero }"));
+                assert!(req.variables.is_some());
+            }
+            _ => panic!("expected single"),
+        }
+    }
+
+    #[test]
+    fn test_parse_post_batch() {
+        let payload = parse_post_request(
+            Some("application/json"),
+            r#"[{"query": "{ a }"}, {"query": "{ b }"}]"#,
+        )
+        .unwrap();
+        match payload {
+            GraphQLPayload::Batch(reqs) => assert_eq!(reqs.len(), 2),
+            _ => panic!("expected batch"),
+        }
+    }
+
+    #[test]
+    fn test_parse_post_graphql_content_type() {
+        let payload =
+            parse_post_request(Some("application/graphql"), "{ hero }").unwrap();
+        match payload {
+            GraphQLPayload::Single(req) => assert_eq!(req.query.as_deref(), Some("{ hero }")),
+            _ => panic!("expected single"),
+        }
+    }
+
+    #[test]
+    fn test_parse_post_invalid() {
+        assert!(parse_post_request(Some("application/json"), "not json").is_err());
+        assert!(parse_post_request(Some("application/json"), "[]").is_err());
+        assert!(parse_post_request(Some("application/json"), "42").is_err());
+        assert!(parse_post_request(Some("text/plain"), "{}").is_err());
+    }
+
+    #[test]
+    fn test_persisted_query_hash() {
+        let payload = parse_post_request(
+            Some("application/json"),
+            r#"{"extensions": {"persistedQuery": {"version": 1, "sha256Hash": "ABC123"}}}"#,
+        )
+        .unwrap();
+        let req = match payload {
+            GraphQLPayload::Single(r) => r,
+            _ => panic!("expected single"),
+        };
+        assert_eq!(persisted_query_hash(&req).as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn test_apq_hash_matches_sha256() {
+        assert_eq!(
+            apq_hash("{__typename}"),
+            "ecf4edb46db40b5132295c0291d62fb65d6759a9eedfa4d5d612dd5ec54a6b38"
+        );
+    }
+
+    #[test]
+    fn test_error_body() {
+        let err = GraphQLHttpError::persisted_query_not_found();
+        let body = err.to_body();
+        assert!(body.contains("PersistedQueryNotFound"));
+        assert!(body.contains("PERSISTED_QUERY_NOT_FOUND"));
+    }
+
+    #[test]
+    fn test_to_execute_json() {
+        let req = GraphQLRequest {
+            query: Some("{ hero }".to_string()),
+            operation_name: None,
+            variables: None,
+            extensions: None,
+        };
+        assert_eq!(
+            to_execute_json(&req),
+            r#"{"query":"{ hero }","operationName":null,"variables":null}"#
+        );
+    }
+
+    #[test]
+    fn test_graphiql_html() {
+        let html = graphiql_html("/graphql");
+        assert!(html.contains("GraphiQL"));
+        assert!(html.contains("/graphql"));
+    }
+}
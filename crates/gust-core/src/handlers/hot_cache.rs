@@ -0,0 +1,307 @@
+//! In-memory hot file cache for static serving
+//!
+//! [`HotFileCache`] keeps the bodies of small static files in RAM so
+//! top assets are served with zero disk I/O. The cache is bounded by a
+//! total byte budget with least-recently-used eviction, entries expire
+//! by an optional TTL, and callers key entries by path plus file
+//! version so a changed file naturally rolls over to a fresh entry.
+
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "native")]
+use parking_lot::RwLock;
+
+#[cfg(not(feature = "native"))]
+use std::sync::RwLock;
+
+/// Hot file cache configuration
+#[derive(Clone)]
+pub struct HotFileCacheConfig {
+    /// Total byte budget for cached bodies
+    pub max_bytes: u64,
+    /// Only files up to this size are cached
+    pub max_file_bytes: u64,
+    /// Per-entry time to live; `None` keeps entries until evicted
+    pub ttl: Option<Duration>,
+}
+
+impl Default for HotFileCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 32 * 1024 * 1024,  // 32 MiB
+            max_file_bytes: 256 * 1024,   // 256 KiB
+            ttl: None,
+        }
+    }
+}
+
+impl HotFileCacheConfig {
+    pub fn max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    pub fn max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_bytes = bytes;
+        self
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+/// Snapshot of hot file cache counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HotFileCacheStats {
+    /// Bodies served from RAM
+    pub hits: u64,
+    /// Lookups that went to the backend
+    pub misses: u64,
+    /// Bodies currently cached
+    pub entries: u64,
+    /// Bytes currently cached
+    pub bytes: u64,
+    /// Entries evicted to stay within the byte budget
+    pub evictions: u64,
+}
+
+struct HotEntry {
+    body: Bytes,
+    inserted: Instant,
+    /// Access sequence number for LRU ordering
+    last_access: AtomicU64,
+}
+
+struct Inner {
+    entries: HashMap<String, HotEntry>,
+    bytes: u64,
+}
+
+/// Size-bounded LRU cache of file bodies
+pub struct HotFileCache {
+    config: HotFileCacheConfig,
+    inner: RwLock<Inner>,
+    seq: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl HotFileCache {
+    pub fn new(config: HotFileCacheConfig) -> Self {
+        Self {
+            config,
+            inner: RwLock::new(Inner {
+                entries: HashMap::new(),
+                bytes: 0,
+            }),
+            seq: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// Fetch a cached body, bumping its recency
+    pub fn get(&self, key: &str) -> Option<Bytes> {
+        {
+            let inner = self.read_inner();
+            if let Some(entry) = inner.entries.get(key) {
+                if !self.is_expired(entry) {
+                    entry
+                        .last_access
+                        .store(self.next_seq(), Ordering::Relaxed);
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Some(entry.body.clone());
+                }
+            } else {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        }
+        // Expired: drop it so the byte budget frees up immediately
+        let mut inner = self.write_inner();
+        if let Some(entry) = inner.entries.remove(key) {
+            inner.bytes -= entry.body.len() as u64;
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    /// Cache a body; returns false when it exceeds the size limits
+    pub fn insert(&self, key: &str, body: Bytes) -> bool {
+        let size = body.len() as u64;
+        if size > self.config.max_file_bytes || size > self.config.max_bytes {
+            return false;
+        }
+
+        let mut inner = self.write_inner();
+        if let Some(old) = inner.entries.remove(key) {
+            inner.bytes -= old.body.len() as u64;
+        }
+
+        // Evict least-recently-used entries until the body fits
+        while inner.bytes + size > self.config.max_bytes {
+            let lru_key = match inner
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_access.load(Ordering::Relaxed))
+                .map(|(k, _)| k.clone())
+            {
+                Some(k) => k,
+                None => break,
+            };
+            if let Some(evicted) = inner.entries.remove(&lru_key) {
+                inner.bytes -= evicted.body.len() as u64;
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        inner.bytes += size;
+        inner.entries.insert(
+            key.to_string(),
+            HotEntry {
+                body,
+                inserted: Instant::now(),
+                last_access: AtomicU64::new(self.next_seq()),
+            },
+        );
+        true
+    }
+
+    /// Drop one cached key; returns whether it was cached
+    pub fn invalidate(&self, key: &str) -> bool {
+        let mut inner = self.write_inner();
+        match inner.entries.remove(key) {
+            Some(entry) => {
+                inner.bytes -= entry.body.len() as u64;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop every cached body
+    pub fn clear(&self) {
+        let mut inner = self.write_inner();
+        inner.entries.clear();
+        inner.bytes = 0;
+    }
+
+    /// Snapshot the cache counters
+    pub fn stats(&self) -> HotFileCacheStats {
+        let inner = self.read_inner();
+        HotFileCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: inner.entries.len() as u64,
+            bytes: inner.bytes,
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    fn is_expired(&self, entry: &HotEntry) -> bool {
+        match self.config.ttl {
+            Some(ttl) => entry.inserted.elapsed() > ttl,
+            None => false,
+        }
+    }
+
+    fn next_seq(&self) -> u64 {
+        self.seq.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    #[cfg(feature = "native")]
+    fn read_inner(&self) -> parking_lot::RwLockReadGuard<'_, Inner> {
+        self.inner.read()
+    }
+
+    #[cfg(not(feature = "native"))]
+    fn read_inner(&self) -> std::sync::RwLockReadGuard<'_, Inner> {
+        self.inner.read().unwrap()
+    }
+
+    #[cfg(feature = "native")]
+    fn write_inner(&self) -> parking_lot::RwLockWriteGuard<'_, Inner> {
+        self.inner.write()
+    }
+
+    #[cfg(not(feature = "native"))]
+    fn write_inner(&self) -> std::sync::RwLockWriteGuard<'_, Inner> {
+        self.inner.write().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_insert_and_stats() {
+        let cache = HotFileCache::new(HotFileCacheConfig::default());
+
+        assert!(cache.get("app.js").is_none());
+        assert!(cache.insert("app.js", Bytes::from_static(b"void 0")));
+        assert_eq!(cache.get("app.js"), Some(Bytes::from_static(b"void 0")));
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.bytes, 6);
+
+        assert!(cache.invalidate("app.js"));
+        assert_eq!(cache.stats().bytes, 0);
+    }
+
+    #[test]
+    fn test_lru_eviction_within_byte_budget() {
+        let config = HotFileCacheConfig::default().max_bytes(10).max_file_bytes(10);
+        let cache = HotFileCache::new(config);
+
+        assert!(cache.insert("a", Bytes::from_static(b"aaaa")));
+        assert!(cache.insert("b", Bytes::from_static(b"bbbb")));
+        // Touch "a" so "b" is the least recently used
+        cache.get("a");
+
+        assert!(cache.insert("c", Bytes::from_static(b"cccc")));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.bytes, 8);
+    }
+
+    #[test]
+    fn test_oversized_bodies_are_not_cached() {
+        let config = HotFileCacheConfig::default().max_file_bytes(3);
+        let cache = HotFileCache::new(config);
+
+        assert!(!cache.insert("big", Bytes::from_static(b"too large")));
+        assert!(cache.insert("ok", Bytes::from_static(b"ok!")));
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[test]
+    fn test_ttl_expiry() {
+        let config = HotFileCacheConfig::default().ttl(Duration::from_millis(5));
+        let cache = HotFileCache::new(config);
+
+        assert!(cache.insert("a", Bytes::from_static(b"aa")));
+        assert!(cache.get("a").is_some());
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(cache.get("a").is_none());
+        // The expired entry was dropped, freeing its bytes
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 0);
+        assert_eq!(stats.bytes, 0);
+    }
+}
@@ -0,0 +1,341 @@
+//! JSON-RPC 2.0 envelope handling
+//!
+//! Parses and validates request envelopes (single and batch), builds
+//! spec-shaped response/error objects, and classifies notifications —
+//! all before the host runtime is invoked. Method execution stays in
+//! the host (JS handler or Rust callback).
+
+use crate::middleware::validate::Value;
+use crate::pure::json::{parse_json, serialize_json, write_json_string};
+
+/// Request ID: string, number, or explicit null
+///
+/// Absent IDs mark a notification and never produce a response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonRpcId {
+    Null,
+    Number(f64),
+    String(String),
+}
+
+impl JsonRpcId {
+    /// Serialize the ID for a response envelope
+    pub fn to_json(&self) -> String {
+        match self {
+            JsonRpcId::Null => "null".to_string(),
+            JsonRpcId::Number(n) => {
+                if n.fract() == 0.0 && n.abs() < 9e15 {
+                    format!("{}", *n as i64)
+                } else {
+                    format!("{}", n)
+                }
+            }
+            JsonRpcId::String(s) => {
+                let mut out = String::new();
+                write_json_string(s, &mut out);
+                out
+            }
+        }
+    }
+}
+
+/// A validated JSON-RPC 2.0 request
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonRpcRequest {
+    /// Request ID; `None` means notification (no response expected)
+    pub id: Option<JsonRpcId>,
+    /// Method name
+    pub method: String,
+    /// Params (array or object), if provided
+    pub params: Option<Value>,
+}
+
+impl JsonRpcRequest {
+    /// Whether this request is a notification
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+
+    /// Serialize to the normalized JSON passed to the executor
+    pub fn to_execute_json(&self) -> String {
+        let mut out = String::from("{\"jsonrpc\":\"2.0\",\"id\":");
+        match &self.id {
+            Some(id) => out.push_str(&id.to_json()),
+            None => out.push_str("null"),
+        }
+        out.push_str(",\"method\":");
+        write_json_string(&self.method, &mut out);
+        out.push_str(",\"params\":");
+        match &self.params {
+            Some(p) => out.push_str(&serialize_json(p)),
+            None => out.push_str("null"),
+        }
+        out.push('}');
+        out
+    }
+}
+
+/// A parsed payload: single request or batch
+///
+/// Batch entries that failed validation carry their error so the
+/// response array lines up with the request array per the spec.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonRpcPayload {
+    Single(JsonRpcRequest),
+    Batch(Vec<Result<JsonRpcRequest, JsonRpcError>>),
+}
+
+/// JSON-RPC 2.0 error object
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonRpcError {
+    /// Error code (spec-reserved range -32768..=-32000 plus app codes)
+    pub code: i64,
+    /// Short error description
+    pub message: String,
+}
+
+impl JsonRpcError {
+    pub fn parse_error() -> Self {
+        Self {
+            code: -32700,
+            message: "Parse error".to_string(),
+        }
+    }
+
+    pub fn invalid_request(detail: impl Into<String>) -> Self {
+        Self {
+            code: -32600,
+            message: format!("Invalid Request: {}", detail.into()),
+        }
+    }
+
+    pub fn method_not_found(method: &str) -> Self {
+        Self {
+            code: -32601,
+            message: format!("Method not found: {}", method),
+        }
+    }
+
+    pub fn invalid_params(detail: impl Into<String>) -> Self {
+        Self {
+            code: -32602,
+            message: format!("Invalid params: {}", detail.into()),
+        }
+    }
+
+    pub fn internal_error(detail: impl Into<String>) -> Self {
+        Self {
+            code: -32603,
+            message: format!("Internal error: {}", detail.into()),
+        }
+    }
+}
+
+/// Parse and validate a JSON-RPC request body
+///
+/// Returns `Err` only when the whole body is unusable (parse error or
+/// empty batch); per-entry batch validation errors are carried inline.
+pub fn parse_request_body(body: &str) -> Result<JsonRpcPayload, JsonRpcError> {
+    let value = parse_json(body).map_err(|_| JsonRpcError::parse_error())?;
+
+    match value {
+        Value::Object(_) => match validate_request(&value) {
+            Ok(req) => Ok(JsonRpcPayload::Single(req)),
+            Err(e) => Err(e),
+        },
+        Value::Array(items) => {
+            if items.is_empty() {
+                return Err(JsonRpcError::invalid_request("empty batch"));
+            }
+            Ok(JsonRpcPayload::Batch(
+                items.iter().map(validate_request).collect(),
+            ))
+        }
+        _ => Err(JsonRpcError::invalid_request(
+            "body must be an object or array",
+        )),
+    }
+}
+
+fn validate_request(value: &Value) -> Result<JsonRpcRequest, JsonRpcError> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| JsonRpcError::invalid_request("request must be an object"))?;
+
+    match obj.get("jsonrpc") {
+        Some(Value::String(v)) if v == "2.0" => {}
+        _ => return Err(JsonRpcError::invalid_request("jsonrpc must be \"2.0\"")),
+    }
+
+    let method = match obj.get("method") {
+        Some(Value::String(m)) => m.clone(),
+        Some(_) => return Err(JsonRpcError::invalid_request("method must be a string")),
+        None => return Err(JsonRpcError::invalid_request("missing method")),
+    };
+
+    let params = match obj.get("params") {
+        None | Some(Value::Null) => None,
+        Some(p @ Value::Array(_)) | Some(p @ Value::Object(_)) => Some(p.clone()),
+        Some(_) => {
+            return Err(JsonRpcError::invalid_params(
+                "params must be an array or object",
+            ))
+        }
+    };
+
+    let id = match obj.get("id") {
+        None => None,
+        Some(Value::Null) => Some(JsonRpcId::Null),
+        Some(Value::Number(n)) => Some(JsonRpcId::Number(*n)),
+        Some(Value::String(s)) => Some(JsonRpcId::String(s.clone())),
+        Some(_) => {
+            return Err(JsonRpcError::invalid_request(
+                "id must be a string, number, or null",
+            ))
+        }
+    };
+
+    Ok(JsonRpcRequest { id, method, params })
+}
+
+/// Build a success response envelope
+///
+/// `result_json` must already be valid JSON (the executor's output).
+pub fn success_response(id: &JsonRpcId, result_json: &str) -> String {
+    format!(
+        "{{\"jsonrpc\":\"2.0\",\"id\":{},\"result\":{}}}",
+        id.to_json(),
+        result_json
+    )
+}
+
+/// Build an error response envelope
+///
+/// `id` is `None` when the request ID could not be determined (parse
+/// errors), which the spec maps to `"id": null`.
+pub fn error_response(id: Option<&JsonRpcId>, error: &JsonRpcError) -> String {
+    let mut out = String::from("{\"jsonrpc\":\"2.0\",\"id\":");
+    match id {
+        Some(id) => out.push_str(&id.to_json()),
+        None => out.push_str("null"),
+    }
+    out.push_str(&format!(",\"error\":{{\"code\":{},\"message\":", error.code));
+    write_json_string(&error.message, &mut out);
+    out.push_str("}}");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_request() {
+        let payload =
+            parse_request_body(r#"{"jsonrpc": "2.0", "method": "sum", "params": [1, 2], "id": 1}"#)
+                .unwrap();
+        match payload {
+            JsonRpcPayload::Single(req) => {
+                assert_eq!(req.method, "sum");
+                assert_eq!(req.id, Some(JsonRpcId::Number(1.0)));
+                assert!(!req.is_notification());
+            }
+            _ => panic!("expected single"),
+        }
+    }
+
+    #[test]
+    fn test_parse_notification() {
+        let payload =
+            parse_request_body(r#"{"jsonrpc": "2.0", "method": "notify"}"#).unwrap();
+        match payload {
+            JsonRpcPayload::Single(req) => assert!(req.is_notification()),
+            _ => panic!("expected single"),
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_request_body("{not json").unwrap_err();
+        assert_eq!(err.code, -32700);
+    }
+
+    #[test]
+    fn test_invalid_version() {
+        let err = parse_request_body(r#"{"jsonrpc": "1.0", "method": "x", "id": 1}"#).unwrap_err();
+        assert_eq!(err.code, -32600);
+    }
+
+    #[test]
+    fn test_invalid_params_type() {
+        let err = parse_request_body(r#"{"jsonrpc": "2.0", "method": "x", "params": 42, "id": 1}"#)
+            .unwrap_err();
+        assert_eq!(err.code, -32602);
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let err = parse_request_body("[]").unwrap_err();
+        assert_eq!(err.code, -32600);
+    }
+
+    #[test]
+    fn test_batch_mixed_validity() {
+        let payload = parse_request_body(
+            r#"[{"jsonrpc": "2.0", "method": "a", "id": 1}, {"bad": true}, 42]"#,
+        )
+        .unwrap();
+        match payload {
+            JsonRpcPayload::Batch(entries) => {
+                assert_eq!(entries.len(), 3);
+                assert!(entries[0].is_ok());
+                assert!(entries[1].is_err());
+                assert!(entries[2].is_err());
+            }
+            _ => panic!("expected batch"),
+        }
+    }
+
+    #[test]
+    fn test_success_response() {
+        let id = JsonRpcId::Number(7.0);
+        assert_eq!(
+            success_response(&id, "19"),
+            r#"{"jsonrpc":"2.0","id":7,"result":19}"#
+        );
+    }
+
+    #[test]
+    fn test_error_response_with_null_id() {
+        let body = error_response(None, &JsonRpcError::parse_error());
+        assert_eq!(
+            body,
+            r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32700,"message":"Parse error"}}"#
+        );
+    }
+
+    #[test]
+    fn test_string_id_roundtrip() {
+        let payload =
+            parse_request_body(r#"{"jsonrpc": "2.0", "method": "x", "id": "abc\"d"}"#).unwrap();
+        let req = match payload {
+            JsonRpcPayload::Single(r) => r,
+            _ => panic!("expected single"),
+        };
+        let id = req.id.unwrap();
+        assert_eq!(id.to_json(), r#""abc\"d""#);
+    }
+
+    #[test]
+    fn test_execute_json() {
+        let req = JsonRpcRequest {
+            id: Some(JsonRpcId::Number(1.0)),
+            method: "sum".to_string(),
+            params: Some(Value::Array(vec![Value::Number(1.0), Value::Number(2.0)])),
+        };
+        assert_eq!(
+            req.to_execute_json(),
+            r#"{"jsonrpc":"2.0","id":1,"method":"sum","params":[1,2]}"#
+        );
+    }
+}
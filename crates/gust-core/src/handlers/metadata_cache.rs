@@ -0,0 +1,269 @@
+//! Cached file metadata for static serving
+//!
+//! [`MetadataCache`] wraps a [`FileSource`] and keeps entry metadata
+//! (size, mtime) plus the derived ETag and MIME type in a concurrent
+//! map, so hot assets skip the per-request stat syscall. Cached paths
+//! are revalidated by [`revalidate`](MetadataCache::revalidate) — a
+//! polling watcher task under the `native` feature — which drops
+//! entries whose backing file changed or disappeared.
+
+use super::file_source::{FileEntry, FileSource};
+use super::static_files::{entry_etag, file_mime_type};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "native")]
+use parking_lot::RwLock;
+
+#[cfg(not(feature = "native"))]
+use std::sync::RwLock;
+
+/// Metadata cached for one path
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileMeta {
+    /// Backend entry (size, mtime, is_dir)
+    pub entry: FileEntry,
+    /// ETag derived from mtime and size
+    pub etag: String,
+    /// MIME type from the file extension
+    pub mime: &'static str,
+}
+
+impl FileMeta {
+    fn build(path: &str, entry: FileEntry) -> Self {
+        Self {
+            etag: entry_etag(&entry),
+            mime: file_mime_type(Path::new(path)),
+            entry,
+        }
+    }
+}
+
+/// Snapshot of metadata cache counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MetadataCacheStats {
+    /// Lookups served from the cache
+    pub hits: u64,
+    /// Lookups that went to the backend
+    pub misses: u64,
+    /// Paths currently cached
+    pub entries: u64,
+    /// Entries dropped because the backing file changed or disappeared
+    pub invalidations: u64,
+}
+
+/// Caching [`FileSource`] decorator
+///
+/// Existing paths are cached on first lookup; missing paths always go
+/// to the backend so new files show up without waiting for a watcher
+/// tick. File contents are not cached — only metadata.
+pub struct MetadataCache {
+    inner: Box<dyn FileSource>,
+    entries: RwLock<HashMap<String, FileMeta>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl MetadataCache {
+    pub fn new(inner: impl FileSource + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+            entries: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+        }
+    }
+
+    /// Cached metadata lookup; fills from the backend on miss
+    pub fn lookup(&self, path: &str) -> Option<FileMeta> {
+        if let Some(meta) = self.read_entries().get(path) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Some(meta.clone());
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let meta = FileMeta::build(path, self.inner.entry(path)?);
+        self.write_entries().insert(path.to_string(), meta.clone());
+        Some(meta)
+    }
+
+    /// Cached metadata without counting a hit or filling on miss
+    pub fn peek(&self, path: &str) -> Option<FileMeta> {
+        self.read_entries().get(path).cloned()
+    }
+
+    /// Drop one cached path; returns whether it was cached
+    pub fn invalidate(&self, path: &str) -> bool {
+        let dropped = self.write_entries().remove(path).is_some();
+        if dropped {
+            self.invalidations.fetch_add(1, Ordering::Relaxed);
+        }
+        dropped
+    }
+
+    /// Drop every cached path
+    pub fn clear(&self) {
+        self.write_entries().clear();
+    }
+
+    /// Re-stat every cached path, dropping entries whose backing file
+    /// changed or disappeared; returns the number dropped
+    pub fn revalidate(&self) -> usize {
+        let paths: Vec<String> = self.read_entries().keys().cloned().collect();
+        let mut dropped = 0;
+        for path in paths {
+            let fresh = self.inner.entry(&path);
+            let stale = match self.read_entries().get(&path) {
+                Some(meta) => fresh.as_ref() != Some(&meta.entry),
+                None => false,
+            };
+            if stale && self.write_entries().remove(&path).is_some() {
+                self.invalidations.fetch_add(1, Ordering::Relaxed);
+                dropped += 1;
+            }
+        }
+        dropped
+    }
+
+    /// Snapshot the cache counters
+    pub fn stats(&self) -> MetadataCacheStats {
+        MetadataCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.read_entries().len() as u64,
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Spawn a watcher task that revalidates cached paths at `interval`
+    ///
+    /// Polling keeps the watcher portable across platforms and backends;
+    /// abort the returned handle to stop it.
+    #[cfg(feature = "native")]
+    pub fn watch(
+        self: &std::sync::Arc<Self>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let cache = std::sync::Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                cache.revalidate();
+            }
+        })
+    }
+
+    #[cfg(feature = "native")]
+    fn read_entries(&self) -> parking_lot::RwLockReadGuard<'_, HashMap<String, FileMeta>> {
+        self.entries.read()
+    }
+
+    #[cfg(not(feature = "native"))]
+    fn read_entries(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, FileMeta>> {
+        self.entries.read().unwrap()
+    }
+
+    #[cfg(feature = "native")]
+    fn write_entries(&self) -> parking_lot::RwLockWriteGuard<'_, HashMap<String, FileMeta>> {
+        self.entries.write()
+    }
+
+    #[cfg(not(feature = "native"))]
+    fn write_entries(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, FileMeta>> {
+        self.entries.write().unwrap()
+    }
+}
+
+impl FileSource for MetadataCache {
+    fn entry(&self, path: &str) -> Option<FileEntry> {
+        self.lookup(path).map(|m| m.entry)
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        self.inner.read(path)
+    }
+
+    fn list(&self, path: &str) -> Option<Vec<(String, bool)>> {
+        self.inner.list(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::file_source::{LocalFs, MemorySource};
+
+    #[test]
+    fn test_lookup_caches_and_counts() {
+        let source = MemorySource::from_entries([("css/app.css", "body{}")]).mtime(1_700_000_000);
+        let cache = MetadataCache::new(source);
+
+        let meta = cache.lookup("css/app.css").unwrap();
+        assert_eq!(meta.entry.size, 6);
+        assert_eq!(meta.mime, "text/css; charset=utf-8");
+        assert_eq!(meta.etag, "\"6553f100-6\"");
+
+        // Second lookup comes from the cache
+        assert_eq!(cache.lookup("css/app.css"), Some(meta));
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.entries, 1);
+
+        // Missing paths are not cached
+        assert!(cache.lookup("missing.txt").is_none());
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[test]
+    fn test_revalidate_drops_changed_entries() {
+        let dir = std::env::temp_dir().join(format!(
+            "gust-metadata-cache-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), b"hello").unwrap();
+        std::fs::write(dir.join("b.txt"), b"world").unwrap();
+
+        let cache = MetadataCache::new(LocalFs::new(&dir));
+        assert_eq!(cache.lookup("a.txt").unwrap().entry.size, 5);
+        assert_eq!(cache.lookup("b.txt").unwrap().entry.size, 5);
+
+        // Unchanged files survive revalidation
+        assert_eq!(cache.revalidate(), 0);
+
+        std::fs::write(dir.join("a.txt"), b"hello again").unwrap();
+        std::fs::remove_file(dir.join("b.txt")).unwrap();
+        assert_eq!(cache.revalidate(), 2);
+
+        let stats = cache.stats();
+        assert_eq!(stats.invalidations, 2);
+        assert_eq!(stats.entries, 0);
+        assert_eq!(cache.lookup("a.txt").unwrap().entry.size, 11);
+        assert!(cache.lookup("b.txt").is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_peek_and_invalidate() {
+        let source = MemorySource::from_entries([("index.html", "<html></html>")]);
+        let cache = MetadataCache::new(source);
+
+        assert!(cache.peek("index.html").is_none());
+        cache.lookup("index.html").unwrap();
+        assert!(cache.peek("index.html").is_some());
+
+        assert!(cache.invalidate("index.html"));
+        assert!(!cache.invalidate("index.html"));
+        assert_eq!(cache.stats().invalidations, 1);
+
+        // Peek neither counts nor fills
+        assert!(cache.peek("index.html").is_none());
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.misses, 1);
+    }
+}
@@ -4,14 +4,48 @@
 
 pub mod websocket;
 pub mod sse;
+pub mod send_queue;
+pub mod file_source;
+pub mod hot_cache;
+pub mod metadata_cache;
 pub mod static_files;
 pub mod health;
+pub mod graphql;
+pub mod jsonrpc;
+pub mod tus;
+pub mod well_known;
 
 pub use websocket::{
     WebSocket, WebSocketMessage, WebSocketHandler,
     Frame, Opcode, CloseFrame,
-    is_websocket_upgrade, generate_accept_key, upgrade_response,
+    FrameDecoder, FrameDecoderConfig, ProtocolError,
+    ConnectionState, WebSocketConnection, WebSocketEvent,
+    ExtensionOffer, UpgradeNegotiation,
+    negotiate_subprotocol, negotiate_extensions, parse_extension_offers,
+    is_websocket_upgrade, generate_accept_key, upgrade_response, upgrade_response_with,
 };
 pub use sse::{Sse, SseEvent, SseStream};
-pub use static_files::{StaticFiles, StaticFileConfig};
+pub use send_queue::{OverflowPolicy, PushOutcome, SendQueue, SseQueue};
+pub use file_source::{FileEntry, FileSource, LocalFs, MemorySource};
+#[cfg(feature = "native")]
+pub use file_source::S3Source;
+pub use hot_cache::{HotFileCache, HotFileCacheConfig, HotFileCacheStats};
+pub use metadata_cache::{FileMeta, MetadataCache, MetadataCacheStats};
+pub use static_files::{StaticFiles, StaticFileConfig, ListingSort};
 pub use health::{Health, HealthCheck, HealthStatus};
+pub use graphql::{
+    GraphQLRequest, GraphQLPayload, GraphQLHttpError,
+    parse_get_request as parse_graphql_get, parse_post_request as parse_graphql_post,
+    persisted_query_hash, apq_hash, graphiql_html,
+};
+pub use jsonrpc::{
+    JsonRpcRequest, JsonRpcPayload, JsonRpcError, JsonRpcId,
+    parse_request_body as parse_jsonrpc_body,
+    success_response as jsonrpc_success_response,
+    error_response as jsonrpc_error_response,
+};
+pub use tus::{
+    Tus, TusConfig, TusStore, UploadInfo,
+    DiskStore as TusDiskStore, MemoryStore as TusMemoryStore,
+};
+pub use well_known::WellKnown;
@@ -0,0 +1,213 @@
+//! Bounded per-connection send queue
+//!
+//! Broadcast fan-out (WebSocket rooms, SSE feeds) queues outgoing
+//! items per connection; a slow consumer must not balloon memory.
+//! [`SendQueue`] caps the queue and applies a configurable
+//! [`OverflowPolicy`] when a publisher outruns the connection.
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+
+/// What to do when a connection's outgoing queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Evict the oldest queued item to make room (lossy, bounded)
+    DropOldest,
+    /// Mark the connection for closing; the consumer is too slow
+    CloseConnection,
+    /// Block the publishing thread until the consumer drains
+    BlockPublisher,
+}
+
+/// Result of a [`SendQueue::push`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// Item queued within the limit
+    Queued,
+    /// Item queued after evicting the oldest entry
+    DroppedOldest,
+    /// Queue is marked closed; the item was discarded
+    Closed,
+}
+
+struct QueueState<T> {
+    items: VecDeque<T>,
+    closed: bool,
+    dropped: u64,
+}
+
+/// Bounded FIFO between publishers and one connection's writer
+///
+/// Publishers call [`push`](Self::push) from any thread; the
+/// connection driver drains with [`take`](Self::take). With
+/// [`OverflowPolicy::CloseConnection`] the driver should check
+/// [`is_closed`](Self::is_closed) after handler callbacks and start
+/// the close handshake.
+pub struct SendQueue<T> {
+    state: Mutex<QueueState<T>>,
+    space: Condvar,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+impl<T> SendQueue<T> {
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                items: VecDeque::new(),
+                closed: false,
+                dropped: 0,
+            }),
+            space: Condvar::new(),
+            capacity: capacity.max(1),
+            policy,
+        }
+    }
+
+    /// Queue with no practical bound (for trusted, low-volume sends)
+    pub fn unbounded() -> Self {
+        Self::new(usize::MAX, OverflowPolicy::DropOldest)
+    }
+
+    /// Queue an item, applying the overflow policy when full
+    pub fn push(&self, item: T) -> PushOutcome {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(_) => return PushOutcome::Closed,
+        };
+        if state.closed {
+            return PushOutcome::Closed;
+        }
+        if state.items.len() < self.capacity {
+            state.items.push_back(item);
+            return PushOutcome::Queued;
+        }
+        match self.policy {
+            OverflowPolicy::DropOldest => {
+                state.items.pop_front();
+                state.dropped += 1;
+                state.items.push_back(item);
+                PushOutcome::DroppedOldest
+            }
+            OverflowPolicy::CloseConnection => {
+                state.closed = true;
+                state.dropped += 1;
+                PushOutcome::Closed
+            }
+            OverflowPolicy::BlockPublisher => {
+                while state.items.len() >= self.capacity && !state.closed {
+                    state = match self.space.wait(state) {
+                        Ok(state) => state,
+                        Err(_) => return PushOutcome::Closed,
+                    };
+                }
+                if state.closed {
+                    return PushOutcome::Closed;
+                }
+                state.items.push_back(item);
+                PushOutcome::Queued
+            }
+        }
+    }
+
+    /// Drain everything queued, waking blocked publishers
+    pub fn take(&self) -> Vec<T> {
+        let Ok(mut state) = self.state.lock() else {
+            return Vec::new();
+        };
+        let items = state.items.drain(..).collect();
+        self.space.notify_all();
+        items
+    }
+
+    /// Items currently queued
+    pub fn len(&self) -> usize {
+        self.state.lock().map(|s| s.items.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// True once the overflow policy closed the queue (or [`close`](Self::close) ran)
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().map(|s| s.closed).unwrap_or(true)
+    }
+
+    /// Items discarded by the overflow policy so far
+    pub fn dropped(&self) -> u64 {
+        self.state.lock().map(|s| s.dropped).unwrap_or(0)
+    }
+
+    /// Refuse further pushes and wake blocked publishers
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.space.notify_all();
+    }
+}
+
+/// Per-connection queue of SSE events for broadcast fan-out
+pub type SseQueue = SendQueue<super::sse::SseEvent>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_oldest_evicts_front() {
+        let queue = SendQueue::new(2, OverflowPolicy::DropOldest);
+        assert_eq!(queue.push(1), PushOutcome::Queued);
+        assert_eq!(queue.push(2), PushOutcome::Queued);
+        assert_eq!(queue.push(3), PushOutcome::DroppedOldest);
+        assert_eq!(queue.take(), vec![2, 3]);
+        assert_eq!(queue.dropped(), 1);
+        assert!(!queue.is_closed());
+    }
+
+    #[test]
+    fn test_close_connection_marks_closed() {
+        let queue = SendQueue::new(1, OverflowPolicy::CloseConnection);
+        assert_eq!(queue.push("a"), PushOutcome::Queued);
+        assert_eq!(queue.push("b"), PushOutcome::Closed);
+        assert!(queue.is_closed());
+        // Buffered items still drain so a close frame can follow them
+        assert_eq!(queue.take(), vec!["a"]);
+        assert_eq!(queue.push("c"), PushOutcome::Closed);
+    }
+
+    #[test]
+    fn test_block_publisher_waits_for_drain() {
+        use std::sync::Arc;
+
+        let queue = Arc::new(SendQueue::new(1, OverflowPolicy::BlockPublisher));
+        queue.push(1);
+
+        let publisher = {
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || queue.push(2))
+        };
+        // Give the publisher time to block, then drain to release it
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert_eq!(queue.take(), vec![1]);
+        assert_eq!(publisher.join().unwrap(), PushOutcome::Queued);
+        assert_eq!(queue.take(), vec![2]);
+    }
+
+    #[test]
+    fn test_close_releases_blocked_publisher() {
+        use std::sync::Arc;
+
+        let queue = Arc::new(SendQueue::new(1, OverflowPolicy::BlockPublisher));
+        queue.push(1);
+
+        let publisher = {
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || queue.push(2))
+        };
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        queue.close();
+        assert_eq!(publisher.join().unwrap(), PushOutcome::Closed);
+    }
+}
@@ -16,6 +16,8 @@ pub struct SseEvent {
     pub data: String,
     /// Retry interval in milliseconds
     pub retry: Option<u32>,
+    /// Comment emitted before the fields (keep-alives, debugging)
+    pub comment: Option<String>,
 }
 
 impl SseEvent {
@@ -27,6 +29,14 @@ impl SseEvent {
         }
     }
 
+    /// Create a comment-only event (`: text`), used for keep-alives
+    pub fn comment(text: impl Into<String>) -> Self {
+        Self {
+            comment: Some(text.into()),
+            ..Default::default()
+        }
+    }
+
     /// Set event ID
     pub fn id(mut self, id: impl Into<String>) -> Self {
         self.id = Some(id.into());
@@ -46,18 +56,31 @@ impl SseEvent {
     }
 
     /// Serialize to SSE format
+    ///
+    /// Field values are sanitized so a `\n` or `\r` smuggled into an
+    /// id or event name cannot inject extra fields into the stream;
+    /// data is split on any newline convention into `data:` lines.
     pub fn to_string(&self) -> String {
         let mut output = String::new();
 
+        if let Some(ref comment) = self.comment {
+            for line in split_universal_lines(comment) {
+                output.push_str(": ");
+                output.push_str(line);
+                output.push('\n');
+            }
+        }
+
         if let Some(ref id) = self.id {
             output.push_str("id: ");
-            output.push_str(id);
+            // The spec additionally forbids NUL in the id field
+            output.push_str(&sanitize_field(id).replace('\0', ""));
             output.push('\n');
         }
 
         if let Some(ref event) = self.event {
             output.push_str("event: ");
-            output.push_str(event);
+            output.push_str(&sanitize_field(event));
             output.push('\n');
         }
 
@@ -68,10 +91,12 @@ impl SseEvent {
         }
 
         // Data can be multiple lines
-        for line in self.data.lines() {
-            output.push_str("data: ");
-            output.push_str(line);
-            output.push('\n');
+        if !self.data.is_empty() {
+            for line in split_universal_lines(&self.data) {
+                output.push_str("data: ");
+                output.push_str(line);
+                output.push('\n');
+            }
         }
 
         output.push('\n'); // Empty line to end event
@@ -84,6 +109,40 @@ impl SseEvent {
     }
 }
 
+/// Strip `\r`/`\n` from single-line field values
+fn sanitize_field(value: &str) -> String {
+    value.chars().filter(|c| *c != '\r' && *c != '\n').collect()
+}
+
+/// Split on `\r\n`, `\r`, or `\n` — the conventions EventSource accepts
+///
+/// A trailing newline does not produce an empty final line.
+fn split_universal_lines(text: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let bytes = text.as_bytes();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\r' => {
+                lines.push(&text[start..i]);
+                i += if bytes.get(i + 1) == Some(&b'\n') { 2 } else { 1 };
+                start = i;
+            }
+            b'\n' => {
+                lines.push(&text[start..i]);
+                i += 1;
+                start = i;
+            }
+            _ => i += 1,
+        }
+    }
+    if start < text.len() {
+        lines.push(&text[start..]);
+    }
+    lines
+}
+
 /// SSE Stream for sending multiple events
 pub struct SseStream {
     events: Vec<SseEvent>,
@@ -118,14 +177,7 @@ impl SseStream {
 
     /// Add a comment (for keep-alive)
     pub fn comment(&mut self, text: impl Into<String>) {
-        let mut output = String::from(": ");
-        output.push_str(&text.into());
-        output.push('\n');
-        // Store as raw data event (hacky but works)
-        self.events.push(SseEvent {
-            data: output,
-            ..Default::default()
-        });
+        self.events.push(SseEvent::comment(text));
     }
 
     /// Get last event ID
@@ -281,4 +333,32 @@ mod tests {
         let ka = Sse::keep_alive();
         assert_eq!(ka, ": keep-alive\n\n");
     }
+
+    #[test]
+    fn test_comment_event() {
+        let event = SseEvent::comment("keep-alive");
+        assert_eq!(event.to_string(), ": keep-alive\n\n");
+
+        let mut stream = SseStream::new();
+        stream.comment("hb");
+        assert_eq!(stream.to_string(), ": hb\n\n");
+    }
+
+    #[test]
+    fn test_field_values_cannot_inject_fields() {
+        let event = SseEvent::new("ok").id("1\nevent: fake").event("up\r\ndata: x");
+        let output = event.to_string();
+        assert!(output.contains("id: 1event: fake\n"));
+        assert!(output.contains("event: updata: x\n"));
+        assert!(!output.contains("\nevent: fake\n"));
+    }
+
+    #[test]
+    fn test_data_splits_on_any_newline_convention() {
+        let event = SseEvent::new("a\r\nb\rc\nd\n");
+        assert_eq!(
+            event.to_string(),
+            "data: a\ndata: b\ndata: c\ndata: d\n\n"
+        );
+    }
 }
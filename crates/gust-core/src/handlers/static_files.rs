@@ -1,10 +1,28 @@
 //! Static file serving handler
 //!
 //! Efficient static file serving with caching and range support.
+//! Assets are read through a pluggable [`FileSource`] backend.
 
 use crate::{Request, Response, ResponseBuilder, StatusCode, Method};
-use std::path::{Path, PathBuf};
+use crate::middleware::compress::{preferred_encodings, Encoding};
+use super::file_source::{FileEntry, FileSource, LocalFs};
+use super::hot_cache::{HotFileCache, HotFileCacheConfig, HotFileCacheStats};
+use super::metadata_cache::MetadataCache;
+use bytes::Bytes;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Directory listing sort order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingSort {
+    /// Directories first, then files, each alphabetical (default)
+    DirsFirst,
+    /// Plain alphabetical, directories and files mixed
+    Name,
+    /// Backend order, unsorted
+    Unsorted,
+}
 
 /// Static file configuration
 #[derive(Clone)]
@@ -15,10 +33,20 @@ pub struct StaticFileConfig {
     pub index: String,
     /// Enable directory listing
     pub listing: bool,
+    /// Custom listing HTML template with `{path}` and `{entries}`
+    /// placeholders; `None` uses the built-in page
+    pub listing_template: Option<String>,
+    /// Listing sort order
+    pub listing_sort: ListingSort,
+    /// Names hidden from listings (exact names or `*.ext` patterns)
+    pub listing_hide: Vec<String>,
     /// Cache max-age in seconds
     pub max_age: u32,
     /// Enable ETag
     pub etag: bool,
+    /// Emit weak (`W/`-prefixed) ETags; mtime+size validators are
+    /// semantically weak, strong is kept as the compatible default
+    pub weak_etags: bool,
     /// Enable Last-Modified
     pub last_modified: bool,
     /// Custom headers
@@ -27,6 +55,11 @@ pub struct StaticFileConfig {
     pub hidden: bool,
     /// Fallback file (for SPA)
     pub fallback: Option<String>,
+    /// Serve precompressed sibling files (`asset.br`, `asset.gz`)
+    /// matching the request's Accept-Encoding
+    pub precompressed: bool,
+    /// Keep small file bodies in a RAM cache
+    pub hot_cache: Option<HotFileCacheConfig>,
 }
 
 impl Default for StaticFileConfig {
@@ -35,12 +68,18 @@ impl Default for StaticFileConfig {
             root: PathBuf::from("."),
             index: "index.html".to_string(),
             listing: false,
+            listing_template: None,
+            listing_sort: ListingSort::DirsFirst,
+            listing_hide: Vec::new(),
             max_age: 86400, // 1 day
             etag: true,
+            weak_etags: false,
             last_modified: true,
             headers: HashMap::new(),
             hidden: false,
             fallback: None,
+            precompressed: false,
+            hot_cache: None,
         }
     }
 }
@@ -63,6 +102,21 @@ impl StaticFileConfig {
         self
     }
 
+    pub fn listing_template(mut self, template: impl Into<String>) -> Self {
+        self.listing_template = Some(template.into());
+        self
+    }
+
+    pub fn listing_sort(mut self, sort: ListingSort) -> Self {
+        self.listing_sort = sort;
+        self
+    }
+
+    pub fn listing_hide(mut self, pattern: impl Into<String>) -> Self {
+        self.listing_hide.push(pattern.into());
+        self
+    }
+
     pub fn max_age(mut self, seconds: u32) -> Self {
         self.max_age = seconds;
         self
@@ -73,6 +127,11 @@ impl StaticFileConfig {
         self
     }
 
+    pub fn weak_etags(mut self, enabled: bool) -> Self {
+        self.weak_etags = enabled;
+        self
+    }
+
     pub fn fallback(mut self, file: impl Into<String>) -> Self {
         self.fallback = Some(file.into());
         self
@@ -82,16 +141,77 @@ impl StaticFileConfig {
         self.headers.insert(key.into(), value.into());
         self
     }
+
+    pub fn precompressed(mut self, enabled: bool) -> Self {
+        self.precompressed = enabled;
+        self
+    }
+
+    pub fn hot_cache(mut self, config: HotFileCacheConfig) -> Self {
+        self.hot_cache = Some(config);
+        self
+    }
 }
 
 /// Static file handler
 pub struct StaticFiles {
     config: StaticFileConfig,
+    source: Box<dyn FileSource>,
+    metadata: Option<Arc<MetadataCache>>,
+    hot: Option<HotFileCache>,
 }
 
 impl StaticFiles {
+    /// Serve from the local filesystem at `config.root`
     pub fn new(config: StaticFileConfig) -> Self {
-        Self { config }
+        let source = Box::new(LocalFs::new(config.root.clone()));
+        let hot = config.hot_cache.clone().map(HotFileCache::new);
+        Self {
+            config,
+            source,
+            metadata: None,
+            hot,
+        }
+    }
+
+    /// Serve from a custom backend (embedded bundle, S3, ...)
+    ///
+    /// `config.root` is ignored; paths are resolved by the source.
+    pub fn with_source(config: StaticFileConfig, source: impl FileSource + 'static) -> Self {
+        let hot = config.hot_cache.clone().map(HotFileCache::new);
+        Self {
+            config,
+            source: Box::new(source),
+            metadata: None,
+            hot,
+        }
+    }
+
+    /// Serve from the local filesystem with cached file metadata
+    ///
+    /// Size, mtime, ETag and MIME type are cached per path in a
+    /// [`MetadataCache`], so hot assets skip the stat syscall on repeat
+    /// requests. Use [`metadata_cache`](Self::metadata_cache) to read
+    /// stats or start the revalidation watcher.
+    pub fn cached(config: StaticFileConfig) -> Self {
+        let cache = Arc::new(MetadataCache::new(LocalFs::new(config.root.clone())));
+        let hot = config.hot_cache.clone().map(HotFileCache::new);
+        Self {
+            config,
+            source: Box::new(Arc::clone(&cache)),
+            metadata: Some(cache),
+            hot,
+        }
+    }
+
+    /// The metadata cache, when built via [`cached`](Self::cached)
+    pub fn metadata_cache(&self) -> Option<&Arc<MetadataCache>> {
+        self.metadata.as_ref()
+    }
+
+    /// Hot file cache counters, when `config.hot_cache` is set
+    pub fn hot_cache_stats(&self) -> Option<HotFileCacheStats> {
+        self.hot.as_ref().map(|c| c.stats())
     }
 
     /// Serve static files from directory
@@ -102,6 +222,16 @@ impl StaticFiles {
     /// Handle request for static file
     #[cfg(feature = "native")]
     pub async fn handle(&self, req: &Request) -> Response {
+        self.handle_inner(req)
+    }
+
+    /// Handle request for static file
+    #[cfg(not(feature = "native"))]
+    pub fn handle(&self, req: &Request) -> Response {
+        self.handle_inner(req)
+    }
+
+    fn handle_inner(&self, req: &Request) -> Response {
         // Only handle GET and HEAD
         if req.method != Method::Get && req.method != Method::Head {
             return ResponseBuilder::new(StatusCode::METHOD_NOT_ALLOWED)
@@ -110,83 +240,44 @@ impl StaticFiles {
         }
 
         // Sanitize path
-        let path = self.sanitize_path(&req.path);
-        if path.is_none() {
-            return self.not_found();
-        }
-        let path = path.unwrap();
-
-        // Check if file exists
-        let full_path = self.config.root.join(&path);
+        let path = match self.sanitize_path(&req.path) {
+            Some(p) => p,
+            None => return self.not_found(),
+        };
+        let path = path.to_string_lossy().replace('\\', "/");
 
-        match tokio::fs::metadata(&full_path).await {
-            Ok(meta) => {
-                if meta.is_dir() {
+        match self.source.entry(&path) {
+            Some(entry) => {
+                if entry.is_dir {
                     // Try index file
-                    let index_path = full_path.join(&self.config.index);
-                    if let Ok(index_meta) = tokio::fs::metadata(&index_path).await {
-                        if index_meta.is_file() {
-                            return self.serve_file(&index_path, &index_meta, req).await;
+                    let index_path = join_path(&path, &self.config.index);
+                    if let Some(index_entry) = self.source.entry(&index_path) {
+                        if !index_entry.is_dir {
+                            return self.serve_file(&index_path, &index_entry, req);
                         }
                     }
 
                     // Directory listing
                     if self.config.listing {
-                        return self.list_directory(&full_path, &req.path).await;
+                        return self.list_directory(&path, req);
                     }
 
                     self.not_found()
                 } else {
-                    self.serve_file(&full_path, &meta, req).await
+                    self.serve_file(&path, &entry, req)
                 }
             }
-            Err(_) => {
+            None => {
                 // Try fallback
                 if let Some(ref fallback) = self.config.fallback {
-                    let fallback_path = self.config.root.join(fallback);
-                    if let Ok(meta) = tokio::fs::metadata(&fallback_path).await {
-                        return self.serve_file(&fallback_path, &meta, req).await;
-                    }
-                }
-                self.not_found()
-            }
-        }
-    }
-
-    /// Synchronous handle (for non-native)
-    #[cfg(not(feature = "native"))]
-    pub fn handle(&self, req: &Request) -> Response {
-        // Only handle GET and HEAD
-        if req.method != Method::Get && req.method != Method::Head {
-            return ResponseBuilder::new(StatusCode::METHOD_NOT_ALLOWED)
-                .body("Method not allowed")
-                .build();
-        }
-
-        // Sanitize path
-        let path = match self.sanitize_path(&req.path) {
-            Some(p) => p,
-            None => return self.not_found(),
-        };
-
-        // Check if file exists
-        let full_path = self.config.root.join(&path);
-
-        match std::fs::metadata(&full_path) {
-            Ok(meta) => {
-                if meta.is_dir() {
-                    let index_path = full_path.join(&self.config.index);
-                    if let Ok(index_meta) = std::fs::metadata(&index_path) {
-                        if index_meta.is_file() {
-                            return self.serve_file_sync(&index_path, &index_meta, req);
+                    if let Some(entry) = self.source.entry(fallback) {
+                        if !entry.is_dir {
+                            return self.serve_file(fallback, &entry, req);
                         }
                     }
-                    self.not_found()
-                } else {
-                    self.serve_file_sync(&full_path, &meta, req)
                 }
+                self.not_found()
             }
-            Err(_) => self.not_found(),
         }
     }
 
@@ -212,42 +303,95 @@ impl StaticFiles {
         Some(result)
     }
 
-    #[cfg(feature = "native")]
-    async fn serve_file(
-        &self,
-        path: &Path,
-        meta: &std::fs::Metadata,
-        req: &Request,
-    ) -> Response {
-        // Check ETag
-        if self.config.etag {
-            let etag = self.generate_etag(meta);
-            if let Some(if_none_match) = req.header("if-none-match") {
-                if if_none_match == etag {
+    fn serve_file(&self, path: &str, entry: &FileEntry, req: &Request) -> Response {
+        // Reuse cached ETag/MIME when the metadata cache is active
+        let cached = self.metadata.as_ref().and_then(|c| c.peek(path));
+
+        // Pick a precompressed sibling (e.g. app.js.br) when acceptable
+        let variant = if self.config.precompressed {
+            self.select_variant(path, req)
+        } else {
+            None
+        };
+
+        // Check ETag (per selected representation, hence the encoding suffix)
+        let etag = self.config.etag.then(|| {
+            let base = match &cached {
+                Some(meta) => meta.etag.clone(),
+                None => entry_etag(entry),
+            };
+            let tag = match &variant {
+                Some((_, encoding, _)) => {
+                    format!("\"{}-{}\"", base.trim_matches('"'), encoding.as_str())
+                }
+                None => base,
+            };
+            if self.config.weak_etags {
+                format!("W/{}", tag)
+            } else {
+                tag
+            }
+        });
+        if let (Some(etag), Some(if_none_match)) = (&etag, req.header("if-none-match")) {
+            if crate::middleware::range::check_if_none_match(if_none_match, etag) {
+                return ResponseBuilder::new(StatusCode::NOT_MODIFIED).body("").build();
+            }
+        }
+
+        // Check If-Modified-Since (only consulted without If-None-Match)
+        if self.config.last_modified && req.header("if-none-match").is_none() {
+            if let (Some(mtime), Some(since)) = (entry.mtime, req.header("if-modified-since")) {
+                if crate::middleware::range::check_if_modified_since(since, mtime) {
                     return ResponseBuilder::new(StatusCode::NOT_MODIFIED).body("").build();
                 }
             }
         }
 
         // Read file
-        let content = match tokio::fs::read(path).await {
-            Ok(c) => c,
-            Err(_) => return self.not_found(),
+        let content = match &variant {
+            Some((variant_path, _, variant_entry)) => {
+                self.read_content(variant_path, variant_entry)
+            }
+            None => self.read_content(path, entry),
+        };
+        let content = match content {
+            Some(c) => c,
+            None => return self.not_found(),
         };
 
         // Build response
+        let mime = match &cached {
+            Some(meta) => meta.mime,
+            None => file_mime_type(Path::new(path)),
+        };
         let mut builder = ResponseBuilder::new(StatusCode::OK)
-            .header("Content-Type", self.mime_type(path))
+            .header("Content-Type", mime)
             .header("Content-Length", &content.len().to_string());
 
-        if self.config.etag {
-            builder = builder.header("ETag", &self.generate_etag(meta));
+        if let Some(etag) = &etag {
+            builder = builder.header("ETag", etag);
+        }
+
+        if self.config.last_modified {
+            if let Some(mtime) = entry.mtime {
+                builder = builder.header(
+                    "Last-Modified",
+                    crate::pure::http_date::format_http_date(mtime),
+                );
+            }
         }
 
         if self.config.max_age > 0 {
             builder = builder.header("Cache-Control", &format!("max-age={}", self.config.max_age));
         }
 
+        if let Some((_, encoding, _)) = &variant {
+            builder = builder.header("Content-Encoding", encoding.as_str());
+        }
+        if self.config.precompressed {
+            builder = builder.header("Vary", "Accept-Encoding");
+        }
+
         for (k, v) in &self.config.headers {
             builder = builder.header(k, v);
         }
@@ -260,88 +404,143 @@ impl StaticFiles {
         }
     }
 
-    #[cfg(not(feature = "native"))]
-    fn serve_file_sync(
-        &self,
-        path: &Path,
-        meta: &std::fs::Metadata,
-        req: &Request,
-    ) -> Response {
-        let content = match std::fs::read(path) {
-            Ok(c) => c,
-            Err(_) => return self.not_found(),
-        };
-
-        let mut builder = ResponseBuilder::new(StatusCode::OK)
-            .header("Content-Type", self.mime_type(path))
-            .header("Content-Length", &content.len().to_string());
-
-        if self.config.etag {
-            builder = builder.header("ETag", &self.generate_etag(meta));
+    /// Pick a precompressed sibling file matching Accept-Encoding
+    ///
+    /// Candidates are tried in the client's q-value order (brotli
+    /// preferred on ties), sharing the compress middleware's
+    /// negotiation rules.
+    fn select_variant(&self, path: &str, req: &Request) -> Option<(String, Encoding, FileEntry)> {
+        let accept = req.header("accept-encoding")?;
+        for encoding in preferred_encodings(accept) {
+            let ext = match encoding {
+                Encoding::Brotli => "br",
+                Encoding::Gzip => "gz",
+                _ => continue,
+            };
+            let candidate = format!("{}.{}", path, ext);
+            if let Some(entry) = self.source.entry(&candidate) {
+                if !entry.is_dir {
+                    return Some((candidate, encoding, entry));
+                }
+            }
         }
+        None
+    }
 
-        if req.method == Method::Head {
-            builder.body("").build()
-        } else {
-            builder.body(content).build()
+    /// Read a body, serving it from the hot cache when possible
+    ///
+    /// Cache keys include mtime and size, so a changed file rolls over
+    /// to a fresh entry instead of serving stale bytes; the old entry
+    /// ages out through LRU eviction or TTL.
+    fn read_content(&self, path: &str, entry: &FileEntry) -> Option<Bytes> {
+        let cache = match &self.hot {
+            Some(c) => c,
+            None => return self.source.read(path).map(Bytes::from),
+        };
+        let key = format!("{}#{:x}-{:x}", path, entry.mtime.unwrap_or(0), entry.size);
+        if let Some(body) = cache.get(&key) {
+            return Some(body);
         }
+        let body = Bytes::from(self.source.read(path)?);
+        cache.insert(&key, body.clone());
+        Some(body)
     }
 
-    #[cfg(feature = "native")]
-    async fn list_directory(&self, path: &Path, request_path: &str) -> Response {
-        let mut entries = Vec::new();
-
-        let mut dir = match tokio::fs::read_dir(path).await {
-            Ok(d) => d,
-            Err(_) => return self.not_found(),
+    fn list_directory(&self, path: &str, req: &Request) -> Response {
+        let mut entries = match self.source.list(path) {
+            Some(e) => e,
+            None => return self.not_found(),
         };
 
-        while let Ok(Some(entry)) = dir.next_entry().await {
-            let name = entry.file_name().to_string_lossy().to_string();
-            if !self.config.hidden && name.starts_with('.') {
-                continue;
-            }
-
-            let is_dir = entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false);
-            entries.push((name, is_dir));
+        if !self.config.hidden {
+            entries.retain(|(name, _)| !name.starts_with('.'));
+        }
+        if !self.config.listing_hide.is_empty() {
+            entries.retain(|(name, _)| {
+                !self
+                    .config
+                    .listing_hide
+                    .iter()
+                    .any(|pattern| hide_pattern_matches(pattern, name))
+            });
         }
 
-        entries.sort_by(|a, b| {
-            match (a.1, b.1) {
+        match self.config.listing_sort {
+            ListingSort::DirsFirst => entries.sort_by(|a, b| match (a.1, b.1) {
                 (true, false) => std::cmp::Ordering::Less,
                 (false, true) => std::cmp::Ordering::Greater,
                 _ => a.0.cmp(&b.0),
-            }
-        });
+            }),
+            ListingSort::Name => entries.sort_by(|a, b| a.0.cmp(&b.0)),
+            ListingSort::Unsorted => {}
+        }
+
+        if wants_json_listing(req) {
+            return ResponseBuilder::new(StatusCode::OK)
+                .header("Content-Type", "application/json")
+                .body(self.render_listing_json(&req.path, &entries))
+                .build();
+        }
 
-        let html = self.render_listing(request_path, &entries);
+        let html = self.render_listing(&req.path, &entries);
         ResponseBuilder::new(StatusCode::OK)
             .header("Content-Type", "text/html; charset=utf-8")
             .body(html)
             .build()
     }
 
-    fn render_listing(&self, path: &str, entries: &[(String, bool)]) -> String {
-        let mut html = String::from("<!DOCTYPE html><html><head><meta charset=\"utf-8\">");
-        html.push_str(&format!("<title>Index of {}</title>", path));
-        html.push_str("<style>body{font-family:monospace;padding:20px}a{text-decoration:none}a:hover{text-decoration:underline}</style>");
-        html.push_str("</head><body>");
-        html.push_str(&format!("<h1>Index of {}</h1>", path));
-        html.push_str("<hr><pre>");
+    /// Render listing entries as a JSON document
+    fn render_listing_json(&self, path: &str, entries: &[(String, bool)]) -> String {
+        use crate::pure::json::write_json_string;
 
-        if path != "/" {
-            html.push_str("<a href=\"..\">..</a>\n");
+        let mut out = String::from("{\"path\":");
+        write_json_string(path, &mut out);
+        out.push_str(",\"entries\":[");
+        for (i, (name, is_dir)) in entries.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"name\":");
+            write_json_string(name, &mut out);
+            out.push_str(",\"type\":");
+            out.push_str(if *is_dir {
+                "\"directory\""
+            } else {
+                "\"file\""
+            });
+            out.push('}');
         }
+        out.push_str("]}");
+        out
+    }
 
+    fn render_listing(&self, path: &str, entries: &[(String, bool)]) -> String {
+        let mut links = String::new();
+        if path != "/" {
+            links.push_str("<a href=\"..\">..</a>\n");
+        }
         for (name, is_dir) in entries {
             let display = if *is_dir {
                 format!("{}/", name)
             } else {
                 name.clone()
             };
-            html.push_str(&format!("<a href=\"{}\">{}</a>\n", name, display));
+            links.push_str(&format!("<a href=\"{}\">{}</a>\n", name, display));
+        }
+
+        if let Some(template) = &self.config.listing_template {
+            return template
+                .replace("{path}", path)
+                .replace("{entries}", &links);
         }
 
+        let mut html = String::from("<!DOCTYPE html><html><head><meta charset=\"utf-8\">");
+        html.push_str(&format!("<title>Index of {}</title>", path));
+        html.push_str("<style>body{font-family:monospace;padding:20px}a{text-decoration:none}a:hover{text-decoration:underline}</style>");
+        html.push_str("</head><body>");
+        html.push_str(&format!("<h1>Index of {}</h1>", path));
+        html.push_str("<hr><pre>");
+        html.push_str(&links);
         html.push_str("</pre><hr></body></html>");
         html
     }
@@ -352,76 +551,101 @@ impl StaticFiles {
             .body("Not Found")
             .build()
     }
+}
+
+/// ETag derived from an entry's mtime and size
+pub(crate) fn entry_etag(entry: &FileEntry) -> String {
+    format!("\"{:x}-{:x}\"", entry.mtime.unwrap_or(0), entry.size)
+}
+
+/// MIME type for a served file path (extension-based)
+pub(crate) fn file_mime_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+
+    match ext.to_lowercase().as_str() {
+        // Text
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain; charset=utf-8",
+        "md" => "text/markdown; charset=utf-8",
+        "csv" => "text/csv",
+
+        // Images
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+
+        // Audio/Video
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+
+        // Fonts
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "eot" => "application/vnd.ms-fontobject",
+
+        // Archives
+        "zip" => "application/zip",
+        "gz" | "gzip" => "application/gzip",
+        "tar" => "application/x-tar",
+
+        // Documents
+        "pdf" => "application/pdf",
+
+        // WebAssembly
+        "wasm" => "application/wasm",
+
+        // Default
+        _ => "application/octet-stream",
+    }
+}
 
-    fn generate_etag(&self, meta: &std::fs::Metadata) -> String {
-        use std::time::UNIX_EPOCH;
-
-        let mtime = meta
-            .modified()
-            .ok()
-            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
-
-        let size = meta.len();
-        format!("\"{:x}-{:x}\"", mtime, size)
-    }
-
-    fn mime_type(&self, path: &Path) -> &'static str {
-        let ext = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("");
-
-        match ext.to_lowercase().as_str() {
-            // Text
-            "html" | "htm" => "text/html; charset=utf-8",
-            "css" => "text/css; charset=utf-8",
-            "js" | "mjs" => "text/javascript; charset=utf-8",
-            "json" => "application/json",
-            "xml" => "application/xml",
-            "txt" => "text/plain; charset=utf-8",
-            "md" => "text/markdown; charset=utf-8",
-            "csv" => "text/csv",
-
-            // Images
-            "png" => "image/png",
-            "jpg" | "jpeg" => "image/jpeg",
-            "gif" => "image/gif",
-            "svg" => "image/svg+xml",
-            "ico" => "image/x-icon",
-            "webp" => "image/webp",
-            "avif" => "image/avif",
-
-            // Audio/Video
-            "mp3" => "audio/mpeg",
-            "ogg" => "audio/ogg",
-            "wav" => "audio/wav",
-            "mp4" => "video/mp4",
-            "webm" => "video/webm",
-
-            // Fonts
-            "woff" => "font/woff",
-            "woff2" => "font/woff2",
-            "ttf" => "font/ttf",
-            "otf" => "font/otf",
-            "eot" => "application/vnd.ms-fontobject",
-
-            // Archives
-            "zip" => "application/zip",
-            "gz" | "gzip" => "application/gzip",
-            "tar" => "application/x-tar",
-
-            // Documents
-            "pdf" => "application/pdf",
-
-            // WebAssembly
-            "wasm" => "application/wasm",
-
-            // Default
-            _ => "application/octet-stream",
+/// Should a directory listing be served as JSON?
+///
+/// Triggered by `?format=json` or an `Accept` header that asks for
+/// `application/json` without `text/html`.
+fn wants_json_listing(req: &Request) -> bool {
+    if let Some(query) = &req.query {
+        if query.split('&').any(|pair| pair == "format=json") {
+            return true;
         }
     }
+    match req.header("accept") {
+        Some(accept) => accept.contains("application/json") && !accept.contains("text/html"),
+        None => false,
+    }
+}
+
+/// Match a listing hide pattern (exact name or `*.ext` suffix)
+fn hide_pattern_matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_prefix('*') {
+        Some(suffix) => name.ends_with(suffix),
+        None => name == pattern,
+    }
+}
+
+/// Join sanitized relative paths with a forward slash
+fn join_path(base: &str, name: &str) -> String {
+    if base.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", base, name)
+    }
 }
 
 #[cfg(test)]
@@ -438,13 +662,286 @@ mod tests {
         assert!(handler.sanitize_path("/.hidden").is_none());
     }
 
+    #[test]
+    fn test_serve_from_memory_source() {
+        use super::super::file_source::MemorySource;
+        use crate::RequestBuilder;
+
+        let source = MemorySource::from_entries([
+            ("index.html", "<html>home</html>"),
+            ("css/app.css", "body{}"),
+        ]);
+        let handler = StaticFiles::with_source(StaticFileConfig::default(), source);
+
+        let req = RequestBuilder::new(Method::Get, "/css/app.css").build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.header("Content-Type"), Some("text/css; charset=utf-8"));
+        assert_eq!(&res.body[..], b"body{}");
+
+        // Root resolves to the index file
+        let req = RequestBuilder::new(Method::Get, "/").build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(&res.body[..], b"<html>home</html>");
+
+        let req = RequestBuilder::new(Method::Get, "/missing.txt").build();
+        assert_eq!(handler.handle_inner(&req).status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_fallback_from_source() {
+        use super::super::file_source::MemorySource;
+        use crate::RequestBuilder;
+
+        let source = MemorySource::from_entries([("index.html", "<html>spa</html>")]);
+        let config = StaticFileConfig::default().fallback("index.html");
+        let handler = StaticFiles::with_source(config, source);
+
+        let req = RequestBuilder::new(Method::Get, "/app/settings").build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(&res.body[..], b"<html>spa</html>");
+    }
+
+    #[test]
+    fn test_last_modified_conditional() {
+        use super::super::file_source::MemorySource;
+        use crate::pure::http_date::format_http_date;
+        use crate::RequestBuilder;
+
+        // 2024-02-29 12:00:00 UTC
+        let mtime = 1709208000;
+        let source = MemorySource::from_entries([("index.html", "<html>home</html>")]).mtime(mtime);
+        let handler = StaticFiles::with_source(StaticFileConfig::default(), source);
+
+        let req = RequestBuilder::new(Method::Get, "/index.html").build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(
+            res.header("Last-Modified"),
+            Some(format_http_date(mtime).as_str())
+        );
+
+        // Not modified since the served date -> 304
+        let req = RequestBuilder::new(Method::Get, "/index.html")
+            .header("If-Modified-Since", format_http_date(mtime))
+            .build();
+        assert_eq!(handler.handle_inner(&req).status, StatusCode::NOT_MODIFIED);
+
+        // Modified after the client's date -> full response
+        let req = RequestBuilder::new(Method::Get, "/index.html")
+            .header("If-Modified-Since", format_http_date(mtime - 86400))
+            .build();
+        assert_eq!(handler.handle_inner(&req).status, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_listing_json_format() {
+        use super::super::file_source::MemorySource;
+        use crate::RequestBuilder;
+
+        let source = MemorySource::from_entries([
+            ("docs/readme.md", "# hi"),
+            ("docs/api/index.json", "{}"),
+        ]);
+        let config = StaticFileConfig::default().listing(true);
+        let handler = StaticFiles::with_source(config, source);
+
+        let mut req = RequestBuilder::new(Method::Get, "/docs").build();
+        req.query = Some("format=json".to_string());
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.header("Content-Type"), Some("application/json"));
+        let body = String::from_utf8(res.body.to_vec()).unwrap();
+        assert_eq!(
+            body,
+            "{\"path\":\"/docs\",\"entries\":[{\"name\":\"api\",\"type\":\"directory\"},{\"name\":\"readme.md\",\"type\":\"file\"}]}"
+        );
+
+        // Accept header works too
+        let req = RequestBuilder::new(Method::Get, "/docs")
+            .header("Accept", "application/json")
+            .build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.header("Content-Type"), Some("application/json"));
+    }
+
+    #[test]
+    fn test_listing_template_and_hide() {
+        use super::super::file_source::MemorySource;
+        use crate::RequestBuilder;
+
+        let source = MemorySource::from_entries([
+            ("files/a.txt", "a"),
+            ("files/b.log", "b"),
+        ]);
+        let config = StaticFileConfig::default()
+            .listing(true)
+            .listing_hide("*.log")
+            .listing_template("<ul data-path=\"{path}\">{entries}</ul>");
+        let handler = StaticFiles::with_source(config, source);
+
+        let req = RequestBuilder::new(Method::Get, "/files").build();
+        let body = String::from_utf8(handler.handle_inner(&req).body.to_vec()).unwrap();
+        assert!(body.starts_with("<ul data-path=\"/files\">"));
+        assert!(body.contains("a.txt"));
+        assert!(!body.contains("b.log"));
+    }
+
+    #[test]
+    fn test_hide_pattern_matches() {
+        assert!(hide_pattern_matches("*.log", "debug.log"));
+        assert!(!hide_pattern_matches("*.log", "debug.txt"));
+        assert!(hide_pattern_matches("Thumbs.db", "Thumbs.db"));
+        assert!(!hide_pattern_matches("Thumbs.db", "thumbs.db"));
+    }
+
     #[test]
     fn test_mime_type() {
-        let handler = StaticFiles::serve(".");
+        assert_eq!(file_mime_type(Path::new("index.html")), "text/html; charset=utf-8");
+        assert_eq!(file_mime_type(Path::new("style.css")), "text/css; charset=utf-8");
+        assert_eq!(file_mime_type(Path::new("image.png")), "image/png");
+        assert_eq!(file_mime_type(Path::new("unknown")), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_weak_etag_generation_and_comparison() {
+        use super::super::file_source::MemorySource;
+        use crate::RequestBuilder;
+
+        let source = MemorySource::from_entries([("index.html", "<html>home</html>")]).mtime(1000);
+        let config = StaticFileConfig::default().weak_etags(true);
+        let handler = StaticFiles::with_source(config, source);
+
+        let req = RequestBuilder::new(Method::Get, "/index.html").build();
+        let res = handler.handle_inner(&req);
+        let etag = res.header("ETag").unwrap().to_string();
+        assert!(etag.starts_with("W/\""));
+
+        // If-None-Match uses weak comparison: the strong form matches too
+        let strong = etag.trim_start_matches("W/").to_string();
+        let req = RequestBuilder::new(Method::Get, "/index.html")
+            .header("If-None-Match", strong)
+            .build();
+        assert_eq!(handler.handle_inner(&req).status, StatusCode::NOT_MODIFIED);
+
+        let req = RequestBuilder::new(Method::Get, "/index.html")
+            .header("If-None-Match", "\"something-else\"")
+            .build();
+        assert_eq!(handler.handle_inner(&req).status, StatusCode::OK);
+    }
 
-        assert_eq!(handler.mime_type(Path::new("index.html")), "text/html; charset=utf-8");
-        assert_eq!(handler.mime_type(Path::new("style.css")), "text/css; charset=utf-8");
-        assert_eq!(handler.mime_type(Path::new("image.png")), "image/png");
-        assert_eq!(handler.mime_type(Path::new("unknown")), "application/octet-stream");
+    #[test]
+    fn test_hot_cache_serves_from_ram() {
+        use super::super::file_source::MemorySource;
+        use crate::RequestBuilder;
+
+        let source = MemorySource::from_entries([("app.js", "void 0")]);
+        let config = StaticFileConfig::default().hot_cache(HotFileCacheConfig::default());
+        let handler = StaticFiles::with_source(config, source);
+
+        let req = RequestBuilder::new(Method::Get, "/app.js").build();
+        assert_eq!(handler.handle_inner(&req).status, StatusCode::OK);
+        let res = handler.handle_inner(&req);
+        assert_eq!(&res.body[..], b"void 0");
+
+        let stats = handler.hot_cache_stats().unwrap();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.bytes, 6);
+    }
+
+    #[test]
+    fn test_precompressed_variant_negotiation() {
+        use super::super::file_source::MemorySource;
+        use crate::RequestBuilder;
+
+        let source = MemorySource::from_entries([
+            ("app.js", "var answer = 42;"),
+            ("app.js.gz", "gz-bytes"),
+            ("app.js.br", "br-bytes"),
+        ]);
+        let config = StaticFileConfig::default()
+            .precompressed(true)
+            .hot_cache(HotFileCacheConfig::default());
+        let handler = StaticFiles::with_source(config, source);
+
+        // Brotli wins when both are acceptable
+        let req = RequestBuilder::new(Method::Get, "/app.js")
+            .header("Accept-Encoding", "gzip, br")
+            .build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(&res.body[..], b"br-bytes");
+        assert_eq!(res.header("Content-Encoding"), Some("br"));
+        assert_eq!(res.header("Content-Type"), Some("text/javascript; charset=utf-8"));
+        assert_eq!(res.header("Vary"), Some("Accept-Encoding"));
+        let br_etag = res.header("ETag").unwrap().to_string();
+        assert!(br_etag.ends_with("-br\""));
+
+        // Conditional request against the variant ETag
+        let req = RequestBuilder::new(Method::Get, "/app.js")
+            .header("Accept-Encoding", "br")
+            .header("If-None-Match", br_etag)
+            .build();
+        assert_eq!(handler.handle_inner(&req).status, StatusCode::NOT_MODIFIED);
+
+        // Gzip only
+        let req = RequestBuilder::new(Method::Get, "/app.js")
+            .header("Accept-Encoding", "gzip")
+            .build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(&res.body[..], b"gz-bytes");
+        assert_eq!(res.header("Content-Encoding"), Some("gzip"));
+
+        // No Accept-Encoding falls back to the identity file
+        let req = RequestBuilder::new(Method::Get, "/app.js").build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(&res.body[..], b"var answer = 42;");
+        assert_eq!(res.header("Content-Encoding"), None);
+
+        // q-values outrank the server's brotli preference
+        let req = RequestBuilder::new(Method::Get, "/app.js")
+            .header("Accept-Encoding", "br;q=0.1, gzip;q=0.9")
+            .build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(&res.body[..], b"gz-bytes");
+        assert_eq!(res.header("Content-Encoding"), Some("gzip"));
+    }
+
+    #[test]
+    fn test_cached_serving_uses_metadata_cache() {
+        use crate::RequestBuilder;
+
+        let dir = std::env::temp_dir().join(format!(
+            "gust-static-cached-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("app.js"), b"void 0").unwrap();
+
+        let handler = StaticFiles::cached(StaticFileConfig::new(&dir));
+
+        let req = RequestBuilder::new(Method::Get, "/app.js").build();
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.header("Content-Type"), Some("text/javascript; charset=utf-8"));
+        let etag = res.header("ETag").unwrap().to_string();
+
+        // Repeat request is served from cached metadata
+        let res = handler.handle_inner(&req);
+        assert_eq!(res.header("ETag"), Some(etag.as_str()));
+        let stats = handler.metadata_cache().unwrap().stats();
+        assert!(stats.hits >= 1);
+        assert_eq!(stats.entries, 1);
+
+        // Conditional request against the cached ETag
+        let req = RequestBuilder::new(Method::Get, "/app.js")
+            .header("If-None-Match", etag)
+            .build();
+        assert_eq!(handler.handle_inner(&req).status, StatusCode::NOT_MODIFIED);
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
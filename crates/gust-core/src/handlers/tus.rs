@@ -0,0 +1,718 @@
+//! Resumable upload handler (tus.io protocol 1.0.0)
+//!
+//! Implements creation, HEAD offset checks, PATCH append with the
+//! checksum extension, termination, and expiration on top of a
+//! pluggable [`TusStore`]. Chunks go to disk via [`DiskStore`] by
+//! default; [`MemoryStore`] exists for tests and ephemeral uploads.
+
+use crate::crypto::{base64_decode, md5, sha1, sha256};
+use crate::pure::http_date::format_http_date;
+use crate::{Method, Request, Response, ResponseBuilder, StatusCode};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Protocol version implemented by this handler
+pub const TUS_VERSION: &str = "1.0.0";
+
+/// Extensions advertised via Tus-Extension
+const TUS_EXTENSIONS: &str = "creation,creation-with-upload,expiration,checksum,termination";
+
+/// Metadata about an in-progress upload
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadInfo {
+    /// Bytes received so far
+    pub offset: u64,
+    /// Declared total length
+    pub length: u64,
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+    /// Raw Upload-Metadata header from creation, if any
+    pub metadata: Option<String>,
+}
+
+/// Pluggable storage backend for upload chunks
+pub trait TusStore: Send + Sync {
+    /// Create a new upload with the declared total length
+    fn create(&self, id: &str, length: u64, metadata: Option<&str>) -> Result<(), String>;
+
+    /// Look up upload state
+    fn info(&self, id: &str) -> Option<UploadInfo>;
+
+    /// Append a chunk at the given offset, returning the new offset
+    fn append(&self, id: &str, offset: u64, data: &[u8]) -> Result<u64, String>;
+
+    /// Remove an upload; returns true if it existed
+    fn remove(&self, id: &str) -> bool;
+}
+
+/// In-memory store (tests, ephemeral uploads)
+#[derive(Default)]
+pub struct MemoryStore {
+    uploads: Mutex<HashMap<String, (UploadInfo, Vec<u8>)>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read back the assembled bytes (for tests)
+    pub fn data(&self, id: &str) -> Option<Vec<u8>> {
+        self.uploads.lock().unwrap().get(id).map(|(_, d)| d.clone())
+    }
+}
+
+impl TusStore for MemoryStore {
+    fn create(&self, id: &str, length: u64, metadata: Option<&str>) -> Result<(), String> {
+        let info = UploadInfo {
+            offset: 0,
+            length,
+            created_at: now_secs(),
+            metadata: metadata.map(|m| m.to_string()),
+        };
+        self.uploads
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), (info, Vec::new()));
+        Ok(())
+    }
+
+    fn info(&self, id: &str) -> Option<UploadInfo> {
+        self.uploads.lock().unwrap().get(id).map(|(i, _)| i.clone())
+    }
+
+    fn append(&self, id: &str, offset: u64, data: &[u8]) -> Result<u64, String> {
+        let mut uploads = self.uploads.lock().unwrap();
+        let (info, bytes) = uploads
+            .get_mut(id)
+            .ok_or_else(|| "Upload not found".to_string())?;
+        if info.offset != offset {
+            return Err("Offset mismatch".to_string());
+        }
+        bytes.extend_from_slice(data);
+        info.offset += data.len() as u64;
+        Ok(info.offset)
+    }
+
+    fn remove(&self, id: &str) -> bool {
+        self.uploads.lock().unwrap().remove(id).is_some()
+    }
+}
+
+/// Disk-backed store: `<root>/<id>` holds chunks, `<root>/<id>.info`
+/// holds length/created/metadata.
+pub struct DiskStore {
+    root: std::path::PathBuf,
+}
+
+impl DiskStore {
+    pub fn new(root: impl Into<std::path::PathBuf>) -> std::io::Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)?;
+        Ok(Self { root })
+    }
+
+    fn data_path(&self, id: &str) -> std::path::PathBuf {
+        self.root.join(id)
+    }
+
+    fn info_path(&self, id: &str) -> std::path::PathBuf {
+        self.root.join(format!("{}.info", id))
+    }
+}
+
+impl TusStore for DiskStore {
+    fn create(&self, id: &str, length: u64, metadata: Option<&str>) -> Result<(), String> {
+        std::fs::write(self.data_path(id), b"").map_err(|e| e.to_string())?;
+        let info = format!(
+            "length={}\ncreated={}\nmetadata={}\n",
+            length,
+            now_secs(),
+            metadata.unwrap_or("")
+        );
+        std::fs::write(self.info_path(id), info).map_err(|e| e.to_string())
+    }
+
+    fn info(&self, id: &str) -> Option<UploadInfo> {
+        let raw = std::fs::read_to_string(self.info_path(id)).ok()?;
+        let mut length = 0u64;
+        let mut created_at = 0u64;
+        let mut metadata = None;
+        for line in raw.lines() {
+            if let Some(v) = line.strip_prefix("length=") {
+                length = v.parse().ok()?;
+            } else if let Some(v) = line.strip_prefix("created=") {
+                created_at = v.parse().ok()?;
+            } else if let Some(v) = line.strip_prefix("metadata=") {
+                if !v.is_empty() {
+                    metadata = Some(v.to_string());
+                }
+            }
+        }
+        let offset = std::fs::metadata(self.data_path(id)).ok()?.len();
+        Some(UploadInfo {
+            offset,
+            length,
+            created_at,
+            metadata,
+        })
+    }
+
+    fn append(&self, id: &str, offset: u64, data: &[u8]) -> Result<u64, String> {
+        use std::io::Write;
+        let current = std::fs::metadata(self.data_path(id))
+            .map_err(|_| "Upload not found".to_string())?
+            .len();
+        if current != offset {
+            return Err("Offset mismatch".to_string());
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(self.data_path(id))
+            .map_err(|e| e.to_string())?;
+        file.write_all(data).map_err(|e| e.to_string())?;
+        Ok(current + data.len() as u64)
+    }
+
+    fn remove(&self, id: &str) -> bool {
+        let removed = std::fs::remove_file(self.data_path(id)).is_ok();
+        let _ = std::fs::remove_file(self.info_path(id));
+        removed
+    }
+}
+
+/// Tus handler configuration
+#[derive(Clone, Default)]
+pub struct TusConfig {
+    /// Maximum declared upload size (Tus-Max-Size)
+    pub max_size: Option<u64>,
+    /// Upload lifetime in seconds; expired uploads are removed lazily
+    pub expiration_secs: Option<u64>,
+}
+
+impl TusConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    pub fn expiration(mut self, secs: u64) -> Self {
+        self.expiration_secs = Some(secs);
+        self
+    }
+}
+
+/// Resumable upload handler
+pub struct Tus {
+    store: Box<dyn TusStore>,
+    config: TusConfig,
+    next_id: AtomicU64,
+}
+
+impl Tus {
+    pub fn new(store: impl TusStore + 'static, config: TusConfig) -> Self {
+        Self {
+            store: Box::new(store),
+            config,
+            next_id: AtomicU64::new(seed_id()),
+        }
+    }
+
+    /// Handle a request under the tus base path
+    ///
+    /// `base_path` is the creation endpoint (e.g. `/uploads`); uploads
+    /// live at `{base_path}/{id}`.
+    pub fn handle(&self, req: &Request, base_path: &str) -> Response {
+        // OPTIONS never requires the version header
+        if req.method == Method::Options {
+            return self.options_response();
+        }
+
+        // All other requests must speak our protocol version
+        match req.header("tus-resumable") {
+            Some(v) if v == TUS_VERSION => {}
+            _ => {
+                return self
+                    .with_tus_headers(ResponseBuilder::new(StatusCode(412)))
+                    .header("Tus-Version", TUS_VERSION)
+                    .body("Precondition Failed")
+                    .build();
+            }
+        }
+
+        let upload_id = req
+            .path
+            .strip_prefix(base_path)
+            .map(|rest| rest.trim_start_matches('/'))
+            .filter(|rest| !rest.is_empty());
+
+        // IDs are generated by us; reject anything path-like before it
+        // reaches a store. Dot segments arrive unnormalized, so a raw
+        // `DELETE <base>/../x` would otherwise escape the store root.
+        if let Some(id) = upload_id {
+            if id.contains('/') || id.contains('\\') || id.contains("..") {
+                return self.not_found();
+            }
+        }
+
+        match (req.method, upload_id) {
+            (Method::Post, None) => self.create(req, base_path),
+            (Method::Head, Some(id)) => self.head(id),
+            (Method::Patch, Some(id)) => self.patch(req, id),
+            (Method::Delete, Some(id)) => self.delete(id),
+            _ => self
+                .with_tus_headers(ResponseBuilder::new(StatusCode::METHOD_NOT_ALLOWED))
+                .body("Method Not Allowed")
+                .build(),
+        }
+    }
+
+    fn options_response(&self) -> Response {
+        let mut builder = ResponseBuilder::new(StatusCode::NO_CONTENT)
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Tus-Version", TUS_VERSION)
+            .header("Tus-Extension", TUS_EXTENSIONS);
+        if let Some(max) = self.config.max_size {
+            builder = builder.header("Tus-Max-Size", max.to_string());
+        }
+        builder.build()
+    }
+
+    fn create(&self, req: &Request, base_path: &str) -> Response {
+        let length: u64 = match req.header("upload-length").and_then(|v| v.parse().ok()) {
+            Some(l) => l,
+            None => {
+                return self
+                    .with_tus_headers(ResponseBuilder::new(StatusCode::BAD_REQUEST))
+                    .body("Missing or invalid Upload-Length")
+                    .build();
+            }
+        };
+
+        if let Some(max) = self.config.max_size {
+            if length > max {
+                return self
+                    .with_tus_headers(ResponseBuilder::new(StatusCode::PAYLOAD_TOO_LARGE))
+                    .body("Upload exceeds Tus-Max-Size")
+                    .build();
+            }
+        }
+
+        let id = self.generate_id();
+        let metadata = req.header("upload-metadata");
+
+        if let Err(e) = self.store.create(&id, length, metadata) {
+            return self
+                .with_tus_headers(ResponseBuilder::new(StatusCode::INTERNAL_SERVER_ERROR))
+                .body(e)
+                .build();
+        }
+
+        // creation-with-upload: body may carry the first chunk
+        let mut offset = 0u64;
+        if !req.body.is_empty()
+            && req.content_type() == Some("application/offset+octet-stream")
+        {
+            match self.store.append(&id, 0, &req.body) {
+                Ok(new_offset) => offset = new_offset,
+                Err(e) => {
+                    return self
+                        .with_tus_headers(ResponseBuilder::new(StatusCode::INTERNAL_SERVER_ERROR))
+                        .body(e)
+                        .build();
+                }
+            }
+        }
+
+        let mut builder = self
+            .with_tus_headers(ResponseBuilder::new(StatusCode::CREATED))
+            .header("Location", format!("{}/{}", base_path, id))
+            .header("Upload-Offset", offset.to_string());
+        if let Some(expires) = self.expires_at(now_secs()) {
+            builder = builder.header("Upload-Expires", format_http_date(expires));
+        }
+        builder.build()
+    }
+
+    fn head(&self, id: &str) -> Response {
+        match self.live_info(id) {
+            Some(info) => {
+                let mut builder = self
+                    .with_tus_headers(ResponseBuilder::new(StatusCode::OK))
+                    .header("Upload-Offset", info.offset.to_string())
+                    .header("Upload-Length", info.length.to_string())
+                    .header("Cache-Control", "no-store");
+                if let Some(metadata) = &info.metadata {
+                    builder = builder.header("Upload-Metadata", metadata.clone());
+                }
+                builder.build()
+            }
+            None => self.not_found(),
+        }
+    }
+
+    fn patch(&self, req: &Request, id: &str) -> Response {
+        if req.content_type() != Some("application/offset+octet-stream") {
+            return self
+                .with_tus_headers(ResponseBuilder::new(StatusCode(415)))
+                .body("Content-Type must be application/offset+octet-stream")
+                .build();
+        }
+
+        let info = match self.live_info(id) {
+            Some(i) => i,
+            None => return self.not_found(),
+        };
+
+        let offset: u64 = match req.header("upload-offset").and_then(|v| v.parse().ok()) {
+            Some(o) => o,
+            None => {
+                return self
+                    .with_tus_headers(ResponseBuilder::new(StatusCode::BAD_REQUEST))
+                    .body("Missing or invalid Upload-Offset")
+                    .build();
+            }
+        };
+
+        if offset != info.offset {
+            return self
+                .with_tus_headers(ResponseBuilder::new(StatusCode::CONFLICT))
+                .header("Upload-Offset", info.offset.to_string())
+                .body("Offset mismatch")
+                .build();
+        }
+
+        if info.offset + req.body.len() as u64 > info.length {
+            return self
+                .with_tus_headers(ResponseBuilder::new(StatusCode::PAYLOAD_TOO_LARGE))
+                .body("Chunk exceeds declared Upload-Length")
+                .build();
+        }
+
+        // Checksum extension: Upload-Checksum: "<algorithm> <base64>"
+        if let Some(header) = req.header("upload-checksum") {
+            match verify_upload_checksum(header, &req.body) {
+                ChecksumResult::Ok => {}
+                ChecksumResult::Mismatch => {
+                    // 460 is the spec's checksum-mismatch code
+                    return self
+                        .with_tus_headers(ResponseBuilder::new(StatusCode(460)))
+                        .body("Checksum Mismatch")
+                        .build();
+                }
+                ChecksumResult::Unsupported => {
+                    return self
+                        .with_tus_headers(ResponseBuilder::new(StatusCode::BAD_REQUEST))
+                        .body("Unsupported checksum algorithm")
+                        .build();
+                }
+            }
+        }
+
+        match self.store.append(id, offset, &req.body) {
+            Ok(new_offset) => {
+                let mut builder = self
+                    .with_tus_headers(ResponseBuilder::new(StatusCode::NO_CONTENT))
+                    .header("Upload-Offset", new_offset.to_string());
+                if let Some(expires) = self.expires_at(info.created_at) {
+                    builder = builder.header("Upload-Expires", format_http_date(expires));
+                }
+                builder.build()
+            }
+            Err(e) => self
+                .with_tus_headers(ResponseBuilder::new(StatusCode::INTERNAL_SERVER_ERROR))
+                .body(e)
+                .build(),
+        }
+    }
+
+    fn delete(&self, id: &str) -> Response {
+        if self.store.remove(id) {
+            self.with_tus_headers(ResponseBuilder::new(StatusCode::NO_CONTENT))
+                .build()
+        } else {
+            self.not_found()
+        }
+    }
+
+    /// Fetch info, lazily removing expired uploads
+    fn live_info(&self, id: &str) -> Option<UploadInfo> {
+        let info = self.store.info(id)?;
+        if let Some(expires) = self.expires_at(info.created_at) {
+            if now_secs() >= expires {
+                self.store.remove(id);
+                return None;
+            }
+        }
+        Some(info)
+    }
+
+    fn expires_at(&self, created_at: u64) -> Option<u64> {
+        self.config.expiration_secs.map(|ttl| created_at + ttl)
+    }
+
+    fn not_found(&self) -> Response {
+        self.with_tus_headers(ResponseBuilder::new(StatusCode::NOT_FOUND))
+            .body("Not Found")
+            .build()
+    }
+
+    fn with_tus_headers(&self, builder: ResponseBuilder) -> ResponseBuilder {
+        builder.header("Tus-Resumable", TUS_VERSION)
+    }
+
+    fn generate_id(&self) -> String {
+        let n = self.next_id.fetch_add(1, Ordering::SeqCst);
+        format!("{:016x}", n)
+    }
+}
+
+enum ChecksumResult {
+    Ok,
+    Mismatch,
+    Unsupported,
+}
+
+fn verify_upload_checksum(header: &str, body: &[u8]) -> ChecksumResult {
+    let (algorithm, encoded) = match header.split_once(' ') {
+        Some(parts) => parts,
+        None => return ChecksumResult::Unsupported,
+    };
+
+    let expected = match base64_decode(encoded.trim()) {
+        Some(e) => e,
+        None => return ChecksumResult::Mismatch,
+    };
+
+    let actual: Vec<u8> = match algorithm.trim().to_lowercase().as_str() {
+        "sha1" => sha1(body).to_vec(),
+        "sha256" => sha256(body).to_vec(),
+        "md5" => md5(body).to_vec(),
+        _ => return ChecksumResult::Unsupported,
+    };
+
+    if actual == expected {
+        ChecksumResult::Ok
+    } else {
+        ChecksumResult::Mismatch
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn seed_id() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_nanos() as u64 ^ (now.as_secs() << 32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::base64_encode;
+    use crate::RequestBuilder;
+
+    fn tus() -> Tus {
+        Tus::new(MemoryStore::new(), TusConfig::new().max_size(1024))
+    }
+
+    fn create_upload(handler: &Tus, length: u64) -> String {
+        let req = RequestBuilder::new(Method::Post, "/uploads")
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Upload-Length", length.to_string())
+            .build();
+        let res = handler.handle(&req, "/uploads");
+        assert_eq!(res.status, StatusCode::CREATED);
+        let location = res.header("Location").unwrap();
+        location.rsplit('/').next().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_options_advertises_extensions() {
+        let req = RequestBuilder::new(Method::Options, "/uploads").build();
+        let res = tus().handle(&req, "/uploads");
+        assert_eq!(res.status, StatusCode::NO_CONTENT);
+        assert_eq!(res.header("Tus-Version"), Some(TUS_VERSION));
+        assert!(res.header("Tus-Extension").unwrap().contains("checksum"));
+        assert_eq!(res.header("Tus-Max-Size"), Some("1024"));
+    }
+
+    #[test]
+    fn test_missing_version_header() {
+        let req = RequestBuilder::new(Method::Post, "/uploads")
+            .header("Upload-Length", "10")
+            .build();
+        let res = tus().handle(&req, "/uploads");
+        assert_eq!(res.status.0, 412);
+    }
+
+    #[test]
+    fn test_create_and_head() {
+        let handler = tus();
+        let id = create_upload(&handler, 10);
+
+        let req = RequestBuilder::new(Method::Head, format!("/uploads/{}", id))
+            .header("Tus-Resumable", TUS_VERSION)
+            .build();
+        let res = handler.handle(&req, "/uploads");
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.header("Upload-Offset"), Some("0"));
+        assert_eq!(res.header("Upload-Length"), Some("10"));
+        assert_eq!(res.header("Cache-Control"), Some("no-store"));
+    }
+
+    #[test]
+    fn test_create_exceeding_max_size() {
+        let req = RequestBuilder::new(Method::Post, "/uploads")
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Upload-Length", "4096")
+            .build();
+        let res = tus().handle(&req, "/uploads");
+        assert_eq!(res.status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn test_patch_appends_and_completes() {
+        let handler = tus();
+        let id = create_upload(&handler, 10);
+
+        let req = RequestBuilder::new(Method::Patch, format!("/uploads/{}", id))
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Content-Type", "application/offset+octet-stream")
+            .header("Upload-Offset", "0")
+            .body("hello")
+            .build();
+        let res = handler.handle(&req, "/uploads");
+        assert_eq!(res.status, StatusCode::NO_CONTENT);
+        assert_eq!(res.header("Upload-Offset"), Some("5"));
+
+        let req = RequestBuilder::new(Method::Patch, format!("/uploads/{}", id))
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Content-Type", "application/offset+octet-stream")
+            .header("Upload-Offset", "5")
+            .body("world")
+            .build();
+        let res = handler.handle(&req, "/uploads");
+        assert_eq!(res.header("Upload-Offset"), Some("10"));
+    }
+
+    #[test]
+    fn test_patch_offset_conflict() {
+        let handler = tus();
+        let id = create_upload(&handler, 10);
+
+        let req = RequestBuilder::new(Method::Patch, format!("/uploads/{}", id))
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Content-Type", "application/offset+octet-stream")
+            .header("Upload-Offset", "5")
+            .body("xx")
+            .build();
+        let res = handler.handle(&req, "/uploads");
+        assert_eq!(res.status, StatusCode::CONFLICT);
+        assert_eq!(res.header("Upload-Offset"), Some("0"));
+    }
+
+    #[test]
+    fn test_patch_checksum() {
+        let handler = tus();
+        let id = create_upload(&handler, 10);
+
+        let good = format!("sha1 {}", base64_encode(&sha1(b"hello")));
+        let req = RequestBuilder::new(Method::Patch, format!("/uploads/{}", id))
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Content-Type", "application/offset+octet-stream")
+            .header("Upload-Offset", "0")
+            .header("Upload-Checksum", good)
+            .body("hello")
+            .build();
+        assert_eq!(handler.handle(&req, "/uploads").status, StatusCode::NO_CONTENT);
+
+        let bad = format!("sha1 {}", base64_encode(&sha1(b"other")));
+        let req = RequestBuilder::new(Method::Patch, format!("/uploads/{}", id))
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Content-Type", "application/offset+octet-stream")
+            .header("Upload-Offset", "5")
+            .header("Upload-Checksum", bad)
+            .body("world")
+            .build();
+        assert_eq!(handler.handle(&req, "/uploads").status.0, 460);
+    }
+
+    #[test]
+    fn test_delete_terminates() {
+        let handler = tus();
+        let id = create_upload(&handler, 10);
+
+        let req = RequestBuilder::new(Method::Delete, format!("/uploads/{}", id))
+            .header("Tus-Resumable", TUS_VERSION)
+            .build();
+        assert_eq!(handler.handle(&req, "/uploads").status, StatusCode::NO_CONTENT);
+
+        let req = RequestBuilder::new(Method::Head, format!("/uploads/{}", id))
+            .header("Tus-Resumable", TUS_VERSION)
+            .build();
+        assert_eq!(handler.handle(&req, "/uploads").status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_path_like_ids_rejected() {
+        // A store that panics proves the guard fires before any
+        // backend (and thus the filesystem) is touched
+        struct Untouchable;
+        impl TusStore for Untouchable {
+            fn create(&self, _: &str, _: u64, _: Option<&str>) -> Result<(), String> {
+                panic!("store touched")
+            }
+            fn info(&self, _: &str) -> Option<UploadInfo> {
+                panic!("store touched")
+            }
+            fn append(&self, _: &str, _: u64, _: &[u8]) -> Result<u64, String> {
+                panic!("store touched")
+            }
+            fn remove(&self, _: &str) -> bool {
+                panic!("store touched")
+            }
+        }
+
+        let handler = Tus::new(Untouchable, TusConfig::new());
+        for path in ["/uploads/../x", "/uploads/a/b", "/uploads/..\\x", "/uploads/.."] {
+            for method in [Method::Head, Method::Patch, Method::Delete] {
+                let req = RequestBuilder::new(method, path)
+                    .header("Tus-Resumable", TUS_VERSION)
+                    .build();
+                let res = handler.handle(&req, "/uploads");
+                assert_eq!(res.status, StatusCode::NOT_FOUND, "{:?} {}", method, path);
+            }
+        }
+    }
+
+    #[test]
+    fn test_disk_store_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("gust-tus-test-{}", seed_id()));
+        let store = DiskStore::new(&dir).unwrap();
+
+        store.create("abc", 10, Some("meta")).unwrap();
+        assert_eq!(store.info("abc").unwrap().offset, 0);
+        assert_eq!(store.append("abc", 0, b"hello").unwrap(), 5);
+        assert_eq!(store.append("abc", 5, b"world").unwrap(), 10);
+        assert_eq!(store.info("abc").unwrap().offset, 10);
+        assert_eq!(store.info("abc").unwrap().metadata.as_deref(), Some("meta"));
+        assert!(store.append("abc", 3, b"x").is_err());
+        assert!(store.remove("abc"));
+        assert!(store.info("abc").is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -16,7 +16,7 @@ pub enum WebSocketMessage {
 }
 
 /// WebSocket close frame
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CloseFrame {
     pub code: u16,
     pub reason: String,
@@ -63,7 +63,7 @@ impl Opcode {
 }
 
 /// WebSocket frame
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Frame {
     pub fin: bool,
     pub opcode: Opcode,
@@ -205,12 +205,14 @@ impl Frame {
             None
         };
 
-        // Payload
-        if data.len() < offset + payload_len {
+        // Payload; checked_add guards against a 64-bit extended length
+        // overflowing usize (found by the websocket_frame fuzz target)
+        let frame_end = offset.checked_add(payload_len)?;
+        if data.len() < frame_end {
             return None;
         }
 
-        let mut payload = data[offset..offset + payload_len].to_vec();
+        let mut payload = data[offset..frame_end].to_vec();
 
         // Unmask if needed
         if let Some(mask) = mask {
@@ -226,7 +228,574 @@ impl Frame {
             payload,
         };
 
-        Some((frame, offset + payload_len))
+        Some((frame, frame_end))
+    }
+}
+
+/// Protocol violation detected while decoding incoming frames
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolError {
+    /// RSV1-3 set without a negotiated extension
+    ReservedBitsSet,
+    /// Unknown opcode nibble
+    InvalidOpcode(u8),
+    /// Client-to-server frame arrived unmasked
+    UnmaskedClientFrame,
+    /// Control frame fragmented or longer than 125 bytes
+    InvalidControlFrame,
+    /// Declared frame length exceeds the configured limit
+    FrameTooLarge { size: u64, limit: usize },
+    /// Reassembled message exceeds the configured limit
+    MessageTooLarge { size: usize, limit: usize },
+    /// Continuation without a message in progress, or a new data
+    /// frame while a fragmented message is still open
+    UnexpectedContinuation,
+    /// Text message payload is not valid UTF-8
+    InvalidUtf8,
+}
+
+impl ProtocolError {
+    /// Close code to send before dropping the connection
+    pub fn close_code(&self) -> u16 {
+        match self {
+            ProtocolError::FrameTooLarge { .. } | ProtocolError::MessageTooLarge { .. } => 1009,
+            ProtocolError::InvalidUtf8 => 1007,
+            _ => 1002,
+        }
+    }
+}
+
+impl std::fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolError::ReservedBitsSet => write!(f, "Reserved bits set"),
+            ProtocolError::InvalidOpcode(op) => write!(f, "Invalid opcode 0x{:x}", op),
+            ProtocolError::UnmaskedClientFrame => write!(f, "Client frame not masked"),
+            ProtocolError::InvalidControlFrame => write!(f, "Invalid control frame"),
+            ProtocolError::FrameTooLarge { size, limit } => {
+                write!(f, "Frame of {} bytes exceeds limit of {}", size, limit)
+            }
+            ProtocolError::MessageTooLarge { size, limit } => {
+                write!(f, "Message of {} bytes exceeds limit of {}", size, limit)
+            }
+            ProtocolError::UnexpectedContinuation => write!(f, "Unexpected continuation frame"),
+            ProtocolError::InvalidUtf8 => write!(f, "Text payload is not valid UTF-8"),
+        }
+    }
+}
+
+/// Limits and validation rules for [`FrameDecoder`]
+#[derive(Debug, Clone)]
+pub struct FrameDecoderConfig {
+    /// Maximum payload length of a single frame (default 1 MiB)
+    pub max_frame_size: usize,
+    /// Maximum size of a reassembled fragmented message (default 4 MiB)
+    pub max_message_size: usize,
+    /// Require incoming frames to be masked, as RFC 6455 mandates
+    /// for client-to-server frames (default true)
+    pub require_masked: bool,
+}
+
+impl Default for FrameDecoderConfig {
+    fn default() -> Self {
+        Self {
+            max_frame_size: 1024 * 1024,
+            max_message_size: 4 * 1024 * 1024,
+            require_masked: true,
+        }
+    }
+}
+
+impl FrameDecoderConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum single-frame payload length
+    pub fn max_frame_size(mut self, bytes: usize) -> Self {
+        self.max_frame_size = bytes;
+        self
+    }
+
+    /// Set the maximum reassembled message size
+    pub fn max_message_size(mut self, bytes: usize) -> Self {
+        self.max_message_size = bytes;
+        self
+    }
+
+    /// Set whether incoming frames must be masked
+    pub fn require_masked(mut self, required: bool) -> Self {
+        self.require_masked = required;
+        self
+    }
+}
+
+/// Streaming WebSocket frame decoder
+///
+/// Unlike [`Frame::decode`], which needs the whole frame in one slice,
+/// the decoder is fed bytes as they arrive and validates the header
+/// (RSV bits, masking, declared length) before buffering any payload,
+/// so a hostile 8 GiB length prefix is rejected without allocating.
+///
+/// ```
+/// use gust_core::handlers::websocket::{Frame, FrameDecoder, FrameDecoderConfig};
+///
+/// let mut decoder = FrameDecoder::new(FrameDecoderConfig::new().require_masked(false));
+/// decoder.feed(&Frame::text("hi").encode());
+/// let frame = decoder.next_frame().unwrap().unwrap();
+/// assert_eq!(frame.payload, b"hi");
+/// ```
+#[derive(Debug, Default)]
+pub struct FrameDecoder {
+    config: FrameDecoderConfig,
+    buffer: Vec<u8>,
+    /// Opcode of the fragmented message being reassembled
+    message_opcode: Option<Opcode>,
+    message: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new(config: FrameDecoderConfig) -> Self {
+        Self {
+            config,
+            buffer: Vec::new(),
+            message_opcode: None,
+            message: Vec::new(),
+        }
+    }
+
+    /// Append bytes received from the connection
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Number of buffered bytes not yet consumed by a frame
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Decode the next complete frame, if one is buffered
+    ///
+    /// Returns `Ok(None)` when more bytes are needed and `Err` on a
+    /// protocol violation; after an error the connection should be
+    /// closed with [`ProtocolError::close_code`].
+    pub fn next_frame(&mut self) -> Result<Option<Frame>, ProtocolError> {
+        let buf = &self.buffer;
+        if buf.len() < 2 {
+            return Ok(None);
+        }
+
+        if buf[0] & 0x70 != 0 {
+            return Err(ProtocolError::ReservedBitsSet);
+        }
+        let fin = (buf[0] & 0x80) != 0;
+        let opcode = Opcode::from_u8(buf[0]).ok_or(ProtocolError::InvalidOpcode(buf[0] & 0x0F))?;
+
+        let masked = (buf[1] & 0x80) != 0;
+        if self.config.require_masked && !masked {
+            return Err(ProtocolError::UnmaskedClientFrame);
+        }
+
+        // Declared payload length, validated before any buffering
+        let (payload_len, mut offset) = match buf[1] & 0x7F {
+            126 => {
+                if buf.len() < 4 {
+                    return Ok(None);
+                }
+                (u16::from_be_bytes([buf[2], buf[3]]) as u64, 4)
+            }
+            127 => {
+                if buf.len() < 10 {
+                    return Ok(None);
+                }
+                (
+                    u64::from_be_bytes([
+                        buf[2], buf[3], buf[4], buf[5], buf[6], buf[7], buf[8], buf[9],
+                    ]),
+                    10,
+                )
+            }
+            n => (n as u64, 2),
+        };
+
+        let is_control = matches!(opcode, Opcode::Close | Opcode::Ping | Opcode::Pong);
+        if is_control && (!fin || payload_len > 125) {
+            return Err(ProtocolError::InvalidControlFrame);
+        }
+        if payload_len > self.config.max_frame_size as u64 {
+            return Err(ProtocolError::FrameTooLarge {
+                size: payload_len,
+                limit: self.config.max_frame_size,
+            });
+        }
+        let payload_len = payload_len as usize;
+
+        let mask = if masked {
+            if buf.len() < offset + 4 {
+                return Ok(None);
+            }
+            let mask = [buf[offset], buf[offset + 1], buf[offset + 2], buf[offset + 3]];
+            offset += 4;
+            Some(mask)
+        } else {
+            None
+        };
+
+        if buf.len() - offset < payload_len {
+            return Ok(None);
+        }
+
+        let mut payload = buf[offset..offset + payload_len].to_vec();
+        if let Some(mask) = mask {
+            for (i, byte) in payload.iter_mut().enumerate() {
+                *byte ^= mask[i % 4];
+            }
+        }
+        self.buffer.drain(..offset + payload_len);
+
+        Ok(Some(Frame {
+            fin,
+            opcode,
+            mask,
+            payload,
+        }))
+    }
+
+    /// Decode the next complete message, reassembling fragments
+    ///
+    /// Control frames (ping/pong/close) are returned immediately even
+    /// when they interleave a fragmented message.
+    pub fn next_message(&mut self) -> Result<Option<WebSocketMessage>, ProtocolError> {
+        while let Some(frame) = self.next_frame()? {
+            match frame.opcode {
+                Opcode::Ping => return Ok(Some(WebSocketMessage::Ping(frame.payload))),
+                Opcode::Pong => return Ok(Some(WebSocketMessage::Pong(frame.payload))),
+                Opcode::Close => {
+                    let close = if frame.payload.len() >= 2 {
+                        Some(CloseFrame {
+                            code: u16::from_be_bytes([frame.payload[0], frame.payload[1]]),
+                            reason: String::from_utf8_lossy(&frame.payload[2..]).into_owned(),
+                        })
+                    } else {
+                        None
+                    };
+                    return Ok(Some(WebSocketMessage::Close(close)));
+                }
+                Opcode::Text | Opcode::Binary => {
+                    if self.message_opcode.is_some() {
+                        return Err(ProtocolError::UnexpectedContinuation);
+                    }
+                    if frame.fin {
+                        self.check_message_size(frame.payload.len())?;
+                        return Self::finish_message(frame.opcode, frame.payload).map(Some);
+                    }
+                    self.check_message_size(frame.payload.len())?;
+                    self.message_opcode = Some(frame.opcode);
+                    self.message = frame.payload;
+                }
+                Opcode::Continuation => {
+                    let opcode = self
+                        .message_opcode
+                        .ok_or(ProtocolError::UnexpectedContinuation)?;
+                    self.check_message_size(self.message.len() + frame.payload.len())?;
+                    self.message.extend_from_slice(&frame.payload);
+                    if frame.fin {
+                        self.message_opcode = None;
+                        let payload = std::mem::take(&mut self.message);
+                        return Self::finish_message(opcode, payload).map(Some);
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn check_message_size(&self, size: usize) -> Result<(), ProtocolError> {
+        if size > self.config.max_message_size {
+            return Err(ProtocolError::MessageTooLarge {
+                size,
+                limit: self.config.max_message_size,
+            });
+        }
+        Ok(())
+    }
+
+    fn finish_message(opcode: Opcode, payload: Vec<u8>) -> Result<WebSocketMessage, ProtocolError> {
+        match opcode {
+            Opcode::Text => String::from_utf8(payload)
+                .map(WebSocketMessage::Text)
+                .map_err(|_| ProtocolError::InvalidUtf8),
+            _ => Ok(WebSocketMessage::Binary(payload)),
+        }
+    }
+}
+
+/// Connection lifecycle state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Handshake complete, data frames flow both ways
+    Open,
+    /// A close frame has been sent, awaiting the peer's echo
+    Closing,
+    /// Close handshake finished or connection failed
+    Closed,
+}
+
+/// Event surfaced to the application by [`WebSocketConnection`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebSocketEvent {
+    /// Complete text message
+    Text(String),
+    /// Complete binary message
+    Binary(Vec<u8>),
+    /// Pong arrived (liveness confirmed)
+    Pong(Vec<u8>),
+    /// Close handshake finished; the transport should be dropped
+    /// after flushing [`WebSocketConnection::take_outgoing`]
+    Closed(Option<CloseFrame>),
+}
+
+/// Heartbeat policy for keepalive pings
+#[derive(Debug, Clone, Copy)]
+pub struct Heartbeat {
+    /// Milliseconds between keepalive pings
+    pub interval_ms: u64,
+    /// Milliseconds to wait for a pong before counting it missed
+    pub timeout_ms: u64,
+    /// Consecutive missed pongs before the peer is declared dead
+    pub missed_pong_limit: u32,
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self {
+            interval_ms: 30_000,
+            timeout_ms: 10_000,
+            missed_pong_limit: 2,
+        }
+    }
+}
+
+/// Per-connection liveness counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LivenessMetrics {
+    /// Keepalive pings sent
+    pub pings_sent: u64,
+    /// Pongs received (keepalive or unsolicited)
+    pub pongs_received: u64,
+    /// Consecutive pings currently unanswered
+    pub missed_pongs: u32,
+    /// Round-trip time of the last answered ping, in ms
+    pub last_rtt_ms: Option<u64>,
+}
+
+/// Sans-IO WebSocket connection state machine
+///
+/// Tracks open/closing/closed, echoes close frames with the peer's
+/// code, answers pings, and enforces ping/pong liveness deadlines.
+/// Time is passed in by the caller as milliseconds, so the same state
+/// machine drives the native (tokio) and WASM (JS timer) layers.
+///
+/// The caller feeds wire bytes to [`receive`](Self::receive), writes
+/// whatever [`take_outgoing`](Self::take_outgoing) returns, and calls
+/// [`tick`](Self::tick) periodically for keepalive pings.
+#[derive(Debug)]
+pub struct WebSocketConnection {
+    decoder: FrameDecoder,
+    state: ConnectionState,
+    outgoing: Vec<Frame>,
+    /// Queue keepalive pings every this many ms (None = disabled)
+    ping_interval_ms: Option<u64>,
+    /// Count a pong as missed when it is this overdue
+    pong_timeout_ms: u64,
+    /// Consecutive missed pongs before closing 1001
+    missed_pong_limit: u32,
+    last_ping_ms: u64,
+    awaiting_pong_since: Option<u64>,
+    metrics: LivenessMetrics,
+}
+
+impl WebSocketConnection {
+    pub fn new(config: FrameDecoderConfig) -> Self {
+        Self {
+            decoder: FrameDecoder::new(config),
+            state: ConnectionState::Open,
+            outgoing: Vec::new(),
+            ping_interval_ms: None,
+            pong_timeout_ms: 10_000,
+            missed_pong_limit: 1,
+            last_ping_ms: 0,
+            awaiting_pong_since: None,
+            metrics: LivenessMetrics::default(),
+        }
+    }
+
+    /// Enable keepalive pings every `interval_ms` milliseconds
+    pub fn ping_interval(mut self, interval_ms: u64) -> Self {
+        self.ping_interval_ms = Some(interval_ms);
+        self
+    }
+
+    /// Set how long to wait for a pong before counting it missed
+    pub fn pong_timeout(mut self, timeout_ms: u64) -> Self {
+        self.pong_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Set how many consecutive missed pongs close the connection
+    pub fn missed_pong_limit(mut self, limit: u32) -> Self {
+        self.missed_pong_limit = limit.max(1);
+        self
+    }
+
+    /// Apply a full [`Heartbeat`] policy in one call
+    pub fn heartbeat(self, heartbeat: Heartbeat) -> Self {
+        self.ping_interval(heartbeat.interval_ms)
+            .pong_timeout(heartbeat.timeout_ms)
+            .missed_pong_limit(heartbeat.missed_pong_limit)
+    }
+
+    /// Liveness counters for this connection
+    pub fn liveness(&self) -> LivenessMetrics {
+        self.metrics
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Feed bytes from the transport and collect resulting events
+    ///
+    /// On a protocol error the appropriate close frame is queued and
+    /// the connection moves to `Closed`; the caller should flush
+    /// outgoing frames and drop the transport.
+    pub fn receive(
+        &mut self,
+        data: &[u8],
+        now_ms: u64,
+    ) -> Result<Vec<WebSocketEvent>, ProtocolError> {
+        if self.state == ConnectionState::Closed {
+            return Ok(Vec::new());
+        }
+        self.decoder.feed(data);
+
+        let mut events = Vec::new();
+        loop {
+            let message = match self.decoder.next_message() {
+                Ok(Some(message)) => message,
+                Ok(None) => break,
+                Err(error) => {
+                    // Fail the connection: send the matching close
+                    // code, skip the echo wait
+                    self.outgoing
+                        .push(Frame::close(error.close_code(), "Protocol error"));
+                    self.state = ConnectionState::Closed;
+                    return Err(error);
+                }
+            };
+            match message {
+                WebSocketMessage::Text(text) => events.push(WebSocketEvent::Text(text)),
+                WebSocketMessage::Binary(data) => events.push(WebSocketEvent::Binary(data)),
+                WebSocketMessage::Ping(payload) => {
+                    // Answer pings transparently while not closed
+                    self.outgoing.push(Frame::pong(payload));
+                }
+                WebSocketMessage::Pong(payload) => {
+                    if let Some(since) = self.awaiting_pong_since.take() {
+                        self.metrics.last_rtt_ms = Some(now_ms.saturating_sub(since));
+                    }
+                    self.metrics.pongs_received += 1;
+                    self.metrics.missed_pongs = 0;
+                    self.last_ping_ms = now_ms;
+                    events.push(WebSocketEvent::Pong(payload));
+                }
+                WebSocketMessage::Close(close) => {
+                    if self.state == ConnectionState::Open {
+                        // Peer initiated: echo their code (1000 when
+                        // none was given) and finish the handshake
+                        let code = close.as_ref().map(|c| c.code).unwrap_or(1000);
+                        self.outgoing.push(Frame::close(code, ""));
+                    }
+                    self.state = ConnectionState::Closed;
+                    events.push(WebSocketEvent::Closed(close));
+                    break;
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Queue a text message; returns false once closing has started
+    pub fn send_text(&mut self, data: impl Into<String>) -> bool {
+        self.send_frame(Frame::text(data))
+    }
+
+    /// Queue a binary message; returns false once closing has started
+    pub fn send_binary(&mut self, data: impl Into<Vec<u8>>) -> bool {
+        self.send_frame(Frame::binary(data))
+    }
+
+    fn send_frame(&mut self, frame: Frame) -> bool {
+        if self.state != ConnectionState::Open {
+            return false;
+        }
+        self.outgoing.push(frame);
+        true
+    }
+
+    /// Start the close handshake from our side
+    pub fn close(&mut self, code: u16, reason: &str) {
+        if self.state != ConnectionState::Open {
+            return;
+        }
+        self.outgoing.push(Frame::close(code, reason));
+        self.state = ConnectionState::Closing;
+    }
+
+    /// Advance time-based behavior: keepalive pings and pong deadlines
+    ///
+    /// An overdue pong counts as missed; below the limit the peer is
+    /// re-pinged, at the limit a close 1001 (going away) is queued and
+    /// the connection is declared dead.
+    pub fn tick(&mut self, now_ms: u64) -> Option<WebSocketEvent> {
+        if self.state == ConnectionState::Closed {
+            return None;
+        }
+        if let Some(since) = self.awaiting_pong_since {
+            if now_ms.saturating_sub(since) >= self.pong_timeout_ms {
+                self.awaiting_pong_since = None;
+                self.metrics.missed_pongs += 1;
+                if self.metrics.missed_pongs >= self.missed_pong_limit {
+                    self.outgoing.push(Frame::close(1001, "Peer unresponsive"));
+                    self.state = ConnectionState::Closed;
+                    return Some(WebSocketEvent::Closed(Some(CloseFrame {
+                        code: 1001,
+                        reason: "Peer unresponsive".to_string(),
+                    })));
+                }
+                // Below the limit: try again immediately
+                self.send_ping(now_ms);
+            }
+        } else if let Some(interval) = self.ping_interval_ms {
+            if self.state == ConnectionState::Open
+                && now_ms.saturating_sub(self.last_ping_ms) >= interval
+            {
+                self.send_ping(now_ms);
+            }
+        }
+        None
+    }
+
+    fn send_ping(&mut self, now_ms: u64) {
+        self.outgoing.push(Frame::ping(Vec::new()));
+        self.metrics.pings_sent += 1;
+        self.last_ping_ms = now_ms;
+        self.awaiting_pong_since = Some(now_ms);
+    }
+
+    /// Drain frames queued for the transport to write
+    pub fn take_outgoing(&mut self) -> Vec<Frame> {
+        std::mem::take(&mut self.outgoing)
     }
 }
 
@@ -242,18 +811,40 @@ pub trait WebSocketHandler: Send + Sync {
 pub struct WebSocket {
     /// Connection ID
     pub id: u64,
-    /// Send buffer
-    send_buffer: std::sync::Mutex<Vec<Frame>>,
+    /// Send queue (bounded when constructed via [`with_queue`](Self::with_queue))
+    send_queue: crate::handlers::send_queue::SendQueue<Frame>,
 }
 
 impl WebSocket {
     pub fn new(id: u64) -> Self {
         Self {
             id,
-            send_buffer: std::sync::Mutex::new(Vec::new()),
+            send_queue: crate::handlers::send_queue::SendQueue::unbounded(),
+        }
+    }
+
+    /// Create with a bounded send queue and overflow policy
+    pub fn with_queue(
+        id: u64,
+        capacity: usize,
+        policy: crate::handlers::send_queue::OverflowPolicy,
+    ) -> Self {
+        Self {
+            id,
+            send_queue: crate::handlers::send_queue::SendQueue::new(capacity, policy),
         }
     }
 
+    /// True once the overflow policy decided the consumer is too slow
+    pub fn is_send_queue_closed(&self) -> bool {
+        self.send_queue.is_closed()
+    }
+
+    /// Frames discarded by the overflow policy so far
+    pub fn dropped_frames(&self) -> u64 {
+        self.send_queue.dropped()
+    }
+
     /// Send text message
     pub fn send_text(&self, data: impl Into<String>) {
         let frame = Frame::text(data);
@@ -279,18 +870,12 @@ impl WebSocket {
     }
 
     fn send_frame(&self, frame: Frame) {
-        if let Ok(mut buf) = self.send_buffer.lock() {
-            buf.push(frame);
-        }
+        let _ = self.send_queue.push(frame);
     }
 
     /// Take pending frames
     pub fn take_frames(&self) -> Vec<Frame> {
-        if let Ok(mut buf) = self.send_buffer.lock() {
-            std::mem::take(&mut *buf)
-        } else {
-            Vec::new()
-        }
+        self.send_queue.take()
     }
 }
 
@@ -305,6 +890,95 @@ pub fn is_websocket_upgrade(req: &Request) -> bool {
         && key.is_some()
 }
 
+/// Subprotocols and extensions the server is willing to accept
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeNegotiation {
+    /// Supported subprotocols, in no particular order
+    pub protocols: Vec<String>,
+    /// Supported extension names (parameters are not negotiated)
+    pub extensions: Vec<String>,
+}
+
+impl UpgradeNegotiation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a supported subprotocol
+    pub fn protocol(mut self, name: impl Into<String>) -> Self {
+        self.protocols.push(name.into());
+        self
+    }
+
+    /// Add a supported extension
+    pub fn extension(mut self, name: impl Into<String>) -> Self {
+        self.extensions.push(name.into());
+        self
+    }
+}
+
+/// One extension from a `Sec-WebSocket-Extensions` offer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionOffer {
+    pub name: String,
+    /// Parameters as (name, optional value) pairs
+    pub params: Vec<(String, Option<String>)>,
+}
+
+/// Pick a subprotocol from the client's comma-separated offer
+///
+/// The client lists protocols in preference order; the first one the
+/// server supports wins. Returns `None` when nothing matches.
+pub fn negotiate_subprotocol(offer: &str, supported: &[String]) -> Option<String> {
+    offer
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .find(|name| supported.iter().any(|s| s == name))
+        .map(|name| name.to_string())
+}
+
+/// Parse a `Sec-WebSocket-Extensions` offer into structured entries
+///
+/// Format: `permessage-deflate; client_max_window_bits=15, foo`.
+pub fn parse_extension_offers(header: &str) -> Vec<ExtensionOffer> {
+    header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';').map(str::trim);
+            let name = parts.next().filter(|n| !n.is_empty())?;
+            let params = parts
+                .filter(|p| !p.is_empty())
+                .map(|p| match p.split_once('=') {
+                    Some((key, value)) => (
+                        key.trim().to_string(),
+                        Some(value.trim().trim_matches('"').to_string()),
+                    ),
+                    None => (p.to_string(), None),
+                })
+                .collect();
+            Some(ExtensionOffer {
+                name: name.to_string(),
+                params,
+            })
+        })
+        .collect()
+}
+
+/// Accept the offered extensions the server supports, in client order
+///
+/// Parameters are stripped: the server replies with the bare extension
+/// name, which every extension must accept per RFC 6455 §9.1.
+pub fn negotiate_extensions(offer: &str, supported: &[String]) -> Vec<String> {
+    let mut accepted: Vec<String> = Vec::new();
+    for ext in parse_extension_offers(offer) {
+        if supported.contains(&ext.name) && !accepted.contains(&ext.name) {
+            accepted.push(ext.name);
+        }
+    }
+    accepted
+}
+
 /// Generate WebSocket accept key
 pub fn generate_accept_key(key: &str) -> String {
     const MAGIC: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
@@ -319,17 +993,37 @@ pub fn generate_accept_key(key: &str) -> String {
 
 /// Create WebSocket upgrade response
 pub fn upgrade_response(req: &Request) -> Option<Response> {
+    upgrade_response_with(req, &UpgradeNegotiation::new())
+}
+
+/// Create an upgrade response, negotiating subprotocol and extensions
+///
+/// When the client offered a subprotocol the server supports, the
+/// winner is echoed in `Sec-WebSocket-Protocol`; accepted extensions
+/// go into `Sec-WebSocket-Extensions`. Headers are omitted when
+/// nothing was offered or nothing matched.
+pub fn upgrade_response_with(req: &Request, negotiation: &UpgradeNegotiation) -> Option<Response> {
     let key = req.header("sec-websocket-key")?;
     let accept = generate_accept_key(key);
 
-    Some(
-        ResponseBuilder::new(StatusCode(101))
-            .header("Upgrade", "websocket")
-            .header("Connection", "Upgrade")
-            .header("Sec-WebSocket-Accept", &accept)
-            .body("")
-            .build(),
-    )
+    let mut builder = ResponseBuilder::new(StatusCode(101))
+        .header("Upgrade", "websocket")
+        .header("Connection", "Upgrade")
+        .header("Sec-WebSocket-Accept", &accept);
+
+    if let Some(offer) = req.header("sec-websocket-protocol") {
+        if let Some(protocol) = negotiate_subprotocol(offer, &negotiation.protocols) {
+            builder = builder.header("Sec-WebSocket-Protocol", &protocol);
+        }
+    }
+    if let Some(offer) = req.header("sec-websocket-extensions") {
+        let accepted = negotiate_extensions(offer, &negotiation.extensions);
+        if !accepted.is_empty() {
+            builder = builder.header("Sec-WebSocket-Extensions", accepted.join(", "));
+        }
+    }
+
+    Some(builder.body("").build())
 }
 
 // SHA-1 and Base64 implementations moved to crate::crypto module (SSOT)
@@ -346,6 +1040,69 @@ mod tests {
         assert_eq!(accept, "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
     }
 
+    #[test]
+    fn test_negotiate_subprotocol_prefers_client_order() {
+        let supported = vec!["graphql-ws".to_string(), "chat.v2".to_string()];
+        assert_eq!(
+            negotiate_subprotocol("chat.v2, graphql-ws", &supported),
+            Some("chat.v2".to_string())
+        );
+        assert_eq!(negotiate_subprotocol("chat.v1", &supported), None);
+        assert_eq!(negotiate_subprotocol("", &supported), None);
+    }
+
+    #[test]
+    fn test_parse_extension_offers() {
+        let offers =
+            parse_extension_offers("permessage-deflate; client_max_window_bits=15, x-foo");
+        assert_eq!(offers.len(), 2);
+        assert_eq!(offers[0].name, "permessage-deflate");
+        assert_eq!(
+            offers[0].params,
+            vec![("client_max_window_bits".to_string(), Some("15".to_string()))]
+        );
+        assert_eq!(offers[1].name, "x-foo");
+        assert!(offers[1].params.is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_response_negotiates_headers() {
+        let mut req = Request::new(crate::Method::Get, "/ws");
+        req.headers.push((
+            "sec-websocket-key".to_string(),
+            "dGhlIHNhbXBsZSBub25jZQ==".to_string(),
+        ));
+        req.headers.push((
+            "sec-websocket-protocol".to_string(),
+            "chat.v1, chat.v2".to_string(),
+        ));
+        req.headers.push((
+            "sec-websocket-extensions".to_string(),
+            "permessage-deflate; server_no_context_takeover, x-unknown".to_string(),
+        ));
+
+        let negotiation = UpgradeNegotiation::new()
+            .protocol("chat.v2")
+            .extension("permessage-deflate");
+        let response = upgrade_response_with(&req, &negotiation).unwrap();
+        assert_eq!(response.status.as_u16(), 101);
+        assert_eq!(response.header("sec-websocket-protocol"), Some("chat.v2"));
+        assert_eq!(
+            response.header("sec-websocket-extensions"),
+            Some("permessage-deflate")
+        );
+
+        // No offer, no headers
+        let mut plain = Request::new(crate::Method::Get, "/ws");
+        plain.headers.push((
+            "sec-websocket-key".to_string(),
+            "dGhlIHNhbXBsZSBub25jZQ==".to_string(),
+        ));
+        let response = upgrade_response_with(&plain, &negotiation).unwrap();
+        assert!(response.header("sec-websocket-protocol").is_none());
+        assert!(response.header("sec-websocket-extensions").is_none());
+    }
+
     #[test]
     fn test_frame_encode_decode() {
         let original = Frame::text("Hello, World!");
@@ -356,6 +1113,212 @@ mod tests {
         assert_eq!(decoded.payload, b"Hello, World!");
     }
 
+    fn masked(mut frame: Frame) -> Vec<u8> {
+        frame.mask = Some([0x12, 0x34, 0x56, 0x78]);
+        frame.encode()
+    }
+
+    #[test]
+    fn test_decoder_incremental_feed() {
+        let mut decoder = FrameDecoder::new(FrameDecoderConfig::new());
+        let encoded = masked(Frame::text("Hello"));
+
+        // Feed one byte at a time; the frame completes only at the end
+        for byte in &encoded[..encoded.len() - 1] {
+            decoder.feed(&[*byte]);
+            assert_eq!(decoder.next_frame(), Ok(None));
+        }
+        decoder.feed(&encoded[encoded.len() - 1..]);
+        let frame = decoder.next_frame().unwrap().unwrap();
+        assert_eq!(frame.payload, b"Hello");
+        assert_eq!(decoder.buffered(), 0);
+    }
+
+    #[test]
+    fn test_decoder_rejects_oversized_declared_length() {
+        // 8 GiB declared length must be rejected from the 10-byte
+        // header alone, without buffering any payload
+        let mut decoder = FrameDecoder::new(FrameDecoderConfig::new());
+        let mut data = vec![0x82, 0xFF];
+        data.extend_from_slice(&(8u64 << 30).to_be_bytes());
+        decoder.feed(&data);
+        assert!(matches!(
+            decoder.next_frame(),
+            Err(ProtocolError::FrameTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decoder_requires_mask_and_clear_rsv() {
+        let mut decoder = FrameDecoder::new(FrameDecoderConfig::new());
+        decoder.feed(&Frame::text("unmasked").encode());
+        assert_eq!(decoder.next_frame(), Err(ProtocolError::UnmaskedClientFrame));
+
+        let mut decoder = FrameDecoder::new(FrameDecoderConfig::new());
+        let mut encoded = masked(Frame::text("rsv"));
+        encoded[0] |= 0x40; // RSV1
+        decoder.feed(&encoded);
+        assert_eq!(decoder.next_frame(), Err(ProtocolError::ReservedBitsSet));
+    }
+
+    #[test]
+    fn test_decoder_reassembles_fragmented_message() {
+        let mut decoder = FrameDecoder::new(FrameDecoderConfig::new());
+        let mut first = Frame::text("Hel");
+        first.fin = false;
+        let mut rest = Frame {
+            fin: true,
+            opcode: Opcode::Continuation,
+            mask: None,
+            payload: b"lo".to_vec(),
+        };
+        decoder.feed(&masked(first));
+        // A ping interleaved mid-message is surfaced immediately
+        decoder.feed(&masked(Frame::ping(b"p".to_vec())));
+        rest.mask = Some([1, 2, 3, 4]);
+        decoder.feed(&rest.encode());
+
+        assert!(matches!(
+            decoder.next_message(),
+            Ok(Some(WebSocketMessage::Ping(_)))
+        ));
+        match decoder.next_message() {
+            Ok(Some(WebSocketMessage::Text(text))) => assert_eq!(text, "Hello"),
+            other => panic!("expected text message, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decoder_message_size_limit() {
+        let config = FrameDecoderConfig::new().max_message_size(4);
+        let mut decoder = FrameDecoder::new(config);
+        let mut first = Frame::text("abc");
+        first.fin = false;
+        decoder.feed(&masked(first));
+        let cont = Frame {
+            fin: true,
+            opcode: Opcode::Continuation,
+            mask: Some([9, 9, 9, 9]),
+            payload: b"de".to_vec(),
+        };
+        decoder.feed(&cont.encode());
+        assert!(matches!(
+            decoder.next_message(),
+            Err(ProtocolError::MessageTooLarge { size: 5, limit: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_connection_echoes_peer_close() {
+        let mut conn = WebSocketConnection::new(FrameDecoderConfig::new());
+        let events = conn.receive(&masked(Frame::close(1001, "bye")), 0).unwrap();
+
+        assert_eq!(conn.state(), ConnectionState::Closed);
+        assert!(matches!(&events[..], [WebSocketEvent::Closed(Some(f))] if f.code == 1001));
+        // The echo carries the peer's close code back
+        let outgoing = conn.take_outgoing();
+        assert_eq!(outgoing.len(), 1);
+        assert_eq!(outgoing[0].opcode, Opcode::Close);
+        assert_eq!(&outgoing[0].payload[..2], &1001u16.to_be_bytes());
+    }
+
+    #[test]
+    fn test_connection_close_handshake_we_initiate() {
+        let mut conn = WebSocketConnection::new(FrameDecoderConfig::new());
+        conn.close(1000, "done");
+        assert_eq!(conn.state(), ConnectionState::Closing);
+        // Sends are refused once closing has started
+        assert!(!conn.send_text("late"));
+
+        // Peer echo completes the handshake without a second close
+        let events = conn.receive(&masked(Frame::close(1000, "")), 0).unwrap();
+        assert_eq!(conn.state(), ConnectionState::Closed);
+        assert!(matches!(&events[..], [WebSocketEvent::Closed(Some(_))]));
+        assert_eq!(conn.take_outgoing().len(), 1); // only our initial close
+    }
+
+    #[test]
+    fn test_connection_answers_ping_and_fails_on_protocol_error() {
+        let mut conn = WebSocketConnection::new(FrameDecoderConfig::new());
+        conn.receive(&masked(Frame::ping(b"hb".to_vec())), 0).unwrap();
+        let outgoing = conn.take_outgoing();
+        assert_eq!(outgoing[0].opcode, Opcode::Pong);
+        assert_eq!(outgoing[0].payload, b"hb");
+
+        // Unmasked client frame: connection fails with close code 1002
+        let err = conn.receive(&Frame::text("x").encode(), 0).unwrap_err();
+        assert_eq!(err, ProtocolError::UnmaskedClientFrame);
+        assert_eq!(conn.state(), ConnectionState::Closed);
+        let close = conn.take_outgoing();
+        assert_eq!(close[0].opcode, Opcode::Close);
+        assert_eq!(&close[0].payload[..2], &1002u16.to_be_bytes());
+    }
+
+    #[test]
+    fn test_connection_ping_deadline() {
+        let mut conn = WebSocketConnection::new(FrameDecoderConfig::new())
+            .ping_interval(1_000)
+            .pong_timeout(500);
+
+        assert!(conn.tick(1_000).is_none());
+        assert_eq!(conn.take_outgoing()[0].opcode, Opcode::Ping);
+
+        // Pong in time resets the deadline
+        conn.receive(&masked(Frame::pong(Vec::new())), 1_200).unwrap();
+        assert!(conn.tick(1_400).is_none());
+        assert_eq!(conn.state(), ConnectionState::Open);
+
+        // Next ping goes unanswered past the timeout
+        conn.tick(2_200);
+        let event = conn.tick(2_800).expect("deadline must fire");
+        assert!(matches!(event, WebSocketEvent::Closed(Some(f)) if f.code == 1001));
+        assert_eq!(conn.state(), ConnectionState::Closed);
+        // The 1001 close is also written to the wire
+        let outgoing = conn.take_outgoing();
+        let close = outgoing.last().unwrap();
+        assert_eq!(close.opcode, Opcode::Close);
+        assert_eq!(&close.payload[..2], &1001u16.to_be_bytes());
+    }
+
+    #[test]
+    fn test_connection_missed_pong_limit_and_metrics() {
+        let mut conn = WebSocketConnection::new(FrameDecoderConfig::new()).heartbeat(Heartbeat {
+            interval_ms: 1_000,
+            timeout_ms: 500,
+            missed_pong_limit: 2,
+        });
+
+        // First ping answered: RTT recorded
+        conn.tick(1_000);
+        conn.receive(&masked(Frame::pong(Vec::new())), 1_250).unwrap();
+        let metrics = conn.liveness();
+        assert_eq!(metrics.pings_sent, 1);
+        assert_eq!(metrics.pongs_received, 1);
+        assert_eq!(metrics.last_rtt_ms, Some(250));
+
+        // One missed pong re-pings instead of closing
+        conn.tick(2_250);
+        assert!(conn.tick(2_800).is_none());
+        assert_eq!(conn.liveness().missed_pongs, 1);
+        assert_eq!(conn.state(), ConnectionState::Open);
+
+        // Second consecutive miss reaches the limit
+        let event = conn.tick(3_400).expect("limit must fire");
+        assert!(matches!(event, WebSocketEvent::Closed(Some(f)) if f.code == 1001));
+        assert_eq!(conn.liveness().missed_pongs, 2);
+        assert_eq!(conn.liveness().pings_sent, 3);
+    }
+
+    #[test]
+    fn test_decode_huge_extended_length() {
+        // 64-bit extended length of u64::MAX must not overflow the
+        // frame-end computation; the frame is simply incomplete
+        let mut data = vec![0x81, 0xFF];
+        data.extend_from_slice(&u64::MAX.to_be_bytes());
+        data.extend_from_slice(&[0; 8]);
+        assert!(Frame::decode(&data).is_none());
+    }
+
     #[test]
     fn test_close_frame() {
         let frame = Frame::close(1000, "Normal");
@@ -0,0 +1,202 @@
+//! Well-known route helpers
+//!
+//! Pre-rendered responses for `/robots.txt`, `/favicon.ico`, and
+//! `/.well-known/*` entries (security.txt, change-password).
+//! Configured once at startup and served as clones of pre-built
+//! responses, so crawler and browser housekeeping requests never
+//! reach application handlers.
+
+use crate::{Method, Request, Response, ResponseBuilder, StatusCode};
+
+/// Default cache lifetime for well-known responses (one day)
+const DEFAULT_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// Pre-rendered well-known routes
+pub struct WellKnown {
+    cache_control: String,
+    entries: Vec<(String, Response)>,
+}
+
+impl WellKnown {
+    pub fn new() -> Self {
+        Self {
+            cache_control: DEFAULT_CACHE_CONTROL.to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Set the Cache-Control value for all entries, including ones
+    /// already registered
+    pub fn cache_control(mut self, value: impl Into<String>) -> Self {
+        self.cache_control = value.into();
+        for (_, res) in self.entries.iter_mut() {
+            for (name, header_value) in res.headers.iter_mut() {
+                if name.eq_ignore_ascii_case("cache-control") {
+                    *header_value = self.cache_control.clone();
+                }
+            }
+        }
+        self
+    }
+
+    /// Serve `content` as `/robots.txt`
+    pub fn robots(self, content: impl Into<String>) -> Self {
+        self.entry("/robots.txt", "text/plain; charset=utf-8", content.into())
+    }
+
+    /// Serve `bytes` as `/favicon.ico`
+    pub fn favicon(self, bytes: impl Into<bytes::Bytes>) -> Self {
+        self.entry("/favicon.ico", "image/x-icon", bytes.into())
+    }
+
+    /// Serve `content` as `/.well-known/security.txt` (RFC 9116)
+    pub fn security_txt(self, content: impl Into<String>) -> Self {
+        self.entry(
+            "/.well-known/security.txt",
+            "text/plain; charset=utf-8",
+            content.into(),
+        )
+    }
+
+    /// Redirect `/.well-known/change-password` to the account
+    /// password page
+    pub fn change_password(mut self, url: impl Into<String>) -> Self {
+        let res = ResponseBuilder::new(StatusCode::FOUND)
+            .header("location", url.into())
+            .header("cache-control", &self.cache_control)
+            .build();
+        self.add("/.well-known/change-password".to_string(), res);
+        self
+    }
+
+    /// Serve an arbitrary pre-rendered entry at `path`
+    pub fn entry(
+        mut self,
+        path: impl Into<String>,
+        content_type: impl Into<String>,
+        body: impl Into<bytes::Bytes>,
+    ) -> Self {
+        let res = ResponseBuilder::new(StatusCode::OK)
+            .header("content-type", content_type.into())
+            .header("cache-control", &self.cache_control)
+            .body(body.into())
+            .build();
+        self.add(path.into(), res);
+        self
+    }
+
+    /// Registered paths, in insertion order
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(path, _)| path.as_str())
+    }
+
+    /// Look up the pre-rendered response for `path`
+    pub fn handle(&self, path: &str) -> Option<Response> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry == path)
+            .map(|(_, res)| res.clone())
+    }
+
+    /// Register or replace an entry
+    fn add(&mut self, path: String, res: Response) {
+        if let Some(existing) = self.entries.iter_mut().find(|(entry, _)| entry == &path) {
+            existing.1 = res;
+        } else {
+            self.entries.push((path, res));
+        }
+    }
+}
+
+impl Default for WellKnown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serving well-known routes from the middleware chain short-circuits
+/// dispatch before routing or any dynamic handler runs
+impl crate::middleware::Middleware for WellKnown {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        if req.method != Method::Get && req.method != Method::Head {
+            return None;
+        }
+        self.handle(&req.path)
+    }
+
+    fn after(&self, _req: &Request, _res: &mut Response) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::Middleware;
+    use crate::RequestBuilder;
+
+    #[test]
+    fn test_robots_and_favicon() {
+        let well_known = WellKnown::new()
+            .robots("User-agent: *\nDisallow: /admin\n")
+            .favicon(vec![0u8, 1, 2, 3]);
+
+        let res = well_known.handle("/robots.txt").unwrap();
+        assert_eq!(res.status, StatusCode::OK);
+        assert!(res
+            .headers
+            .iter()
+            .any(|(name, value)| name == "content-type" && value.starts_with("text/plain")));
+        assert_eq!(&res.body[..], b"User-agent: *\nDisallow: /admin\n");
+
+        let res = well_known.handle("/favicon.ico").unwrap();
+        assert!(res
+            .headers
+            .iter()
+            .any(|(name, value)| name == "content-type" && value == "image/x-icon"));
+        assert_eq!(res.body.len(), 4);
+
+        assert!(well_known.handle("/unknown").is_none());
+    }
+
+    #[test]
+    fn test_change_password_redirect() {
+        let well_known = WellKnown::new().change_password("https://example.com/account/password");
+
+        let res = well_known.handle("/.well-known/change-password").unwrap();
+        assert_eq!(res.status, StatusCode::FOUND);
+        assert!(res
+            .headers
+            .iter()
+            .any(|(name, value)| name == "location"
+                && value == "https://example.com/account/password"));
+    }
+
+    #[test]
+    fn test_cache_control_applies_to_existing_entries() {
+        let well_known = WellKnown::new()
+            .robots("User-agent: *\n")
+            .cache_control("no-store");
+
+        let res = well_known.handle("/robots.txt").unwrap();
+        assert!(res
+            .headers
+            .iter()
+            .any(|(name, value)| name == "cache-control" && value == "no-store"));
+    }
+
+    #[test]
+    fn test_middleware_serves_get_and_head_only() {
+        let well_known = WellKnown::new().security_txt("Contact: mailto:security@example.com\n");
+
+        let mut req = RequestBuilder::new(Method::Get, "/.well-known/security.txt").build();
+        assert!(well_known.before(&mut req).is_some());
+
+        let mut req = RequestBuilder::new(Method::Head, "/.well-known/security.txt").build();
+        assert!(well_known.before(&mut req).is_some());
+
+        let mut req = RequestBuilder::new(Method::Post, "/.well-known/security.txt").build();
+        assert!(well_known.before(&mut req).is_none());
+
+        let mut req = RequestBuilder::new(Method::Get, "/other").build();
+        assert!(well_known.before(&mut req).is_none());
+    }
+}
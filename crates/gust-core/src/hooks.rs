@@ -0,0 +1,210 @@
+//! Lifecycle event hooks
+//!
+//! A lightweight registry the server emits structured events into:
+//! request received, response sent, handler error, connection opened
+//! or closed. Listeners are plain `Fn` closures so custom telemetry
+//! and plugins can observe the request path without patching dispatch.
+//! Emission is synchronous on the hot path — listeners should be
+//! cheap and hand anything slow to a channel or task.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// A request entered dispatch
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    pub method: String,
+    pub path: String,
+}
+
+/// A response is about to be sent
+#[derive(Debug, Clone)]
+pub struct ResponseEvent {
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    /// Time spent in dispatch, middleware included
+    pub duration: Duration,
+}
+
+/// A request finished with a server error (5xx)
+#[derive(Debug, Clone)]
+pub struct ErrorEvent {
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub message: String,
+}
+
+/// Connection lifecycle phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionPhase {
+    Opened,
+    Closed,
+}
+
+/// A connection was accepted or finished
+#[derive(Debug, Clone)]
+pub struct ConnectionEvent {
+    pub remote_addr: String,
+    pub phase: ConnectionPhase,
+}
+
+type Listeners<E> = RwLock<Vec<Arc<dyn Fn(&E) + Send + Sync>>>;
+
+fn emit<E>(listeners: &Listeners<E>, event: &E) {
+    let Ok(listeners) = listeners.read() else {
+        return;
+    };
+    for listener in listeners.iter() {
+        listener(event);
+    }
+}
+
+/// Registry of lifecycle listeners
+///
+/// Registration is interior-mutable so listeners can be added through
+/// a shared handle after the server is built.
+pub struct HookRegistry {
+    on_request: Listeners<RequestEvent>,
+    on_response: Listeners<ResponseEvent>,
+    on_error: Listeners<ErrorEvent>,
+    on_connection: Listeners<ConnectionEvent>,
+}
+
+impl HookRegistry {
+    pub fn new() -> Self {
+        Self {
+            on_request: RwLock::new(Vec::new()),
+            on_response: RwLock::new(Vec::new()),
+            on_error: RwLock::new(Vec::new()),
+            on_connection: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn on_request(&self, listener: impl Fn(&RequestEvent) + Send + Sync + 'static) {
+        self.on_request.write().unwrap().push(Arc::new(listener));
+    }
+
+    pub fn on_response(&self, listener: impl Fn(&ResponseEvent) + Send + Sync + 'static) {
+        self.on_response.write().unwrap().push(Arc::new(listener));
+    }
+
+    pub fn on_error(&self, listener: impl Fn(&ErrorEvent) + Send + Sync + 'static) {
+        self.on_error.write().unwrap().push(Arc::new(listener));
+    }
+
+    pub fn on_connection(&self, listener: impl Fn(&ConnectionEvent) + Send + Sync + 'static) {
+        self.on_connection.write().unwrap().push(Arc::new(listener));
+    }
+
+    /// Whether any request/response/error listener is registered;
+    /// lets dispatch skip payload construction entirely
+    pub fn observes_requests(&self) -> bool {
+        !self.on_request.read().unwrap().is_empty()
+            || !self.on_response.read().unwrap().is_empty()
+            || !self.on_error.read().unwrap().is_empty()
+    }
+
+    pub fn observes_connections(&self) -> bool {
+        !self.on_connection.read().unwrap().is_empty()
+    }
+
+    pub fn emit_request(&self, event: &RequestEvent) {
+        emit(&self.on_request, event);
+    }
+
+    pub fn emit_response(&self, event: &ResponseEvent) {
+        emit(&self.on_response, event);
+    }
+
+    pub fn emit_error(&self, event: &ErrorEvent) {
+        emit(&self.on_error, event);
+    }
+
+    pub fn emit_connection(&self, event: &ConnectionEvent) {
+        emit(&self.on_connection, event);
+    }
+}
+
+impl Default for HookRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_listeners_receive_events() {
+        let hooks = HookRegistry::new();
+        let seen: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let log = Arc::clone(&seen);
+        hooks.on_request(move |event| {
+            log.lock().unwrap().push(format!("req {} {}", event.method, event.path));
+        });
+        let log = Arc::clone(&seen);
+        hooks.on_response(move |event| {
+            log.lock().unwrap().push(format!("res {}", event.status));
+        });
+        let log = Arc::clone(&seen);
+        hooks.on_connection(move |event| {
+            log.lock().unwrap().push(format!("conn {:?}", event.phase));
+        });
+
+        hooks.emit_connection(&ConnectionEvent {
+            remote_addr: "127.0.0.1:9000".to_string(),
+            phase: ConnectionPhase::Opened,
+        });
+        hooks.emit_request(&RequestEvent {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+        });
+        hooks.emit_response(&ResponseEvent {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+            status: 200,
+            duration: Duration::from_millis(3),
+        });
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["conn Opened", "req GET /users", "res 200"]
+        );
+    }
+
+    #[test]
+    fn test_observes_flags() {
+        let hooks = HookRegistry::new();
+        assert!(!hooks.observes_requests());
+        assert!(!hooks.observes_connections());
+
+        hooks.on_error(|_| {});
+        assert!(hooks.observes_requests());
+        hooks.on_connection(|_| {});
+        assert!(hooks.observes_connections());
+    }
+
+    #[test]
+    fn test_all_listeners_run_in_order() {
+        let hooks = HookRegistry::new();
+        let seen: Arc<Mutex<Vec<u32>>> = Arc::new(Mutex::new(Vec::new()));
+
+        for i in 0..3 {
+            let log = Arc::clone(&seen);
+            hooks.on_error(move |_| log.lock().unwrap().push(i));
+        }
+        hooks.emit_error(&ErrorEvent {
+            method: "GET".to_string(),
+            path: "/".to_string(),
+            status: 500,
+            message: "boom".to_string(),
+        });
+
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 2]);
+    }
+}
@@ -7,6 +7,7 @@
 //! - `native` - Native server with tokio/hyper
 //! - `tls` - TLS support via rustls
 //! - `compress` - Compression support (gzip, brotli)
+//! - `tower` - Tower/Axum compatibility layer
 
 #![forbid(unsafe_code)]
 #![warn(clippy::all)]
@@ -21,6 +22,14 @@ pub mod middleware;
 pub mod pure;
 pub mod handlers;
 pub mod tracing;
+pub mod pool;
+pub mod balance;
+pub mod hooks;
+pub mod timeout;
+pub mod profile;
+
+#[cfg(feature = "native")]
+pub mod plugin;
 
 #[cfg(feature = "native")]
 pub mod http2;
@@ -28,17 +37,51 @@ pub mod http2;
 #[cfg(feature = "native")]
 pub mod server;
 
+#[cfg(feature = "native")]
+pub mod s3;
+
+#[cfg(feature = "native")]
+pub mod dns;
+
+#[cfg(feature = "native")]
+pub mod scope;
+
+#[cfg(feature = "native")]
+pub mod upstream;
+
+#[cfg(feature = "native")]
+pub mod discovery;
+
+#[cfg(feature = "native")]
+pub mod outlier;
+
+#[cfg(feature = "native")]
+pub mod raw_tcp;
+
+#[cfg(feature = "native")]
+pub mod raw_udp;
+
+#[cfg(feature = "raw-http1")]
+pub mod raw_http1;
+
 #[cfg(feature = "tls")]
 pub mod tls;
 
+#[cfg(feature = "tower")]
+pub mod tower_compat;
+
 // Re-exports
 pub use error::{Error, Result};
 pub use request::{Method, Request, RequestBuilder};
 pub use response::{Response, ResponseBuilder, StatusCode};
-pub use router::{Router, Match};
+pub use router::{Router, Match, RouteError};
+pub use timeout::{TimeoutConfig, TimeoutHierarchy, TimeoutPhase};
+pub use profile::Profiler;
 
 // Middleware re-exports
 pub use middleware::{Middleware, MiddlewareChain};
+pub use pool::{ObjectPool, Pooled, PoolStats};
+pub use hooks::{ConnectionEvent, ConnectionPhase, ErrorEvent, HookRegistry, RequestEvent, ResponseEvent};
 pub use pure::{parse_client_ip, fixed_window_decision, sliding_window_decision, rate_limit_headers, RateLimitDecision};
 
 // Handlers re-exports
@@ -48,11 +91,16 @@ pub use handlers::{
     is_websocket_upgrade, generate_accept_key, upgrade_response as websocket_upgrade_response,
     Sse, SseEvent, SseStream,
     StaticFiles, StaticFileConfig,
+    FileMeta, MetadataCache, MetadataCacheStats,
+    HotFileCache, HotFileCacheConfig, HotFileCacheStats,
     Health, HealthCheck, HealthStatus,
 };
 
 #[cfg(feature = "native")]
-pub use server::{ServerConfig, ServerState, StaticRoute, DynamicHandler, ConnectionTracker};
+pub use server::{ServerConfig, ServerState, StaticRoute, DynamicHandler, ConnectionTracker, ConnectionStats, KeepAliveStats, Server, ServerBuilder};
+
+#[cfg(feature = "native")]
+pub use plugin::{GustPlugin, PluginRoute, PLUGIN_ABI_VERSION};
 
 #[cfg(feature = "native")]
 pub use server::{create_optimized_socket, from_hyper_request, to_hyper_response};
@@ -60,8 +108,37 @@ pub use server::{create_optimized_socket, from_hyper_request, to_hyper_response}
 #[cfg(feature = "native")]
 pub use http2::{Http2Settings, Http2Response, PushPromise, Priority, ConnectionInfo};
 
+#[cfg(feature = "native")]
+pub use s3::{S3Client, S3Config, ObjectInfo};
+
+#[cfg(feature = "native")]
+pub use dns::{default_resolver, DnsConfig, Resolver, SrvRecord};
+
+#[cfg(feature = "native")]
+pub use scope::RequestScope;
+
+#[cfg(feature = "native")]
+pub use upstream::{UpstreamBody, UpstreamConfig, UpstreamPool, UpstreamStats, UpstreamTtfb};
+
+#[cfg(feature = "native")]
+pub use discovery::ServiceSet;
+
+#[cfg(feature = "native")]
+pub use outlier::{HealthChecker, HealthCheckConfig, HealthStats, HealthTransition, MemberHealth, Probe};
+
+#[cfg(feature = "native")]
+pub use raw_tcp::{RawTcpListener, TcpConnection, TcpEvent, TcpEventHandler};
+
+#[cfg(feature = "native")]
+pub use raw_udp::{Datagram, UdpBatchHandler, UdpConfig, UdpListener};
+
+pub use balance::{AffinityKey, HashRing, RingCheckout};
+
 #[cfg(feature = "tls")]
-pub use tls::{TlsConfig, load_certs, load_private_key};
+pub use tls::{TlsConfig, load_certs, load_private_key, server_config_from_der};
+
+#[cfg(feature = "tower")]
+pub use tower_compat::{handler_service, GustService, HandlerService};
 
 // Re-export core dependencies for downstream crates (SSOT)
 pub use bytes;
@@ -75,3 +152,5 @@ pub use hyper_util;
 pub use http_body_util;
 #[cfg(feature = "native")]
 pub use num_cpus;
+#[cfg(feature = "tower")]
+pub use tower;
@@ -0,0 +1,421 @@
+//! Adaptive concurrency limiting (AIMD/Vegas)
+//!
+//! Instead of a hand-tuned concurrency cap, the limiter probes for the
+//! latency-optimal in-flight limit: it remembers the best (no-load)
+//! latency seen and estimates queueing from how far the current window
+//! average sits above it, Vegas-style. Little queueing grows the limit
+//! additively; heavy queueing shrinks it multiplicatively (AIMD), so
+//! the limit converges just below the point where latency climbs.
+//! Excess load is shed with `503` + `Retry-After`. Limits are tracked
+//! per route so one slow endpoint cannot starve the rest.
+
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Adaptive limiter configuration
+#[derive(Debug, Clone)]
+pub struct AdaptiveConfig {
+    /// Limit floor; the limiter never sheds below this (default: 1)
+    pub min_limit: u64,
+    /// Limit ceiling (default: 1000)
+    pub max_limit: u64,
+    /// Starting limit for new routes (default: 16)
+    pub initial_limit: u64,
+    /// Estimated queued requests below which the limit grows
+    /// (Vegas alpha, default: 3.0)
+    pub alpha: f64,
+    /// Estimated queued requests above which the limit shrinks
+    /// (Vegas beta, default: 6.0)
+    pub beta: f64,
+    /// Multiplicative decrease factor on overload (default: 0.9)
+    pub backoff_ratio: f64,
+    /// Completed requests per limit adjustment (default: 10)
+    pub sample_window: u64,
+    /// Track one limit per route path instead of a single global one
+    /// (default: true)
+    pub per_route: bool,
+    /// Retry-After seconds on shed responses (default: 1)
+    pub retry_after_secs: u32,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            min_limit: 1,
+            max_limit: 1000,
+            initial_limit: 16,
+            alpha: 3.0,
+            beta: 6.0,
+            backoff_ratio: 0.9,
+            sample_window: 10,
+            per_route: true,
+            retry_after_secs: 1,
+        }
+    }
+}
+
+impl AdaptiveConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min_limit(mut self, limit: u64) -> Self {
+        self.min_limit = limit.max(1);
+        self
+    }
+
+    pub fn max_limit(mut self, limit: u64) -> Self {
+        self.max_limit = limit.max(1);
+        self
+    }
+
+    pub fn initial_limit(mut self, limit: u64) -> Self {
+        self.initial_limit = limit.max(1);
+        self
+    }
+
+    /// Vegas thresholds: grow below `alpha` queued, shrink above
+    /// `beta` queued
+    pub fn thresholds(mut self, alpha: f64, beta: f64) -> Self {
+        self.alpha = alpha.max(0.0);
+        self.beta = beta.max(self.alpha);
+        self
+    }
+
+    pub fn backoff_ratio(mut self, ratio: f64) -> Self {
+        self.backoff_ratio = ratio.clamp(0.1, 1.0);
+        self
+    }
+
+    pub fn sample_window(mut self, samples: u64) -> Self {
+        self.sample_window = samples.max(1);
+        self
+    }
+
+    pub fn per_route(mut self, per_route: bool) -> Self {
+        self.per_route = per_route;
+        self
+    }
+
+    pub fn retry_after_secs(mut self, secs: u32) -> Self {
+        self.retry_after_secs = secs;
+        self
+    }
+}
+
+/// Gauges for one limiter
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveStats {
+    /// Current in-flight limit
+    pub limit: u64,
+    /// Requests currently in flight
+    pub in_flight: u64,
+    /// Requests shed at the limit
+    pub shed: u64,
+    /// Best latency observed (no-load baseline), in milliseconds
+    pub min_latency_ms: f64,
+    /// Average latency of the last full sample window, in milliseconds
+    pub window_latency_ms: f64,
+}
+
+/// Latency samples driving the next limit adjustment
+struct SampleState {
+    /// Best latency seen; the Vegas no-load baseline
+    min_latency: Option<Duration>,
+    /// Sum and count for the current window
+    window_sum: Duration,
+    window_count: u64,
+    /// Average of the last completed window
+    last_window_avg: Option<Duration>,
+}
+
+/// One adaptive limit (one route, or the global one)
+pub struct AdaptiveLimiter {
+    config: AdaptiveConfig,
+    limit: AtomicU64,
+    in_flight: AtomicU64,
+    shed: AtomicU64,
+    samples: Mutex<SampleState>,
+}
+
+impl AdaptiveLimiter {
+    pub fn new(config: AdaptiveConfig) -> Self {
+        let initial = config.initial_limit.clamp(config.min_limit, config.max_limit);
+        Self {
+            config,
+            limit: AtomicU64::new(initial),
+            in_flight: AtomicU64::new(0),
+            shed: AtomicU64::new(0),
+            samples: Mutex::new(SampleState {
+                min_latency: None,
+                window_sum: Duration::ZERO,
+                window_count: 0,
+                last_window_avg: None,
+            }),
+        }
+    }
+
+    /// Take an in-flight slot; `false` means shed this request
+    pub fn try_acquire(&self) -> bool {
+        let limit = self.limit.load(Ordering::Relaxed);
+        // Optimistic increment; back out if the limit was crossed
+        if self.in_flight.fetch_add(1, Ordering::Relaxed) < limit {
+            true
+        } else {
+            self.in_flight.fetch_sub(1, Ordering::Relaxed);
+            self.shed.fetch_add(1, Ordering::Relaxed);
+            false
+        }
+    }
+
+    /// Release a slot and feed the observed latency into the window
+    pub fn release(&self, latency: Duration) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+
+        let Ok(mut samples) = self.samples.lock() else {
+            return;
+        };
+        samples.min_latency = Some(match samples.min_latency {
+            Some(min) => min.min(latency),
+            None => latency,
+        });
+        samples.window_sum += latency;
+        samples.window_count += 1;
+        if samples.window_count < self.config.sample_window {
+            return;
+        }
+
+        let avg = samples.window_sum / samples.window_count as u32;
+        samples.last_window_avg = Some(avg);
+        samples.window_sum = Duration::ZERO;
+        samples.window_count = 0;
+        let min = samples.min_latency.unwrap_or(avg);
+        drop(samples);
+
+        // Vegas: queued ~= limit * (1 - minRTT / avgRTT)
+        let limit = self.limit.load(Ordering::Relaxed);
+        let queued = if avg > Duration::ZERO {
+            limit as f64 * (1.0 - min.as_secs_f64() / avg.as_secs_f64())
+        } else {
+            0.0
+        };
+        let next = if queued < self.config.alpha {
+            limit + 1
+        } else if queued > self.config.beta {
+            ((limit as f64 * self.config.backoff_ratio) as u64).max(1)
+        } else {
+            limit
+        };
+        self.limit.store(
+            next.clamp(self.config.min_limit, self.config.max_limit),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Current gauges for this limiter
+    pub fn stats(&self) -> AdaptiveStats {
+        let (min_latency_ms, window_latency_ms) = self
+            .samples
+            .lock()
+            .map(|samples| {
+                (
+                    samples
+                        .min_latency
+                        .map(|d| d.as_secs_f64() * 1000.0)
+                        .unwrap_or(0.0),
+                    samples
+                        .last_window_avg
+                        .map(|d| d.as_secs_f64() * 1000.0)
+                        .unwrap_or(0.0),
+                )
+            })
+            .unwrap_or((0.0, 0.0));
+        AdaptiveStats {
+            limit: self.limit.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            shed: self.shed.load(Ordering::Relaxed),
+            min_latency_ms,
+            window_latency_ms,
+        }
+    }
+
+    /// Current in-flight limit
+    pub fn limit(&self) -> u64 {
+        self.limit.load(Ordering::Relaxed)
+    }
+}
+
+/// Adaptive concurrency middleware over per-route limiters
+pub struct AdaptiveConcurrency {
+    config: AdaptiveConfig,
+    limiters: Mutex<HashMap<String, Arc<AdaptiveLimiter>>>,
+    created_at: Instant,
+}
+
+impl AdaptiveConcurrency {
+    pub fn new(config: AdaptiveConfig) -> Self {
+        Self {
+            config,
+            limiters: Mutex::new(HashMap::new()),
+            created_at: Instant::now(),
+        }
+    }
+
+    fn key(&self, req: &Request) -> String {
+        if self.config.per_route {
+            req.path.clone()
+        } else {
+            "*".to_string()
+        }
+    }
+
+    /// Limiter for a key, created at the initial limit on first use
+    pub fn limiter(&self, key: &str) -> Arc<AdaptiveLimiter> {
+        let mut limiters = self.limiters.lock().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(
+            limiters
+                .entry(key.to_string())
+                .or_insert_with(|| Arc::new(AdaptiveLimiter::new(self.config.clone()))),
+        )
+    }
+
+    /// Gauges for every tracked route
+    pub fn stats(&self) -> Vec<(String, AdaptiveStats)> {
+        let limiters = self.limiters.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<(String, AdaptiveStats)> = limiters
+            .iter()
+            .map(|(key, limiter)| (key.clone(), limiter.stats()))
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+}
+
+impl Default for AdaptiveConcurrency {
+    fn default() -> Self {
+        Self::new(AdaptiveConfig::default())
+    }
+}
+
+impl super::Middleware for AdaptiveConcurrency {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        let key = self.key(req);
+        if !self.limiter(&key).try_acquire() {
+            return Some(
+                ResponseBuilder::new(StatusCode(503))
+                    .header("content-type", "text/plain")
+                    .header("retry-after", self.config.retry_after_secs.to_string())
+                    .body("Service Unavailable")
+                    .build(),
+            );
+        }
+        req.params.insert(
+            "_adaptive_start".to_string(),
+            self.created_at.elapsed().as_micros().to_string(),
+        );
+        None
+    }
+
+    fn after(&self, req: &Request, _res: &mut Response) {
+        // Shed requests never set the marker and hold no slot
+        let Some(start) = req.params.get("_adaptive_start") else {
+            return;
+        };
+        let latency = start
+            .parse::<u64>()
+            .map(|micros| {
+                self.created_at
+                    .elapsed()
+                    .saturating_sub(Duration::from_micros(micros))
+            })
+            .unwrap_or(Duration::ZERO);
+        self.limiter(&self.key(req)).release(latency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::Middleware;
+    use crate::{Method, RequestBuilder};
+
+    fn request(path: &str) -> Request {
+        RequestBuilder::new(Method::Get, path).build()
+    }
+
+    #[test]
+    fn test_limit_grows_on_low_latency() {
+        let limiter = AdaptiveLimiter::new(
+            AdaptiveConfig::new().initial_limit(4).sample_window(5),
+        );
+
+        // Uniform latency: no queueing signal, limit climbs additively
+        for _ in 0..5 {
+            assert!(limiter.try_acquire());
+            limiter.release(Duration::from_millis(5));
+        }
+        assert_eq!(limiter.limit(), 5);
+    }
+
+    #[test]
+    fn test_limit_backs_off_on_queueing() {
+        let limiter = AdaptiveLimiter::new(
+            AdaptiveConfig::new().initial_limit(100).sample_window(4),
+        );
+
+        // Establish the no-load baseline
+        limiter.try_acquire();
+        limiter.release(Duration::from_millis(1));
+        // Then a window where latency is far above it: heavy queueing
+        for _ in 0..4 {
+            limiter.try_acquire();
+            limiter.release(Duration::from_millis(50));
+        }
+        assert_eq!(limiter.limit(), 90);
+
+        let stats = limiter.stats();
+        assert_eq!(stats.min_latency_ms, 1.0);
+        assert!(stats.window_latency_ms > 30.0);
+    }
+
+    #[test]
+    fn test_sheds_at_limit_and_respects_floor() {
+        let limiter = AdaptiveLimiter::new(
+            AdaptiveConfig::new().initial_limit(2).min_limit(2),
+        );
+
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        assert_eq!(limiter.stats().shed, 1);
+        limiter.release(Duration::from_millis(1));
+        assert!(limiter.try_acquire());
+    }
+
+    #[test]
+    fn test_middleware_tracks_routes_independently() {
+        let adaptive = AdaptiveConcurrency::new(
+            AdaptiveConfig::new().initial_limit(1),
+        );
+
+        // Saturate /slow; /fast must still be admitted
+        let mut slow = request("/slow");
+        assert!(adaptive.before(&mut slow).is_none());
+        let shed = adaptive.before(&mut request("/slow")).unwrap();
+        assert_eq!(shed.status.0, 503);
+        assert!(shed
+            .headers
+            .iter()
+            .any(|(name, _)| name == "retry-after"));
+        assert!(adaptive.before(&mut request("/fast")).is_none());
+
+        adaptive.after(&slow, &mut Response::ok());
+        let stats = adaptive.stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].0, "/fast");
+        assert_eq!(stats[1].1.shed, 1);
+    }
+}
@@ -0,0 +1,382 @@
+//! Request prioritization and admission control
+//!
+//! Sits in front of handler dispatch: each request gets a priority
+//! (from a route rule or a request header) and, once concurrency or
+//! latency targets are breached, low-priority requests are shed with
+//! `503` + `Retry-After` instead of queueing behind work the server
+//! cannot absorb. Latency overload is judged CoDel-style: the window
+//! is breached only when the *fastest* request of a full interval
+//! exceeded the target, so one slow outlier never triggers shedding.
+
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Request priority, lowest first so priorities compare naturally
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Shed first: background jobs, prefetches, crawlers
+    Low,
+    /// Default for unmarked requests
+    Normal,
+    /// Shed only at the hard concurrency cap: health checks, payments
+    High,
+}
+
+impl Priority {
+    /// Parse a header value ("low", "normal", "high")
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "low" => Some(Priority::Low),
+            "normal" => Some(Priority::Normal),
+            "high" => Some(Priority::High),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+        }
+    }
+}
+
+/// Admission control configuration
+#[derive(Debug, Clone)]
+pub struct AdmissionConfig {
+    /// In-flight requests where Normal traffic starts shedding; Low
+    /// sheds at 3/4 of this, High at twice it (default: 256)
+    pub max_concurrency: u64,
+    /// Latency target; a full interval whose fastest request exceeds
+    /// this sheds Low traffic until latency recovers (default: 100ms)
+    pub target_latency: Duration,
+    /// Evaluation window for the latency target (default: 1s)
+    pub interval: Duration,
+    /// Retry-After seconds on shed responses (default: 1)
+    pub retry_after_secs: u32,
+    /// Header carrying the client-requested priority
+    /// (default: x-priority)
+    pub header: String,
+    /// Path-prefix priority rules, first match wins; these override
+    /// the header so clients cannot promote routes marked Low
+    pub routes: Vec<(String, Priority)>,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 256,
+            target_latency: Duration::from_millis(100),
+            interval: Duration::from_secs(1),
+            retry_after_secs: 1,
+            header: "x-priority".to_string(),
+            routes: Vec::new(),
+        }
+    }
+}
+
+impl AdmissionConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_concurrency(mut self, max: u64) -> Self {
+        self.max_concurrency = max.max(1);
+        self
+    }
+
+    pub fn target_latency(mut self, target: Duration) -> Self {
+        self.target_latency = target;
+        self
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn retry_after_secs(mut self, secs: u32) -> Self {
+        self.retry_after_secs = secs;
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>) -> Self {
+        self.header = name.into();
+        self
+    }
+
+    /// Pin a path prefix to a priority (e.g. `/healthz` -> High,
+    /// `/export` -> Low)
+    pub fn route(mut self, prefix: impl Into<String>, priority: Priority) -> Self {
+        self.routes.push((prefix.into(), priority));
+        self
+    }
+}
+
+/// Snapshot of admission activity
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdmissionStats {
+    /// Requests admitted to dispatch
+    pub admitted: u64,
+    /// Requests shed with 503
+    pub shed: u64,
+    /// Requests currently in flight
+    pub in_flight: u64,
+    /// Latency target currently breached
+    pub latency_breached: bool,
+}
+
+/// CoDel-style latency window
+struct Window {
+    start: Instant,
+    /// Fastest completed request this interval
+    min_latency: Option<Duration>,
+}
+
+/// Priority-aware admission control middleware
+pub struct Admission {
+    config: AdmissionConfig,
+    in_flight: AtomicU64,
+    admitted: AtomicU64,
+    shed: AtomicU64,
+    latency_breached: AtomicBool,
+    window: Mutex<Window>,
+    /// Creation time; request start offsets are stored relative to it
+    created_at: Instant,
+}
+
+impl Admission {
+    pub fn new(config: AdmissionConfig) -> Self {
+        Self {
+            config,
+            in_flight: AtomicU64::new(0),
+            admitted: AtomicU64::new(0),
+            shed: AtomicU64::new(0),
+            latency_breached: AtomicBool::new(false),
+            window: Mutex::new(Window {
+                start: Instant::now(),
+                min_latency: None,
+            }),
+            created_at: Instant::now(),
+        }
+    }
+
+    /// Priority for a request: route rules first, then the header
+    pub fn priority(&self, req: &Request) -> Priority {
+        for (prefix, priority) in &self.config.routes {
+            if req.path.starts_with(prefix.as_str()) {
+                return *priority;
+            }
+        }
+        req.header(&self.config.header)
+            .and_then(Priority::parse)
+            .unwrap_or(Priority::Normal)
+    }
+
+    /// Whether a request of this priority is admitted right now
+    pub fn admits(&self, priority: Priority) -> bool {
+        let in_flight = self.in_flight.load(Ordering::Relaxed);
+        let max = self.config.max_concurrency;
+        match priority {
+            Priority::High => in_flight < max * 2,
+            Priority::Normal => in_flight < max,
+            Priority::Low => {
+                in_flight < max * 3 / 4 && !self.latency_breached.load(Ordering::Relaxed)
+            }
+        }
+    }
+
+    /// Record a completed request's latency into the current window
+    fn record_latency(&self, latency: Duration) {
+        let Ok(mut window) = self.window.lock() else {
+            return;
+        };
+        window.min_latency = Some(match window.min_latency {
+            Some(min) => min.min(latency),
+            None => latency,
+        });
+        if window.start.elapsed() >= self.config.interval {
+            // A full interval where even the fastest request missed
+            // the target means sustained overload, not an outlier
+            let breached = window
+                .min_latency
+                .map(|min| min > self.config.target_latency)
+                .unwrap_or(false);
+            self.latency_breached.store(breached, Ordering::Relaxed);
+            window.start = Instant::now();
+            window.min_latency = None;
+        }
+    }
+
+    /// Snapshot of counters and the current overload state
+    pub fn stats(&self) -> AdmissionStats {
+        AdmissionStats {
+            admitted: self.admitted.load(Ordering::Relaxed),
+            shed: self.shed.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            latency_breached: self.latency_breached.load(Ordering::Relaxed),
+        }
+    }
+
+    fn shed_response(&self) -> Response {
+        ResponseBuilder::new(StatusCode(503))
+            .header("content-type", "text/plain")
+            .header("retry-after", self.config.retry_after_secs.to_string())
+            .body("Service Unavailable")
+            .build()
+    }
+}
+
+impl Default for Admission {
+    fn default() -> Self {
+        Self::new(AdmissionConfig::default())
+    }
+}
+
+impl super::Middleware for Admission {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        let priority = self.priority(req);
+        if !self.admits(priority) {
+            self.shed.fetch_add(1, Ordering::Relaxed);
+            return Some(self.shed_response());
+        }
+
+        self.admitted.fetch_add(1, Ordering::Relaxed);
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        req.params.insert(
+            "_admission_start".to_string(),
+            self.created_at.elapsed().as_micros().to_string(),
+        );
+        None
+    }
+
+    fn after(&self, req: &Request, _res: &mut Response) {
+        // Shed requests never set the marker and hold no slot
+        let Some(start) = req.params.get("_admission_start") else {
+            return;
+        };
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        if let Ok(micros) = start.parse::<u64>() {
+            let latency = self
+                .created_at
+                .elapsed()
+                .saturating_sub(Duration::from_micros(micros));
+            self.record_latency(latency);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::Middleware;
+    use crate::{Method, RequestBuilder};
+
+    fn request(path: &str, priority: Option<&str>) -> Request {
+        let mut builder = RequestBuilder::new(Method::Get, path);
+        if let Some(priority) = priority {
+            builder = builder.header("x-priority", priority);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn test_priority_from_header_and_routes() {
+        let admission = Admission::new(
+            AdmissionConfig::new()
+                .route("/healthz", Priority::High)
+                .route("/export", Priority::Low),
+        );
+
+        assert_eq!(admission.priority(&request("/", None)), Priority::Normal);
+        assert_eq!(
+            admission.priority(&request("/", Some("low"))),
+            Priority::Low
+        );
+        assert_eq!(
+            admission.priority(&request("/", Some("HIGH"))),
+            Priority::High
+        );
+        // Route rules override the header in both directions
+        assert_eq!(
+            admission.priority(&request("/healthz", Some("low"))),
+            Priority::High
+        );
+        assert_eq!(
+            admission.priority(&request("/export/users", Some("high"))),
+            Priority::Low
+        );
+    }
+
+    #[test]
+    fn test_concurrency_sheds_by_priority() {
+        let admission = Admission::new(AdmissionConfig::new().max_concurrency(4));
+
+        // Fill to the Low watermark (3/4 of 4 = 3)
+        for _ in 0..3 {
+            assert!(admission.before(&mut request("/", None)).is_none());
+        }
+        assert!(!admission.admits(Priority::Low));
+        assert!(admission.admits(Priority::Normal));
+
+        // One more reaches the Normal limit; only High gets through
+        assert!(admission.before(&mut request("/", None)).is_none());
+        assert!(!admission.admits(Priority::Normal));
+        assert!(admission.admits(Priority::High));
+
+        let response = admission.before(&mut request("/", Some("low"))).unwrap();
+        assert_eq!(response.status.0, 503);
+        assert!(response
+            .headers
+            .iter()
+            .any(|(name, value)| name == "retry-after" && value == "1"));
+
+        let stats = admission.stats();
+        assert_eq!(stats.admitted, 4);
+        assert_eq!(stats.shed, 1);
+        assert_eq!(stats.in_flight, 4);
+    }
+
+    #[test]
+    fn test_after_releases_slot_only_for_admitted() {
+        let admission = Admission::new(AdmissionConfig::new().max_concurrency(4));
+
+        let mut admitted = request("/", None);
+        assert!(admission.before(&mut admitted).is_none());
+        assert_eq!(admission.stats().in_flight, 1);
+
+        // A request without the marker (shed, or admission added
+        // mid-flight) must not release anyone else's slot
+        let unmarked = request("/", None);
+        admission.after(&unmarked, &mut Response::ok());
+        assert_eq!(admission.stats().in_flight, 1);
+
+        admission.after(&admitted, &mut Response::ok());
+        assert_eq!(admission.stats().in_flight, 0);
+    }
+
+    #[test]
+    fn test_latency_breach_sheds_low_priority() {
+        let admission = Admission::new(
+            AdmissionConfig::new()
+                .target_latency(Duration::ZERO)
+                .interval(Duration::ZERO),
+        );
+
+        let mut req = request("/", None);
+        assert!(admission.before(&mut req).is_none());
+        std::thread::sleep(Duration::from_millis(2));
+        admission.after(&req, &mut Response::ok());
+
+        // Even the fastest request of the interval missed the target
+        assert!(admission.stats().latency_breached);
+        assert!(!admission.admits(Priority::Low));
+        assert!(admission.admits(Priority::Normal));
+        assert!(admission
+            .before(&mut request("/", Some("low")))
+            .is_some());
+    }
+}
@@ -15,6 +15,115 @@ use parking_lot::RwLock;
 #[cfg(not(feature = "native"))]
 use std::sync::RwLock;
 
+/// Which query parameters contribute to the cache key
+#[derive(Clone)]
+pub enum QueryKeys {
+    /// All parameters, normalized and sorted (default)
+    All,
+    /// Ignore the query string entirely
+    None,
+    /// Only the named parameters
+    Only(Vec<String>),
+}
+
+/// Cache key shape: which request attributes form the key
+///
+/// Keys always include method and path; the shape adds normalized
+/// query parameters and selected headers/cookies so caching stays
+/// correct behind CDNs that vary responses.
+#[derive(Clone, Default)]
+pub struct CacheKeyConfig {
+    /// Request headers included in the key
+    pub vary_headers: Vec<String>,
+    /// Cookie names included in the key
+    pub vary_cookies: Vec<String>,
+    /// Query parameter handling
+    pub query: Option<QueryKeys>,
+}
+
+impl CacheKeyConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn vary_header(mut self, name: impl Into<String>) -> Self {
+        self.vary_headers.push(name.into().to_lowercase());
+        self
+    }
+
+    pub fn vary_cookie(mut self, name: impl Into<String>) -> Self {
+        self.vary_cookies.push(name.into());
+        self
+    }
+
+    pub fn query(mut self, keys: QueryKeys) -> Self {
+        self.query = Some(keys);
+        self
+    }
+}
+
+/// Build a cache key for a request under a key shape
+pub fn build_cache_key(req: &Request, key: &CacheKeyConfig) -> String {
+    let mut out = format!("{}:{}", req.method.as_str(), req.path);
+
+    // Normalized, sorted query parameters
+    out.push(':');
+    let query = key.query.as_ref().unwrap_or(&QueryKeys::All);
+    if !matches!(query, QueryKeys::None) {
+        let mut params: Vec<(String, String)> = req.query_params().into_iter().collect();
+        if let QueryKeys::Only(names) = query {
+            params.retain(|(name, _)| names.contains(name));
+        }
+        params.sort();
+        for (i, (name, value)) in params.iter().enumerate() {
+            if i > 0 {
+                out.push('&');
+            }
+            out.push_str(name);
+            out.push('=');
+            out.push_str(value);
+        }
+    }
+
+    for name in &key.vary_headers {
+        out.push_str(":h:");
+        out.push_str(name);
+        out.push('=');
+        out.push_str(req.header(name).unwrap_or(""));
+    }
+
+    if !key.vary_cookies.is_empty() {
+        let cookies = crate::pure::cookie::parse_cookies(req.header("cookie").unwrap_or(""));
+        for name in &key.vary_cookies {
+            out.push_str(":c:");
+            out.push_str(name);
+            out.push('=');
+            out.push_str(cookies.get(name).map(|v| v.as_str()).unwrap_or(""));
+        }
+    }
+
+    out
+}
+
+/// Hit/miss counters for one key shape
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Hit ratio in [0, 1]; 0 when no lookups have happened
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
 /// Cache configuration
 #[derive(Clone)]
 pub struct CacheConfig {
@@ -24,8 +133,12 @@ pub struct CacheConfig {
     pub max_entries: usize,
     /// Methods to cache (default: GET, HEAD)
     pub methods: Vec<Method>,
-    /// Key generator
+    /// Key generator, used when no key shape is configured
     pub key_fn: fn(&Request) -> String,
+    /// Default cache key shape
+    pub key: Option<CacheKeyConfig>,
+    /// Per-route key shapes as (path prefix, shape); first match wins
+    pub route_keys: Vec<(String, CacheKeyConfig)>,
     /// Condition for caching
     pub condition: Option<fn(&Request, &Response) -> bool>,
 }
@@ -37,6 +150,8 @@ impl Default for CacheConfig {
             max_entries: 1000,
             methods: vec![Method::Get, Method::Head],
             key_fn: default_cache_key,
+            key: None,
+            route_keys: Vec::new(),
             condition: None,
         }
     }
@@ -77,6 +192,18 @@ impl CacheConfig {
         self
     }
 
+    /// Set the default cache key shape
+    pub fn key(mut self, key: CacheKeyConfig) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Add a per-route key shape for paths under a prefix
+    pub fn route_key(mut self, prefix: impl Into<String>, key: CacheKeyConfig) -> Self {
+        self.route_keys.push((prefix.into(), key));
+        self
+    }
+
     pub fn condition(mut self, f: fn(&Request, &Response) -> bool) -> Self {
         self.condition = Some(f);
         self
@@ -89,6 +216,10 @@ pub struct CacheEntry {
     pub response: CachedResponse,
     pub created_at: Instant,
     pub ttl: Duration,
+    /// Request path the entry was cached under (for purges)
+    pub path: String,
+    /// Surrogate keys from the response, for tag-based invalidation
+    pub tags: Vec<String>,
 }
 
 impl CacheEntry {
@@ -111,6 +242,13 @@ pub trait CacheStore: Send + Sync {
     fn set(&self, key: String, entry: CacheEntry);
     fn remove(&self, key: &str);
     fn clear(&self);
+
+    /// Purge entries by path; a trailing `*` makes it a prefix match.
+    /// Returns the number of entries removed.
+    fn purge_path(&self, pattern: &str) -> usize;
+
+    /// Purge entries carrying a surrogate key tag
+    fn purge_tag(&self, tag: &str) -> usize;
 }
 
 /// In-memory LRU cache store
@@ -185,12 +323,31 @@ impl CacheStore for MemoryCache {
         let mut entries = self.write_entries();
         entries.clear();
     }
+
+    fn purge_path(&self, pattern: &str) -> usize {
+        let mut entries = self.write_entries();
+        let before = entries.len();
+        match pattern.strip_suffix('*') {
+            Some(prefix) => entries.retain(|_, e| !e.path.starts_with(prefix)),
+            None => entries.retain(|_, e| e.path != pattern),
+        }
+        before - entries.len()
+    }
+
+    fn purge_tag(&self, tag: &str) -> usize {
+        let mut entries = self.write_entries();
+        let before = entries.len();
+        entries.retain(|_, e| !e.tags.iter().any(|t| t == tag));
+        before - entries.len()
+    }
 }
 
 /// Cache middleware
 pub struct Cache<S: CacheStore = MemoryCache> {
     config: CacheConfig,
     store: Arc<S>,
+    /// Hit/miss counters per key shape label
+    stats: std::sync::Mutex<HashMap<String, CacheStats>>,
 }
 
 impl Cache<MemoryCache> {
@@ -199,6 +356,7 @@ impl Cache<MemoryCache> {
         Self {
             config,
             store: Arc::new(store),
+            stats: std::sync::Mutex::new(HashMap::new()),
         }
     }
 }
@@ -208,12 +366,65 @@ impl<S: CacheStore> Cache<S> {
         Self {
             config,
             store: Arc::new(store),
+            stats: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
+    /// Resolve the key shape for a request: (shape label, cache key)
+    fn cache_key(&self, req: &Request) -> (String, String) {
+        for (prefix, key) in &self.config.route_keys {
+            if req.path.starts_with(prefix.as_str()) {
+                return (prefix.clone(), build_cache_key(req, key));
+            }
+        }
+        if let Some(key) = &self.config.key {
+            return ("default".to_string(), build_cache_key(req, key));
+        }
+        ("default".to_string(), (self.config.key_fn)(req))
+    }
+
+    fn record(&self, shape: &str, hit: bool) {
+        let mut stats = self.stats.lock().unwrap();
+        let entry = stats.entry(shape.to_string()).or_default();
+        if hit {
+            entry.hits += 1;
+        } else {
+            entry.misses += 1;
+        }
+    }
+
+    /// Hit/miss statistics per key shape (route prefix or "default")
+    pub fn stats(&self) -> Vec<(String, CacheStats)> {
+        let mut stats: Vec<(String, CacheStats)> = self
+            .stats
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
     fn should_cache_method(&self, method: &Method) -> bool {
         self.config.methods.contains(method)
     }
+
+    /// Purge cached responses by path; a trailing `*` purges a prefix.
+    /// Returns the number of entries removed.
+    pub fn purge(&self, pattern: &str) -> usize {
+        self.store.purge_path(pattern)
+    }
+
+    /// Purge cached responses carrying a `Surrogate-Key` tag
+    pub fn purge_tag(&self, tag: &str) -> usize {
+        self.store.purge_tag(tag)
+    }
+
+    /// Drop every cached response
+    pub fn clear(&self) {
+        self.store.clear();
+    }
 }
 
 impl<S: CacheStore + 'static> Middleware for Cache<S> {
@@ -224,9 +435,11 @@ impl<S: CacheStore + 'static> Middleware for Cache<S> {
         }
 
         // Check for cached response
-        let key = (self.config.key_fn)(req);
+        let (shape, key) = self.cache_key(req);
+        let cached = self.store.get(&key);
+        self.record(&shape, cached.is_some());
 
-        if let Some(entry) = self.store.get(&key) {
+        if let Some(entry) = cached {
             // Return cached response
             let mut res = Response {
                 status: crate::StatusCode(entry.response.status),
@@ -269,6 +482,14 @@ impl<S: CacheStore + 'static> Middleware for Cache<S> {
             None => return,
         };
 
+        // Surrogate keys (space-separated tags) enable tag-based purges
+        let tags = res
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("surrogate-key"))
+            .map(|(_, v)| v.split_whitespace().map(|t| t.to_string()).collect())
+            .unwrap_or_default();
+
         // Store in cache
         let entry = CacheEntry {
             response: CachedResponse {
@@ -278,6 +499,8 @@ impl<S: CacheStore + 'static> Middleware for Cache<S> {
             },
             created_at: Instant::now(),
             ttl: self.config.ttl,
+            path: req.path.clone(),
+            tags,
         };
 
         self.store.set(key, entry);
@@ -298,6 +521,14 @@ pub fn etag(body: &[u8]) -> String {
     format!("\"{}\"", hex_encode_u64(hash))
 }
 
+/// Generate a weak (`W/`-prefixed) ETag from response body
+///
+/// For responses that are semantically equivalent but not
+/// byte-for-byte reproducible (e.g. re-rendered with a timestamp).
+pub fn weak_etag(body: &[u8]) -> String {
+    format!("W/{}", etag(body))
+}
+
 fn simple_hash(data: &[u8]) -> u64 {
     let mut hash: u64 = 0xcbf29ce484222325; // FNV offset basis
     for &byte in data {
@@ -321,10 +552,8 @@ mod tests {
         assert_eq!(config.ttl, Duration::from_secs(600));
     }
 
-    #[test]
-    fn test_memory_cache() {
-        let cache = MemoryCache::new(10);
-        let entry = CacheEntry {
+    fn entry(path: &str, tags: &[&str]) -> CacheEntry {
+        CacheEntry {
             response: CachedResponse {
                 status: 200,
                 headers: SmallVec::new(),
@@ -332,13 +561,126 @@ mod tests {
             },
             created_at: Instant::now(),
             ttl: Duration::from_secs(60),
-        };
+            path: path.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
 
-        cache.set("key1".to_string(), entry.clone());
+    #[test]
+    fn test_memory_cache() {
+        let cache = MemoryCache::new(10);
+        cache.set("key1".to_string(), entry("/a", &[]));
         assert!(cache.get("key1").is_some());
         assert!(cache.get("key2").is_none());
     }
 
+    #[test]
+    fn test_purge_path_exact_and_wildcard() {
+        let cache = MemoryCache::new(10);
+        cache.set("GET:/users/1:".to_string(), entry("/users/1", &[]));
+        cache.set("GET:/users/2:".to_string(), entry("/users/2", &[]));
+        cache.set("GET:/posts:".to_string(), entry("/posts", &[]));
+
+        assert_eq!(cache.purge_path("/users/1"), 1);
+        assert!(cache.get("GET:/users/1:").is_none());
+        assert!(cache.get("GET:/users/2:").is_some());
+
+        assert_eq!(cache.purge_path("/users/*"), 1);
+        assert!(cache.get("GET:/users/2:").is_none());
+        assert!(cache.get("GET:/posts:").is_some());
+    }
+
+    #[test]
+    fn test_purge_tag() {
+        let cache = MemoryCache::new(10);
+        cache.set("a".to_string(), entry("/a", &["products", "home"]));
+        cache.set("b".to_string(), entry("/b", &["products"]));
+        cache.set("c".to_string(), entry("/c", &["checkout"]));
+
+        assert_eq!(cache.purge_tag("products"), 2);
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn test_surrogate_key_captured_on_response() {
+        use crate::{RequestBuilder, ResponseBuilder, StatusCode};
+
+        let cache = Cache::new(CacheConfig::new());
+        let mut req = RequestBuilder::new(Method::Get, "/products/1").build();
+        assert!(cache.before(&mut req).is_none());
+
+        let mut res = ResponseBuilder::new(StatusCode::OK)
+            .header("Surrogate-Key", "products product-1")
+            .body("data")
+            .build();
+        cache.after(&req, &mut res);
+
+        assert_eq!(cache.purge_tag("product-1"), 1);
+    }
+
+    #[test]
+    fn test_build_cache_key_normalizes_query() {
+        use crate::RequestBuilder;
+
+        let key = CacheKeyConfig::new();
+        let mut a = RequestBuilder::new(Method::Get, "/search").build();
+        a.query = Some("b=2&a=1".to_string());
+        let mut b = RequestBuilder::new(Method::Get, "/search").build();
+        b.query = Some("a=1&b=2".to_string());
+
+        assert_eq!(build_cache_key(&a, &key), build_cache_key(&b, &key));
+
+        // Ignoring the query collapses all variants
+        let no_query = CacheKeyConfig::new().query(QueryKeys::None);
+        let mut c = RequestBuilder::new(Method::Get, "/search").build();
+        c.query = Some("tracking=xyz".to_string());
+        assert_eq!(build_cache_key(&a, &no_query), build_cache_key(&c, &no_query));
+    }
+
+    #[test]
+    fn test_build_cache_key_vary_header_and_cookie() {
+        use crate::RequestBuilder;
+
+        let key = CacheKeyConfig::new()
+            .vary_header("Accept-Language")
+            .vary_cookie("session");
+
+        let en = RequestBuilder::new(Method::Get, "/")
+            .header("Accept-Language", "en")
+            .header("Cookie", "session=abc")
+            .build();
+        let de = RequestBuilder::new(Method::Get, "/")
+            .header("Accept-Language", "de")
+            .header("Cookie", "session=abc")
+            .build();
+
+        assert_ne!(build_cache_key(&en, &key), build_cache_key(&de, &key));
+        assert!(build_cache_key(&en, &key).contains(":h:accept-language=en"));
+        assert!(build_cache_key(&en, &key).contains(":c:session=abc"));
+    }
+
+    #[test]
+    fn test_stats_per_key_shape() {
+        use crate::{RequestBuilder, ResponseBuilder, StatusCode};
+
+        let config = CacheConfig::new().route_key("/api", CacheKeyConfig::new());
+        let cache = Cache::new(config);
+
+        let mut req = RequestBuilder::new(Method::Get, "/api/users").build();
+        assert!(cache.before(&mut req).is_none()); // miss
+        let mut res = ResponseBuilder::new(StatusCode::OK).body("x").build();
+        cache.after(&req, &mut res);
+
+        let mut req = RequestBuilder::new(Method::Get, "/api/users").build();
+        assert!(cache.before(&mut req).is_some()); // hit
+
+        let stats = cache.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].0, "/api");
+        assert_eq!(stats[0].1, CacheStats { hits: 1, misses: 1 });
+        assert!((stats[0].1.hit_ratio() - 0.5).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_etag() {
         let tag = etag(b"hello world");
@@ -24,19 +24,127 @@ impl Encoding {
         }
     }
 
-    /// Parse from Accept-Encoding header
+    /// Pick the client's preferred encoding from an Accept-Encoding header
+    ///
+    /// Honors q-values (highest wins, `q=0` excludes) and breaks ties
+    /// by server preference: br > gzip > deflate > identity.
     pub fn from_accept_encoding(header: &str) -> Self {
-        // Priority: br > gzip > deflate > identity
-        if header.contains("br") {
-            Encoding::Brotli
-        } else if header.contains("gzip") {
-            Encoding::Gzip
-        } else if header.contains("deflate") {
-            Encoding::Deflate
-        } else {
-            Encoding::Identity
+        preferred_encodings(header)
+            .into_iter()
+            .next()
+            .unwrap_or(Encoding::Identity)
+    }
+
+    /// Parse a single Content-Encoding token
+    pub fn from_token(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Encoding::Gzip),
+            "br" => Some(Encoding::Brotli),
+            "deflate" => Some(Encoding::Deflate),
+            "identity" => Some(Encoding::Identity),
+            _ => None,
+        }
+    }
+}
+
+/// Check whether an Accept-Encoding header allows an encoding
+///
+/// Matches the encoding's token (or `*`) unless it carries `q=0`.
+/// Identity is always acceptable.
+pub fn accepts_encoding(accept: &str, encoding: Encoding) -> bool {
+    if encoding == Encoding::Identity {
+        return true;
+    }
+    for part in accept.split(',') {
+        let mut pieces = part.split(';');
+        let name = pieces.next().unwrap_or("").trim();
+        if !name.eq_ignore_ascii_case(encoding.as_str()) && name != "*" {
+            continue;
+        }
+        let rejected = pieces.any(|p| {
+            p.trim()
+                .strip_prefix("q=")
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .map(|q| q == 0.0)
+                .unwrap_or(false)
+        });
+        if !rejected {
+            return true;
         }
     }
+    false
+}
+
+/// Parse an Accept-Encoding header into `(encoding, q)` preferences
+///
+/// Entries are ranked by q-value (descending) with server preference
+/// (br > gzip > deflate > identity) breaking ties. A `*` wildcard
+/// stands in for every encoding not listed explicitly. `q=0` entries
+/// are kept so callers can distinguish "refused" from "not mentioned".
+pub fn parse_accept_encoding(header: &str) -> Vec<(Encoding, f32)> {
+    let mut prefs: Vec<(Encoding, f32)> = Vec::new();
+    let mut wildcard_q = None;
+    for part in header.split(',') {
+        let mut pieces = part.split(';');
+        let name = pieces.next().unwrap_or("").trim();
+        if name.is_empty() {
+            continue;
+        }
+        let mut q = 1.0f32;
+        for param in pieces {
+            if let Some(value) = param.trim().strip_prefix("q=") {
+                q = value.trim().parse().unwrap_or(0.0);
+            }
+        }
+        if name == "*" {
+            wildcard_q = Some(q);
+            continue;
+        }
+        if let Some(encoding) = Encoding::from_token(name) {
+            if !prefs.iter().any(|(e, _)| *e == encoding) {
+                prefs.push((encoding, q));
+            }
+        }
+    }
+    if let Some(q) = wildcard_q {
+        for encoding in [
+            Encoding::Brotli,
+            Encoding::Gzip,
+            Encoding::Deflate,
+            Encoding::Identity,
+        ] {
+            if !prefs.iter().any(|(e, _)| *e == encoding) {
+                prefs.push((encoding, q));
+            }
+        }
+    }
+    prefs.sort_by(|a, b| {
+        b.1.total_cmp(&a.1)
+            .then_with(|| server_rank(a.0).cmp(&server_rank(b.0)))
+    });
+    prefs
+}
+
+/// Acceptable encodings (q > 0) ranked most preferred first
+///
+/// Shared by the compress middleware and precompressed static asset
+/// selection so both honor the same negotiation rules.
+pub fn preferred_encodings(header: &str) -> Vec<Encoding> {
+    parse_accept_encoding(header)
+        .into_iter()
+        .filter(|(_, q)| *q > 0.0)
+        .map(|(encoding, _)| encoding)
+        .collect()
+}
+
+/// Tie-break order when q-values are equal: best compression first
+fn server_rank(encoding: Encoding) -> u8 {
+    match encoding {
+        Encoding::Brotli => 0,
+        Encoding::Gzip => 1,
+        Encoding::Deflate => 2,
+        Encoding::Identity => 3,
+    }
 }
 
 /// Compression level
@@ -58,7 +166,7 @@ impl CompressionLevel {
     }
 
     #[cfg_attr(not(feature = "compress"), allow(dead_code))]
-    fn brotli_level(&self) -> u32 {
+    pub(crate) fn brotli_level(&self) -> u32 {
         match self {
             CompressionLevel::Fast => 1,
             CompressionLevel::Default => 4,
@@ -67,6 +175,90 @@ impl CompressionLevel {
     }
 }
 
+/// Compress data with the given encoding
+///
+/// Identity (or a build without the `compress` feature) returns the
+/// input unchanged.
+#[cfg(feature = "compress")]
+pub fn compress_data(encoding: Encoding, level: CompressionLevel, data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    match encoding {
+        Encoding::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::new(level.gzip_level()),
+            );
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        }
+        Encoding::Brotli => {
+            let mut output = Vec::new();
+            let params = brotli::enc::BrotliEncoderParams {
+                quality: level.brotli_level() as i32,
+                ..Default::default()
+            };
+            brotli::enc::BrotliCompress(&mut std::io::Cursor::new(data), &mut output, &params)
+                .unwrap();
+            output
+        }
+        Encoding::Deflate => {
+            let mut encoder = flate2::write::DeflateEncoder::new(
+                Vec::new(),
+                flate2::Compression::new(level.gzip_level()),
+            );
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        }
+        Encoding::Identity => data.to_vec(),
+    }
+}
+
+#[cfg(not(feature = "compress"))]
+pub fn compress_data(_encoding: Encoding, _level: CompressionLevel, data: &[u8]) -> Vec<u8> {
+    data.to_vec()
+}
+
+/// Decompress data encoded with the given encoding
+///
+/// Returns `None` when the payload is not valid for the encoding (or
+/// the build lacks the `compress` feature).
+#[cfg(feature = "compress")]
+pub fn decompress_data(encoding: Encoding, data: &[u8]) -> Option<Vec<u8>> {
+    use std::io::Read;
+
+    match encoding {
+        Encoding::Gzip => {
+            let mut output = Vec::new();
+            flate2::read::GzDecoder::new(data)
+                .read_to_end(&mut output)
+                .ok()?;
+            Some(output)
+        }
+        Encoding::Brotli => {
+            let mut output = Vec::new();
+            brotli::BrotliDecompress(&mut std::io::Cursor::new(data), &mut output).ok()?;
+            Some(output)
+        }
+        Encoding::Deflate => {
+            let mut output = Vec::new();
+            flate2::read::DeflateDecoder::new(data)
+                .read_to_end(&mut output)
+                .ok()?;
+            Some(output)
+        }
+        Encoding::Identity => Some(data.to_vec()),
+    }
+}
+
+#[cfg(not(feature = "compress"))]
+pub fn decompress_data(encoding: Encoding, data: &[u8]) -> Option<Vec<u8>> {
+    match encoding {
+        Encoding::Identity => Some(data.to_vec()),
+        _ => None,
+    }
+}
+
 /// Compress middleware
 pub struct Compress {
     level: CompressionLevel,
@@ -104,53 +296,6 @@ impl Compress {
             || content_type.contains("css")
     }
 
-    #[cfg(feature = "compress")]
-    fn compress_gzip(&self, data: &[u8]) -> Vec<u8> {
-        use flate2::write::GzEncoder;
-        use flate2::Compression;
-        use std::io::Write;
-
-        let mut encoder = GzEncoder::new(Vec::new(), Compression::new(self.level.gzip_level()));
-        encoder.write_all(data).unwrap();
-        encoder.finish().unwrap()
-    }
-
-    #[cfg(feature = "compress")]
-    fn compress_brotli(&self, data: &[u8]) -> Vec<u8> {
-        let mut output = Vec::new();
-        let params = brotli::enc::BrotliEncoderParams {
-            quality: self.level.brotli_level() as i32,
-            ..Default::default()
-        };
-        brotli::enc::BrotliCompress(&mut std::io::Cursor::new(data), &mut output, &params).unwrap();
-        output
-    }
-
-    #[cfg(feature = "compress")]
-    fn compress_deflate(&self, data: &[u8]) -> Vec<u8> {
-        use flate2::write::DeflateEncoder;
-        use flate2::Compression;
-        use std::io::Write;
-
-        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::new(self.level.gzip_level()));
-        encoder.write_all(data).unwrap();
-        encoder.finish().unwrap()
-    }
-
-    #[cfg(not(feature = "compress"))]
-    fn compress_gzip(&self, data: &[u8]) -> Vec<u8> {
-        data.to_vec()
-    }
-
-    #[cfg(not(feature = "compress"))]
-    fn compress_brotli(&self, data: &[u8]) -> Vec<u8> {
-        data.to_vec()
-    }
-
-    #[cfg(not(feature = "compress"))]
-    fn compress_deflate(&self, data: &[u8]) -> Vec<u8> {
-        data.to_vec()
-    }
 }
 
 impl Default for Compress {
@@ -172,6 +317,16 @@ impl Middleware for Compress {
             return;
         }
 
+        // Already-encoded bodies (e.g. passed through from an upstream)
+        // must not be compressed a second time
+        if res
+            .headers
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case("content-encoding"))
+        {
+            return;
+        }
+
         // Get content type
         let content_type = res
             .headers
@@ -193,12 +348,7 @@ impl Middleware for Compress {
         }
 
         // Compress body
-        let compressed = match encoding {
-            Encoding::Gzip => self.compress_gzip(&res.body),
-            Encoding::Brotli => self.compress_brotli(&res.body),
-            Encoding::Deflate => self.compress_deflate(&res.body),
-            Encoding::Identity => return,
-        };
+        let compressed = compress_data(encoding, self.level, &res.body);
 
         // Only use compressed if smaller
         if compressed.len() < body_len {
@@ -241,6 +391,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encoding_from_token() {
+        assert_eq!(Encoding::from_token("gzip"), Some(Encoding::Gzip));
+        assert_eq!(Encoding::from_token(" BR "), Some(Encoding::Brotli));
+        assert_eq!(Encoding::from_token("identity"), Some(Encoding::Identity));
+        assert_eq!(Encoding::from_token("zstd"), None);
+    }
+
+    #[test]
+    fn test_q_values_respected() {
+        // q=0 refuses an encoding outright
+        assert_eq!(Encoding::from_accept_encoding("gzip;q=0"), Encoding::Identity);
+        assert_eq!(
+            Encoding::from_accept_encoding("br;q=0, gzip"),
+            Encoding::Gzip
+        );
+
+        // Preference order follows q, not listing order
+        assert_eq!(
+            Encoding::from_accept_encoding("gzip;q=1.0, br;q=0.5"),
+            Encoding::Gzip
+        );
+        assert_eq!(
+            preferred_encodings("gzip;q=0.5, deflate;q=0.8, br;q=0.2"),
+            vec![Encoding::Deflate, Encoding::Gzip, Encoding::Brotli]
+        );
+
+        // An explicit identity preference suppresses compression
+        assert_eq!(
+            Encoding::from_accept_encoding("identity, gzip;q=0.5"),
+            Encoding::Identity
+        );
+    }
+
+    #[test]
+    fn test_wildcard_expansion() {
+        // * covers everything not listed, at its own q
+        assert_eq!(Encoding::from_accept_encoding("*"), Encoding::Brotli);
+        assert_eq!(
+            preferred_encodings("gzip;q=0.9, *;q=0.1"),
+            vec![
+                Encoding::Gzip,
+                Encoding::Brotli,
+                Encoding::Deflate,
+                Encoding::Identity,
+            ]
+        );
+        // Explicit entries are not overridden by the wildcard
+        assert_eq!(
+            Encoding::from_accept_encoding("br;q=0, *"),
+            Encoding::Gzip
+        );
+    }
+
+    #[test]
+    fn test_accepts_encoding() {
+        assert!(accepts_encoding("gzip, deflate, br", Encoding::Gzip));
+        assert!(accepts_encoding("*", Encoding::Brotli));
+        assert!(!accepts_encoding("gzip", Encoding::Brotli));
+        // q=0 explicitly refuses an encoding
+        assert!(!accepts_encoding("gzip;q=0", Encoding::Gzip));
+        assert!(accepts_encoding("gzip;q=0.5", Encoding::Gzip));
+        // Identity is always acceptable
+        assert!(accepts_encoding("", Encoding::Identity));
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_compress_decompress_round_trip() {
+        let data = b"hello hello hello hello hello".repeat(10);
+        for encoding in [Encoding::Gzip, Encoding::Brotli, Encoding::Deflate] {
+            let compressed = compress_data(encoding, CompressionLevel::Default, &data);
+            let restored = decompress_data(encoding, &compressed).unwrap();
+            assert_eq!(restored, data);
+        }
+        // Garbage input is rejected, not passed through
+        assert!(decompress_data(Encoding::Gzip, b"not gzip").is_none());
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_skips_already_encoded_response() {
+        use crate::{Method, RequestBuilder, ResponseBuilder, StatusCode};
+
+        let req = RequestBuilder::new(Method::Get, "/")
+            .header("accept-encoding", "gzip")
+            .build();
+        let body = "x".repeat(2048);
+        let mut res = ResponseBuilder::new(StatusCode::OK)
+            .header("Content-Type", "text/plain")
+            .header("Content-Encoding", "br")
+            .body(body.clone())
+            .build();
+
+        Compress::new().after(&req, &mut res);
+        assert_eq!(res.body, bytes::Bytes::from(body));
+        assert_eq!(
+            res.headers
+                .iter()
+                .filter(|(k, _)| k.eq_ignore_ascii_case("content-encoding"))
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn test_should_compress() {
         let compress = Compress::new();
@@ -168,6 +168,17 @@ impl CookieJar {
         self.pending.iter().map(|c| c.to_header_value())
     }
 
+    /// Merge pending cookies into a response
+    ///
+    /// Uses [`crate::Response::add_cookie`], so a pending cookie
+    /// replaces an existing Set-Cookie for the same name and leaves
+    /// cookies set elsewhere (handler, other middleware) intact.
+    pub fn apply(&self, res: &mut crate::Response) {
+        for value in self.pending_headers() {
+            res.add_cookie(value);
+        }
+    }
+
     /// Check if jar has a cookie
     pub fn contains(&self, name: &str) -> bool {
         self.cookies.contains_key(name)
@@ -242,4 +253,20 @@ mod tests {
         let cookie = Cookie::delete("session");
         assert_eq!(cookie.max_age, Some(0));
     }
+
+    #[test]
+    fn test_cookie_jar_apply_merges() {
+        let mut res = crate::Response::ok();
+        res.add_cookie("session=old; Path=/");
+        res.add_cookie("theme=dark");
+
+        let mut jar = CookieJar::new();
+        jar.set(Cookie::new("session", "new").path("/"));
+        jar.apply(&mut res);
+
+        let cookies: Vec<&str> = res.cookies().collect();
+        assert_eq!(cookies.len(), 2);
+        assert!(cookies[0].starts_with("session=new"));
+        assert_eq!(cookies[1], "theme=dark");
+    }
 }
@@ -4,6 +4,7 @@
 
 use crate::{Request, Response, ResponseBuilder, StatusCode, Method};
 use super::Middleware;
+use crate::crypto::constant_time_eq;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// CSRF configuration
@@ -227,7 +228,7 @@ impl Middleware for Csrf {
                 "{}={}; Path=/; SameSite=Strict; HttpOnly",
                 self.config.cookie_name, token
             );
-            res.headers.push(("Set-Cookie".to_string(), cookie));
+            res.add_cookie(cookie);
 
             // Also expose token in header for JS access
             res.headers.push((self.config.header_name.clone(), token));
@@ -402,16 +403,6 @@ fn sha256(input: &[u8]) -> [u8; 32] {
     result
 }
 
-fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
-    if a.len() != b.len() {
-        return false;
-    }
-    let mut result = 0u8;
-    for (x, y) in a.iter().zip(b.iter()) {
-        result |= x ^ y;
-    }
-    result == 0
-}
 
 #[cfg(test)]
 mod tests {
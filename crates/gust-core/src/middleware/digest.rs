@@ -0,0 +1,288 @@
+//! Request body integrity verification (Content-Digest / Content-MD5)
+//!
+//! Verifies RFC 9530 `Content-Digest` and legacy `Content-MD5` headers
+//! against the request body and rejects mismatches with 400 before the
+//! handler runs. Optionally emits a response digest for download
+//! integrity.
+
+use crate::crypto::{base64_decode, base64_encode, md5, sha256};
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+use super::Middleware;
+
+/// Supported digest algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Sha256,
+    Md5,
+}
+
+impl DigestAlgorithm {
+    /// RFC 9530 algorithm key
+    pub fn key(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "sha-256",
+            DigestAlgorithm::Md5 => "md5",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key.trim().to_lowercase().as_str() {
+            "sha-256" => Some(DigestAlgorithm::Sha256),
+            "md5" => Some(DigestAlgorithm::Md5),
+            _ => None,
+        }
+    }
+
+    /// Compute the digest of a body
+    pub fn compute(&self, body: &[u8]) -> Vec<u8> {
+        match self {
+            DigestAlgorithm::Sha256 => sha256(body).to_vec(),
+            DigestAlgorithm::Md5 => md5(body).to_vec(),
+        }
+    }
+}
+
+/// Digest middleware configuration
+#[derive(Clone)]
+pub struct DigestConfig {
+    /// Verify request digests when a digest header is present
+    pub verify_request: bool,
+    /// Emit a `Content-Digest` on responses using this algorithm
+    pub emit_response: Option<DigestAlgorithm>,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            verify_request: true,
+            emit_response: None,
+        }
+    }
+}
+
+impl DigestConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn verify_request(mut self, verify: bool) -> Self {
+        self.verify_request = verify;
+        self
+    }
+
+    pub fn emit_response(mut self, algorithm: DigestAlgorithm) -> Self {
+        self.emit_response = Some(algorithm);
+        self
+    }
+}
+
+/// Parse an RFC 9530 `Content-Digest` header into (algorithm, digest) pairs
+///
+/// Unknown algorithms are skipped per the spec; malformed entries for
+/// known algorithms yield `None` digests so callers can reject them.
+pub fn parse_content_digest(header: &str) -> Vec<(DigestAlgorithm, Option<Vec<u8>>)> {
+    let mut digests = Vec::new();
+
+    for entry in header.split(',') {
+        let (key, value) = match entry.split_once('=') {
+            Some(kv) => kv,
+            None => continue,
+        };
+
+        let algorithm = match DigestAlgorithm::from_key(key) {
+            Some(a) => a,
+            None => continue, // unknown algorithms are ignored
+        };
+
+        // Byte sequences are wrapped in colons: `sha-256=:BASE64:`
+        let value = value.trim();
+        let decoded = value
+            .strip_prefix(':')
+            .and_then(|v| v.strip_suffix(':'))
+            .and_then(base64_decode);
+
+        digests.push((algorithm, decoded));
+    }
+
+    digests
+}
+
+/// Format an RFC 9530 `Content-Digest` header value
+pub fn format_content_digest(algorithm: DigestAlgorithm, digest: &[u8]) -> String {
+    format!("{}=:{}:", algorithm.key(), base64_encode(digest))
+}
+
+/// Digest verification middleware
+pub struct Digest {
+    config: DigestConfig,
+}
+
+impl Digest {
+    pub fn new(config: DigestConfig) -> Self {
+        Self { config }
+    }
+
+    /// Verify the digest headers on a request against its body
+    ///
+    /// Returns `Err` with a human-readable reason when any present
+    /// digest does not match.
+    pub fn verify(&self, req: &Request) -> Result<(), String> {
+        if let Some(header) = req.header("content-digest") {
+            let digests = parse_content_digest(header);
+            if digests.is_empty() {
+                // Only unknown algorithms: nothing we can verify
+                return Ok(());
+            }
+
+            for (algorithm, expected) in digests {
+                let expected = match expected {
+                    Some(e) => e,
+                    None => {
+                        return Err(format!(
+                            "Malformed {} digest in Content-Digest",
+                            algorithm.key()
+                        ))
+                    }
+                };
+                let actual = algorithm.compute(&req.body);
+                if actual != expected {
+                    return Err(format!("Content-Digest mismatch for {}", algorithm.key()));
+                }
+            }
+        }
+
+        if let Some(header) = req.header("content-md5") {
+            let expected = base64_decode(header.trim())
+                .ok_or_else(|| "Malformed Content-MD5".to_string())?;
+            if md5(&req.body).to_vec() != expected {
+                return Err("Content-MD5 mismatch".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Middleware for Digest {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        if !self.config.verify_request {
+            return None;
+        }
+
+        match self.verify(req) {
+            Ok(()) => None,
+            Err(reason) => Some(
+                ResponseBuilder::new(StatusCode::BAD_REQUEST)
+                    .body(reason)
+                    .build(),
+            ),
+        }
+    }
+
+    fn after(&self, _req: &Request, res: &mut Response) {
+        if let Some(algorithm) = self.config.emit_response {
+            let already_set = res
+                .headers
+                .iter()
+                .any(|(k, _)| k.eq_ignore_ascii_case("content-digest"));
+            if !already_set && !res.body.is_empty() {
+                let digest = algorithm.compute(&res.body);
+                res.headers.push((
+                    "Content-Digest".to_string(),
+                    format_content_digest(algorithm, &digest),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, RequestBuilder};
+
+    fn request_with_digest(body: &str, header_name: &str, header_value: String) -> Request {
+        RequestBuilder::new(Method::Post, "/upload")
+            .header(header_name, header_value)
+            .body(body.to_string())
+            .build()
+    }
+
+    #[test]
+    fn test_content_digest_match() {
+        let body = "hello world";
+        let digest = DigestAlgorithm::Sha256.compute(body.as_bytes());
+        let header = format_content_digest(DigestAlgorithm::Sha256, &digest);
+        let req = request_with_digest(body, "Content-Digest", header);
+
+        let middleware = Digest::new(DigestConfig::default());
+        assert!(middleware.verify(&req).is_ok());
+    }
+
+    #[test]
+    fn test_content_digest_mismatch() {
+        let digest = DigestAlgorithm::Sha256.compute(b"different body");
+        let header = format_content_digest(DigestAlgorithm::Sha256, &digest);
+        let mut req = request_with_digest("hello world", "Content-Digest", header);
+
+        let middleware = Digest::new(DigestConfig::default());
+        let res = middleware.before(&mut req).expect("should reject");
+        assert_eq!(res.status.0, 400);
+    }
+
+    #[test]
+    fn test_content_md5_match() {
+        let body = "payload";
+        let header = base64_encode(&md5(body.as_bytes()));
+        let req = request_with_digest(body, "Content-MD5", header);
+
+        let middleware = Digest::new(DigestConfig::default());
+        assert!(middleware.verify(&req).is_ok());
+    }
+
+    #[test]
+    fn test_content_md5_mismatch() {
+        let header = base64_encode(&md5(b"other"));
+        let mut req = request_with_digest("payload", "Content-MD5", header);
+
+        let middleware = Digest::new(DigestConfig::default());
+        assert!(middleware.before(&mut req).is_some());
+    }
+
+    #[test]
+    fn test_unknown_algorithm_ignored() {
+        let req = request_with_digest("body", "Content-Digest", "sha-512=:AAAA:".to_string());
+        let middleware = Digest::new(DigestConfig::default());
+        assert!(middleware.verify(&req).is_ok());
+    }
+
+    #[test]
+    fn test_response_digest_emission() {
+        let req = RequestBuilder::new(Method::Get, "/file").build();
+        let mut res = ResponseBuilder::new(StatusCode::OK).body("content").build();
+
+        let middleware =
+            Digest::new(DigestConfig::new().emit_response(DigestAlgorithm::Sha256));
+        middleware.after(&req, &mut res);
+
+        let header = res
+            .headers
+            .iter()
+            .find(|(k, _)| k == "Content-Digest")
+            .map(|(_, v)| v.clone())
+            .expect("digest header set");
+        let expected = format_content_digest(
+            DigestAlgorithm::Sha256,
+            &DigestAlgorithm::Sha256.compute(b"content"),
+        );
+        assert_eq!(header, expected);
+    }
+
+    #[test]
+    fn test_parse_content_digest_multiple() {
+        let digests = parse_content_digest("md5=:AAAA:, sha-256=:BBBB:");
+        assert_eq!(digests.len(), 2);
+        assert_eq!(digests[0].0, DigestAlgorithm::Md5);
+        assert_eq!(digests[1].0, DigestAlgorithm::Sha256);
+    }
+}
@@ -0,0 +1,377 @@
+//! Edge cache headers middleware
+//!
+//! Sets `Cache-Control`, `Surrogate-Control`, and `CDN-Cache-Control`
+//! consistently from per-route cache policy declarations, and strips
+//! hop-by-hop headers that must not be forwarded by caches.
+
+use crate::{Request, Response};
+use super::Middleware;
+
+/// Headers that are hop-by-hop per RFC 9110 and must never be cached
+/// or forwarded by an intermediary
+const HOP_BY_HOP: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Cacheability scope for a policy
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Public,
+    Private,
+    NoStore,
+}
+
+/// Declarative cache policy for one route
+///
+/// Rendered into `Cache-Control` (browser), and when a shared-cache
+/// TTL is set, `Surrogate-Control` and `CDN-Cache-Control` (edge).
+#[derive(Clone)]
+pub struct CachePolicy {
+    scope: Scope,
+    /// Browser max-age in seconds
+    max_age: Option<u64>,
+    /// Shared cache (CDN) max-age in seconds
+    s_maxage: Option<u64>,
+    /// stale-while-revalidate window in seconds
+    stale_while_revalidate: Option<u64>,
+    /// stale-if-error window in seconds
+    stale_if_error: Option<u64>,
+    immutable: bool,
+    must_revalidate: bool,
+}
+
+impl CachePolicy {
+    /// Publicly cacheable with a browser max-age
+    pub fn public(max_age: u64) -> Self {
+        Self {
+            scope: Scope::Public,
+            max_age: Some(max_age),
+            s_maxage: None,
+            stale_while_revalidate: None,
+            stale_if_error: None,
+            immutable: false,
+            must_revalidate: false,
+        }
+    }
+
+    /// Cacheable only in the browser, never at the edge
+    pub fn private(max_age: u64) -> Self {
+        Self {
+            scope: Scope::Private,
+            max_age: Some(max_age),
+            s_maxage: None,
+            stale_while_revalidate: None,
+            stale_if_error: None,
+            immutable: false,
+            must_revalidate: false,
+        }
+    }
+
+    /// Never cached anywhere
+    pub fn no_store() -> Self {
+        Self {
+            scope: Scope::NoStore,
+            max_age: None,
+            s_maxage: None,
+            stale_while_revalidate: None,
+            stale_if_error: None,
+            immutable: false,
+            must_revalidate: false,
+        }
+    }
+
+    /// Shared cache (CDN) max-age; also emitted as
+    /// `Surrogate-Control` and `CDN-Cache-Control`
+    pub fn s_maxage(mut self, seconds: u64) -> Self {
+        self.s_maxage = Some(seconds);
+        self
+    }
+
+    pub fn stale_while_revalidate(mut self, seconds: u64) -> Self {
+        self.stale_while_revalidate = Some(seconds);
+        self
+    }
+
+    pub fn stale_if_error(mut self, seconds: u64) -> Self {
+        self.stale_if_error = Some(seconds);
+        self
+    }
+
+    /// Mark the response immutable (fingerprinted assets)
+    pub fn immutable(mut self) -> Self {
+        self.immutable = true;
+        self
+    }
+
+    pub fn must_revalidate(mut self) -> Self {
+        self.must_revalidate = true;
+        self
+    }
+
+    /// Render the `Cache-Control` value
+    pub fn cache_control(&self) -> String {
+        if self.scope == Scope::NoStore {
+            return "no-store".to_string();
+        }
+
+        let mut parts = vec![match self.scope {
+            Scope::Public => "public".to_string(),
+            Scope::Private => "private".to_string(),
+            Scope::NoStore => unreachable!(),
+        }];
+        if let Some(age) = self.max_age {
+            parts.push(format!("max-age={}", age));
+        }
+        if self.scope == Scope::Public {
+            if let Some(age) = self.s_maxage {
+                parts.push(format!("s-maxage={}", age));
+            }
+        }
+        if let Some(secs) = self.stale_while_revalidate {
+            parts.push(format!("stale-while-revalidate={}", secs));
+        }
+        if let Some(secs) = self.stale_if_error {
+            parts.push(format!("stale-if-error={}", secs));
+        }
+        if self.immutable {
+            parts.push("immutable".to_string());
+        }
+        if self.must_revalidate {
+            parts.push("must-revalidate".to_string());
+        }
+        parts.join(", ")
+    }
+
+    /// Render the `Surrogate-Control`/`CDN-Cache-Control` value, if any
+    pub fn surrogate_control(&self) -> Option<String> {
+        match self.scope {
+            Scope::NoStore | Scope::Private => Some("no-store".to_string()),
+            Scope::Public => {
+                let age = self.s_maxage?;
+                let mut parts = vec![format!("max-age={}", age)];
+                if let Some(secs) = self.stale_while_revalidate {
+                    parts.push(format!("stale-while-revalidate={}", secs));
+                }
+                if let Some(secs) = self.stale_if_error {
+                    parts.push(format!("stale-if-error={}", secs));
+                }
+                Some(parts.join(", "))
+            }
+        }
+    }
+}
+
+/// Edge cache headers configuration
+#[derive(Clone)]
+pub struct EdgeCacheConfig {
+    /// Fallback policy when no route matches
+    pub default_policy: Option<CachePolicy>,
+    /// Per-route policies as (path prefix, policy); first match wins
+    pub routes: Vec<(String, CachePolicy)>,
+    /// Remove hop-by-hop headers from responses (default: true)
+    pub strip_hop_by_hop: bool,
+}
+
+impl Default for EdgeCacheConfig {
+    fn default() -> Self {
+        Self {
+            default_policy: None,
+            routes: Vec::new(),
+            strip_hop_by_hop: true,
+        }
+    }
+}
+
+impl EdgeCacheConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the fallback policy for unmatched routes
+    pub fn default_policy(mut self, policy: CachePolicy) -> Self {
+        self.default_policy = Some(policy);
+        self
+    }
+
+    /// Add a policy for paths under a prefix
+    pub fn route(mut self, prefix: impl Into<String>, policy: CachePolicy) -> Self {
+        self.routes.push((prefix.into(), policy));
+        self
+    }
+}
+
+/// Edge cache headers middleware
+pub struct EdgeCache {
+    config: EdgeCacheConfig,
+}
+
+impl EdgeCache {
+    pub fn new(config: EdgeCacheConfig) -> Self {
+        Self { config }
+    }
+
+    fn policy_for(&self, path: &str) -> Option<&CachePolicy> {
+        for (prefix, policy) in &self.config.routes {
+            if path.starts_with(prefix.as_str()) {
+                return Some(policy);
+            }
+        }
+        self.config.default_policy.as_ref()
+    }
+}
+
+/// Remove hop-by-hop headers, including any named by `Connection`
+pub fn strip_hop_by_hop_headers(res: &mut Response) {
+    // Connection may nominate additional hop-by-hop headers
+    let nominated: Vec<String> = res
+        .headers
+        .iter()
+        .filter(|(k, _)| k.eq_ignore_ascii_case("connection"))
+        .flat_map(|(_, v)| v.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect();
+
+    res.headers.retain(|(k, _)| {
+        let name = k.to_ascii_lowercase();
+        !HOP_BY_HOP.contains(&name.as_str()) && !nominated.contains(&name)
+    });
+}
+
+impl Middleware for EdgeCache {
+    fn before(&self, _req: &mut Request) -> Option<Response> {
+        None
+    }
+
+    fn after(&self, req: &Request, res: &mut Response) {
+        if self.config.strip_hop_by_hop {
+            strip_hop_by_hop_headers(res);
+        }
+
+        let Some(policy) = self.policy_for(&req.path) else {
+            return;
+        };
+
+        // A policy owns all three headers; drop stale values first
+        res.headers.retain(|(k, _)| {
+            !k.eq_ignore_ascii_case("cache-control")
+                && !k.eq_ignore_ascii_case("surrogate-control")
+                && !k.eq_ignore_ascii_case("cdn-cache-control")
+        });
+
+        res.headers.push(("Cache-Control".to_string(), policy.cache_control()));
+        if let Some(value) = policy.surrogate_control() {
+            res.headers.push(("Surrogate-Control".to_string(), value.clone()));
+            res.headers.push(("CDN-Cache-Control".to_string(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, RequestBuilder, ResponseBuilder, StatusCode};
+
+    fn get(path: &str) -> Request {
+        RequestBuilder::new(Method::Get, path).build()
+    }
+
+    fn header<'a>(res: &'a Response, name: &str) -> Option<&'a str> {
+        res.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_cache_control_rendering() {
+        let policy = CachePolicy::public(60)
+            .s_maxage(3600)
+            .stale_while_revalidate(30)
+            .immutable();
+        assert_eq!(
+            policy.cache_control(),
+            "public, max-age=60, s-maxage=3600, stale-while-revalidate=30, immutable"
+        );
+        assert_eq!(
+            policy.surrogate_control().as_deref(),
+            Some("max-age=3600, stale-while-revalidate=30")
+        );
+
+        assert_eq!(CachePolicy::no_store().cache_control(), "no-store");
+        assert_eq!(
+            CachePolicy::private(300).surrogate_control().as_deref(),
+            Some("no-store")
+        );
+    }
+
+    #[test]
+    fn test_first_matching_route_wins() {
+        let config = EdgeCacheConfig::new()
+            .route("/assets", CachePolicy::public(31536000).s_maxage(31536000).immutable())
+            .route("/", CachePolicy::no_store());
+        let edge = EdgeCache::new(config);
+
+        let req = get("/assets/app.js");
+        let mut res = ResponseBuilder::new(StatusCode::OK).build();
+        edge.after(&req, &mut res);
+        assert!(header(&res, "cache-control").unwrap().contains("immutable"));
+        assert!(header(&res, "surrogate-control").is_some());
+        assert_eq!(
+            header(&res, "surrogate-control"),
+            header(&res, "cdn-cache-control")
+        );
+
+        let req = get("/api/users");
+        let mut res = ResponseBuilder::new(StatusCode::OK).build();
+        edge.after(&req, &mut res);
+        assert_eq!(header(&res, "cache-control"), Some("no-store"));
+    }
+
+    #[test]
+    fn test_policy_replaces_existing_headers() {
+        let config = EdgeCacheConfig::new().default_policy(CachePolicy::public(60));
+        let edge = EdgeCache::new(config);
+
+        let req = get("/");
+        let mut res = ResponseBuilder::new(StatusCode::OK)
+            .header("Cache-Control", "no-cache")
+            .build();
+        edge.after(&req, &mut res);
+
+        let values: Vec<_> = res
+            .headers
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case("cache-control"))
+            .collect();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].1, "public, max-age=60");
+    }
+
+    #[test]
+    fn test_strips_hop_by_hop_headers() {
+        let edge = EdgeCache::new(EdgeCacheConfig::new());
+
+        let req = get("/");
+        let mut res = ResponseBuilder::new(StatusCode::OK)
+            .header("Connection", "keep-alive, X-Internal")
+            .header("Keep-Alive", "timeout=5")
+            .header("Transfer-Encoding", "chunked")
+            .header("X-Internal", "1")
+            .header("Content-Type", "text/plain")
+            .build();
+        edge.after(&req, &mut res);
+
+        assert!(header(&res, "connection").is_none());
+        assert!(header(&res, "keep-alive").is_none());
+        assert!(header(&res, "transfer-encoding").is_none());
+        assert!(header(&res, "x-internal").is_none());
+        assert_eq!(header(&res, "content-type"), Some("text/plain"));
+    }
+}
@@ -0,0 +1,302 @@
+//! JWE (JSON Web Encryption) support
+//!
+//! Compact serialization (RFC 7516) with direct key agreement (`dir`)
+//! and A256GCM content encryption, so sensitive claims travel
+//! encrypted instead of merely signed. Asymmetric key management
+//! (RSA-OAEP) is not implemented; tokens using it are rejected as
+//! unsupported.
+//!
+//! # Timing side channels
+//!
+//! The AES-256-GCM backing this module is the crate's own minimal
+//! implementation; its S-box lookups are table-indexed and can leak
+//! key material through cache timing to a co-resident attacker.
+//! Deployments with that threat model should terminate JWE with a
+//! vetted crypto library instead.
+
+use super::jwt::{base64url_decode, base64url_encode, claims_from_json, claims_to_json, Claims};
+use super::Middleware;
+use crate::crypto::{aes256_gcm_decrypt, aes256_gcm_encrypt, fill_random};
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+
+/// JWE protected header for dir + A256GCM
+const PROTECTED_HEADER: &str = r#"{"alg":"dir","enc":"A256GCM"}"#;
+
+/// JWE configuration
+#[derive(Clone)]
+pub struct JweConfig {
+    /// 256-bit content encryption key shared by issuer and consumer
+    pub key: [u8; 32],
+    /// Validate exp on decrypt (default: true)
+    pub validate_exp: bool,
+    /// Seconds of leeway for exp (clock skew)
+    pub leeway: u64,
+}
+
+impl JweConfig {
+    /// Build a config from a 32-byte key; shorter or longer keys are
+    /// rejected rather than silently padded or truncated
+    pub fn new(key: impl AsRef<[u8]>) -> Result<Self, JweError> {
+        let key: [u8; 32] = key
+            .as_ref()
+            .try_into()
+            .map_err(|_| JweError::InvalidKey)?;
+        Ok(Self {
+            key,
+            validate_exp: true,
+            leeway: 0,
+        })
+    }
+
+    pub fn leeway(mut self, seconds: u64) -> Self {
+        self.leeway = seconds;
+        self
+    }
+}
+
+/// JWE encoder/decoder
+pub struct Jwe {
+    config: JweConfig,
+}
+
+impl Jwe {
+    pub fn new(config: JweConfig) -> Self {
+        Self { config }
+    }
+
+    /// Encrypt claims to a compact JWE token
+    ///
+    /// `dir` key management: the encrypted-key part is empty and the
+    /// configured key encrypts the content directly.
+    pub fn encrypt(&self, claims: &Claims) -> String {
+        let header_b64 = base64url_encode(PROTECTED_HEADER.as_bytes());
+        // The array API has no error arm that could degrade to a
+        // fixed nonce — nonce reuse under one key breaks GCM entirely
+        let mut nonce = [0u8; 12];
+        fill_random(&mut nonce);
+
+        // The protected header is authenticated as AAD per RFC 7516 §5.1
+        let (ciphertext, tag) = aes256_gcm_encrypt(
+            &self.config.key,
+            &nonce,
+            header_b64.as_bytes(),
+            claims_to_json(claims).as_bytes(),
+        );
+
+        format!(
+            "{}..{}.{}.{}",
+            header_b64,
+            base64url_encode(&nonce),
+            base64url_encode(&ciphertext),
+            base64url_encode(&tag)
+        )
+    }
+
+    /// Decrypt and validate a compact JWE token
+    pub fn decrypt(&self, token: &str) -> Result<Claims, JweError> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if parts.len() != 5 {
+            return Err(JweError::InvalidFormat);
+        }
+
+        let header_bytes = base64url_decode(parts[0]).ok_or(JweError::InvalidFormat)?;
+        let header = String::from_utf8(header_bytes).map_err(|_| JweError::InvalidFormat)?;
+        if header_field(&header, "alg") != Some("dir".to_string()) {
+            return Err(JweError::UnsupportedAlgorithm);
+        }
+        if header_field(&header, "enc") != Some("A256GCM".to_string()) {
+            return Err(JweError::UnsupportedAlgorithm);
+        }
+        // dir: no encrypted key may be present
+        if !parts[1].is_empty() {
+            return Err(JweError::InvalidFormat);
+        }
+
+        let nonce: [u8; 12] = base64url_decode(parts[2])
+            .and_then(|v| v.try_into().ok())
+            .ok_or(JweError::InvalidFormat)?;
+        let ciphertext = base64url_decode(parts[3]).ok_or(JweError::InvalidFormat)?;
+        let tag: [u8; 16] = base64url_decode(parts[4])
+            .and_then(|v| v.try_into().ok())
+            .ok_or(JweError::InvalidFormat)?;
+
+        let plaintext = aes256_gcm_decrypt(
+            &self.config.key,
+            &nonce,
+            parts[0].as_bytes(),
+            &ciphertext,
+            &tag,
+        )
+        .ok_or(JweError::DecryptionFailed)?;
+
+        let json = String::from_utf8(plaintext).map_err(|_| JweError::InvalidFormat)?;
+        let claims = claims_from_json(&json).map_err(|_| JweError::InvalidFormat)?;
+
+        if self.config.validate_exp {
+            if let Some(exp) = claims.exp {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                if now > exp.saturating_add(self.config.leeway) {
+                    return Err(JweError::Expired);
+                }
+            }
+        }
+
+        Ok(claims)
+    }
+}
+
+/// Extract a string field from the protected header
+fn header_field(header: &str, field: &str) -> Option<String> {
+    let pattern = format!(r#""{}":""#, field);
+    let start = header.find(&pattern)? + pattern.len();
+    let end = header[start..].find('"')?;
+    Some(header[start..start + end].to_string())
+}
+
+/// JWE middleware for request authentication with encrypted tokens
+pub struct JweMiddleware {
+    jwe: Jwe,
+}
+
+impl JweMiddleware {
+    pub fn new(config: JweConfig) -> Self {
+        Self {
+            jwe: Jwe::new(config),
+        }
+    }
+}
+
+impl Middleware for JweMiddleware {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        let token = match req
+            .header("authorization")
+            .and_then(|h| h.strip_prefix("Bearer "))
+        {
+            Some(t) => t,
+            None => {
+                return Some(
+                    ResponseBuilder::new(StatusCode::UNAUTHORIZED)
+                        .header("WWW-Authenticate", "Bearer")
+                        .body(r#"{"error":"Missing authorization header"}"#)
+                        .build(),
+                )
+            }
+        };
+
+        match self.jwe.decrypt(token) {
+            Ok(claims) => {
+                if let Some(sub) = &claims.sub {
+                    req.params.insert("_jwt_sub".to_string(), sub.clone());
+                }
+                None
+            }
+            Err(e) => Some(
+                ResponseBuilder::new(StatusCode::UNAUTHORIZED)
+                    .body(format!(r#"{{"error":"{}"}}"#, e))
+                    .build(),
+            ),
+        }
+    }
+
+    fn after(&self, _req: &Request, _res: &mut Response) {}
+}
+
+/// JWE Error
+#[derive(Debug)]
+pub enum JweError {
+    InvalidFormat,
+    InvalidKey,
+    UnsupportedAlgorithm,
+    DecryptionFailed,
+    Expired,
+}
+
+impl std::fmt::Display for JweError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JweError::InvalidFormat => write!(f, "Invalid token format"),
+            JweError::InvalidKey => write!(f, "Key must be 32 bytes"),
+            JweError::UnsupportedAlgorithm => write!(f, "Unsupported algorithm"),
+            JweError::DecryptionFailed => write!(f, "Decryption failed"),
+            JweError::Expired => write!(f, "Token expired"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jwe() -> Jwe {
+        Jwe::new(JweConfig::new([7u8; 32]).unwrap())
+    }
+
+    #[test]
+    fn test_jwe_encrypt_decrypt() {
+        let claims = Claims::new()
+            .sub("user123")
+            .exp_in(3600)
+            .claim("ssn", "sensitive");
+
+        let token = jwe().encrypt(&claims);
+        // Compact JWE: five parts, empty encrypted key for dir
+        assert_eq!(token.split('.').count(), 5);
+        assert!(!token.contains("sensitive"));
+
+        let decoded = jwe().decrypt(&token).unwrap();
+        assert_eq!(decoded.sub, Some("user123".to_string()));
+        assert_eq!(decoded.custom.get("ssn"), Some(&"sensitive".to_string()));
+    }
+
+    #[test]
+    fn test_jwe_wrong_key() {
+        let token = jwe().encrypt(&Claims::new().sub("user"));
+        let other = Jwe::new(JweConfig::new([8u8; 32]).unwrap());
+        assert!(matches!(
+            other.decrypt(&token),
+            Err(JweError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_jwe_tampered_header() {
+        let token = jwe().encrypt(&Claims::new().sub("user"));
+        // Swapping the protected header breaks the AAD binding
+        let parts: Vec<&str> = token.split('.').collect();
+        let forged_header = base64url_encode(br#"{"alg":"dir","enc":"A256GCM","x":1}"#);
+        let forged = format!(
+            "{}..{}.{}.{}",
+            forged_header, parts[2], parts[3], parts[4]
+        );
+        assert!(matches!(
+            jwe().decrypt(&forged),
+            Err(JweError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_jwe_unsupported_key_management() {
+        let header = base64url_encode(br#"{"alg":"RSA-OAEP","enc":"A256GCM"}"#);
+        let token = format!("{}.AAAA.AAAA.AAAA.AAAA", header);
+        assert!(matches!(
+            jwe().decrypt(&token),
+            Err(JweError::UnsupportedAlgorithm)
+        ));
+    }
+
+    #[test]
+    fn test_jwe_expired() {
+        let mut claims = Claims::new().sub("user");
+        claims.exp = Some(1);
+        let token = jwe().encrypt(&claims);
+        assert!(matches!(jwe().decrypt(&token), Err(JweError::Expired)));
+    }
+
+    #[test]
+    fn test_jwe_key_length_checked() {
+        assert!(matches!(JweConfig::new([0u8; 16]), Err(JweError::InvalidKey)));
+    }
+}
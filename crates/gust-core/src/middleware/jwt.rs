@@ -4,6 +4,7 @@
 
 use crate::{Request, Response, ResponseBuilder, StatusCode};
 use super::Middleware;
+use crate::crypto::constant_time_eq;
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -154,7 +155,11 @@ pub struct JwtConfig {
     pub algorithm: Algorithm,
     pub validate_exp: bool,
     pub validate_nbf: bool,
-    pub leeway: u64, // Seconds of leeway for exp/nbf
+    pub leeway: u64, // Seconds of leeway for exp/nbf/iat (clock skew)
+    /// Reject tokens issued more than this many seconds ago (requires iat)
+    pub max_token_age: Option<u64>,
+    /// Reject tokens longer than this many bytes before any parsing
+    pub max_token_size: usize,
 }
 
 impl JwtConfig {
@@ -165,6 +170,8 @@ impl JwtConfig {
             validate_exp: true,
             validate_nbf: true,
             leeway: 0,
+            max_token_age: None,
+            max_token_size: 8192,
         }
     }
 
@@ -177,6 +184,18 @@ impl JwtConfig {
         self.leeway = seconds;
         self
     }
+
+    /// Reject tokens whose `iat` is more than `seconds` in the past,
+    /// bounding the lifetime of stolen tokens even when `exp` is far out
+    pub fn max_token_age(mut self, seconds: u64) -> Self {
+        self.max_token_age = Some(seconds);
+        self
+    }
+
+    pub fn max_token_size(mut self, bytes: usize) -> Self {
+        self.max_token_size = bytes;
+        self
+    }
 }
 
 /// JWT encoder/decoder
@@ -215,6 +234,11 @@ impl Jwt {
 
     /// Decode and verify JWT token
     pub fn decode(&self, token: &str) -> Result<Claims, JwtError> {
+        // Bound the work an attacker can force before any decoding
+        if token.len() > self.config.max_token_size {
+            return Err(JwtError::TokenTooLarge);
+        }
+
         let parts: Vec<&str> = token.split('.').collect();
         if parts.len() != 3 {
             return Err(JwtError::InvalidFormat);
@@ -244,12 +268,44 @@ impl Jwt {
         let claims_str = String::from_utf8(claims_bytes).map_err(|_| JwtError::InvalidFormat)?;
         let claims = self.parse_claims(&claims_str)?;
 
-        // Validate exp/nbf
-        if self.config.validate_exp && claims.is_expired() {
-            return Err(JwtError::Expired);
+        // Validate time claims, applying the same leeway to exp, nbf,
+        // and iat so clock skew is tolerated symmetrically
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let leeway = self.config.leeway;
+
+        if self.config.validate_exp {
+            if let Some(exp) = claims.exp {
+                if now > exp.saturating_add(leeway) {
+                    return Err(JwtError::Expired);
+                }
+            }
         }
-        if self.config.validate_nbf && claims.is_not_yet_valid() {
-            return Err(JwtError::NotYetValid);
+        if self.config.validate_nbf {
+            if let Some(nbf) = claims.nbf {
+                if now.saturating_add(leeway) < nbf {
+                    return Err(JwtError::NotYetValid);
+                }
+            }
+        }
+        // iat sanity: a token "issued" in the future is forged or the
+        // issuer's clock is broken — reject either way
+        if let Some(iat) = claims.iat {
+            if iat > now.saturating_add(leeway) {
+                return Err(JwtError::IssuedInFuture);
+            }
+        }
+        if let Some(max_age) = self.config.max_token_age {
+            match claims.iat {
+                Some(iat) => {
+                    if now.saturating_sub(iat) > max_age.saturating_add(leeway) {
+                        return Err(JwtError::TooOld);
+                    }
+                }
+                None => return Err(JwtError::MissingIssuedAt),
+            }
         }
 
         Ok(claims)
@@ -260,34 +316,7 @@ impl Jwt {
     }
 
     fn claims_to_json(&self, claims: &Claims) -> String {
-        let mut parts = Vec::new();
-
-        if let Some(ref iss) = claims.iss {
-            parts.push(format!(r#""iss":"{}""#, iss));
-        }
-        if let Some(ref sub) = claims.sub {
-            parts.push(format!(r#""sub":"{}""#, sub));
-        }
-        if let Some(ref aud) = claims.aud {
-            parts.push(format!(r#""aud":"{}""#, aud));
-        }
-        if let Some(exp) = claims.exp {
-            parts.push(format!(r#""exp":{}"#, exp));
-        }
-        if let Some(nbf) = claims.nbf {
-            parts.push(format!(r#""nbf":{}"#, nbf));
-        }
-        if let Some(iat) = claims.iat {
-            parts.push(format!(r#""iat":{}"#, iat));
-        }
-        if let Some(ref jti) = claims.jti {
-            parts.push(format!(r#""jti":"{}""#, jti));
-        }
-        for (k, v) in &claims.custom {
-            parts.push(format!(r#""{}":"{}""#, k, v));
-        }
-
-        format!("{{{}}}", parts.join(","))
+        claims_to_json(claims)
     }
 
     fn parse_algorithm(&self, header: &str) -> Result<Algorithm, JwtError> {
@@ -296,6 +325,10 @@ impl Jwt {
             let start = start + 7;
             if let Some(end) = header[start..].find('"') {
                 let alg = &header[start..start + end];
+                // "none" means an unsigned token; never accept it
+                if alg.eq_ignore_ascii_case("none") {
+                    return Err(JwtError::AlgNone);
+                }
                 return Algorithm::from_str(alg).ok_or(JwtError::UnsupportedAlgorithm);
             }
         }
@@ -303,19 +336,69 @@ impl Jwt {
     }
 
     fn parse_claims(&self, json: &str) -> Result<Claims, JwtError> {
-        let mut claims = Claims::new();
+        claims_from_json(json)
+    }
+}
 
-        // Simple JSON parsing
-        claims.iss = extract_string_field(json, "iss");
-        claims.sub = extract_string_field(json, "sub");
-        claims.aud = extract_string_field(json, "aud");
-        claims.jti = extract_string_field(json, "jti");
-        claims.exp = extract_number_field(json, "exp");
-        claims.nbf = extract_number_field(json, "nbf");
-        claims.iat = extract_number_field(json, "iat");
+/// Serialize claims to a JSON object (shared with the JWE encoder)
+pub(crate) fn claims_to_json(claims: &Claims) -> String {
+    let mut parts = Vec::new();
 
-        Ok(claims)
+    if let Some(ref iss) = claims.iss {
+        parts.push(format!(r#""iss":"{}""#, iss));
+    }
+    if let Some(ref sub) = claims.sub {
+        parts.push(format!(r#""sub":"{}""#, sub));
+    }
+    if let Some(ref aud) = claims.aud {
+        parts.push(format!(r#""aud":"{}""#, aud));
     }
+    if let Some(exp) = claims.exp {
+        parts.push(format!(r#""exp":{}"#, exp));
+    }
+    if let Some(nbf) = claims.nbf {
+        parts.push(format!(r#""nbf":{}"#, nbf));
+    }
+    if let Some(iat) = claims.iat {
+        parts.push(format!(r#""iat":{}"#, iat));
+    }
+    if let Some(ref jti) = claims.jti {
+        parts.push(format!(r#""jti":"{}""#, jti));
+    }
+    for (k, v) in &claims.custom {
+        parts.push(format!(r#""{}":"{}""#, k, v));
+    }
+
+    format!("{{{}}}", parts.join(","))
+}
+
+/// Parse claims from a JSON payload (shared with the JWE decoder)
+pub(crate) fn claims_from_json(json: &str) -> Result<Claims, JwtError> {
+    let mut claims = Claims::new();
+
+    // Simple JSON parsing
+    claims.iss = extract_string_field(json, "iss");
+    claims.sub = extract_string_field(json, "sub");
+    claims.aud = extract_string_field(json, "aud");
+    claims.jti = extract_string_field(json, "jti");
+    claims.exp = extract_number_field(json, "exp");
+    claims.nbf = extract_number_field(json, "nbf");
+    claims.iat = extract_number_field(json, "iat");
+
+    // Preserve custom string claims so encode/decode round-trips
+    if let Ok(crate::middleware::validate::Value::Object(fields)) = crate::pure::parse_json(json) {
+        const REGISTERED: [&str; 7] = ["iss", "sub", "aud", "jti", "exp", "nbf", "iat"];
+        for (key, value) in fields {
+            if REGISTERED.contains(&key.as_str()) {
+                continue;
+            }
+            if let crate::middleware::validate::Value::String(value) = value {
+                claims.custom.insert(key, value);
+            }
+        }
+    }
+
+    Ok(claims)
 }
 
 /// JWT middleware for request authentication
@@ -382,8 +465,13 @@ pub enum JwtError {
     InvalidSignature,
     AlgorithmMismatch,
     UnsupportedAlgorithm,
+    AlgNone,
+    TokenTooLarge,
     Expired,
     NotYetValid,
+    IssuedInFuture,
+    TooOld,
+    MissingIssuedAt,
 }
 
 impl std::fmt::Display for JwtError {
@@ -393,14 +481,19 @@ impl std::fmt::Display for JwtError {
             JwtError::InvalidSignature => write!(f, "Invalid signature"),
             JwtError::AlgorithmMismatch => write!(f, "Algorithm mismatch"),
             JwtError::UnsupportedAlgorithm => write!(f, "Unsupported algorithm"),
+            JwtError::AlgNone => write!(f, "Unsigned tokens are not accepted"),
+            JwtError::TokenTooLarge => write!(f, "Token too large"),
             JwtError::Expired => write!(f, "Token expired"),
             JwtError::NotYetValid => write!(f, "Token not yet valid"),
+            JwtError::IssuedInFuture => write!(f, "Token issued in the future"),
+            JwtError::TooOld => write!(f, "Token exceeds maximum age"),
+            JwtError::MissingIssuedAt => write!(f, "Token missing iat claim"),
         }
     }
 }
 
 // Base64URL encoding (no padding, URL-safe)
-fn base64url_encode(input: &[u8]) -> String {
+pub(crate) fn base64url_encode(input: &[u8]) -> String {
     const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
 
     let mut output = String::new();
@@ -427,7 +520,7 @@ fn base64url_encode(input: &[u8]) -> String {
     output
 }
 
-fn base64url_decode(input: &str) -> Option<Vec<u8>> {
+pub(crate) fn base64url_decode(input: &str) -> Option<Vec<u8>> {
     const DECODE: [i8; 256] = {
         let mut table = [-1i8; 256];
         let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
@@ -598,18 +691,6 @@ fn sha256(input: &[u8]) -> [u8; 32] {
     result
 }
 
-// Constant-time comparison
-fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
-    if a.len() != b.len() {
-        return false;
-    }
-
-    let mut result = 0u8;
-    for (x, y) in a.iter().zip(b.iter()) {
-        result |= x ^ y;
-    }
-    result == 0
-}
 
 // Helper functions for JSON parsing
 fn extract_string_field(json: &str, field: &str) -> Option<String> {
@@ -654,6 +735,17 @@ mod tests {
         assert_eq!(decoded.sub, Some("user123".to_string()));
     }
 
+    #[test]
+    fn test_jwt_custom_claims_roundtrip() {
+        let jwt = Jwt::new(JwtConfig::new("secret"));
+
+        let claims = Claims::new().sub("user123").claim("role", "admin");
+        let token = jwt.encode(&claims);
+        let decoded = jwt.decode(&token).unwrap();
+
+        assert_eq!(decoded.custom.get("role"), Some(&"admin".to_string()));
+    }
+
     #[test]
     fn test_jwt_invalid_signature() {
         let jwt1 = Jwt::new(JwtConfig::new("secret1"));
@@ -665,6 +757,92 @@ mod tests {
         assert!(matches!(jwt2.decode(&token), Err(JwtError::InvalidSignature)));
     }
 
+    #[test]
+    fn test_jwt_leeway_tolerates_clock_skew() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        // Expired 10 seconds ago: rejected without leeway, accepted with
+        let claims = Claims::new().sub("user").exp(now - 10);
+        let strict = Jwt::new(JwtConfig::new("secret"));
+        let token = strict.encode(&claims);
+        assert!(matches!(strict.decode(&token), Err(JwtError::Expired)));
+
+        let lenient = Jwt::new(JwtConfig::new("secret").leeway(30));
+        assert!(lenient.decode(&token).is_ok());
+
+        // Not valid for another 10 seconds: same leeway applies
+        let claims = Claims::new().sub("user").nbf(now + 10);
+        let token = strict.encode(&claims);
+        assert!(matches!(strict.decode(&token), Err(JwtError::NotYetValid)));
+        assert!(lenient.decode(&token).is_ok());
+    }
+
+    #[test]
+    fn test_jwt_max_token_age() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let jwt = Jwt::new(JwtConfig::new("secret").max_token_age(3600));
+
+        // Issued two hours ago: too old even though exp is fine
+        let mut claims = Claims::new().sub("user").exp_in(3600);
+        claims.iat = Some(now - 7200);
+        let token = jwt.encode(&claims);
+        assert!(matches!(jwt.decode(&token), Err(JwtError::TooOld)));
+
+        // Fresh token passes
+        let claims = Claims::new().sub("user").exp_in(3600).iat_now();
+        let token = jwt.encode(&claims);
+        assert!(jwt.decode(&token).is_ok());
+
+        // max_token_age requires iat
+        let claims = Claims::new().sub("user").exp_in(3600);
+        let token = jwt.encode(&claims);
+        assert!(matches!(jwt.decode(&token), Err(JwtError::MissingIssuedAt)));
+    }
+
+    #[test]
+    fn test_jwt_iat_in_future() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let jwt = Jwt::new(JwtConfig::new("secret"));
+
+        let mut claims = Claims::new().sub("user");
+        claims.iat = Some(now + 600);
+        let token = jwt.encode(&claims);
+        assert!(matches!(jwt.decode(&token), Err(JwtError::IssuedInFuture)));
+
+        // Within leeway the same token is accepted
+        let lenient = Jwt::new(JwtConfig::new("secret").leeway(900));
+        assert!(lenient.decode(&token).is_ok());
+    }
+
+    #[test]
+    fn test_jwt_rejects_alg_none() {
+        let jwt = Jwt::new(JwtConfig::new("secret"));
+
+        let header = base64url_encode(br#"{"alg":"none","typ":"JWT"}"#);
+        let claims = base64url_encode(br#"{"sub":"user"}"#);
+        let token = format!("{}.{}.", header, claims);
+        assert!(matches!(jwt.decode(&token), Err(JwtError::AlgNone)));
+    }
+
+    #[test]
+    fn test_jwt_rejects_oversized_token() {
+        let jwt = Jwt::new(JwtConfig::new("secret").max_token_size(64));
+
+        let claims = Claims::new().sub("user-with-a-long-enough-subject-line");
+        let token = jwt.encode(&claims);
+        assert!(token.len() > 64);
+        assert!(matches!(jwt.decode(&token), Err(JwtError::TokenTooLarge)));
+    }
+
     #[test]
     fn test_sha256() {
         let hash = sha256(b"hello");
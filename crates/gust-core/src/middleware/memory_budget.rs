@@ -0,0 +1,230 @@
+//! Per-request memory budget accounting
+//!
+//! Estimates how much memory a request occupies (body + headers +
+//! params + handler response) and records the distribution in a
+//! histogram, so p99 request memory is observable when diagnosing
+//! blowups in multi-tenant setups. A budget can either reject
+//! over-budget requests up front or only count them.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::otel::Histogram;
+use super::Middleware;
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+
+/// Memory budget configuration
+#[derive(Clone)]
+pub struct MemoryBudgetConfig {
+    /// Budget in bytes per request; 0 = account only, never exceed
+    pub budget_bytes: usize,
+    /// Reject over-budget requests with 413 (true, default) or only
+    /// count them (false)
+    pub reject: bool,
+    /// Custom error message for rejected requests
+    pub message: String,
+}
+
+impl MemoryBudgetConfig {
+    pub fn new(budget_bytes: usize) -> Self {
+        Self {
+            budget_bytes,
+            reject: true,
+            message: "Request exceeds memory budget".to_string(),
+        }
+    }
+
+    /// Count over-budget requests instead of rejecting them
+    pub fn log_only(mut self) -> Self {
+        self.reject = false;
+        self
+    }
+
+    pub fn message(mut self, msg: impl Into<String>) -> Self {
+        self.message = msg.into();
+        self
+    }
+}
+
+impl Default for MemoryBudgetConfig {
+    fn default() -> Self {
+        // Account only: observe the distribution without enforcement
+        Self::new(0)
+    }
+}
+
+/// Approximate memory held for the request side: body, path, query,
+/// header names/values, and captured route params
+pub fn estimate_request_size(req: &Request) -> usize {
+    let mut size = req.body.len() + req.path.len();
+    size += req.query.as_deref().map(str::len).unwrap_or(0);
+    for (name, value) in &req.headers {
+        size += name.len() + value.len();
+    }
+    for (name, value) in &req.params {
+        size += name.len() + value.len();
+    }
+    size
+}
+
+/// Approximate memory held for the response side: body plus header
+/// names/values
+pub fn estimate_response_size(res: &Response) -> usize {
+    let mut size = res.body.len();
+    for (name, value) in &res.headers {
+        size += name.len() + value.len();
+    }
+    size
+}
+
+/// Memory budget middleware
+///
+/// `before` rejects requests whose request-side estimate already
+/// exceeds the budget (when rejection is enabled); `after` records the
+/// full request + response estimate in the histogram and counts
+/// over-budget totals that could no longer be rejected (e.g. a small
+/// request producing a huge response).
+pub struct MemoryBudget {
+    config: MemoryBudgetConfig,
+    histogram: Arc<Histogram>,
+    over_budget: AtomicU64,
+}
+
+impl MemoryBudget {
+    pub fn new(config: MemoryBudgetConfig) -> Self {
+        Self {
+            config,
+            histogram: Arc::new(Histogram::new("http.request.memory.bytes")),
+            over_budget: AtomicU64::new(0),
+        }
+    }
+
+    /// The per-request memory histogram, for export alongside other
+    /// metrics
+    pub fn histogram(&self) -> Arc<Histogram> {
+        self.histogram.clone()
+    }
+
+    /// Shortcut for the p99 of observed request memory, in bytes
+    pub fn p99(&self) -> f64 {
+        self.histogram.percentile(99.0)
+    }
+
+    /// How many requests exceeded the budget (rejected or counted)
+    pub fn over_budget_count(&self) -> u64 {
+        self.over_budget.load(Ordering::Relaxed)
+    }
+
+    fn exceeds_budget(&self, size: usize) -> bool {
+        self.config.budget_bytes > 0 && size > self.config.budget_bytes
+    }
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self::new(MemoryBudgetConfig::default())
+    }
+}
+
+impl Middleware for MemoryBudget {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        if !self.config.reject {
+            return None;
+        }
+        let size = estimate_request_size(req);
+        if self.exceeds_budget(size) {
+            self.over_budget.fetch_add(1, Ordering::Relaxed);
+            return Some(
+                ResponseBuilder::new(StatusCode::PAYLOAD_TOO_LARGE)
+                    .header("Content-Type", "application/json")
+                    .body(format!(
+                        r#"{{"error":"{}","budget":{},"estimated":{}}}"#,
+                        self.config.message, self.config.budget_bytes, size
+                    ))
+                    .build(),
+            );
+        }
+        None
+    }
+
+    fn after(&self, req: &Request, res: &mut Response) {
+        let total = estimate_request_size(req) + estimate_response_size(res);
+        self.histogram.record(total as f64);
+        if self.exceeds_budget(total) {
+            self.over_budget.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, RequestBuilder};
+
+    fn request_with_body(size: usize) -> Request {
+        RequestBuilder::new(Method::Post, "/upload")
+            .header("content-type", "application/octet-stream")
+            .body(vec![0u8; size])
+            .build()
+    }
+
+    #[test]
+    fn test_estimate_counts_all_parts() {
+        let req = RequestBuilder::new(Method::Get, "/users/42")
+            .query("expand=posts")
+            .header("accept", "application/json")
+            .body("hello")
+            .build();
+
+        // body(5) + path(9) + query(12) + header(6+16)
+        assert_eq!(estimate_request_size(&req), 48);
+
+        let res = ResponseBuilder::new(StatusCode::OK)
+            .header("x-a", "bb")
+            .body("12345678")
+            .build();
+        // body(8) + header(3+2)
+        assert_eq!(estimate_response_size(&res), 13);
+    }
+
+    #[test]
+    fn test_over_budget_request_rejected() {
+        let budget = MemoryBudget::new(MemoryBudgetConfig::new(1024));
+
+        let mut small = request_with_body(100);
+        assert!(budget.before(&mut small).is_none());
+
+        let mut large = request_with_body(2048);
+        let response = budget.before(&mut large).expect("should reject");
+        assert_eq!(response.status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(budget.over_budget_count(), 1);
+    }
+
+    #[test]
+    fn test_log_only_counts_without_rejecting() {
+        let budget = MemoryBudget::new(MemoryBudgetConfig::new(1024).log_only());
+
+        let mut large = request_with_body(2048);
+        assert!(budget.before(&mut large).is_none());
+
+        let mut res = Response::ok();
+        budget.after(&large, &mut res);
+        assert_eq!(budget.over_budget_count(), 1);
+        assert_eq!(budget.histogram().count(), 1);
+    }
+
+    #[test]
+    fn test_histogram_records_totals() {
+        let budget = MemoryBudget::default();
+
+        for size in [100, 200, 10_000] {
+            let req = request_with_body(size);
+            let mut res = Response::ok();
+            budget.after(&req, &mut res);
+        }
+
+        assert_eq!(budget.histogram().count(), 3);
+        // p99 reflects the outlier request
+        assert!(budget.p99() > 10_000.0);
+    }
+}
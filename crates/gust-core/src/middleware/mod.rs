@@ -7,6 +7,8 @@ pub mod compress;
 pub mod cookie;
 pub mod auth;
 pub mod jwt;
+pub mod jwe;
+pub mod paseto;
 pub mod csrf;
 pub mod rate_limit;
 pub mod security;
@@ -19,30 +21,67 @@ pub mod validate;
 pub mod range;
 pub mod proxy;
 pub mod otel;
+pub mod digest;
+pub mod edge_cache;
+pub mod admission;
+pub mod adaptive;
+pub mod transform;
+pub mod response_transform;
+pub mod memory_budget;
+pub mod shared_dict;
+pub mod response_budget;
+pub mod refresh_token;
+pub mod replay;
 
 // Re-exports for convenience
 pub use cors::{Cors, CorsConfig};
-pub use compress::{Compress, CompressionLevel, Encoding};
+pub use compress::{Compress, CompressionLevel, Encoding, accepts_encoding, compress_data, decompress_data, parse_accept_encoding, preferred_encodings};
 pub use cookie::{Cookie, CookieJar, SameSite};
 pub use auth::{BasicAuth, BearerAuth, ApiKeyAuth, BasicCredentials, BearerToken};
 pub use jwt::{Jwt, JwtConfig, Claims, Algorithm as JwtAlgorithm, JwtError};
+pub use jwe::{Jwe, JweConfig, JweError, JweMiddleware};
+pub use paseto::{Paseto, PasetoConfig, PasetoError, PasetoMiddleware};
 pub use csrf::{Csrf, CsrfConfig};
 pub use rate_limit::{RateLimit, RateLimitConfig, RateLimitStore, MemoryStore as RateLimitMemoryStore};
 pub use security::{Security, SecurityConfig, FrameOptions, HstsConfig};
 pub use body_limit::{BodyLimit, BodyLimitConfig, format_size};
-pub use cache::{Cache, CacheConfig, CacheStore, MemoryCache, etag};
+pub use cache::{Cache, CacheConfig, CacheKeyConfig, CacheStats, CacheStore, MemoryCache, QueryKeys, build_cache_key, etag, weak_etag};
 pub use tracing::{Tracing, TracingConfig, IdGenerator, generate_uuid, generate_nano_id, generate_short_id};
 pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState, CircuitStats, Bulkhead, BulkheadConfig};
-pub use session::{Session, SessionConfig, SessionStore, MemoryStore as SessionMemoryStore, SessionData, SessionValue, SameSite as SessionSameSite};
+pub use session::{Session, SessionConfig, SessionStore, MemoryStore as SessionMemoryStore, SessionData, SessionValue, SameSite as SessionSameSite, PRINCIPAL_KEY as SESSION_PRINCIPAL_KEY};
 pub use validate::{Schema, SchemaType, StringFormat, ValidationError, ValidationResult, Value, ValidateConfig, validate};
-pub use range::{Range, ParsedRange, RangeConfig, RangeResponse, parse_range, content_range, get_mime_type, generate_etag};
+pub use range::{Range, ParsedRange, RangeConfig, RangeResponse, parse_range, content_range, get_mime_type, generate_etag, generate_weak_etag, etag_strong_match, etag_weak_match, check_if_match, check_if_none_match, multipart_body, multipart_boundary, multipart_content_length, multipart_content_type};
 pub use proxy::{ProxyInfo, ProxyConfig, Protocol, TrustProxy, TrustedAddress, extract_proxy_info, parse_forwarded_for};
 pub use otel::{
     Span, SpanContext, SpanStatus, SpanKind, SpanEvent, SpanAttributes, AttributeValue,
-    Tracer, TracerConfig, Counter, Gauge, Histogram, MetricsCollector,
+    Tracer, TracerConfig, Counter, Gauge, Histogram, MetricsCollector, Labels, Exemplar,
     generate_trace_id, generate_span_id, parse_traceparent, format_traceparent,
     parse_tracestate, format_tracestate, http_attrs, service_attrs,
 };
+pub use digest::{
+    Digest, DigestConfig, DigestAlgorithm, parse_content_digest, format_content_digest,
+};
+pub use edge_cache::{EdgeCache, EdgeCacheConfig, CachePolicy, strip_hop_by_hop_headers};
+pub use admission::{Admission, AdmissionConfig, AdmissionStats, Priority};
+pub use adaptive::{AdaptiveConcurrency, AdaptiveConfig, AdaptiveLimiter, AdaptiveStats};
+pub use transform::{Transform, TransformConfig};
+pub use response_transform::{ResponseTransform, ResponseTransformConfig, StreamingInjector};
+pub use memory_budget::{
+    estimate_request_size, estimate_response_size, MemoryBudget, MemoryBudgetConfig,
+};
+pub use shared_dict::{
+    SharedDict, SharedDictConfig, SharedDictStats,
+    compress_with_dictionary, decompress_with_dictionary, train_dictionary,
+};
+pub use response_budget::{
+    BudgetPolicy, ResponseBudget, ResponseBudgetConfig, ResponseBudgetStats, RouteBudget,
+};
+pub use replay::{
+    MemoryNonceStore, NonceStore, ReplayConfig, ReplayGuard, ReplayStats,
+};
+pub use refresh_token::{
+    IssuedToken, RefreshError, RefreshTokenConfig, RefreshTokenStats, RefreshTokens,
+};
 
 use crate::{Request, Response};
 
@@ -55,6 +94,18 @@ pub trait Middleware: Send + Sync {
     fn after(&self, req: &Request, res: &mut Response);
 }
 
+/// Shared middleware handles (e.g. a cache that is also purged
+/// programmatically) can be added to a chain via `Arc`
+impl<M: Middleware + ?Sized> Middleware for std::sync::Arc<M> {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        (**self).before(req)
+    }
+
+    fn after(&self, req: &Request, res: &mut Response) {
+        (**self).after(req, res)
+    }
+}
+
 /// Middleware chain
 pub struct MiddlewareChain {
     middlewares: Vec<Box<dyn Middleware>>,
@@ -71,6 +122,11 @@ impl MiddlewareChain {
         self.middlewares.push(Box::new(middleware));
     }
 
+    /// Add an already-boxed middleware (e.g. from a plugin)
+    pub fn add_boxed(&mut self, middleware: Box<dyn Middleware>) {
+        self.middlewares.push(middleware);
+    }
+
     /// Check if middleware chain is empty
     #[inline]
     pub fn is_empty(&self) -> bool {
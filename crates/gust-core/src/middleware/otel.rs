@@ -166,6 +166,30 @@ impl SpanEvent {
     }
 }
 
+/// Span link to a span in another trace
+///
+/// Links correlate causally-related spans that are not parent/child,
+/// e.g. a batch span referencing each originating request in a fan-out.
+#[derive(Debug, Clone)]
+pub struct SpanLink {
+    pub context: SpanContext,
+    pub attributes: SpanAttributes,
+}
+
+impl SpanLink {
+    pub fn new(context: SpanContext) -> Self {
+        Self {
+            context,
+            attributes: HashMap::new(),
+        }
+    }
+
+    pub fn with_attributes(mut self, attrs: SpanAttributes) -> Self {
+        self.attributes = attrs;
+        self
+    }
+}
+
 /// Span kind
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SpanKind {
@@ -200,6 +224,7 @@ pub struct Span {
     pub attributes: SpanAttributes,
     pub status: SpanStatus,
     pub events: Vec<SpanEvent>,
+    pub links: Vec<SpanLink>,
 }
 
 impl Span {
@@ -214,6 +239,7 @@ impl Span {
             attributes: HashMap::new(),
             status: SpanStatus::Unset,
             events: Vec::new(),
+            links: Vec::new(),
         }
     }
 
@@ -236,6 +262,10 @@ impl Span {
         self.events.push(event);
     }
 
+    pub fn add_link(&mut self, link: SpanLink) {
+        self.links.push(link);
+    }
+
     pub fn end(&mut self) {
         if self.end_time_ns.is_none() {
             self.end_time_ns = Some(current_time_nanos());
@@ -388,6 +418,73 @@ pub fn format_tracestate(state: &HashMap<String, String>) -> String {
         .join(",")
 }
 
+/// Parse W3C baggage header
+/// Format: key1=value1;property1,key2=value2
+/// Entry properties (after `;`) are dropped; values are percent-decoded.
+pub fn parse_baggage(header: &str) -> HashMap<String, String> {
+    let mut entries = HashMap::new();
+    for member in header.split(',') {
+        // Properties annotate the entry but are not part of the value
+        let member = member.split(';').next().unwrap_or("");
+        if let Some((key, value)) = member.split_once('=') {
+            let key = key.trim();
+            if key.is_empty() {
+                continue;
+            }
+            entries.insert(key.to_string(), percent_decode(value.trim()));
+        }
+    }
+    entries
+}
+
+/// Format W3C baggage header
+/// Values are percent-encoded where the spec disallows raw octets.
+pub fn format_baggage(entries: &HashMap<String, String>) -> String {
+    entries
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, percent_encode_baggage_value(v)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            if let Ok(byte) = u8::from_str_radix(&s[i + 1..i + 3], 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        // Invalid escapes pass through unchanged
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn percent_encode_baggage_value(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for &byte in s.as_bytes() {
+        // baggage-octet excludes control chars, space, DEL, and the
+        // delimiters `"` `,` `;` `\`; everything else passes through
+        let reserved = matches!(byte, b'%' | b'"' | b',' | b';' | b'\\');
+        if byte <= b' ' || byte >= 0x7f || reserved {
+            out.push_str(&format!("%{:02X}", byte));
+        } else {
+            out.push(byte as char);
+        }
+    }
+    out
+}
+
 // ============================================================================
 // Tracer
 // ============================================================================
@@ -471,20 +568,166 @@ impl Tracer {
     }
 }
 
+// ============================================================================
+// Tail Sampling
+// ============================================================================
+
+/// Tail sampler configuration
+#[derive(Debug, Clone)]
+pub struct TailSamplerConfig {
+    /// Keep traces whose slowest span meets this duration
+    pub latency_threshold_ms: f64,
+    /// Maximum number of in-flight traces buffered at once
+    pub max_buffered_traces: usize,
+}
+
+impl Default for TailSamplerConfig {
+    fn default() -> Self {
+        Self {
+            latency_threshold_ms: 1000.0,
+            max_buffered_traces: 1024,
+        }
+    }
+}
+
+impl TailSamplerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn latency_threshold_ms(mut self, threshold: f64) -> Self {
+        self.latency_threshold_ms = threshold;
+        self
+    }
+
+    pub fn max_buffered_traces(mut self, max: usize) -> Self {
+        self.max_buffered_traces = max;
+        self
+    }
+}
+
+/// Tail-based sampling processor
+///
+/// Buffers spans per trace and decides keep/drop once the trace is
+/// complete, so the decision can consider the whole trace: traces
+/// containing an error span or exceeding the latency threshold are
+/// kept, everything else is dropped. This cuts exporter volume without
+/// losing the traces worth looking at.
+pub struct TailSampler {
+    config: TailSamplerConfig,
+    buffer: RwLock<HashMap<String, Vec<Span>>>,
+    sampled: RwLock<Vec<Span>>,
+}
+
+impl TailSampler {
+    pub fn new(config: TailSamplerConfig) -> Self {
+        Self {
+            config,
+            buffer: RwLock::new(HashMap::new()),
+            sampled: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Buffer a finished span under its trace
+    ///
+    /// Spans for a new trace are dropped once `max_buffered_traces`
+    /// in-flight traces are already buffered.
+    pub fn record(&self, span: Span) {
+        let mut buffer = self.buffer.write().unwrap();
+        if buffer.len() >= self.config.max_buffered_traces
+            && !buffer.contains_key(&span.context.trace_id)
+        {
+            return;
+        }
+        buffer
+            .entry(span.context.trace_id.clone())
+            .or_default()
+            .push(span);
+    }
+
+    /// Mark a trace complete and apply the sampling decision
+    ///
+    /// Returns true if the trace was kept.
+    pub fn finish_trace(&self, trace_id: &str) -> bool {
+        let Some(spans) = self.buffer.write().unwrap().remove(trace_id) else {
+            return false;
+        };
+
+        if self.should_keep(&spans) {
+            self.sampled.write().unwrap().extend(spans);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn should_keep(&self, spans: &[Span]) -> bool {
+        spans.iter().any(|span| {
+            span.status == SpanStatus::Error
+                || span
+                    .duration_ms()
+                    .is_some_and(|ms| ms >= self.config.latency_threshold_ms)
+        })
+    }
+
+    /// Get and clear spans from kept traces
+    pub fn drain_sampled(&self) -> Vec<Span> {
+        std::mem::take(&mut *self.sampled.write().unwrap())
+    }
+
+    /// Number of in-flight traces awaiting a decision
+    pub fn buffered_trace_count(&self) -> usize {
+        self.buffer.read().unwrap().len()
+    }
+}
+
 // ============================================================================
 // Metrics
 // ============================================================================
 
+/// Label set attached to a metric series (e.g. method, route, status)
+pub type Labels = Vec<(String, String)>;
+
+/// Escape a label value for the Prometheus text format
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Series identity: the metric name plus its sorted, rendered label
+/// set (`name{method="GET",route="/users"}`). Doubles as the sample
+/// name in the Prometheus exposition.
+fn series_key(name: &str, labels: &Labels) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let mut sorted: Vec<&(String, String)> = labels.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    let rendered: Vec<String> = sorted
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+        .collect();
+    format!("{}{{{}}}", name, rendered.join(","))
+}
+
 /// Counter metric (monotonically increasing)
 pub struct Counter {
     name: String,
+    labels: Labels,
     value: AtomicU64,
 }
 
 impl Counter {
     pub fn new(name: impl Into<String>) -> Self {
+        Self::with_labels(name, Labels::new())
+    }
+
+    pub fn with_labels(name: impl Into<String>, labels: Labels) -> Self {
         Self {
             name: name.into(),
+            labels,
             value: AtomicU64::new(0),
         }
     }
@@ -493,6 +736,10 @@ impl Counter {
         &self.name
     }
 
+    pub fn labels(&self) -> &Labels {
+        &self.labels
+    }
+
     pub fn add(&self, delta: u64) {
         self.value.fetch_add(delta, Ordering::Relaxed);
     }
@@ -509,13 +756,19 @@ impl Counter {
 /// Gauge metric (can increase or decrease)
 pub struct Gauge {
     name: String,
+    labels: Labels,
     value: AtomicU64, // Store f64 bits
 }
 
 impl Gauge {
     pub fn new(name: impl Into<String>) -> Self {
+        Self::with_labels(name, Labels::new())
+    }
+
+    pub fn with_labels(name: impl Into<String>, labels: Labels) -> Self {
         Self {
             name: name.into(),
+            labels,
             value: AtomicU64::new(0),
         }
     }
@@ -524,6 +777,10 @@ impl Gauge {
         &self.name
     }
 
+    pub fn labels(&self) -> &Labels {
+        &self.labels
+    }
+
     pub fn set(&self, value: f64) {
         self.value.store(value.to_bits(), Ordering::Relaxed);
     }
@@ -533,21 +790,40 @@ impl Gauge {
     }
 }
 
+/// Trace reference attached to a histogram sample, linking a metric
+/// spike back to the trace that caused it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exemplar {
+    /// Trace ID of the request that produced the sample
+    pub trace_id: String,
+    /// The recorded value
+    pub value: f64,
+}
+
 /// Histogram metric (value distribution)
 pub struct Histogram {
     name: String,
+    labels: Labels,
     buckets: RwLock<Vec<f64>>,
     count: AtomicU64,
     sum: AtomicU64, // Store f64 bits
+    /// Most recent exemplar, exported for metrics-to-trace navigation
+    exemplar: RwLock<Option<Exemplar>>,
 }
 
 impl Histogram {
     pub fn new(name: impl Into<String>) -> Self {
+        Self::with_labels(name, Labels::new())
+    }
+
+    pub fn with_labels(name: impl Into<String>, labels: Labels) -> Self {
         Self {
             name: name.into(),
+            labels,
             buckets: RwLock::new(Vec::new()),
             count: AtomicU64::new(0),
             sum: AtomicU64::new(0f64.to_bits()),
+            exemplar: RwLock::new(None),
         }
     }
 
@@ -555,6 +831,10 @@ impl Histogram {
         &self.name
     }
 
+    pub fn labels(&self) -> &Labels {
+        &self.labels
+    }
+
     pub fn record(&self, value: f64) {
         self.buckets.write().unwrap().push(value);
         self.count.fetch_add(1, Ordering::Relaxed);
@@ -575,6 +855,20 @@ impl Histogram {
         }
     }
 
+    /// Record a value with a trace-ID exemplar; the latest exemplar
+    /// is kept and exported alongside the histogram
+    pub fn record_with_exemplar(&self, value: f64, trace_id: impl Into<String>) {
+        self.record(value);
+        *self.exemplar.write().unwrap() = Some(Exemplar {
+            trace_id: trace_id.into(),
+            value,
+        });
+    }
+
+    pub fn exemplar(&self) -> Option<Exemplar> {
+        self.exemplar.read().unwrap().clone()
+    }
+
     pub fn count(&self) -> u64 {
         self.count.load(Ordering::Relaxed)
     }
@@ -608,11 +902,21 @@ impl Histogram {
 
 use std::sync::Arc;
 
+/// Default cap on label combinations per metric name
+const DEFAULT_MAX_SERIES: usize = 64;
+
 /// Metrics collector
+///
+/// Series are keyed by name plus rendered label set. Cardinality is
+/// bounded per metric name: once a metric has `max_series` label
+/// combinations, further combinations collapse into the unlabeled
+/// base series so an unbounded label (user IDs, raw paths) cannot
+/// exhaust memory.
 pub struct MetricsCollector {
     counters: RwLock<HashMap<String, Arc<Counter>>>,
     gauges: RwLock<HashMap<String, Arc<Gauge>>>,
     histograms: RwLock<HashMap<String, Arc<Histogram>>>,
+    max_series: usize,
 }
 
 impl MetricsCollector {
@@ -621,9 +925,27 @@ impl MetricsCollector {
             counters: RwLock::new(HashMap::new()),
             gauges: RwLock::new(HashMap::new()),
             histograms: RwLock::new(HashMap::new()),
+            max_series: DEFAULT_MAX_SERIES,
         }
     }
 
+    /// Cap on label combinations per metric name (default: 64)
+    pub fn series_limit(mut self, limit: usize) -> Self {
+        self.max_series = limit.max(1);
+        self
+    }
+
+    /// Whether a new labeled series for `name` fits under the cap
+    fn series_fits<T>(&self, map: &HashMap<String, Arc<T>>, name: &str) -> bool {
+        map.keys()
+            .filter(|key| {
+                key.as_str() == name
+                    || (key.starts_with(name) && key[name.len()..].starts_with('{'))
+            })
+            .count()
+            < self.max_series
+    }
+
     pub fn counter(&self, name: &str) -> Arc<Counter> {
         {
             let counters = self.counters.read().unwrap();
@@ -638,6 +960,29 @@ impl MetricsCollector {
             .clone()
     }
 
+    /// Counter series for a label set; at the cardinality cap the
+    /// labels are dropped and the base series is returned instead
+    pub fn counter_with(&self, name: &str, labels: Labels) -> Arc<Counter> {
+        let key = series_key(name, &labels);
+        {
+            let counters = self.counters.read().unwrap();
+            if let Some(counter) = counters.get(&key) {
+                return Arc::clone(counter);
+            }
+        }
+
+        {
+            let mut counters = self.counters.write().unwrap();
+            if counters.contains_key(&key) || self.series_fits(&counters, name) {
+                return counters
+                    .entry(key)
+                    .or_insert_with(|| Arc::new(Counter::with_labels(name, labels)))
+                    .clone();
+            }
+        }
+        self.counter(name)
+    }
+
     pub fn gauge(&self, name: &str) -> Arc<Gauge> {
         {
             let gauges = self.gauges.read().unwrap();
@@ -652,6 +997,29 @@ impl MetricsCollector {
             .clone()
     }
 
+    /// Gauge series for a label set; at the cardinality cap the
+    /// labels are dropped and the base series is returned instead
+    pub fn gauge_with(&self, name: &str, labels: Labels) -> Arc<Gauge> {
+        let key = series_key(name, &labels);
+        {
+            let gauges = self.gauges.read().unwrap();
+            if let Some(gauge) = gauges.get(&key) {
+                return Arc::clone(gauge);
+            }
+        }
+
+        {
+            let mut gauges = self.gauges.write().unwrap();
+            if gauges.contains_key(&key) || self.series_fits(&gauges, name) {
+                return gauges
+                    .entry(key)
+                    .or_insert_with(|| Arc::new(Gauge::with_labels(name, labels)))
+                    .clone();
+            }
+        }
+        self.gauge(name)
+    }
+
     pub fn histogram(&self, name: &str) -> Arc<Histogram> {
         {
             let histograms = self.histograms.read().unwrap();
@@ -666,27 +1034,90 @@ impl MetricsCollector {
             .clone()
     }
 
+    /// Histogram series for a label set; at the cardinality cap the
+    /// labels are dropped and the base series is returned instead
+    pub fn histogram_with(&self, name: &str, labels: Labels) -> Arc<Histogram> {
+        let key = series_key(name, &labels);
+        {
+            let histograms = self.histograms.read().unwrap();
+            if let Some(histogram) = histograms.get(&key) {
+                return Arc::clone(histogram);
+            }
+        }
+
+        {
+            let mut histograms = self.histograms.write().unwrap();
+            if histograms.contains_key(&key) || self.series_fits(&histograms, name) {
+                return histograms
+                    .entry(key)
+                    .or_insert_with(|| Arc::new(Histogram::with_labels(name, labels)))
+                    .clone();
+            }
+        }
+        self.histogram(name)
+    }
+
     /// Export metrics in Prometheus text format
+    ///
+    /// Series of one metric are grouped under a single `# TYPE` line;
+    /// histogram exemplars are rendered OpenMetrics-style after the
+    /// `_count` sample (`# {trace_id="..."} value`).
     pub fn to_prometheus(&self) -> String {
         let mut lines = Vec::new();
+        let mut typed: std::collections::HashSet<String> = std::collections::HashSet::new();
 
         // Counters
-        for (name, counter) in self.counters.read().unwrap().iter() {
-            lines.push(format!("# TYPE {} counter", name));
-            lines.push(format!("{} {}", name, counter.get()));
+        let counters = self.counters.read().unwrap();
+        let mut keys: Vec<&String> = counters.keys().collect();
+        keys.sort();
+        for key in keys {
+            let counter = &counters[key];
+            if typed.insert(counter.name().to_string()) {
+                lines.push(format!("# TYPE {} counter", counter.name()));
+            }
+            lines.push(format!("{} {}", key, counter.get()));
         }
 
         // Gauges
-        for (name, gauge) in self.gauges.read().unwrap().iter() {
-            lines.push(format!("# TYPE {} gauge", name));
-            lines.push(format!("{} {}", name, gauge.get()));
+        let gauges = self.gauges.read().unwrap();
+        let mut keys: Vec<&String> = gauges.keys().collect();
+        keys.sort();
+        for key in keys {
+            let gauge = &gauges[key];
+            if typed.insert(gauge.name().to_string()) {
+                lines.push(format!("# TYPE {} gauge", gauge.name()));
+            }
+            lines.push(format!("{} {}", key, gauge.get()));
         }
 
         // Histograms
-        for (name, histogram) in self.histograms.read().unwrap().iter() {
-            lines.push(format!("# TYPE {} histogram", name));
-            lines.push(format!("{}_count {}", name, histogram.count()));
-            lines.push(format!("{}_sum {}", name, histogram.sum()));
+        let histograms = self.histograms.read().unwrap();
+        let mut keys: Vec<&String> = histograms.keys().collect();
+        keys.sort();
+        for key in keys {
+            let histogram = &histograms[key];
+            if typed.insert(histogram.name().to_string()) {
+                lines.push(format!("# TYPE {} histogram", histogram.name()));
+            }
+            let count_suffix = series_key(
+                &format!("{}_count", histogram.name()),
+                histogram.labels(),
+            );
+            match histogram.exemplar() {
+                Some(exemplar) => lines.push(format!(
+                    "{} {} # {{trace_id=\"{}\"}} {}",
+                    count_suffix,
+                    histogram.count(),
+                    escape_label_value(&exemplar.trace_id),
+                    exemplar.value
+                )),
+                None => lines.push(format!("{} {}", count_suffix, histogram.count())),
+            }
+            lines.push(format!(
+                "{} {}",
+                series_key(&format!("{}_sum", histogram.name()), histogram.labels()),
+                histogram.sum()
+            ));
         }
 
         lines.join("\n")
@@ -811,6 +1242,46 @@ mod tests {
         assert_eq!(span.events.len(), 1);
     }
 
+    #[test]
+    fn test_span_links() {
+        let remote = SpanContext::new();
+        let mut attrs = SpanAttributes::new();
+        attrs.insert("batch.index".to_string(), AttributeValue::Int(3));
+
+        let mut span = Span::new("fan-out");
+        span.add_link(SpanLink::new(remote.clone()).with_attributes(attrs));
+
+        assert_eq!(span.links.len(), 1);
+        assert_eq!(span.links[0].context.trace_id, remote.trace_id);
+        assert_eq!(span.links[0].context.span_id, remote.span_id);
+        assert_eq!(
+            span.links[0].attributes.get("batch.index"),
+            Some(&AttributeValue::Int(3))
+        );
+    }
+
+    #[test]
+    fn test_parse_baggage() {
+        let entries =
+            parse_baggage("userId=alice,serverNode=DF%2028;metadata=rollout,isProduction=false");
+
+        assert_eq!(entries.get("userId"), Some(&"alice".to_string()));
+        // Value is percent-decoded; the `;metadata=rollout` property is dropped
+        assert_eq!(entries.get("serverNode"), Some(&"DF 28".to_string()));
+        assert_eq!(entries.get("isProduction"), Some(&"false".to_string()));
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[test]
+    fn test_format_baggage_roundtrip() {
+        let mut entries = HashMap::new();
+        entries.insert("userId".to_string(), "alice smith".to_string());
+
+        let header = format_baggage(&entries);
+        assert_eq!(header, "userId=alice%20smith");
+        assert_eq!(parse_baggage(&header), entries);
+    }
+
     #[test]
     fn test_tracer() {
         let tracer = Tracer::new(TracerConfig::new("test-service"));
@@ -826,6 +1297,64 @@ mod tests {
         assert_eq!(tracer.pending_count(), 0);
     }
 
+    #[test]
+    fn test_tail_sampler() {
+        let sampler = TailSampler::new(TailSamplerConfig::new().latency_threshold_ms(50.0));
+
+        // Healthy fast trace: dropped
+        let mut span = Span::new("fast");
+        span.end_with_status(SpanStatus::Ok);
+        let fast_trace = span.context.trace_id.clone();
+        sampler.record(span);
+        assert_eq!(sampler.buffered_trace_count(), 1);
+        assert!(!sampler.finish_trace(&fast_trace));
+        assert_eq!(sampler.buffered_trace_count(), 0);
+
+        // Trace with an error span: kept, including its healthy spans
+        let mut root = Span::new("root");
+        let mut child = Span::new("child").with_parent(&root.context);
+        let error_trace = root.context.trace_id.clone();
+        root.end_with_status(SpanStatus::Ok);
+        child.end_with_status(SpanStatus::Error);
+        sampler.record(root);
+        sampler.record(child);
+        assert!(sampler.finish_trace(&error_trace));
+        assert_eq!(sampler.drain_sampled().len(), 2);
+
+        // Slow trace: kept
+        let mut span = Span::new("slow");
+        span.end_time_ns = Some(span.start_time_ns + 60_000_000); // 60ms
+        let slow_trace = span.context.trace_id.clone();
+        sampler.record(span);
+        assert!(sampler.finish_trace(&slow_trace));
+        assert_eq!(sampler.drain_sampled().len(), 1);
+
+        // Unknown trace IDs are a no-op
+        assert!(!sampler.finish_trace("deadbeef"));
+    }
+
+    #[test]
+    fn test_tail_sampler_buffer_cap() {
+        let sampler = TailSampler::new(TailSamplerConfig::new().max_buffered_traces(1));
+
+        let first = Span::new("first");
+        let first_trace = first.context.trace_id.clone();
+        sampler.record(first);
+
+        // A second trace exceeds the cap and is dropped; spans for the
+        // buffered trace are still accepted
+        sampler.record(Span::new("second"));
+        assert_eq!(sampler.buffered_trace_count(), 1);
+
+        let mut child = Span::new("child");
+        child.context.trace_id = first_trace.clone();
+        sampler.record(child);
+        assert_eq!(sampler.buffered_trace_count(), 1);
+
+        assert!(!sampler.finish_trace(&first_trace));
+        assert_eq!(sampler.buffered_trace_count(), 0);
+    }
+
     #[test]
     fn test_counter() {
         let counter = Counter::new("requests_total");
@@ -876,4 +1405,65 @@ mod tests {
         assert!(prometheus.contains("http_requests 2"));
         assert!(prometheus.contains("connections 5"));
     }
+
+    #[test]
+    fn test_labeled_series() {
+        let collector = MetricsCollector::new();
+        let labels = vec![
+            ("route".to_string(), "/users".to_string()),
+            ("method".to_string(), "GET".to_string()),
+        ];
+
+        collector.counter_with("http_requests", labels.clone()).inc();
+        collector.counter_with("http_requests", labels.clone()).inc();
+        // Label order must not create a second series
+        let reversed = vec![labels[1].clone(), labels[0].clone()];
+        collector.counter_with("http_requests", reversed).inc();
+
+        let prometheus = collector.to_prometheus();
+        assert!(prometheus.contains("# TYPE http_requests counter"));
+        assert!(prometheus.contains("http_requests{method=\"GET\",route=\"/users\"} 3"));
+        // One TYPE line per metric name, not per series
+        assert_eq!(prometheus.matches("# TYPE http_requests").count(), 1);
+    }
+
+    #[test]
+    fn test_series_cardinality_bound() {
+        let collector = MetricsCollector::new().series_limit(3);
+
+        for i in 0..10 {
+            collector
+                .counter_with("hits", vec![("user".to_string(), i.to_string())])
+                .inc();
+        }
+
+        // Three labeled series fit; the overflow collapses into the
+        // unlabeled base series instead of allocating new ones
+        assert_eq!(collector.counters.read().unwrap().len(), 4);
+        assert_eq!(collector.counter("hits").get(), 7);
+    }
+
+    #[test]
+    fn test_histogram_exemplar() {
+        let collector = MetricsCollector::new();
+        let histogram = collector.histogram_with(
+            "latency_ms",
+            vec![("route".to_string(), "/slow".to_string())],
+        );
+        histogram.record(10.0);
+        histogram.record_with_exemplar(250.0, "4bf92f3577b34da6a3ce929d0e0e4736");
+
+        assert_eq!(
+            histogram.exemplar(),
+            Some(Exemplar {
+                trace_id: "4bf92f3577b34da6a3ce929d0e0e4736".to_string(),
+                value: 250.0,
+            })
+        );
+        let prometheus = collector.to_prometheus();
+        assert!(prometheus.contains(
+            "latency_ms_count{route=\"/slow\"} 2 # {trace_id=\"4bf92f3577b34da6a3ce929d0e0e4736\"} 250"
+        ));
+        assert!(prometheus.contains("latency_ms_sum{route=\"/slow\"} 260"));
+    }
 }
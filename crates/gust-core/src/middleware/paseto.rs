@@ -0,0 +1,429 @@
+//! PASETO v4 token support
+//!
+//! Platform-Agnostic Security Tokens as a modern alternative to JWT:
+//! versioned protocols instead of negotiable algorithms, so there is
+//! no `alg` header to confuse. Implements `v4.local` (XChaCha20 with
+//! a BLAKE2b MAC) and `v4.public` (Ed25519 signatures), sharing the
+//! JWT claims model. The purpose is selected by how the config is
+//! built: [`PasetoConfig::local`], [`PasetoConfig::public`], or
+//! [`PasetoConfig::public_verify_only`].
+//!
+//! # Timing side channels
+//!
+//! The primitives are the crate's own minimal implementations.
+//! Ed25519 scalar multiplication is branch-free and tags are compared
+//! in constant time, but the field arithmetic has not been audited to
+//! the level of a dedicated crypto library. Deployments where
+//! co-resident attackers can take fine-grained timing measurements
+//! should front token issuance with a vetted implementation.
+
+use super::jwt::{base64url_decode, base64url_encode, claims_from_json, claims_to_json, Claims};
+use super::Middleware;
+use crate::crypto::{
+    blake2b_keyed, constant_time_eq, ed25519_public_key, ed25519_sign, ed25519_verify,
+    random_bytes, xchacha20_xor,
+};
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+
+const LOCAL_HEADER: &str = "v4.local.";
+const PUBLIC_HEADER: &str = "v4.public.";
+
+/// Key material, implying the token purpose
+#[derive(Clone)]
+enum PasetoKey {
+    /// 256-bit symmetric key for v4.local
+    Local([u8; 32]),
+    /// Ed25519 seed for v4.public issuance and verification
+    Secret([u8; 32]),
+    /// Ed25519 public key for v4.public verification only
+    Public([u8; 32]),
+}
+
+/// PASETO configuration
+#[derive(Clone)]
+pub struct PasetoConfig {
+    key: PasetoKey,
+    /// Validate exp on decode (default: true)
+    pub validate_exp: bool,
+    /// Seconds of leeway for exp (clock skew)
+    pub leeway: u64,
+}
+
+impl PasetoConfig {
+    /// v4.local: encrypted tokens under a 32-byte shared key
+    pub fn local(key: impl AsRef<[u8]>) -> Result<Self, PasetoError> {
+        Ok(Self::with_key(PasetoKey::Local(key_bytes(key)?)))
+    }
+
+    /// v4.public: signed tokens from a 32-byte Ed25519 seed
+    pub fn public(seed: impl AsRef<[u8]>) -> Result<Self, PasetoError> {
+        Ok(Self::with_key(PasetoKey::Secret(key_bytes(seed)?)))
+    }
+
+    /// v4.public verification with just the 32-byte public key
+    pub fn public_verify_only(public_key: impl AsRef<[u8]>) -> Result<Self, PasetoError> {
+        Ok(Self::with_key(PasetoKey::Public(key_bytes(public_key)?)))
+    }
+
+    fn with_key(key: PasetoKey) -> Self {
+        Self {
+            key,
+            validate_exp: true,
+            leeway: 0,
+        }
+    }
+
+    pub fn leeway(mut self, seconds: u64) -> Self {
+        self.leeway = seconds;
+        self
+    }
+}
+
+fn key_bytes(key: impl AsRef<[u8]>) -> Result<[u8; 32], PasetoError> {
+    key.as_ref().try_into().map_err(|_| PasetoError::InvalidKey)
+}
+
+/// PASETO encoder/decoder
+pub struct Paseto {
+    config: PasetoConfig,
+}
+
+impl Paseto {
+    pub fn new(config: PasetoConfig) -> Self {
+        Self { config }
+    }
+
+    /// The Ed25519 public key for a v4.public config, for distribution
+    /// to verifiers
+    pub fn public_key(&self) -> Option<[u8; 32]> {
+        match &self.config.key {
+            PasetoKey::Secret(seed) => Some(ed25519_public_key(seed)),
+            PasetoKey::Public(public) => Some(*public),
+            PasetoKey::Local(_) => None,
+        }
+    }
+
+    /// Issue a token for the claims (encrypt for local, sign for public)
+    pub fn encode(&self, claims: &Claims) -> Result<String, PasetoError> {
+        let message = claims_to_json(claims);
+        match &self.config.key {
+            PasetoKey::Local(key) => Ok(encrypt_local(key, message.as_bytes())),
+            PasetoKey::Secret(seed) => Ok(sign_public(seed, message.as_bytes())),
+            PasetoKey::Public(_) => Err(PasetoError::MissingSecretKey),
+        }
+    }
+
+    /// Decode and validate a token of the configured purpose
+    pub fn decode(&self, token: &str) -> Result<Claims, PasetoError> {
+        let message = match &self.config.key {
+            PasetoKey::Local(key) => decrypt_local(key, token)?,
+            PasetoKey::Secret(seed) => verify_public(&ed25519_public_key(seed), token)?,
+            PasetoKey::Public(public) => verify_public(public, token)?,
+        };
+
+        let json = String::from_utf8(message).map_err(|_| PasetoError::InvalidFormat)?;
+        let claims = claims_from_json(&json).map_err(|_| PasetoError::InvalidFormat)?;
+
+        if self.config.validate_exp {
+            if let Some(exp) = claims.exp {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                if now > exp.saturating_add(self.config.leeway) {
+                    return Err(PasetoError::Expired);
+                }
+            }
+        }
+
+        Ok(claims)
+    }
+}
+
+/// Pre-Authentication Encoding (PASETO spec): unambiguous
+/// concatenation with little-endian length prefixes
+fn pae(pieces: &[&[u8]]) -> Vec<u8> {
+    let mut out = (pieces.len() as u64).to_le_bytes().to_vec();
+    for piece in pieces {
+        out.extend_from_slice(&(piece.len() as u64).to_le_bytes());
+        out.extend_from_slice(piece);
+    }
+    out
+}
+
+fn encrypt_local(key: &[u8; 32], message: &[u8]) -> String {
+    let nonce = random_bytes(32);
+    let (ek, n2, ak) = derive_local_keys(key, &nonce);
+    let ciphertext = xchacha20_xor(&ek, &n2, message);
+    let pre_auth = pae(&[LOCAL_HEADER.as_bytes(), &nonce, &ciphertext, b"", b""]);
+    let tag = blake2b_keyed(32, &ak, &pre_auth);
+
+    let mut payload = nonce;
+    payload.extend_from_slice(&ciphertext);
+    payload.extend_from_slice(&tag);
+    format!("{}{}", LOCAL_HEADER, base64url_encode(&payload))
+}
+
+fn decrypt_local(key: &[u8; 32], token: &str) -> Result<Vec<u8>, PasetoError> {
+    let payload = token
+        .strip_prefix(LOCAL_HEADER)
+        .ok_or(PasetoError::UnsupportedVersion)?;
+    let (payload, footer) = split_footer(payload)?;
+    let payload = base64url_decode(payload).ok_or(PasetoError::InvalidFormat)?;
+    if payload.len() < 64 {
+        return Err(PasetoError::InvalidFormat);
+    }
+
+    let nonce = &payload[..32];
+    let ciphertext = &payload[32..payload.len() - 32];
+    let tag = &payload[payload.len() - 32..];
+
+    let (ek, n2, ak) = derive_local_keys(key, nonce);
+    let pre_auth = pae(&[LOCAL_HEADER.as_bytes(), nonce, ciphertext, &footer, b""]);
+    let expected = blake2b_keyed(32, &ak, &pre_auth);
+    if !constant_time_eq(&expected, tag) {
+        return Err(PasetoError::VerificationFailed);
+    }
+
+    Ok(xchacha20_xor(&ek, &n2, ciphertext))
+}
+
+/// Derive encryption key, XChaCha nonce, and auth key from the
+/// master key and per-token nonce (PASETO v4 spec §encrypt)
+fn derive_local_keys(key: &[u8; 32], nonce: &[u8]) -> ([u8; 32], [u8; 24], [u8; 32]) {
+    let mut ek_input = b"paseto-encryption-key".to_vec();
+    ek_input.extend_from_slice(nonce);
+    let tmp = blake2b_keyed(56, key, &ek_input);
+    let ek: [u8; 32] = tmp[..32].try_into().unwrap();
+    let n2: [u8; 24] = tmp[32..].try_into().unwrap();
+
+    let mut ak_input = b"paseto-auth-key-for-aead".to_vec();
+    ak_input.extend_from_slice(nonce);
+    let ak: [u8; 32] = blake2b_keyed(32, key, &ak_input).try_into().unwrap();
+
+    (ek, n2, ak)
+}
+
+fn sign_public(seed: &[u8; 32], message: &[u8]) -> String {
+    let pre_auth = pae(&[PUBLIC_HEADER.as_bytes(), message, b"", b""]);
+    let signature = ed25519_sign(seed, &pre_auth);
+
+    let mut payload = message.to_vec();
+    payload.extend_from_slice(&signature);
+    format!("{}{}", PUBLIC_HEADER, base64url_encode(&payload))
+}
+
+fn verify_public(public: &[u8; 32], token: &str) -> Result<Vec<u8>, PasetoError> {
+    let payload = token
+        .strip_prefix(PUBLIC_HEADER)
+        .ok_or(PasetoError::UnsupportedVersion)?;
+    let (payload, footer) = split_footer(payload)?;
+    let payload = base64url_decode(payload).ok_or(PasetoError::InvalidFormat)?;
+    if payload.len() < 64 {
+        return Err(PasetoError::InvalidFormat);
+    }
+
+    let message = &payload[..payload.len() - 64];
+    let signature: [u8; 64] = payload[payload.len() - 64..].try_into().unwrap();
+
+    let pre_auth = pae(&[PUBLIC_HEADER.as_bytes(), message, &footer, b""]);
+    if !ed25519_verify(public, &pre_auth, &signature) {
+        return Err(PasetoError::VerificationFailed);
+    }
+
+    Ok(message.to_vec())
+}
+
+/// Split an optional `.footer` part off the payload; the decoded
+/// footer is authenticated but otherwise ignored
+fn split_footer(payload: &str) -> Result<(&str, Vec<u8>), PasetoError> {
+    match payload.split_once('.') {
+        Some((payload, footer)) => {
+            let footer = base64url_decode(footer).ok_or(PasetoError::InvalidFormat)?;
+            Ok((payload, footer))
+        }
+        None => Ok((payload, Vec::new())),
+    }
+}
+
+/// PASETO middleware for request authentication
+pub struct PasetoMiddleware {
+    paseto: Paseto,
+}
+
+impl PasetoMiddleware {
+    pub fn new(config: PasetoConfig) -> Self {
+        Self {
+            paseto: Paseto::new(config),
+        }
+    }
+}
+
+impl Middleware for PasetoMiddleware {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        let token = match req
+            .header("authorization")
+            .and_then(|h| h.strip_prefix("Bearer "))
+        {
+            Some(t) => t,
+            None => {
+                return Some(
+                    ResponseBuilder::new(StatusCode::UNAUTHORIZED)
+                        .header("WWW-Authenticate", "Bearer")
+                        .body(r#"{"error":"Missing authorization header"}"#)
+                        .build(),
+                )
+            }
+        };
+
+        match self.paseto.decode(token) {
+            Ok(claims) => {
+                if let Some(sub) = &claims.sub {
+                    req.params.insert("_jwt_sub".to_string(), sub.clone());
+                }
+                None
+            }
+            Err(e) => Some(
+                ResponseBuilder::new(StatusCode::UNAUTHORIZED)
+                    .body(format!(r#"{{"error":"{}"}}"#, e))
+                    .build(),
+            ),
+        }
+    }
+
+    fn after(&self, _req: &Request, _res: &mut Response) {}
+}
+
+/// PASETO Error
+#[derive(Debug)]
+pub enum PasetoError {
+    InvalidFormat,
+    InvalidKey,
+    UnsupportedVersion,
+    VerificationFailed,
+    Expired,
+    MissingSecretKey,
+}
+
+impl std::fmt::Display for PasetoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasetoError::InvalidFormat => write!(f, "Invalid token format"),
+            PasetoError::InvalidKey => write!(f, "Key must be 32 bytes"),
+            PasetoError::UnsupportedVersion => write!(f, "Unsupported token version"),
+            PasetoError::VerificationFailed => write!(f, "Verification failed"),
+            PasetoError::Expired => write!(f, "Token expired"),
+            PasetoError::MissingSecretKey => write!(f, "Config can only verify tokens"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paseto_local_roundtrip() {
+        let paseto = Paseto::new(PasetoConfig::local([1u8; 32]).unwrap());
+        let claims = Claims::new()
+            .sub("user123")
+            .exp_in(3600)
+            .claim("role", "admin");
+
+        let token = paseto.encode(&claims).unwrap();
+        assert!(token.starts_with("v4.local."));
+        assert!(!token.contains("admin"));
+
+        let decoded = paseto.decode(&token).unwrap();
+        assert_eq!(decoded.sub, Some("user123".to_string()));
+        assert_eq!(decoded.custom.get("role"), Some(&"admin".to_string()));
+    }
+
+    #[test]
+    fn test_paseto_local_wrong_key() {
+        let token = Paseto::new(PasetoConfig::local([1u8; 32]).unwrap())
+            .encode(&Claims::new().sub("user"))
+            .unwrap();
+        let other = Paseto::new(PasetoConfig::local([2u8; 32]).unwrap());
+        assert!(matches!(
+            other.decode(&token),
+            Err(PasetoError::VerificationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_paseto_public_roundtrip() {
+        let signer = Paseto::new(PasetoConfig::public([5u8; 32]).unwrap());
+        let token = signer.encode(&Claims::new().sub("user123")).unwrap();
+        assert!(token.starts_with("v4.public."));
+
+        // The verifier only needs the public key
+        let public = signer.public_key().unwrap();
+        let verifier = Paseto::new(PasetoConfig::public_verify_only(public).unwrap());
+        let decoded = verifier.decode(&token).unwrap();
+        assert_eq!(decoded.sub, Some("user123".to_string()));
+
+        // Verify-only configs cannot issue
+        assert!(matches!(
+            verifier.encode(&Claims::new()),
+            Err(PasetoError::MissingSecretKey)
+        ));
+    }
+
+    #[test]
+    fn test_paseto_public_tamper() {
+        let signer = Paseto::new(PasetoConfig::public([5u8; 32]).unwrap());
+        let token = signer.encode(&Claims::new().sub("user")).unwrap();
+
+        // Flip a character in the payload
+        let mut bytes = token.into_bytes();
+        let i = bytes.len() - 10;
+        bytes[i] = if bytes[i] == b'A' { b'B' } else { b'A' };
+        let forged = String::from_utf8(bytes).unwrap();
+        assert!(signer.decode(&forged).is_err());
+    }
+
+    #[test]
+    fn test_paseto_purpose_mismatch() {
+        let local = Paseto::new(PasetoConfig::local([1u8; 32]).unwrap());
+        let public = Paseto::new(PasetoConfig::public([5u8; 32]).unwrap());
+
+        let token = public.encode(&Claims::new().sub("user")).unwrap();
+        assert!(matches!(
+            local.decode(&token),
+            Err(PasetoError::UnsupportedVersion)
+        ));
+        let token = local.encode(&Claims::new().sub("user")).unwrap();
+        assert!(matches!(
+            public.decode(&token),
+            Err(PasetoError::UnsupportedVersion)
+        ));
+    }
+
+    #[test]
+    fn test_paseto_expired() {
+        let paseto = Paseto::new(PasetoConfig::local([1u8; 32]).unwrap());
+        let mut claims = Claims::new().sub("user");
+        claims.exp = Some(1);
+        let token = paseto.encode(&claims).unwrap();
+        assert!(matches!(paseto.decode(&token), Err(PasetoError::Expired)));
+
+        // Leeway rescues recently expired tokens
+        let lenient = Paseto::new(
+            PasetoConfig::local([1u8; 32]).unwrap().leeway(u64::MAX),
+        );
+        assert!(lenient.decode(&token).is_ok());
+    }
+
+    #[test]
+    fn test_paseto_key_length_checked() {
+        assert!(matches!(
+            PasetoConfig::local([0u8; 16]),
+            Err(PasetoError::InvalidKey)
+        ));
+        assert!(matches!(
+            PasetoConfig::public([0u8; 64]),
+            Err(PasetoError::InvalidKey)
+        ));
+    }
+}
@@ -237,6 +237,17 @@ pub fn parse_forwarded_for(header: &str) -> Vec<String> {
         .collect()
 }
 
+/// Normalize a Host value: IDN hosts convert to their punycode ASCII
+/// form so host comparisons see one canonical spelling; ASCII hosts
+/// pass through unchanged (no allocation beyond the copy)
+fn normalize_host(host: &str) -> String {
+    if host.is_ascii() {
+        host.to_string()
+    } else {
+        crate::pure::idna::domain_to_ascii(host)
+    }
+}
+
 /// Check if IP should be trusted
 pub fn is_trusted(ip: &str, trust: &TrustProxy) -> bool {
     match trust {
@@ -265,7 +276,7 @@ pub fn extract_proxy_info(
     if !is_trusted(socket_ip, &config.trust) {
         return ProxyInfo {
             ip: socket_ip.to_string(),
-            host: host_header.unwrap_or("localhost").to_string(),
+            host: normalize_host(host_header.unwrap_or("localhost")),
             protocol: Protocol::Http,
             port: 80,
             ips: vec![socket_ip.to_string()],
@@ -287,6 +298,19 @@ pub fn extract_proxy_info(
             let index = all_ips.len().saturating_sub(*n + 1);
             all_ips.get(index).cloned().unwrap_or_else(|| socket_ip.to_string())
         }
+        TrustProxy::Addresses(_) => {
+            // Per-hop evaluation (Forwarded spec): walk the chain
+            // right-to-left past trusted hops; the first untrusted
+            // address is the client. A fully-trusted chain falls back
+            // to the leftmost entry.
+            all_ips
+                .iter()
+                .rev()
+                .find(|ip| !is_trusted(ip, &config.trust))
+                .or_else(|| all_ips.first())
+                .cloned()
+                .unwrap_or_else(|| socket_ip.to_string())
+        }
         _ => {
             // Use first forwarded IP or socket IP
             forwarded_ips.first().cloned().unwrap_or_else(|| socket_ip.to_string())
@@ -294,10 +318,11 @@ pub fn extract_proxy_info(
     };
 
     // Parse other headers
-    let host = get_header(&config.host_header)
-        .or(host_header)
-        .unwrap_or("localhost")
-        .to_string();
+    let host = normalize_host(
+        get_header(&config.host_header)
+            .or(host_header)
+            .unwrap_or("localhost"),
+    );
 
     let protocol = get_header(&config.proto_header)
         .and_then(|p| p.parse().ok())
@@ -386,6 +411,24 @@ mod tests {
         assert_eq!(info.ips, vec!["1.1.1.1", "2.2.2.2", "10.0.0.1"]);
     }
 
+    #[test]
+    fn test_extract_proxy_info_idn_host() {
+        let config = ProxyConfig::new().trust_all();
+        let headers = vec![
+            ("x-forwarded-host".to_string(), "bücher.example:8443".to_string()),
+        ];
+
+        let info = extract_proxy_info(&config, "10.0.0.1", &headers, None);
+
+        // IDN hosts normalize to punycode, preserving the port
+        assert_eq!(info.host, "xn--bcher-kva.example:8443");
+
+        // Untrusted path normalizes the Host header the same way
+        let config = ProxyConfig::new();
+        let info = extract_proxy_info(&config, "10.0.0.1", &[], Some("münchen.de"));
+        assert_eq!(info.host, "xn--mnchen-3ya.de");
+    }
+
     #[test]
     fn test_extract_proxy_info_trust_count() {
         let config = ProxyConfig::new().trust_count(1);
@@ -399,6 +442,26 @@ mod tests {
         assert_eq!(info.ip, "2.2.2.2");
     }
 
+    #[test]
+    fn test_extract_proxy_info_per_hop() {
+        let config = ProxyConfig::new().trust_addresses(vec!["10.0.0.0/8", "2.2.2.2"]);
+
+        // A spoofed leftmost entry is skipped: walking right-to-left
+        // past the trusted hops stops at the first untrusted address
+        let headers = vec![
+            ("x-forwarded-for".to_string(), "9.9.9.9, 1.1.1.1, 2.2.2.2".to_string()),
+        ];
+        let info = extract_proxy_info(&config, "10.0.0.1", &headers, None);
+        assert_eq!(info.ip, "1.1.1.1");
+
+        // Fully-trusted chain falls back to the leftmost entry
+        let headers = vec![
+            ("x-forwarded-for".to_string(), "2.2.2.2".to_string()),
+        ];
+        let info = extract_proxy_info(&config, "10.0.0.1", &headers, None);
+        assert_eq!(info.ip, "2.2.2.2");
+    }
+
     #[test]
     fn test_protocol() {
         assert_eq!(Protocol::Http.as_str(), "http");
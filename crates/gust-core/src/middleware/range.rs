@@ -134,6 +134,8 @@ pub struct RangeConfig {
     pub max_ranges: usize,
     /// Enable multipart responses for multiple ranges
     pub multipart: bool,
+    /// Emit weak (`W/`-prefixed) ETags from the mtime+size generator
+    pub weak_etags: bool,
 }
 
 impl Default for RangeConfig {
@@ -141,10 +143,80 @@ impl Default for RangeConfig {
         Self {
             max_ranges: 1,
             multipart: false,
+            weak_etags: false,
         }
     }
 }
 
+impl RangeConfig {
+    /// Generate an ETag with the configured strength
+    pub fn etag_for(&self, mtime_ms: u64, size: u64) -> String {
+        if self.weak_etags {
+            generate_weak_etag(mtime_ms, size)
+        } else {
+            generate_etag(mtime_ms, size)
+        }
+    }
+}
+
+/// Generate a boundary for a multipart/byteranges response
+pub fn multipart_boundary() -> String {
+    format!("gust-{}", crate::middleware::otel::generate_span_id())
+}
+
+/// Content-Type header value for a multipart/byteranges response
+pub fn multipart_content_type(boundary: &str) -> String {
+    format!("multipart/byteranges; boundary={}", boundary)
+}
+
+/// Header block opening one part: dash-boundary, the part's
+/// content-type and content-range, and the blank line before its data
+fn multipart_part_header(boundary: &str, content_type: &str, range: &Range, total: u64) -> String {
+    format!(
+        "--{}\r\ncontent-type: {}\r\ncontent-range: {}\r\n\r\n",
+        boundary,
+        content_type,
+        content_range(range.start, range.end, total)
+    )
+}
+
+/// Body length of a multipart/byteranges response, computed without
+/// building it (so Content-Length can be set up front)
+pub fn multipart_content_length(
+    ranges: &[Range],
+    total: u64,
+    content_type: &str,
+    boundary: &str,
+) -> u64 {
+    let mut length = 0u64;
+    for range in ranges {
+        length += multipart_part_header(boundary, content_type, range, total).len() as u64;
+        length += range.content_length() + 2; // part data + closing CRLF
+    }
+    length + boundary.len() as u64 + 6 // "--" boundary "--\r\n"
+}
+
+/// Build a multipart/byteranges body (RFC 7233 §4.1) from the full
+/// entity bytes
+///
+/// `content_type` is the entity's own type, repeated per part;
+/// ranges must already be validated against the entity size. The
+/// result's length always matches [`multipart_content_length`].
+pub fn multipart_body(data: &[u8], ranges: &[Range], content_type: &str, boundary: &str) -> Vec<u8> {
+    let total = data.len() as u64;
+    let capacity = multipart_content_length(ranges, total, content_type, boundary) as usize;
+    let mut body = Vec::with_capacity(capacity);
+    for range in ranges {
+        body.extend_from_slice(
+            multipart_part_header(boundary, content_type, range, total).as_bytes(),
+        );
+        body.extend_from_slice(&data[range.start as usize..=range.end as usize]);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+    body
+}
+
 /// MIME type detection for common media files
 pub fn get_mime_type(extension: &str) -> &'static str {
     match extension.to_lowercase().as_str() {
@@ -191,31 +263,71 @@ pub fn generate_etag(mtime_ms: u64, size: u64) -> String {
     format!("\"{:x}-{:x}\"", mtime_ms, size)
 }
 
+/// Generate a weak ETag (`W/` prefix) from file metadata
+///
+/// mtime+size validators are semantically weak — two byte-for-byte
+/// different bodies can share them — so weak is the honest marking.
+pub fn generate_weak_etag(mtime_ms: u64, size: u64) -> String {
+    format!("W/{}", generate_etag(mtime_ms, size))
+}
+
+/// The opaque tag without a `W/` prefix
+fn opaque_tag(etag: &str) -> &str {
+    etag.strip_prefix("W/").unwrap_or(etag)
+}
+
+/// Weak ETag comparison (RFC 7232 §2.3.2): `W/` prefixes are ignored,
+/// only the opaque tags must match
+pub fn etag_weak_match(a: &str, b: &str) -> bool {
+    opaque_tag(a) == opaque_tag(b)
+}
+
+/// Strong ETag comparison (RFC 7232 §2.3.2): a weak ETag on either
+/// side never matches
+pub fn etag_strong_match(a: &str, b: &str) -> bool {
+    !a.starts_with("W/") && !b.starts_with("W/") && a == b
+}
+
 /// Check If-None-Match header
+///
+/// Returns true when the stored ETag matches and a 304 (or 412 for
+/// state-changing methods) applies. If-None-Match uses weak comparison
+/// and accepts a comma-separated list or `*`.
 pub fn check_if_none_match(if_none_match: &str, etag: &str) -> bool {
-    if_none_match == etag || if_none_match == "*"
+    if if_none_match.trim() == "*" {
+        return true;
+    }
+    if_none_match
+        .split(',')
+        .any(|candidate| etag_weak_match(candidate.trim(), etag))
+}
+
+/// Check If-Match header
+///
+/// Returns true when the precondition holds. If-Match uses strong
+/// comparison — a weak ETag never satisfies it — and accepts a
+/// comma-separated list or `*` (which any existing representation
+/// satisfies).
+pub fn check_if_match(if_match: &str, etag: &str) -> bool {
+    if if_match.trim() == "*" {
+        return true;
+    }
+    if_match
+        .split(',')
+        .any(|candidate| etag_strong_match(candidate.trim(), etag))
 }
 
 /// Check If-Modified-Since header
+///
+/// Returns true when the resource has NOT been modified since the
+/// given date, i.e. a 304 can be served. Unparseable dates are
+/// ignored per RFC 7232.
 pub fn check_if_modified_since(if_modified_since: &str, mtime: u64) -> bool {
-    // Parse HTTP date format and compare
-    // This is a simplified implementation
-    parse_http_date(if_modified_since)
+    crate::pure::http_date::parse_http_date(if_modified_since)
         .map(|since| mtime <= since)
         .unwrap_or(false)
 }
 
-/// Parse HTTP date (simplified)
-fn parse_http_date(date: &str) -> Option<u64> {
-    // HTTP dates are in format: "Sun, 06 Nov 1994 08:49:37 GMT"
-    // This is a very simplified parser - in production, use a proper date parser
-
-    // For now, just return None to disable If-Modified-Since checking
-    // A full implementation would parse RFC 7231 date formats
-    let _ = date;
-    None
-}
-
 /// Range response builder
 #[derive(Debug)]
 pub struct RangeResponse {
@@ -283,6 +395,35 @@ impl RangeResponse {
         }
     }
 
+    /// Build response for multiple ranges (206, multipart/byteranges)
+    ///
+    /// The caller builds the body with [`multipart_body`] using the
+    /// same boundary; `content_type` is the entity's own type, which
+    /// reappears in each part (the response's Content-Type is the
+    /// multipart one). Content-Range stays off the response — each
+    /// part carries its own.
+    pub fn multipart(
+        content_type: &str,
+        ranges: &[Range],
+        total_size: u64,
+        boundary: &str,
+        etag: &str,
+        last_modified: &str,
+        max_age: u32,
+    ) -> Self {
+        Self {
+            status: 206,
+            content_type: multipart_content_type(boundary),
+            content_length: multipart_content_length(ranges, total_size, content_type, boundary),
+            content_range: None,
+            accept_ranges: "bytes".to_string(),
+            etag: etag.to_string(),
+            last_modified: last_modified.to_string(),
+            cache_control: format!("public, max-age={}", max_age),
+            range: None,
+        }
+    }
+
     /// Build 304 Not Modified response
     pub fn not_modified(etag: &str, last_modified: &str) -> Self {
         Self {
@@ -418,6 +559,46 @@ mod tests {
         assert_eq!(range.content_length(), 100);
     }
 
+    #[test]
+    fn test_multipart_body_and_content_length_agree() {
+        let data: Vec<u8> = (0..=255u8).collect();
+        let ranges = [Range::new(0, 9), Range::new(100, 119)];
+        let boundary = "test-boundary";
+
+        let body = multipart_body(&data, &ranges, "application/octet-stream", boundary);
+        assert_eq!(
+            body.len() as u64,
+            multipart_content_length(
+                &ranges,
+                data.len() as u64,
+                "application/octet-stream",
+                boundary
+            )
+        );
+
+        let text = String::from_utf8_lossy(&body);
+        assert!(text.starts_with("--test-boundary\r\n"));
+        assert!(text.contains("content-range: bytes 0-9/256\r\n"));
+        assert!(text.contains("content-range: bytes 100-119/256\r\n"));
+        assert!(text.ends_with("--test-boundary--\r\n"));
+        // The part data itself is present
+        assert_eq!(&body[body.len() - 19 - 22..body.len() - 19 - 2], &data[100..120]);
+    }
+
+    #[test]
+    fn test_multipart_response_metadata() {
+        let ranges = [Range::new(0, 99), Range::new(200, 299)];
+        let response =
+            RangeResponse::multipart("video/mp4", &ranges, 1000, "b", "\"etag\"", "now", 60);
+        assert_eq!(response.status, 206);
+        assert_eq!(response.content_type, "multipart/byteranges; boundary=b");
+        assert!(response.content_range.is_none());
+        assert_eq!(
+            response.content_length,
+            multipart_content_length(&ranges, 1000, "video/mp4", "b")
+        );
+    }
+
     #[test]
     fn test_mime_types() {
         assert_eq!(get_mime_type("mp4"), "video/mp4");
@@ -427,12 +608,68 @@ mod tests {
         assert_eq!(get_mime_type("unknown"), "application/octet-stream");
     }
 
+    #[test]
+    fn test_check_if_modified_since() {
+        // RFC 7231 example date: Sun, 06 Nov 1994 08:49:37 GMT
+        let date = "Sun, 06 Nov 1994 08:49:37 GMT";
+        assert!(check_if_modified_since(date, 784111777));
+        assert!(check_if_modified_since(date, 784111776));
+        assert!(!check_if_modified_since(date, 784111778));
+        // Malformed dates are ignored (no 304)
+        assert!(!check_if_modified_since("garbage", 0));
+    }
+
     #[test]
     fn test_etag_generation() {
         let etag = generate_etag(1234567890, 1000);
         assert!(etag.starts_with('"'));
         assert!(etag.ends_with('"'));
         assert!(etag.contains('-'));
+
+        let weak = generate_weak_etag(1234567890, 1000);
+        assert_eq!(weak, format!("W/{}", etag));
+        assert_eq!(RangeConfig::default().etag_for(1234567890, 1000), etag);
+        let config = RangeConfig {
+            weak_etags: true,
+            ..Default::default()
+        };
+        assert_eq!(config.etag_for(1234567890, 1000), weak);
+    }
+
+    #[test]
+    fn test_weak_vs_strong_comparison() {
+        // Weak comparison ignores W/ on either side
+        assert!(etag_weak_match("W/\"abc\"", "\"abc\""));
+        assert!(etag_weak_match("\"abc\"", "W/\"abc\""));
+        assert!(etag_weak_match("W/\"abc\"", "W/\"abc\""));
+        assert!(!etag_weak_match("W/\"abc\"", "\"def\""));
+
+        // Strong comparison never matches a weak ETag
+        assert!(etag_strong_match("\"abc\"", "\"abc\""));
+        assert!(!etag_strong_match("W/\"abc\"", "\"abc\""));
+        assert!(!etag_strong_match("\"abc\"", "W/\"abc\""));
+    }
+
+    #[test]
+    fn test_if_none_match_semantics() {
+        // Weak comparison, list form and *
+        assert!(check_if_none_match("\"abc\"", "\"abc\""));
+        assert!(check_if_none_match("W/\"abc\"", "\"abc\""));
+        assert!(check_if_none_match("\"abc\"", "W/\"abc\""));
+        assert!(check_if_none_match("\"xyz\", W/\"abc\"", "\"abc\""));
+        assert!(check_if_none_match("*", "\"abc\""));
+        assert!(!check_if_none_match("\"xyz\"", "\"abc\""));
+    }
+
+    #[test]
+    fn test_if_match_semantics() {
+        // Strong comparison only
+        assert!(check_if_match("\"abc\"", "\"abc\""));
+        assert!(check_if_match("\"xyz\", \"abc\"", "\"abc\""));
+        assert!(check_if_match("*", "\"abc\""));
+        assert!(!check_if_match("W/\"abc\"", "\"abc\""));
+        assert!(!check_if_match("\"abc\"", "W/\"abc\""));
+        assert!(!check_if_match("\"xyz\"", "\"abc\""));
     }
 
     #[test]
@@ -0,0 +1,445 @@
+//! Refresh Token Rotation
+//!
+//! Opaque remember-me / refresh tokens with hashed storage, rotation
+//! on use, and replay detection: presenting an already-rotated token
+//! revokes its whole family. Complements the session and JWT
+//! middleware for long-lived auth flows.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::crypto::sha256_hex;
+
+/// Generate an opaque token (32 random bytes, hex-encoded)
+///
+/// Tokens are long-lived bearer credentials, so they come from the
+/// OS CSPRNG — a predictable token defeats the reuse detection.
+fn generate_token() -> String {
+    crate::crypto::random_hex(32)
+}
+
+/// Refresh token configuration
+#[derive(Debug, Clone)]
+pub struct RefreshTokenConfig {
+    /// Lifetime of each issued token (default: 30 days)
+    pub ttl: Duration,
+    /// Lifetime cap for a whole family from first issuance (default:
+    /// 90 days); rotation never extends a family past it
+    pub family_ttl: Duration,
+}
+
+impl Default for RefreshTokenConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(30 * 24 * 60 * 60),
+            family_ttl: Duration::from_secs(90 * 24 * 60 * 60),
+        }
+    }
+}
+
+impl RefreshTokenConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn family_ttl(mut self, ttl: Duration) -> Self {
+        self.family_ttl = ttl;
+        self
+    }
+}
+
+/// Why a refresh token was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshError {
+    /// Token was never issued, was revoked, or has been pruned
+    Unknown,
+    /// Token or its family has expired
+    Expired,
+    /// Token was already rotated — replay detected, family revoked
+    Reused,
+}
+
+impl std::fmt::Display for RefreshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RefreshError::Unknown => write!(f, "Unknown refresh token"),
+            RefreshError::Expired => write!(f, "Refresh token expired"),
+            RefreshError::Reused => write!(f, "Refresh token reuse detected"),
+        }
+    }
+}
+
+impl std::error::Error for RefreshError {}
+
+/// A freshly issued refresh token
+///
+/// The opaque token is only ever returned here; storage keeps its
+/// SHA-256 hash.
+#[derive(Debug, Clone)]
+pub struct IssuedToken {
+    /// The opaque token to hand to the client
+    pub token: String,
+    /// Family this token belongs to (stable across rotations)
+    pub family: String,
+    /// Subject the token was issued to
+    pub subject: String,
+    /// Time until the token expires
+    pub expires_in: Duration,
+}
+
+/// Refresh token counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RefreshTokenStats {
+    /// Tokens issued (initial issuance, not rotations)
+    pub issued: u64,
+    /// Successful rotations
+    pub rotated: u64,
+    /// Replays of already-rotated tokens
+    pub reuse_detected: u64,
+    /// Tokens revoked (explicitly or via family/replay revocation)
+    pub revoked: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenState {
+    Active,
+    /// Rotated away; kept around to detect replays
+    Rotated,
+}
+
+struct StoredToken {
+    subject: String,
+    family: String,
+    expires: Instant,
+    family_expires: Instant,
+    state: TokenState,
+}
+
+/// In-memory refresh token issuer with rotation and replay detection
+pub struct RefreshTokens {
+    config: RefreshTokenConfig,
+    /// Keyed by SHA-256 hash of the opaque token
+    tokens: RwLock<HashMap<String, StoredToken>>,
+    /// Token hashes per family
+    families: RwLock<HashMap<String, Vec<String>>>,
+    issued: AtomicU64,
+    rotated: AtomicU64,
+    reuse_detected: AtomicU64,
+    revoked: AtomicU64,
+}
+
+impl RefreshTokens {
+    pub fn new(config: RefreshTokenConfig) -> Self {
+        Self {
+            config,
+            tokens: RwLock::new(HashMap::new()),
+            families: RwLock::new(HashMap::new()),
+            issued: AtomicU64::new(0),
+            rotated: AtomicU64::new(0),
+            reuse_detected: AtomicU64::new(0),
+            revoked: AtomicU64::new(0),
+        }
+    }
+
+    /// Issue a new token in a new family (e.g. at login)
+    pub fn issue(&self, subject: impl Into<String>) -> IssuedToken {
+        self.issued.fetch_add(1, Ordering::Relaxed);
+        let family = generate_token();
+        let family_expires = Instant::now() + self.config.family_ttl;
+        self.insert_token(subject.into(), family, family_expires)
+    }
+
+    /// Exchange a token for its successor in the same family
+    ///
+    /// The presented token is marked rotated and stays stored so a
+    /// replay is detected: presenting it again revokes every token in
+    /// the family and returns [`RefreshError::Reused`].
+    pub fn rotate(&self, token: &str) -> Result<IssuedToken, RefreshError> {
+        let hash = sha256_hex(token.as_bytes());
+        let now = Instant::now();
+
+        enum Outcome {
+            Rotate(String, String, Instant),
+            Replayed(String),
+            Expired(String),
+        }
+
+        // Decide under the tokens lock, act on the family index after
+        // releasing it (the two locks are never held together)
+        let outcome = {
+            let mut tokens = self.tokens.write().unwrap();
+            match tokens.get_mut(&hash) {
+                None => return Err(RefreshError::Unknown),
+                Some(stored) if stored.state == TokenState::Rotated => {
+                    Outcome::Replayed(stored.family.clone())
+                }
+                Some(stored) if stored.expires <= now || stored.family_expires <= now => {
+                    let family = stored.family.clone();
+                    tokens.remove(&hash);
+                    Outcome::Expired(family)
+                }
+                Some(stored) => {
+                    stored.state = TokenState::Rotated;
+                    Outcome::Rotate(
+                        stored.subject.clone(),
+                        stored.family.clone(),
+                        stored.family_expires,
+                    )
+                }
+            }
+        };
+
+        match outcome {
+            Outcome::Rotate(subject, family, family_expires) => {
+                self.rotated.fetch_add(1, Ordering::Relaxed);
+                Ok(self.insert_token(subject, family, family_expires))
+            }
+            Outcome::Replayed(family) => {
+                // Replay of a rotated token: the real client's current
+                // token may be in an attacker's hands, burn the family
+                self.reuse_detected.fetch_add(1, Ordering::Relaxed);
+                self.revoke_family(&family);
+                Err(RefreshError::Reused)
+            }
+            Outcome::Expired(family) => {
+                self.unindex(&family, &hash);
+                Err(RefreshError::Expired)
+            }
+        }
+    }
+
+    /// Check a token without rotating it, returning its subject
+    pub fn validate(&self, token: &str) -> Result<String, RefreshError> {
+        let hash = sha256_hex(token.as_bytes());
+        let now = Instant::now();
+        let tokens = self.tokens.read().unwrap();
+        match tokens.get(&hash) {
+            None => Err(RefreshError::Unknown),
+            Some(stored) if stored.state == TokenState::Rotated => Err(RefreshError::Reused),
+            Some(stored) if stored.expires <= now || stored.family_expires <= now => {
+                Err(RefreshError::Expired)
+            }
+            Some(stored) => Ok(stored.subject.clone()),
+        }
+    }
+
+    /// Revoke a single token
+    pub fn revoke(&self, token: &str) -> bool {
+        let hash = sha256_hex(token.as_bytes());
+        let removed = self.tokens.write().unwrap().remove(&hash);
+        if let Some(stored) = removed {
+            self.unindex(&stored.family, &hash);
+            self.revoked.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Revoke every token in a family, returning the count
+    pub fn revoke_family(&self, family: &str) -> usize {
+        let hashes = match self.families.write().unwrap().remove(family) {
+            Some(hashes) => hashes,
+            None => return 0,
+        };
+        let mut tokens = self.tokens.write().unwrap();
+        let mut removed = 0;
+        for hash in &hashes {
+            if tokens.remove(hash).is_some() {
+                removed += 1;
+            }
+        }
+        self.revoked.fetch_add(removed as u64, Ordering::Relaxed);
+        removed
+    }
+
+    /// Revoke every token issued to a subject, returning the count
+    pub fn revoke_subject(&self, subject: &str) -> usize {
+        let families: Vec<String> = {
+            let tokens = self.tokens.read().unwrap();
+            let mut families: Vec<String> = tokens
+                .values()
+                .filter(|stored| stored.subject == subject)
+                .map(|stored| stored.family.clone())
+                .collect();
+            families.sort();
+            families.dedup();
+            families
+        };
+        families
+            .iter()
+            .map(|family| self.revoke_family(family))
+            .sum()
+    }
+
+    /// Drop expired tokens and empty families
+    pub fn cleanup(&self) {
+        let now = Instant::now();
+        let mut expired = Vec::new();
+        self.tokens.write().unwrap().retain(|hash, stored| {
+            if stored.expires > now && stored.family_expires > now {
+                true
+            } else {
+                expired.push(hash.clone());
+                false
+            }
+        });
+        if !expired.is_empty() {
+            let mut families = self.families.write().unwrap();
+            for hashes in families.values_mut() {
+                hashes.retain(|hash| !expired.contains(hash));
+            }
+            families.retain(|_, hashes| !hashes.is_empty());
+        }
+    }
+
+    /// Number of stored tokens (including rotated replay sentinels)
+    pub fn len(&self) -> usize {
+        self.tokens.read().unwrap().len()
+    }
+
+    /// Check if empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a snapshot of the counters
+    pub fn stats(&self) -> RefreshTokenStats {
+        RefreshTokenStats {
+            issued: self.issued.load(Ordering::Relaxed),
+            rotated: self.rotated.load(Ordering::Relaxed),
+            reuse_detected: self.reuse_detected.load(Ordering::Relaxed),
+            revoked: self.revoked.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Store a fresh token and index it under its family
+    fn insert_token(
+        &self,
+        subject: String,
+        family: String,
+        family_expires: Instant,
+    ) -> IssuedToken {
+        let now = Instant::now();
+        let expires = std::cmp::min(now + self.config.ttl, family_expires);
+        let token = generate_token();
+        let hash = sha256_hex(token.as_bytes());
+
+        self.tokens.write().unwrap().insert(
+            hash.clone(),
+            StoredToken {
+                subject: subject.clone(),
+                family: family.clone(),
+                expires,
+                family_expires,
+                state: TokenState::Active,
+            },
+        );
+        self.families
+            .write()
+            .unwrap()
+            .entry(family.clone())
+            .or_default()
+            .push(hash);
+
+        IssuedToken {
+            token,
+            family,
+            subject,
+            expires_in: expires.saturating_duration_since(now),
+        }
+    }
+
+    /// Remove a token hash from the family index
+    fn unindex(&self, family: &str, hash: &str) {
+        let mut families = self.families.write().unwrap();
+        if let Some(hashes) = families.get_mut(family) {
+            hashes.retain(|existing| existing != hash);
+            if hashes.is_empty() {
+                families.remove(family);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_and_rotate() {
+        let tokens = RefreshTokens::new(RefreshTokenConfig::default());
+
+        let first = tokens.issue("alice");
+        assert_eq!(first.subject, "alice");
+        assert_eq!(tokens.validate(&first.token), Ok("alice".to_string()));
+
+        let second = tokens.rotate(&first.token).unwrap();
+        assert_eq!(second.subject, "alice");
+        assert_eq!(second.family, first.family);
+        assert_ne!(second.token, first.token);
+
+        let stats = tokens.stats();
+        assert_eq!(stats.issued, 1);
+        assert_eq!(stats.rotated, 1);
+    }
+
+    #[test]
+    fn test_reuse_revokes_family() {
+        let tokens = RefreshTokens::new(RefreshTokenConfig::default());
+
+        let first = tokens.issue("alice");
+        let second = tokens.rotate(&first.token).unwrap();
+
+        // Replaying the rotated token burns the whole family
+        assert_eq!(tokens.rotate(&first.token).unwrap_err(), RefreshError::Reused);
+        assert_eq!(tokens.rotate(&second.token).unwrap_err(), RefreshError::Unknown);
+
+        let stats = tokens.stats();
+        assert_eq!(stats.reuse_detected, 1);
+        assert!(stats.revoked >= 2);
+    }
+
+    #[test]
+    fn test_expiry() {
+        let config = RefreshTokenConfig::new().ttl(Duration::from_millis(5));
+        let tokens = RefreshTokens::new(config);
+
+        let issued = tokens.issue("alice");
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(tokens.rotate(&issued.token).unwrap_err(), RefreshError::Expired);
+
+        // Cleanup prunes anything expired
+        tokens.cleanup();
+        assert!(tokens.is_empty());
+    }
+
+    #[test]
+    fn test_revoke_subject() {
+        let tokens = RefreshTokens::new(RefreshTokenConfig::default());
+
+        let alice1 = tokens.issue("alice");
+        let alice2 = tokens.issue("alice");
+        let bob = tokens.issue("bob");
+
+        assert_eq!(tokens.revoke_subject("alice"), 2);
+        assert_eq!(tokens.validate(&alice1.token), Err(RefreshError::Unknown));
+        assert_eq!(tokens.validate(&alice2.token), Err(RefreshError::Unknown));
+        assert_eq!(tokens.validate(&bob.token), Ok("bob".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_token() {
+        let tokens = RefreshTokens::new(RefreshTokenConfig::default());
+        assert_eq!(tokens.rotate("never-issued").unwrap_err(), RefreshError::Unknown);
+        assert!(!tokens.revoke("never-issued"));
+        assert_eq!(tokens.revoke_family("no-such-family"), 0);
+    }
+}
@@ -0,0 +1,359 @@
+//! Replay protection middleware
+//!
+//! Rejects tokens and signed requests that have already been seen
+//! within a window, keyed by the JWT `jti` claim or an explicit nonce
+//! header. Complements the JWT middleware: signature validation proves
+//! who minted a token, the nonce cache proves it has not been presented
+//! twice.
+
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+use super::Middleware;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Replay protection configuration
+#[derive(Clone)]
+pub struct ReplayConfig {
+    /// How long a seen nonce stays rejected (default: 5 minutes);
+    /// should cover the token lifetime or the signature validity window
+    pub window: Duration,
+    /// Read the nonce from this header instead of the Bearer `jti`
+    /// claim (for signed non-JWT requests)
+    pub nonce_header: Option<String>,
+    /// Reject requests that carry no nonce at all (default: false —
+    /// requests without one pass through for other middleware to judge)
+    pub require_nonce: bool,
+    /// Path prefixes to protect; empty protects every path
+    pub path_prefixes: Vec<String>,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(300),
+            nonce_header: None,
+            require_nonce: false,
+            path_prefixes: Vec::new(),
+        }
+    }
+}
+
+impl ReplayConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn nonce_header(mut self, name: impl Into<String>) -> Self {
+        self.nonce_header = Some(name.into());
+        self
+    }
+
+    pub fn require_nonce(mut self) -> Self {
+        self.require_nonce = true;
+        self
+    }
+
+    /// Protect only paths under this prefix; may be called repeatedly
+    pub fn protect_path(mut self, prefix: impl Into<String>) -> Self {
+        self.path_prefixes.push(prefix.into());
+        self
+    }
+}
+
+/// Nonce store trait
+///
+/// A distributed deployment backs this with Redis (`SET key NX PX
+/// window`) so replays are caught across instances; the bundled
+/// memory store covers a single process.
+pub trait NonceStore: Send + Sync {
+    /// Record a nonce if it has not been seen inside the window;
+    /// returns false when the nonce is a replay
+    fn insert_if_absent(&self, nonce: &str, window: Duration) -> bool;
+}
+
+/// In-memory nonce store
+pub struct MemoryNonceStore {
+    seen: RwLock<HashMap<String, Instant>>,
+}
+
+impl MemoryNonceStore {
+    pub fn new() -> Self {
+        Self {
+            seen: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Drop nonces whose window has passed
+    pub fn cleanup(&self) {
+        let now = Instant::now();
+        self.seen.write().unwrap().retain(|_, expires| *expires > now);
+    }
+
+    /// Tracked nonce count (including expired entries not yet cleaned)
+    pub fn len(&self) -> usize {
+        self.seen.read().unwrap().len()
+    }
+
+    /// Check if empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clear all nonces
+    pub fn clear(&self) {
+        self.seen.write().unwrap().clear();
+    }
+}
+
+impl Default for MemoryNonceStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NonceStore for MemoryNonceStore {
+    fn insert_if_absent(&self, nonce: &str, window: Duration) -> bool {
+        let now = Instant::now();
+        let mut seen = self.seen.write().unwrap();
+        if let Some(expires) = seen.get(nonce) {
+            if *expires > now {
+                return false;
+            }
+        }
+        seen.insert(nonce.to_string(), now + window);
+        true
+    }
+}
+
+/// Replay rejection counters
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayStats {
+    /// Nonces checked against the store
+    pub checked: u64,
+    /// Requests rejected as replays
+    pub rejected: u64,
+}
+
+/// Replay protection middleware
+pub struct ReplayGuard<S: NonceStore = MemoryNonceStore> {
+    config: ReplayConfig,
+    store: Arc<S>,
+    checked: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl ReplayGuard<MemoryNonceStore> {
+    pub fn new(config: ReplayConfig) -> Self {
+        Self::with_store(config, Arc::new(MemoryNonceStore::new()))
+    }
+}
+
+impl<S: NonceStore> ReplayGuard<S> {
+    pub fn with_store(config: ReplayConfig, store: Arc<S>) -> Self {
+        Self {
+            config,
+            store,
+            checked: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    /// Snapshot of replay counters
+    pub fn stats(&self) -> ReplayStats {
+        ReplayStats {
+            checked: self.checked.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    fn protects(&self, path: &str) -> bool {
+        self.config.path_prefixes.is_empty()
+            || self
+                .config
+                .path_prefixes
+                .iter()
+                .any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    fn extract_nonce(&self, req: &Request) -> Option<String> {
+        if let Some(header) = &self.config.nonce_header {
+            return req.header(header).map(|s| s.to_string());
+        }
+        let token = req.header("authorization")?.strip_prefix("Bearer ")?;
+        bearer_jti(token)
+    }
+}
+
+impl<S: NonceStore> Middleware for ReplayGuard<S> {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        if !self.protects(&req.path) {
+            return None;
+        }
+
+        let nonce = match self.extract_nonce(req) {
+            Some(nonce) => nonce,
+            None => {
+                if self.config.require_nonce {
+                    return Some(
+                        ResponseBuilder::new(StatusCode::UNAUTHORIZED)
+                            .body(r#"{"error":"Missing nonce"}"#)
+                            .build(),
+                    );
+                }
+                return None;
+            }
+        };
+
+        self.checked.fetch_add(1, Ordering::Relaxed);
+        if self.store.insert_if_absent(&nonce, self.config.window) {
+            None
+        } else {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            Some(
+                ResponseBuilder::new(StatusCode::UNAUTHORIZED)
+                    .body(r#"{"error":"Replayed token"}"#)
+                    .build(),
+            )
+        }
+    }
+
+    fn after(&self, _req: &Request, _res: &mut Response) {}
+}
+
+/// Extract the `jti` claim from an unverified JWT payload
+///
+/// Signature checking is the JWT middleware's job; the replay guard
+/// only needs the nonce, and a forged `jti` still burns itself on
+/// first use.
+fn bearer_jti(token: &str) -> Option<String> {
+    let payload = token.split('.').nth(1)?;
+    let decoded = base64url_decode(payload)?;
+    let json = String::from_utf8(decoded).ok()?;
+    if let Ok(crate::middleware::validate::Value::Object(fields)) = crate::pure::parse_json(&json) {
+        for (key, value) in fields {
+            if key == "jti" {
+                if let crate::middleware::validate::Value::String(jti) = value {
+                    return Some(jti);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn base64url_decode(input: &str) -> Option<Vec<u8>> {
+    let standard: String = input
+        .chars()
+        .map(|c| match c {
+            '-' => '+',
+            '_' => '/',
+            c => c,
+        })
+        .collect();
+    crate::crypto::base64_decode(&standard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::base64url_encode;
+    use crate::{Method, RequestBuilder};
+
+    fn bearer_request(jti: &str) -> Request {
+        let payload = base64url_encode(format!(r#"{{"sub":"user","jti":"{}"}}"#, jti).as_bytes());
+        RequestBuilder::new(Method::Post, "/api/pay")
+            .header("authorization", format!("Bearer h.{}.s", payload))
+            .build()
+    }
+
+    #[test]
+    fn test_memory_store_replay() {
+        let store = MemoryNonceStore::new();
+        assert!(store.insert_if_absent("abc", Duration::from_secs(60)));
+        assert!(!store.insert_if_absent("abc", Duration::from_secs(60)));
+        assert!(store.insert_if_absent("def", Duration::from_secs(60)));
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn test_memory_store_window_expiry() {
+        let store = MemoryNonceStore::new();
+        // Zero window: the nonce expires immediately and may be reused
+        assert!(store.insert_if_absent("abc", Duration::ZERO));
+        assert!(store.insert_if_absent("abc", Duration::ZERO));
+
+        store.cleanup();
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_replayed_jti() {
+        let guard = ReplayGuard::new(ReplayConfig::new());
+
+        let mut req = bearer_request("token-1");
+        assert!(guard.before(&mut req).is_none());
+
+        // Same jti again: rejected and counted
+        let mut req = bearer_request("token-1");
+        let res = guard.before(&mut req).unwrap();
+        assert_eq!(res.status.0, 401);
+
+        // A different jti passes
+        let mut req = bearer_request("token-2");
+        assert!(guard.before(&mut req).is_none());
+
+        let stats = guard.stats();
+        assert_eq!(stats.checked, 3);
+        assert_eq!(stats.rejected, 1);
+    }
+
+    #[test]
+    fn test_nonce_header_mode() {
+        let guard = ReplayGuard::new(
+            ReplayConfig::new()
+                .nonce_header("x-nonce")
+                .require_nonce(),
+        );
+
+        let mut req = RequestBuilder::new(Method::Post, "/webhook")
+            .header("x-nonce", "n-1")
+            .build();
+        assert!(guard.before(&mut req).is_none());
+
+        let mut req = RequestBuilder::new(Method::Post, "/webhook")
+            .header("x-nonce", "n-1")
+            .build();
+        assert_eq!(guard.before(&mut req).unwrap().status.0, 401);
+
+        // require_nonce: a request without one is rejected too
+        let mut req = RequestBuilder::new(Method::Post, "/webhook").build();
+        assert_eq!(guard.before(&mut req).unwrap().status.0, 401);
+    }
+
+    #[test]
+    fn test_path_scoping() {
+        let guard = ReplayGuard::new(ReplayConfig::new().protect_path("/api/pay"));
+
+        // Outside the protected prefix: the same jti is never checked
+        let payload = base64url_encode(br#"{"jti":"t"}"#);
+        let mut req = RequestBuilder::new(Method::Get, "/health")
+            .header("authorization", format!("Bearer h.{}.s", payload))
+            .build();
+        assert!(guard.before(&mut req).is_none());
+        assert!(guard.before(&mut req).is_none());
+        assert_eq!(guard.stats().checked, 0);
+
+        // Inside it, replay protection applies
+        let mut req = bearer_request("t");
+        assert!(guard.before(&mut req).is_none());
+        let mut req = bearer_request("t");
+        assert!(guard.before(&mut req).is_some());
+    }
+}
@@ -0,0 +1,325 @@
+//! Per-route response budget enforcement
+//!
+//! Bounds the size and shape of responses (body bytes, header count)
+//! per route, protecting mobile clients from accidental megabyte
+//! payloads. Violations are counted and, depending on the route's
+//! policy, either logged, truncated with a warning header, or failed
+//! with a 500 so the regression is caught instead of shipped.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::Middleware;
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+
+/// What to do with a response that exceeds its budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetPolicy {
+    /// Count the violation, leave the response untouched
+    Log,
+    /// Cut the body down to the byte budget and flag it with an
+    /// `X-Budget-Truncated` header carrying the original size
+    Truncate,
+    /// Replace the response with a 500 error
+    Fail,
+}
+
+/// Budget for one route (or the default)
+#[derive(Clone)]
+pub struct RouteBudget {
+    /// Maximum body bytes; 0 = unlimited
+    pub max_bytes: usize,
+    /// Maximum header count; 0 = unlimited
+    pub max_headers: usize,
+    /// Violation policy
+    pub policy: BudgetPolicy,
+}
+
+impl Default for RouteBudget {
+    fn default() -> Self {
+        Self {
+            max_bytes: 0,
+            max_headers: 0,
+            policy: BudgetPolicy::Log,
+        }
+    }
+}
+
+impl RouteBudget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_bytes(mut self, bytes: usize) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    pub fn max_headers(mut self, count: usize) -> Self {
+        self.max_headers = count;
+        self
+    }
+
+    pub fn policy(mut self, policy: BudgetPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+/// Response budget configuration
+///
+/// Route patterns are exact paths or trailing-`*` prefixes (the same
+/// shape the cache purge API uses); the first matching route wins,
+/// falling back to the default budget.
+#[derive(Clone, Default)]
+pub struct ResponseBudgetConfig {
+    /// Budget applied when no route pattern matches
+    pub default_budget: Option<RouteBudget>,
+    /// (pattern, budget) pairs checked in order
+    pub routes: Vec<(String, RouteBudget)>,
+}
+
+impl ResponseBudgetConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn default_budget(mut self, budget: RouteBudget) -> Self {
+        self.default_budget = Some(budget);
+        self
+    }
+
+    pub fn route(mut self, pattern: impl Into<String>, budget: RouteBudget) -> Self {
+        self.routes.push((pattern.into(), budget));
+        self
+    }
+}
+
+/// Snapshot of response budget counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResponseBudgetStats {
+    /// Responses that exceeded their budget
+    pub violations: u64,
+    /// Responses truncated to fit
+    pub truncated: u64,
+    /// Responses replaced with a 500
+    pub failed: u64,
+}
+
+/// Response budget middleware
+pub struct ResponseBudget {
+    config: ResponseBudgetConfig,
+    violations: AtomicU64,
+    truncated: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl ResponseBudget {
+    pub fn new(config: ResponseBudgetConfig) -> Self {
+        Self {
+            config,
+            violations: AtomicU64::new(0),
+            truncated: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+        }
+    }
+
+    /// Snapshot the violation counters
+    pub fn stats(&self) -> ResponseBudgetStats {
+        ResponseBudgetStats {
+            violations: self.violations.load(Ordering::Relaxed),
+            truncated: self.truncated.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+        }
+    }
+
+    fn budget_for(&self, path: &str) -> Option<&RouteBudget> {
+        self.config
+            .routes
+            .iter()
+            .find(|(pattern, _)| pattern_matches(pattern, path))
+            .map(|(_, budget)| budget)
+            .or(self.config.default_budget.as_ref())
+    }
+}
+
+impl Middleware for ResponseBudget {
+    fn before(&self, _req: &mut Request) -> Option<Response> {
+        None
+    }
+
+    fn after(&self, req: &Request, res: &mut Response) {
+        let budget = match self.budget_for(&req.path) {
+            Some(b) => b,
+            None => return,
+        };
+
+        let body_len = res.body.len();
+        let over_bytes = budget.max_bytes > 0 && body_len > budget.max_bytes;
+        let over_headers = budget.max_headers > 0 && res.headers.len() > budget.max_headers;
+        if !over_bytes && !over_headers {
+            return;
+        }
+        self.violations.fetch_add(1, Ordering::Relaxed);
+
+        match budget.policy {
+            BudgetPolicy::Log => {}
+            BudgetPolicy::Truncate => {
+                // Header-count violations are only flagged; dropping
+                // arbitrary headers would break more than it protects
+                if over_bytes {
+                    res.body = res.body.slice(..budget.max_bytes);
+                    res.headers
+                        .retain(|(k, _)| !k.eq_ignore_ascii_case("content-length"));
+                    res.headers.push((
+                        "Content-Length".to_string(),
+                        res.body.len().to_string(),
+                    ));
+                }
+                res.headers.push((
+                    "X-Budget-Truncated".to_string(),
+                    body_len.to_string(),
+                ));
+                self.truncated.fetch_add(1, Ordering::Relaxed);
+            }
+            BudgetPolicy::Fail => {
+                *res = ResponseBuilder::new(StatusCode::INTERNAL_SERVER_ERROR)
+                    .header("Content-Type", "application/json")
+                    .body(format!(
+                        r#"{{"error":"Response exceeds budget","max_bytes":{},"actual_bytes":{},"max_headers":{},"actual_headers":{}}}"#,
+                        budget.max_bytes,
+                        body_len,
+                        budget.max_headers,
+                        res.headers.len()
+                    ))
+                    .build();
+                self.failed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Match a route pattern (exact path or trailing-`*` prefix)
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => path.starts_with(prefix),
+        None => path == pattern,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, RequestBuilder};
+
+    fn response_with_body(size: usize) -> Response {
+        ResponseBuilder::new(StatusCode::OK)
+            .header("Content-Type", "application/json")
+            .header("Content-Length", size.to_string())
+            .body(vec![b'x'; size])
+            .build()
+    }
+
+    #[test]
+    fn test_log_policy_counts_only() {
+        let config = ResponseBudgetConfig::new()
+            .default_budget(RouteBudget::new().max_bytes(100));
+        let budget = ResponseBudget::new(config);
+
+        let req = RequestBuilder::new(Method::Get, "/api/items").build();
+        let mut res = response_with_body(500);
+        budget.after(&req, &mut res);
+
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.body.len(), 500);
+        assert_eq!(budget.stats().violations, 1);
+        assert_eq!(budget.stats().truncated, 0);
+    }
+
+    #[test]
+    fn test_truncate_policy_cuts_body_and_flags() {
+        let config = ResponseBudgetConfig::new().default_budget(
+            RouteBudget::new()
+                .max_bytes(100)
+                .policy(BudgetPolicy::Truncate),
+        );
+        let budget = ResponseBudget::new(config);
+
+        let req = RequestBuilder::new(Method::Get, "/api/items").build();
+        let mut res = response_with_body(500);
+        budget.after(&req, &mut res);
+
+        assert_eq!(res.body.len(), 100);
+        assert_eq!(res.header("Content-Length"), Some("100"));
+        assert_eq!(res.header("X-Budget-Truncated"), Some("500"));
+        assert_eq!(budget.stats().truncated, 1);
+
+        // Within budget: untouched
+        let mut res = response_with_body(50);
+        budget.after(&req, &mut res);
+        assert_eq!(res.header("X-Budget-Truncated"), None);
+    }
+
+    #[test]
+    fn test_fail_policy_returns_500() {
+        let config = ResponseBudgetConfig::new().default_budget(
+            RouteBudget::new().max_bytes(100).policy(BudgetPolicy::Fail),
+        );
+        let budget = ResponseBudget::new(config);
+
+        let req = RequestBuilder::new(Method::Get, "/api/items").build();
+        let mut res = response_with_body(500);
+        budget.after(&req, &mut res);
+
+        assert_eq!(res.status, StatusCode::INTERNAL_SERVER_ERROR);
+        let body = String::from_utf8(res.body.to_vec()).unwrap();
+        assert!(body.contains("\"actual_bytes\":500"));
+        assert_eq!(budget.stats().failed, 1);
+    }
+
+    #[test]
+    fn test_header_count_budget() {
+        let config = ResponseBudgetConfig::new().default_budget(
+            RouteBudget::new()
+                .max_headers(2)
+                .policy(BudgetPolicy::Truncate),
+        );
+        let budget = ResponseBudget::new(config);
+
+        let req = RequestBuilder::new(Method::Get, "/api/items").build();
+        let mut res = ResponseBuilder::new(StatusCode::OK)
+            .header("a", "1")
+            .header("b", "2")
+            .header("c", "3")
+            .body("ok")
+            .build();
+        budget.after(&req, &mut res);
+
+        // Headers are kept, the violation is flagged and counted
+        assert!(res.header("c").is_some());
+        assert_eq!(res.header("X-Budget-Truncated"), Some("2"));
+        assert_eq!(budget.stats().violations, 1);
+    }
+
+    #[test]
+    fn test_route_patterns_override_default() {
+        let config = ResponseBudgetConfig::new()
+            .route(
+                "/api/mobile/*",
+                RouteBudget::new().max_bytes(100).policy(BudgetPolicy::Fail),
+            )
+            .default_budget(RouteBudget::new().max_bytes(1000).policy(BudgetPolicy::Fail));
+        let budget = ResponseBudget::new(config);
+
+        // Mobile route gets the tight budget
+        let req = RequestBuilder::new(Method::Get, "/api/mobile/feed").build();
+        let mut res = response_with_body(500);
+        budget.after(&req, &mut res);
+        assert_eq!(res.status, StatusCode::INTERNAL_SERVER_ERROR);
+
+        // Other routes fall back to the default
+        let req = RequestBuilder::new(Method::Get, "/api/items").build();
+        let mut res = response_with_body(500);
+        budget.after(&req, &mut res);
+        assert_eq!(res.status, StatusCode::OK);
+    }
+}
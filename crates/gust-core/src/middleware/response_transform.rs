@@ -0,0 +1,367 @@
+//! Response transformation and HTML injection
+//!
+//! Injects snippets into HTML responses (the classic `</head>`
+//! analytics tag), rewrites absolute URLs for reverse-proxied apps,
+//! and post-processes response headers. Buffered responses are
+//! transformed in [`super::Middleware::after`]; streaming paths can
+//! drive the same injection through [`StreamingInjector`], which
+//! carries at most `marker_len - 1` bytes across chunk boundaries so
+//! memory stays bounded no matter the body size.
+
+use crate::{Request, Response};
+use bytes::Bytes;
+
+/// Find the first occurrence of `needle` in `haystack`
+fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return None;
+    }
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Response transformation rules
+#[derive(Debug, Clone)]
+pub struct ResponseTransformConfig {
+    /// Snippets inserted before the first occurrence of a marker
+    injections: Vec<(String, String)>,
+    /// Absolute URL rewrites (from -> to), applied to every occurrence
+    url_rewrites: Vec<(String, String)>,
+    /// Response headers to set, replacing existing values
+    set_headers: Vec<(String, String)>,
+    /// Response headers to drop
+    remove_headers: Vec<String>,
+    /// Content-type prefixes whose bodies are transformed
+    /// (default: text/html)
+    content_types: Vec<String>,
+}
+
+impl Default for ResponseTransformConfig {
+    fn default() -> Self {
+        Self {
+            injections: Vec::new(),
+            url_rewrites: Vec::new(),
+            set_headers: Vec::new(),
+            remove_headers: Vec::new(),
+            content_types: vec!["text/html".to_string()],
+        }
+    }
+}
+
+impl ResponseTransformConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert `snippet` before the first occurrence of `marker`
+    /// (e.g. an analytics tag before `</head>`)
+    pub fn inject_before(mut self, marker: impl Into<String>, snippet: impl Into<String>) -> Self {
+        self.injections.push((marker.into(), snippet.into()));
+        self
+    }
+
+    /// Rewrite every occurrence of an absolute URL, for apps that
+    /// render upstream origins behind a reverse proxy
+    pub fn rewrite_url(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.url_rewrites.push((from.into(), to.into()));
+        self
+    }
+
+    /// Set a response header, replacing existing values
+    pub fn set_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Drop a response header
+    pub fn remove_header(mut self, name: impl Into<String>) -> Self {
+        self.remove_headers.push(name.into());
+        self
+    }
+
+    /// Replace the content-type prefixes whose bodies are transformed
+    pub fn content_type(mut self, prefix: impl Into<String>) -> Self {
+        self.content_types = vec![prefix.into()];
+        self
+    }
+}
+
+/// Response transform middleware
+pub struct ResponseTransform {
+    config: ResponseTransformConfig,
+}
+
+impl ResponseTransform {
+    pub fn new(config: ResponseTransformConfig) -> Self {
+        Self { config }
+    }
+
+    fn transforms_body(&self, res: &Response) -> bool {
+        // Compressed bodies cannot be rewritten in place; run the
+        // transform ahead of compression in the chain instead
+        let encoded = res.headers.iter().any(|(name, value)| {
+            name.eq_ignore_ascii_case("content-encoding") && !value.eq_ignore_ascii_case("identity")
+        });
+        if encoded {
+            return false;
+        }
+        res.headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| {
+                self.config
+                    .content_types
+                    .iter()
+                    .any(|prefix| value.starts_with(prefix.as_str()))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Apply body rules to a full buffer
+    fn transform_body(&self, body: &[u8]) -> Option<Vec<u8>> {
+        let mut out: Option<Vec<u8>> = None;
+        for (marker, snippet) in &self.config.injections {
+            let current = out.as_deref().unwrap_or(body);
+            if let Some(at) = find_subsequence(current, marker.as_bytes()) {
+                let mut next = Vec::with_capacity(current.len() + snippet.len());
+                next.extend_from_slice(&current[..at]);
+                next.extend_from_slice(snippet.as_bytes());
+                next.extend_from_slice(&current[at..]);
+                out = Some(next);
+            }
+        }
+        for (from, to) in &self.config.url_rewrites {
+            let mut current = out.take().unwrap_or_else(|| body.to_vec());
+            let mut at = 0;
+            while let Some(found) = find_subsequence(&current[at..], from.as_bytes()) {
+                let found = at + found;
+                current.splice(found..found + from.len(), to.bytes());
+                at = found + to.len();
+            }
+            out = Some(current);
+        }
+        out
+    }
+}
+
+impl super::Middleware for ResponseTransform {
+    fn before(&self, _req: &mut Request) -> Option<Response> {
+        None
+    }
+
+    fn after(&self, _req: &Request, res: &mut Response) {
+        if self.transforms_body(res) {
+            if let Some(body) = self.transform_body(&res.body) {
+                res.body = Bytes::from(body);
+                for (name, value) in res.headers.iter_mut() {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        *value = res.body.len().to_string();
+                    }
+                }
+            }
+        }
+
+        for name in &self.config.remove_headers {
+            res.headers
+                .retain(|(header, _)| !header.eq_ignore_ascii_case(name));
+        }
+        for (name, value) in &self.config.set_headers {
+            res.headers
+                .retain(|(header, _)| !header.eq_ignore_ascii_case(name));
+            res.headers.push((name.clone(), value.clone()));
+        }
+    }
+}
+
+/// Chunk-by-chunk snippet injection with bounded memory
+///
+/// Feed body chunks through [`push`](Self::push) and flush with
+/// [`finish`](Self::finish); the snippet lands before the first
+/// occurrence of the marker even when the marker spans a chunk
+/// boundary. Only a partial-marker tail is ever buffered, so a
+/// multi-gigabyte body streams at a fixed memory cost.
+pub struct StreamingInjector {
+    marker: Vec<u8>,
+    snippet: Vec<u8>,
+    /// Trailing bytes of the last chunk that may start the marker
+    carry: Vec<u8>,
+    injected: bool,
+}
+
+impl StreamingInjector {
+    pub fn new(marker: impl Into<String>, snippet: impl Into<String>) -> Self {
+        Self {
+            marker: marker.into().into_bytes(),
+            snippet: snippet.into().into_bytes(),
+            carry: Vec::new(),
+            injected: false,
+        }
+    }
+
+    /// Process one chunk, returning the bytes safe to forward
+    pub fn push(&mut self, chunk: &[u8]) -> Bytes {
+        if self.injected || self.marker.is_empty() {
+            return Bytes::copy_from_slice(chunk);
+        }
+
+        let mut buffer = std::mem::take(&mut self.carry);
+        buffer.extend_from_slice(chunk);
+
+        if let Some(at) = find_subsequence(&buffer, &self.marker) {
+            self.injected = true;
+            let mut out = Vec::with_capacity(buffer.len() + self.snippet.len());
+            out.extend_from_slice(&buffer[..at]);
+            out.extend_from_slice(&self.snippet);
+            out.extend_from_slice(&buffer[at..]);
+            return Bytes::from(out);
+        }
+
+        // Hold back any tail that could be the start of the marker
+        let hold = longest_marker_prefix(&buffer, &self.marker);
+        let emit = buffer.len() - hold;
+        self.carry = buffer.split_off(emit);
+        Bytes::from(buffer)
+    }
+
+    /// Flush any held-back bytes once the body ends
+    pub fn finish(&mut self) -> Bytes {
+        Bytes::from(std::mem::take(&mut self.carry))
+    }
+
+    /// Whether the snippet has been injected
+    pub fn injected(&self) -> bool {
+        self.injected
+    }
+}
+
+/// Length of the longest marker prefix that ends `buffer`
+fn longest_marker_prefix(buffer: &[u8], marker: &[u8]) -> usize {
+    let max = (marker.len() - 1).min(buffer.len());
+    (1..=max)
+        .rev()
+        .find(|&len| buffer[buffer.len() - len..] == marker[..len])
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::Middleware;
+    use crate::{Method, RequestBuilder, ResponseBuilder, StatusCode};
+
+    fn html_response(body: &str) -> Response {
+        ResponseBuilder::new(StatusCode(200))
+            .header("content-type", "text/html; charset=utf-8")
+            .header("content-length", body.len().to_string())
+            .body(body.to_string())
+            .build()
+    }
+
+    #[test]
+    fn test_head_injection_and_content_length() {
+        let transform = ResponseTransform::new(
+            ResponseTransformConfig::new()
+                .inject_before("</head>", "<script src=\"/a.js\"></script>"),
+        );
+
+        let req = RequestBuilder::new(Method::Get, "/").build();
+        let mut res = html_response("<html><head></head><body></body></html>");
+        transform.after(&req, &mut res);
+
+        let body = String::from_utf8(res.body.to_vec()).unwrap();
+        assert_eq!(
+            body,
+            "<html><head><script src=\"/a.js\"></script></head><body></body></html>"
+        );
+        assert!(res
+            .headers
+            .iter()
+            .any(|(name, value)| name == "content-length"
+                && value == &res.body.len().to_string()));
+    }
+
+    #[test]
+    fn test_url_rewrite_and_headers() {
+        let transform = ResponseTransform::new(
+            ResponseTransformConfig::new()
+                .rewrite_url("http://upstream:8080", "https://example.com")
+                .remove_header("server")
+                .set_header("x-transformed", "1"),
+        );
+
+        let req = RequestBuilder::new(Method::Get, "/").build();
+        let mut res = html_response(
+            "<a href=\"http://upstream:8080/a\">x</a><img src=\"http://upstream:8080/b.png\">",
+        );
+        res.headers.push(("server".to_string(), "upstream".to_string()));
+        transform.after(&req, &mut res);
+
+        let body = String::from_utf8(res.body.to_vec()).unwrap();
+        assert_eq!(
+            body,
+            "<a href=\"https://example.com/a\">x</a><img src=\"https://example.com/b.png\">"
+        );
+        assert!(!res.headers.iter().any(|(name, _)| name == "server"));
+        assert!(res
+            .headers
+            .iter()
+            .any(|(name, value)| name == "x-transformed" && value == "1"));
+    }
+
+    #[test]
+    fn test_skips_compressed_and_non_html_bodies() {
+        let transform = ResponseTransform::new(
+            ResponseTransformConfig::new().inject_before("</head>", "<!-- x -->"),
+        );
+        let req = RequestBuilder::new(Method::Get, "/").build();
+
+        let mut res = html_response("<head></head>");
+        res.headers
+            .push(("content-encoding".to_string(), "gzip".to_string()));
+        let original = res.body.clone();
+        transform.after(&req, &mut res);
+        assert_eq!(res.body, original);
+
+        let mut res = ResponseBuilder::new(StatusCode(200))
+            .header("content-type", "application/json")
+            .body("{\"head\":\"</head>\"}")
+            .build();
+        let original = res.body.clone();
+        transform.after(&req, &mut res);
+        assert_eq!(res.body, original);
+    }
+
+    #[test]
+    fn test_streaming_injection_across_chunk_boundary() {
+        let mut injector = StreamingInjector::new("</head>", "<!-- inject -->");
+
+        // The marker is split mid-way between two chunks
+        let mut out = Vec::new();
+        out.extend_from_slice(&injector.push(b"<html><head><title>t</title></he"));
+        out.extend_from_slice(&injector.push(b"ad><body>"));
+        out.extend_from_slice(&injector.push(b"</body></html>"));
+        out.extend_from_slice(&injector.finish());
+
+        assert!(injector.injected());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "<html><head><title>t</title><!-- inject --></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn test_streaming_injector_bounds_carry() {
+        let mut injector = StreamingInjector::new("</head>", "x");
+
+        // No marker anywhere: everything must flow through with at
+        // most marker_len - 1 bytes held back
+        let chunk = b"aaaaaaaaaaaaaaaaaaaaaaaa<";
+        let emitted = injector.push(chunk);
+        assert_eq!(emitted.len(), chunk.len() - 1);
+        let emitted = injector.push(b"div>");
+        assert_eq!(&emitted[..], b"<div>");
+        assert_eq!(injector.finish().len(), 0);
+        assert!(!injector.injected());
+    }
+}
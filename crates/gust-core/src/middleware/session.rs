@@ -11,31 +11,6 @@ use std::time::{Duration, Instant};
 // Cryptography (zero-dependency implementations)
 // ============================================================================
 
-/// Generate random bytes
-fn generate_random_bytes(len: usize) -> Vec<u8> {
-    // Simple PRNG based on system time for session IDs
-    // In production, this should use a proper CSPRNG
-    use std::time::SystemTime;
-
-    let seed = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos() as u64;
-
-    let mut state = seed;
-    let mut result = Vec::with_capacity(len);
-
-    for _ in 0..len {
-        // xorshift64
-        state ^= state << 13;
-        state ^= state >> 7;
-        state ^= state << 17;
-        result.push((state & 0xFF) as u8);
-    }
-
-    result
-}
-
 /// HMAC-SHA256 implementation
 fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
     const BLOCK_SIZE: usize = 64;
@@ -185,6 +160,12 @@ fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
 /// Session data type alias
 pub type SessionData = HashMap<String, SessionValue>;
 
+/// Reserved session data key that binds a session to a principal (user)
+///
+/// Stores indexing sessions by principal read this key from the saved
+/// data, so a session joins the index as soon as it is persisted.
+pub const PRINCIPAL_KEY: &str = "_principal";
+
 /// Session value types
 #[derive(Debug, Clone, PartialEq)]
 pub enum SessionValue {
@@ -367,11 +348,27 @@ pub trait SessionStore: Send + Sync {
     fn destroy(&self, id: &str);
     /// Touch session (update expiry)
     fn touch(&self, id: &str, max_age: Duration);
+
+    /// Session IDs bound to a principal, oldest first
+    ///
+    /// Stores without a principal index return an empty list.
+    fn sessions_for_principal(&self, _principal: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Destroy every session bound to a principal, returning the count
+    fn revoke_principal(&self, _principal: &str) -> usize {
+        0
+    }
 }
 
 /// In-memory session store (not for production)
 pub struct MemoryStore {
     sessions: RwLock<HashMap<String, StoredSession>>,
+    /// Session IDs per principal, oldest first
+    principals: RwLock<HashMap<String, Vec<String>>>,
+    /// Max concurrent sessions per principal (0 = unlimited)
+    max_per_principal: usize,
 }
 
 struct StoredSession {
@@ -383,12 +380,26 @@ impl MemoryStore {
     pub fn new() -> Self {
         Self {
             sessions: RwLock::new(HashMap::new()),
+            principals: RwLock::new(HashMap::new()),
+            max_per_principal: 0,
+        }
+    }
+
+    /// Limit each principal to `max` concurrent sessions
+    ///
+    /// When a new session for a principal exceeds the limit, the
+    /// oldest session for that principal is evicted. `0` is unlimited.
+    pub fn with_max_per_principal(max: usize) -> Self {
+        Self {
+            max_per_principal: max,
+            ..Self::new()
         }
     }
 
     /// Clear all sessions
     pub fn clear(&self) {
         self.sessions.write().unwrap().clear();
+        self.principals.write().unwrap().clear();
     }
 
     /// Get session count
@@ -404,12 +415,41 @@ impl MemoryStore {
     /// Cleanup expired sessions
     pub fn cleanup(&self) {
         let now = Instant::now();
-        self.sessions.write().unwrap().retain(|_, session| {
-            session.expires > now
+        let mut expired = Vec::new();
+        self.sessions.write().unwrap().retain(|id, session| {
+            if session.expires > now {
+                true
+            } else {
+                expired.push(id.clone());
+                false
+            }
         });
+        if !expired.is_empty() {
+            let mut principals = self.principals.write().unwrap();
+            for ids in principals.values_mut() {
+                ids.retain(|id| !expired.contains(id));
+            }
+            principals.retain(|_, ids| !ids.is_empty());
+        }
+    }
+
+    /// Remove a session ID from the principal index
+    fn unindex(&self, principal: &str, id: &str) {
+        let mut principals = self.principals.write().unwrap();
+        if let Some(ids) = principals.get_mut(principal) {
+            ids.retain(|existing| existing != id);
+            if ids.is_empty() {
+                principals.remove(principal);
+            }
+        }
     }
 }
 
+/// Principal bound to session data, if any
+fn data_principal(data: &SessionData) -> Option<&str> {
+    data.get(PRINCIPAL_KEY).and_then(|v| v.as_str())
+}
+
 impl Default for MemoryStore {
     fn default() -> Self {
         Self::new()
@@ -429,15 +469,50 @@ impl SessionStore for MemoryStore {
     }
 
     fn set(&self, id: &str, data: SessionData, max_age: Duration) {
-        let mut sessions = self.sessions.write().unwrap();
-        sessions.insert(id.to_string(), StoredSession {
-            data,
-            expires: Instant::now() + max_age,
-        });
+        let principal = data_principal(&data).map(str::to_string);
+        let previous = {
+            let mut sessions = self.sessions.write().unwrap();
+            sessions.insert(id.to_string(), StoredSession {
+                data,
+                expires: Instant::now() + max_age,
+            })
+        };
+
+        // Re-binding a session to a different principal drops it from
+        // the old index entry
+        let old_principal = previous.as_ref().and_then(|s| data_principal(&s.data));
+        if let Some(old) = old_principal {
+            if principal.as_deref() != Some(old) {
+                self.unindex(old, id);
+            }
+        }
+
+        let Some(principal) = principal else { return };
+        let evicted = {
+            let mut principals = self.principals.write().unwrap();
+            let ids = principals.entry(principal).or_default();
+            if !ids.iter().any(|existing| existing == id) {
+                ids.push(id.to_string());
+            }
+            if self.max_per_principal > 0 && ids.len() > self.max_per_principal {
+                ids.drain(..ids.len() - self.max_per_principal).collect()
+            } else {
+                Vec::new()
+            }
+        };
+        if !evicted.is_empty() {
+            let mut sessions = self.sessions.write().unwrap();
+            for old_id in &evicted {
+                sessions.remove(old_id);
+            }
+        }
     }
 
     fn destroy(&self, id: &str) {
-        self.sessions.write().unwrap().remove(id);
+        let removed = self.sessions.write().unwrap().remove(id);
+        if let Some(principal) = removed.as_ref().and_then(|s| data_principal(&s.data)) {
+            self.unindex(principal, id);
+        }
     }
 
     fn touch(&self, id: &str, max_age: Duration) {
@@ -446,11 +521,29 @@ impl SessionStore for MemoryStore {
             session.expires = Instant::now() + max_age;
         }
     }
+
+    fn sessions_for_principal(&self, principal: &str) -> Vec<String> {
+        self.principals
+            .read()
+            .unwrap()
+            .get(principal)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn revoke_principal(&self, principal: &str) -> usize {
+        let ids = match self.principals.write().unwrap().remove(principal) {
+            Some(ids) => ids,
+            None => return 0,
+        };
+        let mut sessions = self.sessions.write().unwrap();
+        ids.iter().filter(|id| sessions.remove(*id).is_some()).count()
+    }
 }
 
 /// Generate a secure session ID
 pub fn generate_session_id() -> String {
-    let bytes = generate_random_bytes(24);
+    let bytes = crate::crypto::random_bytes(24);
     base64_url_encode(&bytes)
 }
 
@@ -547,6 +640,19 @@ impl Session {
         self.data.remove(key)
     }
 
+    /// Bind this session to a principal (e.g. after login)
+    ///
+    /// Stores under [`PRINCIPAL_KEY`] so principal-indexing stores can
+    /// enforce concurrent session limits and bulk revocation.
+    pub fn set_principal(&mut self, principal: impl Into<String>) {
+        self.set(PRINCIPAL_KEY, principal.into());
+    }
+
+    /// Principal this session is bound to, if any
+    pub fn principal(&self) -> Option<&str> {
+        self.data.get(PRINCIPAL_KEY).and_then(|v| v.as_str())
+    }
+
     /// Check if modified
     pub fn is_modified(&self) -> bool {
         self.modified
@@ -687,6 +793,72 @@ mod tests {
         assert!(store.get(id).is_none());
     }
 
+    #[test]
+    fn test_principal_index_and_limit() {
+        let store = MemoryStore::with_max_per_principal(2);
+
+        let mut data = SessionData::new();
+        data.insert(PRINCIPAL_KEY.to_string(), SessionValue::from("alice"));
+
+        store.set("s1", data.clone(), Duration::from_secs(3600));
+        store.set("s2", data.clone(), Duration::from_secs(3600));
+        assert_eq!(store.sessions_for_principal("alice"), vec!["s1", "s2"]);
+
+        // Third session evicts the oldest
+        store.set("s3", data.clone(), Duration::from_secs(3600));
+        assert_eq!(store.sessions_for_principal("alice"), vec!["s2", "s3"]);
+        assert!(store.get("s1").is_none());
+        assert!(store.get("s3").is_some());
+
+        // Re-saving an indexed session does not evict anything
+        store.set("s2", data, Duration::from_secs(3600));
+        assert_eq!(store.sessions_for_principal("alice"), vec!["s2", "s3"]);
+    }
+
+    #[test]
+    fn test_revoke_principal() {
+        let store = MemoryStore::new();
+
+        let mut alice = SessionData::new();
+        alice.insert(PRINCIPAL_KEY.to_string(), SessionValue::from("alice"));
+        let mut bob = SessionData::new();
+        bob.insert(PRINCIPAL_KEY.to_string(), SessionValue::from("bob"));
+
+        store.set("a1", alice.clone(), Duration::from_secs(3600));
+        store.set("a2", alice, Duration::from_secs(3600));
+        store.set("b1", bob, Duration::from_secs(3600));
+
+        assert_eq!(store.revoke_principal("alice"), 2);
+        assert!(store.get("a1").is_none());
+        assert!(store.get("a2").is_none());
+        assert!(store.get("b1").is_some());
+        assert!(store.sessions_for_principal("alice").is_empty());
+
+        assert_eq!(store.revoke_principal("nobody"), 0);
+    }
+
+    #[test]
+    fn test_destroy_unbinds_principal() {
+        let store = MemoryStore::new();
+
+        let mut data = SessionData::new();
+        data.insert(PRINCIPAL_KEY.to_string(), SessionValue::from("alice"));
+        store.set("s1", data, Duration::from_secs(3600));
+
+        store.destroy("s1");
+        assert!(store.sessions_for_principal("alice").is_empty());
+    }
+
+    #[test]
+    fn test_session_principal_helpers() {
+        let mut session = Session::new("test".to_string(), SessionData::new(), true);
+        assert_eq!(session.principal(), None);
+
+        session.set_principal("alice");
+        assert_eq!(session.principal(), Some("alice"));
+        assert!(session.is_modified());
+    }
+
     #[test]
     fn test_session_operations() {
         let mut session = Session::new("test".to_string(), SessionData::new(), true);
@@ -0,0 +1,472 @@
+//! Shared-dictionary compression for repetitive API responses
+//!
+//! Repetitive JSON payloads share most of their bytes between
+//! responses, and brotli compresses them dramatically better against a
+//! custom dictionary built from representative samples. [`SharedDict`]
+//! holds such a dictionary, optionally serves it to clients, and
+//! compresses responses for requests that advertise the dictionary via
+//! a negotiation header (`X-Dictionary` by default). Compression-ratio
+//! counters are exposed through [`stats`](SharedDict::stats).
+
+use crate::{Request, Response, ResponseBuilder, StatusCode};
+use super::compress::{accepts_encoding, CompressionLevel, Encoding};
+use super::Middleware;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Shared-dictionary compression configuration
+#[derive(Clone)]
+pub struct SharedDictConfig {
+    /// Negotiation header carrying the dictionary id
+    pub header: String,
+    /// Minimum body size to compress
+    pub min_size: usize,
+    /// Brotli compression level
+    pub level: CompressionLevel,
+    /// Content type prefixes eligible for dictionary compression
+    pub content_types: Vec<String>,
+    /// Serve the dictionary itself at this path (e.g. `/_gust/dictionary`)
+    pub serve_path: Option<String>,
+}
+
+impl Default for SharedDictConfig {
+    fn default() -> Self {
+        Self {
+            header: "x-dictionary".to_string(),
+            min_size: 256,
+            level: CompressionLevel::Default,
+            content_types: vec!["application/json".to_string()],
+            serve_path: None,
+        }
+    }
+}
+
+impl SharedDictConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn header(mut self, name: impl Into<String>) -> Self {
+        self.header = name.into().to_lowercase();
+        self
+    }
+
+    pub fn min_size(mut self, bytes: usize) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    pub fn level(mut self, level: CompressionLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn content_type(mut self, prefix: impl Into<String>) -> Self {
+        self.content_types.push(prefix.into());
+        self
+    }
+
+    pub fn serve_path(mut self, path: impl Into<String>) -> Self {
+        self.serve_path = Some(path.into());
+        self
+    }
+}
+
+/// Snapshot of shared-dictionary compression counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SharedDictStats {
+    /// Responses compressed against the dictionary
+    pub compressed_responses: u64,
+    /// Body bytes before compression
+    pub raw_bytes: u64,
+    /// Body bytes after compression
+    pub compressed_bytes: u64,
+}
+
+impl SharedDictStats {
+    /// Compression ratio in [0, 1]; 0 when nothing was compressed
+    pub fn ratio(&self) -> f64 {
+        if self.raw_bytes == 0 {
+            0.0
+        } else {
+            1.0 - self.compressed_bytes as f64 / self.raw_bytes as f64
+        }
+    }
+}
+
+/// Shared-dictionary compression middleware
+///
+/// Requests opt in by sending the negotiation header with the
+/// dictionary id (obtained from the serve path or out of band); the
+/// response echoes the id alongside `Content-Encoding: br` so the
+/// client knows to decompress against the dictionary.
+pub struct SharedDict {
+    config: SharedDictConfig,
+    dictionary: Vec<u8>,
+    id: String,
+    compressed_responses: AtomicU64,
+    raw_bytes: AtomicU64,
+    compressed_bytes: AtomicU64,
+}
+
+impl SharedDict {
+    /// Use an already-trained dictionary
+    pub fn new(config: SharedDictConfig, dictionary: Vec<u8>) -> Self {
+        let id = dictionary_id(&dictionary);
+        Self {
+            config,
+            dictionary,
+            id,
+            compressed_responses: AtomicU64::new(0),
+            raw_bytes: AtomicU64::new(0),
+            compressed_bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Train a dictionary from representative response bodies
+    pub fn trained<'a>(
+        config: SharedDictConfig,
+        samples: impl IntoIterator<Item = &'a [u8]>,
+        max_bytes: usize,
+    ) -> Self {
+        let samples: Vec<&[u8]> = samples.into_iter().collect();
+        let dictionary = train_dictionary(&samples, max_bytes);
+        Self::new(config, dictionary)
+    }
+
+    /// The dictionary id clients send in the negotiation header
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The raw dictionary bytes
+    pub fn dictionary(&self) -> &[u8] {
+        &self.dictionary
+    }
+
+    /// Snapshot the compression counters
+    pub fn stats(&self) -> SharedDictStats {
+        SharedDictStats {
+            compressed_responses: self.compressed_responses.load(Ordering::Relaxed),
+            raw_bytes: self.raw_bytes.load(Ordering::Relaxed),
+            compressed_bytes: self.compressed_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    fn eligible_content_type(&self, res: &Response) -> bool {
+        let content_type = res
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+            .map(|(_, v)| v.as_str())
+            .unwrap_or("");
+        self.config
+            .content_types
+            .iter()
+            .any(|prefix| content_type.starts_with(prefix.as_str()))
+    }
+}
+
+impl Middleware for SharedDict {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        // Serve the dictionary itself when configured
+        let serve_path = self.config.serve_path.as_deref()?;
+        if req.path != serve_path {
+            return None;
+        }
+        Some(
+            ResponseBuilder::new(StatusCode::OK)
+                .header("Content-Type", "application/octet-stream")
+                .header("Cache-Control", "public, max-age=31536000, immutable")
+                .header(&self.config.header, &self.id)
+                .body(self.dictionary.clone())
+                .build(),
+        )
+    }
+
+    fn after(&self, req: &Request, res: &mut Response) {
+        // Only requests that hold this exact dictionary opt in
+        if req.header(&self.config.header) != Some(self.id.as_str()) {
+            return;
+        }
+        if !accepts_encoding(
+            req.header("accept-encoding").unwrap_or(""),
+            Encoding::Brotli,
+        ) {
+            return;
+        }
+
+        let body_len = res.body.len();
+        if body_len < self.config.min_size || !self.eligible_content_type(res) {
+            return;
+        }
+
+        // Already-encoded bodies must not be compressed a second time
+        if res
+            .headers
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case("content-encoding"))
+        {
+            return;
+        }
+
+        let compressed =
+            compress_with_dictionary(&res.body, &self.dictionary, self.config.level);
+
+        // Only use compressed if smaller
+        if compressed.len() < body_len {
+            self.compressed_responses.fetch_add(1, Ordering::Relaxed);
+            self.raw_bytes.fetch_add(body_len as u64, Ordering::Relaxed);
+            self.compressed_bytes
+                .fetch_add(compressed.len() as u64, Ordering::Relaxed);
+
+            res.body = bytes::Bytes::from(compressed);
+            res.headers
+                .push(("Content-Encoding".to_string(), "br".to_string()));
+            res.headers
+                .push((self.config.header.clone(), self.id.clone()));
+            res.headers.push((
+                "Vary".to_string(),
+                format!("Accept-Encoding, {}", self.config.header),
+            ));
+            res.headers.retain(|(k, _)| !k.eq_ignore_ascii_case("content-length"));
+            res.headers.push((
+                "Content-Length".to_string(),
+                res.body.len().to_string(),
+            ));
+        }
+    }
+}
+
+/// Short content-addressed dictionary id (prefix of the SHA-256)
+fn dictionary_id(dictionary: &[u8]) -> String {
+    let mut hex = crate::crypto::sha256_hex(dictionary);
+    hex.truncate(16);
+    hex
+}
+
+/// Build a dictionary from representative sample bodies
+///
+/// Duplicate samples are collapsed and ordered so the most frequent
+/// content sits at the end of the dictionary, where brotli backward
+/// references are cheapest; the result is trimmed from the front to
+/// `max_bytes`. This is a simple frequency heuristic, not a suffix
+/// trainer — feed it real response bodies for best results.
+pub fn train_dictionary(samples: &[&[u8]], max_bytes: usize) -> Vec<u8> {
+    let mut counts: HashMap<&[u8], usize> = HashMap::new();
+    let mut order: Vec<&[u8]> = Vec::new();
+    for sample in samples {
+        let count = counts.entry(sample).or_insert(0);
+        if *count == 0 {
+            order.push(sample);
+        }
+        *count += 1;
+    }
+
+    // Stable sort keeps first-seen order among equally frequent samples
+    order.sort_by_key(|sample| counts[sample]);
+
+    let mut dictionary = Vec::new();
+    for sample in order {
+        dictionary.extend_from_slice(sample);
+    }
+    if dictionary.len() > max_bytes {
+        dictionary.drain(..dictionary.len() - max_bytes);
+    }
+    dictionary
+}
+
+/// Brotli-compress data against a custom dictionary
+///
+/// A build without the `compress` feature returns the input unchanged.
+#[cfg(feature = "compress")]
+pub fn compress_with_dictionary(
+    data: &[u8],
+    dictionary: &[u8],
+    level: CompressionLevel,
+) -> Vec<u8> {
+    use brotli::enc::StandardAlloc;
+    use brotli::interface;
+    use brotli::InputReferenceMut;
+
+    let params = brotli::enc::BrotliEncoderParams {
+        quality: level.brotli_level() as i32,
+        ..Default::default()
+    };
+    let mut input_buffer: [u8; 4096] = [0; 4096];
+    let mut output_buffer: [u8; 4096] = [0; 4096];
+    let mut nop_callback = |_data: &mut interface::PredictionModeContextMap<InputReferenceMut>,
+                            _cmds: &mut [interface::StaticCommand],
+                            _mb: interface::InputPair,
+                            _m: &mut StandardAlloc| ();
+    let mut output = Vec::new();
+    brotli::BrotliCompressCustomIoCustomDict(
+        &mut brotli::IoReaderWrapper(&mut std::io::Cursor::new(data)),
+        &mut brotli::IoWriterWrapper(&mut output),
+        &mut input_buffer[..],
+        &mut output_buffer[..],
+        &params,
+        StandardAlloc::default(),
+        &mut nop_callback,
+        dictionary,
+        std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Unexpected EOF"),
+    )
+    .unwrap();
+    output
+}
+
+#[cfg(not(feature = "compress"))]
+pub fn compress_with_dictionary(
+    data: &[u8],
+    _dictionary: &[u8],
+    _level: CompressionLevel,
+) -> Vec<u8> {
+    data.to_vec()
+}
+
+/// Decompress brotli data that was compressed against a custom dictionary
+///
+/// Returns `None` for invalid payloads (or a build without the
+/// `compress` feature).
+#[cfg(feature = "compress")]
+pub fn decompress_with_dictionary(data: &[u8], dictionary: &[u8]) -> Option<Vec<u8>> {
+    use brotli::enc::StandardAlloc;
+    use brotli::{Allocator, SliceWrapperMut};
+
+    let mut alloc = StandardAlloc::default();
+    let mut dict_mem =
+        <StandardAlloc as Allocator<u8>>::alloc_cell(&mut alloc, dictionary.len());
+    dict_mem.slice_mut().copy_from_slice(dictionary);
+
+    let mut input_buffer: [u8; 4096] = [0; 4096];
+    let mut output_buffer: [u8; 4096] = [0; 4096];
+    let mut output = Vec::new();
+    brotli::BrotliDecompressCustomIoCustomDict(
+        &mut brotli::IoReaderWrapper(&mut std::io::Cursor::new(data)),
+        &mut brotli::IoWriterWrapper(&mut output),
+        &mut input_buffer[..],
+        &mut output_buffer[..],
+        alloc,
+        StandardAlloc::default(),
+        StandardAlloc::default(),
+        dict_mem,
+        std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "Unexpected EOF"),
+    )
+    .ok()?;
+    Some(output)
+}
+
+#[cfg(not(feature = "compress"))]
+pub fn decompress_with_dictionary(_data: &[u8], _dictionary: &[u8]) -> Option<Vec<u8>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, RequestBuilder};
+
+    fn json_response(body: &str) -> Response {
+        ResponseBuilder::new(StatusCode::OK)
+            .header("Content-Type", "application/json")
+            .body(body.to_string())
+            .build()
+    }
+
+    #[test]
+    fn test_train_dictionary_frequency_and_budget() {
+        let a: &[u8] = b"{\"status\":\"ok\"}";
+        let b: &[u8] = b"{\"status\":\"error\"}";
+        let dict = train_dictionary(&[a, b, a], 1024);
+        // Most frequent sample ends up at the end
+        assert!(dict.ends_with(a));
+        assert_eq!(dict.len(), a.len() + b.len());
+
+        // Budget trims from the front
+        let trimmed = train_dictionary(&[a, b, a], a.len());
+        assert_eq!(trimmed, a);
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_dictionary_roundtrip() {
+        let dictionary = b"{\"users\":[{\"id\":0,\"name\":\"\",\"active\":true}]}".to_vec();
+        let body = b"{\"users\":[{\"id\":42,\"name\":\"gust\",\"active\":true}]}";
+
+        let compressed =
+            compress_with_dictionary(body, &dictionary, CompressionLevel::Default);
+        assert!(compressed.len() < body.len());
+        assert_eq!(
+            decompress_with_dictionary(&compressed, &dictionary).unwrap(),
+            body
+        );
+
+        // The wrong dictionary must not silently decode to the wrong bytes
+        assert_ne!(
+            decompress_with_dictionary(&compressed, b"unrelated").as_deref(),
+            Some(&body[..])
+        );
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_middleware_negotiation() {
+        let body = "{\"items\":[1,2,3],\"status\":\"ok\",\"errors\":[]}".repeat(16);
+        let middleware = SharedDict::trained(
+            SharedDictConfig::new(),
+            [body.as_bytes()],
+            64 * 1024,
+        );
+        let id = middleware.id().to_string();
+
+        // No negotiation header: untouched
+        let req = RequestBuilder::new(Method::Get, "/api/items").build();
+        let mut res = json_response(&body);
+        middleware.after(&req, &mut res);
+        assert_eq!(res.header("Content-Encoding"), None);
+
+        // Wrong id: untouched
+        let req = RequestBuilder::new(Method::Get, "/api/items")
+            .header("X-Dictionary", "deadbeef")
+            .header("Accept-Encoding", "br")
+            .build();
+        let mut res = json_response(&body);
+        middleware.after(&req, &mut res);
+        assert_eq!(res.header("Content-Encoding"), None);
+
+        // Matching id and br acceptable: compressed against the dictionary
+        let req = RequestBuilder::new(Method::Get, "/api/items")
+            .header("X-Dictionary", id.as_str())
+            .header("Accept-Encoding", "gzip, br")
+            .build();
+        let mut res = json_response(&body);
+        middleware.after(&req, &mut res);
+        assert_eq!(res.header("Content-Encoding"), Some("br"));
+        assert_eq!(res.header("x-dictionary"), Some(id.as_str()));
+        assert!(res.body.len() < body.len());
+        assert_eq!(
+            decompress_with_dictionary(&res.body, middleware.dictionary()).unwrap(),
+            body.as_bytes()
+        );
+
+        let stats = middleware.stats();
+        assert_eq!(stats.compressed_responses, 1);
+        assert_eq!(stats.raw_bytes, body.len() as u64);
+        assert!(stats.ratio() > 0.5);
+    }
+
+    #[test]
+    fn test_serve_path_returns_dictionary() {
+        let config = SharedDictConfig::new().serve_path("/_gust/dictionary");
+        let middleware = SharedDict::new(config, b"sample dictionary".to_vec());
+
+        let mut req = RequestBuilder::new(Method::Get, "/_gust/dictionary").build();
+        let res = middleware.before(&mut req).unwrap();
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(&res.body[..], b"sample dictionary");
+        assert_eq!(res.header("x-dictionary"), Some(middleware.id()));
+
+        let mut req = RequestBuilder::new(Method::Get, "/api/items").build();
+        assert!(middleware.before(&mut req).is_none());
+    }
+}
@@ -19,12 +19,21 @@ pub struct TracingConfig {
     pub log_responses: bool,
     /// ID generator
     pub id_generator: IdGenerator,
+    /// Parse/generate W3C trace context (trace + span IDs)
+    pub propagate_trace: bool,
 }
 
 /// ID generator type
+///
+/// `UuidV7`, `Ulid`, and `Snowflake` encode the creation time in the
+/// leading bits, so request IDs sort chronologically in logs.
 #[derive(Clone, Copy)]
 pub enum IdGenerator {
     Uuid,
+    UuidV7,
+    Ulid,
+    /// Snowflake-style 64-bit ID; the payload is the 10-bit machine ID
+    Snowflake(u16),
     NanoId,
     ShortId,
     Counter,
@@ -38,6 +47,7 @@ impl Default for TracingConfig {
             log_requests: false,
             log_responses: false,
             id_generator: IdGenerator::NanoId,
+            propagate_trace: false,
         }
     }
 }
@@ -71,6 +81,13 @@ impl TracingConfig {
         self.id_generator = generator;
         self
     }
+
+    /// Parse incoming W3C traceparent headers (continuing the caller's
+    /// trace) and generate trace/span IDs for this request
+    pub fn propagate_trace(mut self) -> Self {
+        self.propagate_trace = true;
+        self
+    }
 }
 
 /// Generate UUID v4
@@ -110,6 +127,64 @@ pub fn generate_short_id() -> String {
     bytes.iter().map(|&b| ALPHABET[(b as usize) % ALPHABET.len()] as char).collect()
 }
 
+/// Generate a time-ordered UUIDv7 (RFC 9562)
+pub fn generate_uuid_v7() -> String {
+    crate::crypto::uuid_v7()
+}
+
+/// Generate a ULID (26 Crockford base32 characters)
+///
+/// 48-bit millisecond timestamp followed by 80 random bits; shorter
+/// than a UUID and sorts lexicographically by creation time.
+pub fn generate_ulid() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let mut random = [0u8; 10];
+    crate::crypto::fill_random_or_fallback(&mut random);
+    encode_ulid(unix_ms, random)
+}
+
+/// Crockford base32 encoding of timestamp + randomness
+fn encode_ulid(unix_ms: u64, random: [u8; 10]) -> String {
+    const ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    let mut value = (unix_ms as u128) << 80;
+    for (i, byte) in random.iter().enumerate() {
+        value |= (*byte as u128) << (8 * (9 - i));
+    }
+    // 26 chars × 5 bits = 130 bits; the top two bits are always zero
+    (0..26)
+        .map(|i| ALPHABET[((value >> (125 - 5 * i)) & 0x1F) as usize] as char)
+        .collect()
+}
+
+/// Snowflake-style epoch: 2020-01-01T00:00:00Z, keeping the 41-bit
+/// millisecond field good until ~2089
+const SNOWFLAKE_EPOCH_MS: u64 = 1_577_836_800_000;
+
+/// Generate a Snowflake-style decimal ID (41-bit timestamp,
+/// 10-bit machine ID, 12-bit sequence)
+///
+/// The sequence is a global counter masked to 12 bits, so IDs stay
+/// unique as long as one machine issues fewer than 4096 per
+/// millisecond.
+pub fn generate_snowflake_id(machine_id: u16) -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    static SEQUENCE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    let unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let elapsed = unix_ms.saturating_sub(SNOWFLAKE_EPOCH_MS);
+    let sequence = SEQUENCE.fetch_add(1, std::sync::atomic::Ordering::Relaxed) & 0xFFF;
+
+    let id = ((elapsed & 0x1FF_FFFF_FFFF) << 22) | (((machine_id as u64) & 0x3FF) << 12) | sequence;
+    id.to_string()
+}
+
 /// Simple counter-based ID
 static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
 
@@ -164,6 +239,9 @@ impl Middleware for Tracing {
                 if self.config.generate_id {
                     Some(match self.config.id_generator {
                         IdGenerator::Uuid => generate_uuid(),
+                        IdGenerator::UuidV7 => generate_uuid_v7(),
+                        IdGenerator::Ulid => generate_ulid(),
+                        IdGenerator::Snowflake(machine_id) => generate_snowflake_id(machine_id),
                         IdGenerator::NanoId => generate_nano_id(),
                         IdGenerator::ShortId => generate_short_id(),
                         IdGenerator::Counter => generate_counter_id(),
@@ -178,6 +256,22 @@ impl Middleware for Tracing {
             req.params.insert("_request_start".to_string(), format!("{:?}", Instant::now()));
         }
 
+        // W3C trace context: continue the caller's trace when a valid
+        // traceparent arrives, otherwise start a new one; the span ID
+        // is always fresh for this hop
+        if self.config.propagate_trace {
+            use super::otel::{generate_span_id, generate_trace_id, parse_traceparent};
+
+            let trace_id = req
+                .header("traceparent")
+                .and_then(parse_traceparent)
+                .map(|ctx| ctx.trace_id)
+                .unwrap_or_else(generate_trace_id);
+
+            req.params.insert("_trace_id".to_string(), trace_id);
+            req.params.insert("_span_id".to_string(), generate_span_id());
+        }
+
         // Log request
         if self.config.log_requests {
             let id = req.params.get("_request_id").map(|s| s.as_str()).unwrap_or("-");
@@ -229,10 +323,72 @@ mod tests {
         assert_eq!(id.len(), 8);
     }
 
+    #[test]
+    fn test_uuid_v7_format() {
+        let id = generate_uuid_v7();
+        assert_eq!(id.len(), 36);
+        assert_eq!(id.as_bytes()[14], b'7'); // version nibble
+    }
+
+    #[test]
+    fn test_ulid_format() {
+        let id = generate_ulid();
+        assert_eq!(id.len(), 26);
+        assert!(id.bytes().all(|b| b"0123456789ABCDEFGHJKMNPQRSTVWXYZ".contains(&b)));
+    }
+
+    #[test]
+    fn test_ulid_sorts_chronologically() {
+        let earlier = encode_ulid(1_700_000_000_000, [0xFF; 10]);
+        let later = encode_ulid(1_700_000_000_001, [0x00; 10]);
+        assert!(earlier < later);
+    }
+
+    #[test]
+    fn test_snowflake_id() {
+        let id1 = generate_snowflake_id(42);
+        let id2 = generate_snowflake_id(42);
+        assert_ne!(id1, id2);
+        // Machine ID occupies bits 12..22
+        let value: u64 = id1.parse().unwrap();
+        assert_eq!((value >> 12) & 0x3FF, 42);
+    }
+
     #[test]
     fn test_counter_id() {
         let id1 = generate_counter_id();
         let id2 = generate_counter_id();
         assert_ne!(id1, id2);
     }
+
+    #[test]
+    fn test_propagate_trace() {
+        use crate::Method;
+
+        let tracing = Tracing::new(TracingConfig::new().propagate_trace());
+
+        // Incoming traceparent: trace ID is continued, span ID is fresh
+        let mut req = crate::RequestBuilder::new(Method::Get, "/")
+            .header(
+                "traceparent",
+                "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            )
+            .build();
+        assert!(tracing.before(&mut req).is_none());
+        assert_eq!(
+            req.params.get("_trace_id").map(|s| s.as_str()),
+            Some("0af7651916cd43dd8448eb211c80319c")
+        );
+        let span_id = req.params.get("_span_id").unwrap();
+        assert_eq!(span_id.len(), 16);
+        assert_ne!(span_id, "b7ad6b7169203331");
+
+        // No traceparent: a new trace is started
+        let mut req = crate::RequestBuilder::new(Method::Get, "/").build();
+        tracing.before(&mut req);
+        assert_eq!(req.params.get("_trace_id").unwrap().len(), 32);
+
+        // Request ID is still assigned alongside the trace context
+        assert!(req.params.contains_key("_request_id"));
+    }
 }
@@ -0,0 +1,251 @@
+//! Declarative request transformation
+//!
+//! Small header and path rewrites (inject or strip headers, set
+//! defaults, strip path prefixes, append query parameters) configured
+//! as rules instead of hand-written middleware. Rules run in the
+//! order they were added, before routing, so rewritten paths are what
+//! the router and any proxy handler see.
+
+use crate::{Request, Response};
+
+/// One transformation applied to an incoming request
+#[derive(Debug, Clone)]
+enum Rule {
+    /// Set a header, replacing any existing values
+    SetHeader { name: String, value: String },
+    /// Set a header only when the request does not carry one
+    DefaultHeader { name: String, value: String },
+    /// Drop all values of a header
+    RemoveHeader { name: String },
+    /// Move a header's values to a new name
+    RenameHeader { from: String, to: String },
+    /// Strip a leading path prefix (`/api/users` -> `/users`)
+    StripPrefix { prefix: String },
+    /// Append a query parameter
+    AppendQuery { name: String, value: String },
+}
+
+/// Transformation rules, applied in insertion order
+#[derive(Debug, Clone, Default)]
+pub struct TransformConfig {
+    rules: Vec<Rule>,
+}
+
+impl TransformConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a header, replacing any values the client sent
+    pub fn set_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.rules.push(Rule::SetHeader {
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Set a header only when the request does not already carry one
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.rules.push(Rule::DefaultHeader {
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Drop a header entirely
+    pub fn remove_header(mut self, name: impl Into<String>) -> Self {
+        self.rules.push(Rule::RemoveHeader { name: name.into() });
+        self
+    }
+
+    /// Move a header's values to a new name
+    pub fn rename_header(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.rules.push(Rule::RenameHeader {
+            from: from.into(),
+            to: to.into(),
+        });
+        self
+    }
+
+    /// Strip a leading path prefix; a fully-stripped path becomes `/`
+    pub fn strip_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.rules.push(Rule::StripPrefix {
+            prefix: prefix.into(),
+        });
+        self
+    }
+
+    /// Append a query parameter to the request's query string
+    pub fn append_query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.rules.push(Rule::AppendQuery {
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+}
+
+/// Declarative request transform middleware
+pub struct Transform {
+    config: TransformConfig,
+}
+
+impl Transform {
+    pub fn new(config: TransformConfig) -> Self {
+        Self { config }
+    }
+
+    /// Apply all rules to a request, in order
+    pub fn apply(&self, req: &mut Request) {
+        for rule in &self.config.rules {
+            match rule {
+                Rule::SetHeader { name, value } => {
+                    req.headers
+                        .retain(|(header, _)| !header.eq_ignore_ascii_case(name));
+                    req.headers.push((name.clone(), value.clone()));
+                }
+                Rule::DefaultHeader { name, value } => {
+                    let present = req
+                        .headers
+                        .iter()
+                        .any(|(header, _)| header.eq_ignore_ascii_case(name));
+                    if !present {
+                        req.headers.push((name.clone(), value.clone()));
+                    }
+                }
+                Rule::RemoveHeader { name } => {
+                    req.headers
+                        .retain(|(header, _)| !header.eq_ignore_ascii_case(name));
+                }
+                Rule::RenameHeader { from, to } => {
+                    for (header, _) in req.headers.iter_mut() {
+                        if header.eq_ignore_ascii_case(from) {
+                            *header = to.clone();
+                        }
+                    }
+                }
+                Rule::StripPrefix { prefix } => {
+                    // Only strip at a segment boundary: `/api` must not
+                    // rewrite `/apikeys`
+                    if let Some(rest) = req.path.strip_prefix(prefix.as_str()) {
+                        if rest.is_empty() {
+                            req.path = "/".to_string();
+                        } else if rest.starts_with('/') {
+                            req.path = rest.to_string();
+                        }
+                    }
+                }
+                Rule::AppendQuery { name, value } => {
+                    let pair = format!("{}={}", name, value);
+                    req.query = Some(match req.query.take() {
+                        Some(query) if !query.is_empty() => format!("{}&{}", query, pair),
+                        _ => pair,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl super::Middleware for Transform {
+    fn before(&self, req: &mut Request) -> Option<Response> {
+        self.apply(req);
+        None
+    }
+
+    fn after(&self, _req: &Request, _res: &mut Response) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::Middleware;
+    use crate::{Method, RequestBuilder};
+
+    #[test]
+    fn test_header_rules() {
+        let transform = Transform::new(
+            TransformConfig::new()
+                .set_header("x-forwarded-proto", "https")
+                .default_header("accept", "application/json")
+                .default_header("x-request-source", "edge")
+                .remove_header("cookie")
+                .rename_header("x-old-id", "x-request-id"),
+        );
+
+        let mut req = RequestBuilder::new(Method::Get, "/")
+            .header("X-Forwarded-Proto", "http")
+            .header("Accept", "text/html")
+            .header("Cookie", "session=abc")
+            .header("X-Old-Id", "42")
+            .build();
+        assert!(transform.before(&mut req).is_none());
+
+        // set replaces, default keeps the client's value
+        assert_eq!(req.header("x-forwarded-proto"), Some("https"));
+        assert_eq!(req.header("accept"), Some("text/html"));
+        // default fills in the missing header
+        assert_eq!(req.header("x-request-source"), Some("edge"));
+        assert_eq!(req.header("cookie"), None);
+        assert_eq!(req.header("x-old-id"), None);
+        assert_eq!(req.header("x-request-id"), Some("42"));
+    }
+
+    #[test]
+    fn test_strip_prefix() {
+        let transform = Transform::new(TransformConfig::new().strip_prefix("/api"));
+
+        let mut req = RequestBuilder::new(Method::Get, "/api/users").build();
+        transform.apply(&mut req);
+        assert_eq!(req.path, "/users");
+
+        // Fully-stripped paths normalize to /
+        let mut req = RequestBuilder::new(Method::Get, "/api").build();
+        transform.apply(&mut req);
+        assert_eq!(req.path, "/");
+
+        // Non-matching paths are untouched, including partial segments
+        let mut req = RequestBuilder::new(Method::Get, "/health").build();
+        transform.apply(&mut req);
+        assert_eq!(req.path, "/health");
+        let mut req = RequestBuilder::new(Method::Get, "/apikeys").build();
+        transform.apply(&mut req);
+        assert_eq!(req.path, "/apikeys");
+    }
+
+    #[test]
+    fn test_append_query() {
+        let transform = Transform::new(
+            TransformConfig::new()
+                .append_query("version", "2")
+                .append_query("source", "edge"),
+        );
+
+        let mut req = RequestBuilder::new(Method::Get, "/search").build();
+        transform.apply(&mut req);
+        assert_eq!(req.query.as_deref(), Some("version=2&source=edge"));
+
+        let mut req = RequestBuilder::new(Method::Get, "/search").build();
+        req.query = Some("q=gust".to_string());
+        transform.apply(&mut req);
+        assert_eq!(req.query.as_deref(), Some("q=gust&version=2&source=edge"));
+    }
+
+    #[test]
+    fn test_rules_run_in_insertion_order() {
+        // Rename first, then set: the set wins because it runs later
+        let transform = Transform::new(
+            TransformConfig::new()
+                .rename_header("x-id", "x-request-id")
+                .set_header("x-request-id", "fixed"),
+        );
+
+        let mut req = RequestBuilder::new(Method::Get, "/")
+            .header("x-id", "original")
+            .build();
+        transform.apply(&mut req);
+        assert_eq!(req.header("x-request-id"), Some("fixed"));
+    }
+}
@@ -0,0 +1,434 @@
+//! Outlier detection and active health checks for upstreams
+//!
+//! A [`HealthChecker`] probes upstream members on an interval (plain
+//! TCP connect or an HTTP GET) and applies consecutive-failure /
+//! consecutive-success thresholds before flipping a member between
+//! healthy and unhealthy. Transitions drain the member from the
+//! connection pool and are recorded as events and counters. Passive
+//! outlier detection plugs into the same thresholds: callers feed
+//! request outcomes through [`HealthChecker::record`].
+
+use crate::upstream::UpstreamPool;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How a member is probed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Probe {
+    /// Healthy when a TCP connection can be established
+    Tcp,
+    /// Healthy when `GET path` returns a 2xx or 3xx status
+    Http { path: String },
+}
+
+/// Probe and threshold configuration
+#[derive(Debug, Clone)]
+pub struct HealthCheckConfig {
+    pub probe: Probe,
+    /// Time between probe rounds (default 10s)
+    pub interval: Duration,
+    /// Per-probe timeout (default 2s)
+    pub timeout: Duration,
+    /// Consecutive failures before a member is marked unhealthy
+    pub unhealthy_threshold: u32,
+    /// Consecutive successes before an unhealthy member is restored
+    pub healthy_threshold: u32,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            probe: Probe::Tcp,
+            interval: Duration::from_secs(10),
+            timeout: Duration::from_secs(2),
+            unhealthy_threshold: 3,
+            healthy_threshold: 2,
+        }
+    }
+}
+
+impl HealthCheckConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn probe(mut self, probe: Probe) -> Self {
+        self.probe = probe;
+        self
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn unhealthy_threshold(mut self, threshold: u32) -> Self {
+        self.unhealthy_threshold = threshold.max(1);
+        self
+    }
+
+    pub fn healthy_threshold(mut self, threshold: u32) -> Self {
+        self.healthy_threshold = threshold.max(1);
+        self
+    }
+}
+
+/// Health state of one member
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberHealth {
+    Healthy,
+    Unhealthy,
+}
+
+/// One recorded state transition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthTransition {
+    pub member: SocketAddr,
+    pub to: MemberHealth,
+}
+
+/// Counters describing probe activity
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HealthStats {
+    /// Probes (or recorded outcomes) observed
+    pub probes: u64,
+    /// Failed probes or outcomes
+    pub failures: u64,
+    /// Healthy/unhealthy transitions
+    pub transitions: u64,
+}
+
+struct MemberState {
+    health: MemberHealth,
+    consecutive_failures: u32,
+    consecutive_successes: u32,
+}
+
+/// Tracks member health from active probes and passive outcomes
+pub struct HealthChecker {
+    config: HealthCheckConfig,
+    state: Mutex<HashMap<SocketAddr, MemberState>>,
+    events: Mutex<Vec<HealthTransition>>,
+    /// Pool drained/restored on transitions, with the key scheme used
+    /// by callers when dialing
+    pool: Option<(Arc<UpstreamPool>, String)>,
+    probes: AtomicU64,
+    failures: AtomicU64,
+    transitions: AtomicU64,
+}
+
+impl HealthChecker {
+    pub fn new(config: HealthCheckConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(HashMap::new()),
+            events: Mutex::new(Vec::new()),
+            pool: None,
+            probes: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            transitions: AtomicU64::new(0),
+        }
+    }
+
+    /// Drain unhealthy members from a pool (and restore recovered
+    /// ones); `scheme` must match the pool keys callers dial with
+    pub fn pool(mut self, pool: Arc<UpstreamPool>, scheme: impl Into<String>) -> Self {
+        self.pool = Some((pool, scheme.into()));
+        self
+    }
+
+    /// Replace the probed member set; new members start healthy,
+    /// departed members are forgotten
+    pub fn set_members(&self, members: Vec<SocketAddr>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.retain(|addr, _| members.contains(addr));
+        for addr in members {
+            state.entry(addr).or_insert(MemberState {
+                health: MemberHealth::Healthy,
+                consecutive_failures: 0,
+                consecutive_successes: 0,
+            });
+        }
+    }
+
+    /// Current health of a member; unknown members count as healthy so
+    /// discovery can add them before the first probe round
+    pub fn is_healthy(&self, addr: SocketAddr) -> bool {
+        self.state
+            .lock()
+            .map(|state| {
+                state
+                    .get(&addr)
+                    .map(|m| m.health == MemberHealth::Healthy)
+                    .unwrap_or(true)
+            })
+            .unwrap_or(true)
+    }
+
+    /// Members currently marked healthy
+    pub fn healthy_members(&self) -> Vec<SocketAddr> {
+        self.state
+            .lock()
+            .map(|state| {
+                state
+                    .iter()
+                    .filter(|(_, m)| m.health == MemberHealth::Healthy)
+                    .map(|(addr, _)| *addr)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Feed one probe result or request outcome through the thresholds
+    pub fn record(&self, addr: SocketAddr, success: bool) {
+        self.probes.fetch_add(1, Ordering::Relaxed);
+        if !success {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let transition = {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            let Some(member) = state.get_mut(&addr) else {
+                return;
+            };
+            if success {
+                member.consecutive_failures = 0;
+                member.consecutive_successes += 1;
+                if member.health == MemberHealth::Unhealthy
+                    && member.consecutive_successes >= self.config.healthy_threshold
+                {
+                    member.health = MemberHealth::Healthy;
+                    Some(MemberHealth::Healthy)
+                } else {
+                    None
+                }
+            } else {
+                member.consecutive_successes = 0;
+                member.consecutive_failures += 1;
+                if member.health == MemberHealth::Healthy
+                    && member.consecutive_failures >= self.config.unhealthy_threshold
+                {
+                    member.health = MemberHealth::Unhealthy;
+                    Some(MemberHealth::Unhealthy)
+                } else {
+                    None
+                }
+            }
+        };
+
+        if let Some(to) = transition {
+            self.transitions.fetch_add(1, Ordering::Relaxed);
+            if let Some((pool, scheme)) = &self.pool {
+                let key = format!("{}://{}", scheme, addr);
+                match to {
+                    MemberHealth::Unhealthy => pool.drain(&key),
+                    MemberHealth::Healthy => pool.restore(&key),
+                }
+            }
+            if let Ok(mut events) = self.events.lock() {
+                events.push(HealthTransition { member: addr, to });
+            }
+        }
+    }
+
+    /// Drain recorded transitions (for logs/metrics pipelines)
+    pub fn take_events(&self) -> Vec<HealthTransition> {
+        self.events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+
+    /// Snapshot of probe counters
+    pub fn stats(&self) -> HealthStats {
+        HealthStats {
+            probes: self.probes.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            transitions: self.transitions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Probe every member once, concurrently
+    pub async fn run_once(&self) {
+        let members: Vec<SocketAddr> = self
+            .state
+            .lock()
+            .map(|state| state.keys().copied().collect())
+            .unwrap_or_default();
+
+        let mut probes = tokio::task::JoinSet::new();
+        for addr in members {
+            let probe = self.config.probe.clone();
+            let timeout = self.config.timeout;
+            probes.spawn(async move { (addr, run_probe(&probe, addr, timeout).await) });
+        }
+        while let Some(result) = probes.join_next().await {
+            if let Ok((addr, success)) = result {
+                self.record(addr, success);
+            }
+        }
+    }
+
+    /// Probe on the configured interval until the handle is aborted
+    pub fn spawn(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let checker = Arc::clone(self);
+        tokio::spawn(async move {
+            let interval = checker.config.interval.max(Duration::from_millis(100));
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                checker.run_once().await;
+            }
+        })
+    }
+}
+
+/// Run one probe against a member
+async fn run_probe(probe: &Probe, addr: SocketAddr, timeout: Duration) -> bool {
+    let attempt = async {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.ok()?;
+        match probe {
+            Probe::Tcp => Some(true),
+            Probe::Http { path } => {
+                use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+                let request = format!(
+                    "GET {} HTTP/1.1\r\nhost: {}\r\nconnection: close\r\n\r\n",
+                    path, addr
+                );
+                stream.write_all(request.as_bytes()).await.ok()?;
+                let mut buf = [0u8; 64];
+                let len = stream.read(&mut buf).await.ok()?;
+                // "HTTP/1.1 NNN ..." — healthy on 2xx/3xx
+                let head = std::str::from_utf8(&buf[..len]).ok()?;
+                let status: u16 = head.split_whitespace().nth(1)?.parse().ok()?;
+                Some((200..400).contains(&status))
+            }
+        }
+    };
+    tokio::time::timeout(timeout, attempt)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::upstream::UpstreamConfig;
+
+    fn member() -> SocketAddr {
+        "10.0.0.1:8080".parse().unwrap()
+    }
+
+    #[test]
+    fn test_thresholds_and_transitions() {
+        let checker = HealthChecker::new(
+            HealthCheckConfig::new()
+                .unhealthy_threshold(2)
+                .healthy_threshold(2),
+        );
+        checker.set_members(vec![member()]);
+
+        // One failure is not enough
+        checker.record(member(), false);
+        assert!(checker.is_healthy(member()));
+
+        checker.record(member(), false);
+        assert!(!checker.is_healthy(member()));
+        assert_eq!(
+            checker.take_events(),
+            vec![HealthTransition {
+                member: member(),
+                to: MemberHealth::Unhealthy,
+            }]
+        );
+
+        // Recovery needs two consecutive successes
+        checker.record(member(), true);
+        assert!(!checker.is_healthy(member()));
+        checker.record(member(), false);
+        checker.record(member(), true);
+        checker.record(member(), true);
+        assert!(checker.is_healthy(member()));
+
+        let stats = checker.stats();
+        assert_eq!(stats.probes, 6);
+        assert_eq!(stats.failures, 3);
+        assert_eq!(stats.transitions, 2);
+    }
+
+    #[test]
+    fn test_transition_drains_pool() {
+        let pool = Arc::new(UpstreamPool::new(UpstreamConfig::default()));
+        let checker = HealthChecker::new(HealthCheckConfig::new().unhealthy_threshold(1))
+            .pool(Arc::clone(&pool), "http");
+        checker.set_members(vec![member()]);
+
+        checker.record(member(), false);
+        // Drained keys refuse check-ins; observable through discarded
+        // growing when a drain drops idle connections (none here), so
+        // assert via restore round-trip not panicking and health state
+        assert!(!checker.is_healthy(member()));
+        checker.record(member(), true);
+        checker.record(member(), true);
+        assert!(checker.is_healthy(member()));
+    }
+
+    #[tokio::test]
+    async fn test_tcp_probe_round() {
+        // A listening socket is healthy; a closed port is not
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let alive = listener.local_addr().unwrap();
+        let dead: SocketAddr = "127.0.0.1:1".parse().unwrap();
+
+        let checker = HealthChecker::new(
+            HealthCheckConfig::new()
+                .unhealthy_threshold(1)
+                .timeout(Duration::from_millis(300)),
+        );
+        checker.set_members(vec![alive, dead]);
+        checker.run_once().await;
+
+        assert!(checker.is_healthy(alive));
+        assert!(!checker.is_healthy(dead));
+        assert_eq!(checker.healthy_members(), vec![alive]);
+    }
+
+    #[tokio::test]
+    async fn test_http_probe_checks_status() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for status in ["200 OK", "503 Service Unavailable"] {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 512];
+                let _ = stream.read(&mut buf).await;
+                let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\n\r\n", status);
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let probe = Probe::Http {
+            path: "/healthz".to_string(),
+        };
+        assert!(run_probe(&probe, addr, Duration::from_millis(500)).await);
+        assert!(!run_probe(&probe, addr, Duration::from_millis(500)).await);
+    }
+}
@@ -0,0 +1,206 @@
+//! Zero-copy HTTP/1.1 request parser
+//!
+//! Fills [`ParsedRequest`] and a [`HeaderOffsets`] table in one pass
+//! with no allocations, using the SWAR scanners from [`scan`]. Shared
+//! SSOT between the WASM bindings and the native `raw-http1` engine.
+
+use super::scan;
+use super::{HeaderOffsets, Method, ParsedRequest, MAX_HEADERS};
+
+/// Parse HTTP request - returns all data in one pass
+/// header_offsets is filled with [name_start, name_end, value_start, value_end] for each header
+#[inline]
+pub fn parse_request(buf: &[u8], header_offsets: &mut HeaderOffsets) -> ParsedRequest {
+    let len = buf.len();
+    let mut result = ParsedRequest::default();
+
+    // Minimum request: "GET / HTTP/1.1\r\n\r\n" = 18 bytes
+    if len < 18 {
+        return result; // state = 0 (incomplete)
+    }
+
+    // Parse method (SWAR-accelerated)
+    let method_end = match scan::find_byte(buf, b' ') {
+        Some(i) if i < 8 => i, // Methods are max 7 chars
+        _ => return result,
+    };
+
+    result.method = match Method::parse(&buf[..method_end]) {
+        Some(m) => m,
+        None => {
+            result.state = 2; // error
+            return result;
+        }
+    };
+
+    let mut pos = method_end + 1;
+
+    // Parse path and query
+    result.path_start = pos as u32;
+
+    // Find end of request line (SWAR-accelerated)
+    let line_end = match scan::find_byte2(&buf[pos..], b'\r', b'\n') {
+        Some(i) => pos + i,
+        None => return result, // incomplete
+    };
+
+    // Find space before HTTP version, parsing path/query
+    let path_end;
+    let mut query_start: u32 = 0;
+    let mut query_end: u32 = 0;
+
+    match scan::find_byte2(&buf[pos..line_end], b'?', b' ') {
+        Some(i) if buf[pos + i] == b' ' => {
+            // No query string
+            path_end = pos + i;
+        }
+        Some(i) => {
+            // Query string: find the space after it
+            path_end = pos + i;
+            query_start = (pos + i + 1) as u32;
+            match scan::find_byte(&buf[query_start as usize..line_end], b' ') {
+                Some(j) => query_end = query_start + j as u32,
+                None => return result, // malformed
+            }
+        }
+        None => return result, // malformed
+    }
+
+    result.path_end = path_end as u32;
+    result.query_start = query_start;
+    result.query_end = query_end;
+
+    // Skip to end of request line
+    pos = line_end;
+    if pos + 1 >= len {
+        return result;
+    }
+
+    // Skip \r\n or \n
+    if buf[pos] == b'\r' {
+        pos += 2;
+    } else {
+        pos += 1;
+    }
+
+    // Parse headers
+    let mut header_count: u32 = 0;
+    let max_headers = MAX_HEADERS as u32;
+
+    loop {
+        if pos >= len {
+            return result;
+        }
+
+        // Check for end of headers
+        if buf[pos] == b'\r' {
+            if pos + 1 >= len {
+                return result;
+            }
+            if buf[pos + 1] == b'\n' {
+                pos += 2;
+                break;
+            }
+        } else if buf[pos] == b'\n' {
+            pos += 1;
+            break;
+        }
+
+        // Find colon (SWAR-accelerated)
+        let name_start = pos;
+        let colon = match scan::find_byte(&buf[pos..], b':') {
+            Some(i) => pos + i,
+            None => return result,
+        };
+        let name_end = colon;
+
+        // Skip colon and whitespace
+        pos = colon + 1;
+        while pos < len && (buf[pos] == b' ' || buf[pos] == b'\t') {
+            pos += 1;
+        }
+
+        // Find end of header value (SWAR-accelerated)
+        let value_start = pos;
+        let line_end = match scan::find_byte2(&buf[pos..], b'\r', b'\n') {
+            Some(i) => pos + i,
+            None => return result,
+        };
+        let value_end = line_end;
+
+        // Store header offsets
+        if header_count < max_headers {
+            let idx = (header_count * 4) as usize;
+            header_offsets[idx] = name_start as u32;
+            header_offsets[idx + 1] = name_end as u32;
+            header_offsets[idx + 2] = value_start as u32;
+            header_offsets[idx + 3] = value_end as u32;
+            header_count += 1;
+        }
+
+        // Skip to next line
+        pos = line_end;
+        if pos < len && buf[pos] == b'\r' {
+            pos += 1;
+        }
+        if pos < len && buf[pos] == b'\n' {
+            pos += 1;
+        }
+    }
+
+    result.headers_count = header_count;
+    result.body_start = pos as u32;
+    result.state = 1; // complete
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_get() {
+        let req = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let mut offsets: HeaderOffsets = [0; MAX_HEADERS * 4];
+
+        let result = parse_request(req, &mut offsets);
+        assert_eq!(result.state, 1);
+        assert_eq!(result.method, Method::Get);
+        assert_eq!(result.headers_count, 1);
+    }
+
+    #[test]
+    fn test_parse_with_query() {
+        let req = b"GET /users?page=1&limit=10 HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let mut offsets: HeaderOffsets = [0; MAX_HEADERS * 4];
+
+        let result = parse_request(req, &mut offsets);
+        assert_eq!(result.state, 1);
+        assert!(result.query_start > 0);
+        assert_eq!(&req[result.path_start as usize..result.path_end as usize], b"/users");
+        assert_eq!(
+            &req[result.query_start as usize..result.query_end as usize],
+            b"page=1&limit=10"
+        );
+    }
+
+    #[test]
+    fn test_incomplete() {
+        let req = b"GET / HTTP/1.1\r\n";
+        let mut offsets: HeaderOffsets = [0; MAX_HEADERS * 4];
+
+        let result = parse_request(req, &mut offsets);
+        assert_eq!(result.state, 0);
+    }
+
+    #[test]
+    fn test_body_start_after_headers() {
+        let req = b"POST /submit HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello";
+        let mut offsets: HeaderOffsets = [0; MAX_HEADERS * 4];
+
+        let result = parse_request(req, &mut offsets);
+        assert_eq!(result.state, 1);
+        assert_eq!(&req[result.body_start as usize..], b"hello");
+    }
+}
@@ -3,8 +3,11 @@
 //! This module provides shared HTTP parsing types used by both
 //! native (via hyper) and WASM builds.
 
+mod http1;
 mod method;
+pub mod scan;
 
+pub use http1::parse_request;
 pub use method::Method;
 
 /// Maximum number of headers to parse
@@ -48,3 +51,24 @@ impl Default for ParsedRequest {
         }
     }
 }
+
+/// Find a header by name (case-insensitive) in parsed offsets
+///
+/// Returns the value byte range in `buf`. Uses SWAR-accelerated
+/// comparison so lookups stay cheap for long header names.
+pub fn find_header(
+    buf: &[u8],
+    offsets: &HeaderOffsets,
+    headers_count: u32,
+    name: &[u8],
+) -> Option<(usize, usize)> {
+    for i in 0..headers_count.min(MAX_HEADERS as u32) {
+        let idx = (i * 4) as usize;
+        let name_start = offsets[idx] as usize;
+        let name_end = offsets[idx + 1] as usize;
+        if scan::eq_ignore_case(&buf[name_start..name_end], name) {
+            return Some((offsets[idx + 2] as usize, offsets[idx + 3] as usize));
+        }
+    }
+    None
+}
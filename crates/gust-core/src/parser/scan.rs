@@ -0,0 +1,183 @@
+//! SWAR-accelerated byte scanning for the standalone parser path
+//!
+//! Processes 8-byte words instead of single bytes using SWAR (SIMD
+//! within a register). Unlike intrinsics-based SIMD this needs no
+//! `unsafe` and runs identically on x86, ARM, and wasm; hosts with
+//! vector units are reported by [`acceleration`] for diagnostics.
+
+const WORD: usize = 8;
+const LO: u64 = 0x0101_0101_0101_0101;
+const HI: u64 = 0x8080_8080_8080_8080;
+
+/// True if any byte of `x` is zero (classic SWAR zero-byte test)
+#[inline(always)]
+fn has_zero_byte(x: u64) -> bool {
+    x.wrapping_sub(LO) & !x & HI != 0
+}
+
+/// Broadcast a byte to all lanes of a word
+#[inline(always)]
+fn splat(b: u8) -> u64 {
+    LO * b as u64
+}
+
+/// Index of the first matching byte in a word, given the zero-byte mask
+#[inline(always)]
+fn first_match(x: u64) -> usize {
+    let mask = x.wrapping_sub(LO) & !x & HI;
+    (mask.trailing_zeros() / 8) as usize
+}
+
+/// Find the first occurrence of `needle`, 8 bytes at a time
+#[inline]
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    let pattern = splat(needle);
+    let mut offset = 0;
+
+    while offset + WORD <= haystack.len() {
+        let word = u64::from_le_bytes(haystack[offset..offset + WORD].try_into().unwrap());
+        let xored = word ^ pattern;
+        if has_zero_byte(xored) {
+            return Some(offset + first_match(xored));
+        }
+        offset += WORD;
+    }
+
+    haystack[offset..]
+        .iter()
+        .position(|&b| b == needle)
+        .map(|i| offset + i)
+}
+
+/// Find the first occurrence of either needle, 8 bytes at a time
+#[inline]
+pub fn find_byte2(haystack: &[u8], needle1: u8, needle2: u8) -> Option<usize> {
+    let pattern1 = splat(needle1);
+    let pattern2 = splat(needle2);
+    let mut offset = 0;
+
+    while offset + WORD <= haystack.len() {
+        let word = u64::from_le_bytes(haystack[offset..offset + WORD].try_into().unwrap());
+        let x1 = word ^ pattern1;
+        let x2 = word ^ pattern2;
+        if has_zero_byte(x1) || has_zero_byte(x2) {
+            let i1 = if has_zero_byte(x1) { first_match(x1) } else { WORD };
+            let i2 = if has_zero_byte(x2) { first_match(x2) } else { WORD };
+            return Some(offset + i1.min(i2));
+        }
+        offset += WORD;
+    }
+
+    haystack[offset..]
+        .iter()
+        .position(|&b| b == needle1 || b == needle2)
+        .map(|i| offset + i)
+}
+
+/// Lowercase ASCII letters in a word, leaving other bytes untouched
+///
+/// For each byte in `A`..=`Z`, sets bit 0x20; other bytes pass through.
+#[inline(always)]
+fn to_lower_word(x: u64) -> u64 {
+    // Byte is >= 'A' when (byte + (0x80 - 'A')) has its high bit set;
+    // combined with the <= 'Z' test this isolates uppercase letters.
+    // High bits are masked off first so lane additions cannot carry
+    // into the neighbouring byte; high-bit input bytes are excluded.
+    let seven = x & !HI;
+    let ge_a = seven.wrapping_add(splat(0x80 - b'A')) & HI;
+    let gt_z = seven.wrapping_add(splat(0x80 - b'Z' - 1)) & HI;
+    let is_upper = ge_a & !gt_z & !(x & HI);
+    x | (is_upper >> 2)
+}
+
+/// Case-insensitive ASCII comparison, 8 bytes at a time
+///
+/// Used for header name matching, where names arrive in arbitrary case.
+#[inline]
+pub fn eq_ignore_case(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut offset = 0;
+    while offset + WORD <= a.len() {
+        let wa = u64::from_le_bytes(a[offset..offset + WORD].try_into().unwrap());
+        let wb = u64::from_le_bytes(b[offset..offset + WORD].try_into().unwrap());
+        if to_lower_word(wa) != to_lower_word(wb) {
+            return false;
+        }
+        offset += WORD;
+    }
+
+    a[offset..].eq_ignore_ascii_case(&b[offset..])
+}
+
+/// Human-readable description of the scanning acceleration in use
+///
+/// The SWAR path is portable and always active; on x86-64 the host's
+/// vector capabilities are reported alongside for diagnostics.
+pub fn acceleration() -> &'static str {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") {
+            "swar (host: avx2)"
+        } else {
+            "swar (host: sse2)"
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        "swar (wasm)"
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "wasm32")))]
+    {
+        "swar"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scalar reference for the SWAR scanners
+    fn scalar_find2(haystack: &[u8], n1: u8, n2: u8) -> Option<usize> {
+        haystack.iter().position(|&b| b == n1 || b == n2)
+    }
+
+    #[test]
+    fn test_find_byte() {
+        let buf = b"GET /users?page=1 HTTP/1.1\r\n";
+        assert_eq!(find_byte(buf, b' '), Some(3));
+        assert_eq!(find_byte(buf, b'?'), Some(10));
+        assert_eq!(find_byte(buf, b'\r'), Some(26));
+        assert_eq!(find_byte(buf, b'X'), None);
+        assert_eq!(find_byte(b"", b' '), None);
+    }
+
+    #[test]
+    fn test_find_byte2_matches_scalar() {
+        let buf = b"GET /a/very/long/path/with/segments?q=1&x=2 HTTP/1.1\r\nHost: x\r\n\r\n";
+        for window in 0..buf.len() {
+            let hay = &buf[window..];
+            assert_eq!(find_byte2(hay, b'?', b' '), scalar_find2(hay, b'?', b' '));
+            assert_eq!(find_byte2(hay, b'\r', b'\n'), scalar_find2(hay, b'\r', b'\n'));
+        }
+    }
+
+    #[test]
+    fn test_eq_ignore_case() {
+        assert!(eq_ignore_case(b"Content-Type", b"content-type"));
+        assert!(eq_ignore_case(b"ACCEPT-ENCODING", b"accept-encoding"));
+        assert!(eq_ignore_case(b"X-Request-Id", b"x-request-id"));
+        assert!(!eq_ignore_case(b"content-type", b"content-len"));
+        assert!(!eq_ignore_case(b"content-type", b"content-typo"));
+        // Non-letter bytes must compare exactly, not mod 0x20
+        assert!(!eq_ignore_case(b"a-b", b"a_b"));
+        assert!(!eq_ignore_case(b"12345678x", b"12345678y"));
+    }
+
+    #[test]
+    fn test_acceleration_reports_swar() {
+        assert!(acceleration().starts_with("swar"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Plugin API
+//!
+//! A [`GustPlugin`] bundles middleware, routes, and startup/shutdown
+//! hooks behind one trait so third parties can extend the server
+//! without forking gust-core. Plugins are registered on the builder
+//! ([`crate::ServerBuilder::plugin`]); their middleware joins the
+//! chain, their routes join the router, and their lifecycle hooks run
+//! when `serve` starts and when its shutdown future resolves.
+//!
+//! A plugin can also live in a separate cdylib, declared with
+//! [`declare_plugin!`]. The exported surface is two `extern "C"`
+//! symbols (an ABI version check and a constructor), but the
+//! constructed value is a `Box<dyn GustPlugin>` — host and plugin
+//! must be built with the same compiler and gust-core version, which
+//! [`PLUGIN_ABI_VERSION`] guards. gust-core itself forbids unsafe
+//! code, so the `dlopen` side lives with the host binary (the gust
+//! CLI loads plugins via `--plugin`); any loader just resolves the
+//! two symbols and checks the version before calling the constructor.
+
+use crate::server::DynamicHandler;
+use crate::{Middleware, Request, Response};
+use std::future::Future;
+use std::sync::Arc;
+
+/// ABI version for cdylib plugins; bumped whenever the
+/// [`GustPlugin`] trait or its payload types change shape
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// A route contributed by a plugin
+pub struct PluginRoute {
+    pub method: String,
+    pub path: String,
+    pub handler: DynamicHandler,
+}
+
+impl PluginRoute {
+    pub fn new<F, Fut>(method: &str, path: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            handler: Arc::new(move |req| {
+                let handler = Arc::clone(&handler);
+                Box::pin(async move { handler(req).await })
+            }),
+        }
+    }
+}
+
+/// A server extension: middleware, routes, and lifecycle hooks
+pub trait GustPlugin: Send + Sync {
+    /// Plugin name, used in diagnostics
+    fn name(&self) -> &str;
+
+    /// Middleware to append to the server's chain, if any
+    fn middleware(&self) -> Option<Box<dyn Middleware>> {
+        None
+    }
+
+    /// Routes to add to the server's router
+    fn routes(&self) -> Vec<PluginRoute> {
+        Vec::new()
+    }
+
+    /// Called once when `serve` starts accepting connections
+    fn on_startup(&self) {}
+
+    /// Called once when the serve loop's shutdown future resolves
+    fn on_shutdown(&self) {}
+}
+
+/// Export a [`GustPlugin`] from a cdylib
+///
+/// ```ignore
+/// struct Metrics;
+/// impl GustPlugin for Metrics { /* ... */ }
+///
+/// gust_core::declare_plugin!(Metrics::default());
+/// ```
+#[macro_export]
+macro_rules! declare_plugin {
+    ($constructor:expr) => {
+        #[no_mangle]
+        pub extern "C" fn gust_plugin_abi_version() -> u32 {
+            $crate::plugin::PLUGIN_ABI_VERSION
+        }
+
+        /// # Safety
+        /// The caller takes ownership of the returned box
+        #[no_mangle]
+        pub extern "C" fn gust_plugin_create(
+        ) -> *mut Box<dyn $crate::plugin::GustPlugin> {
+            Box::into_raw(Box::new(
+                Box::new($constructor) as Box<dyn $crate::plugin::GustPlugin>
+            ))
+        }
+    };
+}
+
+/// Loaded plugins arrive as trait objects; let them register on the
+/// builder like any other plugin
+impl GustPlugin for Box<dyn GustPlugin> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn middleware(&self) -> Option<Box<dyn Middleware>> {
+        (**self).middleware()
+    }
+
+    fn routes(&self) -> Vec<PluginRoute> {
+        (**self).routes()
+    }
+
+    fn on_startup(&self) {
+        (**self).on_startup()
+    }
+
+    fn on_shutdown(&self) {
+        (**self).on_shutdown()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Method, Request, Server};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Default)]
+    struct CountingPlugin {
+        startups: AtomicU64,
+        shutdowns: AtomicU64,
+    }
+
+    struct TagResponses;
+
+    impl Middleware for TagResponses {
+        fn before(&self, _req: &mut Request) -> Option<Response> {
+            None
+        }
+
+        fn after(&self, _req: &Request, res: &mut Response) {
+            res.headers.push(("x-plugin".to_string(), "counting".to_string()));
+        }
+    }
+
+    impl GustPlugin for Arc<CountingPlugin> {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn middleware(&self) -> Option<Box<dyn Middleware>> {
+            Some(Box::new(TagResponses))
+        }
+
+        fn routes(&self) -> Vec<PluginRoute> {
+            vec![PluginRoute::new("GET", "/plugin/ping", |_req| async {
+                crate::ResponseBuilder::new(crate::StatusCode(200))
+                    .body("pong")
+                    .build()
+            })]
+        }
+
+        fn on_startup(&self) {
+            self.startups.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_shutdown(&self) {
+            self.shutdowns.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plugin_routes_and_middleware() {
+        let server = Server::builder()
+            .plugin(Arc::new(CountingPlugin::default()))
+            .build();
+
+        let response = server
+            .handle(Request::new(Method::Get, "/plugin/ping".to_string()))
+            .await;
+        assert_eq!(response.status.0, 200);
+        assert_eq!(&response.body[..], b"pong");
+        assert!(response
+            .headers
+            .iter()
+            .any(|(name, value)| name == "x-plugin" && value == "counting"));
+    }
+
+    #[tokio::test]
+    async fn test_plugin_lifecycle_hooks() {
+        let plugin = Arc::new(CountingPlugin::default());
+        let server = Server::builder().plugin(Arc::clone(&plugin)).build();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let serve = tokio::spawn(async move {
+            server
+                .serve_on(listener, async {
+                    let _ = shutdown_rx.await;
+                })
+                .await
+        });
+
+        // Startup fires before the loop accepts; give the task a beat
+        tokio::task::yield_now().await;
+        assert_eq!(plugin.startups.load(Ordering::Relaxed), 1);
+        assert_eq!(plugin.shutdowns.load(Ordering::Relaxed), 0);
+
+        shutdown_tx.send(()).unwrap();
+        serve.await.unwrap().unwrap();
+        assert_eq!(plugin.shutdowns.load(Ordering::Relaxed), 1);
+    }
+
+}
@@ -0,0 +1,222 @@
+//! Object pooling for hot-path allocations
+//!
+//! Request handling allocates header maps, param maps, and body
+//! buffers on every request. An [`ObjectPool`] keeps cleared instances
+//! around for reuse so steady-state traffic stops paying the allocator
+//! for these transient values.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Counters describing pool effectiveness
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Checkouts served from the idle list
+    pub reused: u64,
+    /// Checkouts that had to allocate because the pool was empty
+    pub created: u64,
+    /// Values returned to the idle list on drop
+    pub returned: u64,
+    /// Values dropped because the idle list was full or they were detached
+    pub discarded: u64,
+}
+
+impl PoolStats {
+    /// Fraction of checkouts served without allocating; 0 when unused
+    pub fn reuse_ratio(&self) -> f64 {
+        let total = self.reused + self.created;
+        if total == 0 {
+            0.0
+        } else {
+            self.reused as f64 / total as f64
+        }
+    }
+}
+
+struct PoolInner<T> {
+    /// Cleared values ready for reuse
+    idle: Mutex<Vec<T>>,
+    /// Maximum idle values kept; excess returns are dropped
+    max_idle: usize,
+    init: fn() -> T,
+    reset: fn(&mut T),
+    reused: AtomicU64,
+    created: AtomicU64,
+    returned: AtomicU64,
+    discarded: AtomicU64,
+}
+
+/// A pool of reusable values
+///
+/// Cloning is cheap and shares the underlying pool.
+pub struct ObjectPool<T> {
+    inner: Arc<PoolInner<T>>,
+}
+
+impl<T> Clone for ObjectPool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> ObjectPool<T> {
+    /// Create a pool keeping at most `max_idle` cleared values around
+    ///
+    /// `init` allocates a fresh value when the pool is empty; `reset`
+    /// clears a value before it goes back on the idle list.
+    pub fn new(max_idle: usize, init: fn() -> T, reset: fn(&mut T)) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                idle: Mutex::new(Vec::new()),
+                max_idle,
+                init,
+                reset,
+                reused: AtomicU64::new(0),
+                created: AtomicU64::new(0),
+                returned: AtomicU64::new(0),
+                discarded: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Check out a value, reusing an idle one when available
+    pub fn get(&self) -> Pooled<T> {
+        let recycled = self.inner.idle.lock().unwrap().pop();
+        let value = match recycled {
+            Some(value) => {
+                self.inner.reused.fetch_add(1, Ordering::Relaxed);
+                value
+            }
+            None => {
+                self.inner.created.fetch_add(1, Ordering::Relaxed);
+                (self.inner.init)()
+            }
+        };
+
+        Pooled {
+            value: Some(value),
+            pool: Arc::clone(&self.inner),
+        }
+    }
+
+    /// Number of idle values currently pooled
+    pub fn idle(&self) -> usize {
+        self.inner.idle.lock().unwrap().len()
+    }
+
+    /// Snapshot of pool counters
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            reused: self.inner.reused.load(Ordering::Relaxed),
+            created: self.inner.created.load(Ordering::Relaxed),
+            returned: self.inner.returned.load(Ordering::Relaxed),
+            discarded: self.inner.discarded.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Guard that returns its value to the pool on drop
+pub struct Pooled<T> {
+    value: Option<T>,
+    pool: Arc<PoolInner<T>>,
+}
+
+impl<T> Pooled<T> {
+    /// Take ownership of the value, skipping the return to the pool
+    pub fn detach(mut self) -> T {
+        self.pool.discarded.fetch_add(1, Ordering::Relaxed);
+        self.value.take().expect("pooled value already taken")
+    }
+}
+
+impl<T> Deref for Pooled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().expect("pooled value already taken")
+    }
+}
+
+impl<T> DerefMut for Pooled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().expect("pooled value already taken")
+    }
+}
+
+impl<T> Drop for Pooled<T> {
+    fn drop(&mut self) {
+        let Some(mut value) = self.value.take() else {
+            return;
+        };
+
+        let mut idle = self.pool.idle.lock().unwrap();
+        if idle.len() < self.pool.max_idle {
+            (self.pool.reset)(&mut value);
+            idle.push(value);
+            self.pool.returned.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.pool.discarded.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_reuse_after_return() {
+        let pool: ObjectPool<Vec<u8>> = ObjectPool::new(4, Vec::new, Vec::clear);
+
+        {
+            let mut buf = pool.get();
+            buf.extend_from_slice(b"hello");
+        }
+        assert_eq!(pool.idle(), 1);
+
+        // Reused value comes back cleared but keeps its capacity
+        let buf = pool.get();
+        assert!(buf.is_empty());
+        assert!(buf.capacity() >= 5);
+        assert_eq!(pool.idle(), 0);
+
+        let stats = pool.stats();
+        assert_eq!(stats.created, 1);
+        assert_eq!(stats.reused, 1);
+        assert_eq!(stats.returned, 1);
+        assert!((stats.reuse_ratio() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_max_idle_cap() {
+        let pool: ObjectPool<Vec<u8>> = ObjectPool::new(1, Vec::new, Vec::clear);
+
+        let a = pool.get();
+        let b = pool.get();
+        drop(a);
+        drop(b); // Pool already holds one idle value
+
+        assert_eq!(pool.idle(), 1);
+        let stats = pool.stats();
+        assert_eq!(stats.returned, 1);
+        assert_eq!(stats.discarded, 1);
+    }
+
+    #[test]
+    fn test_detach_skips_return() {
+        let pool: ObjectPool<HashMap<String, String>> =
+            ObjectPool::new(4, HashMap::new, HashMap::clear);
+
+        let mut map = pool.get();
+        map.insert("host".to_string(), "example.com".to_string());
+        let owned = map.detach();
+
+        assert_eq!(owned.len(), 1);
+        assert_eq!(pool.idle(), 0);
+        assert_eq!(pool.stats().discarded, 1);
+    }
+}
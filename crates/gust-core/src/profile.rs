@@ -0,0 +1,81 @@
+//! On-demand wall-clock profiling
+//!
+//! Aggregates time-weighted samples into flamegraph-compatible folded
+//! stacks (`frame;frame;frame weight`). The engine records where
+//! request time went while a capture is active — no symbolization or
+//! signal handling, so captures are safe to take in production and
+//! the output feeds straight into `flamegraph.pl` or speedscope.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// A time-weighted folded-stacks profile under construction
+///
+/// Stacks are semicolon-separated frames (`gust;handler;/users/:id`);
+/// weights are microseconds of wall time, summed per stack. Recording
+/// is a single mutex-guarded map insert, cheap enough to leave in the
+/// request path while a capture runs.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    stacks: Mutex<HashMap<String, u64>>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add wall time to a stack (frames joined with `;`)
+    pub fn record(&self, stack: &str, micros: u64) {
+        let mut stacks = self.stacks.lock().unwrap();
+        *stacks.entry(stack.to_string()).or_insert(0) += micros;
+    }
+
+    /// Number of distinct stacks recorded so far
+    pub fn stack_count(&self) -> usize {
+        self.stacks.lock().unwrap().len()
+    }
+
+    /// The profile in folded-stacks format, one `stack weight` line
+    /// per stack, sorted for deterministic output
+    pub fn folded(&self) -> String {
+        let stacks = self.stacks.lock().unwrap();
+        let mut lines: Vec<_> = stacks.iter().collect();
+        lines.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut out = String::new();
+        for (stack, micros) in lines {
+            out.push_str(stack);
+            out.push(' ');
+            out.push_str(&micros.to_string());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_merges_weights_per_stack() {
+        let profiler = Profiler::new();
+        profiler.record("gust;handler;/users/:id", 100);
+        profiler.record("gust;handler;/users/:id", 50);
+        profiler.record("gust;handler;/posts", 30);
+
+        assert_eq!(profiler.stack_count(), 2);
+        assert_eq!(
+            profiler.folded(),
+            "gust;handler;/posts 30\ngust;handler;/users/:id 150\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_profile_is_empty_output() {
+        let profiler = Profiler::new();
+        assert_eq!(profiler.stack_count(), 0);
+        assert_eq!(profiler.folded(), "");
+    }
+}
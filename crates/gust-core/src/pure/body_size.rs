@@ -70,6 +70,51 @@ pub fn exceeds_limit(size: u64, max_size: u64) -> bool {
     size > max_size
 }
 
+/// A per-content-type body limit (e.g. 100KB JSON, 50MB multipart)
+#[derive(Debug, Clone)]
+pub struct BodyLimitRule {
+    /// Media type (`application/json`) or prefix ending in `/`
+    /// (`multipart/` matches every multipart subtype)
+    pub content_type: String,
+    /// Maximum body size in bytes for matching requests
+    pub max_bytes: u64,
+}
+
+/// Pick the body limit for a request: the first rule matching the
+/// Content-Type wins, otherwise the global fallback applies.
+///
+/// Matching is case-insensitive on the media type alone — parameters
+/// like `; charset=utf-8` or `; boundary=...` are ignored. Requests
+/// without a Content-Type use the fallback.
+#[must_use]
+pub fn resolve_body_limit(
+    rules: &[BodyLimitRule],
+    content_type: Option<&str>,
+    fallback: u64,
+) -> u64 {
+    let Some(content_type) = content_type else {
+        return fallback;
+    };
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    for rule in rules {
+        let rule_type = rule.content_type.to_ascii_lowercase();
+        let matches = if rule_type.ends_with('/') {
+            media_type.starts_with(&rule_type)
+        } else {
+            media_type == rule_type
+        };
+        if matches {
+            return rule.max_bytes;
+        }
+    }
+    fallback
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +140,41 @@ mod tests {
         assert!(!exceeds_limit(100, 100));
         assert!(exceeds_limit(101, 100));
     }
+
+    #[test]
+    fn resolve_per_content_type() {
+        let rules = vec![
+            BodyLimitRule {
+                content_type: "application/json".to_string(),
+                max_bytes: 100 * 1024,
+            },
+            BodyLimitRule {
+                content_type: "multipart/".to_string(),
+                max_bytes: 50 * 1024 * 1024,
+            },
+        ];
+
+        // Exact match, parameters and case ignored
+        assert_eq!(
+            resolve_body_limit(&rules, Some("application/json; charset=utf-8"), 1024),
+            100 * 1024
+        );
+        assert_eq!(
+            resolve_body_limit(&rules, Some("Application/JSON"), 1024),
+            100 * 1024
+        );
+        // Prefix rule matches any subtype
+        assert_eq!(
+            resolve_body_limit(
+                &rules,
+                Some("multipart/form-data; boundary=abc"),
+                1024
+            ),
+            50 * 1024 * 1024
+        );
+        // Unmatched or missing content type falls back
+        assert_eq!(resolve_body_limit(&rules, Some("text/plain"), 1024), 1024);
+        assert_eq!(resolve_body_limit(&rules, None, 1024), 1024);
+        assert_eq!(resolve_body_limit(&[], Some("application/json"), 1024), 1024);
+    }
 }
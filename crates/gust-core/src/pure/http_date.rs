@@ -0,0 +1,267 @@
+//! HTTP date formatting and parsing (RFC 7231)
+//!
+//! No external dependencies; operates on Unix timestamps.
+
+const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Format a Unix timestamp (seconds) as an IMF-fixdate string
+///
+/// Example: `Sun, 06 Nov 1994 08:49:37 GMT`
+pub fn format_http_date(epoch_secs: u64) -> String {
+    let days_since_epoch = epoch_secs / 86400;
+    let secs_of_day = epoch_secs % 86400;
+
+    let (year, month, day) = civil_from_days(days_since_epoch as i64);
+    // 1970-01-01 was a Thursday (weekday index 4)
+    let weekday = ((days_since_epoch + 4) % 7) as usize;
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        DAYS[weekday],
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60,
+    )
+}
+
+/// Format a Unix timestamp (seconds) as an AWS `x-amz-date` string
+///
+/// Example: `20130524T000000Z`
+pub fn format_amz_date(epoch_secs: u64) -> String {
+    let days_since_epoch = epoch_secs / 86400;
+    let secs_of_day = epoch_secs % 86400;
+    let (year, month, day) = civil_from_days(days_since_epoch as i64);
+
+    format!(
+        "{}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60,
+    )
+}
+
+/// Parse an HTTP date to a Unix timestamp (seconds)
+///
+/// Accepts the preferred IMF-fixdate plus the two obsolete formats a
+/// server must still recognize (RFC 7231 section 7.1.1.1):
+/// - `Sun, 06 Nov 1994 08:49:37 GMT` (IMF-fixdate)
+/// - `Sunday, 06-Nov-94 08:49:37 GMT` (RFC 850)
+/// - `Sun Nov  6 08:49:37 1994` (ANSI C asctime)
+///
+/// Returns `None` for malformed input or dates before the epoch.
+pub fn parse_http_date(date: &str) -> Option<u64> {
+    let date = date.trim();
+    if let Some((_, rest)) = date.split_once(',') {
+        let rest = rest.trim();
+        if rest.split_whitespace().next()?.contains('-') {
+            parse_rfc850(rest)
+        } else {
+            parse_imf_fixdate(rest)
+        }
+    } else {
+        parse_asctime(date)
+    }
+}
+
+/// `06 Nov 1994 08:49:37 GMT` (weekday and comma already stripped)
+fn parse_imf_fixdate(rest: &str) -> Option<u64> {
+    let mut parts = rest.split_whitespace();
+    let day: u64 = parts.next()?.parse().ok()?;
+    let month = month_index(parts.next()?)?;
+    let year: i64 = parts.next()?.parse().ok()?;
+    let time = parse_time(parts.next()?)?;
+    if parts.next()? != "GMT" || parts.next().is_some() {
+        return None;
+    }
+    timestamp(year, month, day, time)
+}
+
+/// `06-Nov-94 08:49:37 GMT` (weekday and comma already stripped)
+fn parse_rfc850(rest: &str) -> Option<u64> {
+    let mut parts = rest.split_whitespace();
+    let mut date = parts.next()?.split('-');
+    let day: u64 = date.next()?.parse().ok()?;
+    let month = month_index(date.next()?)?;
+    let year: i64 = date.next()?.parse().ok()?;
+    if date.next().is_some() {
+        return None;
+    }
+    // Two-digit years: RFC 850 dates are long obsolete, so 70-99 map
+    // to 19xx and 00-69 to 20xx
+    let year = if year < 70 {
+        year + 2000
+    } else if year < 100 {
+        year + 1900
+    } else {
+        year
+    };
+    let time = parse_time(parts.next()?)?;
+    if parts.next()? != "GMT" || parts.next().is_some() {
+        return None;
+    }
+    timestamp(year, month, day, time)
+}
+
+/// `Sun Nov  6 08:49:37 1994` (no comma, day may be space-padded)
+fn parse_asctime(date: &str) -> Option<u64> {
+    let mut parts = date.split_whitespace();
+    let _weekday = parts.next()?;
+    let month = month_index(parts.next()?)?;
+    let day: u64 = parts.next()?.parse().ok()?;
+    let time = parse_time(parts.next()?)?;
+    let year: i64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    timestamp(year, month, day, time)
+}
+
+/// Month abbreviation to 1-based index
+fn month_index(month: &str) -> Option<u64> {
+    MONTHS
+        .iter()
+        .position(|m| m.eq_ignore_ascii_case(month))
+        .map(|i| i as u64 + 1)
+}
+
+/// `08:49:37` to seconds of day
+fn parse_time(time: &str) -> Option<u64> {
+    let mut parts = time.split(':');
+    let hour: u64 = parts.next()?.parse().ok()?;
+    let minute: u64 = parts.next()?.parse().ok()?;
+    let second: u64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    Some(hour * 3600 + minute * 60 + second)
+}
+
+/// Combine calendar fields into a Unix timestamp, rejecting invalid
+/// dates and anything before the epoch
+fn timestamp(year: i64, month: u64, day: u64, secs_of_day: u64) -> Option<u64> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let days = days_from_civil(year, month as u32, day as u32);
+    // Round-trip to reject out-of-range days like Feb 30
+    if civil_from_days(days) != (year, month as u32, day as u32) {
+        return None;
+    }
+    if days < 0 {
+        return None;
+    }
+    Some(days as u64 * 86400 + secs_of_day)
+}
+
+/// Convert (year, month, day) to days since 1970-01-01
+///
+/// Inverse of [`civil_from_days`], same algorithm family.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as u64; // year of era [0, 399]
+    let mp = if m > 2 { m - 3 } else { m + 9 } as u64; // March-based month
+    let doy = (153 * mp + 2) / 5 + d as u64 - 1; // day of year [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // day of era
+    era * 146097 + doe as i64 - 719468
+}
+
+/// Convert days since 1970-01-01 to (year, month, day)
+///
+/// Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64; // day of era [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // year of era [0, 399]
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // day of year [0, 365]
+    let mp = (5 * doy + 2) / 153; // month index [0, 11], March-based
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_epoch() {
+        assert_eq!(format_http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+    }
+
+    #[test]
+    fn test_format_rfc_example() {
+        // RFC 7231's canonical example date
+        assert_eq!(format_http_date(784111777), "Sun, 06 Nov 1994 08:49:37 GMT");
+    }
+
+    #[test]
+    fn test_format_amz_date() {
+        // AWS SigV4 documentation example date
+        assert_eq!(format_amz_date(1369353600), "20130524T000000Z");
+    }
+
+    #[test]
+    fn test_format_leap_year() {
+        // 2024-02-29 12:00:00 UTC
+        assert_eq!(format_http_date(1709208000), "Thu, 29 Feb 2024 12:00:00 GMT");
+    }
+
+    #[test]
+    fn test_parse_all_three_formats() {
+        // RFC 7231's canonical example date in each accepted format
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784111777)
+        );
+        assert_eq!(
+            parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"),
+            Some(784111777)
+        );
+        assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), Some(784111777));
+    }
+
+    #[test]
+    fn test_parse_roundtrips_format() {
+        for &epoch in &[0u64, 784111777, 1709208000, 4102444799] {
+            assert_eq!(parse_http_date(&format_http_date(epoch)), Some(epoch));
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        assert_eq!(parse_http_date(""), None);
+        assert_eq!(parse_http_date("not a date"), None);
+        // Missing GMT suffix
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37"), None);
+        // Out-of-range fields
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 24:00:00 GMT"), None);
+        assert_eq!(parse_http_date("Wed, 30 Feb 1994 08:49:37 GMT"), None);
+        // Pre-epoch dates have no u64 representation
+        assert_eq!(parse_http_date("Tue, 31 Dec 1969 23:59:59 GMT"), None);
+    }
+
+    #[test]
+    fn test_parse_two_digit_year_windows() {
+        // 70-99 -> 19xx, 00-69 -> 20xx
+        assert_eq!(
+            parse_http_date("Thursday, 01-Jan-70 00:00:00 GMT"),
+            Some(0)
+        );
+        assert_eq!(
+            parse_http_date("Saturday, 01-Jan-00 00:00:00 GMT"),
+            Some(946684800)
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! IDN host normalization (punycode, RFC 3492)
+//!
+//! Converts internationalized hostnames to their ASCII (ACE) form so
+//! host comparisons and routing see one canonical spelling regardless
+//! of whether a client sent `bücher.example` or `xn--bcher-kva.example`.
+
+// RFC 3492 parameters
+const BASE: u32 = 36;
+const TMIN: u32 = 1;
+const TMAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+
+/// Normalize a host (optionally with a `:port` suffix) to ASCII
+///
+/// ASCII labels are lowercased as-is; labels with non-ASCII characters
+/// are lowercased and punycode-encoded with the `xn--` ACE prefix.
+/// Bracketed IPv6 literals pass through untouched (lowercased). A
+/// label the encoder cannot represent is left in lowercased Unicode
+/// rather than dropped.
+pub fn domain_to_ascii(host: &str) -> String {
+    if host.starts_with('[') {
+        // IPv6 literal, possibly with a port: nothing to encode
+        return host.to_ascii_lowercase();
+    }
+    let (name, port) = match host.rsplit_once(':') {
+        Some((n, p)) if !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()) => (n, Some(p)),
+        _ => (host, None),
+    };
+
+    let labels: Vec<String> = name
+        .split('.')
+        .map(|label| {
+            if label.is_ascii() {
+                label.to_ascii_lowercase()
+            } else {
+                let lower = label.to_lowercase();
+                match punycode_encode(&lower) {
+                    Some(encoded) => format!("xn--{}", encoded),
+                    None => lower,
+                }
+            }
+        })
+        .collect();
+
+    let mut out = labels.join(".");
+    if let Some(port) = port {
+        out.push(':');
+        out.push_str(port);
+    }
+    out
+}
+
+/// Encode one label per RFC 3492; `None` on (absurdly long) overflow
+fn punycode_encode(input: &str) -> Option<String> {
+    let code_points: Vec<u32> = input.chars().map(|c| c as u32).collect();
+    let mut output: String = input.chars().filter(|c| c.is_ascii()).collect();
+    let basic_len = output.chars().count() as u32;
+    if basic_len > 0 {
+        output.push('-');
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut handled = basic_len;
+
+    while (handled as usize) < code_points.len() {
+        // Next code point to represent, in ascending order
+        let m = code_points.iter().copied().filter(|&c| c >= n).min()?;
+        delta = delta.checked_add((m - n).checked_mul(handled + 1)?)?;
+        n = m;
+
+        for &c in &code_points {
+            if c < n {
+                delta = delta.checked_add(1)?;
+            }
+            if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = k.saturating_sub(bias).clamp(TMIN, TMAX);
+                    if q < t {
+                        break;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic_len);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta += 1;
+        n += 1;
+    }
+    Some(output)
+}
+
+/// Bias adaptation (RFC 3492 §6.1)
+fn adapt(mut delta: u32, num_points: u32, first_time: bool) -> u32 {
+    delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - TMIN) * TMAX) / 2 {
+        delta /= BASE - TMIN;
+        k += BASE;
+    }
+    k + (((BASE - TMIN + 1) * delta) / (delta + SKEW))
+}
+
+fn encode_digit(d: u32) -> char {
+    if d < 26 {
+        (b'a' + d as u8) as char
+    } else {
+        (b'0' + (d - 26) as u8) as char
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_host_lowercased() {
+        assert_eq!(domain_to_ascii("API.Example.COM"), "api.example.com");
+        assert_eq!(domain_to_ascii("localhost:8080"), "localhost:8080");
+    }
+
+    #[test]
+    fn test_idn_labels_encode() {
+        assert_eq!(domain_to_ascii("bücher.example"), "xn--bcher-kva.example");
+        assert_eq!(domain_to_ascii("日本語.jp"), "xn--wgv71a119e.jp");
+        // Mixed: only the non-ASCII label gets the ACE prefix
+        assert_eq!(
+            domain_to_ascii("shop.münchen.de:443"),
+            "shop.xn--mnchen-3ya.de:443"
+        );
+    }
+
+    #[test]
+    fn test_uppercase_unicode_normalizes() {
+        // Case-folds before encoding, matching the lowercase form
+        assert_eq!(domain_to_ascii("BÜCHER.example"), "xn--bcher-kva.example");
+    }
+
+    #[test]
+    fn test_ipv6_literal_untouched() {
+        assert_eq!(domain_to_ascii("[::1]:3000"), "[::1]:3000");
+    }
+}
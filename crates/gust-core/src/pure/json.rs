@@ -0,0 +1,473 @@
+//! Minimal JSON parser and serializer.
+//!
+//! Parses into the `Value` type shared with schema validation so
+//! protocol adapters (GraphQL, JSON-RPC) can inspect envelopes in
+//! Rust without pulling in serde.
+
+use crate::middleware::validate::Value;
+use std::collections::HashMap;
+
+/// JSON parse error with byte position
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonError {
+    /// Byte offset where parsing failed
+    pub position: usize,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl JsonError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+/// Parse a JSON document into a `Value`
+///
+/// Rejects trailing content after the top-level value.
+pub fn parse_json(input: &str) -> Result<Value, JsonError> {
+    let mut parser = Parser {
+        bytes: input.as_bytes(),
+        pos: 0,
+        depth: 0,
+    };
+    parser.skip_whitespace();
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.bytes.len() {
+        return Err(JsonError::new(parser.pos, "Unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+/// Serialize a `Value` back to compact JSON
+pub fn serialize_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(value, &mut out);
+    out
+}
+
+/// Maximum nesting depth before parsing is aborted
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while let Some(&b) = self.bytes.get(self.pos) {
+            if b == b' ' || b == b'\t' || b == b'\n' || b == b'\r' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(JsonError::new(
+                self.pos,
+                format!("Expected '{}'", byte as char),
+            ))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, JsonError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(JsonError::new(self.pos, "Maximum nesting depth exceeded"));
+        }
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => Ok(Value::String(self.parse_string()?)),
+            Some(b't') | Some(b'f') => self.parse_bool(),
+            Some(b'n') => self.parse_null(),
+            Some(b'-') | Some(b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(JsonError::new(self.pos, "Unexpected character")),
+            None => Err(JsonError::new(self.pos, "Unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Value, JsonError> {
+        self.expect(b'{')?;
+        self.depth += 1;
+        let mut map = HashMap::new();
+        self.skip_whitespace();
+
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            self.depth -= 1;
+            return Ok(Value::Object(map));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+            let value = self.parse_value()?;
+            map.insert(key, value);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.pos += 1;
+                }
+                Some(b'}') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(JsonError::new(self.pos, "Expected ',' or '}'")),
+            }
+        }
+
+        self.depth -= 1;
+        Ok(Value::Object(map))
+    }
+
+    fn parse_array(&mut self) -> Result<Value, JsonError> {
+        self.expect(b'[')?;
+        self.depth += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            self.depth -= 1;
+            return Ok(Value::Array(items));
+        }
+
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.pos += 1;
+                }
+                Some(b']') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(JsonError::new(self.pos, "Expected ',' or ']'")),
+            }
+        }
+
+        self.depth -= 1;
+        Ok(Value::Array(items))
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(b'"') => out.push('"'),
+                        Some(b'\\') => out.push('\\'),
+                        Some(b'/') => out.push('/'),
+                        Some(b'b') => out.push('\u{0008}'),
+                        Some(b'f') => out.push('\u{000C}'),
+                        Some(b'n') => out.push('\n'),
+                        Some(b'r') => out.push('\r'),
+                        Some(b't') => out.push('\t'),
+                        Some(b'u') => {
+                            self.pos += 1;
+                            let code = self.parse_hex4()?;
+                            // Handle UTF-16 surrogate pairs
+                            let ch = if (0xD800..0xDC00).contains(&code) {
+                                if self.peek() == Some(b'\\') {
+                                    self.pos += 1;
+                                    self.expect(b'u')?;
+                                    let low = self.parse_hex4()?;
+                                    if !(0xDC00..0xE000).contains(&low) {
+                                        return Err(JsonError::new(self.pos, "Invalid surrogate pair"));
+                                    }
+                                    let combined =
+                                        0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                                    char::from_u32(combined)
+                                } else {
+                                    return Err(JsonError::new(self.pos, "Unpaired surrogate"));
+                                }
+                            } else {
+                                char::from_u32(code)
+                            };
+                            match ch {
+                                Some(c) => out.push(c),
+                                None => {
+                                    return Err(JsonError::new(self.pos, "Invalid unicode escape"))
+                                }
+                            }
+                            continue; // parse_hex4 already advanced
+                        }
+                        _ => return Err(JsonError::new(self.pos, "Invalid escape sequence")),
+                    }
+                    self.pos += 1;
+                }
+                Some(b) if b < 0x20 => {
+                    return Err(JsonError::new(self.pos, "Unescaped control character"));
+                }
+                Some(_) => {
+                    // Copy a full UTF-8 sequence
+                    let start = self.pos;
+                    self.pos += 1;
+                    while self.pos < self.bytes.len() && (self.bytes[self.pos] & 0xC0) == 0x80 {
+                        self.pos += 1;
+                    }
+                    match std::str::from_utf8(&self.bytes[start..self.pos]) {
+                        Ok(s) => out.push_str(s),
+                        Err(_) => return Err(JsonError::new(start, "Invalid UTF-8")),
+                    }
+                }
+                None => return Err(JsonError::new(self.pos, "Unterminated string")),
+            }
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonError> {
+        let mut code: u32 = 0;
+        for _ in 0..4 {
+            let digit = match self.peek() {
+                Some(b @ b'0'..=b'9') => (b - b'0') as u32,
+                Some(b @ b'a'..=b'f') => (b - b'a' + 10) as u32,
+                Some(b @ b'A'..=b'F') => (b - b'A' + 10) as u32,
+                _ => return Err(JsonError::new(self.pos, "Invalid hex digit")),
+            };
+            code = (code << 4) | digit;
+            self.pos += 1;
+        }
+        Ok(code)
+    }
+
+    fn parse_number(&mut self) -> Result<Value, JsonError> {
+        let start = self.pos;
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            while matches!(self.peek(), Some(b'0'..=b'9')) {
+                self.pos += 1;
+            }
+        }
+        if matches!(self.peek(), Some(b'e') | Some(b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+') | Some(b'-')) {
+                self.pos += 1;
+            }
+            while matches!(self.peek(), Some(b'0'..=b'9')) {
+                self.pos += 1;
+            }
+        }
+
+        let text = std::str::from_utf8(&self.bytes[start..self.pos])
+            .map_err(|_| JsonError::new(start, "Invalid number"))?;
+        let number: f64 = text
+            .parse()
+            .map_err(|_| JsonError::new(start, "Invalid number"))?;
+        // Exponents like 6e666 overflow f64 to infinity, which has no
+        // JSON representation (found by the parse_json fuzz target)
+        if !number.is_finite() {
+            return Err(JsonError::new(start, "Number out of range"));
+        }
+        Ok(Value::Number(number))
+    }
+
+    fn parse_bool(&mut self) -> Result<Value, JsonError> {
+        if self.bytes[self.pos..].starts_with(b"true") {
+            self.pos += 4;
+            Ok(Value::Bool(true))
+        } else if self.bytes[self.pos..].starts_with(b"false") {
+            self.pos += 5;
+            Ok(Value::Bool(false))
+        } else {
+            Err(JsonError::new(self.pos, "Invalid literal"))
+        }
+    }
+
+    fn parse_null(&mut self) -> Result<Value, JsonError> {
+        if self.bytes[self.pos..].starts_with(b"null") {
+            self.pos += 4;
+            Ok(Value::Null)
+        } else {
+            Err(JsonError::new(self.pos, "Invalid literal"))
+        }
+    }
+}
+
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(true) => out.push_str("true"),
+        Value::Bool(false) => out.push_str("false"),
+        Value::Number(n) => {
+            if !n.is_finite() {
+                // Infinity/NaN cannot be represented in JSON
+                out.push_str("null");
+            } else if n.fract() == 0.0 && n.abs() < 9e15 {
+                out.push_str(&format!("{}", *n as i64));
+            } else {
+                out.push_str(&format!("{}", n));
+            }
+        }
+        Value::String(s) => write_json_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            out.push('{');
+            // Sort keys for deterministic output (HashMap iteration order is random)
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for (i, key) in keys.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_string(key, out);
+                out.push(':');
+                write_value(&map[*key], out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// Escape and quote a string for JSON output
+pub fn write_json_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                out.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_primitives() {
+        assert_eq!(parse_json("null").unwrap(), Value::Null);
+        assert_eq!(parse_json("true").unwrap(), Value::Bool(true));
+        assert_eq!(parse_json("false").unwrap(), Value::Bool(false));
+        assert_eq!(parse_json("42").unwrap(), Value::Number(42.0));
+        assert_eq!(parse_json("-1.5e2").unwrap(), Value::Number(-150.0));
+        assert_eq!(
+            parse_json("\"hello\"").unwrap(),
+            Value::String("hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reject_overflowing_exponent() {
+        // 6e666 overflows f64; infinity has no JSON serialization
+        assert!(parse_json("6e666").is_err());
+        assert!(parse_json("-1e999").is_err());
+        assert_eq!(serialize_json(&Value::Number(f64::INFINITY)), "null");
+    }
+
+    #[test]
+    fn test_parse_string_escapes() {
+        assert_eq!(
+            parse_json(r#""a\nb\t\"c\"""#).unwrap(),
+            Value::String("a\nb\t\"c\"".to_string())
+        );
+        assert_eq!(
+            parse_json(r#""é""#).unwrap(),
+            Value::String("é".to_string())
+        );
+        // Surrogate pair
+        assert_eq!(
+            parse_json(r#""😀""#).unwrap(),
+            Value::String("😀".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_nested() {
+        let value = parse_json(r#"{"a": [1, 2, {"b": null}], "c": "d"}"#).unwrap();
+        let obj = value.as_object().unwrap();
+        let arr = obj.get("a").unwrap().as_array().unwrap();
+        assert_eq!(arr.len(), 3);
+        assert_eq!(obj.get("c").unwrap().as_str(), Some("d"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_json("").is_err());
+        assert!(parse_json("{").is_err());
+        assert!(parse_json("[1,]").is_err());
+        assert!(parse_json("{\"a\": 1} extra").is_err());
+        assert!(parse_json("\"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let deep = "[".repeat(200) + &"]".repeat(200);
+        assert!(parse_json(&deep).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let input = r#"{"a":[1,2.5,true,null],"b":"x\"y"}"#;
+        let value = parse_json(input).unwrap();
+        let output = serialize_json(&value);
+        assert_eq!(parse_json(&output).unwrap(), value);
+    }
+
+    #[test]
+    fn test_serialize_integers_without_fraction() {
+        assert_eq!(serialize_json(&Value::Number(3.0)), "3");
+        assert_eq!(serialize_json(&Value::Number(3.25)), "3.25");
+    }
+}
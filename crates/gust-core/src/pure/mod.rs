@@ -11,7 +11,10 @@ pub use rate_limit_math::{
     fixed_window_decision, rate_limit_headers, sliding_window_decision, RateLimitDecision,
 };
 
-pub use body_size::{exceeds_limit, format_size, parse_size_bytes, parse_size_str};
+pub use body_size::{
+    exceeds_limit, format_size, parse_size_bytes, parse_size_str, resolve_body_limit,
+    BodyLimitRule,
+};
 pub use cors_origin::{create_cors_headers, get_allowed_origin, is_origin_allowed};
 
 pub mod cookie;
@@ -25,3 +28,15 @@ pub use http_range::{content_range, is_range_satisfiable, is_websocket_upgrade,
 
 pub mod sse_format;
 pub use sse_format::{format_sse, format_sse_event, sse_event, sse_headers_block};
+
+pub mod json;
+pub use json::{parse_json, serialize_json, JsonError};
+
+pub mod toml;
+pub use toml::{parse_toml, TomlError};
+
+pub mod http_date;
+pub use http_date::{format_amz_date, format_http_date, parse_http_date};
+
+pub mod idna;
+pub use idna::domain_to_ascii;
@@ -0,0 +1,386 @@
+//! Minimal TOML parser.
+//!
+//! Parses configuration files into the `Value` type shared with the
+//! JSON parser and schema validation, so config loading can inspect
+//! either format through one representation without pulling in serde.
+//!
+//! Supports the subset used by configuration files: `[table]` headers
+//! (including dotted nesting), bare and quoted keys, basic and literal
+//! strings, integers, floats, booleans, and single-line arrays.
+
+use crate::middleware::validate::Value;
+use std::collections::HashMap;
+
+/// TOML parse error with line number
+#[derive(Debug, Clone, PartialEq)]
+pub struct TomlError {
+    /// 1-based line where parsing failed
+    pub line: usize,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl TomlError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for TomlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at line {}", self.message, self.line)
+    }
+}
+
+/// Parse a TOML document into a `Value::Object`
+pub fn parse_toml(input: &str) -> Result<Value, TomlError> {
+    let mut root: HashMap<String, Value> = HashMap::new();
+    let mut table_path: Vec<String> = Vec::new();
+
+    for (idx, raw_line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            if header.starts_with('[') {
+                return Err(TomlError::new(
+                    line_no,
+                    "Arrays of tables ([[...]]) are not supported",
+                ));
+            }
+            let header = header
+                .strip_suffix(']')
+                .ok_or_else(|| TomlError::new(line_no, "Unterminated table header"))?
+                .trim();
+            if header.is_empty() {
+                return Err(TomlError::new(line_no, "Empty table header"));
+            }
+            table_path = header
+                .split('.')
+                .map(|part| parse_key(part.trim(), line_no))
+                .collect::<Result<_, _>>()?;
+            // Materialize the table so empty sections still appear
+            table_mut(&mut root, &table_path, line_no)?;
+            continue;
+        }
+
+        let (key_part, value_part) = line
+            .split_once('=')
+            .ok_or_else(|| TomlError::new(line_no, "Expected 'key = value'"))?;
+        let key = parse_key(key_part.trim(), line_no)?;
+        let value = parse_value(value_part.trim(), line_no)?;
+
+        let table = table_mut(&mut root, &table_path, line_no)?;
+        if table.contains_key(&key) {
+            return Err(TomlError::new(
+                line_no,
+                format!("Duplicate key '{}'", key),
+            ));
+        }
+        table.insert(key, value);
+    }
+
+    Ok(Value::Object(root))
+}
+
+/// Strip a `#` comment, ignoring `#` inside quoted strings
+fn strip_comment(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let mut in_basic = false;
+    let mut in_literal = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if in_basic => i += 1,
+            b'"' if !in_literal => in_basic = !in_basic,
+            b'\'' if !in_basic => in_literal = !in_literal,
+            b'#' if !in_basic && !in_literal => return &line[..i],
+            _ => {}
+        }
+        i += 1;
+    }
+    line
+}
+
+/// Walk (and create) nested tables for a `[a.b]` path
+fn table_mut<'a>(
+    root: &'a mut HashMap<String, Value>,
+    path: &[String],
+    line_no: usize,
+) -> Result<&'a mut HashMap<String, Value>, TomlError> {
+    let mut current = root;
+    for part in path {
+        let entry = current
+            .entry(part.clone())
+            .or_insert_with(|| Value::Object(HashMap::new()));
+        current = match entry {
+            Value::Object(obj) => obj,
+            _ => {
+                return Err(TomlError::new(
+                    line_no,
+                    format!("'{}' is already defined as a non-table value", part),
+                ))
+            }
+        };
+    }
+    Ok(current)
+}
+
+fn parse_key(key: &str, line_no: usize) -> Result<String, TomlError> {
+    if let Some(quoted) = key.strip_prefix('"').and_then(|k| k.strip_suffix('"')) {
+        return Ok(quoted.to_string());
+    }
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(TomlError::new(line_no, format!("Invalid key '{}'", key)));
+    }
+    Ok(key.to_string())
+}
+
+fn parse_value(value: &str, line_no: usize) -> Result<Value, TomlError> {
+    if value.is_empty() {
+        return Err(TomlError::new(line_no, "Missing value"));
+    }
+
+    if value.starts_with('[') {
+        return parse_array(value, line_no);
+    }
+
+    if value.starts_with('{') {
+        return Err(TomlError::new(line_no, "Inline tables are not supported"));
+    }
+
+    if let Some(rest) = value.strip_prefix('"') {
+        let (string, consumed) = parse_basic_string(rest, line_no)?;
+        if !rest[consumed..].trim().is_empty() {
+            return Err(TomlError::new(line_no, "Unexpected trailing characters"));
+        }
+        return Ok(Value::String(string));
+    }
+
+    if let Some(rest) = value.strip_prefix('\'') {
+        let end = rest
+            .find('\'')
+            .ok_or_else(|| TomlError::new(line_no, "Unterminated string"))?;
+        if !rest[end + 1..].trim().is_empty() {
+            return Err(TomlError::new(line_no, "Unexpected trailing characters"));
+        }
+        return Ok(Value::String(rest[..end].to_string()));
+    }
+
+    match value {
+        "true" => return Ok(Value::Bool(true)),
+        "false" => return Ok(Value::Bool(false)),
+        _ => {}
+    }
+
+    // Numbers: TOML allows underscore separators
+    let numeric = value.replace('_', "");
+    if let Ok(n) = numeric.parse::<f64>() {
+        if n.is_finite() {
+            return Ok(Value::Number(n));
+        }
+    }
+
+    Err(TomlError::new(
+        line_no,
+        format!("Invalid value '{}' (strings must be quoted)", value),
+    ))
+}
+
+/// Parse a single-line array of scalar values
+fn parse_array(value: &str, line_no: usize) -> Result<Value, TomlError> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or_else(|| {
+            TomlError::new(line_no, "Arrays must open and close on the same line")
+        })?;
+
+    let mut items = Vec::new();
+    for item in split_array_items(inner) {
+        let item = item.trim();
+        if item.is_empty() {
+            continue; // Trailing comma
+        }
+        items.push(parse_value(item, line_no)?);
+    }
+    Ok(Value::Array(items))
+}
+
+/// Split array items on commas, ignoring commas inside quoted strings
+fn split_array_items(inner: &str) -> Vec<&str> {
+    let bytes = inner.as_bytes();
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut in_basic = false;
+    let mut in_literal = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if in_basic => i += 1,
+            b'"' if !in_literal => in_basic = !in_basic,
+            b'\'' if !in_basic => in_literal = !in_literal,
+            b',' if !in_basic && !in_literal => {
+                items.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    items.push(&inner[start..]);
+    items
+}
+
+/// Parse a basic string body (after the opening quote), returning the
+/// decoded string and the bytes consumed including the closing quote
+fn parse_basic_string(rest: &str, line_no: usize) -> Result<(String, usize), TomlError> {
+    let bytes = rest.as_bytes();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => return Ok((out, i + 1)),
+            b'\\' => {
+                let escape = bytes
+                    .get(i + 1)
+                    .ok_or_else(|| TomlError::new(line_no, "Unterminated escape"))?;
+                match escape {
+                    b'"' => out.push('"'),
+                    b'\\' => out.push('\\'),
+                    b'n' => out.push('\n'),
+                    b't' => out.push('\t'),
+                    b'r' => out.push('\r'),
+                    b'u' => {
+                        let hex = rest.get(i + 2..i + 6).ok_or_else(|| {
+                            TomlError::new(line_no, "Invalid unicode escape")
+                        })?;
+                        let code = u32::from_str_radix(hex, 16).map_err(|_| {
+                            TomlError::new(line_no, "Invalid unicode escape")
+                        })?;
+                        let c = char::from_u32(code).ok_or_else(|| {
+                            TomlError::new(line_no, "Invalid unicode escape")
+                        })?;
+                        out.push(c);
+                        i += 6;
+                        continue;
+                    }
+                    _ => {
+                        return Err(TomlError::new(
+                            line_no,
+                            format!("Unknown escape '\\{}'", *escape as char),
+                        ))
+                    }
+                }
+                i += 2;
+            }
+            _ => {
+                // Copy the full UTF-8 character
+                let c = rest[i..].chars().next().unwrap();
+                out.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+    Err(TomlError::new(line_no, "Unterminated string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obj(value: &Value) -> &HashMap<String, Value> {
+        value.as_object().expect("expected object")
+    }
+
+    #[test]
+    fn test_parse_scalars_and_tables() {
+        let doc = "\
+# server config
+port = 3000
+hostname = \"0.0.0.0\"
+http2 = false
+
+[cors]
+origins = [\"https://a.example\", \"https://b.example\"]
+max_age = 600
+
+[rate_limit]
+max_requests = 100
+window_seconds = 60
+";
+        let value = parse_toml(doc).unwrap();
+        let root = obj(&value);
+
+        assert_eq!(root.get("port"), Some(&Value::Number(3000.0)));
+        assert_eq!(
+            root.get("hostname"),
+            Some(&Value::String("0.0.0.0".to_string()))
+        );
+        assert_eq!(root.get("http2"), Some(&Value::Bool(false)));
+
+        let cors = obj(root.get("cors").unwrap());
+        assert_eq!(
+            cors.get("origins").unwrap().as_array().unwrap().len(),
+            2
+        );
+        assert_eq!(cors.get("max_age"), Some(&Value::Number(600.0)));
+
+        let rate_limit = obj(root.get("rate_limit").unwrap());
+        assert_eq!(rate_limit.get("max_requests"), Some(&Value::Number(100.0)));
+    }
+
+    #[test]
+    fn test_parse_nested_tables_and_strings() {
+        let doc = "\
+[tls]
+cert_path = 'certs/server.pem' # literal string
+key_path = \"certs/key # not a comment.pem\"
+
+[a.b]
+c = \"nested\"
+";
+        let value = parse_toml(doc).unwrap();
+        let root = obj(&value);
+
+        let tls = obj(root.get("tls").unwrap());
+        assert_eq!(
+            tls.get("cert_path"),
+            Some(&Value::String("certs/server.pem".to_string()))
+        );
+        assert_eq!(
+            tls.get("key_path"),
+            Some(&Value::String("certs/key # not a comment.pem".to_string()))
+        );
+
+        let b = obj(obj(root.get("a").unwrap()).get("b").unwrap());
+        assert_eq!(b.get("c"), Some(&Value::String("nested".to_string())));
+    }
+
+    #[test]
+    fn test_parse_errors_carry_line_numbers() {
+        let err = parse_toml("port = 3000\nbad line\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("key = value"));
+
+        let err = parse_toml("name = unquoted\n").unwrap_err();
+        assert!(err.message.contains("quoted"));
+
+        let err = parse_toml("port = 1\nport = 2\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("Duplicate"));
+
+        assert!(parse_toml("[tls\n").is_err());
+        assert!(parse_toml("x = {a = 1}\n").is_err());
+    }
+}
@@ -0,0 +1,380 @@
+//! Minimal HTTP/1.1 server loop without hyper (`raw-http1` feature)
+//!
+//! A benchmark/plaintext engine: reads requests with the offset-based
+//! parser, serves pre-rendered static responses straight from the
+//! router, and falls back to [`ServerState::handle`] for dynamic
+//! routes. Keep-alive and pipelined requests are handled by draining
+//! the read buffer in place; there is no TLS, HTTP/2, upgrade, or
+//! chunked-transfer support — use the hyper engine for those.
+
+use crate::parser::{find_header, parse_request, scan, HeaderOffsets, Method, MAX_HEADERS};
+use crate::server::{ConnectionStats, ConnectionTracker, DynamicHandler, ServerState};
+use crate::{Request, Response};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Pre-rendered response lookup for the raw engine fast path
+///
+/// Returns complete HTTP/1.1 response bytes written to the socket
+/// verbatim, bypassing [`Request`]/[`Response`] construction entirely.
+pub type StaticLookup = Arc<dyn Fn(Method, &str) -> Option<bytes::Bytes> + Send + Sync>;
+
+/// Hard cap on a buffered request (request line + headers + body)
+const MAX_REQUEST_SIZE: usize = 16 * 1024 * 1024;
+
+const BAD_REQUEST: &[u8] =
+    b"HTTP/1.1 400 Bad Request\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+const TOO_LARGE: &[u8] =
+    b"HTTP/1.1 413 Payload Too Large\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+
+/// Accept loop for the raw HTTP/1.1 engine over a [`ServerState`]
+///
+/// Runs until the listener errors or the tracker signals shutdown;
+/// connection reuse is reported to the tracker like the hyper engine.
+pub async fn serve(
+    listener: TcpListener,
+    state: Arc<ServerState>,
+    tracker: Arc<ConnectionTracker>,
+) -> std::io::Result<()> {
+    let static_state = state.clone();
+    let static_lookup: StaticLookup =
+        Arc::new(move |method, path| static_state.get_static_response(method, path));
+    let handler: DynamicHandler = Arc::new(move |req| {
+        let state = state.clone();
+        Box::pin(async move { state.handle(req).await })
+    });
+    serve_with(listener, static_lookup, handler, tracker).await
+}
+
+/// Accept loop for the raw HTTP/1.1 engine with custom dispatch
+///
+/// Generic entry point used by embedders (e.g. the Node bindings) that
+/// route outside [`ServerState`]: `static_lookup` serves the
+/// pre-rendered fast path, `handler` everything else.
+pub async fn serve_with(
+    listener: TcpListener,
+    static_lookup: StaticLookup,
+    handler: DynamicHandler,
+    tracker: Arc<ConnectionTracker>,
+) -> std::io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+
+        if tracker.is_shutting_down() {
+            drop(stream);
+            continue;
+        }
+
+        let static_lookup = static_lookup.clone();
+        let handler = handler.clone();
+        let tracker = tracker.clone();
+        tracker.increment();
+
+        tokio::spawn(async move {
+            let stats = ConnectionStats::new();
+            let _ = handle_connection(stream, static_lookup, handler, &stats).await;
+            tracker.record_connection_closed(stats.requests());
+            tracker.decrement();
+        });
+    }
+}
+
+/// Serve requests on one connection until close or error
+async fn handle_connection(
+    mut stream: TcpStream,
+    static_lookup: StaticLookup,
+    handler: DynamicHandler,
+    stats: &ConnectionStats,
+) -> std::io::Result<()> {
+    stream.set_nodelay(true).ok();
+
+    let mut buf: Vec<u8> = Vec::with_capacity(8192);
+    let mut read_buf = [0u8; 8192];
+    let mut offsets: HeaderOffsets = [0; MAX_HEADERS * 4];
+
+    loop {
+        // Buffer until a complete request head is parsed
+        let parsed = loop {
+            let parsed = parse_request(&buf, &mut offsets);
+            match parsed.state {
+                1 => break parsed,
+                2 => {
+                    stream.write_all(BAD_REQUEST).await?;
+                    return Ok(());
+                }
+                _ => {
+                    if buf.len() > MAX_REQUEST_SIZE {
+                        stream.write_all(TOO_LARGE).await?;
+                        return Ok(());
+                    }
+                    let n = stream.read(&mut read_buf).await?;
+                    if n == 0 {
+                        return Ok(()); // client closed
+                    }
+                    buf.extend_from_slice(&read_buf[..n]);
+                }
+            }
+        };
+
+        // Buffer the body per Content-Length (no chunked support)
+        let body_start = parsed.body_start as usize;
+        let content_length = match body_length(&buf, &offsets, parsed.headers_count) {
+            Some(len) => len,
+            None => {
+                // Framing errors must close the connection: leftover
+                // body bytes would desynchronize pipelined parsing
+                stream.write_all(BAD_REQUEST).await?;
+                return Ok(());
+            }
+        };
+        if content_length > MAX_REQUEST_SIZE {
+            stream.write_all(TOO_LARGE).await?;
+            return Ok(());
+        }
+        while buf.len() < body_start + content_length {
+            let n = stream.read(&mut read_buf).await?;
+            if n == 0 {
+                return Ok(());
+            }
+            buf.extend_from_slice(&read_buf[..n]);
+        }
+
+        let close = find_header(&buf, &offsets, parsed.headers_count, b"connection")
+            .map(|(s, e)| scan::eq_ignore_case(&buf[s..e], b"close"))
+            .unwrap_or(false);
+
+        stats.begin_request();
+
+        let Ok(path) =
+            std::str::from_utf8(&buf[parsed.path_start as usize..parsed.path_end as usize])
+        else {
+            stream.write_all(BAD_REQUEST).await?;
+            stats.end_request();
+            return Ok(());
+        };
+
+        // Fast path: pre-rendered static response, no Request allocation
+        if let Some(bytes) = static_lookup(parsed.method, path) {
+            stream.write_all(&bytes).await?;
+        } else {
+            let request = build_request(&buf, &offsets, &parsed, path, content_length);
+            let response = handler(request).await;
+            stream.write_all(&to_framed_bytes(response)).await?;
+        }
+
+        stats.end_request();
+
+        // Drop the consumed request, keeping pipelined bytes in place
+        buf.drain(..body_start + content_length);
+
+        if close {
+            return Ok(());
+        }
+    }
+}
+
+/// Resolve the body length from the framing headers (RFC 9112 §6)
+///
+/// Returns `None` when the framing cannot be trusted: any
+/// Transfer-Encoding (no transfer codings are implemented, and
+/// reading a chunked body per Content-Length would smuggle it into
+/// the next pipelined request), an unparseable Content-Length, or
+/// conflicting duplicate Content-Length values.
+fn body_length(buf: &[u8], offsets: &HeaderOffsets, headers_count: u32) -> Option<usize> {
+    let mut length: Option<usize> = None;
+    for i in 0..headers_count.min(MAX_HEADERS as u32) {
+        let idx = (i * 4) as usize;
+        let name = &buf[offsets[idx] as usize..offsets[idx + 1] as usize];
+        let value = &buf[offsets[idx + 2] as usize..offsets[idx + 3] as usize];
+        if scan::eq_ignore_case(name, b"transfer-encoding") {
+            return None;
+        }
+        if scan::eq_ignore_case(name, b"content-length") {
+            let parsed = std::str::from_utf8(value).ok()?.trim().parse::<usize>().ok()?;
+            match length {
+                Some(existing) if existing != parsed => return None,
+                _ => length = Some(parsed),
+            }
+        }
+    }
+    Some(length.unwrap_or(0))
+}
+
+/// Build a [`Request`] from parsed offsets for the dynamic path
+fn build_request(
+    buf: &[u8],
+    offsets: &HeaderOffsets,
+    parsed: &crate::parser::ParsedRequest,
+    path: &str,
+    content_length: usize,
+) -> Request {
+    let mut request = Request::new(parsed.method, path.to_string());
+
+    if parsed.query_start > 0 {
+        if let Ok(query) =
+            std::str::from_utf8(&buf[parsed.query_start as usize..parsed.query_end as usize])
+        {
+            request.query = Some(query.to_string());
+        }
+    }
+
+    for i in 0..parsed.headers_count.min(MAX_HEADERS as u32) {
+        let idx = (i * 4) as usize;
+        let name = &buf[offsets[idx] as usize..offsets[idx + 1] as usize];
+        let value = &buf[offsets[idx + 2] as usize..offsets[idx + 3] as usize];
+        if let (Ok(name), Ok(value)) = (std::str::from_utf8(name), std::str::from_utf8(value)) {
+            request.headers.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    let body_start = parsed.body_start as usize;
+    if content_length > 0 {
+        request.body = bytes::Bytes::copy_from_slice(&buf[body_start..body_start + content_length]);
+    }
+
+    request
+}
+
+/// Render a response with framing the raw engine can keep-alive on
+///
+/// `to_http1_bytes` omits Content-Length for empty bodies; add it so
+/// clients do not wait for a close to delimit the response.
+fn to_framed_bytes(mut response: Response) -> bytes::Bytes {
+    let has_length = response.headers.iter().any(|(k, _)| {
+        k.eq_ignore_ascii_case("content-length") || k.eq_ignore_ascii_case("transfer-encoding")
+    });
+    if response.body.is_empty() && !has_length {
+        response
+            .headers
+            .push(("content-length".to_string(), "0".to_string()));
+    }
+    response.to_http1_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StatusCode;
+
+    #[test]
+    fn test_framed_bytes_empty_body() {
+        let res = Response::new(StatusCode(204));
+        let bytes = to_framed_bytes(res);
+        let s = std::str::from_utf8(&bytes).unwrap();
+        assert!(s.contains("content-length: 0\r\n"));
+    }
+
+    #[test]
+    fn test_body_length_rejects_untrusted_framing() {
+        fn resolve(head: &str) -> Option<usize> {
+            let mut buf = head.as_bytes().to_vec();
+            let mut offsets: HeaderOffsets = [0; MAX_HEADERS * 4];
+            let parsed = parse_request(&buf, &mut offsets);
+            assert_eq!(parsed.state, 1);
+            buf.truncate(parsed.body_start as usize);
+            body_length(&buf, &offsets, parsed.headers_count)
+        }
+
+        assert_eq!(resolve("POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\n"), Some(5));
+        assert_eq!(resolve("GET / HTTP/1.1\r\nHost: x\r\n\r\n"), Some(0));
+        // Identical duplicates collapse; conflicting ones are rejected
+        assert_eq!(
+            resolve("POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\n"),
+            Some(5)
+        );
+        assert_eq!(
+            resolve("POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 6\r\n\r\n"),
+            None
+        );
+        // Unparseable lengths hard-fail instead of reading zero bytes
+        assert_eq!(resolve("POST / HTTP/1.1\r\nContent-Length: abc\r\n\r\n"), None);
+        assert_eq!(
+            resolve("POST / HTTP/1.1\r\nContent-Length: 99999999999999999999\r\n\r\n"),
+            None
+        );
+        // No transfer coding is implemented; accepting one would leave
+        // the body in the buffer as the next pipelined request
+        assert_eq!(
+            resolve("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n"),
+            None
+        );
+        assert_eq!(
+            resolve("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nContent-Length: 4\r\n\r\n"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_raw_engine_rejects_transfer_encoding() {
+        let state = Arc::new(ServerState::new());
+        let tracker = Arc::new(ConnectionTracker::new());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, state, tracker));
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client
+            .write_all(
+                b"POST / HTTP/1.1\r\nHost: x\r\nTransfer-Encoding: chunked\r\n\r\n\
+                  4\r\nbody\r\n0\r\n\r\nGET /smuggled HTTP/1.1\r\nHost: x\r\n\r\n",
+            )
+            .await
+            .unwrap();
+
+        // One 400 and a closed connection — the smuggled request is
+        // never parsed
+        let mut response = Vec::new();
+        client.read_to_end(&mut response).await.unwrap();
+        let response = std::str::from_utf8(&response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 400"));
+        assert_eq!(response.matches("HTTP/1.1").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_raw_engine_serves_static_and_keeps_alive() {
+        use crate::server::StaticRoute;
+
+        let state = Arc::new(ServerState::new());
+        state
+            .add_static(StaticRoute {
+                method: "GET".to_string(),
+                path: "/health".to_string(),
+                status: 200,
+                content_type: "application/json".to_string(),
+                body: r#"{"status":"ok"}"#.to_string(),
+                handler_id: 0,
+            })
+            .unwrap();
+
+        let tracker = Arc::new(ConnectionTracker::new());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, state, tracker.clone()));
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+
+        // Two requests on the same connection (keep-alive)
+        for _ in 0..2 {
+            client
+                .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n")
+                .await
+                .unwrap();
+            let mut buf = [0u8; 1024];
+            let n = client.read(&mut buf).await.unwrap();
+            let response = std::str::from_utf8(&buf[..n]).unwrap();
+            assert!(response.starts_with("HTTP/1.1 200"));
+            assert!(response.contains(r#"{"status":"ok"}"#));
+        }
+
+        // Unknown route goes through the dynamic dispatch path
+        client
+            .write_all(b"GET /missing HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut buf = [0u8; 1024];
+        let n = client.read(&mut buf).await.unwrap();
+        let response = std::str::from_utf8(&buf[..n]).unwrap();
+        assert!(response.starts_with("HTTP/1.1 404"));
+    }
+}
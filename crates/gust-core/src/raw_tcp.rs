@@ -0,0 +1,273 @@
+//! Raw TCP (non-HTTP) listeners
+//!
+//! Lets custom protocols — SMTP stubs, line-based health probes, debug
+//! consoles — run on the same tokio runtime and
+//! [`ConnectionTracker`](crate::ConnectionTracker) as the HTTP server,
+//! so graceful shutdown and connection accounting cover them too. Each
+//! connection delivers [`TcpEvent`]s to a handler callback; the handler
+//! writes back (or hangs up) through the [`TcpConnection`] handle.
+
+use crate::server::ConnectionTracker;
+use bytes::Bytes;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+/// Read buffer size for raw connections
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Lifecycle and data events delivered to the connection handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TcpEvent {
+    /// Connection accepted; a greeting may be sent from this event
+    Open,
+    /// Bytes read from the peer (one read, not a framed message)
+    Data(Bytes),
+    /// Connection closed by either side; the handle no longer writes
+    Close,
+}
+
+/// Raw connection handler type
+pub type TcpEventHandler =
+    Arc<dyn Fn(Arc<TcpConnection>, TcpEvent) + Send + Sync>;
+
+/// Queued write or close for a connection's writer task
+enum Outgoing {
+    Data(Bytes),
+    Close,
+}
+
+/// Write handle for one raw connection
+///
+/// Cheap to clone via `Arc`; writes are queued and flushed in order by
+/// the connection task, so handlers may send from any thread.
+pub struct TcpConnection {
+    id: u64,
+    peer: SocketAddr,
+    outgoing: mpsc::UnboundedSender<Outgoing>,
+}
+
+impl TcpConnection {
+    /// Listener-unique connection id
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Peer socket address
+    pub fn peer(&self) -> SocketAddr {
+        self.peer
+    }
+
+    /// Queue bytes for the peer; `false` once the connection is gone
+    pub fn send(&self, data: impl Into<Bytes>) -> bool {
+        self.outgoing.send(Outgoing::Data(data.into())).is_ok()
+    }
+
+    /// Close the connection after queued writes flush
+    pub fn close(&self) {
+        let _ = self.outgoing.send(Outgoing::Close);
+    }
+}
+
+/// A raw TCP listener sharing the server's tracker and runtime
+pub struct RawTcpListener {
+    handler: TcpEventHandler,
+    tracker: Arc<ConnectionTracker>,
+    next_id: AtomicU64,
+}
+
+impl RawTcpListener {
+    pub fn new(tracker: Arc<ConnectionTracker>, handler: TcpEventHandler) -> Self {
+        Self {
+            handler,
+            tracker,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Serve on the given address until `shutdown` resolves
+    pub async fn serve_with_shutdown(
+        &self,
+        addr: SocketAddr,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> crate::Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        self.serve_on(listener, shutdown).await
+    }
+
+    /// Serve connections from an already-bound listener until
+    /// `shutdown` resolves
+    ///
+    /// New connections are rejected while the shared tracker is
+    /// shutting down; established ones run until the peer or handler
+    /// closes them.
+    pub async fn serve_on(
+        &self,
+        listener: tokio::net::TcpListener,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> crate::Result<()> {
+        tokio::pin!(shutdown);
+        loop {
+            let (stream, peer) = tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                accepted = listener.accept() => match accepted {
+                    Ok(conn) => conn,
+                    Err(_) => continue,
+                },
+            };
+            if self.tracker.is_shutting_down() {
+                drop(stream);
+                continue;
+            }
+            let _ = stream.set_nodelay(true);
+
+            let (tx, rx) = mpsc::unbounded_channel();
+            let connection = Arc::new(TcpConnection {
+                id: self.next_id.fetch_add(1, Ordering::Relaxed),
+                peer,
+                outgoing: tx,
+            });
+            let handler = Arc::clone(&self.handler);
+            let tracker = Arc::clone(&self.tracker);
+            tracker.increment();
+            tokio::spawn(async move {
+                run_connection(stream, connection, rx, handler).await;
+                tracker.decrement();
+            });
+        }
+    }
+}
+
+/// Pump one connection: reads become `Data` events, queued writes go
+/// out in order, and either side hanging up ends with a `Close` event
+async fn run_connection(
+    mut stream: tokio::net::TcpStream,
+    connection: Arc<TcpConnection>,
+    mut outgoing: mpsc::UnboundedReceiver<Outgoing>,
+    handler: TcpEventHandler,
+) {
+    handler(Arc::clone(&connection), TcpEvent::Open);
+
+    let mut buf = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        tokio::select! {
+            read = stream.read(&mut buf) => match read {
+                Ok(0) | Err(_) => break,
+                Ok(len) => {
+                    let data = Bytes::copy_from_slice(&buf[..len]);
+                    handler(Arc::clone(&connection), TcpEvent::Data(data));
+                }
+            },
+            queued = outgoing.recv() => match queued {
+                Some(Outgoing::Data(data)) => {
+                    if stream.write_all(&data).await.is_err() {
+                        break;
+                    }
+                }
+                // Close requested, or every handle dropped
+                Some(Outgoing::Close) | None => break,
+            },
+        }
+    }
+
+    // Flush writes queued before the close was observed
+    while let Ok(Outgoing::Data(data)) = outgoing.try_recv() {
+        if stream.write_all(&data).await.is_err() {
+            break;
+        }
+    }
+    let _ = stream.shutdown().await;
+    handler(connection, TcpEvent::Close);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bind a listener serving `handler`, returning its address
+    async fn spawn_listener(
+        tracker: Arc<ConnectionTracker>,
+        handler: TcpEventHandler,
+    ) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let raw = RawTcpListener::new(tracker, handler);
+            let _ = raw.serve_on(listener, std::future::pending::<()>()).await;
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_echo_with_greeting() {
+        let tracker = Arc::new(ConnectionTracker::new());
+        let addr = spawn_listener(
+            Arc::clone(&tracker),
+            Arc::new(|conn, event| match event {
+                TcpEvent::Open => {
+                    conn.send(Bytes::from_static(b"220 ready\r\n"));
+                }
+                TcpEvent::Data(data) => {
+                    conn.send(data);
+                }
+                TcpEvent::Close => {}
+            }),
+        )
+        .await;
+
+        let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let mut buf = [0u8; 64];
+        let len = client.read(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], b"220 ready\r\n");
+
+        client.write_all(b"HELO gust\r\n").await.unwrap();
+        let len = client.read(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], b"HELO gust\r\n");
+        assert_eq!(tracker.count(), 1);
+
+        drop(client);
+        for _ in 0..50 {
+            if tracker.count() == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(tracker.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_handler_close_hangs_up() {
+        let tracker = Arc::new(ConnectionTracker::new());
+        let addr = spawn_listener(
+            Arc::clone(&tracker),
+            Arc::new(|conn, event| {
+                if let TcpEvent::Data(_) = event {
+                    conn.send(Bytes::from_static(b"bye\r\n"));
+                    conn.close();
+                }
+            }),
+        )
+        .await;
+
+        let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+        client.write_all(b"QUIT\r\n").await.unwrap();
+        let mut response = Vec::new();
+        client.read_to_end(&mut response).await.unwrap();
+        assert_eq!(response, b"bye\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_rejects_new_connections() {
+        let tracker = Arc::new(ConnectionTracker::new());
+        let addr = spawn_listener(Arc::clone(&tracker), Arc::new(|_, _| {})).await;
+
+        tracker.start_shutdown();
+        let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+        // Accepted then immediately dropped: read returns EOF
+        let mut buf = [0u8; 8];
+        assert_eq!(client.read(&mut buf).await.unwrap(), 0);
+        assert_eq!(tracker.count(), 0);
+    }
+}
@@ -0,0 +1,244 @@
+//! UDP listeners for lightweight telemetry ingestion
+//!
+//! Receives datagrams (statsd counters, log shipping, heartbeats) on
+//! the server's runtime and delivers them to a handler in batches — a
+//! full batch flushes immediately, a partial one when the flush
+//! interval elapses — so per-datagram callback overhead stays off the
+//! receive path.
+
+use bytes::Bytes;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// One received datagram
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Datagram {
+    /// Sender socket address
+    pub peer: SocketAddr,
+    /// Datagram payload
+    pub data: Bytes,
+}
+
+/// Batch handler type; called with at least one datagram
+pub type UdpBatchHandler = Arc<dyn Fn(Vec<Datagram>) + Send + Sync>;
+
+/// Batching configuration for a UDP listener
+#[derive(Debug, Clone)]
+pub struct UdpConfig {
+    /// Datagrams per batch before an immediate flush (default 64)
+    pub batch_size: usize,
+    /// How long a partial batch may wait (default 50ms)
+    pub flush_interval: Duration,
+    /// Largest accepted datagram; longer ones are truncated
+    /// (default 64KB)
+    pub max_datagram_size: usize,
+}
+
+impl Default for UdpConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 64,
+            flush_interval: Duration::from_millis(50),
+            max_datagram_size: 64 * 1024,
+        }
+    }
+}
+
+impl UdpConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size.max(1);
+        self
+    }
+
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    pub fn max_datagram_size(mut self, size: usize) -> Self {
+        self.max_datagram_size = size.max(512);
+        self
+    }
+}
+
+/// A batching UDP listener
+pub struct UdpListener {
+    config: UdpConfig,
+    handler: UdpBatchHandler,
+}
+
+impl UdpListener {
+    pub fn new(config: UdpConfig, handler: UdpBatchHandler) -> Self {
+        Self { config, handler }
+    }
+
+    /// Receive on the given address until `shutdown` resolves
+    pub async fn serve_with_shutdown(
+        &self,
+        addr: SocketAddr,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> crate::Result<()> {
+        let socket = tokio::net::UdpSocket::bind(addr).await?;
+        self.serve_on(socket, shutdown).await
+    }
+
+    /// Receive from an already-bound socket until `shutdown` resolves
+    ///
+    /// The pending partial batch is flushed before returning so
+    /// shutdown loses nothing already received.
+    pub async fn serve_on(
+        &self,
+        socket: tokio::net::UdpSocket,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> crate::Result<()> {
+        tokio::pin!(shutdown);
+        let mut buf = vec![0u8; self.config.max_datagram_size];
+        let mut batch: Vec<Datagram> = Vec::with_capacity(self.config.batch_size);
+        let mut flush = tokio::time::interval(self.config.flush_interval);
+        flush.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => {
+                    if !batch.is_empty() {
+                        (self.handler)(std::mem::take(&mut batch));
+                    }
+                    return Ok(());
+                }
+                received = socket.recv_from(&mut buf) => {
+                    let Ok((len, peer)) = received else { continue };
+                    batch.push(Datagram {
+                        peer,
+                        data: Bytes::copy_from_slice(&buf[..len]),
+                    });
+                    if batch.len() >= self.config.batch_size {
+                        (self.handler)(std::mem::take(&mut batch));
+                        flush.reset();
+                    }
+                }
+                _ = flush.tick() => {
+                    if !batch.is_empty() {
+                        (self.handler)(std::mem::take(&mut batch));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Bind a listener, collecting flushed batches; returns its address
+    async fn spawn_listener(
+        config: UdpConfig,
+        batches: Arc<Mutex<Vec<Vec<Datagram>>>>,
+    ) -> SocketAddr {
+        let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let handler: UdpBatchHandler = Arc::new(move |batch| {
+                batches.lock().unwrap().push(batch);
+            });
+            let listener = UdpListener::new(config, handler);
+            let _ = listener.serve_on(socket, std::future::pending::<()>()).await;
+        });
+        addr
+    }
+
+    async fn wait_for_batches(
+        batches: &Arc<Mutex<Vec<Vec<Datagram>>>>,
+        count: usize,
+    ) -> Vec<Vec<Datagram>> {
+        for _ in 0..100 {
+            if batches.lock().unwrap().len() >= count {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        batches.lock().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_flushes_immediately() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let addr = spawn_listener(
+            UdpConfig::new()
+                .batch_size(3)
+                // Long interval: only the size trigger can flush
+                .flush_interval(Duration::from_secs(30)),
+            Arc::clone(&batches),
+        )
+        .await;
+
+        let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        for metric in ["a:1|c", "b:2|c", "c:3|c"] {
+            client.send_to(metric.as_bytes(), addr).await.unwrap();
+        }
+
+        let flushed = wait_for_batches(&batches, 1).await;
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0].len(), 3);
+        assert_eq!(flushed[0][0].data, Bytes::from_static(b"a:1|c"));
+        assert_eq!(flushed[0][0].peer, client.local_addr().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_partial_batch_flushes_on_interval() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let addr = spawn_listener(
+            UdpConfig::new()
+                .batch_size(100)
+                .flush_interval(Duration::from_millis(20)),
+            Arc::clone(&batches),
+        )
+        .await;
+
+        let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        client.send_to(b"lonely:1|c", addr).await.unwrap();
+
+        let flushed = wait_for_batches(&batches, 1).await;
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0].len(), 1);
+        assert_eq!(flushed[0][0].data, Bytes::from_static(b"lonely:1|c"));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_flushes_pending_batch() {
+        let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        let batches: Arc<Mutex<Vec<Vec<Datagram>>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&batches);
+        let handler: UdpBatchHandler = Arc::new(move |batch| {
+            sink.lock().unwrap().push(batch);
+        });
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            let listener =
+                UdpListener::new(UdpConfig::new().flush_interval(Duration::from_secs(30)), handler);
+            listener
+                .serve_on(socket, async {
+                    let _ = shutdown_rx.await;
+                })
+                .await
+        });
+
+        let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        client.send_to(b"final:1|c", addr).await.unwrap();
+        // Let the datagram land before signalling shutdown
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        shutdown_tx.send(()).unwrap();
+        task.await.unwrap().unwrap();
+
+        let flushed = batches.lock().unwrap().clone();
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0][0].data, Bytes::from_static(b"final:1|c"));
+    }
+}
@@ -21,6 +21,9 @@ pub struct Request {
     pub body: bytes::Bytes,
     /// Route parameters (populated by router)
     pub params: HashMap<String, String>,
+    /// Matched route pattern, e.g. `/users/:id` (populated by router);
+    /// a low-cardinality label for telemetry, unlike `path`
+    pub route_pattern: Option<String>,
 }
 
 impl Request {
@@ -33,6 +36,7 @@ impl Request {
             headers: SmallVec::new(),
             body: bytes::Bytes::new(),
             params: HashMap::new(),
+            route_pattern: None,
         }
     }
 
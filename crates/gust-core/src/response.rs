@@ -210,6 +210,34 @@ impl Response {
         self.header("content-type")
     }
 
+    /// Append a Set-Cookie header with per-cookie merge semantics
+    ///
+    /// A later cookie replaces an existing Set-Cookie for the same
+    /// cookie name in place; cookies for other names are kept, so
+    /// middleware and handlers can layer cookies without clobbering
+    /// each other.
+    pub fn add_cookie(&mut self, value: impl Into<String>) {
+        let value = value.into();
+        let name = cookie_name(&value);
+        if let Some((_, existing)) = self
+            .headers
+            .iter_mut()
+            .find(|(k, v)| k.eq_ignore_ascii_case("set-cookie") && cookie_name(v) == name)
+        {
+            *existing = value;
+        } else {
+            self.headers.push(("set-cookie".to_string(), value));
+        }
+    }
+
+    /// Get all Set-Cookie header values in order
+    pub fn cookies(&self) -> impl Iterator<Item = &str> {
+        self.headers
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case("set-cookie"))
+            .map(|(_, v)| v.as_str())
+    }
+
     /// Get body as string (if UTF-8)
     pub fn body_string(&self) -> Option<String> {
         std::str::from_utf8(&self.body).ok().map(|s| s.to_string())
@@ -257,6 +285,11 @@ impl Default for Response {
     }
 }
 
+/// Extract the cookie name from a Set-Cookie value ("name=..." prefix)
+fn cookie_name(value: &str) -> &str {
+    value.split('=').next().unwrap_or(value).trim()
+}
+
 /// Builder for constructing responses
 pub struct ResponseBuilder {
     response: Response,
@@ -328,4 +361,21 @@ mod tests {
         assert!(s.contains("content-length: 5\r\n"));
         assert!(s.ends_with("\r\n\r\nHello"));
     }
+
+    #[test]
+    fn test_add_cookie_merge() {
+        let mut res = Response::ok();
+        res.add_cookie("session=abc; Path=/");
+        res.add_cookie("theme=dark");
+        // Same cookie name replaces in place, keeping order
+        res.add_cookie("session=xyz; Path=/; HttpOnly");
+
+        let cookies: Vec<&str> = res.cookies().collect();
+        assert_eq!(cookies, vec!["session=xyz; Path=/; HttpOnly", "theme=dark"]);
+
+        // Distinct names accumulate as repeated headers
+        let bytes = res.to_http1_bytes();
+        let s = std::str::from_utf8(&bytes).unwrap();
+        assert_eq!(s.matches("set-cookie:").count(), 2);
+    }
 }
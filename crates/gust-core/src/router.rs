@@ -4,4 +4,4 @@
 //! The actual implementation lives in gust-router to ensure
 //! Single Source of Truth (SSOT) across native and WASM builds.
 
-pub use gust_router::{Match, Router};
+pub use gust_router::{Match, RouteError, Router};
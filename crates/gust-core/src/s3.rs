@@ -0,0 +1,363 @@
+//! Minimal S3-compatible object storage client
+//!
+//! Speaks the S3 REST API with SigV4 signing (see [`crate::crypto::sigv4`])
+//! so static file and upload handlers can use S3, R2, or MinIO as an
+//! alternative backend. Only the object operations the server needs are
+//! implemented: GET, PUT, DELETE, and HEAD.
+
+use crate::crypto::sigv4::{payload_hash, uri_encode, SigV4};
+use crate::pure::http_date::format_amz_date;
+use crate::{Error, Result};
+use bytes::Bytes;
+use http_body_util::Full;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// S3 client configuration
+#[derive(Clone)]
+pub struct S3Config {
+    /// Endpoint URL, e.g. `https://s3.us-east-1.amazonaws.com` or
+    /// `http://127.0.0.1:9000` for MinIO
+    pub endpoint: String,
+    /// Signing region (R2/MinIO accept `auto`/`us-east-1`)
+    pub region: String,
+    /// Bucket name
+    pub bucket: String,
+    /// Access key ID
+    pub access_key: String,
+    /// Secret access key
+    pub secret_key: String,
+    /// Use path-style addressing (`/bucket/key`); required by MinIO,
+    /// default true for compatibility
+    pub path_style: bool,
+}
+
+impl S3Config {
+    pub fn new(
+        endpoint: impl Into<String>,
+        region: impl Into<String>,
+        bucket: impl Into<String>,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            region: region.into(),
+            bucket: bucket.into(),
+            access_key: String::new(),
+            secret_key: String::new(),
+            path_style: true,
+        }
+    }
+
+    pub fn credentials(
+        mut self,
+        access_key: impl Into<String>,
+        secret_key: impl Into<String>,
+    ) -> Self {
+        self.access_key = access_key.into();
+        self.secret_key = secret_key.into();
+        self
+    }
+
+    /// Use virtual-hosted addressing (`bucket.endpoint/key`)
+    pub fn virtual_hosted(mut self) -> Self {
+        self.path_style = false;
+        self
+    }
+}
+
+/// Object metadata from a HEAD request
+#[derive(Debug, Clone)]
+pub struct ObjectInfo {
+    pub content_length: u64,
+    pub content_type: Option<String>,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// Parsed endpoint components
+struct Endpoint {
+    tls: bool,
+    host: String,
+    port: u16,
+}
+
+/// Minimal S3 object client
+pub struct S3Client {
+    config: S3Config,
+}
+
+impl S3Client {
+    pub fn new(config: S3Config) -> Self {
+        Self { config }
+    }
+
+    /// Fetch an object's contents
+    pub async fn get_object(&self, key: &str) -> Result<Bytes> {
+        let (status, _, body) = self.request("GET", key, Bytes::new(), None).await?;
+        if status == 200 {
+            Ok(body)
+        } else {
+            Err(s3_error(status, &body))
+        }
+    }
+
+    /// Store an object
+    pub async fn put_object(
+        &self,
+        key: &str,
+        body: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<()> {
+        let (status, _, response) = self.request("PUT", key, body, content_type).await?;
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(s3_error(status, &response))
+        }
+    }
+
+    /// Delete an object
+    pub async fn delete_object(&self, key: &str) -> Result<()> {
+        let (status, _, body) = self.request("DELETE", key, Bytes::new(), None).await?;
+        // S3 returns 204 whether or not the object existed
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(s3_error(status, &body))
+        }
+    }
+
+    /// Fetch object metadata; `Ok(None)` when the object does not exist
+    pub async fn head_object(&self, key: &str) -> Result<Option<ObjectInfo>> {
+        let (status, headers, body) = self.request("HEAD", key, Bytes::new(), None).await?;
+        match status {
+            200 => {
+                let find = |name: &str| {
+                    headers
+                        .iter()
+                        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                        .map(|(_, v)| v.clone())
+                };
+                Ok(Some(ObjectInfo {
+                    content_length: find("content-length")
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(0),
+                    content_type: find("content-type"),
+                    etag: find("etag"),
+                    last_modified: find("last-modified"),
+                }))
+            }
+            404 => Ok(None),
+            _ => Err(s3_error(status, &body)),
+        }
+    }
+
+    /// Sign and send one request, returning (status, headers, body)
+    async fn request(
+        &self,
+        method: &str,
+        key: &str,
+        body: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<(u16, Vec<(String, String)>, Bytes)> {
+        let endpoint = parse_endpoint(&self.config.endpoint)?;
+        let key = key.trim_start_matches('/');
+
+        let host = if self.config.path_style {
+            endpoint.host.clone()
+        } else {
+            format!("{}.{}", self.config.bucket, endpoint.host)
+        };
+        let canonical_uri = if self.config.path_style {
+            format!("/{}/{}", self.config.bucket, uri_encode(key, false))
+        } else {
+            format!("/{}", uri_encode(key, false))
+        };
+
+        let host_header = if endpoint.port == default_port(endpoint.tls) {
+            host.clone()
+        } else {
+            format!("{}:{}", host, endpoint.port)
+        };
+
+        let amz_date = format_amz_date(now_secs());
+        let content_hash = payload_hash(&body);
+
+        let mut signed_headers = vec![
+            ("host".to_string(), host_header.clone()),
+            ("x-amz-content-sha256".to_string(), content_hash.clone()),
+            ("x-amz-date".to_string(), amz_date.clone()),
+        ];
+        if let Some(ct) = content_type {
+            signed_headers.push(("content-type".to_string(), ct.to_string()));
+        }
+
+        let signer = SigV4 {
+            access_key: &self.config.access_key,
+            secret_key: &self.config.secret_key,
+            region: &self.config.region,
+            service: "s3",
+        };
+        let authorization = signer.authorization(
+            method,
+            &canonical_uri,
+            &[],
+            &signed_headers,
+            &content_hash,
+            &amz_date,
+        );
+
+        let mut builder = hyper::Request::builder()
+            .method(method)
+            .uri(&canonical_uri)
+            .header("host", &host_header)
+            .header("x-amz-content-sha256", &content_hash)
+            .header("x-amz-date", &amz_date)
+            .header("authorization", authorization)
+            .header("content-length", body.len().to_string());
+        if let Some(ct) = content_type {
+            builder = builder.header("content-type", ct);
+        }
+        let request = builder
+            .body(Full::new(body))
+            .map_err(|e| Error::Hyper(e.to_string()))?;
+
+        let scheme = if endpoint.tls { "https" } else { "http" };
+        let key = format!("{}://{}:{}", scheme, endpoint.host, endpoint.port);
+        crate::upstream::default_pool()
+            .request(&key, || self.dial(&endpoint), request)
+            .await
+    }
+
+    /// Open a connection and perform the HTTP/1.1 handshake
+    async fn dial(
+        &self,
+        endpoint: &Endpoint,
+    ) -> Result<hyper::client::conn::http1::SendRequest<Full<Bytes>>> {
+        let stream = crate::dns::default_resolver()
+            .connect(&endpoint.host, endpoint.port)
+            .await?;
+
+        if endpoint.tls {
+            #[cfg(feature = "tls")]
+            {
+                use std::sync::Arc;
+                use tokio_rustls::rustls::pki_types::ServerName;
+
+                let roots = tokio_rustls::rustls::RootCertStore {
+                    roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+                };
+                let tls_config = tokio_rustls::rustls::ClientConfig::builder()
+                    .with_root_certificates(roots)
+                    .with_no_client_auth();
+                let connector = tokio_rustls::TlsConnector::from(Arc::new(tls_config));
+                let server_name = ServerName::try_from(endpoint.host.clone())
+                    .map_err(|_| Error::Tls(format!("Invalid server name: {}", endpoint.host)))?;
+                let stream = connector
+                    .connect(server_name, stream)
+                    .await
+                    .map_err(|e| Error::Tls(e.to_string()))?;
+                return crate::upstream::handshake(stream).await;
+            }
+            #[cfg(not(feature = "tls"))]
+            {
+                return Err(Error::Internal(
+                    "https S3 endpoint requires the `tls` feature".to_string(),
+                ));
+            }
+        }
+
+        crate::upstream::handshake(stream).await
+    }
+}
+
+/// Parse `http[s]://host[:port]` into components
+fn parse_endpoint(endpoint: &str) -> Result<Endpoint> {
+    let (tls, rest) = if let Some(rest) = endpoint.strip_prefix("https://") {
+        (true, rest)
+    } else if let Some(rest) = endpoint.strip_prefix("http://") {
+        (false, rest)
+    } else {
+        return Err(Error::Internal(format!(
+            "S3 endpoint must start with http:// or https://: {}",
+            endpoint
+        )));
+    };
+
+    let rest = rest.trim_end_matches('/');
+    let (host, port) = match rest.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => {
+            let port = port
+                .parse()
+                .map_err(|_| Error::Internal(format!("Invalid S3 endpoint port: {}", rest)))?;
+            (host.to_string(), port)
+        }
+        _ => (rest.to_string(), default_port(tls)),
+    };
+
+    if host.is_empty() {
+        return Err(Error::Internal(format!("Invalid S3 endpoint: {}", endpoint)));
+    }
+
+    Ok(Endpoint { tls, host, port })
+}
+
+fn default_port(tls: bool) -> u16 {
+    if tls {
+        443
+    } else {
+        80
+    }
+}
+
+fn s3_error(status: u16, body: &[u8]) -> Error {
+    // Error bodies are small XML documents; surface the Code element
+    let body = String::from_utf8_lossy(body);
+    let code = body
+        .split_once("<Code>")
+        .and_then(|(_, rest)| rest.split_once("</Code>"))
+        .map(|(code, _)| code.to_string())
+        .unwrap_or_else(|| "UnknownError".to_string());
+    Error::S3 {
+        status,
+        message: code,
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint() {
+        let e = parse_endpoint("http://127.0.0.1:9000").unwrap();
+        assert!(!e.tls);
+        assert_eq!(e.host, "127.0.0.1");
+        assert_eq!(e.port, 9000);
+
+        let e = parse_endpoint("https://s3.us-east-1.amazonaws.com/").unwrap();
+        assert!(e.tls);
+        assert_eq!(e.host, "s3.us-east-1.amazonaws.com");
+        assert_eq!(e.port, 443);
+
+        assert!(parse_endpoint("s3.amazonaws.com").is_err());
+    }
+
+    #[test]
+    fn test_s3_error_extracts_code() {
+        let body = b"<?xml version=\"1.0\"?><Error><Code>NoSuchKey</Code></Error>";
+        match s3_error(404, body) {
+            Error::S3 { status, message } => {
+                assert_eq!(status, 404);
+                assert_eq!(message, "NoSuchKey");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Request-scoped context for outbound calls
+//!
+//! Engines establish a [`RequestScope`] (task-local) around handler
+//! execution carrying the remaining request deadline and the trace
+//! context. Outbound clients — the upstream pool behind the proxy
+//! and S3 — read the ambient scope so their timeout never outlives
+//! the request that triggered them and trace headers follow the call
+//! without any per-call plumbing. Wrap a call in [`detached`] to opt
+//! it out.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    static SCOPE: RequestScope;
+}
+
+/// Context an in-flight request hands to outbound calls
+#[derive(Debug, Clone, Default)]
+pub struct RequestScope {
+    /// Absolute deadline of the surrounding request, if any
+    pub deadline: Option<Instant>,
+    /// W3C trace context (`traceparent`) to propagate downstream
+    pub traceparent: Option<String>,
+}
+
+impl RequestScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the deadline to `timeout` from now
+    pub fn deadline_in(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    pub fn traceparent(mut self, traceparent: impl Into<String>) -> Self {
+        self.traceparent = Some(traceparent.into());
+        self
+    }
+}
+
+/// Run a future with `scope` as the ambient request scope
+pub async fn with_scope<F: Future>(scope: RequestScope, f: F) -> F::Output {
+    SCOPE.scope(scope, f).await
+}
+
+/// Run a future with an empty scope — the per-call opt-out from
+/// deadline and trace propagation
+pub async fn detached<F: Future>(f: F) -> F::Output {
+    SCOPE.scope(RequestScope::new(), f).await
+}
+
+/// The ambient scope, or an empty one outside any request
+pub fn current() -> RequestScope {
+    SCOPE.try_with(Clone::clone).unwrap_or_default()
+}
+
+/// Time left until the ambient deadline; `None` without a deadline,
+/// zero when it has already passed
+pub fn remaining() -> Option<Duration> {
+    current()
+        .deadline
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// The ambient trace context to propagate, if any
+pub fn current_traceparent() -> Option<String> {
+    current().traceparent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scope_is_task_local() {
+        assert!(current().deadline.is_none());
+
+        let scope = RequestScope::new()
+            .deadline_in(Duration::from_secs(30))
+            .traceparent("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01");
+        with_scope(scope, async {
+            assert!(remaining().unwrap() <= Duration::from_secs(30));
+            assert!(current_traceparent().is_some());
+        })
+        .await;
+
+        assert!(remaining().is_none());
+        assert!(current_traceparent().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_detached_clears_the_scope() {
+        let scope = RequestScope::new().deadline_in(Duration::from_secs(30));
+        with_scope(scope, async {
+            detached(async {
+                assert!(remaining().is_none());
+                assert!(current_traceparent().is_none());
+            })
+            .await;
+            assert!(remaining().is_some());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_expired_deadline_reports_zero() {
+        let scope = RequestScope {
+            deadline: Some(Instant::now() - Duration::from_secs(1)),
+            traceparent: None,
+        };
+        with_scope(scope, async {
+            assert_eq!(remaining(), Some(Duration::ZERO));
+        })
+        .await;
+    }
+}
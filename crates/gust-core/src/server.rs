@@ -6,6 +6,11 @@
 //! - SO_REUSEPORT for load balancing
 //! - TCP_NODELAY for low latency
 
+use crate::handlers::send_queue::OverflowPolicy;
+use crate::handlers::websocket::{
+    generate_accept_key, CloseFrame, ConnectionState, FrameDecoderConfig, Heartbeat, WebSocket,
+    WebSocketConnection, WebSocketEvent, WebSocketHandler, WebSocketMessage,
+};
 use crate::{Method, Request, Response, Router, Match, StatusCode};
 use bytes::Bytes;
 use http_body_util::Full;
@@ -76,6 +81,8 @@ pub struct ServerState {
     pub static_responses: RwLock<HashMap<u32, Bytes>>,
     /// Dynamic handlers indexed by handler ID
     pub dynamic_handlers: RwLock<HashMap<u32, DynamicHandler>>,
+    /// Lifecycle hook listeners (request, response, error, connection)
+    pub hooks: Arc<crate::HookRegistry>,
 }
 
 impl ServerState {
@@ -84,6 +91,7 @@ impl ServerState {
             router: RwLock::new(Router::new()),
             static_responses: RwLock::new(HashMap::new()),
             dynamic_handlers: RwLock::new(HashMap::new()),
+            hooks: Arc::new(crate::HookRegistry::new()),
         }
     }
 
@@ -106,8 +114,13 @@ impl ServerState {
     pub async fn handle(&self, req: Request) -> Response {
         let method_str = req.method.to_string();
 
-        // Find matching route
-        if let Some(matched) = self.router.read().find(&method_str, &req.path) {
+        // Find matching route (guard dropped before any await);
+        // the query participates so query-constrained routes match
+        let matched = self
+            .router
+            .read()
+            .find_with_query(&method_str, &req.path, req.query.as_deref());
+        if let Some(matched) = matched {
             let handler_id = matched.handler_id;
 
             // Try static response first (fastest path)
@@ -117,11 +130,19 @@ impl ServerState {
                 return Response::ok();
             }
 
-            // Try dynamic handler
-            if let Some(handler) = self.dynamic_handlers.read().get(&handler_id).cloned() {
+            // Try dynamic handler (clone out so the guard drops before await)
+            let handler = self.dynamic_handlers.read().get(&handler_id).cloned();
+            if let Some(handler) = handler {
                 let mut request = req;
                 request.params = matched.params.into_iter().collect();
-                return handler(request).await;
+                request.route_pattern = Some(matched.pattern);
+                // Outbound calls made by the handler (proxying, S3)
+                // inherit the trace context via the request scope
+                let scope = match request.header("traceparent") {
+                    Some(tp) => crate::scope::RequestScope::new().traceparent(tp),
+                    None => crate::scope::RequestScope::new(),
+                };
+                return crate::scope::with_scope(scope, handler(request)).await;
             }
         }
 
@@ -245,6 +266,18 @@ pub struct ConnectionTracker {
     active: AtomicU64,
     /// Shutdown signal received
     shutting_down: AtomicBool,
+    /// Connections accepted since start
+    total_connections: AtomicU64,
+    /// Requests served on closed connections
+    total_requests: AtomicU64,
+    /// Closed connections that served more than one request
+    reused_connections: AtomicU64,
+    /// Most requests served by any single connection
+    max_requests_per_connection: AtomicU64,
+    /// Requests rejected for exceeding the pipeline depth
+    pipelined_rejected: AtomicU64,
+    /// Maximum in-flight requests allowed per connection
+    max_pipeline_depth: AtomicU64,
 }
 
 impl Default for ConnectionTracker {
@@ -259,6 +292,12 @@ impl ConnectionTracker {
         Self {
             active: AtomicU64::new(0),
             shutting_down: AtomicBool::new(false),
+            total_connections: AtomicU64::new(0),
+            total_requests: AtomicU64::new(0),
+            reused_connections: AtomicU64::new(0),
+            max_requests_per_connection: AtomicU64::new(0),
+            pipelined_rejected: AtomicU64::new(0),
+            max_pipeline_depth: AtomicU64::new(DEFAULT_MAX_PIPELINE_DEPTH),
         }
     }
 
@@ -266,6 +305,7 @@ impl ConnectionTracker {
     #[inline]
     pub fn increment(&self) {
         self.active.fetch_add(1, Ordering::SeqCst);
+        self.total_connections.fetch_add(1, Ordering::SeqCst);
     }
 
     /// Decrement active connection count
@@ -291,13 +331,788 @@ impl ConnectionTracker {
         self.shutting_down.load(Ordering::SeqCst)
     }
 
+    /// Record a closed connection and how many requests it served
+    pub fn record_connection_closed(&self, requests_served: u64) {
+        self.total_requests.fetch_add(requests_served, Ordering::SeqCst);
+        if requests_served > 1 {
+            self.reused_connections.fetch_add(1, Ordering::SeqCst);
+        }
+        self.max_requests_per_connection
+            .fetch_max(requests_served, Ordering::SeqCst);
+    }
+
+    /// Record a request rejected for exceeding the pipeline depth
+    pub fn record_pipelined_rejected(&self) {
+        self.pipelined_rejected.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Maximum in-flight requests allowed per connection
+    #[inline]
+    pub fn max_pipeline_depth(&self) -> u64 {
+        self.max_pipeline_depth.load(Ordering::Relaxed)
+    }
+
+    /// Set the maximum in-flight requests per connection (minimum 1)
+    pub fn set_max_pipeline_depth(&self, depth: u64) {
+        self.max_pipeline_depth.store(depth.max(1), Ordering::Relaxed);
+    }
+
+    /// Snapshot of keep-alive reuse statistics
+    pub fn keep_alive_stats(&self) -> KeepAliveStats {
+        KeepAliveStats {
+            total_connections: self.total_connections.load(Ordering::SeqCst),
+            total_requests: self.total_requests.load(Ordering::SeqCst),
+            reused_connections: self.reused_connections.load(Ordering::SeqCst),
+            max_requests_per_connection: self.max_requests_per_connection.load(Ordering::SeqCst),
+            pipelined_rejected: self.pipelined_rejected.load(Ordering::SeqCst),
+        }
+    }
+
     /// Reset shutdown state (for testing or restart)
     pub fn reset(&self) {
         self.shutting_down.store(false, Ordering::SeqCst);
         self.active.store(0, Ordering::SeqCst);
+        self.total_connections.store(0, Ordering::SeqCst);
+        self.total_requests.store(0, Ordering::SeqCst);
+        self.reused_connections.store(0, Ordering::SeqCst);
+        self.max_requests_per_connection.store(0, Ordering::SeqCst);
+        self.pipelined_rejected.store(0, Ordering::SeqCst);
+    }
+}
+
+/// Default maximum in-flight requests per connection
+///
+/// HTTP/1.1 responses are serialized, so a well-behaved client has at
+/// most one request in flight; anything deeper is pipelining.
+pub const DEFAULT_MAX_PIPELINE_DEPTH: u64 = 1;
+
+/// Aggregate keep-alive reuse statistics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeepAliveStats {
+    /// Connections accepted since start
+    pub total_connections: u64,
+    /// Requests served on closed connections
+    pub total_requests: u64,
+    /// Closed connections that served more than one request
+    pub reused_connections: u64,
+    /// Most requests served by any single connection
+    pub max_requests_per_connection: u64,
+    /// Requests rejected for exceeding the pipeline depth
+    pub pipelined_rejected: u64,
+}
+
+impl KeepAliveStats {
+    /// Average requests served per connection; 0 when none have closed
+    pub fn reuse_ratio(&self) -> f64 {
+        if self.total_connections == 0 {
+            0.0
+        } else {
+            self.total_requests as f64 / self.total_connections as f64
+        }
+    }
+}
+
+/// Per-connection request accounting
+///
+/// One instance lives for the duration of a connection; the serve loop
+/// calls [`begin_request`](Self::begin_request)/[`end_request`](Self::end_request)
+/// around each request and reports the final count to
+/// [`ConnectionTracker::record_connection_closed`] on close.
+#[derive(Debug, Default)]
+pub struct ConnectionStats {
+    /// Requests seen on this connection
+    requests: AtomicU64,
+    /// Requests currently being processed
+    in_flight: AtomicU64,
+}
+
+impl ConnectionStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a request starting; returns how many were already in
+    /// flight on this connection (the pipeline depth)
+    pub fn begin_request(&self) -> u64 {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        self.in_flight.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Record a request finishing
+    pub fn end_request(&self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Requests seen on this connection so far
+    #[inline]
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::SeqCst)
     }
 }
 
+/// Builder for a [`Server`] usable directly from Rust
+///
+/// Routes go through the same [`Router`] and [`ServerState`] as the
+/// napi bindings; middleware uses the [`crate::Middleware`] trait.
+///
+/// ```no_run
+/// use gust_core::{Method, Response, Server};
+///
+/// # async fn run() -> gust_core::Result<()> {
+/// Server::builder()
+///     .get("/hello/:name", |req| async move {
+///         let name = req.param("name").unwrap_or("world").to_string();
+///         Response::text(format!("hello {}", name))
+///     })
+///     .serve("127.0.0.1:3000".parse().unwrap())
+///     .await
+/// # }
+/// ```
+/// A WebSocket endpoint registered on the builder
+#[derive(Clone)]
+struct WebSocketRoute {
+    handler: Arc<dyn WebSocketHandler>,
+    heartbeat: Heartbeat,
+    decoder: FrameDecoderConfig,
+    queue_capacity: usize,
+    overflow: OverflowPolicy,
+}
+
+pub struct ServerBuilder {
+    state: ServerState,
+    middleware: crate::MiddlewareChain,
+    websockets: HashMap<String, WebSocketRoute>,
+    plugins: Vec<Arc<dyn crate::GustPlugin>>,
+    next_handler_id: u32,
+}
+
+impl ServerBuilder {
+    /// Register an async handler for a method and path pattern
+    ///
+    /// Path parameters (`:name`) and wildcards (`*`) follow [`Router`]
+    /// syntax and are available via [`Request::param`].
+    pub fn route<F, Fut>(mut self, method: Method, path: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Response> + Send + 'static,
+    {
+        let handler_id = self.next_handler_id;
+        self.next_handler_id += 1;
+        let handler: DynamicHandler = Arc::new(move |req| Box::pin(handler(req)));
+        let _ = self.state.add_dynamic(method.as_str(), path, handler_id, handler);
+        self
+    }
+
+    /// Register a GET route
+    pub fn get<F, Fut>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Response> + Send + 'static,
+    {
+        self.route(Method::Get, path, handler)
+    }
+
+    /// Register a POST route
+    pub fn post<F, Fut>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Response> + Send + 'static,
+    {
+        self.route(Method::Post, path, handler)
+    }
+
+    /// Register a PUT route
+    pub fn put<F, Fut>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Response> + Send + 'static,
+    {
+        self.route(Method::Put, path, handler)
+    }
+
+    /// Register a DELETE route
+    pub fn delete<F, Fut>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Response> + Send + 'static,
+    {
+        self.route(Method::Delete, path, handler)
+    }
+
+    /// Register a PATCH route
+    pub fn patch<F, Fut>(self, path: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Response> + Send + 'static,
+    {
+        self.route(Method::Patch, path, handler)
+    }
+
+    /// Register a pre-rendered static route (served without a handler)
+    pub fn static_route(mut self, route: StaticRoute) -> Self {
+        let mut route = route;
+        route.handler_id = self.next_handler_id;
+        self.next_handler_id += 1;
+        let _ = self.state.add_static(route);
+        self
+    }
+
+    /// Add middleware; chains run in registration order around every route
+    pub fn middleware<M: crate::Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.add(middleware);
+        self
+    }
+
+    /// Listen for requests entering dispatch
+    pub fn on_request(self, listener: impl Fn(&crate::RequestEvent) + Send + Sync + 'static) -> Self {
+        self.state.hooks.on_request(listener);
+        self
+    }
+
+    /// Listen for responses leaving dispatch (status and duration)
+    pub fn on_response(self, listener: impl Fn(&crate::ResponseEvent) + Send + Sync + 'static) -> Self {
+        self.state.hooks.on_response(listener);
+        self
+    }
+
+    /// Listen for requests that finished with a 5xx status
+    pub fn on_error(self, listener: impl Fn(&crate::ErrorEvent) + Send + Sync + 'static) -> Self {
+        self.state.hooks.on_error(listener);
+        self
+    }
+
+    /// Listen for connections being accepted and finishing
+    pub fn on_connection(self, listener: impl Fn(&crate::ConnectionEvent) + Send + Sync + 'static) -> Self {
+        self.state.hooks.on_connection(listener);
+        self
+    }
+
+    /// Register a plugin: its middleware joins the chain, its routes
+    /// join the router, and its startup/shutdown hooks run with the
+    /// serve loop
+    pub fn plugin<P: crate::GustPlugin + 'static>(mut self, plugin: P) -> Self {
+        if let Some(middleware) = plugin.middleware() {
+            self.middleware.add_boxed(middleware);
+        }
+        for route in plugin.routes() {
+            let handler_id = self.next_handler_id;
+            self.next_handler_id += 1;
+            let _ = self
+                .state
+                .add_dynamic(&route.method, &route.path, handler_id, route.handler);
+        }
+        self.plugins.push(Arc::new(plugin));
+        self
+    }
+
+    /// Register a WebSocket endpoint with the default heartbeat
+    ///
+    /// GET requests to `path` with the upgrade headers are switched to
+    /// the WebSocket protocol and driven through `handler`; keepalive
+    /// pings, pong deadlines, and the close handshake are managed in
+    /// Rust by [`WebSocketConnection`].
+    pub fn websocket<H: WebSocketHandler + 'static>(self, path: &str, handler: H) -> Self {
+        self.websocket_with_heartbeat(path, handler, Heartbeat::default())
+    }
+
+    /// Register a WebSocket endpoint with an explicit heartbeat policy
+    ///
+    /// The send queue defaults to 1024 frames with
+    /// [`OverflowPolicy::DropOldest`], so a slow consumer loses old
+    /// frames instead of growing memory; use
+    /// [`websocket_with_queue`](Self::websocket_with_queue) to change it.
+    pub fn websocket_with_heartbeat<H: WebSocketHandler + 'static>(
+        mut self,
+        path: &str,
+        handler: H,
+        heartbeat: Heartbeat,
+    ) -> Self {
+        self.websockets.insert(
+            path.to_string(),
+            WebSocketRoute {
+                handler: Arc::new(handler),
+                heartbeat,
+                decoder: FrameDecoderConfig::new(),
+                queue_capacity: 1024,
+                overflow: OverflowPolicy::DropOldest,
+            },
+        );
+        self
+    }
+
+    /// Register a WebSocket endpoint with an explicit send-queue bound
+    ///
+    /// With [`OverflowPolicy::CloseConnection`] the connection is
+    /// closed 1013 (try again later) when the queue overflows.
+    pub fn websocket_with_queue<H: WebSocketHandler + 'static>(
+        mut self,
+        path: &str,
+        handler: H,
+        capacity: usize,
+        overflow: OverflowPolicy,
+    ) -> Self {
+        self.websockets.insert(
+            path.to_string(),
+            WebSocketRoute {
+                handler: Arc::new(handler),
+                heartbeat: Heartbeat::default(),
+                decoder: FrameDecoderConfig::new(),
+                queue_capacity: capacity,
+                overflow,
+            },
+        );
+        self
+    }
+
+    /// Finish building without binding a socket
+    pub fn build(self) -> Server {
+        Server {
+            state: Arc::new(self.state),
+            middleware: Arc::new(self.middleware),
+            websockets: Arc::new(self.websockets),
+            plugins: Arc::new(self.plugins),
+        }
+    }
+
+    /// Build and serve on the given address until the process exits
+    pub async fn serve(self, addr: SocketAddr) -> crate::Result<()> {
+        self.build().serve(addr).await
+    }
+}
+
+/// HTTP server for embedding gust-core in Rust applications
+///
+/// Construct via [`Server::builder`]. The napi bindings keep their own
+/// serve loop; this one is for crate users without Node.
+pub struct Server {
+    state: Arc<ServerState>,
+    middleware: Arc<crate::MiddlewareChain>,
+    websockets: Arc<HashMap<String, WebSocketRoute>>,
+    plugins: Arc<Vec<Arc<dyn crate::GustPlugin>>>,
+}
+
+impl Server {
+    /// Start building a server
+    pub fn builder() -> ServerBuilder {
+        ServerBuilder {
+            state: ServerState::new(),
+            middleware: crate::MiddlewareChain::new(),
+            websockets: HashMap::new(),
+            plugins: Vec::new(),
+            next_handler_id: 1,
+        }
+    }
+
+    /// Serve on the given address until the process exits
+    pub async fn serve(&self, addr: SocketAddr) -> crate::Result<()> {
+        self.serve_with_shutdown(addr, std::future::pending::<()>())
+            .await
+    }
+
+    /// Serve on the given address until `shutdown` resolves
+    ///
+    /// In-flight connections are dropped when the future resolves;
+    /// wrap handlers if graceful drain is needed.
+    pub async fn serve_with_shutdown(
+        &self,
+        addr: SocketAddr,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> crate::Result<()> {
+        let socket = create_optimized_socket(&addr)?;
+        socket.set_nonblocking(true)?;
+        let listener = tokio::net::TcpListener::from_std(socket.into())?;
+        self.serve_on(listener, shutdown).await
+    }
+
+    /// Serve connections from an already-bound listener until `shutdown` resolves
+    ///
+    /// Lets the caller bind first and observe bind errors synchronously
+    /// (the FFI bindings rely on this) or apply its own socket options.
+    pub async fn serve_on(
+        &self,
+        listener: tokio::net::TcpListener,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> crate::Result<()> {
+        for plugin in self.plugins.iter() {
+            plugin.on_startup();
+        }
+        tokio::pin!(shutdown);
+        loop {
+            let (stream, peer) = tokio::select! {
+                _ = &mut shutdown => {
+                    for plugin in self.plugins.iter() {
+                        plugin.on_shutdown();
+                    }
+                    return Ok(());
+                }
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(_) => continue,
+                },
+            };
+            let _ = stream.set_nodelay(true);
+
+            let state = Arc::clone(&self.state);
+            let middleware = Arc::clone(&self.middleware);
+            let websockets = Arc::clone(&self.websockets);
+            tokio::spawn(async move {
+                let hooks = Arc::clone(&state.hooks);
+                hooks.emit_connection(&crate::ConnectionEvent {
+                    remote_addr: peer.to_string(),
+                    phase: crate::ConnectionPhase::Opened,
+                });
+                serve_hyper_connection(state, middleware, websockets, stream).await;
+                hooks.emit_connection(&crate::ConnectionEvent {
+                    remote_addr: peer.to_string(),
+                    phase: crate::ConnectionPhase::Closed,
+                });
+            });
+        }
+    }
+
+    /// Dispatch a request through middleware and the router
+    ///
+    /// Exposed for custom accept loops and tests; `serve` uses the same
+    /// path for every connection.
+    pub async fn handle(&self, req: Request) -> Response {
+        dispatch_with_middleware(&self.state, &self.middleware, req).await
+    }
+
+    /// Lifecycle hook registry; listeners can be added after build
+    pub fn hooks(&self) -> Arc<crate::HookRegistry> {
+        Arc::clone(&self.state.hooks)
+    }
+
+    /// Serve HTTPS on the given address until the process exits
+    #[cfg(feature = "tls")]
+    pub async fn serve_tls(
+        &self,
+        addr: SocketAddr,
+        tls: Arc<rustls::ServerConfig>,
+    ) -> crate::Result<()> {
+        self.serve_tls_with_shutdown(addr, tls, std::future::pending::<()>())
+            .await
+    }
+
+    /// Serve HTTPS on the given address until `shutdown` resolves
+    ///
+    /// Connections failing the TLS handshake are dropped silently;
+    /// everything after the handshake follows the plain `serve` path.
+    #[cfg(feature = "tls")]
+    pub async fn serve_tls_with_shutdown(
+        &self,
+        addr: SocketAddr,
+        tls: Arc<rustls::ServerConfig>,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> crate::Result<()> {
+        let socket = create_optimized_socket(&addr)?;
+        socket.set_nonblocking(true)?;
+        let listener = tokio::net::TcpListener::from_std(socket.into())?;
+        let acceptor = tokio_rustls::TlsAcceptor::from(tls);
+
+        for plugin in self.plugins.iter() {
+            plugin.on_startup();
+        }
+        tokio::pin!(shutdown);
+        loop {
+            let (stream, peer) = tokio::select! {
+                _ = &mut shutdown => {
+                    for plugin in self.plugins.iter() {
+                        plugin.on_shutdown();
+                    }
+                    return Ok(());
+                }
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(_) => continue,
+                },
+            };
+            let _ = stream.set_nodelay(true);
+
+            let state = Arc::clone(&self.state);
+            let middleware = Arc::clone(&self.middleware);
+            let websockets = Arc::clone(&self.websockets);
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                if let Ok(stream) = acceptor.accept(stream).await {
+                    let hooks = Arc::clone(&state.hooks);
+                    hooks.emit_connection(&crate::ConnectionEvent {
+                        remote_addr: peer.to_string(),
+                        phase: crate::ConnectionPhase::Opened,
+                    });
+                    serve_hyper_connection(state, middleware, websockets, stream).await;
+                    hooks.emit_connection(&crate::ConnectionEvent {
+                        remote_addr: peer.to_string(),
+                        phase: crate::ConnectionPhase::Closed,
+                    });
+                }
+            });
+        }
+    }
+}
+
+async fn serve_hyper_connection<S>(
+    state: Arc<ServerState>,
+    middleware: Arc<crate::MiddlewareChain>,
+    websockets: Arc<HashMap<String, WebSocketRoute>>,
+    stream: S,
+) where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let io = hyper_util::rt::TokioIo::new(stream);
+    let service = hyper::service::service_fn(move |req| {
+        let state = Arc::clone(&state);
+        let middleware = Arc::clone(&middleware);
+        let websockets = Arc::clone(&websockets);
+        async move {
+            Ok::<_, std::convert::Infallible>(
+                serve_one_request(&state, &middleware, &websockets, req).await,
+            )
+        }
+    });
+    // Auto-detects HTTP/1.1 vs HTTP/2, matching what TLS ALPN offers
+    let _ = hyper_util::server::conn::auto::Builder::new(hyper_util::rt::TokioExecutor::new())
+        .serve_connection_with_upgrades(io, service)
+        .await;
+}
+
+async fn dispatch_with_middleware(
+    state: &ServerState,
+    middleware: &crate::MiddlewareChain,
+    req: Request,
+) -> Response {
+    // Lifecycle hooks pay for payloads only when someone listens
+    if !state.hooks.observes_requests() {
+        return dispatch_inner(state, middleware, req).await;
+    }
+
+    let method = req.method.to_string();
+    let path = req.path.clone();
+    state.hooks.emit_request(&crate::RequestEvent {
+        method: method.clone(),
+        path: path.clone(),
+    });
+
+    let start = std::time::Instant::now();
+    let response = dispatch_inner(state, middleware, req).await;
+    let status = response.status.0;
+    if status >= 500 {
+        state.hooks.emit_error(&crate::ErrorEvent {
+            method: method.clone(),
+            path: path.clone(),
+            status,
+            message: String::from_utf8_lossy(&response.body).into_owned(),
+        });
+    }
+    state.hooks.emit_response(&crate::ResponseEvent {
+        method,
+        path,
+        status,
+        duration: start.elapsed(),
+    });
+    response
+}
+
+async fn dispatch_inner(
+    state: &ServerState,
+    middleware: &crate::MiddlewareChain,
+    mut req: Request,
+) -> Response {
+    if middleware.is_empty() {
+        return state.handle(req).await;
+    }
+
+    if let Some(mut early) = middleware.run_before(&mut req) {
+        middleware.run_after(&req, &mut early);
+        return early;
+    }
+
+    // run_before may have attached params (e.g. request ids); clone
+    // so after-hooks see them once the request moves into the handler
+    let after_req = req.clone();
+    let mut response = state.handle(req).await;
+    middleware.run_after(&after_req, &mut response);
+    response
+}
+
+async fn serve_one_request(
+    state: &ServerState,
+    middleware: &crate::MiddlewareChain,
+    websockets: &HashMap<String, WebSocketRoute>,
+    mut req: hyper::Request<Incoming>,
+) -> hyper::Response<Full<Bytes>> {
+    use http_body_util::BodyExt;
+
+    if let Some(route) = websockets.get(req.uri().path()) {
+        if is_upgrade_request(&req) {
+            return start_websocket(route.clone(), &mut req);
+        }
+    }
+
+    let (parts, body) = req.into_parts();
+    let body = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(_) => Bytes::new(),
+    };
+
+    let method = match Method::from_str(parts.method.as_str()) {
+        Ok(method) => method,
+        Err(_) => {
+            return to_hyper_response(Response::new(StatusCode::METHOD_NOT_ALLOWED));
+        }
+    };
+
+    // Static fast path: pre-rendered bytes, no middleware to consult
+    if middleware.is_empty() {
+        if let Some(bytes) = state.get_static_response(method, parts.uri.path()) {
+            return bytes_to_hyper_response(bytes);
+        }
+    }
+
+    let mut request = Request::new(method, parts.uri.path().to_string());
+    request.query = parts.uri.query().map(|q| q.to_string());
+    for (name, value) in &parts.headers {
+        if let Ok(value) = value.to_str() {
+            request.headers.push((name.to_string(), value.to_string()));
+        }
+    }
+    request.body = body;
+
+    to_hyper_response(dispatch_with_middleware(state, middleware, request).await)
+}
+
+fn is_upgrade_request(req: &hyper::Request<Incoming>) -> bool {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+    };
+    header("upgrade").eq_ignore_ascii_case("websocket")
+        && header("connection").to_lowercase().contains("upgrade")
+        && req.headers().contains_key("sec-websocket-key")
+}
+
+/// Answer the upgrade handshake and hand the connection to a driver task
+fn start_websocket(
+    route: WebSocketRoute,
+    req: &mut hyper::Request<Incoming>,
+) -> hyper::Response<Full<Bytes>> {
+    let key = match req
+        .headers()
+        .get("sec-websocket-key")
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(key) => key,
+        None => return to_hyper_response(Response::new(StatusCode::BAD_REQUEST)),
+    };
+    let accept = generate_accept_key(key);
+
+    let upgrade = hyper::upgrade::on(req);
+    tokio::spawn(async move {
+        if let Ok(upgraded) = upgrade.await {
+            drive_websocket(hyper_util::rt::TokioIo::new(upgraded), route).await;
+        }
+    });
+
+    hyper::Response::builder()
+        .status(hyper::StatusCode::SWITCHING_PROTOCOLS)
+        .header("upgrade", "websocket")
+        .header("connection", "Upgrade")
+        .header("sec-websocket-accept", accept)
+        .body(Full::new(Bytes::new()))
+        .unwrap_or_else(|_| to_hyper_response(Response::new(StatusCode::INTERNAL_SERVER_ERROR)))
+}
+
+/// Drive one upgraded connection: reads, heartbeat ticks, handler calls
+async fn drive_websocket<S>(mut io: S, route: WebSocketRoute)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    let ws = WebSocket::with_queue(
+        NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        route.queue_capacity,
+        route.overflow,
+    );
+    let mut conn = WebSocketConnection::new(route.decoder).heartbeat(route.heartbeat);
+
+    let started = tokio::time::Instant::now();
+    let now_ms = |started: tokio::time::Instant| started.elapsed().as_millis() as u64;
+    // Tick often enough to observe the tighter of the two deadlines
+    let tick_every = (route.heartbeat.interval_ms.min(route.heartbeat.timeout_ms) / 2).max(50);
+    let mut ticker = tokio::time::interval(std::time::Duration::from_millis(tick_every));
+
+    route.handler.on_open(&ws);
+
+    let mut buf = vec![0u8; 8192];
+    loop {
+        // Flush frames queued by the state machine and the handler
+        for frame in conn.take_outgoing().into_iter().chain(ws.take_frames()) {
+            if io.write_all(&frame.encode()).await.is_err() {
+                route.handler.on_close(&ws, None);
+                return;
+            }
+        }
+
+        let events = tokio::select! {
+            _ = ticker.tick() => conn.tick(now_ms(started)).into_iter().collect(),
+            read = io.read(&mut buf) => match read {
+                Ok(0) | Err(_) => {
+                    route.handler.on_close(&ws, None);
+                    return;
+                }
+                Ok(n) => match conn.receive(&buf[..n], now_ms(started)) {
+                    Ok(events) => events,
+                    Err(error) => {
+                        route.handler.on_error(&ws, &error.to_string());
+                        flush_frames(&mut io, &mut conn).await;
+                        route.handler.on_close(&ws, Some(CloseFrame::protocol_error()));
+                        return;
+                    }
+                },
+            },
+        };
+
+        for event in events {
+            match event {
+                WebSocketEvent::Text(text) => {
+                    route.handler.on_message(&ws, WebSocketMessage::Text(text));
+                }
+                WebSocketEvent::Binary(data) => {
+                    route.handler.on_message(&ws, WebSocketMessage::Binary(data));
+                }
+                WebSocketEvent::Pong(_) => {}
+                WebSocketEvent::Closed(frame) => {
+                    flush_frames(&mut io, &mut conn).await;
+                    route.handler.on_close(&ws, frame);
+                    return;
+                }
+            }
+        }
+
+        // Overflow policy declared the consumer too slow
+        if ws.is_send_queue_closed() && conn.state() == ConnectionState::Open {
+            conn.close(1013, "Send queue overflow");
+        }
+    }
+}
+
+/// Best-effort write of whatever the state machine still has queued
+async fn flush_frames<S>(io: &mut S, conn: &mut WebSocketConnection)
+where
+    S: tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::AsyncWriteExt;
+    for frame in conn.take_outgoing() {
+        let _ = io.write_all(&frame.encode()).await;
+    }
+    let _ = io.flush().await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,6 +1151,46 @@ mod tests {
         assert!(response.is_some());
     }
 
+    #[test]
+    fn test_keep_alive_stats() {
+        let tracker = ConnectionTracker::new();
+
+        // One reused connection (3 requests), one single-shot
+        tracker.increment();
+        tracker.record_connection_closed(3);
+        tracker.decrement();
+        tracker.increment();
+        tracker.record_connection_closed(1);
+        tracker.decrement();
+
+        let stats = tracker.keep_alive_stats();
+        assert_eq!(stats.total_connections, 2);
+        assert_eq!(stats.total_requests, 4);
+        assert_eq!(stats.reused_connections, 1);
+        assert_eq!(stats.max_requests_per_connection, 3);
+        assert!((stats.reuse_ratio() - 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_connection_stats_pipeline_depth() {
+        let conn = ConnectionStats::new();
+
+        // First request: nothing in flight yet
+        assert_eq!(conn.begin_request(), 0);
+        // Pipelined request arrives before the first finishes
+        assert_eq!(conn.begin_request(), 1);
+        conn.end_request();
+        conn.end_request();
+
+        assert_eq!(conn.requests(), 2);
+
+        // Depth floor: 0 is clamped to 1
+        let tracker = ConnectionTracker::new();
+        assert_eq!(tracker.max_pipeline_depth(), DEFAULT_MAX_PIPELINE_DEPTH);
+        tracker.set_max_pipeline_depth(0);
+        assert_eq!(tracker.max_pipeline_depth(), 1);
+    }
+
     #[test]
     fn test_match_route() {
         let state = ServerState::new();
@@ -355,4 +1210,120 @@ mod tests {
         assert_eq!(m.handler_id, 1);
         assert_eq!(m.params, vec![("id".to_string(), "123".to_string())]);
     }
+
+    #[tokio::test]
+    async fn test_builder_routes_and_params() {
+        let server = Server::builder()
+            .get("/hello/:name", |req| async move {
+                let name = req.param("name").unwrap_or("world").to_string();
+                Response::text(format!("hello {}", name))
+            })
+            .post("/echo", |req| async move {
+                Response::text(req.body.clone())
+            })
+            .build();
+
+        let res = server.handle(Request::new(Method::Get, "/hello/gust")).await;
+        assert_eq!(res.status.as_u16(), 200);
+        assert_eq!(res.body.as_ref(), b"hello gust");
+
+        let mut req = Request::new(Method::Post, "/echo");
+        req.body = Bytes::from_static(b"ping");
+        let res = server.handle(req).await;
+        assert_eq!(res.body.as_ref(), b"ping");
+
+        let res = server.handle(Request::new(Method::Get, "/missing")).await;
+        assert_eq!(res.status.as_u16(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_websocket_route_upgrade_and_echo() {
+        use crate::handlers::websocket::Frame;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        struct Echo;
+        impl WebSocketHandler for Echo {
+            fn on_open(&self, _ws: &WebSocket) {}
+            fn on_message(&self, ws: &WebSocket, msg: WebSocketMessage) {
+                if let WebSocketMessage::Text(text) = msg {
+                    ws.send_text(format!("echo: {}", text));
+                }
+            }
+            fn on_close(&self, _ws: &WebSocket, _frame: Option<CloseFrame>) {}
+            fn on_error(&self, _ws: &WebSocket, _error: &str) {}
+        }
+
+        let server = Server::builder().websocket("/ws", Echo).build();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = server.serve_on(listener, std::future::pending::<()>()).await;
+        });
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                b"GET /ws HTTP/1.1\r\n\
+                  host: localhost\r\n\
+                  upgrade: websocket\r\n\
+                  connection: Upgrade\r\n\
+                  sec-websocket-key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                  sec-websocket-version: 13\r\n\r\n",
+            )
+            .await
+            .unwrap();
+
+        // Read the 101 response up to the end of headers
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        while !response.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut byte).await.unwrap();
+            response.push(byte[0]);
+        }
+        let response = String::from_utf8(response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 101"));
+        assert!(response.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        // Send a masked text frame and expect the echoed reply
+        let mut frame = Frame::text("hi");
+        frame.mask = Some([1, 2, 3, 4]);
+        stream.write_all(&frame.encode()).await.unwrap();
+
+        let mut reply = vec![0u8; 64];
+        let n = stream.read(&mut reply).await.unwrap();
+        let (echoed, _) = Frame::decode(&reply[..n]).unwrap();
+        assert_eq!(echoed.payload, b"echo: hi");
+    }
+
+    #[tokio::test]
+    async fn test_builder_middleware_runs_around_handler() {
+        struct Tag;
+
+        impl crate::Middleware for Tag {
+            fn before(&self, req: &mut Request) -> Option<Response> {
+                if req.path == "/blocked" {
+                    return Some(Response::new(StatusCode::FORBIDDEN));
+                }
+                None
+            }
+
+            fn after(&self, _req: &Request, res: &mut Response) {
+                res.headers.push(("x-tag".to_string(), "1".to_string()));
+            }
+        }
+
+        let server = Server::builder()
+            .get("/ok", |_req| async { Response::ok() })
+            .middleware(Tag)
+            .build();
+
+        let res = server.handle(Request::new(Method::Get, "/ok")).await;
+        assert_eq!(res.status.as_u16(), 200);
+        assert!(res.headers.iter().any(|(n, _)| n == "x-tag"));
+
+        // before() short-circuits, but after() still decorates
+        let res = server.handle(Request::new(Method::Get, "/blocked")).await;
+        assert_eq!(res.status.as_u16(), 403);
+        assert!(res.headers.iter().any(|(n, _)| n == "x-tag"));
+    }
 }
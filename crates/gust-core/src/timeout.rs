@@ -0,0 +1,232 @@
+//! Structured timeout hierarchy
+//!
+//! Replaces a single request timeout with one deadline per request
+//! phase — connect/accept, header read, body read, handler execution,
+//! response write, and a total wall-clock cap — each with its own
+//! status code and metric name. Engines enforce the phases they can
+//! observe (a header-read timeout kills the connection before any
+//! response can be written, so its status only appears in metrics).
+
+use crate::response::StatusCode;
+
+/// A request phase with its own deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeoutPhase {
+    /// Connection accept / TLS handshake
+    Connect,
+    /// Reading the request line and headers
+    Header,
+    /// Reading the request body
+    Body,
+    /// Handler execution
+    Handler,
+    /// Writing the response
+    Write,
+    /// Whole request, from first byte to last
+    Total,
+}
+
+impl TimeoutPhase {
+    /// Status code reported when this phase times out
+    ///
+    /// Client-read phases use 408 (the client was too slow), handler
+    /// and total use 504 (the server was), and write uses 499 (the
+    /// nginx convention for "client went away" — the response cannot
+    /// be delivered, so the code is for logs and metrics only).
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            TimeoutPhase::Connect | TimeoutPhase::Header | TimeoutPhase::Body => StatusCode(408),
+            TimeoutPhase::Handler | TimeoutPhase::Total => StatusCode(504),
+            TimeoutPhase::Write => StatusCode(499),
+        }
+    }
+
+    /// Metric name for counting timeouts in this phase
+    pub fn metric_name(&self) -> &'static str {
+        match self {
+            TimeoutPhase::Connect => "http.server.timeout.connect",
+            TimeoutPhase::Header => "http.server.timeout.header",
+            TimeoutPhase::Body => "http.server.timeout.body",
+            TimeoutPhase::Handler => "http.server.timeout.handler",
+            TimeoutPhase::Write => "http.server.timeout.write",
+            TimeoutPhase::Total => "http.server.timeout.total",
+        }
+    }
+}
+
+/// Per-phase deadlines in milliseconds; 0 disables a phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutConfig {
+    pub connect_ms: u32,
+    pub header_ms: u32,
+    pub body_ms: u32,
+    pub handler_ms: u32,
+    pub write_ms: u32,
+    pub total_ms: u32,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            connect_ms: 10_000,
+            header_ms: 10_000,
+            body_ms: 30_000,
+            handler_ms: 30_000,
+            write_ms: 30_000,
+            total_ms: 0,
+        }
+    }
+}
+
+impl TimeoutConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All phases disabled, for opt-in configuration
+    pub fn none() -> Self {
+        Self {
+            connect_ms: 0,
+            header_ms: 0,
+            body_ms: 0,
+            handler_ms: 0,
+            write_ms: 0,
+            total_ms: 0,
+        }
+    }
+
+    /// The deadline for one phase, in milliseconds (0 = disabled)
+    pub fn phase_ms(&self, phase: TimeoutPhase) -> u32 {
+        match phase {
+            TimeoutPhase::Connect => self.connect_ms,
+            TimeoutPhase::Header => self.header_ms,
+            TimeoutPhase::Body => self.body_ms,
+            TimeoutPhase::Handler => self.handler_ms,
+            TimeoutPhase::Write => self.write_ms,
+            TimeoutPhase::Total => self.total_ms,
+        }
+    }
+
+    pub fn connect_ms(mut self, ms: u32) -> Self {
+        self.connect_ms = ms;
+        self
+    }
+
+    pub fn header_ms(mut self, ms: u32) -> Self {
+        self.header_ms = ms;
+        self
+    }
+
+    pub fn body_ms(mut self, ms: u32) -> Self {
+        self.body_ms = ms;
+        self
+    }
+
+    pub fn handler_ms(mut self, ms: u32) -> Self {
+        self.handler_ms = ms;
+        self
+    }
+
+    pub fn write_ms(mut self, ms: u32) -> Self {
+        self.write_ms = ms;
+        self
+    }
+
+    pub fn total_ms(mut self, ms: u32) -> Self {
+        self.total_ms = ms;
+        self
+    }
+}
+
+/// Global timeout config plus per-route overrides
+///
+/// Routes are keyed by their registered pattern (`/users/:id`); the
+/// first matching override wins and unset phases (0) inherit the
+/// global value, so a route can stretch just its body deadline for
+/// uploads while keeping the rest.
+#[derive(Debug, Clone, Default)]
+pub struct TimeoutHierarchy {
+    global: TimeoutConfig,
+    routes: Vec<(String, TimeoutConfig)>,
+}
+
+impl TimeoutHierarchy {
+    pub fn new(global: TimeoutConfig) -> Self {
+        Self {
+            global,
+            routes: Vec::new(),
+        }
+    }
+
+    pub fn global(&self) -> &TimeoutConfig {
+        &self.global
+    }
+
+    pub fn set_global(&mut self, config: TimeoutConfig) {
+        self.global = config;
+    }
+
+    /// Override timeouts for a route pattern, replacing an existing
+    /// override for the same pattern
+    pub fn set_route(&mut self, pattern: impl Into<String>, config: TimeoutConfig) {
+        let pattern = pattern.into();
+        if let Some(existing) = self.routes.iter_mut().find(|(p, _)| p == &pattern) {
+            existing.1 = config;
+        } else {
+            self.routes.push((pattern, config));
+        }
+    }
+
+    /// The effective deadline for a phase on a route (0 = disabled);
+    /// route overrides with an unset phase fall back to the global
+    pub fn phase_ms(&self, route: Option<&str>, phase: TimeoutPhase) -> u32 {
+        if let Some(route) = route {
+            if let Some((_, config)) = self.routes.iter().find(|(p, _)| p == route) {
+                let ms = config.phase_ms(phase);
+                if ms > 0 {
+                    return ms;
+                }
+            }
+        }
+        self.global.phase_ms(phase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_status_codes() {
+        assert_eq!(TimeoutPhase::Header.status_code(), StatusCode(408));
+        assert_eq!(TimeoutPhase::Body.status_code(), StatusCode(408));
+        assert_eq!(TimeoutPhase::Handler.status_code(), StatusCode(504));
+        assert_eq!(TimeoutPhase::Write.status_code(), StatusCode(499));
+        assert_eq!(TimeoutPhase::Total.status_code(), StatusCode(504));
+    }
+
+    #[test]
+    fn test_config_phase_lookup() {
+        let config = TimeoutConfig::none().body_ms(60_000).handler_ms(5_000);
+        assert_eq!(config.phase_ms(TimeoutPhase::Body), 60_000);
+        assert_eq!(config.phase_ms(TimeoutPhase::Handler), 5_000);
+        assert_eq!(config.phase_ms(TimeoutPhase::Header), 0);
+    }
+
+    #[test]
+    fn test_route_override_inherits_unset_phases() {
+        let mut timeouts = TimeoutHierarchy::new(TimeoutConfig::default());
+        timeouts.set_route("/upload", TimeoutConfig::none().body_ms(300_000));
+
+        // The override stretches only the body deadline
+        assert_eq!(timeouts.phase_ms(Some("/upload"), TimeoutPhase::Body), 300_000);
+        assert_eq!(timeouts.phase_ms(Some("/upload"), TimeoutPhase::Handler), 30_000);
+        // Other routes (and no route) see the global config
+        assert_eq!(timeouts.phase_ms(Some("/other"), TimeoutPhase::Body), 30_000);
+        assert_eq!(timeouts.phase_ms(None, TimeoutPhase::Body), 30_000);
+
+        // Re-registering a pattern replaces the override
+        timeouts.set_route("/upload", TimeoutConfig::none().body_ms(60_000));
+        assert_eq!(timeouts.phase_ms(Some("/upload"), TimeoutPhase::Body), 60_000);
+    }
+}
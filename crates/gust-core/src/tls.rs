@@ -36,8 +36,7 @@ impl TlsConfig {
         let certs = load_certs(&self.cert_path)?;
         let key = load_private_key(&self.key_path)?;
 
-        let config = rustls::ServerConfig::builder()
-            .with_no_client_auth()
+        let config = server_config_builder()?
             .with_single_cert(certs, key)
             .map_err(|e| Error::Tls(e.to_string()))?;
 
@@ -48,6 +47,39 @@ impl TlsConfig {
     }
 }
 
+/// Build a rustls ServerConfig from in-memory DER cert/key bytes
+///
+/// For certificates that never touch disk, e.g. a self-signed
+/// certificate generated at startup. The key must be PKCS#8 DER.
+pub fn server_config_from_der(
+    certs: Vec<Vec<u8>>,
+    pkcs8_key: Vec<u8>,
+) -> Result<Arc<rustls::ServerConfig>> {
+    let certs: Vec<CertificateDer<'static>> =
+        certs.into_iter().map(CertificateDer::from).collect();
+    let key = PrivateKeyDer::Pkcs8(pkcs8_key.into());
+
+    let mut config = server_config_builder()?
+        .with_single_cert(certs, key)
+        .map_err(|e| Error::Tls(e.to_string()))?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(Arc::new(config))
+}
+
+/// rustls config builder pinned to the ring provider
+///
+/// Downstream crates can pull in other rustls crypto backends; pinning
+/// keeps config construction deterministic instead of panicking on an
+/// ambiguous process-level default.
+fn server_config_builder(
+) -> Result<rustls::ConfigBuilder<rustls::ServerConfig, rustls::server::WantsServerCert>> {
+    rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map(|builder| builder.with_no_client_auth())
+        .map_err(|e| Error::Tls(e.to_string()))
+}
+
 /// Load certificates from PEM file
 pub fn load_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
     let file = File::open(Path::new(path))
@@ -0,0 +1,236 @@
+//! Tower compatibility layer
+//!
+//! Bridges gust and the Tower ecosystem in both directions:
+//!
+//! - [`GustService`] exposes a built [`Server`] (router + middleware
+//!   chain) as a `tower::Service<Request>`, so it can sit behind
+//!   existing Tower layers during a migration.
+//! - [`ServerBuilder::route_service`] mounts any Tower service as a
+//!   gust route handler, so middleware stacks built with
+//!   `tower::ServiceBuilder` keep working inside the gust pipeline.
+//!
+//! [`handler_service`] adapts a plain gust async handler into a Tower
+//! service, which is the usual starting point for wrapping layers:
+//!
+//! ```no_run
+//! use gust_core::{Method, Response, Server};
+//! use gust_core::tower_compat::handler_service;
+//! use tower::{Layer, layer::layer_fn};
+//!
+//! # fn build() -> Server {
+//! let stack = layer_fn(|inner| inner) // e.g. tower_http layers
+//!     .layer(handler_service(|_req| async { Response::ok() }));
+//!
+//! Server::builder()
+//!     .route_service(Method::Get, "/wrapped", stack)
+//!     .build()
+//! # }
+//! ```
+
+use crate::server::{Server, ServerBuilder};
+use crate::{Method, Request, Response, StatusCode};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// A `tower::Service` over gust's [`Request`]/[`Response`] pair
+///
+/// Dispatches through the server's middleware chain and router, the
+/// same path [`Server::handle`] uses. Cloning is cheap (the server is
+/// shared behind an `Arc`) and the service is always ready.
+#[derive(Clone)]
+pub struct GustService {
+    server: Arc<Server>,
+}
+
+impl GustService {
+    pub fn new(server: Server) -> Self {
+        Self {
+            server: Arc::new(server),
+        }
+    }
+}
+
+impl tower::Service<Request> for GustService {
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let server = Arc::clone(&self.server);
+        Box::pin(async move { Ok(server.handle(req).await) })
+    }
+}
+
+impl Server {
+    /// Wrap this server in a [`GustService`] for use in Tower stacks
+    pub fn into_service(self) -> GustService {
+        GustService::new(self)
+    }
+}
+
+impl ServerBuilder {
+    /// Mount a Tower service as the handler for a method and path
+    ///
+    /// The service is cloned per request and driven through
+    /// `ServiceExt::oneshot`, so readiness (backpressure) is honored.
+    /// Service errors surface as plain 500 responses.
+    pub fn route_service<S>(self, method: Method, path: &str, service: S) -> Self
+    where
+        S: tower::Service<Request, Response = Response> + Clone + Send + Sync + 'static,
+        S::Error: Send,
+        S::Future: Send,
+    {
+        self.route(method, path, move |req| {
+            let service = service.clone();
+            async move {
+                match tower::ServiceExt::oneshot(service, req).await {
+                    Ok(res) => res,
+                    Err(_) => Response::new(StatusCode::INTERNAL_SERVER_ERROR),
+                }
+            }
+        })
+    }
+}
+
+/// Tower service backed by a gust-style async handler
+///
+/// Built with [`handler_service`]; the inner handler is infallible, so
+/// `Error = Infallible` and layers can rely on `poll_ready` always
+/// being ready.
+#[derive(Clone)]
+pub struct HandlerService<F> {
+    handler: F,
+}
+
+/// Adapt a gust async handler into a Tower service
+///
+/// Useful as the innermost service when wrapping Tower layers around a
+/// handler before mounting it with [`ServerBuilder::route_service`].
+pub fn handler_service<F, Fut>(handler: F) -> HandlerService<F>
+where
+    F: Fn(Request) -> Fut,
+    Fut: Future<Output = Response> + Send + 'static,
+{
+    HandlerService { handler }
+}
+
+impl<F, Fut> tower::Service<Request> for HandlerService<F>
+where
+    F: Fn(Request) -> Fut,
+    Fut: Future<Output = Response> + Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let fut = (self.handler)(req);
+        Box::pin(async move { Ok(fut.await) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::{Layer, Service, ServiceExt};
+
+    /// Minimal Tower middleware: tags every response with a header
+    #[derive(Clone)]
+    struct AddHeader<S> {
+        inner: S,
+    }
+
+    impl<S> Service<Request> for AddHeader<S>
+    where
+        S: Service<Request, Response = Response>,
+        S::Future: Send + 'static,
+        S::Error: Send + 'static,
+    {
+        type Response = Response;
+        type Error = S::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: Request) -> Self::Future {
+            let fut = self.inner.call(req);
+            Box::pin(async move {
+                let mut res = fut.await?;
+                res.headers
+                    .push(("x-tower".to_string(), "1".to_string()));
+                Ok(res)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_as_tower_service() {
+        let server = Server::builder()
+            .get("/ping", |_req| async { Response::text("pong") })
+            .build();
+
+        let service = server.into_service();
+        let res = service
+            .oneshot(Request::new(Method::Get, "/ping"))
+            .await
+            .unwrap();
+        assert_eq!(res.status.as_u16(), 200);
+        assert_eq!(res.body.as_ref(), b"pong");
+    }
+
+    #[tokio::test]
+    async fn test_tower_layer_mounted_as_route() {
+        let stack = tower::layer::layer_fn(|inner| AddHeader { inner })
+            .layer(handler_service(|req: Request| async move {
+                Response::text(format!("id {}", req.param("id").unwrap_or("?")))
+            }));
+
+        let server = Server::builder()
+            .route_service(Method::Get, "/items/:id", stack)
+            .build();
+
+        let res = server.handle(Request::new(Method::Get, "/items/7")).await;
+        assert_eq!(res.body.as_ref(), b"id 7");
+        assert!(res.headers.iter().any(|(n, v)| n == "x-tower" && v == "1"));
+    }
+
+    #[tokio::test]
+    async fn test_service_error_maps_to_500() {
+        #[derive(Clone)]
+        struct AlwaysFails;
+
+        impl Service<Request> for AlwaysFails {
+            type Response = Response;
+            type Error = String;
+            type Future = std::future::Ready<Result<Response, String>>;
+
+            fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), String>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn call(&mut self, _req: Request) -> Self::Future {
+                std::future::ready(Err("boom".to_string()))
+            }
+        }
+
+        let server = Server::builder()
+            .route_service(Method::Get, "/fail", AlwaysFails)
+            .build();
+
+        let res = server.handle(Request::new(Method::Get, "/fail")).await;
+        assert_eq!(res.status.as_u16(), 500);
+    }
+}
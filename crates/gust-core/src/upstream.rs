@@ -0,0 +1,687 @@
+//! Upstream HTTP/1.1 connection pooling
+//!
+//! The proxy and S3 client previously opened a fresh TCP (and TLS)
+//! connection for every request. [`UpstreamPool`] keeps keep-alive
+//! connections around per host so sequential requests reuse them,
+//! with tunable idle caps, idle timeout, and maximum connection
+//! lifetime, and counters exposing checkout/reuse behaviour.
+
+use crate::{Error, Result};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+type Sender = hyper::client::conn::http1::SendRequest<Full<Bytes>>;
+
+/// Pool tuning knobs
+#[derive(Debug, Clone)]
+pub struct UpstreamConfig {
+    /// Idle connections kept per host key (default 8)
+    pub max_idle_per_host: usize,
+    /// Idle connections older than this are not reused (default 90s)
+    pub idle_timeout: Duration,
+    /// Connections older than this are retired even when busy
+    /// reusing them would be possible (default 5 min)
+    pub max_lifetime: Duration,
+}
+
+impl Default for UpstreamConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: 8,
+            idle_timeout: Duration::from_secs(90),
+            max_lifetime: Duration::from_secs(300),
+        }
+    }
+}
+
+impl UpstreamConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_idle_per_host(mut self, max: usize) -> Self {
+        self.max_idle_per_host = max;
+        self
+    }
+
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    pub fn max_lifetime(mut self, lifetime: Duration) -> Self {
+        self.max_lifetime = lifetime;
+        self
+    }
+}
+
+/// Counters describing pool effectiveness
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpstreamStats {
+    /// Logical requests that went through the pool
+    pub checkouts: u64,
+    /// Checkouts with no usable idle connection (had to dial)
+    pub waits: u64,
+    /// Checkouts served over a reused connection
+    pub reused: u64,
+    /// Connections returned to the idle list after a request
+    pub returned: u64,
+    /// Connections dropped instead of returned (closed, or idle list full)
+    pub discarded: u64,
+    /// Idle connections evicted by idle timeout or max lifetime
+    pub expired: u64,
+}
+
+impl UpstreamStats {
+    /// Fraction of checkouts served over a reused connection
+    pub fn reuse_ratio(&self) -> f64 {
+        if self.checkouts == 0 {
+            0.0
+        } else {
+            self.reused as f64 / self.checkouts as f64
+        }
+    }
+}
+
+/// Time-to-first-byte statistics for one upstream key
+///
+/// Measured from checkout to response headers, so a slow dial and a
+/// slow upstream both show up.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpstreamTtfb {
+    /// Exchanges measured
+    pub count: u64,
+    /// Summed time to first byte, in ms
+    pub total_ms: u64,
+    /// Slowest observed time to first byte, in ms
+    pub max_ms: u64,
+}
+
+impl UpstreamTtfb {
+    /// Mean time to first byte in ms
+    pub fn mean_ms(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.total_ms as f64 / self.count as f64
+        }
+    }
+}
+
+/// A streaming upstream response body from
+/// [`request_streamed`](UpstreamPool::request_streamed)
+///
+/// Holds the connection alive until dropped; frames are pulled only
+/// when [`frame`](Self::frame) is polled, so backpressure from the
+/// consumer propagates to the upstream read.
+pub struct UpstreamBody {
+    body: hyper::body::Incoming,
+    // Dropping the sender lets the connection close once the body
+    // finishes; it is never returned to the pool
+    _sender: Sender,
+}
+
+impl UpstreamBody {
+    /// The next chunk of body data, `None` at end of stream
+    pub async fn frame(&mut self) -> Result<Option<Bytes>> {
+        while let Some(frame) = self.body.frame().await {
+            let frame = frame.map_err(|e| Error::Hyper(e.to_string()))?;
+            // Trailers are skipped; only data frames are surfaced
+            if let Ok(data) = frame.into_data() {
+                return Ok(Some(data));
+            }
+        }
+        Ok(None)
+    }
+}
+
+struct IdleConn {
+    sender: Sender,
+    created: Instant,
+    idled: Instant,
+}
+
+/// Per-host pool of keep-alive upstream connections
+///
+/// Callers key connections however they dial them (scheme, host, and
+/// port must all be part of the key) and supply a `dial` future that
+/// performs the handshake; [`request`](Self::request) handles
+/// checkout, send, body collection, and check-in.
+pub struct UpstreamPool {
+    config: UpstreamConfig,
+    idle: Mutex<HashMap<String, Vec<IdleConn>>>,
+    draining: Mutex<HashSet<String>>,
+    checkouts: AtomicU64,
+    waits: AtomicU64,
+    reused: AtomicU64,
+    returned: AtomicU64,
+    discarded: AtomicU64,
+    expired: AtomicU64,
+    ttfb: Mutex<HashMap<String, UpstreamTtfb>>,
+}
+
+impl UpstreamPool {
+    pub fn new(config: UpstreamConfig) -> Self {
+        Self {
+            config,
+            idle: Mutex::new(HashMap::new()),
+            draining: Mutex::new(HashSet::new()),
+            checkouts: AtomicU64::new(0),
+            waits: AtomicU64::new(0),
+            reused: AtomicU64::new(0),
+            returned: AtomicU64::new(0),
+            discarded: AtomicU64::new(0),
+            expired: AtomicU64::new(0),
+            ttfb: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &UpstreamConfig {
+        &self.config
+    }
+
+    /// Send one request, reusing a pooled connection when possible
+    ///
+    /// A stale reused connection falls back to a fresh dial instead of
+    /// failing the request. The response body is collected so the
+    /// connection is immediately reusable; it is returned to the pool
+    /// unless closed or past its lifetime.
+    ///
+    /// The ambient [`crate::scope`] applies: the scope's
+    /// `traceparent` is injected when the request carries none, and
+    /// the whole exchange is capped by the remaining request deadline
+    /// ([`Error::DeadlineExceeded`]). Wrap the call in
+    /// [`crate::scope::detached`] to opt out per call.
+    pub async fn request<F, Fut>(
+        &self,
+        key: &str,
+        dial: F,
+        mut request: hyper::Request<Full<Bytes>>,
+    ) -> Result<(u16, Vec<(String, String)>, Bytes)>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<Sender>>,
+    {
+        inject_traceparent(&mut request);
+        match crate::scope::remaining() {
+            Some(remaining) => tokio::time::timeout(remaining, self.exchange(key, dial, request))
+                .await
+                .map_err(|_| Error::DeadlineExceeded)?,
+            None => self.exchange(key, dial, request).await,
+        }
+    }
+
+    /// Send one request and stream the response body
+    ///
+    /// Status and headers return as soon as the upstream sends them;
+    /// body frames are pulled on demand through
+    /// [`UpstreamBody::frame`], so a consumer that is not ready to
+    /// write (a slow client) pauses the upstream read with only
+    /// hyper's internal frame buffered — no full-body collection.
+    /// The connection is not returned to the pool: reuse would
+    /// require holding the checkout until the body is fully read, so
+    /// it closes when the body is dropped.
+    ///
+    /// The ambient [`crate::scope`] applies up to the response
+    /// headers, like [`request`](Self::request); the body stream
+    /// itself is not cut off mid-transfer.
+    pub async fn request_streamed<F, Fut>(
+        &self,
+        key: &str,
+        dial: F,
+        mut request: hyper::Request<Full<Bytes>>,
+    ) -> Result<(u16, Vec<(String, String)>, UpstreamBody)>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<Sender>>,
+    {
+        inject_traceparent(&mut request);
+        let started = Instant::now();
+        let checkout = self.checkout(key, dial, request);
+        let (conn, response) = match crate::scope::remaining() {
+            Some(remaining) => tokio::time::timeout(remaining, checkout)
+                .await
+                .map_err(|_| Error::DeadlineExceeded)?,
+            None => checkout.await,
+        }?;
+        self.record_ttfb(key, started.elapsed());
+        // Streamed connections never come back
+        self.discarded.fetch_add(1, Ordering::Relaxed);
+
+        let status = response.status().as_u16();
+        let headers = response_headers(&response);
+        Ok((
+            status,
+            headers,
+            UpstreamBody {
+                body: response.into_body(),
+                _sender: conn.sender,
+            },
+        ))
+    }
+
+    async fn exchange<F, Fut>(
+        &self,
+        key: &str,
+        dial: F,
+        request: hyper::Request<Full<Bytes>>,
+    ) -> Result<(u16, Vec<(String, String)>, Bytes)>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<Sender>>,
+    {
+        let started = Instant::now();
+        let (mut conn, response) = self.checkout(key, dial, request).await?;
+        self.record_ttfb(key, started.elapsed());
+
+        let status = response.status().as_u16();
+        let headers = response_headers(&response);
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| Error::Hyper(e.to_string()))?
+            .to_bytes();
+
+        conn.idled = Instant::now();
+        self.check_in(key, conn);
+        Ok((status, headers, body))
+    }
+
+    /// Check out a connection — an idle one when possible, a fresh
+    /// dial otherwise — and send the request on it; a stale reused
+    /// connection falls back to a fresh dial
+    async fn checkout<F, Fut>(
+        &self,
+        key: &str,
+        dial: F,
+        request: hyper::Request<Full<Bytes>>,
+    ) -> Result<(IdleConn, hyper::Response<hyper::body::Incoming>)>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<Sender>>,
+    {
+        self.checkouts.fetch_add(1, Ordering::Relaxed);
+        let (parts, body) = request.into_parts();
+
+        let mut conn = None;
+        if let Some(mut idle) = self.take_idle(key) {
+            match send_on(&mut idle.sender, clone_request(&parts, &body)).await {
+                Ok(response) => conn = Some((idle, response)),
+                // Stale keep-alive connection; dial fresh below
+                Err(_) => {
+                    self.discarded.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            if conn.is_some() {
+                self.reused.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        match conn {
+            Some(pair) => Ok(pair),
+            None => {
+                self.waits.fetch_add(1, Ordering::Relaxed);
+                let mut sender = dial().await?;
+                let response = send_on(&mut sender, clone_request(&parts, &body)).await?;
+                Ok((
+                    IdleConn {
+                        sender,
+                        created: Instant::now(),
+                        idled: Instant::now(),
+                    },
+                    response,
+                ))
+            }
+        }
+    }
+
+    fn record_ttfb(&self, key: &str, elapsed: Duration) {
+        let Ok(mut ttfb) = self.ttfb.lock() else {
+            return;
+        };
+        let entry = ttfb.entry(key.to_string()).or_default();
+        let ms = elapsed.as_millis() as u64;
+        entry.count += 1;
+        entry.total_ms += ms;
+        entry.max_ms = entry.max_ms.max(ms);
+    }
+
+    /// Time to first byte per upstream key, sorted by key
+    pub fn ttfb_stats(&self) -> Vec<(String, UpstreamTtfb)> {
+        let mut stats: Vec<(String, UpstreamTtfb)> = self
+            .ttfb
+            .lock()
+            .map(|ttfb| {
+                ttfb.iter()
+                    .map(|(key, stats)| (key.clone(), stats.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    /// Idle connections currently pooled across all hosts
+    pub fn idle_connections(&self) -> usize {
+        self.idle
+            .lock()
+            .map(|idle| idle.values().map(Vec::len).sum())
+            .unwrap_or(0)
+    }
+
+    /// Snapshot of pool counters
+    pub fn stats(&self) -> UpstreamStats {
+        UpstreamStats {
+            checkouts: self.checkouts.load(Ordering::Relaxed),
+            waits: self.waits.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+            returned: self.returned.load(Ordering::Relaxed),
+            discarded: self.discarded.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Pop the freshest usable idle connection for a host
+    fn take_idle(&self, key: &str) -> Option<IdleConn> {
+        let mut idle = self.idle.lock().ok()?;
+        let conns = idle.get_mut(key)?;
+        let now = Instant::now();
+        while let Some(conn) = conns.pop() {
+            if now.duration_since(conn.idled) > self.config.idle_timeout
+                || now.duration_since(conn.created) > self.config.max_lifetime
+            {
+                self.expired.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+            if conn.sender.is_closed() {
+                self.discarded.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+            return Some(conn);
+        }
+        None
+    }
+
+    /// Start draining a host: its idle connections are dropped and
+    /// in-flight connections are discarded instead of returned, while
+    /// requests already running complete normally
+    pub fn drain(&self, key: &str) {
+        if let Ok(mut draining) = self.draining.lock() {
+            draining.insert(key.to_string());
+        }
+        if let Ok(mut idle) = self.idle.lock() {
+            if let Some(conns) = idle.remove(key) {
+                self.discarded
+                    .fetch_add(conns.len() as u64, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Stop draining a host (it rejoined the member set)
+    pub fn restore(&self, key: &str) {
+        if let Ok(mut draining) = self.draining.lock() {
+            draining.remove(key);
+        }
+    }
+
+    fn is_draining(&self, key: &str) -> bool {
+        self.draining
+            .lock()
+            .map(|draining| draining.contains(key))
+            .unwrap_or(false)
+    }
+
+    fn check_in(&self, key: &str, conn: IdleConn) {
+        if self.is_draining(key) {
+            self.discarded.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        if conn.sender.is_closed()
+            || conn.created.elapsed() > self.config.max_lifetime
+            || self.config.max_idle_per_host == 0
+        {
+            self.discarded.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        let Ok(mut idle) = self.idle.lock() else {
+            return;
+        };
+        let conns = idle.entry(key.to_string()).or_default();
+        if conns.len() >= self.config.max_idle_per_host {
+            self.discarded.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        conns.push(conn);
+        self.returned.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Default for UpstreamPool {
+    fn default() -> Self {
+        Self::new(UpstreamConfig::default())
+    }
+}
+
+/// Process-wide pool with default configuration
+pub fn default_pool() -> &'static UpstreamPool {
+    static POOL: OnceLock<UpstreamPool> = OnceLock::new();
+    POOL.get_or_init(UpstreamPool::default)
+}
+
+/// Inject the ambient scope's trace context unless the caller set
+/// its own `traceparent`
+fn inject_traceparent(request: &mut hyper::Request<Full<Bytes>>) {
+    if let Some(traceparent) = crate::scope::current_traceparent() {
+        if !request.headers().contains_key("traceparent") {
+            if let Ok(value) = hyper::header::HeaderValue::from_str(&traceparent) {
+                request.headers_mut().insert("traceparent", value);
+            }
+        }
+    }
+}
+
+/// Response headers as owned pairs, skipping non-UTF-8 values
+fn response_headers(response: &hyper::Response<hyper::body::Incoming>) -> Vec<(String, String)> {
+    response
+        .headers()
+        .iter()
+        .filter_map(|(k, v)| {
+            v.to_str()
+                .ok()
+                .map(|v| (k.as_str().to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// Rebuild a request from saved parts so a stale-connection retry can
+/// resend it (hyper requests are not `Clone`)
+fn clone_request(
+    parts: &hyper::http::request::Parts,
+    body: &Full<Bytes>,
+) -> hyper::Request<Full<Bytes>> {
+    let mut request = hyper::Request::builder()
+        .method(parts.method.clone())
+        .uri(parts.uri.clone())
+        .body(body.clone())
+        .expect("rebuilding a parsed request cannot fail");
+    *request.headers_mut() = parts.headers.clone();
+    request
+}
+
+async fn send_on(
+    sender: &mut Sender,
+    request: hyper::Request<Full<Bytes>>,
+) -> Result<hyper::Response<hyper::body::Incoming>> {
+    sender
+        .ready()
+        .await
+        .map_err(|e| Error::Hyper(e.to_string()))?;
+    sender
+        .send_request(request)
+        .await
+        .map_err(|e| Error::Hyper(e.to_string()))
+}
+
+/// Perform an HTTP/1.1 handshake over an established stream, spawning
+/// the connection driver
+pub async fn handshake<S>(stream: S) -> Result<Sender>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let io = hyper_util::rt::TokioIo::new(stream);
+    let (sender, connection) = hyper::client::conn::http1::handshake(io)
+        .await
+        .map_err(|e| Error::Hyper(e.to_string()))?;
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+    Ok(sender)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve `responses` keep-alive HTTP/1.1 responses on one accepted
+    /// connection, then close it
+    async fn one_connection_server(responses: usize) -> std::net::SocketAddr {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            for _ in 0..responses {
+                let _ = stream.read(&mut buf).await.unwrap();
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                    .await
+                    .unwrap();
+            }
+            // Hold the connection open until the client hangs up so
+            // check-in never races a server-side close
+            let _ = stream.read(&mut buf).await;
+        });
+        addr
+    }
+
+    fn get_request() -> hyper::Request<Full<Bytes>> {
+        hyper::Request::builder()
+            .uri("/")
+            .header("host", "test")
+            .body(Full::new(Bytes::new()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sequential_requests_reuse_one_connection() {
+        let addr = one_connection_server(3).await;
+        let pool = UpstreamPool::new(UpstreamConfig::default());
+        let dial = || async move { handshake(tokio::net::TcpStream::connect(addr).await?).await };
+
+        for _ in 0..3 {
+            let (status, _, body) = pool.request("test", dial, get_request()).await.unwrap();
+            assert_eq!(status, 200);
+            assert_eq!(body, Bytes::from("ok"));
+        }
+
+        let stats = pool.stats();
+        assert_eq!(stats.checkouts, 3);
+        assert_eq!(stats.waits, 1); // only the first request dialed
+        assert_eq!(stats.reused, 2);
+        assert_eq!(stats.returned, 3);
+        assert!((stats.reuse_ratio() - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(pool.idle_connections(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_streamed_response_pulls_frames_and_records_ttfb() {
+        let addr = one_connection_server(1).await;
+        let pool = UpstreamPool::new(UpstreamConfig::default());
+        let dial = || async move { handshake(tokio::net::TcpStream::connect(addr).await?).await };
+
+        let (status, headers, mut body) = pool
+            .request_streamed("test", dial, get_request())
+            .await
+            .unwrap();
+        assert_eq!(status, 200);
+        assert!(headers.iter().any(|(k, _)| k == "content-length"));
+
+        let mut data = Vec::new();
+        while let Some(chunk) = body.frame().await.unwrap() {
+            data.extend_from_slice(&chunk);
+        }
+        assert_eq!(&data[..], b"ok");
+
+        let ttfb = pool.ttfb_stats();
+        assert_eq!(ttfb.len(), 1);
+        assert_eq!(ttfb[0].0, "test");
+        assert_eq!(ttfb[0].1.count, 1);
+        // Streamed connections are never pooled
+        assert_eq!(pool.idle_connections(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_idle_connections_are_not_reused() {
+        let first = one_connection_server(1).await;
+        let pool = UpstreamPool::new(UpstreamConfig::default().idle_timeout(Duration::ZERO));
+
+        let dial = || async move { handshake(tokio::net::TcpStream::connect(first).await?).await };
+        pool.request("test", dial, get_request()).await.unwrap();
+
+        // The pooled connection is already past the idle timeout, so
+        // the second request dials the new server
+        let second = one_connection_server(1).await;
+        let dial = || async move { handshake(tokio::net::TcpStream::connect(second).await?).await };
+        pool.request("test", dial, get_request()).await.unwrap();
+
+        let stats = pool.stats();
+        assert_eq!(stats.waits, 2);
+        assert_eq!(stats.reused, 0);
+        assert_eq!(stats.expired, 1);
+    }
+
+    #[tokio::test]
+    async fn test_drain_drops_idle_and_blocks_check_in() {
+        let addr = one_connection_server(1).await;
+        let pool = UpstreamPool::new(UpstreamConfig::default());
+        let dial = || async move { handshake(tokio::net::TcpStream::connect(addr).await?).await };
+
+        pool.request("test", dial, get_request()).await.unwrap();
+        assert_eq!(pool.idle_connections(), 1);
+
+        pool.drain("test");
+        assert_eq!(pool.idle_connections(), 0);
+
+        // While draining, finished connections are not pooled again
+        let addr = one_connection_server(1).await;
+        let dial = || async move { handshake(tokio::net::TcpStream::connect(addr).await?).await };
+        pool.request("test", dial, get_request()).await.unwrap();
+        assert_eq!(pool.idle_connections(), 0);
+        assert_eq!(pool.stats().discarded, 2);
+
+        // Restored hosts pool normally
+        pool.restore("test");
+        let addr = one_connection_server(1).await;
+        let dial = || async move { handshake(tokio::net::TcpStream::connect(addr).await?).await };
+        pool.request("test", dial, get_request()).await.unwrap();
+        assert_eq!(pool.idle_connections(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_max_idle_per_host_zero_disables_pooling() {
+        let addr = one_connection_server(1).await;
+        let pool = UpstreamPool::new(UpstreamConfig::default().max_idle_per_host(0));
+        let dial = || async move { handshake(tokio::net::TcpStream::connect(addr).await?).await };
+
+        pool.request("test", dial, get_request()).await.unwrap();
+        assert_eq!(pool.idle_connections(), 0);
+        assert_eq!(pool.stats().discarded, 1);
+    }
+}
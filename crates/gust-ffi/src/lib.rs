@@ -0,0 +1,567 @@
+//! gust-ffi: C ABI bindings for the gust core server and router
+//!
+//! Exposes a handle-based C interface so runtimes with raw FFI
+//! (Deno `Deno.dlopen`, Bun `bun:ffi`) can embed gust without going
+//! through napi-rs. All logic lives in gust-core; this crate only
+//! marshals pointers across the boundary.
+//!
+//! ## Conventions
+//! - Handles are opaque pointers created by `*_new` and released with
+//!   the matching `*_free`; using a handle after free is undefined
+//!   behaviour.
+//! - Strings passed in are NUL-terminated UTF-8. Strings returned are
+//!   allocated by this crate and must be released with
+//!   [`gust_string_free`] (except [`gust_version`], which is static).
+//! - Fallible calls return [`GUST_OK`] or a negative `GUST_ERR_*`
+//!   code; [`gust_last_error`] returns a message for the most recent
+//!   failure on the calling thread.
+
+use gust_core::pure::serialize_json;
+use gust_core::{Router, Server, ServerBuilder, StaticRoute};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+use std::net::ToSocketAddrs;
+
+/// Success
+pub const GUST_OK: i32 = 0;
+/// A required pointer argument was null
+pub const GUST_ERR_NULL: i32 = -1;
+/// A string argument was not valid UTF-8
+pub const GUST_ERR_UTF8: i32 = -2;
+/// An argument was out of range or otherwise invalid
+pub const GUST_ERR_INVALID: i32 = -3;
+/// The address could not be resolved or bound
+pub const GUST_ERR_BIND: i32 = -4;
+/// The handle is in the wrong state for this call (e.g. already serving)
+pub const GUST_ERR_STATE: i32 = -5;
+/// No route matched
+pub const GUST_ERR_NOT_FOUND: i32 = -6;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<Vec<u8>>) {
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+/// Message for the most recent error on the calling thread
+///
+/// Returns null if no error has occurred. The pointer is owned by this
+/// crate and stays valid until the next failing call on the same
+/// thread; do not free it.
+#[no_mangle]
+pub extern "C" fn gust_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |msg| msg.as_ptr())
+    })
+}
+
+/// Crate version as a static NUL-terminated string; do not free
+#[no_mangle]
+pub extern "C" fn gust_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+/// Release a string returned by this crate
+///
+/// # Safety
+/// `ptr` must be null or a pointer previously returned by a gust-ffi
+/// function documented as requiring `gust_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn gust_string_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(unsafe { CString::from_raw(ptr) });
+    }
+}
+
+/// Borrow a NUL-terminated UTF-8 argument, recording errors
+///
+/// # Safety
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn borrow_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, i32> {
+    if ptr.is_null() {
+        set_last_error(format!("{} must not be null", name));
+        return Err(GUST_ERR_NULL);
+    }
+    match unsafe { CStr::from_ptr(ptr) }.to_str() {
+        Ok(s) => Ok(s),
+        Err(_) => {
+            set_last_error(format!("{} must be valid UTF-8", name));
+            Err(GUST_ERR_UTF8)
+        }
+    }
+}
+
+fn into_owned_string(s: String) -> *mut c_char {
+    CString::new(s).unwrap_or_default().into_raw()
+}
+
+// ============================================================================
+// Router
+// ============================================================================
+
+/// Opaque router handle
+pub struct FfiRouter {
+    inner: Router,
+}
+
+/// Create a router; release with [`gust_router_free`]
+#[no_mangle]
+pub extern "C" fn gust_router_new() -> *mut FfiRouter {
+    Box::into_raw(Box::new(FfiRouter {
+        inner: Router::new(),
+    }))
+}
+
+/// Release a router handle
+///
+/// # Safety
+/// `router` must be null or a live handle from [`gust_router_new`].
+#[no_mangle]
+pub unsafe extern "C" fn gust_router_free(router: *mut FfiRouter) {
+    if !router.is_null() {
+        drop(unsafe { Box::from_raw(router) });
+    }
+}
+
+/// Insert a route (`:param` and `*` segments follow router syntax)
+///
+/// # Safety
+/// `router` must be a live handle; `method` and `path` must be
+/// NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn gust_router_insert(
+    router: *mut FfiRouter,
+    method: *const c_char,
+    path: *const c_char,
+    handler_id: u32,
+) -> i32 {
+    if router.is_null() {
+        set_last_error("router must not be null");
+        return GUST_ERR_NULL;
+    }
+    let method = match unsafe { borrow_str(method, "method") } {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    let path = match unsafe { borrow_str(path, "path") } {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    unsafe { &mut *router }.inner.insert(method, path, handler_id);
+    GUST_OK
+}
+
+/// Match a request against the router
+///
+/// On a match writes the handler id to `out_handler_id` and, when
+/// `out_params` is non-null, a JSON object of path parameters (release
+/// with [`gust_string_free`]). Returns [`GUST_ERR_NOT_FOUND`] when no
+/// route matches.
+///
+/// # Safety
+/// `router` must be a live handle; `method` and `path` must be
+/// NUL-terminated strings; out pointers must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn gust_router_find(
+    router: *const FfiRouter,
+    method: *const c_char,
+    path: *const c_char,
+    out_handler_id: *mut u32,
+    out_params: *mut *mut c_char,
+) -> i32 {
+    if router.is_null() {
+        set_last_error("router must not be null");
+        return GUST_ERR_NULL;
+    }
+    let method = match unsafe { borrow_str(method, "method") } {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    let path = match unsafe { borrow_str(path, "path") } {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+
+    let Some(matched) = unsafe { &*router }.inner.find(method, path) else {
+        return GUST_ERR_NOT_FOUND;
+    };
+
+    if !out_handler_id.is_null() {
+        unsafe { *out_handler_id = matched.handler_id };
+    }
+    if !out_params.is_null() {
+        let params: HashMap<String, gust_core::middleware::validate::Value> = matched
+            .params
+            .into_iter()
+            .map(|(k, v)| (k, gust_core::middleware::validate::Value::String(v)))
+            .collect();
+        let json = serialize_json(&gust_core::middleware::validate::Value::Object(params));
+        unsafe { *out_params = into_owned_string(json) };
+    }
+    GUST_OK
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+enum ServerPhase {
+    Building(Box<ServerBuilder>),
+    Running {
+        runtime: gust_core::tokio::runtime::Runtime,
+        shutdown: Option<gust_core::tokio::sync::oneshot::Sender<()>>,
+    },
+    Stopped,
+}
+
+/// Opaque server handle
+pub struct FfiServer {
+    phase: ServerPhase,
+}
+
+/// Create a server; release with [`gust_server_free`]
+#[no_mangle]
+pub extern "C" fn gust_server_new() -> *mut FfiServer {
+    Box::into_raw(Box::new(FfiServer {
+        phase: ServerPhase::Building(Box::new(Server::builder())),
+    }))
+}
+
+/// Register a pre-rendered static route before serving
+///
+/// `body`/`body_len` describe a UTF-8 buffer; it is not NUL-terminated,
+/// so bodies may contain any text.
+///
+/// # Safety
+/// `server` must be a live handle; `method`, `path` and `content_type`
+/// must be NUL-terminated strings; `body` must point to `body_len`
+/// readable bytes (or be null with `body_len == 0`).
+#[no_mangle]
+pub unsafe extern "C" fn gust_server_add_static_route(
+    server: *mut FfiServer,
+    method: *const c_char,
+    path: *const c_char,
+    status: u16,
+    content_type: *const c_char,
+    body: *const u8,
+    body_len: usize,
+) -> i32 {
+    if server.is_null() {
+        set_last_error("server must not be null");
+        return GUST_ERR_NULL;
+    }
+    let method = match unsafe { borrow_str(method, "method") } {
+        Ok(s) => s.to_string(),
+        Err(code) => return code,
+    };
+    let path = match unsafe { borrow_str(path, "path") } {
+        Ok(s) => s.to_string(),
+        Err(code) => return code,
+    };
+    let content_type = match unsafe { borrow_str(content_type, "content_type") } {
+        Ok(s) => s.to_string(),
+        Err(code) => return code,
+    };
+    let body = if body.is_null() {
+        if body_len != 0 {
+            set_last_error("body must not be null when body_len > 0");
+            return GUST_ERR_NULL;
+        }
+        String::new()
+    } else {
+        match std::str::from_utf8(unsafe { std::slice::from_raw_parts(body, body_len) }) {
+            Ok(s) => s.to_string(),
+            Err(_) => {
+                set_last_error("body must be valid UTF-8");
+                return GUST_ERR_UTF8;
+            }
+        }
+    };
+
+    let server = unsafe { &mut *server };
+    let builder = match std::mem::replace(&mut server.phase, ServerPhase::Stopped) {
+        ServerPhase::Building(builder) => builder,
+        phase => {
+            server.phase = phase;
+            set_last_error("routes must be added before gust_server_serve");
+            return GUST_ERR_STATE;
+        }
+    };
+    server.phase = ServerPhase::Building(Box::new(builder.static_route(StaticRoute {
+        method,
+        path,
+        status,
+        content_type,
+        body,
+        handler_id: 0, // assigned by the builder
+    })));
+    GUST_OK
+}
+
+/// Bind and start serving in background threads
+///
+/// Returns once the socket is bound; requests are handled on a
+/// dedicated tokio runtime owned by the handle. Call
+/// [`gust_server_shutdown`] to stop accepting.
+///
+/// # Safety
+/// `server` must be a live handle; `hostname` must be a NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn gust_server_serve(
+    server: *mut FfiServer,
+    hostname: *const c_char,
+    port: u16,
+) -> i32 {
+    if server.is_null() {
+        set_last_error("server must not be null");
+        return GUST_ERR_NULL;
+    }
+    let hostname = match unsafe { borrow_str(hostname, "hostname") } {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+
+    let addr = match (hostname, port).to_socket_addrs() {
+        Ok(mut addrs) => match addrs.next() {
+            Some(addr) => addr,
+            None => {
+                set_last_error(format!("could not resolve {}:{}", hostname, port));
+                return GUST_ERR_BIND;
+            }
+        },
+        Err(err) => {
+            set_last_error(format!("could not resolve {}:{}: {}", hostname, port, err));
+            return GUST_ERR_BIND;
+        }
+    };
+
+    let server = unsafe { &mut *server };
+    let builder = match std::mem::replace(&mut server.phase, ServerPhase::Stopped) {
+        ServerPhase::Building(builder) => builder,
+        phase => {
+            server.phase = phase;
+            set_last_error("server is already serving or stopped");
+            return GUST_ERR_STATE;
+        }
+    };
+
+    let runtime = match gust_core::tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            set_last_error(format!("failed to start runtime: {}", err));
+            return GUST_ERR_STATE;
+        }
+    };
+
+    // Bind synchronously so the caller sees bind failures as a return
+    // code instead of a dead server
+    let listener = match gust_core::create_optimized_socket(&addr)
+        .and_then(|socket| {
+            socket.set_nonblocking(true)?;
+            Ok(socket)
+        })
+        .and_then(|socket| {
+            let _guard = runtime.enter();
+            gust_core::tokio::net::TcpListener::from_std(socket.into())
+        }) {
+        Ok(listener) => listener,
+        Err(err) => {
+            set_last_error(format!("failed to bind {}: {}", addr, err));
+            return GUST_ERR_BIND;
+        }
+    };
+
+    let (shutdown_tx, shutdown_rx) = gust_core::tokio::sync::oneshot::channel::<()>();
+    let built = builder.build();
+    runtime.spawn(async move {
+        let _ = built
+            .serve_on(listener, async {
+                let _ = shutdown_rx.await;
+            })
+            .await;
+    });
+
+    server.phase = ServerPhase::Running {
+        runtime,
+        shutdown: Some(shutdown_tx),
+    };
+    GUST_OK
+}
+
+/// Stop accepting connections
+///
+/// In-flight requests are dropped when the runtime shuts down; the
+/// handle must still be released with [`gust_server_free`].
+///
+/// # Safety
+/// `server` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn gust_server_shutdown(server: *mut FfiServer) -> i32 {
+    if server.is_null() {
+        set_last_error("server must not be null");
+        return GUST_ERR_NULL;
+    }
+    let server = unsafe { &mut *server };
+    match std::mem::replace(&mut server.phase, ServerPhase::Stopped) {
+        ServerPhase::Running {
+            runtime,
+            mut shutdown,
+        } => {
+            if let Some(tx) = shutdown.take() {
+                let _ = tx.send(());
+            }
+            runtime.shutdown_background();
+            GUST_OK
+        }
+        phase => {
+            server.phase = phase;
+            set_last_error("server is not serving");
+            GUST_ERR_STATE
+        }
+    }
+}
+
+/// Release a server handle, shutting it down if still serving
+///
+/// # Safety
+/// `server` must be null or a live handle from [`gust_server_new`].
+#[no_mangle]
+pub unsafe extern "C" fn gust_server_free(server: *mut FfiServer) {
+    if server.is_null() {
+        return;
+    }
+    let mut server = unsafe { Box::from_raw(server) };
+    if let ServerPhase::Running {
+        runtime,
+        mut shutdown,
+    } = std::mem::replace(&mut server.phase, ServerPhase::Stopped)
+    {
+        if let Some(tx) = shutdown.take() {
+            let _ = tx.send(());
+        }
+        runtime.shutdown_background();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    fn c(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    #[test]
+    fn test_version_is_static_string() {
+        let version = unsafe { CStr::from_ptr(gust_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_router_insert_and_find() {
+        let router = gust_router_new();
+        let method = c("GET");
+        let path = c("/users/:id");
+
+        let code = unsafe { gust_router_insert(router, method.as_ptr(), path.as_ptr(), 7) };
+        assert_eq!(code, GUST_OK);
+
+        let lookup = c("/users/42");
+        let mut handler_id = 0u32;
+        let mut params: *mut c_char = std::ptr::null_mut();
+        let code = unsafe {
+            gust_router_find(
+                router,
+                method.as_ptr(),
+                lookup.as_ptr(),
+                &mut handler_id,
+                &mut params,
+            )
+        };
+        assert_eq!(code, GUST_OK);
+        assert_eq!(handler_id, 7);
+        let json = unsafe { CStr::from_ptr(params) }.to_str().unwrap();
+        assert_eq!(json, r#"{"id":"42"}"#);
+        unsafe { gust_string_free(params) };
+
+        let miss = c("/missing");
+        let code = unsafe {
+            gust_router_find(
+                router,
+                method.as_ptr(),
+                miss.as_ptr(),
+                &mut handler_id,
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, GUST_ERR_NOT_FOUND);
+
+        unsafe { gust_router_free(router) };
+    }
+
+    #[test]
+    fn test_null_arguments_set_last_error() {
+        let router = gust_router_new();
+        let code = unsafe { gust_router_insert(router, std::ptr::null(), std::ptr::null(), 0) };
+        assert_eq!(code, GUST_ERR_NULL);
+        let message = unsafe { CStr::from_ptr(gust_last_error()) };
+        assert_eq!(message.to_str().unwrap(), "method must not be null");
+        unsafe { gust_router_free(router) };
+    }
+
+    #[test]
+    fn test_server_lifecycle() {
+        let server = gust_server_new();
+        let method = c("GET");
+        let path = c("/health");
+        let content_type = c("application/json");
+        let body = br#"{"status":"ok"}"#;
+
+        let code = unsafe {
+            gust_server_add_static_route(
+                server,
+                method.as_ptr(),
+                path.as_ptr(),
+                200,
+                content_type.as_ptr(),
+                body.as_ptr(),
+                body.len(),
+            )
+        };
+        assert_eq!(code, GUST_OK);
+
+        // Port 0 lets the OS choose a free port for the test
+        let hostname = c("127.0.0.1");
+        let code = unsafe { gust_server_serve(server, hostname.as_ptr(), 0) };
+        assert_eq!(code, GUST_OK);
+
+        // Adding routes after serve is a state error
+        let code = unsafe {
+            gust_server_add_static_route(
+                server,
+                method.as_ptr(),
+                path.as_ptr(),
+                200,
+                content_type.as_ptr(),
+                body.as_ptr(),
+                body.len(),
+            )
+        };
+        assert_eq!(code, GUST_ERR_STATE);
+
+        assert_eq!(unsafe { gust_server_shutdown(server) }, GUST_OK);
+        assert_eq!(unsafe { gust_server_shutdown(server) }, GUST_ERR_STATE);
+        unsafe { gust_server_free(server) };
+    }
+}
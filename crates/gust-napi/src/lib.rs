@@ -14,28 +14,33 @@
 use gust_core::bytes::Bytes;
 use gust_core::{
     Method, Request, Response, ResponseBuilder, Router, StatusCode,
+    // SSE formatting from core
+    SseEvent as CoreSseEvent,
     // WebSocket support from core
     WebSocketFrame as CoreFrame, WebSocketOpcode as CoreOpcode,
     generate_accept_key as core_generate_accept_key,
     // Connection tracking from core
     ConnectionTracker as CoreConnectionTracker,
+    ConnectionStats as CoreConnectionStats,
     // Middleware
     middleware::{
+        Middleware,
         MiddlewareChain,
         circuit_breaker::{CircuitBreaker as RustCircuitBreaker, CircuitBreakerConfig as RustCBConfig, Bulkhead as RustBulkhead, BulkheadConfig as RustBulkheadConfig, CircuitState as RustCircuitState},
         validate::{Schema as RustSchema, SchemaType as RustSchemaType, StringFormat as RustStringFormat, Value as RustValue, validate as rust_validate},
-        range::{parse_range as rust_parse_range, content_range as rust_content_range, get_mime_type as rust_get_mime_type, generate_etag as rust_generate_etag},
-        proxy::{ProxyConfig as RustProxyConfig, TrustProxy as RustTrustProxy, extract_proxy_info as rust_extract_proxy_info},
-        otel::{Span as RustSpan, SpanContext as RustSpanContext, SpanStatus as RustSpanStatus, Tracer as RustTracer, TracerConfig as RustTracerConfig, MetricsCollector as RustMetricsCollector, generate_trace_id as rust_generate_trace_id, generate_span_id as rust_generate_span_id, parse_traceparent as rust_parse_traceparent, format_traceparent as rust_format_traceparent},
+        range::{parse_range as rust_parse_range, content_range as rust_content_range, get_mime_type as rust_get_mime_type, generate_etag as rust_generate_etag, check_if_none_match as rust_check_if_none_match},
+        cache::etag as rust_body_etag,
+        proxy::{ProxyConfig as RustProxyConfig, TrustProxy as RustTrustProxy, extract_proxy_info as rust_extract_proxy_info, is_trusted as rust_is_trusted},
+        otel::{Span as RustSpan, SpanContext as RustSpanContext, SpanEvent as RustSpanEvent, SpanLink as RustSpanLink, SpanStatus as RustSpanStatus, Tracer as RustTracer, TracerConfig as RustTracerConfig, MetricsCollector as RustMetricsCollector, generate_trace_id as rust_generate_trace_id, generate_span_id as rust_generate_span_id, parse_traceparent as rust_parse_traceparent, format_traceparent as rust_format_traceparent, parse_baggage as rust_parse_baggage, format_baggage as rust_format_baggage},
     },
 };
 use gust_core::http_body_util::{Full, BodyExt};
 use napi::bindgen_prelude::*;
-use napi::threadsafe_function::{ThreadsafeFunction, ErrorStrategy};
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode, ErrorStrategy};
 use napi_derive::napi;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU32, AtomicBool, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, AtomicBool, Ordering};
 use std::time::Duration;
 use gust_core::tokio;
 use gust_core::hyper;
@@ -54,10 +59,25 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 pub struct RequestContext {
     pub method: String,
     pub path: String,
+    /// Matched route pattern (e.g. "/users/:id"); unset when no
+    /// route matched (fallback handler)
+    pub route_pattern: Option<String>,
     pub params: HashMap<String, String>,
     pub query: Option<String>,
     pub headers: HashMap<String, String>,
     pub body: String,
+    /// Request body as raw bytes; set whenever the request has a body
+    /// so binary payloads (images, protobuf) survive the JS boundary.
+    /// `body` is empty when the bytes are not valid UTF-8.
+    pub body_buffer: Option<Buffer>,
+    /// Client IP (socket address, or forwarded IP when the peer is
+    /// trusted via trustProxy); unset on the raw engine
+    pub ip: Option<String>,
+    /// Request protocol ("http"/"https"); unset on the raw engine
+    pub protocol: Option<String>,
+    /// Request host (Host header or trusted X-Forwarded-Host); unset
+    /// on the raw engine
+    pub host: Option<String>,
 }
 
 /// Response from JS handler
@@ -65,7 +85,13 @@ pub struct RequestContext {
 pub struct ResponseData {
     pub status: u32,
     pub headers: HashMap<String, String>,
+    /// Headers as alternating name/value pairs; allows duplicates
+    /// (e.g. multiple Set-Cookie). Takes precedence over `headers`.
+    pub headers_flat: Option<Vec<String>>,
     pub body: String,
+    /// Body as raw bytes; takes precedence over `body` when set so
+    /// binary responses round-trip without UTF-8 conversion
+    pub body_buffer: Option<Buffer>,
     /// Set to true if body is a streaming response (chunked)
     pub streaming: Option<bool>,
 }
@@ -111,6 +137,9 @@ pub struct NativeHandlerContext {
     pub method: String,
     /// Request path
     pub path: String,
+    /// Matched route pattern (e.g. "/users/:id"); a low-cardinality
+    /// label for telemetry, unlike `path`
+    pub route_pattern: Option<String>,
     /// Query string (without ?)
     pub query: String,
     /// Request headers
@@ -119,6 +148,20 @@ pub struct NativeHandlerContext {
     pub params: HashMap<String, String>,
     /// Request body as bytes
     pub body: Vec<u8>,
+    /// Headers as a length-prefixed flat buffer, set instead of
+    /// `headers` when batched transfer is enabled (decoded lazily in JS)
+    pub headers_buffer: Option<Vec<u8>>,
+    /// Route params as alternating name/value pairs, set instead of
+    /// `params` when batched transfer is enabled
+    pub params_flat: Option<Vec<String>>,
+    /// Client IP (socket address, or forwarded IP when the peer is
+    /// trusted via trustProxy); unset on the raw engine
+    pub ip: Option<String>,
+    /// Request protocol ("http"/"https"); unset on the raw engine
+    pub protocol: Option<String>,
+    /// Request host (Host header or trusted X-Forwarded-Host); unset
+    /// on the raw engine
+    pub host: Option<String>,
 }
 
 /// Input for invoke handler callback
@@ -180,6 +223,137 @@ pub struct RateLimitConfig {
     pub key_by: Option<String>,
 }
 
+/// Admission control configuration
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct AdmissionConfig {
+    /// In-flight requests where normal traffic sheds; low-priority
+    /// sheds at 3/4 of this, high-priority at twice it (default: 256)
+    pub max_concurrency: Option<u32>,
+    /// Latency target in ms; a window whose fastest request exceeds it
+    /// sheds low-priority traffic (default: 100)
+    pub target_latency_ms: Option<u32>,
+    /// Latency evaluation window in ms (default: 1000)
+    pub interval_ms: Option<u32>,
+    /// Retry-After seconds on shed 503 responses (default: 1)
+    pub retry_after_seconds: Option<u32>,
+    /// Header carrying the client-requested priority
+    /// (default: x-priority)
+    pub header: Option<String>,
+    /// Path prefix to priority ("low" | "normal" | "high") rules;
+    /// they override the header
+    pub routes: Option<HashMap<String, String>>,
+}
+
+/// Admission control counters
+#[napi(object)]
+pub struct AdmissionStats {
+    /// Requests admitted to dispatch
+    pub admitted: i64,
+    /// Requests shed with 503
+    pub shed: i64,
+    /// Requests currently in flight
+    pub in_flight: i64,
+    /// Latency target currently breached
+    pub latency_breached: bool,
+}
+
+/// Adaptive concurrency limiter configuration
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct AdaptiveConfig {
+    /// Limit floor; the limiter never sheds below this (default: 1)
+    pub min_limit: Option<u32>,
+    /// Limit ceiling (default: 1000)
+    pub max_limit: Option<u32>,
+    /// Starting limit for new routes (default: 16)
+    pub initial_limit: Option<u32>,
+    /// Estimated queued requests below which the limit grows
+    /// (default: 3)
+    pub alpha: Option<f64>,
+    /// Estimated queued requests above which the limit shrinks
+    /// (default: 6)
+    pub beta: Option<f64>,
+    /// Multiplicative decrease factor on overload (default: 0.9)
+    pub backoff_ratio: Option<f64>,
+    /// Completed requests per limit adjustment (default: 10)
+    pub sample_window: Option<u32>,
+    /// Track one limit per route path instead of a single global one
+    /// (default: true)
+    pub per_route: Option<bool>,
+    /// Retry-After seconds on shed 503 responses (default: 1)
+    pub retry_after_seconds: Option<u32>,
+}
+
+/// Declarative request transformation rules
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct TransformConfig {
+    /// Headers to set, replacing any client-sent values
+    pub set_headers: Option<HashMap<String, String>>,
+    /// Headers to set only when the request does not carry one
+    pub default_headers: Option<HashMap<String, String>>,
+    /// Headers to drop entirely
+    pub remove_headers: Option<Vec<String>>,
+    /// Headers to rename (old name to new name)
+    pub rename_headers: Option<HashMap<String, String>>,
+    /// Leading path prefix to strip before routing (e.g. "/api")
+    pub strip_prefix: Option<String>,
+    /// Query parameters to append
+    pub append_query: Option<HashMap<String, String>>,
+}
+
+/// Response transformation and HTML injection rules
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct ResponseTransformConfig {
+    /// Snippets inserted before the first occurrence of a marker
+    /// (marker to snippet, e.g. "</head>" to an analytics tag)
+    pub inject_before: Option<HashMap<String, String>>,
+    /// Absolute URL rewrites applied to every occurrence (from -> to)
+    pub rewrite_urls: Option<HashMap<String, String>>,
+    /// Response headers to set, replacing existing values
+    pub set_headers: Option<HashMap<String, String>>,
+    /// Response headers to drop
+    pub remove_headers: Option<Vec<String>>,
+    /// Content-type prefix whose bodies are transformed
+    /// (default: text/html)
+    pub content_type: Option<String>,
+}
+
+/// Well-known route helpers
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct WellKnownConfig {
+    /// Content served at /robots.txt (text/plain)
+    pub robots: Option<String>,
+    /// Bytes served at /favicon.ico (image/x-icon)
+    pub favicon: Option<Buffer>,
+    /// Content served at /.well-known/security.txt (RFC 9116)
+    pub security_txt: Option<String>,
+    /// Redirect target for /.well-known/change-password
+    pub change_password_url: Option<String>,
+    /// Cache-Control for all entries (default: "public, max-age=86400")
+    pub cache_control: Option<String>,
+}
+
+/// Adaptive limiter gauges for one route
+#[napi(object)]
+pub struct AdaptiveRouteStats {
+    /// Route path, or "*" for the global limiter
+    pub route: String,
+    /// Current in-flight limit
+    pub limit: i64,
+    /// Requests currently in flight
+    pub in_flight: i64,
+    /// Requests shed at the limit
+    pub shed: i64,
+    /// Best latency observed (no-load baseline), in milliseconds
+    pub min_latency_ms: f64,
+    /// Average latency of the last full sample window, in milliseconds
+    pub window_latency_ms: f64,
+}
+
 /// Security headers configuration
 #[napi(object)]
 #[derive(Clone, Default)]
@@ -198,6 +372,116 @@ pub struct SecurityConfig {
     pub referrer_policy: Option<String>,
 }
 
+/// Request tracing configuration
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct TracingConfig {
+    /// Header name for the request ID (default: X-Request-ID)
+    pub header_name: Option<String>,
+    /// Generate a request ID when the header is absent (default: true)
+    pub generate_id: Option<bool>,
+    /// ID generator: "uuid", "uuidv7", "ulid", "snowflake", "nanoid",
+    /// "shortid", or "counter" (default: nanoid). The time-ordered
+    /// generators (uuidv7, ulid, snowflake) sort chronologically in logs.
+    pub id_generator: Option<String>,
+    /// Machine ID (0-1023) for the snowflake generator (default: 0)
+    pub machine_id: Option<u32>,
+    /// Parse/generate W3C trace context — trace + span IDs
+    /// (default: true)
+    pub propagate_trace: Option<bool>,
+    /// Log requests to stderr
+    pub log_requests: Option<bool>,
+    /// Log responses to stderr
+    pub log_responses: Option<bool>,
+}
+
+/// Body digest verification configuration
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct DigestConfig {
+    /// Verify Content-Digest/Content-MD5 request headers (default: true)
+    pub verify_request: Option<bool>,
+    /// Emit a response Content-Digest: "sha-256" or "md5"
+    pub emit_response: Option<String>,
+}
+
+/// Response cache configuration
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct ResponseCacheConfig {
+    /// TTL for cached responses in seconds (default: 300)
+    pub ttl_seconds: Option<u32>,
+    /// Maximum number of cached responses (default: 1000)
+    pub max_entries: Option<u32>,
+    /// Request headers included in the default cache key
+    pub vary_headers: Option<Vec<String>>,
+    /// Cookie names included in the default cache key
+    pub vary_cookies: Option<Vec<String>>,
+    /// Query params in the key: omit for all (sorted), empty for none
+    pub query_params: Option<Vec<String>>,
+    /// Per-route key shapes; first matching prefix wins
+    pub key_rules: Option<Vec<CacheKeyRule>>,
+}
+
+/// Cache key shape for a route prefix
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct CacheKeyRule {
+    /// Path prefix this shape applies to
+    pub prefix: String,
+    /// Request headers included in the cache key
+    pub vary_headers: Option<Vec<String>>,
+    /// Cookie names included in the cache key
+    pub vary_cookies: Option<Vec<String>>,
+    /// Query params in the key: omit for all (sorted), empty for none
+    pub query_params: Option<Vec<String>>,
+}
+
+/// Hit/miss statistics for one cache key shape
+#[napi(object)]
+#[derive(Clone)]
+pub struct CacheShapeStats {
+    /// Key shape label (route prefix or "default")
+    pub shape: String,
+    pub hits: i64,
+    pub misses: i64,
+    /// Hit ratio in [0, 1]
+    pub hit_ratio: f64,
+}
+
+/// Edge cache headers configuration
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct EdgeCacheConfig {
+    /// Fallback policy when no route rule matches
+    pub default_policy: Option<CachePolicyRule>,
+    /// Per-route policies; first matching prefix wins
+    pub routes: Option<Vec<CachePolicyRule>>,
+    /// Remove hop-by-hop headers from responses (default: true)
+    pub strip_hop_by_hop: Option<bool>,
+}
+
+/// Cache policy for one route prefix
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct CachePolicyRule {
+    /// Path prefix this policy applies to (ignored for the default)
+    pub prefix: Option<String>,
+    /// Scope: "public" (default), "private", or "no-store"
+    pub scope: Option<String>,
+    /// Browser max-age in seconds
+    pub max_age: Option<u32>,
+    /// Shared cache (CDN) max-age; emitted as s-maxage,
+    /// Surrogate-Control, and CDN-Cache-Control
+    pub s_maxage: Option<u32>,
+    /// stale-while-revalidate window in seconds
+    pub stale_while_revalidate: Option<u32>,
+    /// stale-if-error window in seconds
+    pub stale_if_error: Option<u32>,
+    /// Mark the response immutable (fingerprinted assets)
+    pub immutable: Option<bool>,
+}
+
 /// Compression configuration
 #[napi(object)]
 #[derive(Clone, Default)]
@@ -228,6 +512,8 @@ pub struct ServerConfig {
     pub rate_limit: Option<RateLimitConfig>,
     /// Security headers configuration
     pub security: Option<SecurityConfig>,
+    /// Request tracing configuration
+    pub tracing: Option<TracingConfig>,
     /// Compression configuration
     pub compression: Option<CompressionConfig>,
     /// TLS/HTTPS configuration
@@ -242,1947 +528,7418 @@ pub struct ServerConfig {
     pub keep_alive_timeout_ms: Option<u32>,
     /// Maximum header size in bytes (default: 8KB)
     pub max_header_size: Option<u32>,
+    /// Maximum in-flight requests per HTTP/1.1 connection (default: 1);
+    /// pipelined requests beyond this depth are rejected with 429
+    pub max_pipeline_depth: Option<u32>,
+    /// Transfer headers/params as flat buffers decoded lazily in JS
+    /// (default: false)
+    pub batched_headers: Option<bool>,
+    /// HTTP engine: "hyper" (default) or "raw" — the raw HTTP/1.1
+    /// engine skips hyper for benchmark/plaintext workloads
+    pub engine: Option<String>,
+    /// Which peers to trust for X-Forwarded-* headers when computing
+    /// per-request client ip/protocol/host (default: None)
+    pub trust_proxy: Option<TrustProxy>,
+    /// Explicit IP/CIDR list of trusted proxies; takes precedence
+    /// over `trust_proxy` and enables per-hop chain evaluation
+    pub trust_proxy_addresses: Option<Vec<String>>,
+    /// Separate admin listener for health/metrics/debug endpoints
+    pub admin: Option<AdminListenerConfig>,
 }
 
-// ============================================================================
-// Circuit Breaker
-// ============================================================================
-
-/// Circuit breaker configuration
+/// One issue found by `validateConfig`
 #[napi(object)]
 #[derive(Clone)]
-pub struct CircuitBreakerConfig {
-    /// Number of failures before opening circuit
-    pub failure_threshold: u32,
-    /// Number of successes before closing circuit (from half-open)
-    pub success_threshold: u32,
-    /// Time in milliseconds before attempting to recover
-    pub reset_timeout_ms: u32,
-    /// Time window in milliseconds for counting failures
-    pub failure_window_ms: u32,
-    /// Request timeout in milliseconds
-    pub timeout_ms: u32,
-    /// Circuit breaker name
-    pub name: Option<String>,
+pub struct ConfigIssue {
+    /// "error" (the server cannot start correctly) or "warning"
+    pub severity: String,
+    /// The offending config field, dotted for nested keys
+    pub field: String,
+    /// What is wrong and how to fix it
+    pub message: String,
 }
 
-/// Circuit breaker state
-#[napi(string_enum)]
-pub enum CircuitState {
-    Closed,
-    Open,
-    HalfOpen,
+fn config_error(field: &str, message: impl Into<String>) -> ConfigIssue {
+    ConfigIssue {
+        severity: "error".to_string(),
+        field: field.to_string(),
+        message: message.into(),
+    }
 }
 
-/// Circuit breaker stats
-#[napi(object)]
-pub struct CircuitStats {
-    pub state: String,
-    pub failures: u32,
-    pub successes: u32,
-    pub total_requests: i64,
-    pub total_failures: i64,
-    pub total_successes: i64,
+fn config_warning(field: &str, message: impl Into<String>) -> ConfigIssue {
+    ConfigIssue {
+        severity: "warning".to_string(),
+        field: field.to_string(),
+        message: message.into(),
+    }
 }
 
-/// Circuit breaker wrapper for napi
+/// Validate a server config without starting a server
+///
+/// Returns every problem found, not just the first: "error" issues
+/// are settings the server cannot honor (conflicting or out of
+/// range), "warning" issues are legal but probably not what was
+/// intended. `withConfig` runs the same checks and fails on errors.
 #[napi]
-pub struct CircuitBreaker {
-    inner: Arc<RustCircuitBreaker>,
+pub fn validate_config(config: ServerConfig) -> Vec<ConfigIssue> {
+    validate_server_config(&config)
 }
 
-#[napi]
-impl CircuitBreaker {
-    #[napi(constructor)]
-    pub fn new(config: CircuitBreakerConfig) -> Self {
-        let name = config.name.unwrap_or_else(|| "default".to_string());
-        let rust_config = RustCBConfig::new(name)
-            .failure_threshold(config.failure_threshold)
-            .success_threshold(config.success_threshold)
-            .reset_timeout(Duration::from_millis(config.reset_timeout_ms as u64))
-            .failure_window(Duration::from_millis(config.failure_window_ms as u64))
-            .timeout(Duration::from_millis(config.timeout_ms as u64));
+fn validate_server_config(config: &ServerConfig) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
 
-        Self {
-            inner: Arc::new(RustCircuitBreaker::new(rust_config)),
+    if let Some(port) = config.port {
+        if port > 65535 {
+            issues.push(config_error(
+                "port",
+                format!("{} is not a valid TCP port (0-65535)", port),
+            ));
         }
     }
 
-    /// Check if request can proceed
-    #[napi]
-    pub fn can_request(&self) -> bool {
-        self.inner.can_request()
+    if config.http2 == Some(true) && config.tls.is_none() {
+        issues.push(config_error(
+            "http2",
+            "HTTP/2 is negotiated via TLS ALPN; enable tls or drop http2",
+        ));
     }
 
-    /// Record successful request
-    #[napi]
-    pub fn record_success(&self) {
-        self.inner.record_success();
+    if let Some(ref tls) = config.tls {
+        if tls.cert_path.is_none() && tls.cert.is_none() {
+            issues.push(config_error(
+                "tls.cert_path",
+                "no certificate provided; set cert_path or cert (PEM)",
+            ));
+        }
+        if tls.key_path.is_none() && tls.key.is_none() {
+            issues.push(config_error(
+                "tls.key_path",
+                "no private key provided; set key_path or key (PEM)",
+            ));
+        }
+        if tls.cert_path.is_some() && tls.cert.is_some() {
+            issues.push(config_warning(
+                "tls.cert",
+                "both cert_path and cert are set; cert_path takes precedence",
+            ));
+        }
+        if tls.key_path.is_some() && tls.key.is_some() {
+            issues.push(config_warning(
+                "tls.key",
+                "both key_path and key are set; key_path takes precedence",
+            ));
+        }
     }
 
-    /// Record failed request
-    #[napi]
-    pub fn record_failure(&self) {
-        self.inner.record_failure();
+    if let Some(ref rate_limit) = config.rate_limit {
+        if rate_limit.window_seconds == 0 {
+            issues.push(config_error(
+                "rate_limit.window_seconds",
+                "window must be at least 1 second",
+            ));
+        }
+        if rate_limit.max_requests == 0 {
+            issues.push(config_error(
+                "rate_limit.max_requests",
+                "a limit of 0 rejects every request",
+            ));
+        }
     }
 
-    /// Get current state
-    #[napi]
-    pub fn state(&self) -> String {
-        match self.inner.state() {
-            RustCircuitState::Closed => "closed".to_string(),
-            RustCircuitState::Open => "open".to_string(),
-            RustCircuitState::HalfOpen => "half-open".to_string(),
+    if let Some(ref compression) = config.compression {
+        if let Some(level) = compression.level {
+            if level == 0 || level > 11 {
+                issues.push(config_error(
+                    "compression.level",
+                    format!("{} is out of range (1-9 for gzip, 1-11 for brotli)", level),
+                ));
+            } else if level > 9 && compression.brotli != Some(true) {
+                issues.push(config_warning(
+                    "compression.level",
+                    format!("{} exceeds the gzip maximum of 9 and brotli is not enabled", level),
+                ));
+            }
         }
     }
 
-    /// Get statistics
-    #[napi]
-    pub fn stats(&self) -> CircuitStats {
-        let stats = self.inner.stats();
-        CircuitStats {
-            state: match stats.state {
-                RustCircuitState::Closed => "closed".to_string(),
-                RustCircuitState::Open => "open".to_string(),
-                RustCircuitState::HalfOpen => "half-open".to_string(),
-            },
-            failures: stats.failures,
-            successes: stats.successes,
-            total_requests: stats.total_requests as i64,
-            total_failures: stats.total_failures as i64,
-            total_successes: stats.total_successes as i64,
+    if let Some(ref engine) = config.engine {
+        match engine.as_str() {
+            "hyper" => {}
+            "raw" => {
+                if config.tls.is_some() || config.http2 == Some(true) {
+                    issues.push(config_error(
+                        "engine",
+                        "the raw engine does not support TLS or HTTP/2; use the hyper engine",
+                    ));
+                }
+                let middleware = [
+                    ("cors", config.cors.is_some()),
+                    ("rate_limit", config.rate_limit.is_some()),
+                    ("security", config.security.is_some()),
+                    ("tracing", config.tracing.is_some()),
+                    ("compression", config.compression.is_some()),
+                ];
+                for (name, configured) in middleware {
+                    if configured {
+                        issues.push(config_warning(
+                            name,
+                            "middleware is not applied by the raw engine; requests bypass it",
+                        ));
+                    }
+                }
+            }
+            other => {
+                issues.push(config_error(
+                    "engine",
+                    format!("unknown engine '{}' (expected 'hyper' or 'raw')", other),
+                ));
+            }
         }
     }
 
-    /// Reset circuit breaker
-    #[napi]
-    pub fn reset(&self) {
-        self.inner.reset();
+    if config.max_body_size == Some(0) {
+        issues.push(config_warning(
+            "max_body_size",
+            "a limit of 0 rejects every request with a body",
+        ));
     }
+    if config.max_header_size == Some(0) {
+        issues.push(config_error(
+            "max_header_size",
+            "a limit of 0 rejects every request",
+        ));
+    }
+    if config.max_pipeline_depth == Some(0) {
+        issues.push(config_error(
+            "max_pipeline_depth",
+            "a depth of 0 rejects every request; use 1 to disable pipelining",
+        ));
+    }
+    if config.request_timeout_ms == Some(0) {
+        issues.push(config_warning(
+            "request_timeout_ms",
+            "0 disables the request timeout; slow clients can hold connections open",
+        ));
+    }
+
+    if let Some(ref admin) = config.admin {
+        if admin.port.is_none() && admin.socket_path.is_none() {
+            issues.push(config_error(
+                "admin",
+                "set port or socket_path for the admin listener",
+            ));
+        }
+        if let Some(port) = admin.port {
+            if port > 65535 {
+                issues.push(config_error(
+                    "admin.port",
+                    format!("{} is not a valid TCP port (0-65535)", port),
+                ));
+            }
+        }
+        if let Some(ref hostname) = admin.hostname {
+            let loopback = hostname == "localhost"
+                || hostname
+                    .parse::<std::net::IpAddr>()
+                    .map(|ip| ip.is_loopback())
+                    .unwrap_or(false);
+            if !loopback {
+                issues.push(config_warning(
+                    "admin.hostname",
+                    format!(
+                        "'{}' is not loopback; the admin listener is unauthenticated",
+                        hostname
+                    ),
+                ));
+            }
+        }
+    }
+
+    issues
 }
 
-/// Bulkhead configuration
+/// Admin listener configuration (`ServerConfig.admin`)
+///
+/// A second listener that exposes only health, metrics, and debug
+/// endpoints, keeping them off the public port. Bind it to loopback
+/// (the default) or a unix socket.
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct AdminListenerConfig {
+    /// TCP port for the admin listener
+    pub port: Option<u32>,
+    /// Bind address (default "127.0.0.1"; keep it loopback)
+    pub hostname: Option<String>,
+    /// Unix socket path instead of TCP (takes precedence over port)
+    pub socket_path: Option<String>,
+}
+
+/// The address a server is actually bound to, from `address()`
+///
+/// Mirrors Node's `server.address()`: with `serve(0)` the OS assigns
+/// an ephemeral port, and this reports the real one.
 #[napi(object)]
 #[derive(Clone)]
-pub struct BulkheadConfig {
-    /// Maximum concurrent requests
-    pub max_concurrent: u32,
-    /// Maximum queue size
-    pub max_queue: u32,
-    /// Queue timeout in milliseconds
-    pub queue_timeout_ms: u32,
+pub struct ServerAddress {
+    /// Bound IP address (e.g. "127.0.0.1", "::")
+    pub host: String,
+    /// Bound TCP port
+    pub port: u32,
+    /// Address family: "IPv4" or "IPv6"
+    pub family: String,
 }
 
-/// Bulkhead for concurrency limiting
-#[napi]
-pub struct Bulkhead {
-    inner: Arc<RustBulkhead>,
+/// Per-content-type body limit, applied via `setBodyLimits`
+#[napi(object)]
+#[derive(Clone)]
+pub struct BodyLimit {
+    /// Media type (`application/json`) or prefix ending in `/`
+    /// (`multipart/` matches every multipart subtype)
+    pub content_type: String,
+    /// Maximum body size in bytes for matching requests
+    pub max_bytes: u32,
 }
 
-#[napi]
-impl Bulkhead {
-    #[napi(constructor)]
-    pub fn new(config: BulkheadConfig) -> Self {
-        let rust_config = RustBulkheadConfig::new(config.max_concurrent)
-            .max_queue(config.max_queue)
-            .queue_timeout(Duration::from_millis(config.queue_timeout_ms as u64));
+/// Per-phase timeout overrides in milliseconds, applied via
+/// `setTimeouts` (global) or `setRouteTimeouts` (one route pattern)
+///
+/// Unset phases keep their current value for the global config and
+/// inherit the global for a route override; 0 disables a phase.
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct TimeoutsConfig {
+    /// Connection accept / TLS handshake deadline
+    pub connect_ms: Option<u32>,
+    /// Request line + header read deadline
+    pub header_ms: Option<u32>,
+    /// Body read deadline
+    pub body_ms: Option<u32>,
+    /// Handler execution deadline
+    pub handler_ms: Option<u32>,
+    /// Response write deadline
+    pub write_ms: Option<u32>,
+    /// Whole-request wall-clock deadline
+    pub total_ms: Option<u32>,
+}
 
-        Self {
-            inner: Arc::new(RustBulkhead::new(rust_config)),
-        }
-    }
+/// Timeout counts per enforced phase, from `timeoutStats`
+#[napi(object)]
+pub struct TimeoutStats {
+    /// Requests that timed out reading the body (408)
+    pub body: u32,
+    /// Requests whose handler exceeded its deadline (504)
+    pub handler: u32,
+    /// Requests that exceeded the total deadline (504)
+    pub total: u32,
+}
 
-    /// Try to acquire a permit
-    #[napi]
-    pub fn try_acquire(&self) -> bool {
-        self.inner.try_acquire().is_ok()
-    }
+/// Tokio runtime metrics snapshot from `runtimeStats`
+///
+/// A growing global queue depth, or busy time pinned at wall time
+/// times the worker count, signals an event-loop-style stall on the
+/// Rust side (a handler blocking a worker thread).
+#[napi(object)]
+pub struct RuntimeStats {
+    /// Worker threads in the runtime
+    pub workers: u32,
+    /// Tasks currently alive (running or suspended)
+    pub alive_tasks: u32,
+    /// Tasks waiting on the global injection queue
+    pub global_queue_depth: u32,
+    /// Total time all workers spent executing tasks, in ms
+    pub busy_ms: i64,
+    /// Times workers went to sleep waiting for work
+    pub park_count: i64,
+}
 
-    /// Get current running count
-    #[napi]
-    pub fn running(&self) -> u32 {
-        self.inner.running()
+/// Snapshot the tokio runtime's metrics (stable subset: steal counts
+/// and blocking-pool gauges need the unstable metrics cfg)
+fn collect_runtime_stats() -> RuntimeStats {
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let workers = metrics.num_workers();
+    let mut busy_ms = 0u128;
+    let mut park_count = 0u64;
+    for worker in 0..workers {
+        busy_ms += metrics.worker_total_busy_duration(worker).as_millis();
+        park_count += metrics.worker_park_count(worker);
     }
-
-    /// Get queue length
-    #[napi]
-    pub fn queued(&self) -> u32 {
-        self.inner.queued()
+    RuntimeStats {
+        workers: workers as u32,
+        alive_tasks: metrics.num_alive_tasks() as u32,
+        global_queue_depth: metrics.global_queue_depth() as u32,
+        busy_ms: busy_ms as i64,
+        park_count: park_count as i64,
     }
+}
+
+/// Replacement config for `updateMiddleware`; exactly the field
+/// matching the middleware name must be set
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct MiddlewareUpdate {
+    /// New config when updating "cors"
+    pub cors: Option<CorsConfig>,
+    /// New config when updating "rateLimit"
+    pub rate_limit: Option<RateLimitConfig>,
+    /// New config when updating "security"
+    pub security: Option<SecurityConfig>,
+    /// New config when updating "tracing"
+    pub tracing: Option<TracingConfig>,
+}
 
+/// Overlay the Some fields of a JS timeout config onto a base config
+fn apply_timeout_overrides(
+    mut base: gust_core::TimeoutConfig,
+    config: &TimeoutsConfig,
+) -> gust_core::TimeoutConfig {
+    if let Some(ms) = config.connect_ms {
+        base.connect_ms = ms;
+    }
+    if let Some(ms) = config.header_ms {
+        base.header_ms = ms;
+    }
+    if let Some(ms) = config.body_ms {
+        base.body_ms = ms;
+    }
+    if let Some(ms) = config.handler_ms {
+        base.handler_ms = ms;
+    }
+    if let Some(ms) = config.write_ms {
+        base.write_ms = ms;
+    }
+    if let Some(ms) = config.total_ms {
+        base.total_ms = ms;
+    }
+    base
 }
 
 // ============================================================================
-// Validation
+// Config File Loading
 // ============================================================================
 
-/// Validation error
-#[napi(object)]
-#[derive(Clone)]
-pub struct ValidationError {
-    pub path: String,
-    pub message: String,
-    pub code: String,
+/// Parsed config document value (shared by the TOML and JSON parsers)
+type ConfigValue = gust_core::middleware::validate::Value;
+
+fn snake_to_camel(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper_next = false;
+    for c in key.chars() {
+        if c == '_' {
+            upper_next = true;
+        } else if upper_next {
+            out.push(c.to_ascii_uppercase());
+            upper_next = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
 }
 
-/// Validation result
-#[napi(object)]
-pub struct ValidationResult {
-    pub valid: bool,
-    pub errors: Vec<ValidationError>,
+fn camel_to_snake(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('_');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
 }
 
-/// Schema type enum
-#[napi(string_enum)]
-pub enum SchemaType {
-    String,
-    Number,
-    Boolean,
-    Object,
-    Array,
-    Any,
+/// Look up a key by its snake_case name (TOML convention) or the
+/// camelCase spelling (JSON convention)
+fn config_lookup<'a>(
+    obj: &'a HashMap<String, ConfigValue>,
+    key: &str,
+) -> Option<&'a ConfigValue> {
+    obj.get(key).or_else(|| obj.get(&snake_to_camel(key)))
 }
 
-/// String format enum
-#[napi(string_enum)]
-pub enum StringFormat {
-    Email,
-    Url,
-    Uuid,
-    Date,
-    DateTime,
+fn config_label(section: &str, key: &str) -> String {
+    if section.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", section, key)
+    }
 }
 
-/// Validate JSON value against schema
-#[napi]
-pub fn validate_json(
-    json_str: String,
-    schema_type: SchemaType,
-    required: bool,
-    min_length: Option<u32>,
-    max_length: Option<u32>,
-    format: Option<StringFormat>,
-    min: Option<f64>,
-    max: Option<f64>,
-    is_integer: Option<bool>,
-) -> ValidationResult {
-    // Parse JSON
-    let value = match parse_json_to_value(&json_str) {
-        Ok(v) => v,
-        Err(e) => {
-            return ValidationResult {
-                valid: false,
-                errors: vec![ValidationError {
-                    path: "$".to_string(),
-                    message: format!("Invalid JSON: {}", e),
-                    code: "invalid_type".to_string(),
-                }],
-            };
+/// Reject unknown keys so typos fail loudly instead of being ignored
+fn check_known_keys(
+    obj: &HashMap<String, ConfigValue>,
+    section: &str,
+    allowed: &[&str],
+) -> std::result::Result<(), String> {
+    for key in obj.keys() {
+        if !allowed.contains(&camel_to_snake(key).as_str()) {
+            return Err(format!(
+                "Unknown config key '{}'",
+                config_label(section, key)
+            ));
         }
+    }
+    Ok(())
+}
+
+fn config_u32(
+    obj: &HashMap<String, ConfigValue>,
+    section: &str,
+    key: &str,
+) -> std::result::Result<Option<u32>, String> {
+    let Some(value) = config_lookup(obj, key) else {
+        return Ok(None);
     };
+    let n = value
+        .as_f64()
+        .ok_or_else(|| format!("{} must be a number", config_label(section, key)))?;
+    if n.fract() != 0.0 || n < 0.0 || n > u32::MAX as f64 {
+        return Err(format!(
+            "{} must be a non-negative integer",
+            config_label(section, key)
+        ));
+    }
+    Ok(Some(n as u32))
+}
 
-    // Build schema
-    let rust_schema_type = match schema_type {
-        SchemaType::String => RustSchemaType::String,
-        SchemaType::Number => RustSchemaType::Number,
-        SchemaType::Boolean => RustSchemaType::Boolean,
-        SchemaType::Object => RustSchemaType::Object,
-        SchemaType::Array => RustSchemaType::Array,
-        SchemaType::Any => RustSchemaType::Any,
+fn config_bool(
+    obj: &HashMap<String, ConfigValue>,
+    section: &str,
+    key: &str,
+) -> std::result::Result<Option<bool>, String> {
+    let Some(value) = config_lookup(obj, key) else {
+        return Ok(None);
     };
+    value
+        .as_bool()
+        .map(Some)
+        .ok_or_else(|| format!("{} must be a boolean", config_label(section, key)))
+}
 
-    let rust_format = format.map(|f| match f {
-        StringFormat::Email => RustStringFormat::Email,
-        StringFormat::Url => RustStringFormat::Url,
-        StringFormat::Uuid => RustStringFormat::Uuid,
-        StringFormat::Date => RustStringFormat::Date,
-        StringFormat::DateTime => RustStringFormat::DateTime,
-    });
+fn config_string(
+    obj: &HashMap<String, ConfigValue>,
+    section: &str,
+    key: &str,
+) -> std::result::Result<Option<String>, String> {
+    let Some(value) = config_lookup(obj, key) else {
+        return Ok(None);
+    };
+    value
+        .as_str()
+        .map(|s| Some(s.to_string()))
+        .ok_or_else(|| format!("{} must be a string", config_label(section, key)))
+}
 
-    let schema = RustSchema {
-        schema_type: rust_schema_type,
-        required,
-        nullable: false,
-        min_length: min_length.map(|x| x as usize),
-        max_length: max_length.map(|x| x as usize),
-        pattern: None,
-        format: rust_format,
-        min,
-        max,
-        integer: is_integer.unwrap_or(false),
-        enum_values: None,
-        properties: None,
-        additional_properties: true,
-        items: None,
-        min_items: None,
-        max_items: None,
-        unique_items: false,
+fn config_string_array(
+    obj: &HashMap<String, ConfigValue>,
+    section: &str,
+    key: &str,
+) -> std::result::Result<Option<Vec<String>>, String> {
+    let Some(value) = config_lookup(obj, key) else {
+        return Ok(None);
     };
+    let items = value
+        .as_array()
+        .ok_or_else(|| format!("{} must be an array", config_label(section, key)))?;
+    let mut out = Vec::with_capacity(items.len());
+    for item in items {
+        out.push(
+            item.as_str()
+                .ok_or_else(|| {
+                    format!(
+                        "{} must be an array of strings",
+                        config_label(section, key)
+                    )
+                })?
+                .to_string(),
+        );
+    }
+    Ok(Some(out))
+}
 
-    let errors = rust_validate(&value, &schema, "$");
+/// Fetch a section as a table, or None when absent
+fn config_section<'a>(
+    obj: &'a HashMap<String, ConfigValue>,
+    key: &str,
+) -> std::result::Result<Option<&'a HashMap<String, ConfigValue>>, String> {
+    let Some(value) = config_lookup(obj, key) else {
+        return Ok(None);
+    };
+    value
+        .as_object()
+        .map(Some)
+        .ok_or_else(|| format!("{} must be a table", key))
+}
 
-    ValidationResult {
-        valid: errors.is_empty(),
-        errors: errors
-            .into_iter()
-            .map(|e| ValidationError {
-                path: e.path,
-                message: e.message,
-                code: "validation_error".to_string(),
-            })
-            .collect(),
+fn cors_from_config(
+    obj: &HashMap<String, ConfigValue>,
+) -> std::result::Result<CorsConfig, String> {
+    check_known_keys(
+        obj,
+        "cors",
+        &[
+            "origins",
+            "methods",
+            "allowed_headers",
+            "exposed_headers",
+            "credentials",
+            "max_age",
+        ],
+    )?;
+    Ok(CorsConfig {
+        origins: config_string_array(obj, "cors", "origins")?,
+        methods: config_string_array(obj, "cors", "methods")?,
+        allowed_headers: config_string_array(obj, "cors", "allowed_headers")?,
+        exposed_headers: config_string_array(obj, "cors", "exposed_headers")?,
+        credentials: config_bool(obj, "cors", "credentials")?,
+        max_age: config_u32(obj, "cors", "max_age")?,
+    })
+}
+
+fn rate_limit_from_config(
+    obj: &HashMap<String, ConfigValue>,
+) -> std::result::Result<RateLimitConfig, String> {
+    check_known_keys(
+        obj,
+        "rate_limit",
+        &["max_requests", "window_seconds", "key_by"],
+    )?;
+    Ok(RateLimitConfig {
+        max_requests: config_u32(obj, "rate_limit", "max_requests")?
+            .ok_or_else(|| "rate_limit.max_requests is required".to_string())?,
+        window_seconds: config_u32(obj, "rate_limit", "window_seconds")?
+            .ok_or_else(|| "rate_limit.window_seconds is required".to_string())?,
+        key_by: config_string(obj, "rate_limit", "key_by")?,
+    })
+}
+
+fn security_from_config(
+    obj: &HashMap<String, ConfigValue>,
+) -> std::result::Result<SecurityConfig, String> {
+    check_known_keys(
+        obj,
+        "security",
+        &[
+            "hsts",
+            "hsts_max_age",
+            "frame_options",
+            "content_type_options",
+            "xss_protection",
+            "referrer_policy",
+        ],
+    )?;
+    Ok(SecurityConfig {
+        hsts: config_bool(obj, "security", "hsts")?,
+        hsts_max_age: config_u32(obj, "security", "hsts_max_age")?,
+        frame_options: config_string(obj, "security", "frame_options")?,
+        content_type_options: config_bool(obj, "security", "content_type_options")?,
+        xss_protection: config_bool(obj, "security", "xss_protection")?,
+        referrer_policy: config_string(obj, "security", "referrer_policy")?,
+    })
+}
+
+fn tracing_from_config(
+    obj: &HashMap<String, ConfigValue>,
+) -> std::result::Result<TracingConfig, String> {
+    check_known_keys(
+        obj,
+        "tracing",
+        &[
+            "header_name",
+            "generate_id",
+            "id_generator",
+            "machine_id",
+            "propagate_trace",
+            "log_requests",
+            "log_responses",
+        ],
+    )?;
+    Ok(TracingConfig {
+        header_name: config_string(obj, "tracing", "header_name")?,
+        generate_id: config_bool(obj, "tracing", "generate_id")?,
+        id_generator: config_string(obj, "tracing", "id_generator")?,
+        machine_id: config_u32(obj, "tracing", "machine_id")?,
+        propagate_trace: config_bool(obj, "tracing", "propagate_trace")?,
+        log_requests: config_bool(obj, "tracing", "log_requests")?,
+        log_responses: config_bool(obj, "tracing", "log_responses")?,
+    })
+}
+
+fn compression_from_config(
+    obj: &HashMap<String, ConfigValue>,
+) -> std::result::Result<CompressionConfig, String> {
+    check_known_keys(obj, "compression", &["gzip", "brotli", "threshold", "level"])?;
+    Ok(CompressionConfig {
+        gzip: config_bool(obj, "compression", "gzip")?,
+        brotli: config_bool(obj, "compression", "brotli")?,
+        threshold: config_u32(obj, "compression", "threshold")?,
+        level: config_u32(obj, "compression", "level")?,
+    })
+}
+
+fn tls_from_config(
+    obj: &HashMap<String, ConfigValue>,
+) -> std::result::Result<TlsConfig, String> {
+    check_known_keys(obj, "tls", &["cert_path", "key_path", "cert", "key"])?;
+    Ok(TlsConfig {
+        cert_path: config_string(obj, "tls", "cert_path")?,
+        key_path: config_string(obj, "tls", "key_path")?,
+        cert: config_string(obj, "tls", "cert")?,
+        key: config_string(obj, "tls", "key")?,
+    })
+}
+
+fn admin_from_config(
+    obj: &HashMap<String, ConfigValue>,
+) -> std::result::Result<AdminListenerConfig, String> {
+    check_known_keys(obj, "admin", &["port", "hostname", "socket_path"])?;
+    Ok(AdminListenerConfig {
+        port: config_u32(obj, "admin", "port")?,
+        hostname: config_string(obj, "admin", "hostname")?,
+        socket_path: config_string(obj, "admin", "socket_path")?,
+    })
+}
+
+/// Map a parsed config document to a ServerConfig
+fn server_config_from_value(
+    value: &ConfigValue,
+) -> std::result::Result<ServerConfig, String> {
+    let root = value
+        .as_object()
+        .ok_or_else(|| "config root must be a table".to_string())?;
+    check_known_keys(
+        root,
+        "",
+        &[
+            "port",
+            "hostname",
+            "workers",
+            "cors",
+            "rate_limit",
+            "security",
+            "tracing",
+            "compression",
+            "tls",
+            "http2",
+            "request_timeout_ms",
+            "max_body_size",
+            "keep_alive_timeout_ms",
+            "max_header_size",
+            "max_pipeline_depth",
+            "batched_headers",
+            "engine",
+            "trust_proxy",
+            "trust_proxy_addresses",
+            "admin",
+        ],
+    )?;
+
+    let port = config_u32(root, "", "port")?;
+    if let Some(port) = port {
+        if !(1..=65535).contains(&port) {
+            return Err(format!("port must be between 1 and 65535, got {}", port));
+        }
     }
+
+    let trust_proxy = match config_string(root, "", "trust_proxy")? {
+        Some(mode) => Some(match mode.as_str() {
+            "none" => TrustProxy::None,
+            "all" => TrustProxy::All,
+            "loopback" => TrustProxy::Loopback,
+            other => {
+                return Err(format!(
+                    "trust_proxy must be 'none', 'all', or 'loopback', got '{}'",
+                    other
+                ))
+            }
+        }),
+        None => None,
+    };
+
+    Ok(ServerConfig {
+        port,
+        hostname: config_string(root, "", "hostname")?,
+        workers: config_u32(root, "", "workers")?,
+        cors: config_section(root, "cors")?
+            .map(cors_from_config)
+            .transpose()?,
+        rate_limit: config_section(root, "rate_limit")?
+            .map(rate_limit_from_config)
+            .transpose()?,
+        security: config_section(root, "security")?
+            .map(security_from_config)
+            .transpose()?,
+        tracing: config_section(root, "tracing")?
+            .map(tracing_from_config)
+            .transpose()?,
+        compression: config_section(root, "compression")?
+            .map(compression_from_config)
+            .transpose()?,
+        tls: config_section(root, "tls")?
+            .map(tls_from_config)
+            .transpose()?,
+        http2: config_bool(root, "", "http2")?,
+        request_timeout_ms: config_u32(root, "", "request_timeout_ms")?,
+        max_body_size: config_u32(root, "", "max_body_size")?,
+        keep_alive_timeout_ms: config_u32(root, "", "keep_alive_timeout_ms")?,
+        max_header_size: config_u32(root, "", "max_header_size")?,
+        max_pipeline_depth: config_u32(root, "", "max_pipeline_depth")?,
+        batched_headers: config_bool(root, "", "batched_headers")?,
+        engine: config_string(root, "", "engine")?,
+        trust_proxy,
+        trust_proxy_addresses: config_string_array(root, "", "trust_proxy_addresses")?,
+        admin: config_section(root, "admin")?
+            .map(admin_from_config)
+            .transpose()?,
+    })
 }
 
-fn parse_json_to_value(json: &str) -> std::result::Result<RustValue, String> {
-    // Simple JSON parser - handles basic types
-    let json = json.trim();
+fn env_u32(
+    raw: &str,
+    name: &str,
+) -> std::result::Result<u32, String> {
+    raw.parse::<u32>()
+        .map_err(|_| format!("{} must be a number, got '{}'", name, raw))
+}
 
-    if json == "null" {
-        return Ok(RustValue::Null);
+fn env_bool(raw: &str, name: &str) -> std::result::Result<bool, String> {
+    match raw {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(format!(
+            "{} must be 'true' or 'false', got '{}'",
+            name, raw
+        )),
     }
-    if json == "true" {
-        return Ok(RustValue::Bool(true));
+}
+
+/// Layer GUST_* environment variable overrides onto a config
+///
+/// The lookup is injected so tests don't have to mutate process env.
+fn apply_env_overrides(
+    config: &mut ServerConfig,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> std::result::Result<(), String> {
+    if let Some(raw) = env("GUST_PORT") {
+        let port = env_u32(&raw, "GUST_PORT")?;
+        if !(1..=65535).contains(&port) {
+            return Err(format!(
+                "GUST_PORT must be between 1 and 65535, got {}",
+                port
+            ));
+        }
+        config.port = Some(port);
     }
-    if json == "false" {
-        return Ok(RustValue::Bool(false));
+    if let Some(raw) = env("GUST_HOSTNAME") {
+        config.hostname = Some(raw);
     }
-    if json.starts_with('"') && json.ends_with('"') {
-        return Ok(RustValue::String(json[1..json.len()-1].to_string()));
+    if let Some(raw) = env("GUST_WORKERS") {
+        config.workers = Some(env_u32(&raw, "GUST_WORKERS")?);
     }
-    if let Ok(n) = json.parse::<f64>() {
-        return Ok(RustValue::Number(n));
+    if let Some(raw) = env("GUST_HTTP2") {
+        config.http2 = Some(env_bool(&raw, "GUST_HTTP2")?);
     }
-    if json.starts_with('[') {
-        return Ok(RustValue::Array(vec![])); // Simplified
+    if let Some(raw) = env("GUST_ENGINE") {
+        config.engine = Some(raw);
     }
-    if json.starts_with('{') {
-        return Ok(RustValue::Object(HashMap::new())); // Simplified
+    if let Some(raw) = env("GUST_REQUEST_TIMEOUT_MS") {
+        config.request_timeout_ms = Some(env_u32(&raw, "GUST_REQUEST_TIMEOUT_MS")?);
     }
-
-    Err("Failed to parse JSON".to_string())
+    if let Some(raw) = env("GUST_MAX_BODY_SIZE") {
+        config.max_body_size = Some(env_u32(&raw, "GUST_MAX_BODY_SIZE")?);
+    }
+    if let Some(raw) = env("GUST_KEEP_ALIVE_TIMEOUT_MS") {
+        config.keep_alive_timeout_ms = Some(env_u32(&raw, "GUST_KEEP_ALIVE_TIMEOUT_MS")?);
+    }
+    if let Some(raw) = env("GUST_MAX_HEADER_SIZE") {
+        config.max_header_size = Some(env_u32(&raw, "GUST_MAX_HEADER_SIZE")?);
+    }
+    if let Some(raw) = env("GUST_TLS_CERT_PATH") {
+        config.tls.get_or_insert_with(TlsConfig::default).cert_path = Some(raw);
+    }
+    if let Some(raw) = env("GUST_TLS_KEY_PATH") {
+        config.tls.get_or_insert_with(TlsConfig::default).key_path = Some(raw);
+    }
+    Ok(())
 }
 
-// ============================================================================
-// Range Requests
-// ============================================================================
+/// Load a ServerConfig from a TOML or JSON file plus env overrides
+fn load_config_file(path: &str) -> std::result::Result<ServerConfig, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read config file '{}': {}", path, e))?;
 
-/// Parsed range
-#[napi(object)]
-#[derive(Clone)]
-pub struct ParsedRange {
-    pub start: i64,
-    pub end: i64,
+    let value = if path.ends_with(".toml") {
+        gust_core::pure::parse_toml(&contents).map_err(|e| format!("{}: {}", path, e))?
+    } else if path.ends_with(".json") {
+        gust_core::pure::parse_json(&contents).map_err(|e| format!("{}: {}", path, e))?
+    } else {
+        return Err(format!(
+            "Unsupported config file '{}' (expected a .toml or .json extension)",
+            path
+        ));
+    };
+
+    let mut config =
+        server_config_from_value(&value).map_err(|e| format!("{}: {}", path, e))?;
+    apply_env_overrides(&mut config, &|name| std::env::var(name).ok())?;
+    Ok(config)
 }
 
-/// Range response metadata
+/// Keep-alive connection reuse statistics
 #[napi(object)]
 #[derive(Clone)]
-pub struct RangeResponseMeta {
-    pub status: u32,
-    pub content_type: String,
-    pub content_length: i64,
-    pub content_range: Option<String>,
-    pub accept_ranges: String,
-    pub etag: String,
-}
-
-/// Parse HTTP Range header
-#[napi]
-pub fn parse_range_header(header: String, file_size: i64) -> Option<ParsedRange> {
-    rust_parse_range(&header, file_size as u64).and_then(|parsed| {
-        parsed.ranges.first().map(|r| ParsedRange {
-            start: r.start as i64,
-            end: r.end as i64,
-        })
-    })
+pub struct KeepAliveStats {
+    /// Connections accepted since start
+    pub total_connections: i64,
+    /// Requests served on closed connections
+    pub total_requests: i64,
+    /// Closed connections that served more than one request
+    pub reused_connections: i64,
+    /// Most requests served by any single connection
+    pub max_requests_per_connection: i64,
+    /// Requests rejected for exceeding the pipeline depth
+    pub pipelined_rejected: i64,
+    /// Average requests served per connection
+    pub reuse_ratio: f64,
 }
 
-/// Generate Content-Range header value
-#[napi]
-pub fn content_range_header(start: i64, end: i64, total: i64) -> String {
-    rust_content_range(start as u64, end as u64, total as u64)
+/// Upstream connection pool statistics
+#[napi(object)]
+#[derive(Clone)]
+pub struct UpstreamStats {
+    /// Requests that went through the upstream pool
+    pub checkouts: i64,
+    /// Checkouts with no usable idle connection (had to dial)
+    pub waits: i64,
+    /// Checkouts served over a reused connection
+    pub reused: i64,
+    /// Connections returned to the idle list after a request
+    pub returned: i64,
+    /// Connections dropped instead of returned
+    pub discarded: i64,
+    /// Idle connections evicted by idle timeout or max lifetime
+    pub expired: i64,
+    /// Idle connections currently pooled
+    pub idle_connections: i64,
+    /// Fraction of checkouts served over a reused connection
+    pub reuse_ratio: f64,
 }
 
-/// Get MIME type from file extension
-#[napi]
-pub fn get_mime_type(extension: String) -> String {
-    rust_get_mime_type(&extension).to_string()
+/// Time to first byte for one upstream key, from `upstreamTtfb`
+#[napi(object)]
+#[derive(Clone)]
+pub struct UpstreamTtfbStats {
+    /// Upstream key (scheme://host:port)
+    pub key: String,
+    /// Exchanges measured
+    pub count: i64,
+    /// Mean time from checkout to response headers, in ms
+    pub mean_ms: f64,
+    /// Slowest observed, in ms
+    pub max_ms: i64,
 }
 
-/// Generate ETag from file metadata
-#[napi]
-pub fn generate_etag(mtime_ms: i64, size: i64) -> String {
-    rust_generate_etag(mtime_ms as u64, size as u64)
+/// Handler dispatch counters, for comparing the direct (sync) path
+/// against the Promise path
+#[napi(object)]
+pub struct DispatchStats {
+    /// Dispatches through the direct path (bun-direct sync routes)
+    pub sync_dispatches: i64,
+    /// Dispatches through the Promise path
+    pub async_dispatches: i64,
 }
 
 // ============================================================================
-// Proxy Headers
+// Circuit Breaker
 // ============================================================================
 
-/// Proxy information
+/// Circuit breaker configuration
 #[napi(object)]
 #[derive(Clone)]
-pub struct ProxyInfo {
-    /// Client IP address
-    pub ip: String,
-    /// Original host
-    pub host: String,
-    /// Original protocol (http/https)
-    pub protocol: String,
-    /// Original port
-    pub port: u32,
-    /// Chain of forwarded IPs
-    pub ips: Vec<String>,
+pub struct CircuitBreakerConfig {
+    /// Number of failures before opening circuit
+    pub failure_threshold: u32,
+    /// Number of successes before closing circuit (from half-open)
+    pub success_threshold: u32,
+    /// Time in milliseconds before attempting to recover
+    pub reset_timeout_ms: u32,
+    /// Time window in milliseconds for counting failures
+    pub failure_window_ms: u32,
+    /// Request timeout in milliseconds
+    pub timeout_ms: u32,
+    /// Circuit breaker name
+    pub name: Option<String>,
 }
 
-/// Proxy trust mode
+/// Circuit breaker state
 #[napi(string_enum)]
-pub enum TrustProxy {
-    None,
-    All,
-    Loopback,
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
 }
 
-/// Extract proxy information from headers
-#[napi]
-pub fn extract_proxy_info(
-    trust: TrustProxy,
-    socket_ip: String,
-    forwarded_for: Option<String>,
-    forwarded_host: Option<String>,
-    forwarded_proto: Option<String>,
-    forwarded_port: Option<String>,
-    host_header: Option<String>,
-) -> ProxyInfo {
-    let rust_trust = match trust {
-        TrustProxy::None => RustTrustProxy::None,
-        TrustProxy::All => RustTrustProxy::All,
-        TrustProxy::Loopback => RustTrustProxy::Addresses(vec![
-            gust_core::middleware::proxy::TrustedAddress::parse("127.0.0.1").unwrap(),
-            gust_core::middleware::proxy::TrustedAddress::parse("::1").unwrap(),
-            gust_core::middleware::proxy::TrustedAddress::parse("10.0.0.0/8").unwrap(),
-            gust_core::middleware::proxy::TrustedAddress::parse("172.16.0.0/12").unwrap(),
-            gust_core::middleware::proxy::TrustedAddress::parse("192.168.0.0/16").unwrap(),
-        ]),
-    };
+/// Circuit breaker stats
+#[napi(object)]
+pub struct CircuitStats {
+    pub state: String,
+    pub failures: u32,
+    pub successes: u32,
+    pub total_requests: i64,
+    pub total_failures: i64,
+    pub total_successes: i64,
+}
 
-    let config = RustProxyConfig {
-        trust: rust_trust,
-        ip_header: "x-forwarded-for".to_string(),
-        host_header: "x-forwarded-host".to_string(),
-        proto_header: "x-forwarded-proto".to_string(),
-        port_header: "x-forwarded-port".to_string(),
-    };
+/// Circuit breaker wrapper for napi
+#[napi]
+pub struct CircuitBreaker {
+    inner: Arc<RustCircuitBreaker>,
+}
 
-    let mut headers = Vec::new();
-    if let Some(v) = forwarded_for {
-        headers.push(("x-forwarded-for".to_string(), v));
+#[napi]
+impl CircuitBreaker {
+    #[napi(constructor)]
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        let name = config.name.unwrap_or_else(|| "default".to_string());
+        let rust_config = RustCBConfig::new(name)
+            .failure_threshold(config.failure_threshold)
+            .success_threshold(config.success_threshold)
+            .reset_timeout(Duration::from_millis(config.reset_timeout_ms as u64))
+            .failure_window(Duration::from_millis(config.failure_window_ms as u64))
+            .timeout(Duration::from_millis(config.timeout_ms as u64));
+
+        Self {
+            inner: Arc::new(RustCircuitBreaker::new(rust_config)),
+        }
     }
-    if let Some(v) = forwarded_host {
-        headers.push(("x-forwarded-host".to_string(), v));
+
+    /// Check if request can proceed
+    #[napi]
+    pub fn can_request(&self) -> bool {
+        self.inner.can_request()
     }
-    if let Some(v) = forwarded_proto {
-        headers.push(("x-forwarded-proto".to_string(), v));
+
+    /// Record successful request
+    #[napi]
+    pub fn record_success(&self) {
+        self.inner.record_success();
     }
-    if let Some(v) = forwarded_port {
-        headers.push(("x-forwarded-port".to_string(), v));
+
+    /// Record failed request
+    #[napi]
+    pub fn record_failure(&self) {
+        self.inner.record_failure();
     }
 
-    let info = rust_extract_proxy_info(&config, &socket_ip, &headers, host_header.as_deref());
+    /// Get current state
+    #[napi]
+    pub fn state(&self) -> String {
+        match self.inner.state() {
+            RustCircuitState::Closed => "closed".to_string(),
+            RustCircuitState::Open => "open".to_string(),
+            RustCircuitState::HalfOpen => "half-open".to_string(),
+        }
+    }
 
-    ProxyInfo {
-        ip: info.ip,
-        host: info.host,
-        protocol: info.protocol.as_str().to_string(),
-        port: info.port as u32,
-        ips: info.ips,
+    /// Get statistics
+    #[napi]
+    pub fn stats(&self) -> CircuitStats {
+        let stats = self.inner.stats();
+        CircuitStats {
+            state: match stats.state {
+                RustCircuitState::Closed => "closed".to_string(),
+                RustCircuitState::Open => "open".to_string(),
+                RustCircuitState::HalfOpen => "half-open".to_string(),
+            },
+            failures: stats.failures,
+            successes: stats.successes,
+            total_requests: stats.total_requests as i64,
+            total_failures: stats.total_failures as i64,
+            total_successes: stats.total_successes as i64,
+        }
     }
-}
 
-// ============================================================================
-// OpenTelemetry
-// ============================================================================
+    /// Reset circuit breaker
+    #[napi]
+    pub fn reset(&self) {
+        self.inner.reset();
+    }
+}
 
-/// Span context
+/// Bulkhead configuration
 #[napi(object)]
 #[derive(Clone)]
-pub struct SpanContext {
-    pub trace_id: String,
-    pub span_id: String,
-    pub trace_flags: u32,
+pub struct BulkheadConfig {
+    /// Maximum concurrent requests
+    pub max_concurrent: u32,
+    /// Maximum queue size
+    pub max_queue: u32,
+    /// Queue timeout in milliseconds
+    pub queue_timeout_ms: u32,
 }
 
-/// Span status
-#[napi(string_enum)]
-pub enum SpanStatus {
-    Unset,
-    Ok,
-    Error,
+/// Bulkhead for concurrency limiting
+#[napi]
+pub struct Bulkhead {
+    inner: Arc<RustBulkhead>,
 }
 
-/// Generate trace ID (32 hex chars)
 #[napi]
-pub fn generate_trace_id() -> String {
-    rust_generate_trace_id()
+impl Bulkhead {
+    #[napi(constructor)]
+    pub fn new(config: BulkheadConfig) -> Self {
+        let rust_config = RustBulkheadConfig::new(config.max_concurrent)
+            .max_queue(config.max_queue)
+            .queue_timeout(Duration::from_millis(config.queue_timeout_ms as u64));
+
+        Self {
+            inner: Arc::new(RustBulkhead::new(rust_config)),
+        }
+    }
+
+    /// Try to acquire a permit
+    #[napi]
+    pub fn try_acquire(&self) -> bool {
+        self.inner.try_acquire().is_ok()
+    }
+
+    /// Get current running count
+    #[napi]
+    pub fn running(&self) -> u32 {
+        self.inner.running()
+    }
+
+    /// Get queue length
+    #[napi]
+    pub fn queued(&self) -> u32 {
+        self.inner.queued()
+    }
+
 }
 
-/// Generate span ID (16 hex chars)
-#[napi]
-pub fn generate_span_id() -> String {
-    rust_generate_span_id()
+/// Refresh token configuration
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct RefreshTokenConfig {
+    /// Lifetime of each issued token in seconds (default: 30 days)
+    pub ttl_seconds: Option<u32>,
+    /// Lifetime cap for a whole token family in seconds (default: 90
+    /// days); rotation never extends a family past it
+    pub family_ttl_seconds: Option<u32>,
 }
 
-/// Parse W3C traceparent header
-#[napi]
-pub fn parse_traceparent(header: String) -> Option<SpanContext> {
-    rust_parse_traceparent(&header).map(|ctx| SpanContext {
-        trace_id: ctx.trace_id,
-        span_id: ctx.span_id,
-        trace_flags: ctx.trace_flags as u32,
-    })
+/// A freshly issued refresh token
+#[napi(object)]
+pub struct IssuedRefreshToken {
+    /// The opaque token to hand to the client (storage keeps a hash)
+    pub token: String,
+    /// Family this token belongs to (stable across rotations)
+    pub family: String,
+    /// Subject the token was issued to
+    pub subject: String,
+    /// Milliseconds until the token expires
+    pub expires_in_ms: i64,
 }
 
-/// Format W3C traceparent header
-#[napi]
-pub fn format_traceparent(trace_id: String, span_id: String, trace_flags: u32) -> String {
-    let ctx = RustSpanContext {
-        trace_id,
-        span_id,
-        trace_flags: trace_flags as u8,
-        trace_state: None,
-    };
-    rust_format_traceparent(&ctx)
+/// Refresh token counters
+#[napi(object)]
+pub struct RefreshTokenStats {
+    pub issued: i64,
+    pub rotated: i64,
+    pub reuse_detected: i64,
+    pub revoked: i64,
 }
 
-/// Tracer for creating spans
+/// Refresh token issuer with rotation and replay detection
+///
+/// Tokens are opaque and stored hashed. `rotate` exchanges a token
+/// for its successor; replaying an already-rotated token revokes the
+/// whole family (reuse detection).
 #[napi]
-pub struct Tracer {
-    inner: Arc<RustTracer>,
+pub struct RefreshTokens {
+    inner: Arc<gust_core::middleware::RefreshTokens>,
 }
 
 #[napi]
-impl Tracer {
+impl RefreshTokens {
     #[napi(constructor)]
-    pub fn new(service_name: String, sample_rate: Option<f64>) -> Self {
-        let config = RustTracerConfig::new(service_name);
-        let config = if let Some(rate) = sample_rate {
-            config.sample_rate(rate)
-        } else {
-            config
-        };
+    pub fn new(config: Option<RefreshTokenConfig>) -> Self {
+        use gust_core::middleware::RefreshTokenConfig as CoreConfig;
+
+        let config = config.unwrap_or_default();
+        let mut core_config = CoreConfig::new();
+        if let Some(ttl) = config.ttl_seconds {
+            core_config = core_config.ttl(Duration::from_secs(ttl as u64));
+        }
+        if let Some(ttl) = config.family_ttl_seconds {
+            core_config = core_config.family_ttl(Duration::from_secs(ttl as u64));
+        }
 
         Self {
-            inner: Arc::new(RustTracer::new(config)),
+            inner: Arc::new(gust_core::middleware::RefreshTokens::new(core_config)),
         }
     }
 
-    /// Start a new span
+    /// Issue a new token in a new family (e.g. at login)
     #[napi]
-    pub fn start_span(&self, name: String) -> Span {
-        Span {
-            inner: Some(self.inner.start_span(name)),
-        }
+    pub fn issue(&self, subject: String) -> IssuedRefreshToken {
+        issued_to_napi(self.inner.issue(subject))
     }
 
-    /// Start a child span
+    /// Exchange a token for its successor in the same family
+    ///
+    /// Rejects with "Unknown refresh token", "Refresh token expired"
+    /// or "Refresh token reuse detected"; on reuse the whole family
+    /// has been revoked.
     #[napi]
-    pub fn start_child_span(&self, name: String, parent_trace_id: String, parent_span_id: String) -> Span {
-        let parent_ctx = RustSpanContext {
-            trace_id: parent_trace_id,
-            span_id: parent_span_id,
-            trace_flags: 1,
-            trace_state: None,
-        };
-        Span {
-            inner: Some(self.inner.start_child_span(name, &parent_ctx)),
-        }
+    pub fn rotate(&self, token: String) -> Result<IssuedRefreshToken> {
+        self.inner
+            .rotate(&token)
+            .map(issued_to_napi)
+            .map_err(|e| Error::from_reason(e.to_string()))
     }
 
-    /// Get pending span count
+    /// Check a token without rotating it, returning its subject
     #[napi]
-    pub fn pending_count(&self) -> u32 {
-        self.inner.pending_count() as u32
+    pub fn validate(&self, token: String) -> Result<String> {
+        self.inner
+            .validate(&token)
+            .map_err(|e| Error::from_reason(e.to_string()))
     }
-}
 
-/// A span representing a unit of work
-#[napi]
-pub struct Span {
-    inner: Option<RustSpan>,
-}
+    /// Revoke a single token
+    #[napi]
+    pub fn revoke(&self, token: String) -> bool {
+        self.inner.revoke(&token)
+    }
 
-#[napi]
-impl Span {
-    /// Get span context
+    /// Revoke every token in a family, returning the count
     #[napi]
-    pub fn context(&self) -> Option<SpanContext> {
-        self.inner.as_ref().map(|s| SpanContext {
-            trace_id: s.context.trace_id.clone(),
-            span_id: s.context.span_id.clone(),
-            trace_flags: s.context.trace_flags as u32,
-        })
+    pub fn revoke_family(&self, family: String) -> u32 {
+        self.inner.revoke_family(&family) as u32
     }
 
-    /// Set attribute
+    /// Revoke every token issued to a subject, returning the count
     #[napi]
-    pub fn set_attribute(&mut self, key: String, value: String) {
-        if let Some(ref mut span) = self.inner {
-            span.set_attribute(key, value);
-        }
-    }
-
-    /// Set numeric attribute
-    #[napi]
-    pub fn set_attribute_number(&mut self, key: String, value: f64) {
-        if let Some(ref mut span) = self.inner {
-            span.set_attribute(key, gust_core::middleware::otel::AttributeValue::Float(value));
-        }
+    pub fn revoke_subject(&self, subject: String) -> u32 {
+        self.inner.revoke_subject(&subject) as u32
     }
 
-    /// End span
+    /// Drop expired tokens
     #[napi]
-    pub fn end(&mut self) {
-        if let Some(ref mut span) = self.inner {
-            span.end();
-        }
+    pub fn cleanup(&self) {
+        self.inner.cleanup();
     }
 
-    /// End span with status
+    /// Get counters
     #[napi]
-    pub fn end_with_status(&mut self, status: SpanStatus) {
-        if let Some(ref mut span) = self.inner {
-            let rust_status = match status {
-                SpanStatus::Unset => RustSpanStatus::Unset,
-                SpanStatus::Ok => RustSpanStatus::Ok,
-                SpanStatus::Error => RustSpanStatus::Error,
-            };
-            span.end_with_status(rust_status);
+    pub fn stats(&self) -> RefreshTokenStats {
+        let stats = self.inner.stats();
+        RefreshTokenStats {
+            issued: stats.issued as i64,
+            rotated: stats.rotated as i64,
+            reuse_detected: stats.reuse_detected as i64,
+            revoked: stats.revoked as i64,
         }
     }
+}
 
-    /// Get duration in milliseconds
-    #[napi]
-    pub fn duration_ms(&self) -> Option<f64> {
-        self.inner.as_ref().and_then(|s| s.duration_ms())
+fn issued_to_napi(issued: gust_core::middleware::IssuedToken) -> IssuedRefreshToken {
+    IssuedRefreshToken {
+        token: issued.token,
+        family: issued.family,
+        subject: issued.subject,
+        expires_in_ms: issued.expires_in.as_millis() as i64,
     }
 }
 
-/// Metrics collector
-#[napi]
-pub struct MetricsCollector {
-    inner: Arc<RustMetricsCollector>,
+// ============================================================================
+// Password hashing
+// ============================================================================
+
+/// Cost parameters for passwordHash
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct PasswordHashOptions {
+    /// "argon2id" (default) or "scrypt"
+    pub algorithm: Option<String>,
+    /// argon2id: memory cost in KiB (default: 19456)
+    pub memory_kib: Option<u32>,
+    /// argon2id: passes over memory (default: 2)
+    pub iterations: Option<u32>,
+    /// argon2id: lanes (default: 1)
+    pub parallelism: Option<u32>,
+    /// scrypt: cost exponent, N = 2^logN (default: 15)
+    pub log_n: Option<u32>,
+    /// scrypt: block size factor (default: 8)
+    pub block_size: Option<u32>,
 }
 
+/// Hash a password into a self-describing PHC string
+///
+/// argon2id by default (OWASP cost defaults), scrypt as a fallback
+/// for apps standardized on it. The work runs on the runtime's
+/// blocking pool, so tuning the cost up never stalls the JS thread.
+///
+/// @example
+/// ```typescript
+/// const stored = await passwordHash('hunter2')
+/// // $argon2id$v=19$m=19456,t=2,p=1$...$...
+/// ```
 #[napi]
-impl MetricsCollector {
-    #[napi(constructor)]
-    pub fn new() -> Self {
-        Self {
-            inner: Arc::new(RustMetricsCollector::new()),
+pub async fn password_hash(
+    password: String,
+    options: Option<PasswordHashOptions>,
+) -> Result<String> {
+    use gust_core::crypto::{hash_password, hash_password_scrypt, Argon2Params, ScryptParams};
+
+    let options = options.unwrap_or_default();
+    let algorithm = options
+        .algorithm
+        .clone()
+        .unwrap_or_else(|| "argon2id".to_string());
+
+    let task = tokio::task::spawn_blocking(move || match algorithm.as_str() {
+        "argon2id" => {
+            let mut params = Argon2Params::new();
+            if let Some(memory) = options.memory_kib {
+                params = params.memory_kib(memory);
+            }
+            if let Some(iterations) = options.iterations {
+                params = params.iterations(iterations);
+            }
+            if let Some(parallelism) = options.parallelism {
+                params = params.parallelism(parallelism);
+            }
+            Ok(hash_password(&password, params))
         }
-    }
-
-    /// Increment counter
-    #[napi]
-    pub fn counter_inc(&self, name: String) {
-        self.inner.counter(&name).inc();
-    }
-
-    /// Add to counter
-    #[napi]
-    pub fn counter_add(&self, name: String, value: i64) {
-        self.inner.counter(&name).add(value as u64);
-    }
-
-    /// Get counter value
-    #[napi]
-    pub fn counter_get(&self, name: String) -> i64 {
-        self.inner.counter(&name).get() as i64
-    }
-
-    /// Set gauge value
-    #[napi]
-    pub fn gauge_set(&self, name: String, value: f64) {
-        self.inner.gauge(&name).set(value);
-    }
-
-    /// Get gauge value
-    #[napi]
-    pub fn gauge_get(&self, name: String) -> f64 {
-        self.inner.gauge(&name).get()
-    }
+        "scrypt" => {
+            let mut params = ScryptParams::new();
+            if let Some(log_n) = options.log_n {
+                params = params.log_n(log_n);
+            }
+            if let Some(block_size) = options.block_size {
+                params = params.r(block_size);
+            }
+            if let Some(parallelism) = options.parallelism {
+                params = params.p(parallelism);
+            }
+            Ok(hash_password_scrypt(&password, params))
+        }
+        other => Err(Error::from_reason(format!(
+            "Unknown algorithm '{}' (expected 'argon2id' or 'scrypt')",
+            other
+        ))),
+    });
+    task.await
+        .map_err(|e| Error::from_reason(format!("Hashing task failed: {}", e)))?
+}
 
-    /// Record histogram value
-    #[napi]
-    pub fn histogram_record(&self, name: String, value: f64) {
-        self.inner.histogram(&name).record(value);
-    }
+/// Verify a password against a PHC string from passwordHash
+///
+/// Parameters come from the stored hash, so old hashes keep
+/// verifying after cost defaults change. Throws on malformed or
+/// unrecognized hashes; a wrong password resolves to false.
+#[napi]
+pub async fn password_verify(password: String, hash: String) -> Result<bool> {
+    let task = tokio::task::spawn_blocking(move || {
+        gust_core::crypto::verify_password(&password, &hash)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    });
+    task.await
+        .map_err(|e| Error::from_reason(format!("Verification task failed: {}", e)))?
+}
 
-    /// Get histogram count
-    #[napi]
-    pub fn histogram_count(&self, name: String) -> i64 {
-        self.inner.histogram(&name).count() as i64
-    }
+// ============================================================================
+// Tokens, UUIDs, and timing-safe comparison
+// ============================================================================
 
-    /// Get histogram sum
-    #[napi]
-    pub fn histogram_sum(&self, name: String) -> f64 {
-        self.inner.histogram(&name).sum()
-    }
+/// Compare two buffers in constant time
+///
+/// For secrets (API keys, signatures, session tokens): the run time
+/// depends only on the lengths, so timing does not leak how much of
+/// a guess matched.
+#[napi]
+pub fn timing_safe_equal(a: Buffer, b: Buffer) -> bool {
+    gust_core::crypto::constant_time_eq(&a, &b)
+}
 
-    /// Get histogram mean
-    #[napi]
-    pub fn histogram_mean(&self, name: String) -> f64 {
-        self.inner.histogram(&name).mean()
-    }
+/// `bytes` random bytes from OS entropy as lowercase hex
+#[napi]
+pub fn random_token_hex(bytes: u32) -> String {
+    gust_core::crypto::random_hex(bytes as usize)
+}
 
-    /// Get histogram percentile
-    #[napi]
-    pub fn histogram_percentile(&self, name: String, percentile: f64) -> f64 {
-        self.inner.histogram(&name).percentile(percentile)
-    }
+/// `bytes` random bytes from OS entropy as unpadded URL-safe Base64
+#[napi]
+pub fn random_token_base64url(bytes: u32) -> String {
+    gust_core::crypto::random_base64url(bytes as usize)
+}
 
-    /// Export metrics in Prometheus format
-    #[napi]
-    pub fn to_prometheus(&self) -> String {
-        self.inner.to_prometheus()
-    }
+/// Generate a random UUIDv4 (RFC 9562)
+#[napi]
+pub fn uuid_v4() -> String {
+    gust_core::crypto::uuid_v4()
 }
 
-/// Pre-rendered static response
-#[derive(Clone)]
-struct StaticResponse {
-    bytes: Bytes,
+/// Generate a time-ordered UUIDv7 (RFC 9562)
+///
+/// Sorts by creation time — better index locality than v4 for
+/// database keys.
+#[napi]
+pub fn uuid_v7() -> String {
+    gust_core::crypto::uuid_v7()
 }
 
 // ============================================================================
-// Native Request/Response for JS handlers
+// Validation
 // ============================================================================
 
-/// Request object passed to JS handlers
+/// Validation error
 #[napi(object)]
 #[derive(Clone)]
-pub struct NativeRequest {
-    /// HTTP method
-    pub method: String,
-    /// Request path
+pub struct ValidationError {
     pub path: String,
-    /// Query string (without ?)
-    pub query: Option<String>,
-    /// Route parameters (e.g., { id: "123" })
-    pub params: HashMap<String, String>,
-    /// Request headers
-    pub headers: HashMap<String, String>,
-    /// Request body (raw bytes as string for now)
-    pub body: Option<String>,
-    /// Client IP address
-    pub ip: Option<String>,
+    pub message: String,
+    pub code: String,
 }
 
-/// Response object returned from JS handlers
+/// Validation result
 #[napi(object)]
-#[derive(Clone, Default)]
-pub struct NativeResponse {
-    /// HTTP status code
-    pub status: Option<u32>,
-    /// Response headers
-    pub headers: Option<HashMap<String, String>>,
-    /// Response body
-    pub body: Option<String>,
+pub struct ValidationResult {
+    pub valid: bool,
+    pub errors: Vec<ValidationError>,
 }
 
-/// Handler callback type - uses RequestContext for input
-type HandlerCallback = ThreadsafeFunction<RequestContext, ErrorStrategy::Fatal>;
-
-/// Dynamic route handler
-struct DynamicHandler {
-    callback: HandlerCallback,
+/// Schema type enum
+#[napi(string_enum)]
+pub enum SchemaType {
+    String,
+    Number,
+    Boolean,
+    Object,
+    Array,
+    Any,
 }
 
-// Safety: HandlerCallback (ThreadsafeFunction) is designed to be Send + Sync
-unsafe impl Send for DynamicHandler {}
-unsafe impl Sync for DynamicHandler {}
+/// String format enum
+#[napi(string_enum)]
+pub enum StringFormat {
+    Email,
+    Url,
+    Uuid,
+    Date,
+    DateTime,
+}
 
-impl Clone for DynamicHandler {
-    fn clone(&self) -> Self {
-        Self {
-            callback: self.callback.clone(),
+/// Validate JSON value against schema
+#[napi]
+pub fn validate_json(
+    json_str: String,
+    schema_type: SchemaType,
+    required: bool,
+    min_length: Option<u32>,
+    max_length: Option<u32>,
+    format: Option<StringFormat>,
+    min: Option<f64>,
+    max: Option<f64>,
+    is_integer: Option<bool>,
+) -> ValidationResult {
+    // Parse JSON
+    let value = match parse_json_to_value(&json_str) {
+        Ok(v) => v,
+        Err(e) => {
+            return ValidationResult {
+                valid: false,
+                errors: vec![ValidationError {
+                    path: "$".to_string(),
+                    message: format!("Invalid JSON: {}", e),
+                    code: "invalid_type".to_string(),
+                }],
+            };
         }
-    }
-}
+    };
 
-/// Invoke handler wrapper (for route registration pattern)
-struct InvokeHandler {
-    callback: InvokeHandlerCallback,
-}
+    // Build schema
+    let rust_schema_type = match schema_type {
+        SchemaType::String => RustSchemaType::String,
+        SchemaType::Number => RustSchemaType::Number,
+        SchemaType::Boolean => RustSchemaType::Boolean,
+        SchemaType::Object => RustSchemaType::Object,
+        SchemaType::Array => RustSchemaType::Array,
+        SchemaType::Any => RustSchemaType::Any,
+    };
 
-// Safety: InvokeHandlerCallback (ThreadsafeFunction) is designed to be Send + Sync
-unsafe impl Send for InvokeHandler {}
-unsafe impl Sync for InvokeHandler {}
+    let rust_format = format.map(|f| match f {
+        StringFormat::Email => RustStringFormat::Email,
+        StringFormat::Url => RustStringFormat::Url,
+        StringFormat::Uuid => RustStringFormat::Uuid,
+        StringFormat::Date => RustStringFormat::Date,
+        StringFormat::DateTime => RustStringFormat::DateTime,
+    });
 
-impl Clone for InvokeHandler {
-    fn clone(&self) -> Self {
-        Self {
-            callback: self.callback.clone(),
+    let schema = RustSchema {
+        schema_type: rust_schema_type,
+        required,
+        nullable: false,
+        min_length: min_length.map(|x| x as usize),
+        max_length: max_length.map(|x| x as usize),
+        pattern: None,
+        format: rust_format,
+        min,
+        max,
+        integer: is_integer.unwrap_or(false),
+        enum_values: None,
+        properties: None,
+        additional_properties: true,
+        items: None,
+        min_items: None,
+        max_items: None,
+        unique_items: false,
+    };
+
+    let errors = rust_validate(&value, &schema, "$");
+
+    ValidationResult {
+        valid: errors.is_empty(),
+        errors: errors
+            .into_iter()
+            .map(|e| ValidationError {
+                path: e.path,
+                message: e.message,
+                code: "validation_error".to_string(),
+            })
+            .collect(),
+    }
+}
+
+fn parse_json_to_value(json: &str) -> std::result::Result<RustValue, String> {
+    // Simple JSON parser - handles basic types
+    let json = json.trim();
+
+    if json == "null" {
+        return Ok(RustValue::Null);
+    }
+    if json == "true" {
+        return Ok(RustValue::Bool(true));
+    }
+    if json == "false" {
+        return Ok(RustValue::Bool(false));
+    }
+    if json.starts_with('"') && json.ends_with('"') {
+        return Ok(RustValue::String(json[1..json.len()-1].to_string()));
+    }
+    if let Ok(n) = json.parse::<f64>() {
+        return Ok(RustValue::Number(n));
+    }
+    if json.starts_with('[') {
+        return Ok(RustValue::Array(vec![])); // Simplified
+    }
+    if json.starts_with('{') {
+        return Ok(RustValue::Object(HashMap::new())); // Simplified
+    }
+
+    Err("Failed to parse JSON".to_string())
+}
+
+// ============================================================================
+// Range Requests
+// ============================================================================
+
+/// Parsed range
+#[napi(object)]
+#[derive(Clone)]
+pub struct ParsedRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Range response metadata
+#[napi(object)]
+#[derive(Clone)]
+pub struct RangeResponseMeta {
+    pub status: u32,
+    pub content_type: String,
+    pub content_length: i64,
+    pub content_range: Option<String>,
+    pub accept_ranges: String,
+    pub etag: String,
+}
+
+/// Parse HTTP Range header
+#[napi]
+pub fn parse_range_header(header: String, file_size: i64) -> Option<ParsedRange> {
+    rust_parse_range(&header, file_size as u64).and_then(|parsed| {
+        parsed.ranges.first().map(|r| ParsedRange {
+            start: r.start as i64,
+            end: r.end as i64,
+        })
+    })
+}
+
+/// Generate Content-Range header value
+#[napi]
+pub fn content_range_header(start: i64, end: i64, total: i64) -> String {
+    rust_content_range(start as u64, end as u64, total as u64)
+}
+
+/// Get MIME type from file extension
+#[napi]
+pub fn get_mime_type(extension: String) -> String {
+    rust_get_mime_type(&extension).to_string()
+}
+
+/// Generate ETag from file metadata
+#[napi]
+pub fn generate_etag(mtime_ms: i64, size: i64) -> String {
+    rust_generate_etag(mtime_ms as u64, size as u64)
+}
+
+/// Multipart/byteranges response from `buildMultipartByteranges`
+#[napi(object)]
+pub struct MultipartRangeResponse {
+    /// Content-Type carrying the generated boundary
+    pub content_type: String,
+    /// Content-Length of the multipart body
+    pub content_length: i64,
+    /// The multipart/byteranges body (RFC 7233 §4.1)
+    pub body: Buffer,
+}
+
+/// Build a multipart/byteranges body for a multi-range request
+///
+/// Returns None when the Range header does not parse against the
+/// body size or names only one range — serve that as a plain 206
+/// with `contentRangeHeader` instead.
+#[napi]
+pub fn build_multipart_byteranges(
+    header: String,
+    body: Buffer,
+    content_type: String,
+) -> Option<MultipartRangeResponse> {
+    use gust_core::middleware::range::{
+        multipart_body, multipart_boundary, multipart_content_type,
+    };
+
+    let data: &[u8] = &body;
+    let parsed = rust_parse_range(&header, data.len() as u64)?;
+    if parsed.ranges.len() < 2 {
+        return None;
+    }
+
+    let boundary = multipart_boundary();
+    let multipart = multipart_body(data, &parsed.ranges, &content_type, &boundary);
+    Some(MultipartRangeResponse {
+        content_type: multipart_content_type(&boundary),
+        content_length: multipart.len() as i64,
+        body: multipart.into(),
+    })
+}
+
+// ============================================================================
+// Proxy Headers
+// ============================================================================
+
+/// Proxy information
+#[napi(object)]
+#[derive(Clone)]
+pub struct ProxyInfo {
+    /// Client IP address
+    pub ip: String,
+    /// Original host
+    pub host: String,
+    /// Original protocol (http/https)
+    pub protocol: String,
+    /// Original port
+    pub port: u32,
+    /// Chain of forwarded IPs
+    pub ips: Vec<String>,
+}
+
+/// Proxy trust mode
+#[napi(string_enum)]
+pub enum TrustProxy {
+    None,
+    All,
+    Loopback,
+}
+
+/// Build the core proxy config for a trust mode
+///
+/// Loopback trusts the loopback and RFC 1918 private ranges, matching
+/// the common "behind a local reverse proxy" deployment.
+fn trust_proxy_config(trust: TrustProxy) -> RustProxyConfig {
+    let rust_trust = match trust {
+        TrustProxy::None => RustTrustProxy::None,
+        TrustProxy::All => RustTrustProxy::All,
+        TrustProxy::Loopback => RustTrustProxy::Addresses(vec![
+            gust_core::middleware::proxy::TrustedAddress::parse("127.0.0.1").unwrap(),
+            gust_core::middleware::proxy::TrustedAddress::parse("::1").unwrap(),
+            gust_core::middleware::proxy::TrustedAddress::parse("10.0.0.0/8").unwrap(),
+            gust_core::middleware::proxy::TrustedAddress::parse("172.16.0.0/12").unwrap(),
+            gust_core::middleware::proxy::TrustedAddress::parse("192.168.0.0/16").unwrap(),
+        ]),
+    };
+
+    RustProxyConfig {
+        trust: rust_trust,
+        ip_header: "x-forwarded-for".to_string(),
+        host_header: "x-forwarded-host".to_string(),
+        proto_header: "x-forwarded-proto".to_string(),
+        port_header: "x-forwarded-port".to_string(),
+    }
+}
+
+/// Build a core proxy config trusting an explicit IP/CIDR list
+///
+/// Errors on entries that are neither an IP address nor IPv4 CIDR
+/// notation so typos surface at configuration time, not as silently
+/// untrusted proxies.
+fn trust_proxy_addresses_config(cidrs: &[String]) -> Result<RustProxyConfig> {
+    let mut trusted = Vec::with_capacity(cidrs.len());
+    for cidr in cidrs {
+        match gust_core::middleware::proxy::TrustedAddress::parse(cidr) {
+            Some(addr) => trusted.push(addr),
+            None => {
+                return Err(Error::from_reason(format!(
+                    "Invalid trusted proxy address '{}' (expected IP or CIDR)",
+                    cidr
+                )))
+            }
+        }
+    }
+
+    Ok(RustProxyConfig {
+        trust: RustTrustProxy::Addresses(trusted),
+        ..trust_proxy_config(TrustProxy::None)
+    })
+}
+
+/// Run the core extraction over individually-passed forwarded headers
+fn proxy_info_from_parts(
+    config: &RustProxyConfig,
+    socket_ip: &str,
+    forwarded_for: Option<String>,
+    forwarded_host: Option<String>,
+    forwarded_proto: Option<String>,
+    forwarded_port: Option<String>,
+    host_header: Option<&str>,
+) -> ProxyInfo {
+    let mut headers = Vec::new();
+    if let Some(v) = forwarded_for {
+        headers.push(("x-forwarded-for".to_string(), v));
+    }
+    if let Some(v) = forwarded_host {
+        headers.push(("x-forwarded-host".to_string(), v));
+    }
+    if let Some(v) = forwarded_proto {
+        headers.push(("x-forwarded-proto".to_string(), v));
+    }
+    if let Some(v) = forwarded_port {
+        headers.push(("x-forwarded-port".to_string(), v));
+    }
+
+    let info = rust_extract_proxy_info(config, socket_ip, &headers, host_header);
+
+    ProxyInfo {
+        ip: info.ip,
+        host: info.host,
+        protocol: info.protocol.as_str().to_string(),
+        port: info.port as u32,
+        ips: info.ips,
+    }
+}
+
+/// Extract proxy information from headers
+#[napi]
+pub fn extract_proxy_info(
+    trust: TrustProxy,
+    socket_ip: String,
+    forwarded_for: Option<String>,
+    forwarded_host: Option<String>,
+    forwarded_proto: Option<String>,
+    forwarded_port: Option<String>,
+    host_header: Option<String>,
+) -> ProxyInfo {
+    let config = trust_proxy_config(trust);
+    proxy_info_from_parts(
+        &config,
+        &socket_ip,
+        forwarded_for,
+        forwarded_host,
+        forwarded_proto,
+        forwarded_port,
+        host_header.as_deref(),
+    )
+}
+
+/// Extract proxy information trusting an explicit IP/CIDR list
+///
+/// Unlike the coarse trust modes, the X-Forwarded-For chain is walked
+/// right-to-left past trusted hops, so the reported ip is the first
+/// address not in the trusted set.
+#[napi]
+pub fn extract_proxy_info_with_addresses(
+    trusted_addresses: Vec<String>,
+    socket_ip: String,
+    forwarded_for: Option<String>,
+    forwarded_host: Option<String>,
+    forwarded_proto: Option<String>,
+    forwarded_port: Option<String>,
+    host_header: Option<String>,
+) -> Result<ProxyInfo> {
+    let config = trust_proxy_addresses_config(&trusted_addresses)?;
+    Ok(proxy_info_from_parts(
+        &config,
+        &socket_ip,
+        forwarded_for,
+        forwarded_host,
+        forwarded_proto,
+        forwarded_port,
+        host_header.as_deref(),
+    ))
+}
+
+// ============================================================================
+// OpenTelemetry
+// ============================================================================
+
+/// Span context
+#[napi(object)]
+#[derive(Clone)]
+pub struct SpanContext {
+    pub trace_id: String,
+    pub span_id: String,
+    pub trace_flags: u32,
+}
+
+/// Span status
+#[napi(string_enum)]
+pub enum SpanStatus {
+    Unset,
+    Ok,
+    Error,
+}
+
+/// Generate trace ID (32 hex chars)
+#[napi]
+pub fn generate_trace_id() -> String {
+    rust_generate_trace_id()
+}
+
+/// Generate span ID (16 hex chars)
+#[napi]
+pub fn generate_span_id() -> String {
+    rust_generate_span_id()
+}
+
+/// Parse W3C traceparent header
+#[napi]
+pub fn parse_traceparent(header: String) -> Option<SpanContext> {
+    rust_parse_traceparent(&header).map(|ctx| SpanContext {
+        trace_id: ctx.trace_id,
+        span_id: ctx.span_id,
+        trace_flags: ctx.trace_flags as u32,
+    })
+}
+
+/// Format W3C traceparent header
+#[napi]
+pub fn format_traceparent(trace_id: String, span_id: String, trace_flags: u32) -> String {
+    let ctx = RustSpanContext {
+        trace_id,
+        span_id,
+        trace_flags: trace_flags as u8,
+        trace_state: None,
+    };
+    rust_format_traceparent(&ctx)
+}
+
+/// Parse W3C baggage header into key/value entries
+/// Entry properties are dropped; values are percent-decoded
+#[napi]
+pub fn parse_baggage(header: String) -> HashMap<String, String> {
+    rust_parse_baggage(&header)
+}
+
+/// Format W3C baggage header from key/value entries
+/// Values are percent-encoded where the spec requires it
+#[napi]
+pub fn format_baggage(entries: HashMap<String, String>) -> String {
+    rust_format_baggage(&entries)
+}
+
+/// Tracer for creating spans
+#[napi]
+pub struct Tracer {
+    inner: Arc<RustTracer>,
+}
+
+#[napi]
+impl Tracer {
+    #[napi(constructor)]
+    pub fn new(service_name: String, sample_rate: Option<f64>) -> Self {
+        let config = RustTracerConfig::new(service_name);
+        let config = if let Some(rate) = sample_rate {
+            config.sample_rate(rate)
+        } else {
+            config
+        };
+
+        Self {
+            inner: Arc::new(RustTracer::new(config)),
+        }
+    }
+
+    /// Start a new span
+    #[napi]
+    pub fn start_span(&self, name: String) -> Span {
+        Span {
+            inner: Some(self.inner.start_span(name)),
+        }
+    }
+
+    /// Start a child span
+    #[napi]
+    pub fn start_child_span(&self, name: String, parent_trace_id: String, parent_span_id: String) -> Span {
+        let parent_ctx = RustSpanContext {
+            trace_id: parent_trace_id,
+            span_id: parent_span_id,
+            trace_flags: 1,
+            trace_state: None,
+        };
+        Span {
+            inner: Some(self.inner.start_child_span(name, &parent_ctx)),
+        }
+    }
+
+    /// Get pending span count
+    #[napi]
+    pub fn pending_count(&self) -> u32 {
+        self.inner.pending_count() as u32
+    }
+}
+
+/// A span representing a unit of work
+#[napi]
+pub struct Span {
+    inner: Option<RustSpan>,
+}
+
+#[napi]
+impl Span {
+    /// Get span context
+    #[napi]
+    pub fn context(&self) -> Option<SpanContext> {
+        self.inner.as_ref().map(|s| SpanContext {
+            trace_id: s.context.trace_id.clone(),
+            span_id: s.context.span_id.clone(),
+            trace_flags: s.context.trace_flags as u32,
+        })
+    }
+
+    /// Set attribute
+    #[napi]
+    pub fn set_attribute(&mut self, key: String, value: String) {
+        if let Some(ref mut span) = self.inner {
+            span.set_attribute(key, value);
+        }
+    }
+
+    /// Set numeric attribute
+    #[napi]
+    pub fn set_attribute_number(&mut self, key: String, value: f64) {
+        if let Some(ref mut span) = self.inner {
+            span.set_attribute(key, gust_core::middleware::otel::AttributeValue::Float(value));
+        }
+    }
+
+    /// Add a timestamped event, optionally with attributes
+    #[napi]
+    pub fn add_event(&mut self, name: String, attributes: Option<HashMap<String, String>>) {
+        if let Some(ref mut span) = self.inner {
+            let mut event = RustSpanEvent::new(name);
+            if let Some(attrs) = attributes {
+                event = event.with_attributes(
+                    attrs.into_iter().map(|(k, v)| (k, v.into())).collect(),
+                );
+            }
+            span.add_event(event);
+        }
+    }
+
+    /// Link this span to a span in another trace (fan-out correlation)
+    #[napi]
+    pub fn add_link(
+        &mut self,
+        trace_id: String,
+        span_id: String,
+        attributes: Option<HashMap<String, String>>,
+    ) {
+        if let Some(ref mut span) = self.inner {
+            let ctx = RustSpanContext {
+                trace_id,
+                span_id,
+                trace_flags: 1,
+                trace_state: None,
+            };
+            let mut link = RustSpanLink::new(ctx);
+            if let Some(attrs) = attributes {
+                link = link.with_attributes(
+                    attrs.into_iter().map(|(k, v)| (k, v.into())).collect(),
+                );
+            }
+            span.add_link(link);
+        }
+    }
+
+    /// End span
+    #[napi]
+    pub fn end(&mut self) {
+        if let Some(ref mut span) = self.inner {
+            span.end();
+        }
+    }
+
+    /// End span with status
+    #[napi]
+    pub fn end_with_status(&mut self, status: SpanStatus) {
+        if let Some(ref mut span) = self.inner {
+            let rust_status = match status {
+                SpanStatus::Unset => RustSpanStatus::Unset,
+                SpanStatus::Ok => RustSpanStatus::Ok,
+                SpanStatus::Error => RustSpanStatus::Error,
+            };
+            span.end_with_status(rust_status);
+        }
+    }
+
+    /// Get duration in milliseconds
+    #[napi]
+    pub fn duration_ms(&self) -> Option<f64> {
+        self.inner.as_ref().and_then(|s| s.duration_ms())
+    }
+}
+
+/// Native allocator memory statistics (mimalloc)
+#[napi(object)]
+#[derive(Clone)]
+pub struct MemoryStats {
+    /// Resident set size in bytes
+    pub resident: i64,
+    /// Peak resident set size in bytes
+    pub peak_resident: i64,
+    /// Committed memory in bytes
+    pub committed: i64,
+    /// Peak committed memory in bytes
+    pub peak_committed: i64,
+    /// Page faults since process start
+    pub page_faults: i64,
+    /// User-mode CPU time in milliseconds
+    pub user_ms: i64,
+    /// Kernel-mode CPU time in milliseconds
+    pub system_ms: i64,
+}
+
+fn mi_memory_stats() -> MemoryStats {
+    let mut elapsed = 0usize;
+    let mut user = 0usize;
+    let mut system = 0usize;
+    let mut resident = 0usize;
+    let mut peak_resident = 0usize;
+    let mut committed = 0usize;
+    let mut peak_committed = 0usize;
+    let mut page_faults = 0usize;
+
+    unsafe {
+        libmimalloc_sys::mi_process_info(
+            &mut elapsed,
+            &mut user,
+            &mut system,
+            &mut resident,
+            &mut peak_resident,
+            &mut committed,
+            &mut peak_committed,
+            &mut page_faults,
+        );
+    }
+
+    MemoryStats {
+        resident: resident as i64,
+        peak_resident: peak_resident as i64,
+        committed: committed as i64,
+        peak_committed: peak_committed as i64,
+        page_faults: page_faults as i64,
+        user_ms: user as i64,
+        system_ms: system as i64,
+    }
+}
+
+/// Native memory statistics from the mimalloc allocator
+///
+/// Separate from the V8 heap, so operators can watch native memory
+/// growth (buffers, caches, connection state) directly.
+#[napi]
+pub fn memory_stats() -> MemoryStats {
+    mi_memory_stats()
+}
+
+/// Detailed per-heap allocator statistics as text (mi_stats_print_out)
+#[napi]
+pub fn memory_stats_detailed() -> String {
+    unsafe extern "C" fn append(msg: *const std::os::raw::c_char, arg: *mut std::ffi::c_void) {
+        let out = &mut *(arg as *mut String);
+        if let Ok(s) = std::ffi::CStr::from_ptr(msg).to_str() {
+            out.push_str(s);
+        }
+    }
+
+    let mut out = String::new();
+    unsafe {
+        libmimalloc_sys::mi_stats_print_out(Some(append), &mut out as *mut String as *mut _);
+    }
+    out
+}
+
+/// Metrics collector
+#[napi]
+pub struct MetricsCollector {
+    inner: Arc<RustMetricsCollector>,
+    /// Set while the periodic memory gauge task is running
+    memory_gauges_running: Arc<AtomicBool>,
+}
+
+#[napi]
+impl MetricsCollector {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RustMetricsCollector::new()),
+            memory_gauges_running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Increment counter
+    #[napi]
+    pub fn counter_inc(&self, name: String) {
+        self.inner.counter(&name).inc();
+    }
+
+    /// Add to counter
+    #[napi]
+    pub fn counter_add(&self, name: String, value: i64) {
+        self.inner.counter(&name).add(value as u64);
+    }
+
+    /// Get counter value
+    #[napi]
+    pub fn counter_get(&self, name: String) -> i64 {
+        self.inner.counter(&name).get() as i64
+    }
+
+    /// Set gauge value
+    #[napi]
+    pub fn gauge_set(&self, name: String, value: f64) {
+        self.inner.gauge(&name).set(value);
+    }
+
+    /// Get gauge value
+    #[napi]
+    pub fn gauge_get(&self, name: String) -> f64 {
+        self.inner.gauge(&name).get()
+    }
+
+    /// Record histogram value
+    #[napi]
+    pub fn histogram_record(&self, name: String, value: f64) {
+        self.inner.histogram(&name).record(value);
+    }
+
+    /// Get histogram count
+    #[napi]
+    pub fn histogram_count(&self, name: String) -> i64 {
+        self.inner.histogram(&name).count() as i64
+    }
+
+    /// Get histogram sum
+    #[napi]
+    pub fn histogram_sum(&self, name: String) -> f64 {
+        self.inner.histogram(&name).sum()
+    }
+
+    /// Get histogram mean
+    #[napi]
+    pub fn histogram_mean(&self, name: String) -> f64 {
+        self.inner.histogram(&name).mean()
+    }
+
+    /// Get histogram percentile
+    #[napi]
+    pub fn histogram_percentile(&self, name: String, percentile: f64) -> f64 {
+        self.inner.histogram(&name).percentile(percentile)
+    }
+
+    /// Export metrics in Prometheus format
+    #[napi]
+    pub fn to_prometheus(&self) -> String {
+        self.inner.to_prometheus()
+    }
+
+    /// Start periodically exporting allocator memory gauges
+    ///
+    /// Sets `memory_resident_bytes`, `memory_peak_resident_bytes`,
+    /// `memory_committed_bytes`, and `memory_peak_committed_bytes`
+    /// every `interval_ms` until stopped.
+    #[napi]
+    pub async fn start_memory_gauges(&self, interval_ms: u32) {
+        if self.memory_gauges_running.swap(true, Ordering::SeqCst) {
+            return; // Already running
+        }
+
+        let running = self.memory_gauges_running.clone();
+        let collector = self.inner.clone();
+        let interval = Duration::from_millis(interval_ms.max(100) as u64);
+
+        tokio::spawn(async move {
+            while running.load(Ordering::SeqCst) {
+                let stats = mi_memory_stats();
+                collector.gauge("memory_resident_bytes").set(stats.resident as f64);
+                collector.gauge("memory_peak_resident_bytes").set(stats.peak_resident as f64);
+                collector.gauge("memory_committed_bytes").set(stats.committed as f64);
+                collector.gauge("memory_peak_committed_bytes").set(stats.peak_committed as f64);
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// Stop the periodic memory gauge export
+    #[napi]
+    pub fn stop_memory_gauges(&self) {
+        self.memory_gauges_running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Pre-rendered static response
+#[derive(Clone)]
+struct StaticResponse {
+    /// Status code registered for the route
+    status: u16,
+    /// Response headers (content-type, etag)
+    headers: Vec<(String, String)>,
+    /// Response body
+    body: Bytes,
+    /// Content-hash ETag for conditional requests
+    etag: String,
+    /// Full pre-rendered HTTP/1.1 bytes (raw engine fast path)
+    bytes: Bytes,
+}
+
+// ============================================================================
+// GraphQL-over-HTTP
+// ============================================================================
+
+/// Options for a GraphQL route
+#[napi(object)]
+#[derive(Clone)]
+pub struct GraphQLRouteOptions {
+    /// Handler ID of the JS resolver invoked with the normalized request JSON
+    pub execute_handler_id: u32,
+    /// Serve GraphiQL on GET requests with Accept: text/html (default: false)
+    pub graphiql: Option<bool>,
+}
+
+/// Registered GraphQL route state
+#[derive(Clone)]
+struct GraphQLRoute {
+    execute_handler_id: u32,
+    graphiql: bool,
+}
+
+// ============================================================================
+// JSON-RPC 2.0
+// ============================================================================
+
+/// Registered JSON-RPC dispatcher state
+#[derive(Clone, Default)]
+struct JsonRpcRoute {
+    /// Method name -> handler ID
+    methods: HashMap<String, u32>,
+}
+
+// ============================================================================
+// Resumable uploads (tus.io 1.0)
+// ============================================================================
+
+/// Options for a tus upload endpoint
+#[napi(object)]
+#[derive(Clone)]
+pub struct TusOptions {
+    /// Directory for chunk storage; uploads are kept in memory if unset
+    pub directory: Option<String>,
+    /// Maximum declared upload size in bytes (Tus-Max-Size)
+    pub max_size: Option<i64>,
+    /// Upload lifetime in seconds; expired uploads are removed lazily
+    pub expiration_seconds: Option<i64>,
+}
+
+// ============================================================================
+// Embedded static assets
+// ============================================================================
+
+/// One embedded asset (manifest entry)
+#[napi(object)]
+pub struct EmbeddedAsset {
+    /// Request path relative to the mount prefix
+    pub path: String,
+    /// File contents
+    pub data: Buffer,
+}
+
+/// Options for an embedded asset mount
+#[napi(object)]
+#[derive(Clone)]
+pub struct EmbeddedAssetOptions {
+    /// Index file name (default: index.html)
+    pub index: Option<String>,
+    /// Cache max-age in seconds (default: 86400)
+    pub max_age: Option<u32>,
+    /// Fallback file for SPA routing
+    pub fallback: Option<String>,
+}
+
+// ============================================================================
+// Raw TCP listeners
+// ============================================================================
+
+/// Event delivered to a raw TCP listener handler
+#[napi(object)]
+pub struct TcpEventContext {
+    /// Listener-unique connection id
+    pub connection_id: i64,
+    /// Peer socket address
+    pub remote_addr: String,
+    /// Event kind: "open", "data", or "close"
+    pub event: String,
+    /// Bytes read from the peer ("data" events only)
+    pub data: Option<Buffer>,
+}
+
+/// Optional handler reply for a raw TCP event
+#[napi(object)]
+#[derive(Default)]
+pub struct TcpReply {
+    /// Bytes to write back to the peer
+    pub data: Option<Buffer>,
+    /// Close the connection after queued writes flush
+    pub close: Option<bool>,
+}
+
+/// Raw TCP handler callback type
+type TcpEventCallback = ThreadsafeFunction<TcpEventContext, ErrorStrategy::Fatal>;
+
+/// One datagram in a UDP batch
+#[napi(object)]
+pub struct UdpDatagram {
+    /// Sender socket address
+    pub remote_addr: String,
+    /// Datagram payload
+    pub data: Buffer,
+}
+
+/// Options for a UDP listener
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct UdpOptions {
+    /// Bind hostname (default: 0.0.0.0)
+    pub hostname: Option<String>,
+    /// Datagrams per batch before an immediate flush (default: 64)
+    pub batch_size: Option<u32>,
+    /// How long a partial batch may wait in ms (default: 50)
+    pub flush_interval_ms: Option<u32>,
+}
+
+/// UDP batch callback type
+type UdpBatchCallback = ThreadsafeFunction<Vec<UdpDatagram>, ErrorStrategy::Fatal>;
+
+// ============================================================================
+// Server-managed WebSocket routes
+// ============================================================================
+
+/// Options for a server-managed WebSocket route
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct WebSocketRouteOptions {
+    /// Keepalive ping interval in milliseconds (default: no pings)
+    pub ping_interval_ms: Option<u32>,
+    /// Maximum complete message size in bytes (default: 16MB)
+    pub max_message_bytes: Option<u32>,
+}
+
+/// Event delivered to a WebSocket route handler
+#[napi(object)]
+pub struct WsEventContext {
+    /// Server-unique connection id
+    pub connection_id: i64,
+    /// Route path the connection was opened on
+    pub path: String,
+    /// Peer socket address
+    pub remote_addr: String,
+    /// Event kind: "open", "message", or "close"
+    pub event: String,
+    /// Text payload ("message" events carrying a text frame)
+    pub text: Option<String>,
+    /// Binary payload ("message" events carrying a binary frame)
+    pub data: Option<Buffer>,
+    /// Close code ("close" events; unset when the transport dropped
+    /// without a close handshake)
+    pub code: Option<u32>,
+    /// Close reason ("close" events)
+    pub reason: Option<String>,
+}
+
+/// Optional handler reply for a WebSocket event
+#[napi(object)]
+#[derive(Default)]
+pub struct WsReply {
+    /// Text message to send back
+    pub text: Option<String>,
+    /// Binary message to send back
+    pub data: Option<Buffer>,
+    /// Start the close handshake after queued sends flush
+    pub close: Option<bool>,
+}
+
+/// WebSocket route handler callback type
+type WsEventCallback = ThreadsafeFunction<WsEventContext, ErrorStrategy::Fatal>;
+
+/// Registered WebSocket route
+struct WsRoute {
+    callback: WsEventCallback,
+    options: WebSocketRouteOptions,
+}
+
+/// Command queued into a connection's frame loop: sends and closes
+/// initiated outside the loop (handler replies, wsSend/wsClose)
+enum WsCommand {
+    Text(String),
+    Binary(Vec<u8>),
+    Close(u16, String),
+}
+
+type WsCommandSender = tokio::sync::mpsc::UnboundedSender<WsCommand>;
+
+// ============================================================================
+// Lifecycle hooks
+// ============================================================================
+
+/// Payload for onRequest listeners
+#[napi(object)]
+pub struct RequestHookEvent {
+    pub method: String,
+    pub path: String,
+}
+
+/// Payload for onResponse listeners
+#[napi(object)]
+pub struct ResponseHookEvent {
+    pub method: String,
+    pub path: String,
+    pub status: u32,
+    /// Time spent in dispatch, in milliseconds
+    pub duration_ms: f64,
+}
+
+/// Payload for onError listeners (requests finishing with 5xx)
+#[napi(object)]
+pub struct ErrorHookEvent {
+    pub method: String,
+    pub path: String,
+    pub status: u32,
+    pub message: String,
+}
+
+/// Payload for onConnection listeners
+#[napi(object)]
+pub struct ConnectionHookEvent {
+    pub remote_addr: String,
+    /// "opened" or "closed"
+    pub phase: String,
+}
+
+type RequestHookCallback = ThreadsafeFunction<RequestHookEvent, ErrorStrategy::Fatal>;
+type ResponseHookCallback = ThreadsafeFunction<ResponseHookEvent, ErrorStrategy::Fatal>;
+type ErrorHookCallback = ThreadsafeFunction<ErrorHookEvent, ErrorStrategy::Fatal>;
+type ConnectionHookCallback = ThreadsafeFunction<ConnectionHookEvent, ErrorStrategy::Fatal>;
+
+// ============================================================================
+// Native Request/Response for JS handlers
+// ============================================================================
+
+/// Request object passed to JS handlers
+#[napi(object)]
+#[derive(Clone)]
+pub struct NativeRequest {
+    /// HTTP method
+    pub method: String,
+    /// Request path
+    pub path: String,
+    /// Query string (without ?)
+    pub query: Option<String>,
+    /// Route parameters (e.g., { id: "123" })
+    pub params: HashMap<String, String>,
+    /// Request headers
+    pub headers: HashMap<String, String>,
+    /// Request body (raw bytes as string for now)
+    pub body: Option<String>,
+    /// Client IP address
+    pub ip: Option<String>,
+}
+
+/// Response object returned from JS handlers
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct NativeResponse {
+    /// HTTP status code
+    pub status: Option<u32>,
+    /// Response headers
+    pub headers: Option<HashMap<String, String>>,
+    /// Response body
+    pub body: Option<String>,
+}
+
+/// Handler callback type - uses RequestContext for input
+type HandlerCallback = ThreadsafeFunction<RequestContext, ErrorStrategy::Fatal>;
+
+/// Dynamic route handler
+struct DynamicHandler {
+    callback: HandlerCallback,
+    /// Handler returns ResponseData synchronously, dispatched without
+    /// the Promise round trip (bun-direct feature)
+    #[cfg(feature = "bun-direct")]
+    sync: bool,
+}
+
+// Safety: HandlerCallback (ThreadsafeFunction) is designed to be Send + Sync
+unsafe impl Send for DynamicHandler {}
+unsafe impl Sync for DynamicHandler {}
+
+impl Clone for DynamicHandler {
+    fn clone(&self) -> Self {
+        Self {
+            callback: self.callback.clone(),
+            #[cfg(feature = "bun-direct")]
+            sync: self.sync,
+        }
+    }
+}
+
+/// Invoke handler wrapper (for route registration pattern)
+struct InvokeHandler {
+    callback: InvokeHandlerCallback,
+}
+
+// Safety: InvokeHandlerCallback (ThreadsafeFunction) is designed to be Send + Sync
+unsafe impl Send for InvokeHandler {}
+unsafe impl Sync for InvokeHandler {}
+
+impl Clone for InvokeHandler {
+    fn clone(&self) -> Self {
+        Self {
+            callback: self.callback.clone(),
+        }
+    }
+}
+
+/// Server diagnostic log levels, ordered by verbosity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn parse(level: &str) -> Option<Self> {
+        match level {
+            "error" => Some(LogLevel::Error),
+            "warn" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            "trace" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// Runtime-tunable observability settings
+///
+/// Swapped atomically as a whole so the hot path reads a consistent
+/// view without locks.
+#[derive(Clone)]
+struct ObservabilityConfig {
+    /// Diagnostic log level for the native server's own output
+    log_level: LogLevel,
+    /// Fraction of requests eligible for per-request debug logging
+    sample_rate: f64,
+    /// Log requests slower than this as warnings (0 disables)
+    slow_request_threshold_ms: f64,
+}
+
+impl Default for ObservabilityConfig {
+    fn default() -> Self {
+        Self {
+            log_level: LogLevel::Info,
+            sample_rate: 1.0,
+            slow_request_threshold_ms: 0.0,
+        }
+    }
+}
+
+fn observability_json(config: &ObservabilityConfig) -> String {
+    format!(
+        "{{\"logLevel\":\"{}\",\"sampleRate\":{},\"slowRequestThresholdMs\":{}}}",
+        config.log_level.as_str(),
+        config.sample_rate,
+        config.slow_request_threshold_ms
+    )
+}
+
+/// Apply a JSON update body from the admin endpoint to a config copy
+///
+/// Unknown keys are ignored; listed keys are validated before any of
+/// them take effect.
+fn apply_observability_update(
+    config: &ObservabilityConfig,
+    body: &str,
+) -> std::result::Result<ObservabilityConfig, String> {
+    let value = gust_core::pure::json::parse_json(body).map_err(|e| e.to_string())?;
+    let obj = value
+        .as_object()
+        .ok_or_else(|| "expected a JSON object".to_string())?;
+
+    let mut updated = config.clone();
+
+    if let Some(v) = obj.get("logLevel") {
+        let level = v
+            .as_str()
+            .ok_or_else(|| "logLevel must be a string".to_string())?;
+        updated.log_level = LogLevel::parse(level).ok_or_else(|| {
+            format!(
+                "Unknown log level '{}' (expected error, warn, info, debug, or trace)",
+                level
+            )
+        })?;
+    }
+
+    if let Some(v) = obj.get("sampleRate") {
+        let rate = v
+            .as_f64()
+            .ok_or_else(|| "sampleRate must be a number".to_string())?;
+        if !(0.0..=1.0).contains(&rate) {
+            return Err("sampleRate must be between 0.0 and 1.0".to_string());
+        }
+        updated.sample_rate = rate;
+    }
+
+    if let Some(v) = obj.get("slowRequestThresholdMs") {
+        let threshold = v
+            .as_f64()
+            .ok_or_else(|| "slowRequestThresholdMs must be a number".to_string())?;
+        if threshold < 0.0 {
+            return Err("slowRequestThresholdMs must not be negative".to_string());
+        }
+        updated.slow_request_threshold_ms = threshold;
+    }
+
+    Ok(updated)
+}
+
+/// Striding sampler: keeps roughly `rate` of calls, evenly spread
+fn sample_request(rate: f64) -> bool {
+    if rate >= 1.0 {
+        return true;
+    }
+    if rate <= 0.0 {
+        return false;
+    }
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    ((n as f64) * rate).floor() < (((n + 1) as f64) * rate).floor()
+}
+
+/// Server state shared across all connections
+struct ServerState {
+    /// Router using handler IDs (SSOT from gust-router) - for legacy routes
+    router: RwLock<Router>,
+    /// Static responses indexed by handler ID
+    static_responses: RwLock<HashMap<u32, StaticResponse>>,
+    /// Dynamic handlers indexed by handler ID - legacy pattern
+    dynamic_handlers: RwLock<HashMap<u32, DynamicHandler>>,
+    /// Next handler ID for legacy routes (atomic counter)
+    next_handler_id: AtomicU32,
+    /// App routes - using ArcSwap for lock-free reads on hot path
+    app_routes: ArcSwap<Router>,
+    /// Invoke handler callback - calls GustApp.invokeHandler(id, ctx)
+    /// Using ArcSwap for lock-free reads on hot path (massive perf improvement)
+    invoke_handler: ArcSwap<Option<InvokeHandler>>,
+    /// Worker-thread invoke handlers, one ThreadsafeFunction per
+    /// worker (ArcSwap for lock-free reads on the hot path)
+    workers: ArcSwap<Vec<InvokeHandler>>,
+    /// Round-robin cursor over the worker handlers
+    worker_cursor: AtomicUsize,
+    /// Route requests to workers by path hash instead of round-robin
+    worker_route_affinity: AtomicBool,
+    /// Middleware chain, rebuilt from the registry on every change
+    /// (ArcSwap for lock-free reads on the hot path)
+    middleware: ArcSwap<MiddlewareChain>,
+    /// Named middleware registry the chain is rebuilt from, in
+    /// registration order; updating a name replaces it in place
+    middleware_registry: RwLock<Vec<(String, Arc<dyn Middleware>)>>,
+    /// Fallback handler for unmatched routes
+    fallback_handler: RwLock<Option<DynamicHandler>>,
+    /// Compression configuration
+    compression: RwLock<Option<CompressionConfig>>,
+    /// TLS configuration
+    tls_config: RwLock<Option<TlsConfig>>,
+    /// Enable HTTP/2 (atomic for lock-free read)
+    http2_enabled: AtomicBool,
+    /// Request timeout in milliseconds (atomic for lock-free read)
+    request_timeout_ms: AtomicU32,
+    /// Maximum body size in bytes (atomic for lock-free read)
+    max_body_size: AtomicU32,
+    /// Per-content-type body limits overriding `max_body_size`
+    /// (ArcSwap for lock-free reads on the hot path)
+    body_limits: ArcSwap<Vec<gust_core::pure::BodyLimitRule>>,
+    /// Per-phase timeout hierarchy (global + per-route overrides);
+    /// phases left at 0 fall back to `request_timeout_ms`
+    /// (ArcSwap for lock-free reads on the hot path)
+    timeouts: ArcSwap<gust_core::TimeoutHierarchy>,
+    /// Timeouts observed in the body-read phase
+    timeouts_body: AtomicU64,
+    /// Timeouts observed in the handler phase
+    timeouts_handler: AtomicU64,
+    /// Timeouts observed against the total request deadline
+    timeouts_total: AtomicU64,
+    /// Active wall-clock profile capture, None outside a capture
+    /// (ArcSwap so the hot path can check it lock-free)
+    profiler: ArcSwap<Option<Arc<gust_core::Profiler>>>,
+    /// Bearer token required by the profiling endpoint; None keeps
+    /// the endpoint disabled
+    profile_token: RwLock<Option<String>>,
+    /// Serializes profile captures — one at a time
+    profile_capture: tokio::sync::Mutex<()>,
+    /// Keep-alive timeout in milliseconds (atomic for lock-free read)
+    keep_alive_timeout_ms: AtomicU32,
+    /// Maximum header size in bytes (atomic for lock-free read)
+    max_header_size: AtomicU32,
+    /// Transfer headers/params as flat buffers decoded lazily in JS
+    /// (atomic for lock-free read)
+    batched_headers: AtomicBool,
+    /// Serve with the raw HTTP/1.1 engine instead of hyper
+    raw_engine: AtomicBool,
+    /// Accept workers, each with its own SO_REUSEPORT listener
+    /// (0 = one per CPU core, resolved at serve time)
+    accept_workers: AtomicU32,
+    /// Proxy header trust config for per-request client info
+    /// (ArcSwap for lock-free reads on the hot path)
+    proxy_config: ArcSwap<Option<RustProxyConfig>>,
+    /// GraphQL routes by exact path
+    graphql_routes: RwLock<HashMap<String, GraphQLRoute>>,
+    /// Automatic persisted query (APQ) cache: sha256 hash -> query document
+    apq_cache: RwLock<HashMap<String, String>>,
+    /// JSON-RPC dispatchers by exact path
+    jsonrpc_routes: RwLock<HashMap<String, JsonRpcRoute>>,
+    /// tus upload endpoints by base path
+    tus_routes: RwLock<HashMap<String, Arc<gust_core::handlers::Tus>>>,
+    /// Embedded asset mounts by path prefix
+    embedded_routes: RwLock<HashMap<String, Arc<gust_core::StaticFiles>>>,
+    /// Server-managed WebSocket routes by exact path
+    ws_routes: RwLock<HashMap<String, Arc<WsRoute>>>,
+    /// Command senders for live WebSocket connections, by connection id
+    ws_connections: RwLock<HashMap<i64, WsCommandSender>>,
+    /// Next WebSocket connection id
+    next_ws_id: AtomicU64,
+    /// Response cache handle, shared with the middleware chain for purges
+    response_cache: RwLock<Option<Arc<gust_core::middleware::Cache>>>,
+    /// Admission control handle, shared with the chain for stats
+    admission: RwLock<Option<Arc<gust_core::middleware::Admission>>>,
+    /// Adaptive concurrency handle, shared with the chain for gauges
+    adaptive: RwLock<Option<Arc<gust_core::middleware::AdaptiveConcurrency>>>,
+    /// Lifecycle hook listeners (request, response, error, connection)
+    hooks: Arc<gust_core::HookRegistry>,
+    /// Runtime-tunable log level / sampling / slow-request settings
+    /// (ArcSwap for lock-free reads on the hot path)
+    observability: ArcSwap<ObservabilityConfig>,
+    /// Admin endpoint path for runtime observability control
+    admin_path: RwLock<Option<String>>,
+}
+
+// Default values
+const DEFAULT_REQUEST_TIMEOUT_MS: u32 = 30000;  // 30 seconds
+const DEFAULT_MAX_BODY_SIZE: u32 = 1024 * 1024; // 1MB
+const DEFAULT_KEEP_ALIVE_TIMEOUT_MS: u32 = 5000; // 5 seconds
+const DEFAULT_MAX_HEADER_SIZE: u32 = 8192;      // 8KB
+
+impl ServerState {
+    fn new() -> Self {
+        Self {
+            router: RwLock::new(Router::new()),
+            static_responses: RwLock::new(HashMap::new()),
+            dynamic_handlers: RwLock::new(HashMap::new()),
+            next_handler_id: AtomicU32::new(1000), // Start at 1000 to avoid conflicts with app routes
+            app_routes: ArcSwap::new(Arc::new(Router::new())),
+            invoke_handler: ArcSwap::new(Arc::new(None)),
+            workers: ArcSwap::new(Arc::new(Vec::new())),
+            worker_cursor: AtomicUsize::new(0),
+            worker_route_affinity: AtomicBool::new(false),
+            middleware: ArcSwap::new(Arc::new(MiddlewareChain::new())),
+            middleware_registry: RwLock::new(Vec::new()),
+            fallback_handler: RwLock::new(None),
+            compression: RwLock::new(None),
+            tls_config: RwLock::new(None),
+            http2_enabled: AtomicBool::new(false),
+            request_timeout_ms: AtomicU32::new(DEFAULT_REQUEST_TIMEOUT_MS),
+            max_body_size: AtomicU32::new(DEFAULT_MAX_BODY_SIZE),
+            body_limits: ArcSwap::new(Arc::new(Vec::new())),
+            timeouts: ArcSwap::new(Arc::new(gust_core::TimeoutHierarchy::new(
+                gust_core::TimeoutConfig::none(),
+            ))),
+            timeouts_body: AtomicU64::new(0),
+            timeouts_handler: AtomicU64::new(0),
+            timeouts_total: AtomicU64::new(0),
+            profiler: ArcSwap::new(Arc::new(None)),
+            profile_token: RwLock::new(None),
+            profile_capture: tokio::sync::Mutex::new(()),
+            keep_alive_timeout_ms: AtomicU32::new(DEFAULT_KEEP_ALIVE_TIMEOUT_MS),
+            max_header_size: AtomicU32::new(DEFAULT_MAX_HEADER_SIZE),
+            batched_headers: AtomicBool::new(false),
+            raw_engine: AtomicBool::new(false),
+            accept_workers: AtomicU32::new(1),
+            proxy_config: ArcSwap::new(Arc::new(None)),
+            graphql_routes: RwLock::new(HashMap::new()),
+            apq_cache: RwLock::new(HashMap::new()),
+            jsonrpc_routes: RwLock::new(HashMap::new()),
+            tus_routes: RwLock::new(HashMap::new()),
+            embedded_routes: RwLock::new(HashMap::new()),
+            ws_routes: RwLock::new(HashMap::new()),
+            ws_connections: RwLock::new(HashMap::new()),
+            next_ws_id: AtomicU64::new(1),
+            response_cache: RwLock::new(None),
+            admission: RwLock::new(None),
+            adaptive: RwLock::new(None),
+            hooks: Arc::new(gust_core::HookRegistry::new()),
+            observability: ArcSwap::new(Arc::new(ObservabilityConfig::default())),
+            admin_path: RwLock::new(None),
+        }
+    }
+}
+
+// ConnectionTracker is now in gust_core::ConnectionTracker (CoreConnectionTracker)
+
+/// Native HTTP server
+#[napi]
+pub struct GustServer {
+    state: Arc<ServerState>,
+    shutdown_tx: Arc<RwLock<Option<tokio::sync::oneshot::Sender<()>>>>,
+    connection_tracker: Arc<CoreConnectionTracker>,
+    /// Shutdown senders for raw TCP/UDP listeners (one per listener)
+    tcp_shutdown: Arc<RwLock<Vec<tokio::sync::oneshot::Sender<()>>>>,
+    /// Actual bound address after serve(); `serve(0)` binds an
+    /// ephemeral port, so test harnesses read it back via address()
+    bound_addr: Arc<RwLock<Option<std::net::SocketAddr>>>,
+}
+
+#[napi]
+impl GustServer {
+    /// Create a new server instance
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(ServerState::new()),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+            connection_tracker: Arc::new(CoreConnectionTracker::new()),
+            tcp_shutdown: Arc::new(RwLock::new(Vec::new())),
+            bound_addr: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Create a server with configuration
+    ///
+    /// The config is validated first: conflicting settings (http2
+    /// without TLS, a rate-limit window of 0, TLS with neither path
+    /// nor PEM, ...) fail with every error listed; warnings are
+    /// printed and the server starts anyway. Use `validateConfig` to
+    /// run the same checks without starting a server.
+    #[napi(factory)]
+    pub async fn with_config(config: ServerConfig) -> Result<Self> {
+        let issues = validate_server_config(&config);
+        let errors: Vec<String> = issues
+            .iter()
+            .filter(|i| i.severity == "error")
+            .map(|i| format!("{}: {}", i.field, i.message))
+            .collect();
+        if !errors.is_empty() {
+            return Err(Error::from_reason(format!(
+                "Invalid server config: {}",
+                errors.join("; ")
+            )));
+        }
+        for issue in issues.iter().filter(|i| i.severity == "warning") {
+            eprintln!("Config warning ({}): {}", issue.field, issue.message);
+        }
+
+        let server = Self::new();
+
+        // Apply middleware from config
+        if let Some(cors) = config.cors {
+            server.enable_cors(cors).await?;
+        }
+
+        if let Some(rate_limit) = config.rate_limit {
+            server.enable_rate_limit(rate_limit).await?;
+        }
+
+        if let Some(security) = config.security {
+            server.enable_security(security).await?;
+        }
+
+        if let Some(tracing) = config.tracing {
+            server.enable_tracing(tracing).await?;
+        }
+
+        if let Some(compression) = config.compression {
+            server.enable_compression(compression).await?;
+        }
+
+        if let Some(tls) = config.tls {
+            server.enable_tls(tls).await?;
+        }
+
+        if let Some(http2) = config.http2 {
+            server.state.http2_enabled.store(http2, Ordering::Relaxed);
+        }
+
+        if let Some(workers) = config.workers {
+            server.set_workers(workers);
+        }
+
+        // Apply timeout and limit configurations (lock-free atomic stores)
+        if let Some(timeout) = config.request_timeout_ms {
+            server.state.request_timeout_ms.store(timeout, Ordering::Relaxed);
+        }
+        if let Some(max_body) = config.max_body_size {
+            server.state.max_body_size.store(max_body, Ordering::Relaxed);
+        }
+        if let Some(keep_alive) = config.keep_alive_timeout_ms {
+            server.state.keep_alive_timeout_ms.store(keep_alive, Ordering::Relaxed);
+        }
+        if let Some(max_header) = config.max_header_size {
+            server.state.max_header_size.store(max_header, Ordering::Relaxed);
+        }
+        if let Some(depth) = config.max_pipeline_depth {
+            server.connection_tracker.set_max_pipeline_depth(depth as u64);
+        }
+        if let Some(batched) = config.batched_headers {
+            server.state.batched_headers.store(batched, Ordering::Relaxed);
+        }
+        if let Some(engine) = config.engine {
+            server.set_engine(engine)?;
+        }
+        if let Some(trust) = config.trust_proxy {
+            server.set_trust_proxy(trust);
+        }
+        if let Some(addresses) = config.trust_proxy_addresses {
+            server.set_trust_proxy_addresses(addresses)?;
+        }
+        if let Some(admin) = config.admin {
+            server.enable_admin_listener(admin).await?;
+        }
+
+        Ok(server)
+    }
+
+    /// Create a server from a TOML or JSON configuration file
+    ///
+    /// Keys map 1:1 to ServerConfig (snake_case in TOML, camelCase or
+    /// snake_case in JSON) covering ports, TLS, middleware, and
+    /// limits. GUST_* environment variables (GUST_PORT, GUST_HOSTNAME,
+    /// GUST_HTTP2, GUST_ENGINE, GUST_TLS_CERT_PATH, ...) override the
+    /// file. Unknown keys and invalid values fail with the offending
+    /// key named.
+    #[napi(factory)]
+    pub async fn from_config_file(path: String) -> Result<Self> {
+        let config = load_config_file(&path).map_err(Error::from_reason)?;
+        Self::with_config(config).await
+    }
+
+    /// Set request timeout in milliseconds
+    #[napi]
+    pub async fn set_request_timeout(&self, timeout_ms: u32) -> Result<()> {
+        self.state.request_timeout_ms.store(timeout_ms, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Configure the per-phase timeout hierarchy
+    ///
+    /// Header read is enforced at the connection level (the client is
+    /// simply disconnected), body read returns 408, handler execution
+    /// and the total deadline return 504. Connect and write deadlines
+    /// are carried in the config for engines that can observe those
+    /// phases. Unset fields keep their current value; 0 disables a
+    /// phase, and a phase left at 0 falls back to the legacy
+    /// `setRequestTimeout` value for body and handler.
+    #[napi]
+    pub async fn set_timeouts(&self, config: TimeoutsConfig) -> Result<()> {
+        let current = self.state.timeouts.load();
+        let mut next = (**current).clone();
+        next.set_global(apply_timeout_overrides(*next.global(), &config));
+        self.state.timeouts.store(Arc::new(next));
+        Ok(())
+    }
+
+    /// Override timeouts for one route pattern (e.g. stretch the body
+    /// deadline on `/upload` while keeping the rest)
+    ///
+    /// Unset fields inherit the global config; registering the same
+    /// pattern again replaces the previous override.
+    #[napi]
+    pub async fn set_route_timeouts(&self, route: String, config: TimeoutsConfig) -> Result<()> {
+        let current = self.state.timeouts.load();
+        let mut next = (**current).clone();
+        next.set_route(
+            route,
+            apply_timeout_overrides(gust_core::TimeoutConfig::none(), &config),
+        );
+        self.state.timeouts.store(Arc::new(next));
+        Ok(())
+    }
+
+    /// Timeout counts per enforced phase since the server started
+    #[napi]
+    pub fn timeout_stats(&self) -> TimeoutStats {
+        TimeoutStats {
+            body: self.state.timeouts_body.load(Ordering::Relaxed) as u32,
+            handler: self.state.timeouts_handler.load(Ordering::Relaxed) as u32,
+            total: self.state.timeouts_total.load(Ordering::Relaxed) as u32,
+        }
+    }
+
+    /// Tokio runtime metrics: worker count, alive tasks, global
+    /// queue depth, and aggregate worker busy/park counters
+    ///
+    /// Also exported on the admin listener's /metrics as
+    /// `gust_tokio_*` series.
+    #[napi]
+    pub async fn runtime_stats(&self) -> RuntimeStats {
+        collect_runtime_stats()
+    }
+
+    /// Set maximum body size in bytes
+    #[napi]
+    pub async fn set_max_body_size(&self, max_bytes: u32) -> Result<()> {
+        self.state.max_body_size.store(max_bytes, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Set per-content-type body limits (e.g. 100KB for
+    /// `application/json`, 50MB for `multipart/`)
+    ///
+    /// The first rule matching a request's Content-Type wins; a type
+    /// ending in `/` matches every subtype, and parameters like
+    /// `charset` are ignored. Requests matching no rule keep the
+    /// global `max_body_size`. Pass an empty list to clear.
+    #[napi]
+    pub async fn set_body_limits(&self, limits: Vec<BodyLimit>) -> Result<()> {
+        let rules: Vec<gust_core::pure::BodyLimitRule> = limits
+            .into_iter()
+            .map(|l| gust_core::pure::BodyLimitRule {
+                content_type: l.content_type,
+                max_bytes: l.max_bytes as u64,
+            })
+            .collect();
+        self.state.body_limits.store(Arc::new(rules));
+        Ok(())
+    }
+
+    /// Set keep-alive timeout in milliseconds
+    #[napi]
+    pub async fn set_keep_alive_timeout(&self, timeout_ms: u32) -> Result<()> {
+        self.state.keep_alive_timeout_ms.store(timeout_ms, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Set maximum header size in bytes
+    #[napi]
+    pub async fn set_max_header_size(&self, max_bytes: u32) -> Result<()> {
+        self.state.max_header_size.store(max_bytes, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Enable compression middleware
+    #[napi]
+    pub async fn enable_compression(&self, config: CompressionConfig) -> Result<()> {
+        *self.state.compression.write().await = Some(config);
+        Ok(())
+    }
+
+    /// Set the number of accept workers (0 = one per CPU core)
+    ///
+    /// Each worker binds its own SO_REUSEPORT listener so the kernel
+    /// load-balances incoming connections across accept loops. Takes
+    /// effect on the next serve() call.
+    #[napi]
+    pub fn set_workers(&self, workers: u32) {
+        self.state.accept_workers.store(workers, Ordering::Relaxed);
+    }
+
+    /// Enable TLS/HTTPS
+    #[napi]
+    pub async fn enable_tls(&self, config: TlsConfig) -> Result<()> {
+        *self.state.tls_config.write().await = Some(config);
+        Ok(())
+    }
+
+    /// Enable HTTP/2
+    #[napi]
+    pub async fn enable_http2(&self) -> Result<()> {
+        self.state.http2_enabled.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Enable CORS middleware
+    #[napi]
+    pub async fn enable_cors(&self, config: CorsConfig) -> Result<()> {
+        self.register_middleware("cors", Arc::new(build_cors_middleware(config)))
+            .await;
+        Ok(())
+    }
+
+    /// Enable rate limiting middleware
+    #[napi]
+    pub async fn enable_rate_limit(&self, config: RateLimitConfig) -> Result<()> {
+        self.register_middleware("rateLimit", Arc::new(build_rate_limit_middleware(config)))
+            .await;
+        Ok(())
+    }
+
+    /// Enable priority-aware admission control middleware
+    ///
+    /// Requests get a priority from route rules or the priority
+    /// header; once concurrency or latency targets are breached,
+    /// low-priority requests are shed with 503 + Retry-After before
+    /// handler dispatch. Counters are exposed via admissionStats().
+    #[napi]
+    pub async fn enable_admission(&self, config: AdmissionConfig) -> Result<()> {
+        use gust_core::middleware::admission::{
+            Admission, AdmissionConfig as CoreConfig, Priority,
+        };
+
+        let mut core_config = CoreConfig::new();
+        if let Some(max) = config.max_concurrency {
+            core_config = core_config.max_concurrency(max as u64);
+        }
+        if let Some(target) = config.target_latency_ms {
+            core_config = core_config.target_latency(Duration::from_millis(target as u64));
+        }
+        if let Some(interval) = config.interval_ms {
+            core_config = core_config.interval(Duration::from_millis(interval as u64));
+        }
+        if let Some(secs) = config.retry_after_seconds {
+            core_config = core_config.retry_after_secs(secs);
+        }
+        if let Some(header) = config.header {
+            core_config = core_config.header(header);
+        }
+        if let Some(routes) = config.routes {
+            for (prefix, priority) in routes {
+                let priority = Priority::parse(&priority).ok_or_else(|| {
+                    Error::from_reason(format!(
+                        "Unknown priority '{}' for route '{}' (expected 'low', 'normal', or 'high')",
+                        priority, prefix
+                    ))
+                })?;
+                core_config = core_config.route(prefix, priority);
+            }
+        }
+
+        let admission = Arc::new(Admission::new(core_config));
+        self.register_middleware("admission", Arc::clone(&admission) as Arc<dyn Middleware>)
+            .await;
+        *self.state.admission.write().await = Some(admission);
+        Ok(())
+    }
+
+    /// Admission control counters; zeros until enableAdmission()
+    #[napi]
+    pub async fn admission_stats(&self) -> AdmissionStats {
+        match self.state.admission.read().await.as_ref() {
+            Some(admission) => {
+                let stats = admission.stats();
+                AdmissionStats {
+                    admitted: stats.admitted as i64,
+                    shed: stats.shed as i64,
+                    in_flight: stats.in_flight as i64,
+                    latency_breached: stats.latency_breached,
+                }
+            }
+            None => AdmissionStats {
+                admitted: 0,
+                shed: 0,
+                in_flight: 0,
+                latency_breached: false,
+            },
+        }
+    }
+
+    /// Enable the adaptive concurrency limiter middleware
+    ///
+    /// Probes for the latency-optimal in-flight limit per route:
+    /// the limit grows additively while latency stays near the
+    /// observed no-load baseline and shrinks multiplicatively when
+    /// queueing builds (AIMD/Vegas). Excess load is shed with 503 +
+    /// Retry-After. Gauges are exposed via adaptiveStats().
+    #[napi]
+    pub async fn enable_adaptive_concurrency(&self, config: AdaptiveConfig) -> Result<()> {
+        use gust_core::middleware::adaptive::{
+            AdaptiveConcurrency, AdaptiveConfig as CoreConfig,
+        };
+
+        let mut core_config = CoreConfig::new();
+        if let Some(min) = config.min_limit {
+            core_config = core_config.min_limit(min as u64);
+        }
+        if let Some(max) = config.max_limit {
+            core_config = core_config.max_limit(max as u64);
+        }
+        if let Some(initial) = config.initial_limit {
+            core_config = core_config.initial_limit(initial as u64);
+        }
+        if config.alpha.is_some() || config.beta.is_some() {
+            let alpha = config.alpha.unwrap_or(core_config.alpha);
+            let beta = config.beta.unwrap_or(core_config.beta);
+            core_config = core_config.thresholds(alpha, beta);
+        }
+        if let Some(ratio) = config.backoff_ratio {
+            core_config = core_config.backoff_ratio(ratio);
+        }
+        if let Some(samples) = config.sample_window {
+            core_config = core_config.sample_window(samples as u64);
+        }
+        if let Some(per_route) = config.per_route {
+            core_config = core_config.per_route(per_route);
+        }
+        if let Some(secs) = config.retry_after_seconds {
+            core_config = core_config.retry_after_secs(secs);
+        }
+
+        let adaptive = Arc::new(AdaptiveConcurrency::new(core_config));
+        self.register_middleware("adaptive", Arc::clone(&adaptive) as Arc<dyn Middleware>)
+            .await;
+        *self.state.adaptive.write().await = Some(adaptive);
+        Ok(())
+    }
+
+    /// Adaptive limiter gauges per route; empty until
+    /// enableAdaptiveConcurrency()
+    #[napi]
+    pub async fn adaptive_stats(&self) -> Vec<AdaptiveRouteStats> {
+        match self.state.adaptive.read().await.as_ref() {
+            Some(adaptive) => adaptive
+                .stats()
+                .into_iter()
+                .map(|(route, stats)| AdaptiveRouteStats {
+                    route,
+                    limit: stats.limit as i64,
+                    in_flight: stats.in_flight as i64,
+                    shed: stats.shed as i64,
+                    min_latency_ms: stats.min_latency_ms,
+                    window_latency_ms: stats.window_latency_ms,
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Enable declarative request transformation middleware
+    ///
+    /// Header injection/removal, defaults, renames, path prefix
+    /// stripping, and query parameter appends run in Rust before
+    /// routing, replacing trivial JS middleware for these rewrites.
+    #[napi]
+    pub async fn enable_transform(&self, config: TransformConfig) -> Result<()> {
+        use gust_core::middleware::transform::{Transform, TransformConfig as CoreConfig};
+
+        let mut core_config = CoreConfig::new();
+        if let Some(prefix) = config.strip_prefix {
+            core_config = core_config.strip_prefix(prefix);
+        }
+        if let Some(headers) = config.set_headers {
+            for (name, value) in headers {
+                core_config = core_config.set_header(name, value);
+            }
+        }
+        if let Some(headers) = config.default_headers {
+            for (name, value) in headers {
+                core_config = core_config.default_header(name, value);
+            }
+        }
+        if let Some(headers) = config.remove_headers {
+            for name in headers {
+                core_config = core_config.remove_header(name);
+            }
+        }
+        if let Some(headers) = config.rename_headers {
+            for (from, to) in headers {
+                core_config = core_config.rename_header(from, to);
+            }
+        }
+        if let Some(params) = config.append_query {
+            for (name, value) in params {
+                core_config = core_config.append_query(name, value);
+            }
+        }
+
+        self.register_middleware("transform", Arc::new(Transform::new(core_config)))
+            .await;
+        Ok(())
+    }
+
+    /// Enable response transformation middleware
+    ///
+    /// Injects snippets into HTML bodies (analytics before </head>),
+    /// rewrites absolute upstream URLs for reverse-proxied apps, and
+    /// post-processes response headers. Compressed bodies are left
+    /// untouched, and content-length is corrected after rewrites.
+    #[napi]
+    pub async fn enable_response_transform(
+        &self,
+        config: ResponseTransformConfig,
+    ) -> Result<()> {
+        use gust_core::middleware::response_transform::{
+            ResponseTransform, ResponseTransformConfig as CoreConfig,
+        };
+
+        let mut core_config = CoreConfig::new();
+        if let Some(prefix) = config.content_type {
+            core_config = core_config.content_type(prefix);
+        }
+        if let Some(injections) = config.inject_before {
+            for (marker, snippet) in injections {
+                core_config = core_config.inject_before(marker, snippet);
+            }
+        }
+        if let Some(rewrites) = config.rewrite_urls {
+            for (from, to) in rewrites {
+                core_config = core_config.rewrite_url(from, to);
+            }
+        }
+        if let Some(headers) = config.set_headers {
+            for (name, value) in headers {
+                core_config = core_config.set_header(name, value);
+            }
+        }
+        if let Some(headers) = config.remove_headers {
+            for name in headers {
+                core_config = core_config.remove_header(name);
+            }
+        }
+
+        self.register_middleware(
+            "responseTransform",
+            Arc::new(ResponseTransform::new(core_config)),
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Enable security headers middleware
+    #[napi]
+    pub async fn enable_security(&self, config: SecurityConfig) -> Result<()> {
+        self.register_middleware("security", Arc::new(build_security_middleware(config)))
+            .await;
+        Ok(())
+    }
+
+    /// Enable request body digest verification middleware
+    ///
+    /// Verifies `Content-Digest` (RFC 9530) and legacy `Content-MD5`
+    /// headers against the body, rejecting mismatches with 400 before
+    /// the handler runs. Optionally emits a response digest.
+    #[napi]
+    pub async fn enable_digest(&self, config: DigestConfig) -> Result<()> {
+        use gust_core::middleware::digest::{Digest, DigestAlgorithm, DigestConfig as CoreConfig};
+
+        let emit_response = match config.emit_response.as_deref() {
+            Some("sha-256") => Some(DigestAlgorithm::Sha256),
+            Some("md5") => Some(DigestAlgorithm::Md5),
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "Unsupported digest algorithm: {}",
+                    other
+                )))
+            }
+            None => None,
+        };
+
+        let core_config = CoreConfig {
+            verify_request: config.verify_request.unwrap_or(true),
+            emit_response,
+        };
+
+        let digest = Digest::new(core_config);
+        self.register_middleware("digest", Arc::new(digest)).await;
+        Ok(())
+    }
+
+    /// Enable request tracing middleware
+    ///
+    /// Assigns each request an ID (echoed on the response header) and,
+    /// with propagateTrace on, W3C trace/span IDs. The IDs are injected
+    /// into handler context params as `_request_id`, `_trace_id`, and
+    /// `_span_id` so pino/winston logs can be correlated with spans.
+    #[napi]
+    pub async fn enable_tracing(&self, config: TracingConfig) -> Result<()> {
+        self.register_middleware("tracing", Arc::new(build_tracing_middleware(config)?))
+            .await;
+        Ok(())
+    }
+
+    /// Enable the in-memory response cache middleware
+    ///
+    /// Successful GET/HEAD responses are cached for the configured TTL.
+    /// Responses may carry a `Surrogate-Key` header (space-separated
+    /// tags) for tag-based invalidation via `cachePurge`.
+    #[napi]
+    pub async fn enable_cache(&self, config: Option<ResponseCacheConfig>) -> Result<()> {
+        use gust_core::middleware::{Cache, CacheConfig as CoreConfig};
+
+        let config = config.unwrap_or_default();
+        let mut core_config = CoreConfig::new();
+        if let Some(ttl) = config.ttl_seconds {
+            core_config = core_config.ttl_seconds(ttl as u64);
+        }
+        if let Some(max) = config.max_entries {
+            core_config = core_config.max_entries(max as usize);
+        }
+
+        if let Some(key) = build_cache_key_config(
+            &config.vary_headers,
+            &config.vary_cookies,
+            &config.query_params,
+        ) {
+            core_config = core_config.key(key);
+        }
+        for rule in config.key_rules.unwrap_or_default() {
+            let key = build_cache_key_config(
+                &rule.vary_headers,
+                &rule.vary_cookies,
+                &rule.query_params,
+            )
+            .unwrap_or_default();
+            core_config = core_config.route_key(rule.prefix, key);
+        }
+
+        let cache = Arc::new(Cache::new(core_config));
+        *self.state.response_cache.write().await = Some(cache.clone());
+        self.register_middleware("cache", cache as Arc<dyn Middleware>)
+            .await;
+        Ok(())
+    }
+
+    /// Purge cached responses by path or surrogate key tag
+    ///
+    /// Patterns starting with `/` purge by request path (a trailing `*`
+    /// purges a prefix); anything else purges by `Surrogate-Key` tag.
+    /// Returns the number of entries removed.
+    ///
+    /// @example
+    /// ```typescript
+    /// server.cachePurge('/users/123')   // one path
+    /// server.cachePurge('/users/*')     // wildcard
+    /// server.cachePurge('products')     // tag
+    /// ```
+    #[napi]
+    pub async fn cache_purge(&self, path_or_tag: String) -> Result<u32> {
+        let cache = self.state.response_cache.read().await.clone();
+        let cache = match cache {
+            Some(c) => c,
+            None => return Err(Error::from_reason("Response cache is not enabled")),
+        };
+
+        let removed = if path_or_tag.starts_with('/') {
+            cache.purge(&path_or_tag)
+        } else {
+            cache.purge_tag(&path_or_tag)
+        };
+        Ok(removed as u32)
+    }
+
+    /// Drop every cached response
+    #[napi]
+    pub async fn cache_clear(&self) -> Result<()> {
+        if let Some(cache) = self.state.response_cache.read().await.as_ref() {
+            cache.clear();
+        }
+        Ok(())
+    }
+
+    /// Hit/miss statistics per cache key shape
+    #[napi]
+    pub async fn cache_stats(&self) -> Result<Vec<CacheShapeStats>> {
+        let cache = self.state.response_cache.read().await.clone();
+        let cache = match cache {
+            Some(c) => c,
+            None => return Ok(Vec::new()),
+        };
+
+        Ok(cache
+            .stats()
+            .into_iter()
+            .map(|(shape, stats)| CacheShapeStats {
+                shape,
+                hits: stats.hits as i64,
+                misses: stats.misses as i64,
+                hit_ratio: stats.hit_ratio(),
+            })
+            .collect())
+    }
+
+    /// Enable edge cache headers middleware
+    ///
+    /// Sets Cache-Control, Surrogate-Control, and CDN-Cache-Control
+    /// from per-route policies and strips hop-by-hop headers.
+    #[napi]
+    pub async fn enable_edge_cache(&self, config: EdgeCacheConfig) -> Result<()> {
+        use gust_core::middleware::edge_cache::{EdgeCache, EdgeCacheConfig as CoreConfig};
+
+        let mut core_config = CoreConfig::new();
+        if let Some(rule) = config.default_policy {
+            core_config = core_config.default_policy(build_cache_policy(&rule));
+        }
+        for rule in config.routes.unwrap_or_default() {
+            let prefix = rule.prefix.clone().unwrap_or_else(|| "/".to_string());
+            core_config = core_config.route(prefix, build_cache_policy(&rule));
+        }
+        core_config.strip_hop_by_hop = config.strip_hop_by_hop.unwrap_or(true);
+
+        self.register_middleware("edgeCache", Arc::new(EdgeCache::new(core_config)))
+            .await;
+        Ok(())
+    }
+
+    /// Register a middleware under a stable name and swap in a fresh
+    /// chain: re-registering a name replaces it in place (keeping its
+    /// position), a new name appends. Readers keep the chain they
+    /// loaded, so in-flight requests finish on the old config.
+    async fn register_middleware(&self, name: &str, middleware: Arc<dyn Middleware>) {
+        let mut registry = self.state.middleware_registry.write().await;
+        if let Some(existing) = registry.iter_mut().find(|(n, _)| n == name) {
+            existing.1 = middleware;
+        } else {
+            registry.push((name.to_string(), middleware));
+        }
+        rebuild_middleware_chain(&self.state, &registry);
+    }
+
+    /// Replace a middleware's configuration without a restart
+    ///
+    /// Rebuilds the middleware from the matching `config` field and
+    /// atomically swaps the chain, so the new config applies to the
+    /// next request with no downtime. Updatable names are "cors",
+    /// "rateLimit", "security", and "tracing"; stateful middleware
+    /// (admission, cache, …) must be re-enabled instead so counters
+    /// and entries aren't silently discarded.
+    #[napi]
+    pub async fn update_middleware(&self, name: String, config: MiddlewareUpdate) -> Result<()> {
+        let missing = |name: &str, field: &str| {
+            Error::from_reason(format!(
+                "updateMiddleware('{}') requires the '{}' config field",
+                name, field
+            ))
+        };
+        let middleware: Arc<dyn Middleware> = match name.as_str() {
+            "cors" => Arc::new(build_cors_middleware(
+                config.cors.ok_or_else(|| missing(&name, "cors"))?,
+            )),
+            "rateLimit" => Arc::new(build_rate_limit_middleware(
+                config.rate_limit.ok_or_else(|| missing(&name, "rateLimit"))?,
+            )),
+            "security" => Arc::new(build_security_middleware(
+                config.security.ok_or_else(|| missing(&name, "security"))?,
+            )),
+            "tracing" => Arc::new(build_tracing_middleware(
+                config.tracing.ok_or_else(|| missing(&name, "tracing"))?,
+            )?),
+            other => {
+                return Err(Error::from_reason(format!(
+                    "Middleware '{}' is not updatable (expected 'cors', 'rateLimit', 'security', or 'tracing')",
+                    other
+                )))
+            }
+        };
+        self.register_middleware(&name, middleware).await;
+        Ok(())
+    }
+
+    /// Remove a middleware from the chain by name
+    ///
+    /// Returns whether anything was removed. The remaining middleware
+    /// keep their order, and handles tied to the removed middleware
+    /// (response cache, admission/adaptive stats) are cleared.
+    #[napi]
+    pub async fn remove_middleware(&self, name: String) -> Result<bool> {
+        let mut registry = self.state.middleware_registry.write().await;
+        let before = registry.len();
+        registry.retain(|(n, _)| n != &name);
+        let removed = registry.len() < before;
+        if removed {
+            rebuild_middleware_chain(&self.state, &registry);
+        }
+        drop(registry);
+
+        if removed {
+            match name.as_str() {
+                "cache" => *self.state.response_cache.write().await = None,
+                "admission" => *self.state.admission.write().await = None,
+                "adaptive" => *self.state.adaptive.write().await = None,
+                _ => {}
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Add a static route (pre-rendered response)
+    #[napi]
+    pub async fn add_static_route(
+        &self,
+        method: String,
+        path: String,
+        status: u32,
+        content_type: String,
+        body: String,
+    ) -> Result<()> {
+        // Generate unique handler ID
+        let handler_id = self.state.next_handler_id.fetch_add(1, Ordering::SeqCst);
+
+        if !valid_header_value(&content_type) {
+            return Err(Error::from_reason(format!(
+                "Invalid content type {:?}",
+                content_type
+            )));
+        }
+
+        let etag = rust_body_etag(body.as_bytes());
+        let headers = vec![
+            ("content-type".to_string(), content_type.clone()),
+            ("etag".to_string(), etag.clone()),
+        ];
+
+        // Pre-render the HTTP/1.1 response (raw engine fast path)
+        let mut builder = ResponseBuilder::new(StatusCode(status as u16));
+        for (name, value) in &headers {
+            builder = builder.header(name, value);
+        }
+        let res = builder.body(body.clone()).build();
+        let response_bytes = res.to_http1_bytes();
+
+        let static_response = StaticResponse {
+            status: status as u16,
+            headers,
+            body: Bytes::from(body),
+            etag,
+            bytes: response_bytes,
+        };
+
+        // Store response in HashMap
+        self.state
+            .static_responses
+            .write()
+            .await
+            .insert(handler_id, static_response);
+
+        // Insert route into router
+        self.state
+            .router
+            .write()
+            .await
+            .insert(&method, &path, handler_id);
+
+        Ok(())
+    }
+
+    /// Register well-known route helpers as pre-rendered static routes
+    ///
+    /// robots.txt, favicon.ico, security.txt, and the change-password
+    /// redirect are served from the static fast path (raw engine
+    /// included) without ever invoking JS.
+    #[napi]
+    pub async fn set_well_known(&self, config: WellKnownConfig) -> Result<()> {
+        let cache_control = config
+            .cache_control
+            .unwrap_or_else(|| "public, max-age=86400".to_string());
+        if !valid_header_value(&cache_control) {
+            return Err(Error::from_reason(format!(
+                "Invalid cache control {:?}",
+                cache_control
+            )));
+        }
+
+        type Entry = (&'static str, u16, Vec<(String, String)>, Bytes);
+        let mut entries: Vec<Entry> = Vec::new();
+        if let Some(robots) = config.robots {
+            entries.push((
+                "/robots.txt",
+                200,
+                vec![(
+                    "content-type".to_string(),
+                    "text/plain; charset=utf-8".to_string(),
+                )],
+                Bytes::from(robots),
+            ));
+        }
+        if let Some(favicon) = config.favicon {
+            entries.push((
+                "/favicon.ico",
+                200,
+                vec![("content-type".to_string(), "image/x-icon".to_string())],
+                Bytes::from(favicon.to_vec()),
+            ));
+        }
+        if let Some(security) = config.security_txt {
+            entries.push((
+                "/.well-known/security.txt",
+                200,
+                vec![(
+                    "content-type".to_string(),
+                    "text/plain; charset=utf-8".to_string(),
+                )],
+                Bytes::from(security),
+            ));
+        }
+        if let Some(url) = config.change_password_url {
+            if !valid_header_value(&url) {
+                return Err(Error::from_reason(format!("Invalid redirect url {:?}", url)));
+            }
+            entries.push((
+                "/.well-known/change-password",
+                302,
+                vec![("location".to_string(), url)],
+                Bytes::new(),
+            ));
+        }
+
+        for (path, status, mut headers, body) in entries {
+            headers.push(("cache-control".to_string(), cache_control.clone()));
+            let etag = rust_body_etag(&body);
+            if status == 200 {
+                headers.push(("etag".to_string(), etag.clone()));
+            }
+
+            let mut builder = ResponseBuilder::new(StatusCode(status));
+            for (name, value) in &headers {
+                builder = builder.header(name, value);
+            }
+            let res = builder.body(body.clone()).build();
+            let response_bytes = res.to_http1_bytes();
+
+            let handler_id = self.state.next_handler_id.fetch_add(1, Ordering::SeqCst);
+            self.state.static_responses.write().await.insert(
+                handler_id,
+                StaticResponse {
+                    status,
+                    headers,
+                    body,
+                    etag,
+                    bytes: response_bytes,
+                },
+            );
+            self.state
+                .router
+                .write()
+                .await
+                .insert("GET", path, handler_id);
+        }
+
+        Ok(())
+    }
+
+    /// Add a dynamic route with JS handler callback
+    ///
+    /// The handler will be called with RequestContext and should return ResponseData (or Promise<ResponseData>)
+    #[napi]
+    pub fn add_dynamic_route(
+        &self,
+        method: String,
+        path: String,
+        handler: JsFunction,
+    ) -> Result<()> {
+        // Generate unique handler ID
+        let handler_id = self.state.next_handler_id.fetch_add(1, Ordering::SeqCst);
+
+        // Create threadsafe function that can be called from any thread
+        let tsfn: ThreadsafeFunction<RequestContext, ErrorStrategy::Fatal> = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+
+        let dynamic_handler = DynamicHandler {
+            callback: tsfn,
+            #[cfg(feature = "bun-direct")]
+            sync: false,
+        };
+
+        // Store handler in HashMap
+        self.state.dynamic_handlers.blocking_write().insert(handler_id, dynamic_handler);
+
+        // Insert route into router
+        self.state.router.blocking_write().insert(&method, &path, handler_id);
+
+        Ok(())
+    }
+
+    /// Set fallback handler for unmatched routes
+    #[napi]
+    pub fn set_fallback(
+        &self,
+        handler: JsFunction,
+    ) -> Result<()> {
+        let tsfn: ThreadsafeFunction<RequestContext, ErrorStrategy::Fatal> = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+
+        let handler = DynamicHandler {
+            callback: tsfn,
+            #[cfg(feature = "bun-direct")]
+            sync: false,
+        };
+        *self.state.fallback_handler.blocking_write() = Some(handler);
+        Ok(())
+    }
+
+    // ========================================================================
+    // GustApp Integration (Route Registration Pattern)
+    // ========================================================================
+
+    /// Register routes from GustApp manifest
+    ///
+    /// This enables Rust-side routing with handler ID dispatch.
+    /// Routes are registered in the Rust Radix Trie router.
+    /// When a request matches, `invoke_handler(handler_id, ctx)` is called.
+    ///
+    /// @example
+    /// ```typescript
+    /// const app = createApp({ routes: [...] })
+    /// server.registerRoutes(app.manifest)
+    /// server.setInvokeHandler(app.invokeHandler)
+    /// ```
+    #[napi]
+    pub async fn register_routes(&self, manifest: RouteManifest) -> Result<()> {
+        // Build new router - this happens at startup, not on hot path
+        let mut new_router = Router::new();
+
+        for entry in manifest.routes {
+            // Validated insert: duplicate params and oversized paths
+            // are registration errors, not silent shadowing
+            new_router
+                .try_insert(&entry.method, &entry.path, entry.handler_id)
+                .map_err(|e| {
+                    Error::from_reason(format!(
+                        "Invalid route {} {}: {}",
+                        entry.method, entry.path, e
+                    ))
+                })?;
+        }
+
+        // Atomic swap with ArcSwap - lock-free on read path
+        self.state.app_routes.store(Arc::new(new_router));
+        Ok(())
+    }
+
+    /// Set the invoke handler callback from GustApp
+    ///
+    /// This callback is called when a route matches with:
+    /// - `handlerId`: The handler ID from the route manifest
+    /// - `ctx`: The native handler context with parsed request data
+    ///
+    /// The callback should return a ResponseData (or Promise<ResponseData>).
+    ///
+    /// @example
+    /// ```typescript
+    /// const app = createApp({ routes: [...] })
+    /// server.setInvokeHandler(app.invokeHandler)
+    /// ```
+    #[napi]
+    pub fn set_invoke_handler(&self, handler: JsFunction) -> Result<()> {
+        // Create threadsafe function that accepts (handlerId, context) tuple
+        let tsfn: InvokeHandlerCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                // ctx.value is (u32, NativeHandlerContext)
+                // We need to convert this to JS arguments
+                Ok(vec![ctx.value])
+            })?;
+
+        let invoke = InvokeHandler { callback: tsfn };
+        // Use ArcSwap for lock-free atomic swap
+        self.state.invoke_handler.store(Arc::new(Some(invoke)));
+        Ok(())
+    }
+
+    /// Register an invoke handler running on a worker thread
+    ///
+    /// Call from inside each Node worker thread with that worker's
+    /// invokeHandler: the threadsafe function stays bound to the
+    /// worker's event loop, so CPU-heavy handlers run there in
+    /// parallel instead of queueing on the main thread. With any
+    /// workers registered they take over dispatch from the main-thread
+    /// invoke handler — spread round-robin, or pinned by route after
+    /// setWorkerAffinity("route"). Returns the worker index.
+    #[napi]
+    pub fn add_worker_handler(&self, handler: JsFunction) -> Result<u32> {
+        let tsfn: InvokeHandlerCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+
+        // Copy-on-write append so the dispatch path stays lock-free
+        let current = self.state.workers.load();
+        let mut workers = (**current).clone();
+        workers.push(InvokeHandler { callback: tsfn });
+        let index = workers.len() - 1;
+        self.state.workers.store(Arc::new(workers));
+        Ok(index as u32)
+    }
+
+    /// How requests are spread over worker handlers
+    ///
+    /// "round-robin" (default) alternates workers; "route" pins each
+    /// route to one worker by path hash, keeping its JIT and caches
+    /// warm for that route's handlers.
+    #[napi]
+    pub fn set_worker_affinity(&self, mode: String) -> Result<()> {
+        let route_affinity = match mode.as_str() {
+            "round-robin" => false,
+            "route" => true,
+            other => {
+                return Err(Error::from_reason(format!(
+                    "Unknown worker affinity '{}' (expected 'round-robin' or 'route')",
+                    other
+                )))
+            }
+        };
+        self.state
+            .worker_route_affinity
+            .store(route_affinity, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Number of registered worker handlers
+    #[napi]
+    pub fn worker_count(&self) -> u32 {
+        self.state.workers.load().len() as u32
+    }
+
+    /// Add a GraphQL-over-HTTP route
+    ///
+    /// The Rust side handles GET/POST transport details (query param
+    /// extraction, JSON body parsing, batching, APQ hash lookup, status
+    /// codes) and invokes the JS resolver registered under
+    /// `executeHandlerId` with the normalized request JSON as the body.
+    /// Optionally serves GraphiQL on GET requests that accept HTML.
+    ///
+    /// @example
+    /// ```typescript
+    /// server.addGraphQLRoute('/graphql', { executeHandlerId: 1, graphiql: true })
+    /// ```
+    #[napi]
+    pub async fn add_graphql_route(&self, path: String, options: GraphQLRouteOptions) -> Result<()> {
+        let route = GraphQLRoute {
+            execute_handler_id: options.execute_handler_id,
+            graphiql: options.graphiql.unwrap_or(false),
+        };
+        self.state.graphql_routes.write().await.insert(path, route);
+        Ok(())
+    }
+
+    /// Enable a JSON-RPC 2.0 dispatcher at the given path
+    ///
+    /// Envelope parsing, validation, batching, and notification handling
+    /// happen in Rust; registered methods dispatch to JS handlers by ID.
+    #[napi]
+    pub async fn enable_json_rpc(&self, path: String) -> Result<()> {
+        self.state
+            .jsonrpc_routes
+            .write()
+            .await
+            .entry(path)
+            .or_default();
+        Ok(())
+    }
+
+    /// Register a JSON-RPC method on a dispatcher path
+    ///
+    /// The JS handler receives the normalized request envelope as the body
+    /// and must return the result as JSON (non-2xx status maps to a
+    /// JSON-RPC internal error).
+    #[napi]
+    pub async fn add_json_rpc_method(
+        &self,
+        path: String,
+        method: String,
+        handler_id: u32,
+    ) -> Result<()> {
+        self.state
+            .jsonrpc_routes
+            .write()
+            .await
+            .entry(path)
+            .or_default()
+            .methods
+            .insert(method, handler_id);
+        Ok(())
+    }
+
+    /// Enable a tus.io 1.0 resumable upload endpoint at the given base path
+    ///
+    /// Creation, offset checks, chunk appends with checksums, expiration,
+    /// and termination are handled entirely in Rust. Chunks are stored on
+    /// disk when `directory` is set, otherwise in memory.
+    ///
+    /// @example
+    /// ```typescript
+    /// server.enableTus('/uploads', { directory: './uploads', maxSize: 104857600 })
+    /// ```
+    #[napi]
+    pub async fn enable_tus(&self, path: String, options: Option<TusOptions>) -> Result<()> {
+        use gust_core::handlers::{Tus, TusConfig, TusDiskStore, TusMemoryStore};
+
+        let options = options.unwrap_or(TusOptions {
+            directory: None,
+            max_size: None,
+            expiration_seconds: None,
+        });
+
+        let mut config = TusConfig::new();
+        if let Some(max) = options.max_size {
+            config = config.max_size(max.max(0) as u64);
+        }
+        if let Some(ttl) = options.expiration_seconds {
+            config = config.expiration(ttl.max(0) as u64);
+        }
+
+        let handler = match options.directory {
+            Some(dir) => {
+                let store = TusDiskStore::new(dir)
+                    .map_err(|e| Error::from_reason(format!("Failed to open tus directory: {}", e)))?;
+                Tus::new(store, config)
+            }
+            None => Tus::new(TusMemoryStore::new(), config),
+        };
+
+        self.state
+            .tus_routes
+            .write()
+            .await
+            .insert(path, Arc::new(handler));
+        Ok(())
+    }
+
+    /// Mount embedded static assets at a path prefix
+    ///
+    /// The manifest is typically generated at build time (e.g. by reading
+    /// a directory into buffers during bundling); assets are served from
+    /// memory with zero filesystem access.
+    ///
+    /// @example
+    /// ```typescript
+    /// server.serveEmbedded('/assets', [
+    ///   { path: 'app.js', data: fs.readFileSync('dist/app.js') },
+    /// ])
+    /// ```
+    #[napi]
+    pub async fn serve_embedded(
+        &self,
+        prefix: String,
+        assets: Vec<EmbeddedAsset>,
+        options: Option<EmbeddedAssetOptions>,
+    ) -> Result<()> {
+        use gust_core::handlers::MemorySource;
+        use gust_core::{StaticFileConfig, StaticFiles};
+
+        let mut source = MemorySource::new();
+        for asset in assets {
+            source.insert(asset.path, asset.data.to_vec());
+        }
+
+        let mut config = StaticFileConfig::default();
+        if let Some(options) = options {
+            if let Some(index) = options.index {
+                config = config.index(index);
+            }
+            if let Some(max_age) = options.max_age {
+                config = config.max_age(max_age);
+            }
+            if let Some(fallback) = options.fallback {
+                config = config.fallback(fallback);
+            }
+        }
+
+        let prefix = prefix.trim_end_matches('/').to_string();
+        self.state
+            .embedded_routes
+            .write()
+            .await
+            .insert(prefix, Arc::new(StaticFiles::with_source(config, source)));
+        Ok(())
+    }
+
+    /// Register a server-managed WebSocket route
+    ///
+    /// The Rust side performs the 101 upgrade on hyper's upgraded
+    /// connection and runs the frame loop: ping/pong, keepalive
+    /// deadlines, and the close handshake never reach JS. The handler
+    /// is called with `{connectionId, path, remoteAddr, event, text,
+    /// data, code, reason}` for "open", "message", and "close" events
+    /// and may return (a promise of) `{text, data, close}` to send
+    /// back or start closing; wsSendText/wsSendBinary/wsClose push
+    /// from outside the handler.
+    ///
+    /// @example
+    /// ```typescript
+    /// server.onWebSocket('/ws', (event) => {
+    ///   if (event.event === 'message') return { text: event.text }
+    /// }, { pingIntervalMs: 30000 })
+    /// ```
+    #[napi]
+    pub fn on_web_socket(
+        &self,
+        path: String,
+        handler: JsFunction,
+        options: Option<WebSocketRouteOptions>,
+    ) -> Result<()> {
+        let callback: WsEventCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+        let route = Arc::new(WsRoute {
+            callback,
+            options: options.unwrap_or_default(),
+        });
+        self.state.ws_routes.blocking_write().insert(path, route);
+        Ok(())
+    }
+
+    /// Send a text message on a live WebSocket connection
+    ///
+    /// Returns false when the connection is gone or already closing.
+    #[napi]
+    pub async fn ws_send_text(&self, connection_id: i64, text: String) -> bool {
+        self.ws_command(connection_id, WsCommand::Text(text)).await
+    }
+
+    /// Send a binary message on a live WebSocket connection
+    #[napi]
+    pub async fn ws_send_binary(&self, connection_id: i64, data: Buffer) -> bool {
+        self.ws_command(connection_id, WsCommand::Binary(data.to_vec()))
+            .await
+    }
+
+    /// Start the close handshake on a live WebSocket connection
+    /// (default code 1000)
+    #[napi]
+    pub async fn ws_close(
+        &self,
+        connection_id: i64,
+        code: Option<u32>,
+        reason: Option<String>,
+    ) -> bool {
+        self.ws_command(
+            connection_id,
+            WsCommand::Close(code.unwrap_or(1000) as u16, reason.unwrap_or_default()),
+        )
+        .await
+    }
+
+    /// Number of live server-managed WebSocket connections
+    #[napi]
+    pub async fn ws_connection_count(&self) -> u32 {
+        self.state.ws_connections.read().await.len() as u32
+    }
+
+    /// Queue a command for a live connection's frame loop
+    async fn ws_command(&self, connection_id: i64, command: WsCommand) -> bool {
+        let connections = self.state.ws_connections.read().await;
+        match connections.get(&connection_id) {
+            Some(sender) => sender.send(command).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Check if app routes pattern is configured
+    /// Returns true if invoke_handler is set
+    #[napi]
+    pub fn has_app_routes(&self) -> bool {
+        // Lock-free read with ArcSwap
+        self.state.invoke_handler.load().is_some()
+    }
+
+    /// Clear all app routes (for hot reload)
+    #[napi]
+    pub fn clear_app_routes(&self) -> Result<()> {
+        // Atomic swap with ArcSwap - lock-free
+        self.state.app_routes.store(Arc::new(Router::new()));
+        Ok(())
+    }
+
+    /// Start the server (non-blocking)
+    ///
+    /// Pass port 0 to bind an OS-assigned ephemeral port; read the
+    /// actual port back with `address()`.
+    #[napi]
+    pub async fn serve(&self, port: u32) -> Result<()> {
+        self.serve_with_hostname(port, "0.0.0.0".to_string()).await
+    }
+
+    /// The address the server is actually bound to, or None before
+    /// serve() / after shutdown
+    ///
+    /// Essential with `serve(0)`: the OS assigns the port, and test
+    /// harnesses read it back here.
+    #[napi]
+    pub async fn address(&self) -> Option<ServerAddress> {
+        self.bound_addr.read().await.map(|addr| ServerAddress {
+            host: addr.ip().to_string(),
+            port: addr.port() as u32,
+            family: if addr.is_ipv4() { "IPv4" } else { "IPv6" }.to_string(),
+        })
+    }
+
+    /// Start the server with custom hostname (non-blocking)
+    #[napi]
+    pub async fn serve_with_hostname(&self, port: u32, hostname: String) -> Result<()> {
+        use std::net::SocketAddr;
+        use tokio::net::TcpListener; // from gust_core::tokio
+
+        let addr: SocketAddr = format!("{}:{}", hostname, port)
+            .parse()
+            .map_err(|e| Error::from_reason(format!("Invalid address: {}", e)))?;
+
+        let state = self.state.clone();
+        let tls_config = state.tls_config.read().await.clone();
+        let http2_enabled = state.http2_enabled.load(Ordering::Relaxed);
+        let raw_engine = state.raw_engine.load(Ordering::Relaxed);
+
+        if raw_engine && tls_config.is_some() {
+            return Err(Error::from_reason(
+                "Raw engine does not support TLS; use the hyper engine".to_string(),
+            ));
+        }
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let workers = match state.accept_workers.load(Ordering::Relaxed) {
+            0 => num_cpus::get(),
+            n => n as usize,
+        };
+        let listeners = if workers <= 1 {
+            vec![TcpListener::bind(addr)
+                .await
+                .map_err(|e| Error::from_reason(format!("Bind error: {}", e)))?]
+        } else {
+            bind_worker_listeners(addr, workers)
+                .map_err(|e| Error::from_reason(format!("Bind error: {}", e)))?
+        };
+
+        // Record the actual bound address: with port 0 the OS picks an
+        // ephemeral port, reported back via address()
+        let local_addr = listeners[0]
+            .local_addr()
+            .map_err(|e| Error::from_reason(format!("Bind error: {}", e)))?;
+        *self.bound_addr.write().await = Some(local_addr);
+
+        // One shutdown receiver per accept loop, fed from the single
+        // shutdown() channel
+        let worker_rxs = if listeners.len() == 1 {
+            vec![shutdown_rx]
+        } else {
+            let (txs, rxs): (Vec<_>, Vec<_>) = listeners
+                .iter()
+                .map(|_| tokio::sync::oneshot::channel())
+                .unzip();
+            tokio::spawn(async move {
+                let _ = shutdown_rx.await;
+                for tx in txs {
+                    let _ = tx.send(());
+                }
+            });
+            rxs
+        };
+
+        // Spawn one server task per listener
+        #[allow(unused_variables)]
+        if let Some(tls) = tls_config {
+            // TLS server
+            #[cfg(feature = "tls")]
+            {
+                for (listener, rx) in listeners.into_iter().zip(worker_rxs) {
+                    self.serve_tls(listener, tls.clone(), http2_enabled, state.clone(), rx)
+                        .await?;
+                }
+            }
+            #[cfg(not(feature = "tls"))]
+            {
+                let _ = (tls, http2_enabled); // Suppress unused variable warning
+                return Err(Error::from_reason("TLS support not enabled. Compile with 'tls' feature.".to_string()));
+            }
+        } else if raw_engine {
+            // Raw HTTP/1.1 engine (benchmark/plaintext workloads)
+            for (listener, rx) in listeners.into_iter().zip(worker_rxs) {
+                self.serve_raw(listener, state.clone(), rx).await?;
+            }
+        } else {
+            // Plain HTTP server
+            for (listener, rx) in listeners.into_iter().zip(worker_rxs) {
+                self.serve_http(listener, http2_enabled, state.clone(), rx)
+                    .await?;
+            }
         }
+
+        Ok(())
+    }
+
+    /// Serve HTTP (non-TLS) connections
+    #[allow(unused_variables)]
+    async fn serve_http(
+        &self,
+        listener: tokio::net::TcpListener,
+        http2_enabled: bool, // Reserved for future h2c support
+        state: Arc<ServerState>,
+        shutdown_rx: tokio::sync::oneshot::Receiver<()>,
+    ) -> Result<()> {
+        // Use re-exports from gust_core
+        use hyper::server::conn::http1;
+        use hyper::service::service_fn;
+        use hyper_util::rt::{TokioIo, TokioTimer};
+
+        let tracker = self.connection_tracker.clone();
+
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = async {
+                    loop {
+                        let (stream, peer) = match listener.accept().await {
+                            Ok(conn) => conn,
+                            Err(_) => continue,
+                        };
+
+                        // Reject new connections during shutdown
+                        if tracker.is_shutting_down() {
+                            drop(stream);
+                            continue;
+                        }
+
+                        let state = state.clone();
+                        let conn_tracker = tracker.clone();
+                        conn_tracker.increment();
+
+                        tokio::spawn(async move {
+                            let hooks = Arc::clone(&state.hooks);
+                            if hooks.observes_connections() {
+                                hooks.emit_connection(&gust_core::ConnectionEvent {
+                                    remote_addr: peer.to_string(),
+                                    phase: gust_core::ConnectionPhase::Opened,
+                                });
+                            }
+
+                            let io = TokioIo::new(stream);
+                            // Header-phase deadline, enforced by hyper
+                            // while reading the request head
+                            let header_ms = state
+                                .timeouts
+                                .load()
+                                .phase_ms(None, gust_core::TimeoutPhase::Header);
+                            let conn_stats = Arc::new(CoreConnectionStats::new());
+                            let service_stats = conn_stats.clone();
+                            let service_tracker = conn_tracker.clone();
+                            let service = service_fn(move |req| {
+                                let state = state.clone();
+                                let stats = service_stats.clone();
+                                let tracker = service_tracker.clone();
+                                async move {
+                                    let depth = stats.begin_request();
+                                    let res = if depth >= tracker.max_pipeline_depth() {
+                                        tracker.record_pipelined_rejected();
+                                        eprintln!(
+                                            "Rejected pipelined request from {} (depth {})",
+                                            peer, depth
+                                        );
+                                        Ok(pipeline_reject_response())
+                                    } else {
+                                        handle_request_limited(state, req, peer, "http").await
+                                    };
+                                    stats.end_request();
+                                    res
+                                }
+                            });
+
+                            // HTTP/2 over clear text (h2c) is less common, use HTTP/1.1 by default
+                            let mut conn_builder = http1::Builder::new();
+                            if header_ms > 0 {
+                                conn_builder
+                                    .timer(TokioTimer::new())
+                                    .header_read_timeout(Duration::from_millis(header_ms as u64));
+                            }
+                            // with_upgrades: hands the socket to the
+                            // WebSocket frame loop after a 101
+                            if let Err(e) = conn_builder
+                                .serve_connection(io, service)
+                                .with_upgrades()
+                                .await
+                            {
+                                // Only log if not a normal connection close
+                                if !e.to_string().contains("connection closed") {
+                                    eprintln!("Connection error: {}", e);
+                                }
+                            }
+
+                            conn_tracker.record_connection_closed(conn_stats.requests());
+                            conn_tracker.decrement();
+                            if hooks.observes_connections() {
+                                hooks.emit_connection(&gust_core::ConnectionEvent {
+                                    remote_addr: peer.to_string(),
+                                    phase: gust_core::ConnectionPhase::Closed,
+                                });
+                            }
+                        });
+                    }
+                } => {}
+                _ = shutdown_rx => {
+                    // Signal shutdown - new connections will be rejected
+                    tracker.start_shutdown();
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Serve connections with the raw HTTP/1.1 engine (no hyper)
+    async fn serve_raw(
+        &self,
+        listener: tokio::net::TcpListener,
+        state: Arc<ServerState>,
+        shutdown_rx: tokio::sync::oneshot::Receiver<()>,
+    ) -> Result<()> {
+        use gust_core::raw_http1::{serve_with, StaticLookup};
+
+        let tracker = self.connection_tracker.clone();
+
+        // Pre-rendered static routes are written to the socket verbatim;
+        // try_read so a concurrent route registration falls through to
+        // the dynamic handler instead of blocking the hot path
+        let lookup_state = state.clone();
+        let static_lookup: StaticLookup = Arc::new(move |method, path| {
+            let router = lookup_state.router.try_read().ok()?;
+            let matched = router.find(&method.to_string(), path)?;
+            let responses = lookup_state.static_responses.try_read().ok()?;
+            responses.get(&matched.handler_id).map(|r| r.bytes.clone())
+        });
+
+        let handler_state = state;
+        let handler: gust_core::DynamicHandler = Arc::new(move |req| {
+            let state = handler_state.clone();
+            Box::pin(async move {
+                if !state.hooks.observes_requests() {
+                    return handle_raw_request(state, req).await;
+                }
+
+                let hooks = Arc::clone(&state.hooks);
+                let method = req.method.to_string();
+                let path = req.path.clone();
+                hooks.emit_request(&gust_core::RequestEvent {
+                    method: method.clone(),
+                    path: path.clone(),
+                });
+
+                let start = std::time::Instant::now();
+                let response = handle_raw_request(state, req).await;
+                let status = response.status.0;
+                if status >= 500 {
+                    hooks.emit_error(&gust_core::ErrorEvent {
+                        method: method.clone(),
+                        path: path.clone(),
+                        status,
+                        message: String::from_utf8_lossy(&response.body).into_owned(),
+                    });
+                }
+                hooks.emit_response(&gust_core::ResponseEvent {
+                    method,
+                    path,
+                    status,
+                    duration: start.elapsed(),
+                });
+                response
+            })
+        });
+
+        tokio::spawn(async move {
+            tokio::select! {
+                result = serve_with(listener, static_lookup, handler, tracker.clone()) => {
+                    if let Err(e) = result {
+                        eprintln!("Raw engine error: {}", e);
+                    }
+                }
+                _ = shutdown_rx => {
+                    // Signal shutdown - new connections will be rejected
+                    tracker.start_shutdown();
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Serve TLS connections with optional HTTP/2
+    #[cfg(feature = "tls")]
+    async fn serve_tls(
+        &self,
+        listener: tokio::net::TcpListener,
+        tls_config: TlsConfig,
+        http2_enabled: bool,
+        state: Arc<ServerState>,
+        shutdown_rx: tokio::sync::oneshot::Receiver<()>,
+    ) -> Result<()> {
+        // Use re-exports from gust_core
+        use hyper::server::conn::http1;
+        use hyper::server::conn::http2;
+        use hyper::service::service_fn;
+        use hyper_util::rt::{TokioIo, TokioTimer};
+
+        // Load TLS configuration
+        let tls_acceptor = load_tls_config(&tls_config, http2_enabled)
+            .map_err(|e| Error::from_reason(format!("TLS config error: {}", e)))?;
+
+        let tracker = self.connection_tracker.clone();
+
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = async {
+                    loop {
+                        let (stream, peer) = match listener.accept().await {
+                            Ok(conn) => conn,
+                            Err(_) => continue,
+                        };
+
+                        // Reject new connections during shutdown
+                        if tracker.is_shutting_down() {
+                            drop(stream);
+                            continue;
+                        }
+
+                        let acceptor = tls_acceptor.clone();
+                        let state = state.clone();
+                        let http2 = http2_enabled;
+                        let conn_tracker = tracker.clone();
+                        conn_tracker.increment();
+
+                        tokio::spawn(async move {
+                            // TLS handshake
+                            let tls_stream = match acceptor.accept(stream).await {
+                                Ok(s) => s,
+                                Err(e) => {
+                                    // Only log if not a normal connection close
+                                    if !e.to_string().contains("connection closed") {
+                                        eprintln!("TLS handshake error: {}", e);
+                                    }
+                                    conn_tracker.decrement();
+                                    return;
+                                }
+                            };
+
+                            let hooks = Arc::clone(&state.hooks);
+                            if hooks.observes_connections() {
+                                hooks.emit_connection(&gust_core::ConnectionEvent {
+                                    remote_addr: peer.to_string(),
+                                    phase: gust_core::ConnectionPhase::Opened,
+                                });
+                            }
+
+                            let io = TokioIo::new(tls_stream);
+                            // Header-phase deadline, enforced by hyper
+                            // while reading the request head (HTTP/1.1)
+                            let header_ms = state
+                                .timeouts
+                                .load()
+                                .phase_ms(None, gust_core::TimeoutPhase::Header);
+                            let conn_stats = Arc::new(CoreConnectionStats::new());
+                            let service_stats = conn_stats.clone();
+                            let service_tracker = conn_tracker.clone();
+                            // HTTP/2 multiplexes streams legitimately, so the
+                            // pipeline depth guard only applies to HTTP/1.1
+                            let enforce_depth = !http2;
+                            let service = service_fn(move |req| {
+                                let state = state.clone();
+                                let stats = service_stats.clone();
+                                let tracker = service_tracker.clone();
+                                async move {
+                                    let depth = stats.begin_request();
+                                    let res = if enforce_depth && depth >= tracker.max_pipeline_depth() {
+                                        tracker.record_pipelined_rejected();
+                                        eprintln!(
+                                            "Rejected pipelined request from {} (depth {})",
+                                            peer, depth
+                                        );
+                                        Ok(pipeline_reject_response())
+                                    } else {
+                                        handle_request_limited(state, req, peer, "https").await
+                                    };
+                                    stats.end_request();
+                                    res
+                                }
+                            });
+
+                            // Use HTTP/2 if enabled and negotiated via ALPN
+                            if http2 {
+                                // Try HTTP/2 first, fall back to HTTP/1.1
+                                if let Err(e) = http2::Builder::new(TokioExecutor)
+                                    .serve_connection(io, service)
+                                    .await
+                                {
+                                    if !e.to_string().contains("connection closed") {
+                                        eprintln!("HTTP/2 connection error: {}", e);
+                                    }
+                                }
+                            } else {
+                                let mut conn_builder = http1::Builder::new();
+                                if header_ms > 0 {
+                                    conn_builder.timer(TokioTimer::new()).header_read_timeout(
+                                        Duration::from_millis(header_ms as u64),
+                                    );
+                                }
+                                if let Err(e) = conn_builder
+                                    .serve_connection(io, service)
+                                    .with_upgrades()
+                                    .await
+                                {
+                                    if !e.to_string().contains("connection closed") {
+                                        eprintln!("HTTP/1.1 connection error: {}", e);
+                                    }
+                                }
+                            }
+
+                            conn_tracker.record_connection_closed(conn_stats.requests());
+                            conn_tracker.decrement();
+                            if hooks.observes_connections() {
+                                hooks.emit_connection(&gust_core::ConnectionEvent {
+                                    remote_addr: peer.to_string(),
+                                    phase: gust_core::ConnectionPhase::Closed,
+                                });
+                            }
+                        });
+                    }
+                } => {}
+                _ = shutdown_rx => {
+                    // Signal shutdown - new connections will be rejected
+                    tracker.start_shutdown();
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Register a raw TCP (non-HTTP) listener on `port`
+    ///
+    /// Subscribe to requests entering dispatch
+    ///
+    /// Lifecycle hooks are fire-and-forget: listeners are called with
+    /// a structured payload and their return value (or rejection) is
+    /// ignored, so custom telemetry never slows the request path.
+    #[napi]
+    pub fn on_request(&self, handler: JsFunction) -> Result<()> {
+        let tsfn: RequestHookCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+        self.state.hooks.on_request(move |event| {
+            tsfn.call(
+                RequestHookEvent {
+                    method: event.method.clone(),
+                    path: event.path.clone(),
+                },
+                ThreadsafeFunctionCallMode::NonBlocking,
+            );
+        });
+        Ok(())
+    }
+
+    /// Subscribe to responses leaving dispatch (status and duration)
+    #[napi]
+    pub fn on_response(&self, handler: JsFunction) -> Result<()> {
+        let tsfn: ResponseHookCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+        self.state.hooks.on_response(move |event| {
+            tsfn.call(
+                ResponseHookEvent {
+                    method: event.method.clone(),
+                    path: event.path.clone(),
+                    status: event.status as u32,
+                    duration_ms: event.duration.as_secs_f64() * 1000.0,
+                },
+                ThreadsafeFunctionCallMode::NonBlocking,
+            );
+        });
+        Ok(())
+    }
+
+    /// Subscribe to requests that finished with a 5xx status
+    #[napi]
+    pub fn on_error(&self, handler: JsFunction) -> Result<()> {
+        let tsfn: ErrorHookCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+        self.state.hooks.on_error(move |event| {
+            tsfn.call(
+                ErrorHookEvent {
+                    method: event.method.clone(),
+                    path: event.path.clone(),
+                    status: event.status as u32,
+                    message: event.message.clone(),
+                },
+                ThreadsafeFunctionCallMode::NonBlocking,
+            );
+        });
+        Ok(())
+    }
+
+    /// Subscribe to connections being accepted ("opened") and
+    /// finishing ("closed")
+    #[napi]
+    pub fn on_connection(&self, handler: JsFunction) -> Result<()> {
+        let tsfn: ConnectionHookCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+        self.state.hooks.on_connection(move |event| {
+            tsfn.call(
+                ConnectionHookEvent {
+                    remote_addr: event.remote_addr.clone(),
+                    phase: match event.phase {
+                        gust_core::ConnectionPhase::Opened => "opened".to_string(),
+                        gust_core::ConnectionPhase::Closed => "closed".to_string(),
+                    },
+                },
+                ThreadsafeFunctionCallMode::NonBlocking,
+            );
+        });
+        Ok(())
+    }
+
+    /// For custom protocols (SMTP stubs, line-based health probes)
+    /// colocated with the HTTP server: the listener shares its runtime
+    /// and connection tracker, so activeConnections(), shutdown(), and
+    /// gracefulShutdown() cover raw connections too. The handler is
+    /// called with `{connectionId, remoteAddr, event, data}` for
+    /// "open", "data", and "close" events and may return (a promise
+    /// of) `{data, close}` to write back or hang up. Binding happens
+    /// synchronously so bind errors surface here.
+    #[napi]
+    pub fn add_tcp_listener(
+        &self,
+        port: u32,
+        hostname: Option<String>,
+        handler: JsFunction,
+    ) -> Result<()> {
+        let tsfn: TcpEventCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+
+        let addr: std::net::SocketAddr =
+            format!("{}:{}", hostname.as_deref().unwrap_or("0.0.0.0"), port)
+                .parse()
+                .map_err(|e| Error::from_reason(format!("Invalid address: {}", e)))?;
+        let std_listener = std::net::TcpListener::bind(addr)
+            .map_err(|e| Error::from_reason(format!("Bind error: {}", e)))?;
+        std_listener
+            .set_nonblocking(true)
+            .map_err(|e| Error::from_reason(format!("Socket error: {}", e)))?;
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        self.tcp_shutdown.blocking_write().push(shutdown_tx);
+
+        // Each event is delivered on the runtime so slow JS handlers
+        // never block the connection's read loop
+        let core_handler: gust_core::TcpEventHandler = Arc::new(move |conn, event| {
+            let callback = tsfn.clone();
+            tokio::spawn(async move {
+                deliver_tcp_event(&callback, conn, event).await;
+            });
+        });
+
+        let tracker = self.connection_tracker.clone();
+        napi::bindgen_prelude::spawn(async move {
+            let listener = match tokio::net::TcpListener::from_std(std_listener) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("TCP listener error: {}", e);
+                    return;
+                }
+            };
+            let raw = gust_core::RawTcpListener::new(tracker, core_handler);
+            let shutdown = async {
+                let _ = shutdown_rx.await;
+            };
+            let _ = raw.serve_on(listener, shutdown).await;
+        });
+
+        Ok(())
     }
-}
 
-/// Server state shared across all connections
-struct ServerState {
-    /// Router using handler IDs (SSOT from gust-router) - for legacy routes
-    router: RwLock<Router>,
-    /// Static responses indexed by handler ID
-    static_responses: RwLock<HashMap<u32, StaticResponse>>,
-    /// Dynamic handlers indexed by handler ID - legacy pattern
-    dynamic_handlers: RwLock<HashMap<u32, DynamicHandler>>,
-    /// Next handler ID for legacy routes (atomic counter)
-    next_handler_id: AtomicU32,
-    /// App routes - using ArcSwap for lock-free reads on hot path
-    app_routes: ArcSwap<Router>,
-    /// Invoke handler callback - calls GustApp.invokeHandler(id, ctx)
-    /// Using ArcSwap for lock-free reads on hot path (massive perf improvement)
-    invoke_handler: ArcSwap<Option<InvokeHandler>>,
-    /// Middleware chain
-    middleware: RwLock<MiddlewareChain>,
-    /// Fallback handler for unmatched routes
-    fallback_handler: RwLock<Option<DynamicHandler>>,
-    /// Compression configuration
-    compression: RwLock<Option<CompressionConfig>>,
-    /// TLS configuration
-    tls_config: RwLock<Option<TlsConfig>>,
-    /// Enable HTTP/2 (atomic for lock-free read)
-    http2_enabled: AtomicBool,
-    /// Request timeout in milliseconds (atomic for lock-free read)
-    request_timeout_ms: AtomicU32,
-    /// Maximum body size in bytes (atomic for lock-free read)
-    max_body_size: AtomicU32,
-    /// Keep-alive timeout in milliseconds (atomic for lock-free read)
-    keep_alive_timeout_ms: AtomicU32,
-    /// Maximum header size in bytes (atomic for lock-free read)
-    max_header_size: AtomicU32,
-}
+    /// Receive UDP datagrams on `port`, delivered to JS in batches
+    ///
+    /// For statsd-style telemetry ingestion sharing the server's
+    /// runtime: datagrams are batched on the Rust side (a full batch
+    /// flushes immediately, a partial one when the flush interval
+    /// elapses) and the handler is called with an array of
+    /// `{remoteAddr, data}` objects — fire-and-forget, no reply.
+    /// Binding happens synchronously so bind errors surface here;
+    /// shutdown() flushes and stops the listener.
+    #[napi]
+    pub fn serve_udp(
+        &self,
+        port: u32,
+        handler: JsFunction,
+        options: Option<UdpOptions>,
+    ) -> Result<()> {
+        let tsfn: UdpBatchCallback = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
 
-// Default values
-const DEFAULT_REQUEST_TIMEOUT_MS: u32 = 30000;  // 30 seconds
-const DEFAULT_MAX_BODY_SIZE: u32 = 1024 * 1024; // 1MB
-const DEFAULT_KEEP_ALIVE_TIMEOUT_MS: u32 = 5000; // 5 seconds
-const DEFAULT_MAX_HEADER_SIZE: u32 = 8192;      // 8KB
+        let options = options.unwrap_or_default();
+        let addr: std::net::SocketAddr =
+            format!("{}:{}", options.hostname.as_deref().unwrap_or("0.0.0.0"), port)
+                .parse()
+                .map_err(|e| Error::from_reason(format!("Invalid address: {}", e)))?;
+        let std_socket = std::net::UdpSocket::bind(addr)
+            .map_err(|e| Error::from_reason(format!("Bind error: {}", e)))?;
+        std_socket
+            .set_nonblocking(true)
+            .map_err(|e| Error::from_reason(format!("Socket error: {}", e)))?;
 
-impl ServerState {
-    fn new() -> Self {
-        Self {
-            router: RwLock::new(Router::new()),
-            static_responses: RwLock::new(HashMap::new()),
-            dynamic_handlers: RwLock::new(HashMap::new()),
-            next_handler_id: AtomicU32::new(1000), // Start at 1000 to avoid conflicts with app routes
-            app_routes: ArcSwap::new(Arc::new(Router::new())),
-            invoke_handler: ArcSwap::new(Arc::new(None)),
-            middleware: RwLock::new(MiddlewareChain::new()),
-            fallback_handler: RwLock::new(None),
-            compression: RwLock::new(None),
-            tls_config: RwLock::new(None),
-            http2_enabled: AtomicBool::new(false),
-            request_timeout_ms: AtomicU32::new(DEFAULT_REQUEST_TIMEOUT_MS),
-            max_body_size: AtomicU32::new(DEFAULT_MAX_BODY_SIZE),
-            keep_alive_timeout_ms: AtomicU32::new(DEFAULT_KEEP_ALIVE_TIMEOUT_MS),
-            max_header_size: AtomicU32::new(DEFAULT_MAX_HEADER_SIZE),
+        let mut config = gust_core::UdpConfig::new();
+        if let Some(size) = options.batch_size {
+            config = config.batch_size(size as usize);
+        }
+        if let Some(interval) = options.flush_interval_ms {
+            config = config.flush_interval(Duration::from_millis(interval as u64));
         }
-    }
-}
 
-// ConnectionTracker is now in gust_core::ConnectionTracker (CoreConnectionTracker)
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        self.tcp_shutdown.blocking_write().push(shutdown_tx);
+
+        let batch_handler: gust_core::UdpBatchHandler = Arc::new(move |batch| {
+            let datagrams: Vec<UdpDatagram> = batch
+                .into_iter()
+                .map(|datagram| UdpDatagram {
+                    remote_addr: datagram.peer.to_string(),
+                    data: datagram.data.to_vec().into(),
+                })
+                .collect();
+            tsfn.call(datagrams, ThreadsafeFunctionCallMode::NonBlocking);
+        });
 
-/// Native HTTP server
-#[napi]
-pub struct GustServer {
-    state: Arc<ServerState>,
-    shutdown_tx: Arc<RwLock<Option<tokio::sync::oneshot::Sender<()>>>>,
-    connection_tracker: Arc<CoreConnectionTracker>,
-}
+        napi::bindgen_prelude::spawn(async move {
+            let socket = match tokio::net::UdpSocket::from_std(std_socket) {
+                Ok(socket) => socket,
+                Err(e) => {
+                    eprintln!("UDP listener error: {}", e);
+                    return;
+                }
+            };
+            let listener = gust_core::UdpListener::new(config, batch_handler);
+            let shutdown = async {
+                let _ = shutdown_rx.await;
+            };
+            let _ = listener.serve_on(socket, shutdown).await;
+        });
 
-#[napi]
-impl GustServer {
-    /// Create a new server instance
-    #[napi(constructor)]
-    pub fn new() -> Self {
-        Self {
-            state: Arc::new(ServerState::new()),
-            shutdown_tx: Arc::new(RwLock::new(None)),
-            connection_tracker: Arc::new(CoreConnectionTracker::new()),
+        Ok(())
+    }
+
+    /// Shutdown the server immediately (doesn't wait for connections)
+    #[napi]
+    pub async fn shutdown(&self) {
+        self.connection_tracker.start_shutdown();
+        if let Some(tx) = self.shutdown_tx.write().await.take() {
+            let _ = tx.send(());
+        }
+        for tx in self.tcp_shutdown.write().await.drain(..) {
+            let _ = tx.send(());
         }
+        *self.bound_addr.write().await = None;
     }
 
-    /// Create a server with configuration
-    #[napi(factory)]
-    pub async fn with_config(config: ServerConfig) -> Result<Self> {
-        let server = Self::new();
+    /// Graceful shutdown - waits for active connections to drain
+    /// timeout_ms: Maximum time to wait for connections to drain (0 = no timeout)
+    /// Returns true if all connections drained, false if timeout reached
+    #[napi]
+    pub async fn graceful_shutdown(&self, timeout_ms: u32) -> bool {
+        // Signal shutdown to stop accepting new connections
+        self.connection_tracker.start_shutdown();
 
-        // Apply middleware from config
-        if let Some(cors) = config.cors {
-            server.enable_cors(cors).await?;
+        // Send shutdown signal to server loop
+        if let Some(tx) = self.shutdown_tx.write().await.take() {
+            let _ = tx.send(());
         }
-
-        if let Some(rate_limit) = config.rate_limit {
-            server.enable_rate_limit(rate_limit).await?;
+        for tx in self.tcp_shutdown.write().await.drain(..) {
+            let _ = tx.send(());
         }
+        *self.bound_addr.write().await = None;
 
-        if let Some(security) = config.security {
-            server.enable_security(security).await?;
-        }
+        // Wait for connections to drain
+        let start = std::time::Instant::now();
+        let timeout = if timeout_ms > 0 {
+            Some(Duration::from_millis(timeout_ms as u64))
+        } else {
+            None
+        };
 
-        if let Some(compression) = config.compression {
-            server.enable_compression(compression).await?;
+        loop {
+            let active = self.connection_tracker.count();
+            if active == 0 {
+                return true; // All connections drained
+            }
+
+            // Check timeout
+            if let Some(t) = timeout {
+                if start.elapsed() >= t {
+                    return false; // Timeout reached
+                }
+            }
+
+            // Wait a bit before checking again
+            tokio::time::sleep(Duration::from_millis(10)).await;
         }
+    }
 
-        if let Some(tls) = config.tls {
-            server.enable_tls(tls).await?;
+    /// Get the number of active connections
+    #[napi]
+    pub fn active_connections(&self) -> u32 {
+        self.connection_tracker.count() as u32
+    }
+
+    /// Check if server is shutting down
+    #[napi]
+    pub fn is_shutting_down(&self) -> bool {
+        self.connection_tracker.is_shutting_down()
+    }
+
+    /// Keep-alive connection reuse statistics
+    #[napi]
+    pub fn keep_alive_stats(&self) -> KeepAliveStats {
+        let stats = self.connection_tracker.keep_alive_stats();
+        KeepAliveStats {
+            total_connections: stats.total_connections as i64,
+            total_requests: stats.total_requests as i64,
+            reused_connections: stats.reused_connections as i64,
+            max_requests_per_connection: stats.max_requests_per_connection as i64,
+            pipelined_rejected: stats.pipelined_rejected as i64,
+            reuse_ratio: stats.reuse_ratio(),
         }
+    }
 
-        if let Some(http2) = config.http2 {
-            server.state.http2_enabled.store(http2, Ordering::Relaxed);
+    /// Upstream connection pool statistics (proxying, S3 backends)
+    #[napi]
+    pub fn upstream_stats(&self) -> UpstreamStats {
+        let pool = gust_core::upstream::default_pool();
+        let stats = pool.stats();
+        UpstreamStats {
+            checkouts: stats.checkouts as i64,
+            waits: stats.waits as i64,
+            reused: stats.reused as i64,
+            returned: stats.returned as i64,
+            discarded: stats.discarded as i64,
+            expired: stats.expired as i64,
+            idle_connections: pool.idle_connections() as i64,
+            reuse_ratio: stats.reuse_ratio(),
         }
+    }
 
-        // Apply timeout and limit configurations (lock-free atomic stores)
-        if let Some(timeout) = config.request_timeout_ms {
-            server.state.request_timeout_ms.store(timeout, Ordering::Relaxed);
+    /// Time to first byte per upstream key (checkout to response
+    /// headers), covering proxied and S3 exchanges
+    #[napi]
+    pub fn upstream_ttfb(&self) -> Vec<UpstreamTtfbStats> {
+        gust_core::upstream::default_pool()
+            .ttfb_stats()
+            .into_iter()
+            .map(|(key, stats)| UpstreamTtfbStats {
+                key,
+                count: stats.count as i64,
+                mean_ms: stats.mean_ms(),
+                max_ms: stats.max_ms as i64,
+            })
+            .collect()
+    }
+
+    /// Handler dispatch counters (direct vs Promise path)
+    #[napi]
+    pub fn dispatch_stats(&self) -> DispatchStats {
+        DispatchStats {
+            sync_dispatches: SYNC_DISPATCHES.load(Ordering::Relaxed) as i64,
+            async_dispatches: ASYNC_DISPATCHES.load(Ordering::Relaxed) as i64,
         }
-        if let Some(max_body) = config.max_body_size {
-            server.state.max_body_size.store(max_body, Ordering::Relaxed);
+    }
+
+    /// Set maximum in-flight requests per HTTP/1.1 connection (minimum 1)
+    #[napi]
+    pub fn set_max_pipeline_depth(&self, depth: u32) {
+        self.connection_tracker.set_max_pipeline_depth(depth as u64);
+    }
+
+    /// Transfer headers/params to JS as flat buffers decoded lazily
+    #[napi]
+    pub fn set_batched_headers(&self, enabled: bool) {
+        self.state.batched_headers.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Select the HTTP engine: "hyper" (default) or "raw"
+    ///
+    /// Takes effect on the next serve() call. The raw engine skips
+    /// hyper entirely (benchmark/plaintext workloads); it does not
+    /// support TLS, HTTP/2, middleware, or the protocol handlers.
+    #[napi]
+    pub fn set_engine(&self, engine: String) -> Result<()> {
+        match engine.as_str() {
+            "hyper" => self.state.raw_engine.store(false, Ordering::Relaxed),
+            "raw" => self.state.raw_engine.store(true, Ordering::Relaxed),
+            other => {
+                return Err(Error::from_reason(format!(
+                    "Unknown engine '{}' (expected 'hyper' or 'raw')",
+                    other
+                )))
+            }
         }
-        if let Some(keep_alive) = config.keep_alive_timeout_ms {
-            server.state.keep_alive_timeout_ms.store(keep_alive, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Set which peers to trust for X-Forwarded-* headers
+    ///
+    /// Enables per-request client info: handler contexts get `ip`,
+    /// `protocol`, and `host` computed from the socket address and
+    /// (when the peer is trusted) the forwarded headers. Without this,
+    /// `ip` is always the direct socket address.
+    #[napi]
+    pub fn set_trust_proxy(&self, trust: TrustProxy) {
+        self.state
+            .proxy_config
+            .store(Arc::new(Some(trust_proxy_config(trust))));
+    }
+
+    /// Trust an explicit IP/CIDR list for X-Forwarded-* headers
+    ///
+    /// Enables per-hop chain evaluation: the X-Forwarded-For chain is
+    /// walked right-to-left past trusted hops, so `ip` is the first
+    /// address not in the trusted set rather than the (spoofable)
+    /// leftmost entry. Errors on invalid IP/CIDR entries.
+    #[napi]
+    pub fn set_trust_proxy_addresses(&self, cidrs: Vec<String>) -> Result<()> {
+        self.state
+            .proxy_config
+            .store(Arc::new(Some(trust_proxy_addresses_config(&cidrs)?)));
+        Ok(())
+    }
+
+    /// Set the diagnostic log level at runtime
+    ///
+    /// Accepts "error", "warn", "info", "debug", or "trace". At "debug"
+    /// and above every sampled request is logged with its status and
+    /// duration. Takes effect immediately, no restart required.
+    #[napi]
+    pub fn set_log_level(&self, level: String) -> Result<()> {
+        let level = LogLevel::parse(&level).ok_or_else(|| {
+            Error::from_reason(format!(
+                "Unknown log level '{}' (expected error, warn, info, debug, or trace)",
+                level
+            ))
+        })?;
+        let mut config = (**self.state.observability.load()).clone();
+        config.log_level = level;
+        self.state.observability.store(Arc::new(config));
+        Ok(())
+    }
+
+    /// Set the fraction of requests eligible for debug logging (0.0-1.0)
+    ///
+    /// Slow-request warnings are never sampled away.
+    #[napi]
+    pub fn set_sample_rate(&self, rate: f64) -> Result<()> {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(Error::from_reason(
+                "Sample rate must be between 0.0 and 1.0".to_string(),
+            ));
         }
-        if let Some(max_header) = config.max_header_size {
-            server.state.max_header_size.store(max_header, Ordering::Relaxed);
+        let mut config = (**self.state.observability.load()).clone();
+        config.sample_rate = rate;
+        self.state.observability.store(Arc::new(config));
+        Ok(())
+    }
+
+    /// Warn about requests slower than this threshold in milliseconds
+    ///
+    /// Pass 0 to disable slow-request logging (the default).
+    #[napi]
+    pub fn set_slow_request_threshold(&self, threshold_ms: f64) -> Result<()> {
+        if threshold_ms < 0.0 {
+            return Err(Error::from_reason(
+                "Slow request threshold must not be negative".to_string(),
+            ));
         }
+        let mut config = (**self.state.observability.load()).clone();
+        config.slow_request_threshold_ms = threshold_ms;
+        self.state.observability.store(Arc::new(config));
+        Ok(())
+    }
 
-        Ok(server)
+    /// Mount an admin endpoint for runtime observability control
+    ///
+    /// GET returns the current log level, sample rate, and slow-request
+    /// threshold as JSON; PUT/POST with a JSON body of the same shape
+    /// updates any subset of them without a restart. Mount it on an
+    /// internal-only path or behind auth middleware - it is not
+    /// protected by default.
+    #[napi]
+    pub async fn enable_admin_endpoint(&self, path: String) -> Result<()> {
+        *self.state.admin_path.write().await = Some(path);
+        Ok(())
     }
 
-    /// Set request timeout in milliseconds
+    /// Start a separate admin listener for health, metrics, and debug
+    ///
+    /// Binds loopback (default 127.0.0.1) or a unix socket and serves
+    /// only:
+    /// - `GET /health` — liveness plus connection counts
+    /// - `GET /metrics` — Prometheus text (connections, timeouts)
+    /// - `GET|PUT /debug` — runtime observability settings (the same
+    ///   handler as `enableAdminEndpoint`)
+    ///
+    /// Nothing else is routed, so these endpoints stay off the public
+    /// port. The listener stops with shutdown().
     #[napi]
-    pub async fn set_request_timeout(&self, timeout_ms: u32) -> Result<()> {
-        self.state.request_timeout_ms.store(timeout_ms, Ordering::Relaxed);
+    pub async fn enable_admin_listener(&self, config: AdminListenerConfig) -> Result<()> {
+        let state = self.state.clone();
+        let tracker = self.connection_tracker.clone();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        self.tcp_shutdown.write().await.push(shutdown_tx);
+
+        if let Some(path) = config.socket_path {
+            // A stale socket file from a previous run blocks bind
+            let _ = std::fs::remove_file(&path);
+            let listener = tokio::net::UnixListener::bind(&path)
+                .map_err(|e| Error::from_reason(format!("Admin bind error: {}", e)))?;
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = async {
+                        loop {
+                            if let Ok((stream, _)) = listener.accept().await {
+                                serve_admin_connection(stream, state.clone(), tracker.clone());
+                            }
+                        }
+                    } => {}
+                    _ = shutdown_rx => {
+                        let _ = std::fs::remove_file(&path);
+                    }
+                }
+            });
+            return Ok(());
+        }
+
+        let mut hostname = config.hostname.unwrap_or_else(|| "127.0.0.1".to_string());
+        if hostname == "localhost" {
+            hostname = "127.0.0.1".to_string();
+        }
+        let port = config
+            .port
+            .ok_or_else(|| Error::from_reason("Admin listener needs a port or socket_path".to_string()))?;
+        let addr: std::net::SocketAddr = format!("{}:{}", hostname, port)
+            .parse()
+            .map_err(|e| Error::from_reason(format!("Invalid admin address: {}", e)))?;
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .map_err(|e| Error::from_reason(format!("Admin bind error: {}", e)))?;
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = async {
+                    loop {
+                        if let Ok((stream, _)) = listener.accept().await {
+                            serve_admin_connection(stream, state.clone(), tracker.clone());
+                        }
+                    }
+                } => {}
+                _ = shutdown_rx => {}
+            }
+        });
         Ok(())
     }
 
-    /// Set maximum body size in bytes
+    /// Enable the on-demand profiling endpoint
+    ///
+    /// `GET /_gust/profile?seconds=10` (also `/profile` on the admin
+    /// listener) captures a wall-clock profile of handler execution
+    /// and returns it as flamegraph-compatible folded stacks
+    /// (`gust;handler;<route> <microseconds>` per line). The endpoint
+    /// requires `Authorization: Bearer <token>`; captures run one at
+    /// a time and add a single map insert per request while active.
     #[napi]
-    pub async fn set_max_body_size(&self, max_bytes: u32) -> Result<()> {
-        self.state.max_body_size.store(max_bytes, Ordering::Relaxed);
+    pub async fn enable_profiling(&self, token: String) -> Result<()> {
+        if token.is_empty() {
+            return Err(Error::from_reason(
+                "Profiling token must not be empty".to_string(),
+            ));
+        }
+        *self.state.profile_token.write().await = Some(token);
         Ok(())
     }
+}
 
-    /// Set keep-alive timeout in milliseconds
+/// Bun-optimized direct dispatch (bun-direct feature)
+#[cfg(feature = "bun-direct")]
+#[napi]
+impl GustServer {
+    /// Register a route whose JS handler returns ResponseData
+    /// synchronously
+    ///
+    /// Skips the Promise coercion and await of the normal dispatch
+    /// path: the return value is read directly off the threadsafe
+    /// call. The handler must not return a promise. Compare the two
+    /// paths with dispatchStats().
     #[napi]
-    pub async fn set_keep_alive_timeout(&self, timeout_ms: u32) -> Result<()> {
-        self.state.keep_alive_timeout_ms.store(timeout_ms, Ordering::Relaxed);
+    pub fn add_sync_route(
+        &self,
+        method: String,
+        path: String,
+        handler: JsFunction,
+    ) -> Result<()> {
+        let handler_id = self.state.next_handler_id.fetch_add(1, Ordering::SeqCst);
+
+        let tsfn: ThreadsafeFunction<RequestContext, ErrorStrategy::Fatal> = handler
+            .create_threadsafe_function(0, |ctx| {
+                Ok(vec![ctx.value])
+            })?;
+
+        let dynamic_handler = DynamicHandler {
+            callback: tsfn,
+            sync: true,
+        };
+        self.state.dynamic_handlers.blocking_write().insert(handler_id, dynamic_handler);
+        self.state.router.blocking_write().insert(&method, &path, handler_id);
+
         Ok(())
     }
+}
 
-    /// Set maximum header size in bytes
-    #[napi]
-    pub async fn set_max_header_size(&self, max_bytes: u32) -> Result<()> {
-        self.state.max_header_size.store(max_bytes, Ordering::Relaxed);
-        Ok(())
+impl Default for GustServer {
+    fn default() -> Self {
+        GustServer {
+            state: Arc::new(ServerState::new()),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+            connection_tracker: Arc::new(CoreConnectionTracker::new()),
+            tcp_shutdown: Arc::new(RwLock::new(Vec::new())),
+            bound_addr: Arc::new(RwLock::new(None)),
+        }
     }
+}
 
-    /// Enable compression middleware
-    #[napi]
-    pub async fn enable_compression(&self, config: CompressionConfig) -> Result<()> {
-        *self.state.compression.write().await = Some(config);
-        Ok(())
+/// Pools for transient per-request allocations
+///
+/// Header maps on the middleware path are checked out here instead of
+/// freshly allocated; cleared maps go back on the idle list when the
+/// request finishes.
+struct RequestPools {
+    header_maps: gust_core::ObjectPool<HashMap<String, String>>,
+}
+
+static REQUEST_POOLS: std::sync::OnceLock<RequestPools> = std::sync::OnceLock::new();
+
+fn request_pools() -> &'static RequestPools {
+    REQUEST_POOLS.get_or_init(|| RequestPools {
+        header_maps: gust_core::ObjectPool::new(256, HashMap::new, HashMap::clear),
+    })
+}
+
+/// Request pool effectiveness counters
+#[napi(object)]
+#[derive(Clone)]
+pub struct RequestPoolStats {
+    /// Checkouts served from the idle list
+    pub reused: i64,
+    /// Checkouts that had to allocate
+    pub created: i64,
+    /// Values returned to the idle list
+    pub returned: i64,
+    /// Values dropped or detached instead of returned
+    pub discarded: i64,
+    /// Fraction of checkouts served without allocating
+    pub reuse_ratio: f64,
+}
+
+/// Allocation-reuse statistics for the per-request object pools
+#[napi]
+pub fn request_pool_stats() -> RequestPoolStats {
+    let stats = request_pools().header_maps.stats();
+    RequestPoolStats {
+        reused: stats.reused as i64,
+        created: stats.created as i64,
+        returned: stats.returned as i64,
+        discarded: stats.discarded as i64,
+        reuse_ratio: stats.reuse_ratio(),
     }
+}
 
-    /// Enable TLS/HTTPS
-    #[napi]
-    pub async fn enable_tls(&self, config: TlsConfig) -> Result<()> {
-        *self.state.tls_config.write().await = Some(config);
-        Ok(())
+/// Encode headers as a single length-prefixed flat buffer
+///
+/// Layout per header: u32 LE name length, name bytes (lowercase),
+/// u32 LE value length, value bytes. Crossing the JS boundary once
+/// with one buffer is much cheaper than marshalling a HashMap; the JS
+/// side decodes it lazily on first access.
+fn encode_headers_buffer(headers: &hyper::HeaderMap) -> Vec<u8> {
+    let mut size = 0;
+    for (name, value) in headers {
+        size += 8 + name.as_str().len() + value.len();
     }
 
-    /// Enable HTTP/2
-    #[napi]
-    pub async fn enable_http2(&self) -> Result<()> {
-        self.state.http2_enabled.store(true, Ordering::Relaxed);
-        Ok(())
+    let mut out = Vec::with_capacity(size);
+    for (name, value) in headers {
+        let name = name.as_str(); // HeaderName is always lowercase
+        out.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        out.extend_from_slice(value.as_bytes());
     }
+    out
+}
 
-    /// Enable CORS middleware
-    #[napi]
-    pub async fn enable_cors(&self, config: CorsConfig) -> Result<()> {
-        use gust_core::middleware::cors::{Cors, CorsConfig as CoreConfig};
+/// 429 response for requests pipelined beyond the allowed depth
+fn pipeline_reject_response() -> hyper::Response<Full<Bytes>> {
+    hyper::Response::builder()
+        .status(429)
+        .header("connection", "close")
+        .header("content-type", "text/plain")
+        .body(Full::new(Bytes::from_static(b"Pipelining depth exceeded")))
+        .unwrap()
+}
 
-        let mut core_config = if config.origins.as_ref().map(|o| o.contains(&"*".to_string())).unwrap_or(false) {
-            CoreConfig::default().allow_all_origins()
-        } else {
-            CoreConfig::default()
-        };
+/// Handle incoming HTTP request
+/// Per-request client info derived from the socket address and, when
+/// the peer is trusted, the forwarded headers
+#[derive(Clone)]
+struct ClientInfo {
+    ip: String,
+    protocol: String,
+    host: String,
+}
 
-        // Apply origins
-        if let Some(origins) = config.origins {
-            for origin in origins {
-                if origin != "*" {
-                    core_config = core_config.allow_origin(origin);
-                }
+/// Resolve the body limit for a request: the first per-content-type
+/// rule that matches wins, otherwise the global `max_body_size`
+fn effective_body_limit(state: &ServerState, content_type: Option<&str>) -> usize {
+    let fallback = state.max_body_size.load(Ordering::Relaxed) as u64;
+    let rules = state.body_limits.load();
+    if rules.is_empty() {
+        return fallback as usize;
+    }
+    gust_core::pure::resolve_body_limit(&rules, content_type, fallback) as usize
+}
+
+/// Outcome of reading a request body under a size limit
+enum BodyRead {
+    Complete(Bytes),
+    TooLarge,
+    Timeout,
+}
+
+/// Collect a request body, enforcing the limit while streaming: each
+/// frame is counted as it arrives, so an oversized chunked upload
+/// aborts at the limit instead of buffering to completion first
+async fn read_body_limited(
+    state: &ServerState,
+    body: hyper::body::Incoming,
+    max_body_size: usize,
+    timeout_ms: u32,
+) -> BodyRead {
+    let collect = collect_frames_limited(body, max_body_size);
+    if timeout_ms > 0 {
+        match tokio::time::timeout(Duration::from_millis(timeout_ms as u64), collect).await {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                state.timeouts_body.fetch_add(1, Ordering::Relaxed);
+                BodyRead::Timeout
             }
         }
+    } else {
+        collect.await
+    }
+}
 
-        // Apply methods
-        if let Some(methods) = config.methods {
-            for method in methods {
-                if let Ok(m) = Method::from_str(&method) {
-                    core_config = core_config.allow_method(m);
-                }
+async fn collect_frames_limited(mut body: hyper::body::Incoming, max_body_size: usize) -> BodyRead {
+    let mut buf: Vec<u8> = Vec::new();
+    while let Some(frame) = body.frame().await {
+        let frame = match frame {
+            Ok(frame) => frame,
+            // Matches the previous collect() behavior: a mid-body read
+            // error hands the handler an empty body
+            Err(_) => return BodyRead::Complete(Bytes::new()),
+        };
+        if let Some(data) = frame.data_ref() {
+            if buf.len() + data.len() > max_body_size {
+                return BodyRead::TooLarge;
             }
+            buf.extend_from_slice(data);
         }
+    }
+    BodyRead::Complete(Bytes::from(buf))
+}
 
-        // Apply headers
-        if let Some(headers) = config.allowed_headers {
-            for header in headers {
-                core_config = core_config.allow_header(header);
-            }
+/// Effective deadline for a request phase: the route override, then
+/// the global hierarchy, then (for body and handler only) the legacy
+/// single request timeout; 0 = no deadline
+fn phase_timeout_ms(
+    state: &ServerState,
+    route: Option<&str>,
+    phase: gust_core::TimeoutPhase,
+) -> u32 {
+    let ms = state.timeouts.load().phase_ms(route, phase);
+    if ms > 0 {
+        return ms;
+    }
+    match phase {
+        gust_core::TimeoutPhase::Body | gust_core::TimeoutPhase::Handler => {
+            state.request_timeout_ms.load(Ordering::Relaxed)
         }
+        _ => 0,
+    }
+}
 
-        // Apply exposed headers
-        if let Some(headers) = config.exposed_headers {
-            for header in headers {
-                core_config = core_config.expose_header(header);
+/// Run a handler future under the handler-phase deadline; `None`
+/// means it timed out (the site returns its own 504)
+///
+/// While a profile capture is active, the handler's wall time is
+/// recorded against its route's stack.
+async fn run_handler_limited<F, T>(state: &ServerState, route: Option<&str>, handler: F) -> Option<T>
+where
+    F: std::future::Future<Output = T>,
+{
+    let profiling = state.profiler.load().is_some();
+    let started = profiling.then(std::time::Instant::now);
+
+    let timeout_ms = phase_timeout_ms(state, route, gust_core::TimeoutPhase::Handler);
+    // Narrow the ambient scope's deadline to the handler's, so
+    // outbound calls give up no later than the handler itself
+    let mut scope = gust_core::scope::current();
+    if timeout_ms > 0 {
+        scope.deadline = Some(std::time::Instant::now() + Duration::from_millis(timeout_ms as u64));
+    }
+    let handler = gust_core::scope::with_scope(scope, handler);
+    let result = if timeout_ms == 0 {
+        Some(handler.await)
+    } else {
+        match tokio::time::timeout(Duration::from_millis(timeout_ms as u64), handler).await {
+            Ok(response) => Some(response),
+            Err(_) => {
+                state.timeouts_handler.fetch_add(1, Ordering::Relaxed);
+                None
             }
         }
+    };
 
-        // Apply credentials
-        if let Some(true) = config.credentials {
-            core_config = core_config.allow_credentials();
+    if let Some(started) = started {
+        if let Some(profiler) = state.profiler.load().as_ref() {
+            let stack = format!("gust;handler;{}", route.unwrap_or("(fallback)"));
+            profiler.record(&stack, started.elapsed().as_micros() as u64);
         }
+    }
+    result
+}
 
-        // Apply max age
-        if let Some(max_age) = config.max_age {
-            core_config = core_config.max_age(max_age);
+/// Compute client ip/protocol/host for a request
+///
+/// `scheme` is the listener's own protocol ("http"/"https"); it is
+/// used unless a trusted proxy supplied X-Forwarded-Proto. With no
+/// trustProxy configured, forwarded headers are ignored entirely and
+/// the ip is the direct socket address.
+fn extract_client_info(
+    state: &ServerState,
+    peer: std::net::SocketAddr,
+    scheme: &str,
+    headers: &hyper::HeaderMap,
+) -> ClientInfo {
+    let socket_ip = peer.ip().to_string();
+    let host_header = headers
+        .get(hyper::header::HOST)
+        .and_then(|v| v.to_str().ok());
+
+    let config_guard = state.proxy_config.load();
+    let Some(ref config) = **config_guard else {
+        return ClientInfo {
+            ip: socket_ip,
+            protocol: scheme.to_string(),
+            host: host_header.unwrap_or("localhost").to_string(),
+        };
+    };
+
+    // Only the forwarded headers the config names are relevant; pull
+    // them out instead of materializing the whole header map
+    let mut forwarded = Vec::with_capacity(4);
+    for name in [
+        &config.ip_header,
+        &config.host_header,
+        &config.proto_header,
+        &config.port_header,
+    ] {
+        if let Some(v) = headers.get(name.as_str()).and_then(|v| v.to_str().ok()) {
+            forwarded.push((name.clone(), v.to_string()));
         }
+    }
 
-        let cors = Cors::new(core_config);
-        self.state.middleware.write().await.add(cors);
-        Ok(())
+    let trusted = rust_is_trusted(&socket_ip, &config.trust);
+    let had_proto = forwarded.iter().any(|(n, _)| n == &config.proto_header);
+    let info = rust_extract_proxy_info(config, &socket_ip, &forwarded, host_header);
+
+    ClientInfo {
+        ip: info.ip,
+        // extract_proxy_info defaults to http when no forwarded proto
+        // applies; the listener's own scheme is more accurate then
+        protocol: if trusted && had_proto {
+            info.protocol.as_str().to_string()
+        } else {
+            scheme.to_string()
+        },
+        host: info.host,
     }
+}
 
-    /// Enable rate limiting middleware
-    #[napi]
-    pub async fn enable_rate_limit(&self, config: RateLimitConfig) -> Result<()> {
-        use gust_core::middleware::rate_limit::{RateLimit, RateLimitConfig as CoreConfig};
+/// Bind one listener per accept worker with SO_REUSEPORT
+///
+/// The kernel load-balances incoming connections across the listeners,
+/// so each worker runs its own accept loop without contending on a
+/// shared one. Platforms without SO_REUSEPORT (the second bind fails)
+/// fall back to a single listener whose accept loop is shared by the
+/// runtime.
+fn bind_worker_listeners(
+    addr: std::net::SocketAddr,
+    workers: usize,
+) -> std::io::Result<Vec<tokio::net::TcpListener>> {
+    fn into_tokio(socket: impl Into<std::net::TcpListener>) -> std::io::Result<tokio::net::TcpListener> {
+        let std_listener = socket.into();
+        std_listener.set_nonblocking(true)?;
+        tokio::net::TcpListener::from_std(std_listener)
+    }
 
-        let core_config = CoreConfig::new(
-            config.max_requests,
-            Duration::from_secs(config.window_seconds as u64),
-        );
+    let first = gust_core::create_optimized_socket(&addr)?;
+    // With port 0 the OS picks a port on the first bind; the remaining
+    // workers must bind that same port
+    let bound = first
+        .local_addr()?
+        .as_socket()
+        .unwrap_or(addr);
+    let mut listeners = vec![into_tokio(first)?];
+    for _ in 1..workers {
+        match gust_core::create_optimized_socket(&bound) {
+            Ok(socket) => listeners.push(into_tokio(socket)?),
+            // No SO_REUSEPORT on this platform: the first accept loop
+            // serves alone
+            Err(_) => break,
+        }
+    }
+    Ok(listeners)
+}
 
-        let rate_limit = RateLimit::new(core_config);
-        self.state.middleware.write().await.add(rate_limit);
-        Ok(())
+/// Run the full request pipeline under the total-phase deadline
+/// (0 = no cap, the default)
+async fn handle_request_limited(
+    state: Arc<ServerState>,
+    req: hyper::Request<hyper::body::Incoming>,
+    peer: std::net::SocketAddr,
+    scheme: &'static str,
+) -> std::result::Result<hyper::Response<Full<Bytes>>, std::convert::Infallible> {
+    // Accept-Encoding is captured before the request is consumed so
+    // the finished response can be compressed on the way out
+    let accept_encoding = req
+        .headers()
+        .get(hyper::header::ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let response = handle_request_timed(state.clone(), req, peer, scheme).await?;
+    Ok(compress_hyper_response(&state, accept_encoding.as_deref(), response).await)
+}
+
+async fn handle_request_timed(
+    state: Arc<ServerState>,
+    req: hyper::Request<hyper::body::Incoming>,
+    peer: std::net::SocketAddr,
+    scheme: &'static str,
+) -> std::result::Result<hyper::Response<Full<Bytes>>, std::convert::Infallible> {
+    let total_ms = state
+        .timeouts
+        .load()
+        .phase_ms(None, gust_core::TimeoutPhase::Total);
+    // Outbound Rust calls made while handling this request (the
+    // upstream pool) inherit the incoming trace context through the
+    // ambient request scope
+    let scope = match req
+        .headers()
+        .get("traceparent")
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(tp) => gust_core::scope::RequestScope::new().traceparent(tp),
+        None => gust_core::scope::RequestScope::new(),
+    };
+    // A profile capture blocks for its whole sampling window by
+    // design, so the total deadline must not apply to it
+    if total_ms == 0 || req.uri().path() == PROFILE_PATH {
+        return gust_core::scope::with_scope(scope, handle_request(state, req, peer, scheme))
+            .await;
     }
+    let pipeline =
+        gust_core::scope::with_scope(scope, handle_request(state.clone(), req, peer, scheme));
+    match tokio::time::timeout(Duration::from_millis(total_ms as u64), pipeline).await {
+        Ok(result) => result,
+        Err(_) => {
+            state.timeouts_total.fetch_add(1, Ordering::Relaxed);
+            Ok(hyper::Response::builder()
+                .status(504)
+                .header("content-type", "text/plain")
+                .body(Full::new(Bytes::from("Gateway Timeout")))
+                .unwrap())
+        }
+    }
+}
 
-    /// Enable security headers middleware
-    #[napi]
-    pub async fn enable_security(&self, config: SecurityConfig) -> Result<()> {
-        use gust_core::middleware::security::{Security, SecurityConfig as CoreConfig, FrameOptions, HstsConfig};
+async fn handle_request(
+    state: Arc<ServerState>,
+    req: hyper::Request<hyper::body::Incoming>,
+    peer: std::net::SocketAddr,
+    scheme: &'static str,
+) -> std::result::Result<hyper::Response<Full<Bytes>>, std::convert::Infallible> {
+    // Runtime observability: one lock-free load decides whether this
+    // request is timed and logged at all
+    let obs = state.observability.load_full();
+    let log_debug = obs.log_level >= LogLevel::Debug && sample_request(obs.sample_rate);
+    let warn_slow = obs.slow_request_threshold_ms > 0.0 && obs.log_level >= LogLevel::Warn;
+    if !log_debug && !warn_slow {
+        return dispatch_with_hooks(state, req, peer, scheme).await;
+    }
 
-        let frame_options = match config.frame_options.as_deref() {
-            Some("DENY") => FrameOptions::Deny,
-            Some("SAMEORIGIN") => FrameOptions::SameOrigin,
-            _ => FrameOptions::None,
-        };
+    let method = req.method().as_str().to_string();
+    let path = req.uri().path().to_string();
+    let start = std::time::Instant::now();
+    let res = dispatch_with_hooks(state, req, peer, scheme).await;
+    let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let status = res
+        .as_ref()
+        .map(|r| r.status().as_u16())
+        .unwrap_or_default();
+
+    if warn_slow && elapsed_ms >= obs.slow_request_threshold_ms {
+        eprintln!(
+            "[gust] slow request: {} {} -> {} ({:.2}ms)",
+            method, path, status, elapsed_ms
+        );
+    } else if log_debug {
+        eprintln!(
+            "[gust] {} {} -> {} ({:.2}ms)",
+            method, path, status, elapsed_ms
+        );
+    }
 
-        let hsts = if config.hsts.unwrap_or(false) {
-            Some(HstsConfig {
-                max_age: config.hsts_max_age.unwrap_or(31536000) as u64,
-                include_subdomains: true,
-                preload: false,
-            })
-        } else {
-            None
-        };
+    res
+}
 
-        let core_config = CoreConfig {
-            csp: None,
-            frame_options,
-            content_type_options: config.content_type_options.unwrap_or(false),
-            xss_protection: config.xss_protection.unwrap_or(false),
-            hsts,
-            referrer_policy: config.referrer_policy,
-            permissions_policy: None,
-            coop: None,
-            coep: None,
-            corp: None,
-        };
+/// Dispatch with lifecycle hook emission; payloads are only built
+/// when a listener is registered
+async fn dispatch_with_hooks(
+    state: Arc<ServerState>,
+    req: hyper::Request<hyper::body::Incoming>,
+    peer: std::net::SocketAddr,
+    scheme: &'static str,
+) -> std::result::Result<hyper::Response<Full<Bytes>>, std::convert::Infallible> {
+    if !state.hooks.observes_requests() {
+        return dispatch_request(state, req, peer, scheme).await;
+    }
 
-        let security = Security::new(core_config);
-        self.state.middleware.write().await.add(security);
-        Ok(())
+    let hooks = Arc::clone(&state.hooks);
+    let method = req.method().as_str().to_string();
+    let path = req.uri().path().to_string();
+    hooks.emit_request(&gust_core::RequestEvent {
+        method: method.clone(),
+        path: path.clone(),
+    });
+
+    let start = std::time::Instant::now();
+    let res = dispatch_request(state, req, peer, scheme).await;
+    let status = res
+        .as_ref()
+        .map(|r| r.status().as_u16())
+        .unwrap_or_default();
+    if status >= 500 {
+        let message = res
+            .as_ref()
+            .ok()
+            .and_then(|r| r.status().canonical_reason())
+            .unwrap_or_default()
+            .to_string();
+        hooks.emit_error(&gust_core::ErrorEvent {
+            method: method.clone(),
+            path: path.clone(),
+            status,
+            message,
+        });
     }
+    hooks.emit_response(&gust_core::ResponseEvent {
+        method,
+        path,
+        status,
+        duration: start.elapsed(),
+    });
+    res
+}
 
-    /// Add a static route (pre-rendered response)
-    #[napi]
-    pub async fn add_static_route(
-        &self,
-        method: String,
-        path: String,
-        status: u32,
-        content_type: String,
-        body: String,
-    ) -> Result<()> {
-        // Generate unique handler ID
-        let handler_id = self.state.next_handler_id.fetch_add(1, Ordering::SeqCst);
+async fn dispatch_request(
+    state: Arc<ServerState>,
+    req: hyper::Request<hyper::body::Incoming>,
+    peer: std::net::SocketAddr,
+    scheme: &'static str,
+) -> std::result::Result<hyper::Response<Full<Bytes>>, std::convert::Infallible> {
+    let method_str = req.method().as_str();
+    let path = req.uri().path();
+    let method = Method::from_str(method_str).unwrap_or(Method::Get);
+    let _is_get_or_head = method == Method::Get || method == Method::Head;
 
-        // Pre-render the HTTP/1.1 response
-        let res = ResponseBuilder::new(StatusCode(status as u16))
-            .header("content-type", &content_type)
-            .body(body.clone())
-            .build();
-        let response_bytes = res.to_http1_bytes();
+    // Client ip/protocol/host for handler contexts, computed once so
+    // every dispatch path sees the same view
+    let client = extract_client_info(&state, peer, scheme, req.headers());
 
-        let static_response = StaticResponse {
-            bytes: response_bytes,
-        };
+    // FAST PATH: Check legacy static/dynamic routes first with minimal overhead
+    {
+        let router = state.router.read().await;
+        // HEAD falls back to the GET route; hyper elides the body
+        let matched = router
+            .find_with_query(method_str, path, req.uri().query())
+            .or_else(|| {
+                if method == Method::Head {
+                    router.find_with_query("GET", path, req.uri().query())
+                } else {
+                    None
+                }
+            });
+        if let Some(matched) = matched {
+            let handler_id = matched.handler_id;
+            drop(router);
 
-        // Store response in HashMap
-        self.state
-            .static_responses
-            .write()
-            .await
-            .insert(handler_id, static_response);
+            // Try static response first
+            let static_responses = state.static_responses.read().await;
+            let static_response = static_responses.get(&handler_id).cloned();
+            drop(static_responses);
+            if let Some(static_response) = static_response {
+                let if_none_match = req
+                    .headers()
+                    .get(hyper::header::IF_NONE_MATCH)
+                    .and_then(|v| v.to_str().ok());
+                return Ok(static_to_hyper_response(if_none_match, &static_response));
+            }
 
-        // Insert route into router
-        self.state
-            .router
-            .write()
-            .await
-            .insert(&method, &path, handler_id);
+            // Try dynamic handler
+            let dynamic_handlers = state.dynamic_handlers.read().await;
+            if let Some(handler) = dynamic_handlers.get(&handler_id) {
+                let handler = handler.clone();
+                let params: HashMap<String, String> = matched.params.into_iter().collect();
+                drop(dynamic_handlers);
 
-        Ok(())
+                // Create minimal context for dynamic handler
+                let route_pattern = matched.pattern;
+                let ctx = RequestContext {
+                    method: method_str.to_string(),
+                    path: path.to_string(),
+                    route_pattern: Some(route_pattern.clone()),
+                    query: req.uri().query().map(|s| s.to_string()),
+                    params,
+                    headers: HashMap::new(), // TODO: collect if needed
+                    body: String::new(),     // TODO: read if needed
+                    body_buffer: None,
+                    ip: Some(client.ip.clone()),
+                    protocol: Some(client.protocol.clone()),
+                    host: Some(client.host.clone()),
+                };
+
+                let handler_call = call_js_handler(&handler, ctx);
+                let response =
+                    match run_handler_limited(&state, Some(&route_pattern), handler_call).await {
+                        Some(response) => response,
+                        None => {
+                            return Ok(hyper::Response::builder()
+                                .status(504)
+                                .header("content-type", "text/plain")
+                                .body(Full::new(Bytes::from("Gateway Timeout")))
+                                .unwrap());
+                        }
+                    };
+                return Ok(to_hyper_response(response_data_to_response(response)));
+            }
+        }
     }
 
-    /// Add a dynamic route with JS handler callback
-    ///
-    /// The handler will be called with RequestContext and should return ResponseData (or Promise<ResponseData>)
-    #[napi]
-    pub fn add_dynamic_route(
-        &self,
-        method: String,
-        path: String,
-        handler: JsFunction,
-    ) -> Result<()> {
-        // Generate unique handler ID
-        let handler_id = self.state.next_handler_id.fetch_add(1, Ordering::SeqCst);
+    // FAST PATH 2: Check app routes (GustApp pattern - Rust routing, ID-based dispatch)
+    // OPTIMIZED: Lock-free routing + lock-free invoke_handler read + skip body for GET/HEAD
+    {
+        // OPTIMIZATION: Lock-free read of app routes using ArcSwap
+        let routes = state.app_routes.load();
+        if let Some(matched) = routes.find_with_query(method_str, path, req.uri().query()) {
+            let handler_id = matched.handler_id;
+            let route_pattern = matched.pattern;
+            let params: HashMap<String, String> = matched.params.into_iter().collect();
+            // No need to drop - ArcSwap guard is cheap
 
-        // Create threadsafe function that can be called from any thread
-        let tsfn: ThreadsafeFunction<RequestContext, ErrorStrategy::Fatal> = handler
-            .create_threadsafe_function(0, |ctx| {
-                Ok(vec![ctx.value])
-            })?;
+            // OPTIMIZATION: Lock-free read of invoke handler using ArcSwap
+            let invoke_guard = select_invoke_handler(&state, path);
+            if let Some(ref handler) = invoke_guard {
+                // Extract all data from req BEFORE consuming it
+                let method_str_owned = method_str.to_string();
+                let path_owned = path.to_string();
+                let query_owned = req.uri().query().unwrap_or("").to_string();
 
-        let dynamic_handler = DynamicHandler { callback: tsfn };
+                // OPTIMIZATION: Check if we can skip body reading entirely (GET/HEAD have no body)
+                let skip_body = method == Method::Get || method == Method::Head;
 
-        // Store handler in HashMap
-        self.state.dynamic_handlers.blocking_write().insert(handler_id, dynamic_handler);
+                // OPTIMIZATION: Sucrose-style - skip header collection for simple GET/HEAD routes
+                // If route has no params and is GET/HEAD, handler likely doesn't need headers
+                let skip_headers = skip_body && params.is_empty();
 
-        // Insert route into router
-        self.state.router.blocking_write().insert(&method, &path, handler_id);
+                // Batched transfer: flat buffers crossing the JS boundary
+                // once, decoded lazily on the JS side
+                let batched = state.batched_headers.load(Ordering::Relaxed);
 
-        Ok(())
-    }
+                // Collect headers only if needed (Sucrose-style optimization)
+                let (headers_map, headers_buffer) = if batched && !skip_headers {
+                    (HashMap::new(), Some(encode_headers_buffer(req.headers())))
+                } else if skip_headers {
+                    // Fast path: empty headers for simple GET/HEAD without params
+                    (HashMap::new(), None)
+                } else {
+                    // Full path: collect headers with pre-allocated capacity
+                    let mut map = HashMap::with_capacity(req.headers().len());
+                    for (name, value) in req.headers() {
+                        if let Ok(v) = value.to_str() {
+                            map.insert(name.as_str().to_lowercase(), v.to_string());
+                        }
+                    }
+                    (map, None)
+                };
 
-    /// Set fallback handler for unmatched routes
-    #[napi]
-    pub fn set_fallback(
-        &self,
-        handler: JsFunction,
-    ) -> Result<()> {
-        let tsfn: ThreadsafeFunction<RequestContext, ErrorStrategy::Fatal> = handler
-            .create_threadsafe_function(0, |ctx| {
-                Ok(vec![ctx.value])
-            })?;
+                // OPTIMIZATION: Skip body size check and reading for GET/HEAD
+                let body_bytes = if skip_body {
+                    // GET/HEAD - no body, skip entirely
+                    Bytes::new()
+                } else {
+                    // POST/PUT/PATCH/etc - need to read body under the
+                    // per-content-type limit
+                    let content_type = req
+                        .headers()
+                        .get(hyper::header::CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok());
+                    let max_body_size = effective_body_limit(&state, content_type);
 
-        let handler = DynamicHandler { callback: tsfn };
-        *self.state.fallback_handler.blocking_write() = Some(handler);
-        Ok(())
-    }
+                    // Check body size limit from Content-Length header
+                    // (read from the hyper map - headers_map is empty in batched mode)
+                    if let Some(len) = req
+                        .headers()
+                        .get(hyper::header::CONTENT_LENGTH)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|s| s.parse::<usize>().ok())
+                    {
+                        if len > max_body_size {
+                            return Ok(hyper::Response::builder()
+                                .status(413)
+                                .header("content-type", "text/plain")
+                                .body(Full::new(Bytes::from("Request Entity Too Large")))
+                                .unwrap());
+                        }
+                    }
 
-    // ========================================================================
-    // GustApp Integration (Route Registration Pattern)
-    // ========================================================================
+                    // Read body under the body-phase deadline,
+                    // enforcing the limit while streaming
+                    let request_timeout = phase_timeout_ms(
+                        &state,
+                        Some(&route_pattern),
+                        gust_core::TimeoutPhase::Body,
+                    );
+                    match read_body_limited(&state, req.into_body(), max_body_size, request_timeout).await {
+                        BodyRead::Complete(bytes) => bytes,
+                        BodyRead::TooLarge => {
+                            return Ok(hyper::Response::builder()
+                                .status(413)
+                                .header("content-type", "text/plain")
+                                .body(Full::new(Bytes::from("Request Entity Too Large")))
+                                .unwrap());
+                        }
+                        BodyRead::Timeout => {
+                            return Ok(hyper::Response::builder()
+                                .status(408)
+                                .header("content-type", "text/plain")
+                                .body(Full::new(Bytes::from("Request Timeout")))
+                                .unwrap());
+                        }
+                    }
+                };
 
-    /// Register routes from GustApp manifest
-    ///
-    /// This enables Rust-side routing with handler ID dispatch.
-    /// Routes are registered in the Rust Radix Trie router.
-    /// When a request matches, `invoke_handler(handler_id, ctx)` is called.
-    ///
-    /// @example
-    /// ```typescript
-    /// const app = createApp({ routes: [...] })
-    /// server.registerRoutes(app.manifest)
-    /// server.setInvokeHandler(app.invokeHandler)
-    /// ```
-    #[napi]
-    pub async fn register_routes(&self, manifest: RouteManifest) -> Result<()> {
-        // Build new router - this happens at startup, not on hot path
-        let mut new_router = Router::new();
+                // Create native handler context
+                // Batched transfer sends params as a flat name/value list
+                let (params, params_flat) = if batched {
+                    let mut flat = Vec::with_capacity(params.len() * 2);
+                    for (name, value) in params {
+                        flat.push(name);
+                        flat.push(value);
+                    }
+                    (HashMap::new(), Some(flat))
+                } else {
+                    (params, None)
+                };
 
-        for entry in manifest.routes {
-            // Use insert() instead of route() - new gust-router API
-            new_router.insert(&entry.method, &entry.path, entry.handler_id);
-        }
+                let native_ctx = NativeHandlerContext {
+                    method: method_str_owned,
+                    path: path_owned,
+                    route_pattern: Some(route_pattern.clone()),
+                    query: query_owned,
+                    headers: headers_map,
+                    params,
+                    body: body_bytes.to_vec(),
+                    headers_buffer,
+                    params_flat,
+                    ip: Some(client.ip.clone()),
+                    protocol: Some(client.protocol.clone()),
+                    host: Some(client.host.clone()),
+                };
 
-        // Atomic swap with ArcSwap - lock-free on read path
-        self.state.app_routes.store(Arc::new(new_router));
-        Ok(())
-    }
+                // Create input for invoke handler
+                let input = InvokeHandlerInput {
+                    handler_id,
+                    ctx: native_ctx,
+                };
 
-    /// Set the invoke handler callback from GustApp
-    ///
-    /// This callback is called when a route matches with:
-    /// - `handlerId`: The handler ID from the route manifest
-    /// - `ctx`: The native handler context with parsed request data
-    ///
-    /// The callback should return a ResponseData (or Promise<ResponseData>).
-    ///
-    /// @example
-    /// ```typescript
-    /// const app = createApp({ routes: [...] })
-    /// server.setInvokeHandler(app.invokeHandler)
-    /// ```
-    #[napi]
-    pub fn set_invoke_handler(&self, handler: JsFunction) -> Result<()> {
-        // Create threadsafe function that accepts (handlerId, context) tuple
-        let tsfn: InvokeHandlerCallback = handler
-            .create_threadsafe_function(0, |ctx| {
-                // ctx.value is (u32, NativeHandlerContext)
-                // We need to convert this to JS arguments
-                Ok(vec![ctx.value])
-            })?;
+                // Call invoke handler under the handler-phase deadline
+                let handler_call = call_invoke_handler(&handler.callback, input);
+                let response =
+                    match run_handler_limited(&state, Some(&route_pattern), handler_call).await {
+                        Some(response) => response,
+                        None => {
+                            return Ok(hyper::Response::builder()
+                                .status(504)
+                                .header("content-type", "text/plain")
+                                .body(Full::new(Bytes::from("Gateway Timeout")))
+                                .unwrap());
+                        }
+                    };
+                return Ok(to_hyper_response(response_data_to_response(response)));
+            }
+        }
+    }
 
-        let invoke = InvokeHandler { callback: tsfn };
-        // Use ArcSwap for lock-free atomic swap
-        self.state.invoke_handler.store(Arc::new(Some(invoke)));
-        Ok(())
+    // Admin endpoint (exact-path match, runtime observability control)
+    {
+        let is_admin = {
+            let admin_path = state.admin_path.read().await;
+            admin_path.as_deref() == Some(path)
+        };
+        if is_admin {
+            let response = handle_admin_request(&state, req).await;
+            return Ok(to_hyper_response(response));
+        }
     }
 
-    /// Check if app routes pattern is configured
-    /// Returns true if invoke_handler is set
-    #[napi]
-    pub fn has_app_routes(&self) -> bool {
-        // Lock-free read with ArcSwap
-        self.state.invoke_handler.load().is_some()
+    // Profiling endpoint (exact-path match, token-gated); only
+    // intercepted once enableProfiling() configured a token
+    if path == PROFILE_PATH && state.profile_token.read().await.is_some() {
+        return Ok(handle_profile_request(&state, req.uri(), req.headers()).await);
     }
 
-    /// Clear all app routes (for hot reload)
-    #[napi]
-    pub fn clear_app_routes(&self) -> Result<()> {
-        // Atomic swap with ArcSwap - lock-free
-        self.state.app_routes.store(Arc::new(Router::new()));
-        Ok(())
+    // WebSocket routes (exact-path match): the 101 upgrade happens
+    // here, then the Rust frame loop owns the connection
+    {
+        let ws_route = {
+            let routes = state.ws_routes.read().await;
+            routes.get(path).cloned()
+        };
+        if let Some(route) = ws_route {
+            return Ok(handle_ws_upgrade(state.clone(), req, route, peer));
+        }
     }
 
-    /// Start the server (non-blocking)
-    #[napi]
-    pub async fn serve(&self, port: u32) -> Result<()> {
-        self.serve_with_hostname(port, "0.0.0.0".to_string()).await
+    // GraphQL routes (exact-path match, transport details handled in Rust)
+    {
+        let graphql_route = {
+            let routes = state.graphql_routes.read().await;
+            routes.get(path).cloned()
+        };
+        if let Some(route) = graphql_route {
+            let response = handle_graphql_request(state, req, route, client).await;
+            return Ok(to_hyper_response(response));
+        }
     }
 
-    /// Start the server with custom hostname (non-blocking)
-    #[napi]
-    pub async fn serve_with_hostname(&self, port: u32, hostname: String) -> Result<()> {
-        use std::net::SocketAddr;
-        use tokio::net::TcpListener; // from gust_core::tokio
+    // JSON-RPC dispatchers (exact-path match, envelope handled in Rust)
+    {
+        let jsonrpc_route = {
+            let routes = state.jsonrpc_routes.read().await;
+            routes.get(path).cloned()
+        };
+        if let Some(route) = jsonrpc_route {
+            let response = handle_jsonrpc_request(state, req, route, client).await;
+            return Ok(to_hyper_response(response));
+        }
+    }
 
-        let addr: SocketAddr = format!("{}:{}", hostname, port)
-            .parse()
-            .map_err(|e| Error::from_reason(format!("Invalid address: {}", e)))?;
+    // tus upload endpoints (base path + upload sub-paths handled in Rust)
+    {
+        let tus_route = {
+            let routes = state.tus_routes.read().await;
+            routes
+                .iter()
+                .find(|(base, _)| {
+                    path == base.as_str()
+                        || (path.starts_with(base.as_str())
+                            && path.as_bytes().get(base.len()) == Some(&b'/'))
+                })
+                .map(|(base, handler)| (base.clone(), handler.clone()))
+        };
+        if let Some((base, handler)) = tus_route {
+            let response = handle_tus_request(state, req, handler, &base).await;
+            return Ok(to_hyper_response(response));
+        }
+    }
 
-        let state = self.state.clone();
-        let tls_config = state.tls_config.read().await.clone();
-        let http2_enabled = state.http2_enabled.load(Ordering::Relaxed);
+    // Embedded asset mounts (prefix match, served from memory)
+    {
+        let embedded_route = {
+            let routes = state.embedded_routes.read().await;
+            routes
+                .iter()
+                .find(|(prefix, _)| {
+                    path == prefix.as_str()
+                        || (path.starts_with(prefix.as_str())
+                            && path.as_bytes().get(prefix.len()) == Some(&b'/'))
+                })
+                .map(|(prefix, handler)| (prefix.clone(), handler.clone()))
+        };
+        if let Some((prefix, handler)) = embedded_route {
+            let rest = &path[prefix.len()..];
+            let relative = if rest.is_empty() { "/" } else { rest };
+            let method = Method::from_str(method_str).unwrap_or(Method::Get);
+            let mut builder = gust_core::RequestBuilder::new(method, relative);
+            for (name, value) in req.headers() {
+                if let Ok(v) = value.to_str() {
+                    builder = builder.header(name.as_str(), v);
+                }
+            }
+            let request = builder.build();
+            let response = handler.handle(&request).await;
+            return Ok(to_hyper_response(response));
+        }
+    }
 
-        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
-        *self.shutdown_tx.write().await = Some(shutdown_tx);
+    // Check middleware early to know if we need request object
+    let has_middleware = !state.middleware.load().is_empty();
 
-        let listener = TcpListener::bind(addr)
-            .await
-            .map_err(|e| Error::from_reason(format!("Bind error: {}", e)))?;
+    // FAST PATH: No middleware, check if we can use fallback directly
+    if !has_middleware {
+        // Note: Legacy routes already checked above, so this section is for fallback only
+        // If no route matched, try fallback
+        if true {
+            // No dynamic route match - try fallback with minimal context
+            let fallback = state.fallback_handler.read().await.clone();
+            if let Some(handler) = fallback {
+                // Minimal context for fallback - only allocate what's needed
+                let ctx = RequestContext {
+                    method: method_str.to_string(),
+                    path: path.to_string(),
+                    route_pattern: None,
+                    query: req.uri().query().map(|s| s.to_string()),
+                    params: HashMap::new(),
+                    headers: HashMap::new(), // Empty for fast path
+                    body: String::new(),     // Skip body for GET/HEAD
+                    body_buffer: None,
+                    ip: Some(client.ip.clone()),
+                    protocol: Some(client.protocol.clone()),
+                    host: Some(client.host.clone()),
+                };
 
-        // Spawn server task
-        #[allow(unused_variables)]
-        if let Some(tls) = tls_config {
-            // TLS server
-            #[cfg(feature = "tls")]
-            {
-                self.serve_tls(listener, tls, http2_enabled, state, shutdown_rx).await?;
-            }
-            #[cfg(not(feature = "tls"))]
-            {
-                let _ = (tls, http2_enabled); // Suppress unused variable warning
-                return Err(Error::from_reason("TLS support not enabled. Compile with 'tls' feature.".to_string()));
+                let handler_call = call_js_handler(&handler, ctx);
+                let response = match run_handler_limited(&state, None, handler_call).await {
+                    Some(response) => response,
+                    None => {
+                        return Ok(hyper::Response::builder()
+                            .status(504)
+                            .header("content-type", "text/plain")
+                            .body(Full::new(Bytes::from("Gateway Timeout")))
+                            .unwrap());
+                    }
+                };
+                return Ok(to_hyper_response(response_data_to_response(response)));
             }
-        } else {
-            // Plain HTTP server
-            self.serve_http(listener, http2_enabled, state, shutdown_rx).await?;
-        }
 
-        Ok(())
+            // No fallback - 404
+            return Ok(to_hyper_response(Response::not_found()));
+        }
     }
 
-    /// Serve HTTP (non-TLS) connections
-    #[allow(unused_variables)]
-    async fn serve_http(
-        &self,
-        listener: tokio::net::TcpListener,
-        http2_enabled: bool, // Reserved for future h2c support
-        state: Arc<ServerState>,
-        shutdown_rx: tokio::sync::oneshot::Receiver<()>,
-    ) -> Result<()> {
-        // Use re-exports from gust_core
-        use hyper::server::conn::http1;
-        use hyper::service::service_fn;
-        use hyper_util::rt::TokioIo;
+    // For dynamic routes with middleware, we need full context
+    let method_str = method_str.to_string();
+    let path = path.to_string();
+    let query = req.uri().query().map(|s| s.to_string());
 
-        let tracker = self.connection_tracker.clone();
+    // Collect headers into a pooled HashMap (returned to the pool on drop)
+    let mut headers_map = request_pools().header_maps.get();
+    for (name, value) in req.headers() {
+        if let Ok(v) = value.to_str() {
+            headers_map.insert(name.as_str().to_lowercase(), v.to_string());
+        }
+    }
 
-        tokio::spawn(async move {
-            tokio::select! {
-                _ = async {
-                    loop {
-                        let (stream, _) = match listener.accept().await {
-                            Ok(conn) => conn,
-                            Err(_) => continue,
-                        };
+    // Create request object for middleware (if needed)
+    let middleware = state.middleware.load();
+    let request = if has_middleware {
+        let mut mw_req = Request::new(method, path.clone());
+        mw_req.query = query.clone();
+        for (name, value) in headers_map.iter() {
+            mw_req.headers.push((name.clone(), value.clone()));
+        }
+        // Run before middleware
+        if let Some(early_response) = middleware.run_before(&mut mw_req) {
+            return Ok(to_hyper_response(early_response));
+        }
+        Some(mw_req)
+    } else {
+        None
+    };
+    drop(middleware);
 
-                        // Reject new connections during shutdown
-                        if tracker.is_shutting_down() {
-                            drop(stream);
-                            continue;
-                        }
+    // Correlation IDs assigned by the tracing middleware; threaded into
+    // handler context params so JS loggers can pick them up
+    let tracing_params: Vec<(String, String)> = request
+        .as_ref()
+        .map(|mw_req| {
+            ["_request_id", "_trace_id", "_span_id"]
+                .iter()
+                .filter_map(|k| mw_req.params.get(*k).map(|v| ((*k).to_string(), v.clone())))
+                .collect()
+        })
+        .unwrap_or_default();
 
-                        let state = state.clone();
-                        let conn_tracker = tracker.clone();
-                        conn_tracker.increment();
+    // 2. Try legacy routes again with middleware (shouldn't happen often)
+    // This path is only for cases where middleware exists and modifies request
+    let legacy_result = {
+        let router = state.router.read().await;
+        router.find_with_query(&method_str, &path, query.as_deref())
+    };
 
-                        tokio::spawn(async move {
-                            let io = TokioIo::new(stream);
-                            let service = service_fn(move |req| {
-                                let state = state.clone();
-                                async move {
-                                    handle_request(state, req).await
-                                }
-                            });
+    if let Some(matched) = legacy_result {
+        let handler_id = matched.handler_id;
+        let route_pattern = matched.pattern;
+        let mut params: HashMap<String, String> = matched.params.into_iter().collect();
+        params.extend(tracing_params.iter().cloned());
 
-                            // HTTP/2 over clear text (h2c) is less common, use HTTP/1.1 by default
-                            if let Err(e) = http1::Builder::new()
-                                .serve_connection(io, service)
-                                .await
-                            {
-                                // Only log if not a normal connection close
-                                if !e.to_string().contains("connection closed") {
-                                    eprintln!("Connection error: {}", e);
-                                }
-                            }
+        // Try dynamic handler
+        let dynamic_handlers = state.dynamic_handlers.read().await;
+        if let Some(handler) = dynamic_handlers.get(&handler_id).cloned() {
+            drop(dynamic_handlers);
 
-                            conn_tracker.decrement();
-                        });
+            // Check body size limit (per-content-type, lock-free read)
+            let max_body_size =
+                effective_body_limit(&state, headers_map.get("content-type").map(|s| s.as_str()));
+            if let Some(content_length) = headers_map.get("content-length") {
+                if let Ok(len) = content_length.parse::<usize>() {
+                    if len > max_body_size {
+                        return Ok(hyper::Response::builder()
+                            .status(413)
+                            .header("content-type", "text/plain")
+                            .body(Full::new(Bytes::from("Request Entity Too Large")))
+                            .unwrap());
                     }
-                } => {}
-                _ = shutdown_rx => {
-                    // Signal shutdown - new connections will be rejected
-                    tracker.start_shutdown();
                 }
             }
-        });
 
-        Ok(())
+            // Read body for dynamic handlers, enforcing the limit while streaming
+            let request_timeout = phase_timeout_ms(
+                &state,
+                Some(&route_pattern),
+                gust_core::TimeoutPhase::Body,
+            );
+            let body_bytes = match read_body_limited(&state, req.into_body(), max_body_size, request_timeout).await {
+                BodyRead::Complete(bytes) => bytes,
+                BodyRead::TooLarge => {
+                    return Ok(hyper::Response::builder()
+                        .status(413)
+                        .header("content-type", "text/plain")
+                        .body(Full::new(Bytes::from("Request Entity Too Large")))
+                        .unwrap());
+                }
+                BodyRead::Timeout => {
+                    return Ok(hyper::Response::builder()
+                        .status(408)
+                        .header("content-type", "text/plain")
+                        .body(Full::new(Bytes::from("Request Timeout")))
+                        .unwrap());
+                }
+            };
+            let (body_str, body_buffer) = context_body(&body_bytes);
+
+            // Create RequestContext for JS handler (matches TypeScript interface)
+            let ctx = RequestContext {
+                method: method_str.clone(),
+                path: path.clone(),
+                route_pattern: Some(route_pattern.clone()),
+                query,
+                params,
+                headers: (*headers_map).clone(),
+                body: body_str,
+                body_buffer,
+                ip: Some(client.ip.clone()),
+                protocol: Some(client.protocol.clone()),
+                host: Some(client.host.clone()),
+            };
+
+            // Call JS handler under the handler-phase deadline
+            let handler_call = call_js_handler(&handler, ctx);
+            let response =
+                match run_handler_limited(&state, Some(&route_pattern), handler_call).await {
+                    Some(response) => response,
+                    None => {
+                        return Ok(hyper::Response::builder()
+                            .status(504)
+                            .header("content-type", "text/plain")
+                            .body(Full::new(Bytes::from("Gateway Timeout")))
+                            .unwrap());
+                    }
+                };
+            let mut our_response = response_data_to_response(response);
+
+            // Apply middleware chain (after) - only if middleware exists
+            if let Some(ref req) = request {
+                let middleware = state.middleware.load();
+                middleware.run_after(req, &mut our_response);
+            }
+
+            return Ok(to_hyper_response(our_response));
+        }
     }
 
-    /// Serve TLS connections with optional HTTP/2
-    #[cfg(feature = "tls")]
-    async fn serve_tls(
-        &self,
-        listener: tokio::net::TcpListener,
-        tls_config: TlsConfig,
-        http2_enabled: bool,
-        state: Arc<ServerState>,
-        shutdown_rx: tokio::sync::oneshot::Receiver<()>,
-    ) -> Result<()> {
-        // Use re-exports from gust_core
-        use hyper::server::conn::http1;
-        use hyper::server::conn::http2;
-        use hyper::service::service_fn;
-        use hyper_util::rt::TokioIo;
+    // 3. Try fallback handler
+    let fallback = state.fallback_handler.read().await.clone();
+    if let Some(handler) = fallback {
+        // Check body size limit (per-content-type, lock-free read)
+        let max_body_size =
+            effective_body_limit(&state, headers_map.get("content-type").map(|s| s.as_str()));
+        if let Some(content_length) = headers_map.get("content-length") {
+            if let Ok(len) = content_length.parse::<usize>() {
+                if len > max_body_size {
+                    return Ok(hyper::Response::builder()
+                        .status(413)
+                        .header("content-type", "text/plain")
+                        .body(Full::new(Bytes::from("Request Entity Too Large")))
+                        .unwrap());
+                }
+            }
+        }
 
-        // Load TLS configuration
-        let tls_acceptor = load_tls_config(&tls_config, http2_enabled)
-            .map_err(|e| Error::from_reason(format!("TLS config error: {}", e)))?;
+        // Read body for the fallback handler, enforcing the limit while streaming
+        let request_timeout = phase_timeout_ms(&state, None, gust_core::TimeoutPhase::Body);
+        let body_bytes = match read_body_limited(&state, req.into_body(), max_body_size, request_timeout).await {
+            BodyRead::Complete(bytes) => bytes,
+            BodyRead::TooLarge => {
+                return Ok(hyper::Response::builder()
+                    .status(413)
+                    .header("content-type", "text/plain")
+                    .body(Full::new(Bytes::from("Request Entity Too Large")))
+                    .unwrap());
+            }
+            BodyRead::Timeout => {
+                return Ok(hyper::Response::builder()
+                    .status(408)
+                    .header("content-type", "text/plain")
+                    .body(Full::new(Bytes::from("Request Timeout")))
+                    .unwrap());
+            }
+        };
+        let (body_str, body_buffer) = context_body(&body_bytes);
 
-        let tracker = self.connection_tracker.clone();
+        let ctx = RequestContext {
+            method: method_str,
+            path: path.clone(),
+            route_pattern: None,
+            query,
+            params: tracing_params.into_iter().collect(),
+            // Context owns the map, so it can't go back to the pool
+            headers: headers_map.detach(),
+            body: body_str,
+            body_buffer,
+            ip: Some(client.ip.clone()),
+            protocol: Some(client.protocol.clone()),
+            host: Some(client.host.clone()),
+        };
 
-        tokio::spawn(async move {
-            tokio::select! {
-                _ = async {
-                    loop {
-                        let (stream, _) = match listener.accept().await {
-                            Ok(conn) => conn,
-                            Err(_) => continue,
-                        };
+        let handler_call = call_js_handler(&handler, ctx);
+        let response = match run_handler_limited(&state, None, handler_call).await {
+            Some(response) => response,
+            None => {
+                return Ok(hyper::Response::builder()
+                    .status(504)
+                    .header("content-type", "text/plain")
+                    .body(Full::new(Bytes::from("Gateway Timeout")))
+                    .unwrap());
+            }
+        };
+        let mut our_response = response_data_to_response(response);
 
-                        // Reject new connections during shutdown
-                        if tracker.is_shutting_down() {
-                            drop(stream);
-                            continue;
-                        }
+        // Apply middleware chain (after) - only if middleware exists
+        if let Some(ref req) = request {
+            let middleware = state.middleware.load();
+            middleware.run_after(req, &mut our_response);
+        }
 
-                        let acceptor = tls_acceptor.clone();
-                        let state = state.clone();
-                        let http2 = http2_enabled;
-                        let conn_tracker = tracker.clone();
-                        conn_tracker.increment();
+        return Ok(to_hyper_response(our_response));
+    }
 
-                        tokio::spawn(async move {
-                            // TLS handshake
-                            let tls_stream = match acceptor.accept(stream).await {
-                                Ok(s) => s,
-                                Err(e) => {
-                                    // Only log if not a normal connection close
-                                    if !e.to_string().contains("connection closed") {
-                                        eprintln!("TLS handshake error: {}", e);
-                                    }
-                                    conn_tracker.decrement();
-                                    return;
-                                }
-                            };
+    // 4. No route matched - 404
+    let mut our_response = Response::not_found();
+    if let Some(ref req) = request {
+        let middleware = state.middleware.load();
+        middleware.run_after(req, &mut our_response);
+    }
 
-                            let io = TokioIo::new(tls_stream);
-                            let service = service_fn(move |req| {
-                                let state = state.clone();
-                                async move {
-                                    handle_request(state, req).await
-                                }
-                            });
+    Ok(to_hyper_response(our_response))
+}
+
+/// Handle a request from the raw HTTP/1.1 engine
+///
+/// Covers the benchmark-relevant paths: legacy dynamic handlers and
+/// app routes (static routes are served by the engine's pre-rendered
+/// lookup). The middleware chain and the protocol handlers (GraphQL,
+/// JSON-RPC, tus, embedded assets) require the hyper engine, as does
+/// per-request client info (`ip`/`protocol`/`host`) — the raw engine's
+/// handler does not receive the peer address.
+async fn handle_raw_request(state: Arc<ServerState>, req: Request) -> Response {
+    let method_str = req.method.to_string();
+
+    // Legacy dynamic routes
+    let legacy_result = {
+        let router = state.router.read().await;
+        router.find_with_query(&method_str, &req.path, req.query.as_deref())
+    };
+    if let Some(matched) = legacy_result {
+        let handler = state
+            .dynamic_handlers
+            .read()
+            .await
+            .get(&matched.handler_id)
+            .cloned();
+        if let Some(handler) = handler {
+            let mut headers = HashMap::with_capacity(req.headers.len());
+            for (name, value) in &req.headers {
+                headers.insert(name.to_lowercase(), value.clone());
+            }
+            let route_pattern = matched.pattern;
+            let (body, body_buffer) = context_body(&req.body);
+            let ctx = RequestContext {
+                method: method_str,
+                path: req.path.clone(),
+                route_pattern: Some(route_pattern.clone()),
+                query: req.query.clone(),
+                params: matched.params.into_iter().collect(),
+                headers,
+                body,
+                body_buffer,
+                ip: None,
+                protocol: None,
+                host: None,
+            };
+            let handler_call = call_js_handler(&handler, ctx);
+            let Some(response) =
+                run_handler_limited(&state, Some(&route_pattern), handler_call).await
+            else {
+                return ResponseBuilder::new(StatusCode(504))
+                    .header("content-type", "text/plain")
+                    .body("Gateway Timeout")
+                    .build();
+            };
+            return response_data_to_response(response);
+        }
+    }
 
-                            // Use HTTP/2 if enabled and negotiated via ALPN
-                            if http2 {
-                                // Try HTTP/2 first, fall back to HTTP/1.1
-                                if let Err(e) = http2::Builder::new(TokioExecutor)
-                                    .serve_connection(io, service)
-                                    .await
-                                {
-                                    if !e.to_string().contains("connection closed") {
-                                        eprintln!("HTTP/2 connection error: {}", e);
-                                    }
-                                }
-                            } else {
-                                if let Err(e) = http1::Builder::new()
-                                    .serve_connection(io, service)
-                                    .await
-                                {
-                                    if !e.to_string().contains("connection closed") {
-                                        eprintln!("HTTP/1.1 connection error: {}", e);
-                                    }
-                                }
-                            }
+    // App routes (Rust routing, ID-based dispatch via the JS invoke handler)
+    let routes = state.app_routes.load();
+    if let Some(matched) = routes.find_with_query(&method_str, &req.path, req.query.as_deref()) {
+        let invoke_guard = select_invoke_handler(&state, &req.path);
+        if let Some(ref handler) = invoke_guard {
+            let mut headers = HashMap::with_capacity(req.headers.len());
+            for (name, value) in &req.headers {
+                headers.insert(name.to_lowercase(), value.clone());
+            }
+            let route_pattern = matched.pattern;
+            let native_ctx = NativeHandlerContext {
+                method: method_str,
+                path: req.path.clone(),
+                route_pattern: Some(route_pattern.clone()),
+                query: req.query.clone().unwrap_or_default(),
+                headers,
+                params: matched.params.into_iter().collect(),
+                body: req.body.to_vec(),
+                headers_buffer: None,
+                params_flat: None,
+                ip: None,
+                protocol: None,
+                host: None,
+            };
+            let input = InvokeHandlerInput {
+                handler_id: matched.handler_id,
+                ctx: native_ctx,
+            };
+            let handler_call = call_invoke_handler(&handler.callback, input);
+            let Some(response) =
+                run_handler_limited(&state, Some(&route_pattern), handler_call).await
+            else {
+                return ResponseBuilder::new(StatusCode(504))
+                    .header("content-type", "text/plain")
+                    .body("Gateway Timeout")
+                    .build();
+            };
+            return response_data_to_response(response);
+        }
+    }
 
-                            conn_tracker.decrement();
-                        });
-                    }
-                } => {}
-                _ = shutdown_rx => {
-                    // Signal shutdown - new connections will be rejected
-                    tracker.start_shutdown();
-                }
+    Response::not_found()
+}
+
+/// Handle a request to the admin observability endpoint
+///
+/// GET reports the current settings; PUT/POST applies a partial JSON
+/// update. Validation errors come back as 400 with a JSON error body
+/// and leave the running config untouched.
+/// Serve one connection on the admin listener
+///
+/// Works over TCP and unix sockets alike; the admin endpoints never
+/// need the peer address.
+fn serve_admin_connection<S>(stream: S, state: Arc<ServerState>, tracker: Arc<CoreConnectionTracker>)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper_util::rt::TokioIo;
+
+    tokio::spawn(async move {
+        let io = TokioIo::new(stream);
+        let service = service_fn(move |req| {
+            let state = state.clone();
+            let tracker = tracker.clone();
+            async move {
+                Ok::<_, std::convert::Infallible>(
+                    handle_admin_listener_request(state, tracker, req).await,
+                )
             }
         });
+        let _ = http1::Builder::new().serve_connection(io, service).await;
+    });
+}
 
-        Ok(())
-    }
+/// The profiling endpoint path on the main listener
+const PROFILE_PATH: &str = "/_gust/profile";
 
-    /// Shutdown the server immediately (doesn't wait for connections)
-    #[napi]
-    pub async fn shutdown(&self) {
-        self.connection_tracker.start_shutdown();
-        if let Some(tx) = self.shutdown_tx.write().await.take() {
-            let _ = tx.send(());
-        }
+/// Handle a profile capture request (`/_gust/profile` on the main
+/// listener, `/profile` on the admin listener)
+///
+/// Verifies the bearer token, runs a capture for `seconds` (default
+/// 10, capped at 120), and returns folded stacks. 404 when profiling
+/// was never enabled, 401 on a missing or wrong token, and 409 when
+/// a capture is already running.
+async fn handle_profile_request(
+    state: &Arc<ServerState>,
+    uri: &hyper::Uri,
+    headers: &hyper::HeaderMap,
+) -> hyper::Response<Full<Bytes>> {
+    fn plain(status: u16, body: &'static str) -> hyper::Response<Full<Bytes>> {
+        hyper::Response::builder()
+            .status(status)
+            .header("content-type", "text/plain")
+            .body(Full::new(Bytes::from(body)))
+            .unwrap()
     }
 
-    /// Graceful shutdown - waits for active connections to drain
-    /// timeout_ms: Maximum time to wait for connections to drain (0 = no timeout)
-    /// Returns true if all connections drained, false if timeout reached
-    #[napi]
-    pub async fn graceful_shutdown(&self, timeout_ms: u32) -> bool {
-        // Signal shutdown to stop accepting new connections
-        self.connection_tracker.start_shutdown();
+    let token = state.profile_token.read().await.clone();
+    let Some(token) = token else {
+        return plain(404, "Not Found");
+    };
+    let authorized = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|presented| presented == token)
+        .unwrap_or(false);
+    if !authorized {
+        return plain(401, "Unauthorized");
+    }
 
-        // Send shutdown signal to server loop
-        if let Some(tx) = self.shutdown_tx.write().await.take() {
-            let _ = tx.send(());
-        }
+    let seconds = uri
+        .query()
+        .and_then(|q| q.split('&').find_map(|pair| pair.strip_prefix("seconds=")))
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(10)
+        .clamp(1, 120);
 
-        // Wait for connections to drain
-        let start = std::time::Instant::now();
-        let timeout = if timeout_ms > 0 {
-            Some(Duration::from_millis(timeout_ms as u64))
-        } else {
-            None
-        };
+    let Ok(_capture) = state.profile_capture.try_lock() else {
+        return plain(409, "Profile capture already in progress");
+    };
 
-        loop {
-            let active = self.connection_tracker.count();
-            if active == 0 {
-                return true; // All connections drained
-            }
+    let profiler = Arc::new(gust_core::Profiler::new());
+    state.profiler.store(Arc::new(Some(Arc::clone(&profiler))));
+    tokio::time::sleep(Duration::from_secs(seconds)).await;
+    state.profiler.store(Arc::new(None));
 
-            // Check timeout
-            if let Some(t) = timeout {
-                if start.elapsed() >= t {
-                    return false; // Timeout reached
-                }
-            }
+    hyper::Response::builder()
+        .status(200)
+        .header("content-type", "text/plain")
+        .body(Full::new(Bytes::from(profiler.folded())))
+        .unwrap()
+}
 
-            // Wait a bit before checking again
-            tokio::time::sleep(Duration::from_millis(10)).await;
+/// Route a request on the admin listener: health, metrics, profile
+/// captures, and the runtime observability endpoint — nothing else
+async fn handle_admin_listener_request(
+    state: Arc<ServerState>,
+    tracker: Arc<CoreConnectionTracker>,
+    req: hyper::Request<hyper::body::Incoming>,
+) -> hyper::Response<Full<Bytes>> {
+    match req.uri().path() {
+        "/health" => {
+            let status = if tracker.is_shutting_down() {
+                "shutting_down"
+            } else {
+                "ok"
+            };
+            hyper::Response::builder()
+                .status(200)
+                .header("content-type", "application/json")
+                .body(Full::new(Bytes::from(format!(
+                    r#"{{"status":"{}","activeConnections":{}}}"#,
+                    status,
+                    tracker.count()
+                ))))
+                .unwrap()
+        }
+        "/metrics" => {
+            // OpenMetrics scrapers negotiate via Accept and require
+            // the `# EOF` terminator; Prometheus text 0.0.4 otherwise
+            let openmetrics = req
+                .headers()
+                .get("accept")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.contains("application/openmetrics-text"))
+                .unwrap_or(false);
+            let stats = tracker.keep_alive_stats();
+            let mut body = format!(
+                "# TYPE gust_connections_active gauge\n\
+                 gust_connections_active {}\n\
+                 # TYPE gust_connections_total counter\n\
+                 gust_connections_total {}\n\
+                 # TYPE gust_requests_total counter\n\
+                 gust_requests_total {}\n\
+                 # TYPE gust_pipelined_rejected_total counter\n\
+                 gust_pipelined_rejected_total {}\n\
+                 # TYPE gust_timeouts_total counter\n\
+                 gust_timeouts_total{{phase=\"body\"}} {}\n\
+                 gust_timeouts_total{{phase=\"handler\"}} {}\n\
+                 gust_timeouts_total{{phase=\"total\"}} {}\n",
+                tracker.count(),
+                stats.total_connections,
+                stats.total_requests,
+                stats.pipelined_rejected,
+                state.timeouts_body.load(Ordering::Relaxed),
+                state.timeouts_handler.load(Ordering::Relaxed),
+                state.timeouts_total.load(Ordering::Relaxed),
+            );
+            let rt = collect_runtime_stats();
+            body.push_str(&format!(
+                "# TYPE gust_tokio_workers gauge\n\
+                 gust_tokio_workers {}\n\
+                 # TYPE gust_tokio_alive_tasks gauge\n\
+                 gust_tokio_alive_tasks {}\n\
+                 # TYPE gust_tokio_global_queue_depth gauge\n\
+                 gust_tokio_global_queue_depth {}\n\
+                 # TYPE gust_tokio_busy_ms_total counter\n\
+                 gust_tokio_busy_ms_total {}\n\
+                 # TYPE gust_tokio_park_total counter\n\
+                 gust_tokio_park_total {}\n",
+                rt.workers, rt.alive_tasks, rt.global_queue_depth, rt.busy_ms, rt.park_count,
+            ));
+            let content_type = if openmetrics {
+                body.push_str("# EOF\n");
+                "application/openmetrics-text; version=1.0.0; charset=utf-8"
+            } else {
+                "text/plain; version=0.0.4"
+            };
+            hyper::Response::builder()
+                .status(200)
+                .header("content-type", content_type)
+                .body(Full::new(Bytes::from(body)))
+                .unwrap()
         }
+        "/profile" => handle_profile_request(&state, req.uri(), req.headers()).await,
+        "/debug" => to_hyper_response(handle_admin_request(&state, req).await),
+        _ => hyper::Response::builder()
+            .status(404)
+            .header("content-type", "text/plain")
+            .body(Full::new(Bytes::from("Not Found")))
+            .unwrap(),
     }
+}
 
-    /// Get the number of active connections
-    #[napi]
-    pub fn active_connections(&self) -> u32 {
-        self.connection_tracker.count() as u32
+async fn handle_admin_request(
+    state: &Arc<ServerState>,
+    req: hyper::Request<hyper::body::Incoming>,
+) -> Response {
+    let method = req.method().clone();
+
+    if method == hyper::Method::GET {
+        return ResponseBuilder::new(StatusCode(200))
+            .header("content-type", "application/json")
+            .body(observability_json(&state.observability.load_full()))
+            .build();
     }
 
-    /// Check if server is shutting down
-    #[napi]
-    pub fn is_shutting_down(&self) -> bool {
-        self.connection_tracker.is_shutting_down()
+    if method != hyper::Method::PUT && method != hyper::Method::POST {
+        let mut res = ResponseBuilder::new(StatusCode(405))
+            .header("content-type", "text/plain")
+            .body("Method Not Allowed")
+            .build();
+        res.headers
+            .push(("allow".to_string(), "GET, POST, PUT".to_string()));
+        return res;
     }
-}
 
-impl Default for GustServer {
-    fn default() -> Self {
-        GustServer {
-            state: Arc::new(ServerState::new()),
-            shutdown_tx: Arc::new(RwLock::new(None)),
-            connection_tracker: Arc::new(CoreConnectionTracker::new()),
+    let request_timeout = state.request_timeout_ms.load(Ordering::Relaxed);
+    let body_result = if request_timeout > 0 {
+        tokio::time::timeout(Duration::from_millis(request_timeout as u64), req.collect()).await
+    } else {
+        Ok(req.collect().await)
+    };
+    let body_bytes = match body_result {
+        Ok(Ok(collected)) => collected.to_bytes(),
+        Ok(Err(_)) => Bytes::new(),
+        Err(_) => {
+            return ResponseBuilder::new(StatusCode(408))
+                .header("content-type", "text/plain")
+                .body("Request Timeout")
+                .build();
+        }
+    };
+    let body_str = String::from_utf8(body_bytes.to_vec()).unwrap_or_default();
+
+    let current = state.observability.load_full();
+    match apply_observability_update(&current, &body_str) {
+        Ok(updated) => {
+            let json = observability_json(&updated);
+            state.observability.store(Arc::new(updated));
+            ResponseBuilder::new(StatusCode(200))
+                .header("content-type", "application/json")
+                .body(json)
+                .build()
+        }
+        Err(message) => {
+            let mut body = String::from("{\"error\":");
+            gust_core::pure::json::write_json_string(&message, &mut body);
+            body.push('}');
+            ResponseBuilder::new(StatusCode(400))
+                .header("content-type", "application/json")
+                .body(body)
+                .build()
         }
     }
 }
 
-/// Handle incoming HTTP request
-async fn handle_request(
+/// Handle a request to a registered GraphQL route
+///
+/// Implements the GraphQL-over-HTTP transport: GET query params, POST
+/// JSON bodies (single or batch), APQ hash lookup, GraphiQL serving,
+/// and spec-mandated status codes. Execution is delegated to the JS
+/// resolver via the invoke handler.
+async fn handle_graphql_request(
     state: Arc<ServerState>,
     req: hyper::Request<hyper::body::Incoming>,
-) -> std::result::Result<hyper::Response<Full<Bytes>>, std::convert::Infallible> {
-    let method_str = req.method().as_str();
-    let path = req.uri().path();
-    let method = Method::from_str(method_str).unwrap_or(Method::Get);
-    let _is_get_or_head = method == Method::Get || method == Method::Head;
+    route: GraphQLRoute,
+    client: ClientInfo,
+) -> Response {
+    use gust_core::handlers::graphql as gql;
 
-    // FAST PATH: Check legacy static/dynamic routes first with minimal overhead
-    {
-        let router = state.router.read().await;
-        if let Some(matched) = router.find(method_str, path) {
-            let handler_id = matched.handler_id;
-            drop(router);
+    let method = req.method().as_str().to_string();
+    let path = req.uri().path().to_string();
+    let query_string = req.uri().query().unwrap_or("").to_string();
 
-            // Try static response first
-            let static_responses = state.static_responses.read().await;
-            if let Some(static_response) = static_responses.get(&handler_id) {
-                let response_bytes = static_response.bytes.clone();
-                return Ok(hyper::Response::builder()
-                    .status(200)
-                    .body(Full::new(response_bytes))
-                    .unwrap());
+    let mut headers_map: HashMap<String, String> = HashMap::with_capacity(req.headers().len());
+    for (name, value) in req.headers() {
+        if let Ok(v) = value.to_str() {
+            headers_map.insert(name.as_str().to_lowercase(), v.to_string());
+        }
+    }
+
+    let payload = match method.as_str() {
+        "GET" => {
+            // Serve GraphiQL for browser navigation (HTML accepted, no query)
+            let wants_html = headers_map
+                .get("accept")
+                .map(|a| a.contains("text/html"))
+                .unwrap_or(false);
+            if route.graphiql && wants_html && !query_string.contains("query=") {
+                return ResponseBuilder::new(StatusCode(200))
+                    .header("content-type", "text/html; charset=utf-8")
+                    .body(gql::graphiql_html(&path))
+                    .build();
             }
-            drop(static_responses);
 
-            // Try dynamic handler
-            let dynamic_handlers = state.dynamic_handlers.read().await;
-            if let Some(handler) = dynamic_handlers.get(&handler_id) {
-                let handler = handler.clone();
-                let params: HashMap<String, String> = matched.params.into_iter().collect();
-                drop(dynamic_handlers);
+            match gql::parse_get_request(&query_string) {
+                Ok(r) => gql::GraphQLPayload::Single(r),
+                Err(e) => return graphql_error_response(e),
+            }
+        }
+        "POST" => {
+            // Read body with the same limits as dynamic handlers
+            let max_body_size =
+                effective_body_limit(&state, headers_map.get("content-type").map(|s| s.as_str()));
+            if let Some(content_length) = headers_map.get("content-length") {
+                if let Ok(len) = content_length.parse::<usize>() {
+                    if len > max_body_size {
+                        return ResponseBuilder::new(StatusCode(413))
+                            .header("content-type", "text/plain")
+                            .body("Request Entity Too Large")
+                            .build();
+                    }
+                }
+            }
 
-                // Create minimal context for dynamic handler
-                let ctx = RequestContext {
-                    method: method_str.to_string(),
-                    path: path.to_string(),
-                    query: req.uri().query().map(|s| s.to_string()),
-                    params,
-                    headers: HashMap::new(), // TODO: collect if needed
-                    body: String::new(),     // TODO: read if needed
+            let request_timeout =
+                phase_timeout_ms(&state, Some(&path), gust_core::TimeoutPhase::Body);
+            let body_bytes =
+                match read_body_limited(&state, req.into_body(), max_body_size, request_timeout).await {
+                    BodyRead::Complete(bytes) => bytes,
+                    BodyRead::TooLarge => {
+                        return ResponseBuilder::new(StatusCode(413))
+                            .header("content-type", "text/plain")
+                            .body("Request Entity Too Large")
+                            .build();
+                    }
+                    BodyRead::Timeout => {
+                        return ResponseBuilder::new(StatusCode(408))
+                            .header("content-type", "text/plain")
+                            .body("Request Timeout")
+                            .build();
+                    }
                 };
 
-                let response = call_js_handler(&handler.callback, ctx).await;
-                return Ok(to_hyper_response(response_data_to_response(response)));
+            let body_str = String::from_utf8(body_bytes.to_vec()).unwrap_or_default();
+            let content_type = headers_map.get("content-type").map(|s| s.as_str());
+
+            match gql::parse_post_request(content_type, &body_str) {
+                Ok(p) => p,
+                Err(e) => return graphql_error_response(e),
+            }
+        }
+        _ => {
+            let mut res = graphql_error_response(gql::GraphQLHttpError::method_not_allowed());
+            res.headers.push(("allow".to_string(), "GET, POST".to_string()));
+            return res;
+        }
+    };
+
+    // Resolve APQ hashes against the persisted query cache
+    let payload = match payload {
+        gql::GraphQLPayload::Single(r) => match resolve_apq(&state, r).await {
+            Ok(r) => gql::GraphQLPayload::Single(r),
+            Err(e) => return graphql_error_response(e),
+        },
+        gql::GraphQLPayload::Batch(requests) => {
+            let mut resolved = Vec::with_capacity(requests.len());
+            for r in requests {
+                match resolve_apq(&state, r).await {
+                    Ok(r) => resolved.push(r),
+                    Err(e) => return graphql_error_response(e),
+                }
+            }
+            gql::GraphQLPayload::Batch(resolved)
+        }
+    };
+
+    let execute_json = gql::payload_to_execute_json(&payload);
+
+    let invoke_guard = select_invoke_handler(&state, &path);
+    if let Some(ref handler) = invoke_guard {
+        // GraphQL routes are registered at a fixed path, so the path
+        // is already the pattern
+        let route_pattern = path.clone();
+        let input = InvokeHandlerInput {
+            handler_id: route.execute_handler_id,
+            ctx: NativeHandlerContext {
+                method,
+                route_pattern: Some(route_pattern.clone()),
+                path,
+                query: query_string,
+                headers: headers_map,
+                params: HashMap::new(),
+                body: execute_json.into_bytes(),
+                headers_buffer: None,
+                params_flat: None,
+                ip: Some(client.ip),
+                protocol: Some(client.protocol),
+                host: Some(client.host),
+            },
+        };
+
+        let handler_call = call_invoke_handler(&handler.callback, input);
+        let Some(response) = run_handler_limited(&state, Some(&route_pattern), handler_call).await
+        else {
+            return ResponseBuilder::new(StatusCode(504))
+                .header("content-type", "text/plain")
+                .body("Gateway Timeout")
+                .build();
+        };
+        let mut res = response_data_to_response(response);
+        if !res
+            .headers
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+        {
+            res.headers
+                .push(("content-type".to_string(), "application/json".to_string()));
+        }
+        res
+    } else {
+        graphql_error_response(gql::GraphQLHttpError {
+            status: 500,
+            message: "No invoke handler registered for GraphQL execution".to_string(),
+            code: "INTERNAL_SERVER_ERROR",
+        })
+    }
+}
+
+/// Resolve an APQ hash: verify/store on full requests, look up hash-only ones
+async fn resolve_apq(
+    state: &Arc<ServerState>,
+    mut req: gust_core::handlers::graphql::GraphQLRequest,
+) -> std::result::Result<
+    gust_core::handlers::graphql::GraphQLRequest,
+    gust_core::handlers::graphql::GraphQLHttpError,
+> {
+    use gust_core::handlers::graphql as gql;
+
+    if let Some(hash) = gql::persisted_query_hash(&req) {
+        match &req.query {
+            Some(query) => {
+                if gql::apq_hash(query) != hash {
+                    return Err(gql::GraphQLHttpError::bad_request(
+                        "provided sha does not match query",
+                    ));
+                }
+                state.apq_cache.write().await.insert(hash, query.clone());
+            }
+            None => {
+                let cached = state.apq_cache.read().await.get(&hash).cloned();
+                match cached {
+                    Some(q) => req.query = Some(q),
+                    None => return Err(gql::GraphQLHttpError::persisted_query_not_found()),
+                }
             }
         }
     }
 
-    // FAST PATH 2: Check app routes (GustApp pattern - Rust routing, ID-based dispatch)
-    // OPTIMIZED: Lock-free routing + lock-free invoke_handler read + skip body for GET/HEAD
-    {
-        // OPTIMIZATION: Lock-free read of app routes using ArcSwap
-        let routes = state.app_routes.load();
-        if let Some(matched) = routes.find(method_str, path) {
-            let handler_id = matched.handler_id;
-            let params: HashMap<String, String> = matched.params.into_iter().collect();
-            // No need to drop - ArcSwap guard is cheap
+    if req.query.is_none() {
+        return Err(gql::GraphQLHttpError::bad_request("Must provide query string"));
+    }
 
-            // OPTIMIZATION: Lock-free read of invoke handler using ArcSwap
-            let invoke_guard = state.invoke_handler.load();
-            if let Some(ref handler) = **invoke_guard {
-                // Extract all data from req BEFORE consuming it
-                let method_str_owned = method_str.to_string();
-                let path_owned = path.to_string();
-                let query_owned = req.uri().query().unwrap_or("").to_string();
+    Ok(req)
+}
 
-                // OPTIMIZATION: Check if we can skip body reading entirely (GET/HEAD have no body)
-                let skip_body = method == Method::Get || method == Method::Head;
+/// Handle a request to a registered JSON-RPC dispatcher
+///
+/// Parses and validates the envelope in Rust (single and batch), maps
+/// methods to handler IDs, fires notifications without collecting a
+/// response, and assembles the spec-shaped response array.
+async fn handle_jsonrpc_request(
+    state: Arc<ServerState>,
+    req: hyper::Request<hyper::body::Incoming>,
+    route: JsonRpcRoute,
+    client: ClientInfo,
+) -> Response {
+    use gust_core::handlers::jsonrpc as rpc;
+
+    if req.method() != hyper::Method::POST {
+        let mut res = ResponseBuilder::new(StatusCode(405))
+            .header("content-type", "application/json")
+            .body(rpc::error_response(
+                None,
+                &rpc::JsonRpcError::invalid_request("only POST is supported"),
+            ))
+            .build();
+        res.headers.push(("allow".to_string(), "POST".to_string()));
+        return res;
+    }
 
-                // OPTIMIZATION: Sucrose-style - skip header collection for simple GET/HEAD routes
-                // If route has no params and is GET/HEAD, handler likely doesn't need headers
-                let skip_headers = skip_body && params.is_empty();
+    let method_str = req.method().as_str().to_string();
+    let path = req.uri().path().to_string();
+    let query_string = req.uri().query().unwrap_or("").to_string();
 
-                // Collect headers only if needed (Sucrose-style optimization)
-                let headers_map: HashMap<String, String> = if skip_headers {
-                    // Fast path: empty headers for simple GET/HEAD without params
-                    HashMap::new()
-                } else {
-                    // Full path: collect headers with pre-allocated capacity
-                    let mut map = HashMap::with_capacity(req.headers().len());
-                    for (name, value) in req.headers() {
-                        if let Ok(v) = value.to_str() {
-                            map.insert(name.as_str().to_lowercase(), v.to_string());
-                        }
-                    }
-                    map
-                };
+    let mut headers_map: HashMap<String, String> = HashMap::with_capacity(req.headers().len());
+    for (name, value) in req.headers() {
+        if let Ok(v) = value.to_str() {
+            headers_map.insert(name.as_str().to_lowercase(), v.to_string());
+        }
+    }
 
-                // OPTIMIZATION: Skip body size check and reading for GET/HEAD
-                let body_bytes = if skip_body {
-                    // GET/HEAD - no body, skip entirely
-                    Bytes::new()
-                } else {
-                    // POST/PUT/PATCH/etc - need to read body
-                    let max_body_size = state.max_body_size.load(Ordering::Relaxed) as usize;
+    // Read body with the same limits as dynamic handlers
+    let max_body_size =
+        effective_body_limit(&state, headers_map.get("content-type").map(|s| s.as_str()));
+    if let Some(content_length) = headers_map.get("content-length") {
+        if let Ok(len) = content_length.parse::<usize>() {
+            if len > max_body_size {
+                return ResponseBuilder::new(StatusCode(413))
+                    .header("content-type", "text/plain")
+                    .body("Request Entity Too Large")
+                    .build();
+            }
+        }
+    }
+
+    let request_timeout = phase_timeout_ms(&state, Some(&path), gust_core::TimeoutPhase::Body);
+    let body_bytes = match read_body_limited(&state, req.into_body(), max_body_size, request_timeout)
+        .await
+    {
+        BodyRead::Complete(bytes) => bytes,
+        BodyRead::TooLarge => {
+            return ResponseBuilder::new(StatusCode(413))
+                .header("content-type", "text/plain")
+                .body("Request Entity Too Large")
+                .build();
+        }
+        BodyRead::Timeout => {
+            return ResponseBuilder::new(StatusCode(408))
+                .header("content-type", "text/plain")
+                .body("Request Timeout")
+                .build();
+        }
+    };
+
+    let body_str = String::from_utf8(body_bytes.to_vec()).unwrap_or_default();
 
-                    // Check body size limit from Content-Length header
-                    if let Some(content_length) = headers_map.get("content-length") {
-                        if let Ok(len) = content_length.parse::<usize>() {
-                            if len > max_body_size {
-                                return Ok(hyper::Response::builder()
-                                    .status(413)
-                                    .header("content-type", "text/plain")
-                                    .body(Full::new(Bytes::from("Request Entity Too Large")))
-                                    .unwrap());
-                            }
-                        }
-                    }
+    let payload = match rpc::parse_request_body(&body_str) {
+        Ok(p) => p,
+        Err(e) => {
+            return ResponseBuilder::new(StatusCode(200))
+                .header("content-type", "application/json")
+                .body(rpc::error_response(None, &e))
+                .build();
+        }
+    };
 
-                    // Read body with timeout
-                    let request_timeout = state.request_timeout_ms.load(Ordering::Relaxed);
-                    let body_result = if request_timeout > 0 {
-                        tokio::time::timeout(
-                            Duration::from_millis(request_timeout as u64),
-                            req.collect()
-                        ).await
-                    } else {
-                        Ok(req.collect().await)
-                    };
+    let ctx_template = NativeHandlerContext {
+        method: method_str,
+        // JSON-RPC routes are registered at a fixed path, so the
+        // path is already the pattern
+        route_pattern: Some(path.clone()),
+        path,
+        query: query_string,
+        headers: headers_map,
+        params: HashMap::new(),
+        body: Vec::new(),
+        headers_buffer: None,
+        params_flat: None,
+        ip: Some(client.ip),
+        protocol: Some(client.protocol),
+        host: Some(client.host),
+    };
 
-                    match body_result {
-                        Ok(Ok(collected)) => {
-                            let bytes = collected.to_bytes();
-                            if bytes.len() > max_body_size {
-                                return Ok(hyper::Response::builder()
-                                    .status(413)
-                                    .header("content-type", "text/plain")
-                                    .body(Full::new(Bytes::from("Request Entity Too Large")))
-                                    .unwrap());
-                            }
-                            bytes
-                        },
-                        Ok(Err(_)) => Bytes::new(),
-                        Err(_) => {
-                            return Ok(hyper::Response::builder()
-                                .status(408)
-                                .header("content-type", "text/plain")
-                                .body(Full::new(Bytes::from("Request Timeout")))
-                                .unwrap());
+    match payload {
+        rpc::JsonRpcPayload::Single(request) => {
+            let is_notification = request.is_notification();
+            let response_json =
+                dispatch_jsonrpc(&state, &route, &ctx_template, request).await;
+            if is_notification {
+                return ResponseBuilder::new(StatusCode(204)).build();
+            }
+            ResponseBuilder::new(StatusCode(200))
+                .header("content-type", "application/json")
+                .body(response_json.unwrap_or_default())
+                .build()
+        }
+        rpc::JsonRpcPayload::Batch(entries) => {
+            let mut responses: Vec<String> = Vec::with_capacity(entries.len());
+            for entry in entries {
+                match entry {
+                    Ok(request) => {
+                        if let Some(json) =
+                            dispatch_jsonrpc(&state, &route, &ctx_template, request).await
+                        {
+                            responses.push(json);
                         }
                     }
-                };
+                    Err(e) => responses.push(rpc::error_response(None, &e)),
+                }
+            }
 
-                // Create native handler context
-                let native_ctx = NativeHandlerContext {
-                    method: method_str_owned,
-                    path: path_owned,
-                    query: query_owned,
-                    headers: headers_map,
-                    params,
-                    body: body_bytes.to_vec(),
-                };
+            // A batch of only notifications gets no response body
+            if responses.is_empty() {
+                return ResponseBuilder::new(StatusCode(204)).build();
+            }
 
-                // Create input for invoke handler
-                let input = InvokeHandlerInput {
-                    handler_id,
-                    ctx: native_ctx,
-                };
+            ResponseBuilder::new(StatusCode(200))
+                .header("content-type", "application/json")
+                .body(format!("[{}]", responses.join(",")))
+                .build()
+        }
+    }
+}
 
-                // Call invoke handler with input
-                let response = call_invoke_handler(&handler.callback, input).await;
-                return Ok(to_hyper_response(response_data_to_response(response)));
+/// Dispatch one JSON-RPC request to its JS handler
+///
+/// Returns the response envelope JSON, or `None` for notifications.
+async fn dispatch_jsonrpc(
+    state: &Arc<ServerState>,
+    route: &JsonRpcRoute,
+    ctx_template: &NativeHandlerContext,
+    request: gust_core::handlers::jsonrpc::JsonRpcRequest,
+) -> Option<String> {
+    use gust_core::handlers::jsonrpc as rpc;
+
+    let is_notification = request.is_notification();
+    let id = request.id.clone();
+
+    let handler_id = match route.methods.get(&request.method) {
+        Some(id) => *id,
+        None => {
+            if is_notification {
+                return None;
             }
+            return Some(rpc::error_response(
+                id.as_ref(),
+                &rpc::JsonRpcError::method_not_found(&request.method),
+            ));
         }
-    }
+    };
 
-    // Check middleware early to know if we need request object
-    let middleware = state.middleware.read().await;
-    let has_middleware = !middleware.is_empty();
-    drop(middleware);
+    let handler = match select_invoke_handler(state, &ctx_template.path) {
+        Some(h) => h,
+        None => {
+            if is_notification {
+                return None;
+            }
+            return Some(rpc::error_response(
+                id.as_ref(),
+                &rpc::JsonRpcError::internal_error("no invoke handler registered"),
+            ));
+        }
+    };
 
-    // FAST PATH: No middleware, check if we can use fallback directly
-    if !has_middleware {
-        // Note: Legacy routes already checked above, so this section is for fallback only
-        // If no route matched, try fallback
-        if true {
-            // No dynamic route match - try fallback with minimal context
-            let fallback = state.fallback_handler.read().await.clone();
-            if let Some(handler) = fallback {
-                // Minimal context for fallback - only allocate what's needed
-                let ctx = RequestContext {
-                    method: method_str.to_string(),
-                    path: path.to_string(),
-                    query: req.uri().query().map(|s| s.to_string()),
-                    params: HashMap::new(),
-                    headers: HashMap::new(), // Empty for fast path
-                    body: String::new(),     // Skip body for GET/HEAD
-                };
+    let mut ctx = ctx_template.clone();
+    ctx.body = request.to_execute_json().into_bytes();
 
-                let response = call_js_handler(&handler.callback, ctx).await;
-                return Ok(to_hyper_response(response_data_to_response(response)));
-            }
+    let input = InvokeHandlerInput {
+        handler_id,
+        ctx,
+    };
 
-            // No fallback - 404
-            return Ok(to_hyper_response(Response::not_found()));
+    let handler_call = call_invoke_handler(&handler.callback, input);
+    let Some(response) =
+        run_handler_limited(state, Some(&ctx_template.path), handler_call).await
+    else {
+        if is_notification {
+            return None;
         }
+        return Some(rpc::error_response(
+            id.as_ref(),
+            &rpc::JsonRpcError::internal_error("handler timed out"),
+        ));
+    };
+
+    if is_notification {
+        return None;
     }
 
-    // For dynamic routes with middleware, we need full context
-    let method_str = method_str.to_string();
-    let path = path.to_string();
-    let query = req.uri().query().map(|s| s.to_string());
+    let id = id.unwrap_or(rpc::JsonRpcId::Null);
+    if response.status >= 200 && response.status < 300 {
+        let result = if response.body.is_empty() {
+            "null".to_string()
+        } else {
+            response.body
+        };
+        Some(rpc::success_response(&id, &result))
+    } else {
+        Some(rpc::error_response(
+            Some(&id),
+            &rpc::JsonRpcError::internal_error(response.body),
+        ))
+    }
+}
 
-    // Collect headers into HashMap
-    let mut headers_map: HashMap<String, String> = HashMap::with_capacity(req.headers().len());
+/// Handle a request against a tus upload endpoint
+///
+/// Collects the body under the server's size/timeout limits, then hands
+/// the protocol state machine to the core handler.
+async fn handle_tus_request(
+    state: Arc<ServerState>,
+    req: hyper::Request<hyper::body::Incoming>,
+    handler: Arc<gust_core::handlers::Tus>,
+    base_path: &str,
+) -> Response {
+    let method = Method::from_str(req.method().as_str()).unwrap_or(Method::Get);
+    let path = req.uri().path().to_string();
+
+    let mut builder = gust_core::RequestBuilder::new(method, path);
     for (name, value) in req.headers() {
         if let Ok(v) = value.to_str() {
-            headers_map.insert(name.as_str().to_lowercase(), v.to_string());
+            builder = builder.header(name.as_str(), v);
         }
     }
 
-    // Create request object for middleware (if needed)
-    let middleware = state.middleware.read().await;
-    let request = if has_middleware {
-        let mut mw_req = Request::new(method, path.clone());
-        mw_req.query = query.clone();
-        for (name, value) in &headers_map {
-            mw_req.headers.push((name.clone(), value.clone()));
-        }
-        // Run before middleware
-        if let Some(early_response) = middleware.run_before(&mut mw_req) {
-            return Ok(to_hyper_response(early_response));
+    // Read body with the same limits as dynamic handlers
+    let content_type = req
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok());
+    let max_body_size = effective_body_limit(&state, content_type);
+    if let Some(content_length) = req
+        .headers()
+        .get("content-length")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok())
+    {
+        if content_length > max_body_size {
+            return ResponseBuilder::new(StatusCode(413))
+                .header("Tus-Resumable", gust_core::handlers::tus::TUS_VERSION)
+                .body("Request Entity Too Large")
+                .build();
         }
-        Some(mw_req)
-    } else {
-        None
-    };
-    drop(middleware);
+    }
 
-    // 2. Try legacy routes again with middleware (shouldn't happen often)
-    // This path is only for cases where middleware exists and modifies request
-    let legacy_result = {
-        let router = state.router.read().await;
-        router.find(&method_str, &path)
+    let request_timeout = phase_timeout_ms(&state, Some(base_path), gust_core::TimeoutPhase::Body);
+    let body_bytes = match read_body_limited(&state, req.into_body(), max_body_size, request_timeout)
+        .await
+    {
+        BodyRead::Complete(bytes) => bytes,
+        BodyRead::TooLarge => {
+            return ResponseBuilder::new(StatusCode(413))
+                .header("Tus-Resumable", gust_core::handlers::tus::TUS_VERSION)
+                .body("Request Entity Too Large")
+                .build();
+        }
+        BodyRead::Timeout => {
+            return ResponseBuilder::new(StatusCode(408))
+                .header("Tus-Resumable", gust_core::handlers::tus::TUS_VERSION)
+                .body("Request Timeout")
+                .build();
+        }
     };
 
-    if let Some(matched) = legacy_result {
-        let handler_id = matched.handler_id;
-        let params: HashMap<String, String> = matched.params.into_iter().collect();
-
-        // Try dynamic handler
-        let dynamic_handlers = state.dynamic_handlers.read().await;
-        if let Some(handler) = dynamic_handlers.get(&handler_id).cloned() {
-            drop(dynamic_handlers);
-
-            // Check body size limit (lock-free atomic read)
-            let max_body_size = state.max_body_size.load(Ordering::Relaxed) as usize;
-        if let Some(content_length) = headers_map.get("content-length") {
-            if let Ok(len) = content_length.parse::<usize>() {
-                    if len > max_body_size {
-                        return Ok(hyper::Response::builder()
-                            .status(413)
-                            .header("content-type", "text/plain")
-                            .body(Full::new(Bytes::from("Request Entity Too Large")))
-                            .unwrap());
-                    }
-                }
-            }
+    let request = builder.body(body_bytes).build();
+    handler.handle(&request, base_path)
+}
 
-            // Read body for dynamic handlers with timeout (lock-free atomic read)
-            let request_timeout = state.request_timeout_ms.load(Ordering::Relaxed);
-            let body_result = if request_timeout > 0 {
-                tokio::time::timeout(
-                    Duration::from_millis(request_timeout as u64),
-                    req.collect()
-                ).await
-            } else {
-                Ok(req.collect().await)
-            };
+/// Rebuild the middleware chain from the registry and swap it in
+///
+/// Readers that already loaded the old chain finish with it; the next
+/// request sees the new one.
+fn rebuild_middleware_chain(state: &ServerState, registry: &[(String, Arc<dyn Middleware>)]) {
+    let mut chain = MiddlewareChain::new();
+    for (_, middleware) in registry {
+        chain.add(Arc::clone(middleware));
+    }
+    state.middleware.store(Arc::new(chain));
+}
 
-            let body_bytes = match body_result {
-                Ok(Ok(collected)) => {
-                    let bytes = collected.to_bytes();
-                    // Double-check size after reading (for chunked encoding)
-                    if bytes.len() > max_body_size {
-                        return Ok(hyper::Response::builder()
-                            .status(413)
-                            .header("content-type", "text/plain")
-                            .body(Full::new(Bytes::from("Request Entity Too Large")))
-                            .unwrap());
-                    }
-                    bytes
-                },
-                Ok(Err(_)) => Bytes::new(),
-                Err(_) => {
-                    // Timeout
-                    return Ok(hyper::Response::builder()
-                        .status(408)
-                        .header("content-type", "text/plain")
-                        .body(Full::new(Bytes::from("Request Timeout")))
-                        .unwrap());
-                }
-            };
-            let body_str = String::from_utf8(body_bytes.to_vec()).unwrap_or_default();
+/// Build a CORS middleware from the JS config
+fn build_cors_middleware(config: CorsConfig) -> gust_core::middleware::cors::Cors {
+    use gust_core::middleware::cors::{Cors, CorsConfig as CoreConfig};
 
-            // Create RequestContext for JS handler (matches TypeScript interface)
-            let ctx = RequestContext {
-                method: method_str.clone(),
-                path: path.clone(),
-                query,
-                params,
-                headers: headers_map.clone(),
-                body: body_str,
-            };
+    let mut core_config = if config
+        .origins
+        .as_ref()
+        .map(|o| o.contains(&"*".to_string()))
+        .unwrap_or(false)
+    {
+        CoreConfig::default().allow_all_origins()
+    } else {
+        CoreConfig::default()
+    };
 
-            // Call JS handler
-            let response = call_js_handler(&handler.callback, ctx).await;
-            let mut our_response = response_data_to_response(response);
+    // Apply origins
+    if let Some(origins) = config.origins {
+        for origin in origins {
+            if origin != "*" {
+                core_config = core_config.allow_origin(origin);
+            }
+        }
+    }
 
-            // Apply middleware chain (after) - only if middleware exists
-            if let Some(ref req) = request {
-                let middleware = state.middleware.read().await;
-                middleware.run_after(req, &mut our_response);
+    // Apply methods
+    if let Some(methods) = config.methods {
+        for method in methods {
+            if let Ok(m) = Method::from_str(&method) {
+                core_config = core_config.allow_method(m);
             }
+        }
+    }
 
-            return Ok(to_hyper_response(our_response));
+    // Apply headers
+    if let Some(headers) = config.allowed_headers {
+        for header in headers {
+            core_config = core_config.allow_header(header);
         }
     }
 
-    // 3. Try fallback handler
-    let fallback = state.fallback_handler.read().await.clone();
-    if let Some(handler) = fallback {
-        // Check body size limit (lock-free atomic read)
-        let max_body_size = state.max_body_size.load(Ordering::Relaxed) as usize;
-        if let Some(content_length) = headers_map.get("content-length") {
-            if let Ok(len) = content_length.parse::<usize>() {
-                if len > max_body_size {
-                    return Ok(hyper::Response::builder()
-                        .status(413)
-                        .header("content-type", "text/plain")
-                        .body(Full::new(Bytes::from("Request Entity Too Large")))
-                        .unwrap());
-                }
-            }
+    // Apply exposed headers
+    if let Some(headers) = config.exposed_headers {
+        for header in headers {
+            core_config = core_config.expose_header(header);
         }
+    }
 
-        // Read body for fallback handler with timeout (lock-free atomic read)
-        let request_timeout = state.request_timeout_ms.load(Ordering::Relaxed);
-        let body_result = if request_timeout > 0 {
-            tokio::time::timeout(
-                Duration::from_millis(request_timeout as u64),
-                req.collect()
-            ).await
-        } else {
-            Ok(req.collect().await)
-        };
+    // Apply credentials
+    if let Some(true) = config.credentials {
+        core_config = core_config.allow_credentials();
+    }
 
-        let body_bytes = match body_result {
-            Ok(Ok(collected)) => {
-                let bytes = collected.to_bytes();
-                if bytes.len() > max_body_size {
-                    return Ok(hyper::Response::builder()
-                        .status(413)
-                        .header("content-type", "text/plain")
-                        .body(Full::new(Bytes::from("Request Entity Too Large")))
-                        .unwrap());
+    // Apply max age
+    if let Some(max_age) = config.max_age {
+        core_config = core_config.max_age(max_age);
+    }
+
+    Cors::new(core_config)
+}
+
+/// Build a rate limiting middleware from the JS config
+fn build_rate_limit_middleware(
+    config: RateLimitConfig,
+) -> gust_core::middleware::rate_limit::RateLimit {
+    use gust_core::middleware::rate_limit::{RateLimit, RateLimitConfig as CoreConfig};
+
+    let core_config = CoreConfig::new(
+        config.max_requests,
+        Duration::from_secs(config.window_seconds as u64),
+    );
+    RateLimit::new(core_config)
+}
+
+/// Build a security headers middleware from the JS config
+fn build_security_middleware(config: SecurityConfig) -> gust_core::middleware::security::Security {
+    use gust_core::middleware::security::{
+        FrameOptions, HstsConfig, Security, SecurityConfig as CoreConfig,
+    };
+
+    let frame_options = match config.frame_options.as_deref() {
+        Some("DENY") => FrameOptions::Deny,
+        Some("SAMEORIGIN") => FrameOptions::SameOrigin,
+        _ => FrameOptions::None,
+    };
+
+    let hsts = if config.hsts.unwrap_or(false) {
+        Some(HstsConfig {
+            max_age: config.hsts_max_age.unwrap_or(31536000) as u64,
+            include_subdomains: true,
+            preload: false,
+        })
+    } else {
+        None
+    };
+
+    let core_config = CoreConfig {
+        csp: None,
+        frame_options,
+        content_type_options: config.content_type_options.unwrap_or(false),
+        xss_protection: config.xss_protection.unwrap_or(false),
+        hsts,
+        referrer_policy: config.referrer_policy,
+        permissions_policy: None,
+        coop: None,
+        coep: None,
+        corp: None,
+    };
+
+    Security::new(core_config)
+}
+
+/// Build a request tracing middleware from the JS config; fails on an
+/// unknown id generator
+fn build_tracing_middleware(
+    config: TracingConfig,
+) -> Result<gust_core::middleware::tracing::Tracing> {
+    use gust_core::middleware::tracing::{IdGenerator, Tracing, TracingConfig as CoreConfig};
+
+    let mut core_config = CoreConfig::new();
+    if let Some(name) = config.header_name {
+        core_config = core_config.header_name(name);
+    }
+    if let Some(generate) = config.generate_id {
+        core_config = core_config.generate_id(generate);
+    }
+    if let Some(generator) = config.id_generator {
+        core_config = core_config.id_generator(match generator.as_str() {
+            "uuid" => IdGenerator::Uuid,
+            "uuidv7" => IdGenerator::UuidV7,
+            "ulid" => IdGenerator::Ulid,
+            "snowflake" => {
+                let machine_id = config.machine_id.unwrap_or(0);
+                if machine_id > 0x3FF {
+                    return Err(Error::from_reason(format!(
+                        "Snowflake machine ID {} out of range (0-1023)",
+                        machine_id
+                    )));
                 }
-                bytes
-            },
-            Ok(Err(_)) => Bytes::new(),
-            Err(_) => {
-                return Ok(hyper::Response::builder()
-                    .status(408)
-                    .header("content-type", "text/plain")
-                    .body(Full::new(Bytes::from("Request Timeout")))
-                    .unwrap());
+                IdGenerator::Snowflake(machine_id as u16)
             }
-        };
-        let body_str = String::from_utf8(body_bytes.to_vec()).unwrap_or_default();
+            "nanoid" => IdGenerator::NanoId,
+            "shortid" => IdGenerator::ShortId,
+            "counter" => IdGenerator::Counter,
+            other => {
+                return Err(Error::from_reason(format!(
+                    "Unknown id generator '{}' (expected uuid, uuidv7, ulid, snowflake, nanoid, shortid, or counter)",
+                    other
+                )))
+            }
+        });
+    }
+    if config.propagate_trace.unwrap_or(true) {
+        core_config = core_config.propagate_trace();
+    }
+    if config.log_requests.unwrap_or(false) {
+        core_config = core_config.log_requests();
+    }
+    if config.log_responses.unwrap_or(false) {
+        core_config = core_config.log_responses();
+    }
 
-        let ctx = RequestContext {
-            method: method_str,
-            path: path.clone(),
-            query,
-            params: HashMap::new(),
-            headers: headers_map,
-            body: body_str,
-        };
+    Ok(Tracing::new(core_config))
+}
 
-        let response = call_js_handler(&handler.callback, ctx).await;
-        let mut our_response = response_data_to_response(response);
+/// Build a core cache key shape from optional JS config fields
+fn build_cache_key_config(
+    vary_headers: &Option<Vec<String>>,
+    vary_cookies: &Option<Vec<String>>,
+    query_params: &Option<Vec<String>>,
+) -> Option<gust_core::middleware::CacheKeyConfig> {
+    use gust_core::middleware::{CacheKeyConfig, QueryKeys};
 
-        // Apply middleware chain (after) - only if middleware exists
-        if let Some(ref req) = request {
-            let middleware = state.middleware.read().await;
-            middleware.run_after(req, &mut our_response);
-        }
+    if vary_headers.is_none() && vary_cookies.is_none() && query_params.is_none() {
+        return None;
+    }
 
-        return Ok(to_hyper_response(our_response));
+    let mut key = CacheKeyConfig::new();
+    for header in vary_headers.iter().flatten() {
+        key = key.vary_header(header);
+    }
+    for cookie in vary_cookies.iter().flatten() {
+        key = key.vary_cookie(cookie);
+    }
+    if let Some(params) = query_params {
+        key = key.query(if params.is_empty() {
+            QueryKeys::None
+        } else {
+            QueryKeys::Only(params.clone())
+        });
+    }
+    Some(key)
+}
+
+/// Build a core cache policy from a JS policy rule
+fn build_cache_policy(rule: &CachePolicyRule) -> gust_core::middleware::CachePolicy {
+    use gust_core::middleware::CachePolicy;
+
+    let max_age = rule.max_age.unwrap_or(0) as u64;
+    let mut policy = match rule.scope.as_deref() {
+        Some("no-store") => CachePolicy::no_store(),
+        Some("private") => CachePolicy::private(max_age),
+        _ => CachePolicy::public(max_age),
+    };
+    if let Some(secs) = rule.s_maxage {
+        policy = policy.s_maxage(secs as u64);
+    }
+    if let Some(secs) = rule.stale_while_revalidate {
+        policy = policy.stale_while_revalidate(secs as u64);
+    }
+    if let Some(secs) = rule.stale_if_error {
+        policy = policy.stale_if_error(secs as u64);
+    }
+    if rule.immutable.unwrap_or(false) {
+        policy = policy.immutable();
     }
+    policy
+}
 
-    // 4. No route matched - 404
-    let mut our_response = Response::not_found();
-    if let Some(ref req) = request {
-        let middleware = state.middleware.read().await;
-        middleware.run_after(req, &mut our_response);
-    }
+/// Build a JSON error response from a GraphQL transport error
+fn graphql_error_response(err: gust_core::handlers::graphql::GraphQLHttpError) -> Response {
+    ResponseBuilder::new(StatusCode(err.status))
+        .header("content-type", "application/json")
+        .body(err.to_body())
+        .build()
+}
 
-    Ok(to_hyper_response(our_response))
+/// Pick the invoke handler for a request
+///
+/// With worker handlers registered, requests go to a worker — pinned
+/// by FNV path hash when route affinity is on, round-robin otherwise.
+/// Without workers, the main-thread invoke handler is used.
+fn select_invoke_handler(state: &ServerState, path: &str) -> Option<InvokeHandler> {
+    let workers = state.workers.load();
+    if !workers.is_empty() {
+        let index = if state.worker_route_affinity.load(Ordering::Relaxed) {
+            let mut hash = 0xcbf29ce484222325u64;
+            for byte in path.bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+            (hash % workers.len() as u64) as usize
+        } else {
+            state.worker_cursor.fetch_add(1, Ordering::Relaxed) % workers.len()
+        };
+        return Some(workers[index].clone());
+    }
+    (**state.invoke_handler.load()).clone()
 }
 
+/// Handler dispatches served by the direct (sync) path
+static SYNC_DISPATCHES: AtomicU64 = AtomicU64::new(0);
+/// Handler dispatches served by the Promise (async) path
+static ASYNC_DISPATCHES: AtomicU64 = AtomicU64::new(0);
+
 /// Call JS handler and await result
-async fn call_js_handler(
-    callback: &ThreadsafeFunction<RequestContext, ErrorStrategy::Fatal>,
-    ctx: RequestContext,
-) -> ResponseData {
+async fn call_js_handler(handler: &DynamicHandler, ctx: RequestContext) -> ResponseData {
+    #[cfg(feature = "bun-direct")]
+    if handler.sync {
+        SYNC_DISPATCHES.fetch_add(1, Ordering::Relaxed);
+        return call_js_sync_handler(&handler.callback, ctx).await;
+    }
+
+    ASYNC_DISPATCHES.fetch_add(1, Ordering::Relaxed);
+    let callback = &handler.callback;
     // Use call_async to properly handle Promise returns
     match callback.call_async::<Promise<ResponseData>>(ctx).await {
         Ok(promise) => {
@@ -2191,7 +7948,9 @@ async fn call_js_handler(
                 Err(_) => ResponseData {
                     status: 500,
                     headers: HashMap::new(),
+                    headers_flat: None,
                     body: "Internal Server Error".to_string(),
+                    body_buffer: None,
                     streaming: None,
                 },
             }
@@ -2199,12 +7958,51 @@ async fn call_js_handler(
         Err(_) => ResponseData {
             status: 500,
             headers: HashMap::new(),
+            headers_flat: None,
             body: "Internal Server Error".to_string(),
+            body_buffer: None,
             streaming: None,
         },
     }
 }
 
+/// Call a sync JS handler, reading the return value directly
+///
+/// The return value comes back over a oneshot channel from the
+/// threadsafe call itself — no Promise is allocated, coerced, or
+/// awaited. Handlers that throw or return the wrong shape produce a
+/// 500 like the async path.
+#[cfg(feature = "bun-direct")]
+async fn call_js_sync_handler(callback: &HandlerCallback, ctx: RequestContext) -> ResponseData {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let status = callback.call_with_return_value(
+        ctx,
+        ThreadsafeFunctionCallMode::NonBlocking,
+        move |response: ResponseData| {
+            let _ = tx.send(response);
+            Ok(())
+        },
+    );
+    if status != Status::Ok {
+        return ResponseData {
+            status: 500,
+            headers: HashMap::new(),
+            headers_flat: None,
+            body: "Internal Server Error".to_string(),
+            body_buffer: None,
+            streaming: None,
+        };
+    }
+    rx.await.unwrap_or_else(|_| ResponseData {
+        status: 500,
+        headers: HashMap::new(),
+        headers_flat: None,
+        body: "Internal Server Error".to_string(),
+        body_buffer: None,
+        streaming: None,
+    })
+}
+
 /// Call invoke handler (GustApp pattern) and await result
 ///
 /// This is the new route registration pattern where Rust routes first,
@@ -2221,7 +8019,9 @@ async fn call_invoke_handler(
                 Err(_) => ResponseData {
                     status: 500,
                     headers: HashMap::new(),
+                    headers_flat: None,
                     body: "Internal Server Error".to_string(),
+                    body_buffer: None,
                     streaming: None,
                 },
             }
@@ -2229,26 +8029,446 @@ async fn call_invoke_handler(
         Err(_) => ResponseData {
             status: 500,
             headers: HashMap::new(),
+            headers_flat: None,
             body: "Internal Server Error".to_string(),
+            body_buffer: None,
             streaming: None,
         },
     }
 }
 
+/// Deliver one raw TCP event to JS and apply the optional reply
+async fn deliver_tcp_event(
+    callback: &TcpEventCallback,
+    conn: Arc<gust_core::TcpConnection>,
+    event: gust_core::TcpEvent,
+) {
+    let ctx = TcpEventContext {
+        connection_id: conn.id() as i64,
+        remote_addr: conn.peer().to_string(),
+        event: match &event {
+            gust_core::TcpEvent::Open => "open",
+            gust_core::TcpEvent::Data(_) => "data",
+            gust_core::TcpEvent::Close => "close",
+        }
+        .to_string(),
+        data: match event {
+            gust_core::TcpEvent::Data(data) => Some(data.to_vec().into()),
+            _ => None,
+        },
+    };
+
+    // Handlers that return nothing fail the Promise coercion; treat
+    // that (and rejections) as "no reply"
+    if let Ok(promise) = callback.call_async::<Promise<Option<TcpReply>>>(ctx).await {
+        if let Ok(Some(reply)) = promise.await {
+            if let Some(data) = reply.data {
+                conn.send(Bytes::copy_from_slice(&data));
+            }
+            if reply.close.unwrap_or(false) {
+                conn.close();
+            }
+        }
+    }
+}
+
+/// Answer a WebSocket route request: 101 plus a spawned frame loop
+///
+/// Non-upgrade requests get 426 so plain HTTP probes of a WebSocket
+/// path fail loudly instead of hanging. The frame loop starts once
+/// hyper hands over the socket (after the 101 is flushed).
+fn handle_ws_upgrade(
+    state: Arc<ServerState>,
+    mut req: hyper::Request<hyper::body::Incoming>,
+    route: Arc<WsRoute>,
+    peer: std::net::SocketAddr,
+) -> hyper::Response<Full<Bytes>> {
+    let is_upgrade = req
+        .headers()
+        .get(hyper::header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false)
+        && req
+            .headers()
+            .get(hyper::header::CONNECTION)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_lowercase().contains("upgrade"))
+            .unwrap_or(false);
+    let key = req
+        .headers()
+        .get("sec-websocket-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let Some(key) = key.filter(|_| is_upgrade) else {
+        return hyper::Response::builder()
+            .status(426)
+            .header("upgrade", "websocket")
+            .header("content-type", "text/plain")
+            .body(Full::new(Bytes::from("Upgrade Required")))
+            .unwrap();
+    };
+
+    let path = req.uri().path().to_string();
+    let on_upgrade = hyper::upgrade::on(&mut req);
+    tokio::spawn(async move {
+        match on_upgrade.await {
+            Ok(upgraded) => ws_connection_loop(state, route, upgraded, peer, path).await,
+            Err(e) => eprintln!("WebSocket upgrade error: {}", e),
+        }
+    });
+
+    hyper::Response::builder()
+        .status(101)
+        .header("upgrade", "websocket")
+        .header("connection", "Upgrade")
+        .header("sec-websocket-accept", core_generate_accept_key(&key))
+        .body(Full::new(Bytes::new()))
+        .unwrap()
+}
+
+/// Run one upgraded connection until it closes
+///
+/// Socket reads feed the sans-IO state machine from gust-core, which
+/// answers pings, echoes close frames, and enforces keepalive
+/// deadlines on its own; queued frames flush after every step. Only
+/// open/message/close events cross into JS.
+async fn ws_connection_loop(
+    state: Arc<ServerState>,
+    route: Arc<WsRoute>,
+    upgraded: hyper::upgrade::Upgraded,
+    peer: std::net::SocketAddr,
+    path: String,
+) {
+    use gust_core::handlers::{
+        CloseFrame, ConnectionState, FrameDecoderConfig, WebSocketConnection, WebSocketEvent,
+    };
+    use hyper_util::rt::TokioIo;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let connection_id = state.next_ws_id.fetch_add(1, Ordering::Relaxed) as i64;
+    let (command_tx, mut command_rx) = tokio::sync::mpsc::unbounded_channel();
+    state
+        .ws_connections
+        .write()
+        .await
+        .insert(connection_id, command_tx.clone());
+
+    // Browsers must mask client-to-server frames (RFC 6455 §5.1)
+    let mut config = FrameDecoderConfig::new().require_masked(true);
+    if let Some(max) = route.options.max_message_bytes {
+        config = config.max_message_size(max as usize);
+    }
+    let mut conn = WebSocketConnection::new(config);
+    if let Some(interval) = route.options.ping_interval_ms {
+        conn = conn.ping_interval(interval as u64);
+    }
+
+    // The state machine only compares durations, so a monotonic
+    // connection-relative clock is enough
+    let started = std::time::Instant::now();
+    let remote_addr = peer.to_string();
+    let mut io = TokioIo::new(upgraded);
+    let mut buf = vec![0u8; 16 * 1024];
+    // Close details for the "close" event; stays None when the
+    // transport drops without a close handshake
+    let mut close_frame: Option<CloseFrame> = None;
+
+    deliver_ws_event(
+        &route,
+        command_tx.clone(),
+        ws_event_context(connection_id, &path, &remote_addr, "open"),
+    );
+
+    'conn: loop {
+        // Flush whatever the last step queued (data frames, pongs,
+        // close echoes) before waiting again
+        for frame in conn.take_outgoing() {
+            if io.write_all(&frame.encode()).await.is_err() {
+                break 'conn;
+            }
+        }
+        if conn.state() == ConnectionState::Closed {
+            break;
+        }
+
+        tokio::select! {
+            read = io.read(&mut buf) => {
+                let n = match read {
+                    Ok(0) | Err(_) => break 'conn,
+                    Ok(n) => n,
+                };
+                let now = started.elapsed().as_millis() as u64;
+                match conn.receive(&buf[..n], now) {
+                    Ok(events) => {
+                        for event in events {
+                            match event {
+                                WebSocketEvent::Text(text) => {
+                                    let mut ctx = ws_event_context(
+                                        connection_id, &path, &remote_addr, "message",
+                                    );
+                                    ctx.text = Some(text);
+                                    deliver_ws_event(&route, command_tx.clone(), ctx);
+                                }
+                                WebSocketEvent::Binary(data) => {
+                                    let mut ctx = ws_event_context(
+                                        connection_id, &path, &remote_addr, "message",
+                                    );
+                                    ctx.data = Some(data.into());
+                                    deliver_ws_event(&route, command_tx.clone(), ctx);
+                                }
+                                // Liveness already recorded by the state machine
+                                WebSocketEvent::Pong(_) => {}
+                                WebSocketEvent::Closed(frame) => {
+                                    close_frame = frame;
+                                }
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        // The matching close frame is already queued;
+                        // the flush at the loop top sends it
+                        close_frame = Some(CloseFrame {
+                            code: error.close_code(),
+                            reason: "Protocol error".to_string(),
+                        });
+                    }
+                }
+            }
+            command = command_rx.recv() => {
+                match command {
+                    Some(WsCommand::Text(text)) => {
+                        conn.send_text(text);
+                    }
+                    Some(WsCommand::Binary(data)) => {
+                        conn.send_binary(data);
+                    }
+                    Some(WsCommand::Close(code, reason)) => conn.close(code, &reason),
+                    // Unreachable while the registry holds a sender
+                    None => break 'conn,
+                }
+            }
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {
+                let now = started.elapsed().as_millis() as u64;
+                if let Some(WebSocketEvent::Closed(frame)) = conn.tick(now) {
+                    close_frame = frame;
+                }
+            }
+        }
+    }
+
+    // Best-effort flush of the final close frame; the peer may
+    // already be gone
+    for frame in conn.take_outgoing() {
+        if io.write_all(&frame.encode()).await.is_err() {
+            break;
+        }
+    }
+    let _ = io.shutdown().await;
+
+    state.ws_connections.write().await.remove(&connection_id);
+
+    let mut ctx = ws_event_context(connection_id, &path, &remote_addr, "close");
+    ctx.code = close_frame.as_ref().map(|frame| frame.code as u32);
+    ctx.reason = close_frame.map(|frame| frame.reason);
+    deliver_ws_event(&route, command_tx, ctx);
+}
+
+/// A WebSocket event context with no payload fields set
+fn ws_event_context(
+    connection_id: i64,
+    path: &str,
+    remote_addr: &str,
+    event: &str,
+) -> WsEventContext {
+    WsEventContext {
+        connection_id,
+        path: path.to_string(),
+        remote_addr: remote_addr.to_string(),
+        event: event.to_string(),
+        text: None,
+        data: None,
+        code: None,
+        reason: None,
+    }
+}
+
+/// Deliver one WebSocket event to JS on the runtime
+///
+/// The optional reply comes back through the connection's command
+/// channel, so slow handlers never block the frame loop (or the
+/// ping/pong it keeps alive).
+fn deliver_ws_event(route: &Arc<WsRoute>, commands: WsCommandSender, ctx: WsEventContext) {
+    let callback = route.callback.clone();
+    tokio::spawn(async move {
+        // Handlers that return nothing fail the Promise coercion; treat
+        // that (and rejections) as "no reply"
+        if let Ok(promise) = callback.call_async::<Promise<Option<WsReply>>>(ctx).await {
+            if let Ok(Some(reply)) = promise.await {
+                if let Some(text) = reply.text {
+                    let _ = commands.send(WsCommand::Text(text));
+                }
+                if let Some(data) = reply.data {
+                    let _ = commands.send(WsCommand::Binary(data.to_vec()));
+                }
+                if reply.close.unwrap_or(false) {
+                    let _ = commands.send(WsCommand::Close(1000, String::new()));
+                }
+            }
+        }
+    });
+}
+
+/// Check a header name is an RFC 7230 token
+fn valid_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.bytes().all(|b| {
+            b.is_ascii_alphanumeric()
+                || matches!(
+                    b,
+                    b'!' | b'#'
+                        | b'$'
+                        | b'%'
+                        | b'&'
+                        | b'\''
+                        | b'*'
+                        | b'+'
+                        | b'-'
+                        | b'.'
+                        | b'^'
+                        | b'_'
+                        | b'`'
+                        | b'|'
+                        | b'~'
+                )
+        })
+}
+
+/// Check a header value has no CR/LF injection or invalid bytes
+fn valid_header_value(value: &str) -> bool {
+    value
+        .bytes()
+        .all(|b| b == b'\t' || (b >= 0x20 && b != 0x7f))
+}
+
+/// Split a request body into the `body`/`body_buffer` pair of a
+/// RequestContext
+///
+/// The buffer carries the raw bytes; the string is empty (not lossy)
+/// when the bytes are not valid UTF-8, matching the previous behavior
+/// of text handlers while keeping binary payloads intact.
+fn context_body(bytes: &Bytes) -> (String, Option<Buffer>) {
+    if bytes.is_empty() {
+        return (String::new(), None);
+    }
+    let body = std::str::from_utf8(bytes)
+        .map(str::to_string)
+        .unwrap_or_default();
+    (body, Some(Buffer::from(bytes.to_vec())))
+}
+
 /// Convert ResponseData to our Response type
+///
+/// Header names are canonicalized to lowercase; a name or value that
+/// fails validation (CR/LF injection, invalid bytes) turns the whole
+/// response into a clear 500 instead of panicking downstream.
 fn response_data_to_response(data: ResponseData) -> Response {
+    // A binary body takes precedence over the String field
+    let body = match data.body_buffer {
+        Some(buffer) => Bytes::copy_from_slice(&buffer),
+        None => Bytes::from(data.body),
+    };
     let mut res = ResponseBuilder::new(StatusCode(data.status as u16))
-        .body(data.body)
+        .body(body)
         .build();
 
-    for (name, value) in data.headers {
-        res.headers.push((name, value));
+    fn push(res: &mut Response, name: String, value: String) -> std::result::Result<(), String> {
+        if !valid_header_name(&name) {
+            return Err(format!("invalid header name {:?}", name));
+        }
+        if !valid_header_value(&value) {
+            return Err(format!("invalid value for header {:?}", name));
+        }
+        res.headers.push((name.to_ascii_lowercase(), value));
+        Ok(())
     }
 
-    res
+    // Flat pairs carry duplicate headers (e.g. multiple Set-Cookie)
+    // that a HashMap cannot represent
+    let result = if let Some(flat) = data.headers_flat {
+        let mut pairs = flat.into_iter();
+        let mut result = Ok(());
+        while let (Some(name), Some(value)) = (pairs.next(), pairs.next()) {
+            result = push(&mut res, name, value);
+            if result.is_err() {
+                break;
+            }
+        }
+        result
+    } else {
+        let mut result = Ok(());
+        for (name, value) in data.headers {
+            result = push(&mut res, name, value);
+            if result.is_err() {
+                break;
+            }
+        }
+        result
+    };
+
+    match result {
+        Ok(()) => res,
+        Err(msg) => {
+            eprintln!("Rejected handler response: {}", msg);
+            Response::internal_error(&format!("Invalid response header: {}", msg))
+        }
+    }
+}
+
+/// Serve a pre-rendered static route via hyper
+///
+/// Honors If-None-Match with a 304 (weak validators and comma lists
+/// accepted); HEAD requests reach this with the full response and
+/// hyper elides the body while keeping Content-Length.
+fn static_to_hyper_response(
+    if_none_match: Option<&str>,
+    res: &StaticResponse,
+) -> hyper::Response<Full<Bytes>> {
+    if let Some(inm) = if_none_match {
+        let matches = inm
+            .split(',')
+            .any(|tag| rust_check_if_none_match(tag.trim().trim_start_matches("W/"), &res.etag));
+        if matches {
+            return hyper::Response::builder()
+                .status(304)
+                .header("etag", res.etag.as_str())
+                .body(Full::new(Bytes::new()))
+                .unwrap();
+        }
+    }
+
+    let mut builder = hyper::Response::builder().status(res.status);
+    for (name, value) in &res.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    match builder.body(Full::new(res.body.clone())) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Rejected static response: {}", e);
+            hyper::Response::builder()
+                .status(500)
+                .header("content-type", "text/plain")
+                .body(Full::new(Bytes::from(format!("Invalid response: {}", e))))
+                .unwrap()
+        }
+    }
 }
 
 /// Convert our Response to hyper Response
+///
+/// Invalid headers or status codes produce a clear 500 instead of
+/// panicking the connection task.
 fn to_hyper_response(res: Response) -> hyper::Response<Full<Bytes>> {
     let mut builder = hyper::Response::builder().status(res.status.as_u16());
 
@@ -2256,7 +8476,17 @@ fn to_hyper_response(res: Response) -> hyper::Response<Full<Bytes>> {
         builder = builder.header(name.as_str(), value.as_str());
     }
 
-    builder.body(Full::new(res.body)).unwrap()
+    match builder.body(Full::new(res.body)) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Rejected handler response: {}", e);
+            hyper::Response::builder()
+                .status(500)
+                .header("content-type", "text/plain")
+                .body(Full::new(Bytes::from(format!("Invalid response: {}", e))))
+                .unwrap()
+        }
+    }
 }
 
 /// Check if io_uring is available (Linux kernel 5.1+)
@@ -2487,11 +8717,94 @@ fn maybe_compress_response(
     (body, None)
 }
 
-/// Allow unused for now until compression is fully integrated
-#[allow(dead_code)]
-fn _compression_placeholder() {
-    #[cfg(feature = "compress")]
-    let _ = maybe_compress_response;
+/// Apply the configured compression to a finished response
+///
+/// Already-encoded bodies (proxied upstreams) and SSE pass through
+/// untouched; everything else goes through `maybe_compress_response`,
+/// which enforces the content-type allowlist, threshold, and
+/// Accept-Encoding negotiation.
+#[cfg(feature = "compress")]
+async fn compress_hyper_response(
+    state: &Arc<ServerState>,
+    accept_encoding: Option<&str>,
+    response: hyper::Response<Full<Bytes>>,
+) -> hyper::Response<Full<Bytes>> {
+    let config = match state.compression.read().await.clone() {
+        Some(config) => config,
+        None => return response,
+    };
+
+    if response
+        .headers()
+        .contains_key(hyper::header::CONTENT_ENCODING)
+    {
+        return response;
+    }
+    let content_type = response
+        .headers()
+        .get(hyper::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    // SSE must flush event-by-event; never buffer it through an encoder
+    if content_type
+        .as_deref()
+        .is_some_and(|ct| ct.starts_with("text/event-stream"))
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(never) => match never {},
+    };
+
+    let (body, encoding) =
+        maybe_compress_response(bytes, accept_encoding, content_type.as_deref(), &config);
+    if let Some(encoding) = encoding {
+        if let Ok(value) = hyper::header::HeaderValue::from_str(&encoding) {
+            parts.headers.insert(hyper::header::CONTENT_ENCODING, value);
+        }
+        // An explicit Content-Length from the handler now describes the
+        // uncompressed body; correct it
+        parts
+            .headers
+            .insert(hyper::header::CONTENT_LENGTH, body.len().into());
+        append_vary_accept_encoding(&mut parts.headers);
+    }
+    hyper::Response::from_parts(parts, Full::new(body))
+}
+
+#[cfg(not(feature = "compress"))]
+async fn compress_hyper_response(
+    _state: &Arc<ServerState>,
+    _accept_encoding: Option<&str>,
+    response: hyper::Response<Full<Bytes>>,
+) -> hyper::Response<Full<Bytes>> {
+    response
+}
+
+/// Add Accept-Encoding to Vary so caches key on the negotiated encoding
+#[cfg(feature = "compress")]
+fn append_vary_accept_encoding(headers: &mut hyper::HeaderMap) {
+    match headers.get(hyper::header::VARY).and_then(|v| v.to_str().ok()) {
+        Some(value)
+            if value
+                .split(',')
+                .any(|v| v.trim().eq_ignore_ascii_case("accept-encoding") || v.trim() == "*") => {}
+        Some(value) => {
+            let merged = format!("{}, Accept-Encoding", value);
+            if let Ok(value) = hyper::header::HeaderValue::from_str(&merged) {
+                headers.insert(hyper::header::VARY, value);
+            }
+        }
+        None => {
+            headers.insert(
+                hyper::header::VARY,
+                hyper::header::HeaderValue::from_static("Accept-Encoding"),
+            );
+        }
+    }
 }
 
 // ============================================================================
@@ -2801,6 +9114,34 @@ pub fn is_valid_close_code(code: u32) -> bool {
     }
 }
 
+// ============================================================================
+// Server-Sent Events Formatting
+// ============================================================================
+
+/// Encode a Server-Sent Event in `text/event-stream` wire format
+/// Uses gust_core::SseEvent internally
+///
+/// Data is split into one `data:` line per line (any newline
+/// convention); id/event values are sanitized so they cannot inject
+/// extra fields. Pass a comment for keep-alive (`: text`) events.
+#[napi]
+pub fn encode_sse_event(
+    data: Option<String>,
+    id: Option<String>,
+    event: Option<String>,
+    retry: Option<u32>,
+    comment: Option<String>,
+) -> String {
+    let sse = CoreSseEvent {
+        id,
+        event,
+        data: data.unwrap_or_default(),
+        retry,
+        comment,
+    };
+    sse.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2823,4 +9164,432 @@ mod tests {
         assert!(security.hsts.unwrap());
         assert_eq!(security.frame_options, Some("DENY".to_string()));
     }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_maybe_compress_response_gzip() {
+        let config = CompressionConfig {
+            gzip: Some(true),
+            brotli: None,
+            threshold: Some(16),
+            level: None,
+        };
+        let body = Bytes::from("a".repeat(256));
+
+        let (compressed, encoding) = maybe_compress_response(
+            body.clone(),
+            Some("gzip, deflate"),
+            Some("application/json"),
+            &config,
+        );
+        assert_eq!(encoding.as_deref(), Some("gzip"));
+        assert_eq!(&compressed[..2], &[0x1f, 0x8b]); // gzip magic
+        assert!(compressed.len() < body.len());
+
+        // Below threshold: untouched
+        let (small, encoding) = maybe_compress_response(
+            Bytes::from("ok"),
+            Some("gzip"),
+            Some("application/json"),
+            &config,
+        );
+        assert!(encoding.is_none());
+        assert_eq!(small, Bytes::from("ok"));
+
+        // Non-compressible content type: untouched
+        let (image, encoding) =
+            maybe_compress_response(body.clone(), Some("gzip"), Some("image/png"), &config);
+        assert!(encoding.is_none());
+        assert_eq!(image, body);
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_maybe_compress_response_brotli() {
+        let config = CompressionConfig {
+            gzip: Some(true),
+            brotli: Some(true),
+            threshold: Some(16),
+            level: None,
+        };
+        let body = Bytes::from("a".repeat(256));
+
+        // Brotli preferred when both are accepted
+        let (compressed, encoding) = maybe_compress_response(
+            body.clone(),
+            Some("gzip, br"),
+            Some("text/html"),
+            &config,
+        );
+        assert_eq!(encoding.as_deref(), Some("br"));
+        assert!(compressed.len() < body.len());
+
+        // Client only speaks gzip: falls back
+        let (_, encoding) =
+            maybe_compress_response(body, Some("gzip"), Some("text/html"), &config);
+        assert_eq!(encoding.as_deref(), Some("gzip"));
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_append_vary_accept_encoding() {
+        let mut headers = hyper::HeaderMap::new();
+        append_vary_accept_encoding(&mut headers);
+        assert_eq!(headers.get("vary").unwrap(), "Accept-Encoding");
+
+        // Existing values are merged, not replaced
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("vary", "Origin".parse().unwrap());
+        append_vary_accept_encoding(&mut headers);
+        assert_eq!(headers.get("vary").unwrap(), "Origin, Accept-Encoding");
+
+        // Already present: no duplicate
+        append_vary_accept_encoding(&mut headers);
+        assert_eq!(headers.get("vary").unwrap(), "Origin, Accept-Encoding");
+    }
+
+    #[test]
+    fn test_extract_client_info() {
+        let state = ServerState::new();
+        let peer: std::net::SocketAddr = "10.0.0.5:4000".parse().unwrap();
+
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("host", "internal:3000".parse().unwrap());
+        headers.insert("x-forwarded-for", "203.0.113.9".parse().unwrap());
+        headers.insert("x-forwarded-proto", "https".parse().unwrap());
+        headers.insert("x-forwarded-host", "example.com".parse().unwrap());
+
+        // No trustProxy: forwarded headers are ignored
+        let info = extract_client_info(&state, peer, "http", &headers);
+        assert_eq!(info.ip, "10.0.0.5");
+        assert_eq!(info.protocol, "http");
+        assert_eq!(info.host, "internal:3000");
+
+        // Trusted peer (10.0.0.0/8 is in the loopback set): forwarded
+        // headers determine ip/protocol/host
+        state
+            .proxy_config
+            .store(Arc::new(Some(trust_proxy_config(TrustProxy::Loopback))));
+        let info = extract_client_info(&state, peer, "http", &headers);
+        assert_eq!(info.ip, "203.0.113.9");
+        assert_eq!(info.protocol, "https");
+        assert_eq!(info.host, "example.com");
+
+        // Trusted peer without forwarded proto: listener scheme wins
+        headers.remove("x-forwarded-proto");
+        let info = extract_client_info(&state, peer, "https", &headers);
+        assert_eq!(info.protocol, "https");
+
+        // Untrusted peer: back to the socket view
+        state
+            .proxy_config
+            .store(Arc::new(Some(trust_proxy_config(TrustProxy::None))));
+        let info = extract_client_info(&state, peer, "http", &headers);
+        assert_eq!(info.ip, "10.0.0.5");
+        assert_eq!(info.host, "internal:3000");
+    }
+
+    #[test]
+    fn test_trust_proxy_addresses() {
+        // Invalid entries error at configuration time
+        assert!(trust_proxy_addresses_config(&["not an ip".to_string()]).is_err());
+
+        let state = ServerState::new();
+        state.proxy_config.store(Arc::new(Some(
+            trust_proxy_addresses_config(&["10.0.0.0/8".to_string(), "2.2.2.2".to_string()])
+                .unwrap(),
+        )));
+
+        // Per-hop walk skips trusted hops; the spoofed leftmost entry
+        // is not reported as the client
+        let peer: std::net::SocketAddr = "10.0.0.5:4000".parse().unwrap();
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            "9.9.9.9, 1.1.1.1, 2.2.2.2".parse().unwrap(),
+        );
+        let info = extract_client_info(&state, peer, "http", &headers);
+        assert_eq!(info.ip, "1.1.1.1");
+    }
+
+    #[test]
+    fn test_server_config_from_value() {
+        let doc = "\
+port = 8080
+hostname = \"127.0.0.1\"
+trust_proxy = \"loopback\"
+
+[cors]
+origins = [\"https://app.example\"]
+credentials = true
+
+[rate_limit]
+max_requests = 100
+window_seconds = 60
+
+[tls]
+cert_path = \"certs/server.pem\"
+key_path = \"certs/server.key\"
+";
+        let value = gust_core::pure::parse_toml(doc).unwrap();
+        let config = server_config_from_value(&value).unwrap();
+
+        assert_eq!(config.port, Some(8080));
+        assert_eq!(config.hostname.as_deref(), Some("127.0.0.1"));
+        assert!(matches!(config.trust_proxy, Some(TrustProxy::Loopback)));
+        let cors = config.cors.unwrap();
+        assert_eq!(cors.origins, Some(vec!["https://app.example".to_string()]));
+        assert_eq!(cors.credentials, Some(true));
+        assert_eq!(config.rate_limit.unwrap().max_requests, 100);
+        assert_eq!(
+            config.tls.unwrap().cert_path.as_deref(),
+            Some("certs/server.pem")
+        );
+
+        // Unknown and invalid keys are named in the error
+        let value = gust_core::pure::parse_toml("prot = 8080\n").unwrap();
+        let err = server_config_from_value(&value).err().unwrap();
+        assert!(err.contains("prot"), "{}", err);
+
+        let value = gust_core::pure::parse_toml("port = 99999\n").unwrap();
+        assert!(server_config_from_value(&value).err().unwrap().contains("65535"));
+
+        let value =
+            gust_core::pure::parse_toml("[cors]\norigins = \"not-an-array\"\n").unwrap();
+        assert!(server_config_from_value(&value)
+            .err()
+            .unwrap()
+            .contains("cors.origins"));
+
+        // JSON documents use the same mapping, with camelCase keys
+        let value = gust_core::pure::parse_json(
+            "{\"port\": 8080, \"maxBodySize\": 1048576, \"cors\": {\"maxAge\": 600}}",
+        )
+        .unwrap();
+        let config = server_config_from_value(&value).unwrap();
+        assert_eq!(config.max_body_size, Some(1048576));
+        assert_eq!(config.cors.unwrap().max_age, Some(600));
+    }
+
+    #[test]
+    fn test_apply_env_overrides() {
+        let vars: HashMap<&str, &str> = [
+            ("GUST_PORT", "9090"),
+            ("GUST_HTTP2", "true"),
+            ("GUST_TLS_CERT_PATH", "/etc/gust/cert.pem"),
+        ]
+        .into_iter()
+        .collect();
+        let env = |name: &str| vars.get(name).map(|v| v.to_string());
+
+        let mut config = ServerConfig {
+            port: Some(3000),
+            ..ServerConfig::default()
+        };
+        apply_env_overrides(&mut config, &env).unwrap();
+
+        assert_eq!(config.port, Some(9090));
+        assert_eq!(config.http2, Some(true));
+        assert_eq!(
+            config.tls.unwrap().cert_path.as_deref(),
+            Some("/etc/gust/cert.pem")
+        );
+
+        // Invalid values name the variable
+        let env = |name: &str| (name == "GUST_PORT").then(|| "abc".to_string());
+        let err = apply_env_overrides(&mut ServerConfig::default(), &env).err().unwrap();
+        assert!(err.contains("GUST_PORT"));
+    }
+
+    #[test]
+    fn test_load_config_file_rejects_unknown_extension() {
+        let err = load_config_file("gust.yaml").err().unwrap();
+        assert!(err.contains(".toml or .json"));
+    }
+
+    #[test]
+    fn test_set_log_level() {
+        let server = GustServer::new();
+        assert_eq!(
+            server.state.observability.load().log_level,
+            LogLevel::Info
+        );
+
+        server.set_log_level("debug".to_string()).unwrap();
+        assert_eq!(
+            server.state.observability.load().log_level,
+            LogLevel::Debug
+        );
+
+        // Unknown levels error and leave the config untouched
+        assert!(server.set_log_level("verbose".to_string()).is_err());
+        assert_eq!(
+            server.state.observability.load().log_level,
+            LogLevel::Debug
+        );
+
+        // Levels are ordered by verbosity for threshold checks
+        assert!(LogLevel::Trace > LogLevel::Debug);
+        assert!(LogLevel::Warn > LogLevel::Error);
+    }
+
+    #[test]
+    fn test_apply_observability_update() {
+        let current = ObservabilityConfig::default();
+
+        // Partial updates touch only the listed keys
+        let updated = apply_observability_update(
+            &current,
+            "{\"logLevel\":\"warn\",\"slowRequestThresholdMs\":250}",
+        )
+        .unwrap();
+        assert_eq!(updated.log_level, LogLevel::Warn);
+        assert_eq!(updated.slow_request_threshold_ms, 250.0);
+        assert_eq!(updated.sample_rate, current.sample_rate);
+
+        // Invalid values reject the whole update
+        assert!(apply_observability_update(&current, "{\"sampleRate\":1.5}").is_err());
+        assert!(apply_observability_update(&current, "{\"logLevel\":\"loud\"}").is_err());
+        assert!(apply_observability_update(&current, "not json").is_err());
+    }
+
+    #[test]
+    fn test_sample_request() {
+        assert!(sample_request(1.0));
+        assert!(!sample_request(0.0));
+
+        // A 25% rate keeps roughly a quarter of a run of calls
+        let kept = (0..1000).filter(|_| sample_request(0.25)).count();
+        assert!((200..=300).contains(&kept), "kept {}", kept);
+    }
+
+    #[test]
+    fn test_static_response_conditional() {
+        let body = Bytes::from("hello");
+        let etag = rust_body_etag(b"hello");
+        let res = StaticResponse {
+            status: 201,
+            headers: vec![
+                ("content-type".to_string(), "text/plain".to_string()),
+                ("etag".to_string(), etag.clone()),
+            ],
+            body: body.clone(),
+            etag: etag.clone(),
+            bytes: Bytes::new(),
+        };
+
+        // Status and headers are preserved
+        let hyper_res = static_to_hyper_response(None, &res);
+        assert_eq!(hyper_res.status(), 201);
+        assert_eq!(hyper_res.headers()["content-type"], "text/plain");
+        assert_eq!(hyper_res.headers()["etag"], etag.as_str());
+
+        // Matching If-None-Match (including weak form) yields a 304
+        let hyper_res = static_to_hyper_response(Some(&etag), &res);
+        assert_eq!(hyper_res.status(), 304);
+        let weak = format!("W/{}", etag);
+        let hyper_res = static_to_hyper_response(Some(&weak), &res);
+        assert_eq!(hyper_res.status(), 304);
+        let hyper_res = static_to_hyper_response(Some("\"other\""), &res);
+        assert_eq!(hyper_res.status(), 201);
+    }
+
+    #[test]
+    fn test_header_validation() {
+        assert!(valid_header_name("content-type"));
+        assert!(valid_header_name("X-Custom_1"));
+        assert!(!valid_header_name(""));
+        assert!(!valid_header_name("bad name"));
+        assert!(!valid_header_name("bad:name"));
+
+        assert!(valid_header_value("text/html; charset=utf-8"));
+        assert!(valid_header_value("tab\tseparated"));
+        assert!(!valid_header_value("evil\r\nset-cookie: pwned"));
+        assert!(!valid_header_value("nul\0byte"));
+    }
+
+    /// xorshift64 - cheap deterministic generator for fuzz-style tests
+    fn xorshift(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    #[test]
+    fn test_response_conversion_never_panics() {
+        // Alphabet biased toward header edge cases: separators,
+        // whitespace, CR/LF/NUL injection, DEL, non-token bytes
+        const ALPHABET: &[u8] = b"abcXYZ019-_.:;=/ \t\r\n\x00\x7f\"'";
+
+        fn random_string(seed: &mut u64, max_len: u64) -> String {
+            let len = xorshift(seed) % (max_len + 1);
+            (0..len)
+                .map(|_| ALPHABET[(xorshift(seed) as usize) % ALPHABET.len()] as char)
+                .collect()
+        }
+
+        let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+
+        for _ in 0..1000 {
+            let mut headers = HashMap::new();
+            for _ in 0..(xorshift(&mut seed) % 4) {
+                headers.insert(
+                    random_string(&mut seed, 12),
+                    random_string(&mut seed, 24),
+                );
+            }
+            let headers_flat = if xorshift(&mut seed).is_multiple_of(2) {
+                Some(
+                    (0..xorshift(&mut seed) % 6)
+                        .map(|_| random_string(&mut seed, 16))
+                        .collect(),
+                )
+            } else {
+                None
+            };
+
+            let data = ResponseData {
+                status: (xorshift(&mut seed) % 1200) as u32,
+                headers,
+                headers_flat,
+                body: random_string(&mut seed, 32),
+                body_buffer: None,
+                streaming: None,
+            };
+
+            // Must produce some hyper response, never panic
+            let hyper_res = to_hyper_response(response_data_to_response(data));
+            assert!(hyper_res.status().as_u16() > 0);
+        }
+    }
+
+    #[test]
+    fn test_response_data_rejects_header_injection() {
+        let mut headers = HashMap::new();
+        headers.insert("x-evil".to_string(), "a\r\nx-injected: 1".to_string());
+        let res = response_data_to_response(ResponseData {
+            status: 200,
+            headers,
+            headers_flat: None,
+            body: "ok".to_string(),
+            body_buffer: None,
+            streaming: None,
+        });
+        assert_eq!(res.status.as_u16(), 500);
+
+        // Valid headers pass through with lowercased names
+        let mut headers = HashMap::new();
+        headers.insert("X-Custom".to_string(), "value".to_string());
+        let res = response_data_to_response(ResponseData {
+            status: 200,
+            headers,
+            headers_flat: None,
+            body: "ok".to_string(),
+            body_buffer: None,
+            streaming: None,
+        });
+        assert_eq!(res.status.as_u16(), 200);
+        assert_eq!(res.header("x-custom"), Some("value"));
+    }
 }
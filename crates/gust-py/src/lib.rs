@@ -0,0 +1,492 @@
+//! gust-py: Python bindings for the gust router and middleware primitives
+//!
+//! Exposes the SSOT router, schema validation, JWT, and the pure
+//! middleware helpers via pyo3 so ASGI adapters can reuse the same
+//! logic the JS bindings do. The surface mirrors gust-napi where that
+//! translates naturally to Python (keyword arguments instead of flat
+//! positional options).
+
+use gust_core::middleware::jwt::{Algorithm, Claims, Jwt as CoreJwt, JwtConfig};
+use gust_core::middleware::validate::{
+    validate as validate_schema, Schema, SchemaType, StringFormat,
+};
+use gust_core::pure::{
+    create_cors_headers, fixed_window_decision, get_allowed_origin, is_origin_allowed, parse_json,
+    parse_client_ip, rate_limit_headers as pure_rate_limit_headers,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+
+// ============================================================================
+// Router
+// ============================================================================
+
+/// Radix-tree router shared with the JS bindings
+#[pyclass]
+pub struct Router {
+    inner: gust_core::Router,
+}
+
+#[pymethods]
+impl Router {
+    #[new]
+    fn new() -> Self {
+        Self {
+            inner: gust_core::Router::new(),
+        }
+    }
+
+    /// Insert a route; `:param` and `*` wildcard segments are supported
+    fn insert(&mut self, method: &str, path: &str, handler_id: u32) {
+        self.inner.insert(method, path, handler_id);
+    }
+
+    /// Match a request, returning `(handler_id, params)` or `None`
+    fn find(&self, method: &str, path: &str) -> Option<(u32, HashMap<String, String>)> {
+        self.inner
+            .find(method, path)
+            .map(|m| (m.handler_id, m.params.into_iter().collect()))
+    }
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Single validation failure with a JSONPath-style location
+#[pyclass(get_all, frozen, skip_from_py_object)]
+#[derive(Clone)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+    /// The invalid value, when available (for debugging)
+    pub value: Option<String>,
+}
+
+/// Outcome of validating a JSON document
+#[pyclass(get_all, frozen)]
+pub struct ValidationResult {
+    pub valid: bool,
+    pub errors: Vec<ValidationError>,
+}
+
+fn schema_type_from_str(s: &str) -> PyResult<SchemaType> {
+    match s {
+        "string" => Ok(SchemaType::String),
+        "number" => Ok(SchemaType::Number),
+        "boolean" => Ok(SchemaType::Boolean),
+        "object" => Ok(SchemaType::Object),
+        "array" => Ok(SchemaType::Array),
+        "any" => Ok(SchemaType::Any),
+        other => Err(PyValueError::new_err(format!(
+            "unknown schema type '{}' (expected string, number, boolean, object, array or any)",
+            other
+        ))),
+    }
+}
+
+fn string_format_from_str(s: &str) -> PyResult<StringFormat> {
+    match s {
+        "email" => Ok(StringFormat::Email),
+        "url" => Ok(StringFormat::Url),
+        "uuid" => Ok(StringFormat::Uuid),
+        "date" => Ok(StringFormat::Date),
+        "date-time" => Ok(StringFormat::DateTime),
+        other => Err(PyValueError::new_err(format!(
+            "unknown string format '{}' (expected email, url, uuid, date or date-time)",
+            other
+        ))),
+    }
+}
+
+/// Validate a JSON document against scalar schema constraints
+///
+/// Mirrors the napi `validateJson` surface; constraints are keyword
+/// arguments instead of positional options.
+#[pyfunction]
+#[pyo3(signature = (json_str, schema_type="any", required=true, min_length=None, max_length=None,
+    pattern=None, format=None, min=None, max=None, integer=false))]
+#[allow(clippy::too_many_arguments)]
+fn validate_json(
+    json_str: &str,
+    schema_type: &str,
+    required: bool,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    pattern: Option<String>,
+    format: Option<&str>,
+    min: Option<f64>,
+    max: Option<f64>,
+    integer: bool,
+) -> PyResult<ValidationResult> {
+    let schema = Schema {
+        schema_type: schema_type_from_str(schema_type)?,
+        required,
+        min_length,
+        max_length,
+        pattern,
+        format: format.map(string_format_from_str).transpose()?,
+        min,
+        max,
+        integer,
+        ..Default::default()
+    };
+
+    let value = match parse_json(json_str) {
+        Ok(value) => value,
+        Err(err) => {
+            return Ok(ValidationResult {
+                valid: false,
+                errors: vec![ValidationError {
+                    path: "$".to_string(),
+                    message: format!("Invalid JSON: {}", err),
+                    value: None,
+                }],
+            });
+        }
+    };
+
+    let errors: Vec<ValidationError> = validate_schema(&value, &schema, "$")
+        .into_iter()
+        .map(|e| ValidationError {
+            path: e.path,
+            message: e.message,
+            value: e.value,
+        })
+        .collect();
+    Ok(ValidationResult {
+        valid: errors.is_empty(),
+        errors,
+    })
+}
+
+// ============================================================================
+// JWT
+// ============================================================================
+
+/// HMAC JWT encoder/verifier (HS256/HS384/HS512)
+#[pyclass]
+pub struct Jwt {
+    inner: CoreJwt,
+}
+
+#[pymethods]
+impl Jwt {
+    #[new]
+    #[pyo3(signature = (secret, algorithm="HS256", leeway=0))]
+    fn new(secret: &str, algorithm: &str, leeway: u64) -> PyResult<Self> {
+        let algorithm = match algorithm {
+            "HS256" => Algorithm::HS256,
+            "HS384" => Algorithm::HS384,
+            "HS512" => Algorithm::HS512,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unsupported algorithm '{}' (expected HS256, HS384 or HS512)",
+                    other
+                )));
+            }
+        };
+        Ok(Self {
+            inner: CoreJwt::new(JwtConfig::new(secret).algorithm(algorithm).leeway(leeway)),
+        })
+    }
+
+    /// Sign a token from registered and custom claims
+    ///
+    /// `expires_in` sets `exp` relative to now (and wins over an
+    /// explicit `exp`); custom claim values are strings.
+    #[pyo3(signature = (sub=None, iss=None, aud=None, jti=None, exp=None, nbf=None, iat=None,
+        expires_in=None, custom=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn encode(
+        &self,
+        sub: Option<String>,
+        iss: Option<String>,
+        aud: Option<String>,
+        jti: Option<String>,
+        exp: Option<u64>,
+        nbf: Option<u64>,
+        iat: Option<u64>,
+        expires_in: Option<u64>,
+        custom: Option<HashMap<String, String>>,
+    ) -> String {
+        let mut claims = Claims::new();
+        claims.sub = sub;
+        claims.iss = iss;
+        claims.aud = aud;
+        claims.jti = jti;
+        claims.exp = exp;
+        claims.nbf = nbf;
+        claims.iat = iat;
+        if let Some(seconds) = expires_in {
+            claims = claims.exp_in(seconds);
+        }
+        if let Some(custom) = custom {
+            claims.custom = custom;
+        }
+        self.inner.encode(&claims)
+    }
+
+    /// Verify a token and return its claims as a dict
+    ///
+    /// Raises `ValueError` on bad signatures, malformed tokens, or
+    /// expired/not-yet-valid claims (subject to the configured leeway).
+    fn decode(&self, py: Python<'_>, token: &str) -> PyResult<Py<PyDict>> {
+        let claims = self
+            .inner
+            .decode(token)
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+        let dict = PyDict::new(py);
+        if let Some(iss) = claims.iss {
+            dict.set_item("iss", iss)?;
+        }
+        if let Some(sub) = claims.sub {
+            dict.set_item("sub", sub)?;
+        }
+        if let Some(aud) = claims.aud {
+            dict.set_item("aud", aud)?;
+        }
+        if let Some(jti) = claims.jti {
+            dict.set_item("jti", jti)?;
+        }
+        if let Some(exp) = claims.exp {
+            dict.set_item("exp", exp)?;
+        }
+        if let Some(nbf) = claims.nbf {
+            dict.set_item("nbf", nbf)?;
+        }
+        if let Some(iat) = claims.iat {
+            dict.set_item("iat", iat)?;
+        }
+        for (key, value) in claims.custom {
+            dict.set_item(key, value)?;
+        }
+        Ok(dict.unbind())
+    }
+}
+
+// ============================================================================
+// Middleware primitives
+// ============================================================================
+
+/// Best-effort client IP from proxy headers
+///
+/// Prefers the first `X-Forwarded-For` hop, then `X-Real-IP`, then the
+/// remote address.
+#[pyfunction]
+#[pyo3(signature = (forwarded_for=None, real_ip=None, remote_addr=None))]
+fn client_ip(
+    forwarded_for: Option<&str>,
+    real_ip: Option<&str>,
+    remote_addr: Option<&str>,
+) -> String {
+    parse_client_ip(forwarded_for, real_ip, remote_addr)
+}
+
+/// Fixed-window rate limit decision
+///
+/// Returns `(allowed, remaining, reset_time_ms, new_count)`; the
+/// caller persists `new_count` and `reset_time_ms` per key.
+#[pyfunction]
+#[pyo3(signature = (now_ms, max, window_ms, existing_count=None, existing_reset_ms=None))]
+fn rate_limit_decision(
+    now_ms: u64,
+    max: u32,
+    window_ms: u64,
+    existing_count: Option<u32>,
+    existing_reset_ms: Option<u64>,
+) -> (bool, u32, u64, u32) {
+    let (decision, new_count) =
+        fixed_window_decision(now_ms, max, window_ms, existing_count, existing_reset_ms);
+    (
+        decision.allowed,
+        decision.remaining,
+        decision.reset_time_ms,
+        new_count,
+    )
+}
+
+/// `X-RateLimit-*` / `Retry-After` headers for a decision
+#[pyfunction]
+fn rate_limit_headers(
+    max: u32,
+    remaining: u32,
+    reset_time_ms: u64,
+    now_ms: u64,
+) -> Vec<(String, String)> {
+    pure_rate_limit_headers(max, remaining, reset_time_ms, now_ms).to_vec()
+}
+
+fn as_str_slice(allowed: &Option<Vec<String>>) -> Option<Vec<&str>> {
+    allowed
+        .as_ref()
+        .map(|list| list.iter().map(String::as_str).collect())
+}
+
+/// Whether `origin` passes the allow-list (`None` allows any origin)
+#[pyfunction]
+#[pyo3(signature = (origin, allowed=None))]
+fn cors_origin_allowed(origin: &str, allowed: Option<Vec<String>>) -> bool {
+    is_origin_allowed(origin, as_str_slice(&allowed).as_deref())
+}
+
+/// Resolved `Access-Control-Allow-Origin` value (empty when rejected)
+#[pyfunction]
+#[pyo3(signature = (origin, allowed=None))]
+fn cors_allowed_origin(origin: &str, allowed: Option<Vec<String>>) -> String {
+    get_allowed_origin(origin, as_str_slice(&allowed).as_deref())
+}
+
+/// CORS response headers for an origin (including `Vary` when scoped)
+#[pyfunction]
+#[pyo3(signature = (origin, allowed=None, credentials=false, exposed_headers=None))]
+fn cors_headers(
+    origin: &str,
+    allowed: Option<Vec<String>>,
+    credentials: bool,
+    exposed_headers: Option<Vec<String>>,
+) -> Vec<(String, String)> {
+    let exposed: Vec<&str> = exposed_headers
+        .as_ref()
+        .map(|list| list.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+    create_cors_headers(
+        origin,
+        as_str_slice(&allowed).as_deref(),
+        credentials,
+        &exposed,
+    )
+}
+
+/// Python module: `import gust_py`
+#[pymodule]
+fn gust_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Router>()?;
+    m.add_class::<ValidationError>()?;
+    m.add_class::<ValidationResult>()?;
+    m.add_class::<Jwt>()?;
+    m.add_function(wrap_pyfunction!(validate_json, m)?)?;
+    m.add_function(wrap_pyfunction!(client_ip, m)?)?;
+    m.add_function(wrap_pyfunction!(rate_limit_decision, m)?)?;
+    m.add_function(wrap_pyfunction!(rate_limit_headers, m)?)?;
+    m.add_function(wrap_pyfunction!(cors_origin_allowed, m)?)?;
+    m.add_function(wrap_pyfunction!(cors_allowed_origin, m)?)?;
+    m.add_function(wrap_pyfunction!(cors_headers, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_find_with_params() {
+        let mut router = Router::new();
+        router.insert("GET", "/users/:id", 3);
+
+        let (handler_id, params) = router.find("GET", "/users/42").unwrap();
+        assert_eq!(handler_id, 3);
+        assert_eq!(params.get("id").map(String::as_str), Some("42"));
+        assert!(router.find("GET", "/missing").is_none());
+    }
+
+    #[test]
+    fn test_validate_json_constraints() {
+        let result = validate_json(
+            r#""hi""#,
+            "string",
+            true,
+            Some(5),
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.errors[0].path, "$");
+
+        let result = validate_json(
+            "12", "number", true, None, None, None, None, None, None, true,
+        )
+        .unwrap();
+        assert!(result.valid);
+
+        assert!(validate_json(
+            "{}", "tuple", true, None, None, None, None, None, None, false
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_jwt_roundtrip() {
+        Python::initialize();
+
+        let jwt = Jwt::new("secret", "HS256", 0).unwrap();
+        let token = jwt.encode(
+            Some("user-1".to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(3600),
+            Some(HashMap::from([("role".to_string(), "admin".to_string())])),
+        );
+
+        Python::attach(|py| {
+            let claims = jwt.decode(py, &token).unwrap();
+            let claims = claims.bind(py);
+            let sub: String = claims.get_item("sub").unwrap().unwrap().extract().unwrap();
+            assert_eq!(sub, "user-1");
+            let role: String = claims
+                .get_item("role")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(role, "admin");
+
+            assert!(jwt.decode(py, "not.a.token").is_err());
+        });
+    }
+
+    #[test]
+    fn test_rate_limit_decision_rolls_window() {
+        let (allowed, remaining, reset, count) = rate_limit_decision(1_000, 2, 60_000, None, None);
+        assert!(allowed);
+        assert_eq!((remaining, count), (1, 1));
+
+        let (allowed, remaining, _, count) =
+            rate_limit_decision(2_000, 2, 60_000, Some(2), Some(reset));
+        assert!(!allowed);
+        assert_eq!((remaining, count), (0, 2));
+    }
+
+    #[test]
+    fn test_cors_helpers() {
+        assert!(cors_origin_allowed("https://a.dev", None));
+        assert!(!cors_origin_allowed(
+            "https://b.dev",
+            Some(vec!["https://a.dev".to_string()])
+        ));
+
+        let headers = cors_headers(
+            "https://a.dev",
+            Some(vec!["https://a.dev".to_string()]),
+            true,
+            None,
+        );
+        assert!(headers.contains(&(
+            "access-control-allow-origin".to_string(),
+            "https://a.dev".to_string()
+        )));
+        assert!(headers.contains(&("vary".to_string(), "Origin".to_string())));
+    }
+}
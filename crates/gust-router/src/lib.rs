@@ -8,17 +8,26 @@
 //! - Static paths: `/users`, `/api/v1/health`
 //! - Parameters: `/users/:id`, `/posts/:postId/comments/:commentId`
 //! - Wildcards: `/files/*path`, `/static/*`
+//! - Any-method routes (method `*`) and optional HEAD→GET fallback
+//! - Required query parameters (`/search?q=*`), matched via
+//!   [`Router::find_with_query`]
+//! - Percent-encoded UTF-8 segments decode before matching, so
+//!   `/caf%C3%A9` and `/café` are the same route
 //! - Zero external dependencies
 //!
 //! ## Path Syntax
 //! - `:name` - Named parameter (captures one segment)
 //! - `*` or `*name` - Wildcard (captures remaining path)
+//! - `?q=*&format=json` suffix - Required query parameters (`q=*`
+//!   requires presence, `format=json` an exact value)
 //!
 //! ## Priority
 //! 1. Exact static match (highest)
 //! 2. Parameter match
 //! 3. Wildcard match (lowest)
 //!
+//! [`Router::insert_with_priority`] overrides this order per route.
+//!
 //! ## Example
 //! ```
 //! use gust_router::Router;
@@ -33,8 +42,47 @@
 //! assert_eq!(m.params, vec![("id".to_string(), "123".to_string())]);
 //! ```
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
+/// Method key for routes registered for every method (`*`)
+const ANY_METHOD: &str = "*";
+
+/// Default cap on captured params (and wildcard) per route
+const DEFAULT_MAX_PARAMS: usize = 32;
+/// Default cap on path segments per route
+const DEFAULT_MAX_SEGMENTS: usize = 64;
+
+/// Route registration error, reported by [`Router::try_insert`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    /// The same param name appears twice in one path
+    /// (`/a/:id/b/:id` would silently shadow the first capture)
+    DuplicateParam { name: String },
+    /// More captured params than the configured limit
+    TooManyParams { count: usize, max: usize },
+    /// More path segments than the configured limit
+    TooManySegments { count: usize, max: usize },
+}
+
+impl std::fmt::Display for RouteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteError::DuplicateParam { name } => {
+                write!(f, "duplicate param name :{} in route path", name)
+            }
+            RouteError::TooManyParams { count, max } => {
+                write!(f, "route has {} params, limit is {}", count, max)
+            }
+            RouteError::TooManySegments { count, max } => {
+                write!(f, "route has {} segments, limit is {}", count, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
 /// Route match result
 #[derive(Debug, Clone, PartialEq)]
 pub struct Match {
@@ -42,6 +90,10 @@ pub struct Match {
     pub handler_id: u32,
     /// Captured path parameters as (name, value) pairs
     pub params: Vec<(String, String)>,
+    /// The route pattern as registered (e.g. `/users/:id`),
+    /// normalized to a leading slash; a low-cardinality label for
+    /// telemetry (`http.route`) unlike the raw request path
+    pub pattern: String,
 }
 
 impl Match {
@@ -51,6 +103,25 @@ impl Match {
     }
 }
 
+/// A single required query parameter on a route
+/// (`q=*` requires presence, `format=json` requires an exact value)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct QueryConstraint {
+    name: String,
+    /// `None` = any value (presence only), `Some` = exact raw value
+    value: Option<String>,
+}
+
+/// A route variant that only matches when its query constraints are
+/// satisfied; constrained variants share a terminal with the plain route
+#[derive(Debug)]
+struct ConstrainedRoute {
+    constraints: Vec<QueryConstraint>,
+    handler_id: u32,
+    priority: i32,
+    pattern: String,
+}
+
 /// Trie node for path segment matching
 #[derive(Debug, Default)]
 struct Node {
@@ -62,6 +133,12 @@ struct Node {
     wildcard_child: Option<Box<WildcardNode>>,
     /// Handler ID if this is a terminal node
     handler_id: Option<u32>,
+    /// Priority override for the terminal route (default 0)
+    priority: i32,
+    /// Normalized pattern of the terminal route
+    pattern: String,
+    /// Query-constrained variants, checked before the plain handler
+    constrained: Vec<ConstrainedRoute>,
 }
 
 #[derive(Debug)]
@@ -73,17 +150,49 @@ struct ParamNode {
 #[derive(Debug)]
 struct WildcardNode {
     name: String,
-    handler_id: u32,
+    /// Handler ID of the unconstrained wildcard route, if registered
+    handler_id: Option<u32>,
+    /// Priority override for the wildcard route (default 0)
+    priority: i32,
+    /// Normalized pattern of the wildcard route
+    pattern: String,
+    /// Query-constrained variants, checked before the plain handler
+    constrained: Vec<ConstrainedRoute>,
 }
 
 /// Zero-dependency Radix Trie HTTP Router
 ///
 /// Routes are organized by HTTP method for O(1) method dispatch,
 /// then matched using a radix trie for O(k) path matching.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Router {
-    /// Method -> Trie root
+    /// Method -> Trie root; `*` holds any-method routes
     trees: HashMap<String, Node>,
+    /// Fall back HEAD lookups to the GET tree
+    head_fallback: bool,
+    /// Whether any route carries a non-default priority; when false,
+    /// lookups keep the early-return fast path
+    has_priorities: bool,
+    /// Whether any route declares query constraints; when false,
+    /// [`find_with_query`](Self::find_with_query) skips query parsing
+    has_constraints: bool,
+    /// Param cap enforced by `try_insert`
+    max_params: usize,
+    /// Segment cap enforced by `try_insert`
+    max_segments: usize,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self {
+            trees: HashMap::new(),
+            head_fallback: false,
+            has_priorities: false,
+            has_constraints: false,
+            max_params: DEFAULT_MAX_PARAMS,
+            max_segments: DEFAULT_MAX_SEGMENTS,
+        }
+    }
 }
 
 impl Router {
@@ -92,10 +201,30 @@ impl Router {
         Self::default()
     }
 
+    /// Cap the number of captured params per route (default: 32),
+    /// enforced by [`try_insert`](Self::try_insert)
+    pub fn set_max_params(&mut self, max: usize) {
+        self.max_params = max;
+    }
+
+    /// Cap the number of path segments per route (default: 64),
+    /// enforced by [`try_insert`](Self::try_insert)
+    pub fn set_max_segments(&mut self, max: usize) {
+        self.max_segments = max;
+    }
+
+    /// Fall back HEAD lookups to the GET tree when no HEAD route
+    /// matches (RFC 9110: HEAD is GET without a body). Disabled by
+    /// default; an explicit HEAD route always wins.
+    pub fn set_head_fallback(&mut self, enabled: bool) {
+        self.head_fallback = enabled;
+    }
+
     /// Insert a route
     ///
     /// # Arguments
-    /// * `method` - HTTP method (GET, POST, etc.)
+    /// * `method` - HTTP method (GET, POST, etc.), or `*` to match
+    ///   any method at the lowest priority
     /// * `path` - URL path with optional params (:id) and wildcards (*)
     /// * `handler_id` - Unique identifier for the handler
     ///
@@ -108,18 +237,220 @@ impl Router {
     /// router.insert("POST", "/users", 1);
     /// ```
     pub fn insert(&mut self, method: &str, path: &str, handler_id: u32) {
+        self.insert_with_priority(method, path, handler_id, 0);
+    }
+
+    /// Insert a route with a priority override
+    ///
+    /// By default routes match static > param > wildcard; a higher
+    /// priority overrides that order (e.g. priority 1 on `/docs/*`
+    /// makes it win over `/docs/:page`). All routes inserted via
+    /// [`insert`](Self::insert) have priority 0; ties keep the
+    /// default order, so equal priorities behave exactly like plain
+    /// inserts.
+    pub fn insert_with_priority(
+        &mut self,
+        method: &str,
+        path: &str,
+        handler_id: u32,
+        priority: i32,
+    ) {
+        if priority != 0 {
+            self.has_priorities = true;
+        }
+        let (path, spec) = match path.split_once('?') {
+            Some((p, s)) => (p, s),
+            None => (path, ""),
+        };
+        let constraints = Self::parse_constraint_spec(spec);
+        if !constraints.is_empty() {
+            self.has_constraints = true;
+        }
         let tree = self.trees.entry(method.to_uppercase()).or_default();
-        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
-        Self::insert_node(tree, &segments, handler_id);
+        let segments = Self::route_segments(path);
+        let pattern = Self::normalize_pattern(&segments, &constraints);
+        Self::insert_node(tree, &segments, handler_id, priority, &pattern, &constraints);
+    }
+
+    /// Split a route path into segments, percent-decoding static
+    /// segments so `/caf%C3%A9` registers the same node as `/café`
+    /// (param and wildcard markers stay raw)
+    fn route_segments(path: &str) -> Vec<Cow<'_, str>> {
+        path.split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                if s.starts_with(':') || s.starts_with('*') {
+                    Cow::Borrowed(s)
+                } else {
+                    Self::decode_segment(s)
+                }
+            })
+            .collect()
+    }
+
+    /// Split a request path into segments, percent-decoding each one.
+    /// Decoding happens after splitting, so an encoded `%2F` becomes a
+    /// literal `/` inside a segment and cannot create a new separator.
+    fn path_segments(path: &str) -> Vec<Cow<'_, str>> {
+        path.split('/')
+            .filter(|s| !s.is_empty())
+            .map(Self::decode_segment)
+            .collect()
     }
 
-    fn insert_node(node: &mut Node, segments: &[&str], handler_id: u32) {
+    /// Percent-decode one segment; segments without `%`, with invalid
+    /// escapes, or with non-UTF-8 bytes are matched literally
+    fn decode_segment(segment: &str) -> Cow<'_, str> {
+        if !segment.contains('%') {
+            return Cow::Borrowed(segment);
+        }
+        let bytes = segment.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' && i + 2 < bytes.len() {
+                let hi = (bytes[i + 1] as char).to_digit(16);
+                let lo = (bytes[i + 2] as char).to_digit(16);
+                if let (Some(hi), Some(lo)) = (hi, lo) {
+                    out.push((hi * 16 + lo) as u8);
+                    i += 3;
+                    continue;
+                }
+            }
+            out.push(bytes[i]);
+            i += 1;
+        }
+        match String::from_utf8(out) {
+            Ok(decoded) => Cow::Owned(decoded),
+            Err(_) => Cow::Borrowed(segment),
+        }
+    }
+
+    /// Normalize a route pattern to a leading slash and no empty
+    /// segments, so `/users/` and `users` both register as `/users`;
+    /// query constraints are appended in normalized `?q=*` form
+    fn normalize_pattern(segments: &[Cow<'_, str>], constraints: &[QueryConstraint]) -> String {
+        let mut pattern = if segments.is_empty() {
+            "/".to_string()
+        } else {
+            format!("/{}", segments.join("/"))
+        };
+        for (i, constraint) in constraints.iter().enumerate() {
+            pattern.push(if i == 0 { '?' } else { '&' });
+            pattern.push_str(&constraint.name);
+            pattern.push('=');
+            pattern.push_str(constraint.value.as_deref().unwrap_or("*"));
+        }
+        pattern
+    }
+
+    /// Parse a `?q=*&format=json` route suffix into constraints;
+    /// `name` and `name=*` both mean "present with any value"
+    fn parse_constraint_spec(spec: &str) -> Vec<QueryConstraint> {
+        spec.split('&')
+            .filter(|s| !s.is_empty())
+            .filter_map(|item| {
+                let (name, value) = match item.split_once('=') {
+                    Some((n, v)) if v == "*" || v.is_empty() => (n, None),
+                    Some((n, v)) => (n, Some(v.to_string())),
+                    None => (item, None),
+                };
+                if name.is_empty() {
+                    None
+                } else {
+                    Some(QueryConstraint {
+                        name: name.to_string(),
+                        value,
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Insert a route, validating the path first
+    ///
+    /// Unlike [`insert`](Self::insert), rejects paths with duplicate
+    /// param names (`/a/:id/b/:id` silently shadows the first
+    /// capture) and paths exceeding the configured param or segment
+    /// caps. Nothing is registered when validation fails.
+    pub fn try_insert(
+        &mut self,
+        method: &str,
+        path: &str,
+        handler_id: u32,
+    ) -> Result<(), RouteError> {
+        let (path, spec) = match path.split_once('?') {
+            Some((p, s)) => (p, s),
+            None => (path, ""),
+        };
+        let segments = Self::route_segments(path);
+        if segments.len() > self.max_segments {
+            return Err(RouteError::TooManySegments {
+                count: segments.len(),
+                max: self.max_segments,
+            });
+        }
+
+        // Collect captured names: params plus a named wildcard
+        let mut names: Vec<&str> = Vec::new();
+        for segment in &segments {
+            let name = if let Some(name) = segment.strip_prefix(':') {
+                name
+            } else if let Some(name) = segment.strip_prefix('*') {
+                if name.is_empty() { "*" } else { name }
+            } else {
+                continue;
+            };
+            if names.contains(&name) {
+                return Err(RouteError::DuplicateParam {
+                    name: name.to_string(),
+                });
+            }
+            names.push(name);
+        }
+        if names.len() > self.max_params {
+            return Err(RouteError::TooManyParams {
+                count: names.len(),
+                max: self.max_params,
+            });
+        }
+
+        let constraints = Self::parse_constraint_spec(spec);
+        if !constraints.is_empty() {
+            self.has_constraints = true;
+        }
+        let tree = self.trees.entry(method.to_uppercase()).or_default();
+        let pattern = Self::normalize_pattern(&segments, &constraints);
+        Self::insert_node(tree, &segments, handler_id, 0, &pattern, &constraints);
+        Ok(())
+    }
+
+    fn insert_node(
+        node: &mut Node,
+        segments: &[Cow<'_, str>],
+        handler_id: u32,
+        priority: i32,
+        pattern: &str,
+        constraints: &[QueryConstraint],
+    ) {
         if segments.is_empty() {
-            node.handler_id = Some(handler_id);
+            if constraints.is_empty() {
+                node.handler_id = Some(handler_id);
+                node.priority = priority;
+                node.pattern = pattern.to_string();
+            } else {
+                Self::insert_constrained(
+                    &mut node.constrained,
+                    constraints,
+                    handler_id,
+                    priority,
+                    pattern,
+                );
+            }
             return;
         }
 
-        let segment = segments[0];
+        let segment = segments[0].as_ref();
         let rest = &segments[1..];
 
         if let Some(name) = segment.strip_prefix(':') {
@@ -131,18 +462,63 @@ impl Router {
                 }));
             }
             let param = node.param_child.as_mut().unwrap();
-            Self::insert_node(&mut param.node, rest, handler_id);
+            Self::insert_node(&mut param.node, rest, handler_id, priority, pattern, constraints);
         } else if let Some(name) = segment.strip_prefix('*') {
             // Wildcard segment (*path or bare *)
             let wildcard_name = if name.is_empty() { "*" } else { name };
-            node.wildcard_child = Some(Box::new(WildcardNode {
-                name: wildcard_name.to_string(),
-                handler_id,
-            }));
+            let wildcard = node.wildcard_child.get_or_insert_with(|| {
+                Box::new(WildcardNode {
+                    name: String::new(),
+                    handler_id: None,
+                    priority: 0,
+                    pattern: String::new(),
+                    constrained: Vec::new(),
+                })
+            });
+            wildcard.name = wildcard_name.to_string();
+            if constraints.is_empty() {
+                wildcard.handler_id = Some(handler_id);
+                wildcard.priority = priority;
+                wildcard.pattern = pattern.to_string();
+            } else {
+                Self::insert_constrained(
+                    &mut wildcard.constrained,
+                    constraints,
+                    handler_id,
+                    priority,
+                    pattern,
+                );
+            }
         } else {
             // Static segment
             let child = node.children.entry(segment.to_string()).or_default();
-            Self::insert_node(child, rest, handler_id);
+            Self::insert_node(child, rest, handler_id, priority, pattern, constraints);
+        }
+    }
+
+    /// Register a constrained variant at a terminal, replacing an
+    /// existing variant with identical constraints (mirrors how plain
+    /// re-inserts overwrite the handler)
+    fn insert_constrained(
+        constrained: &mut Vec<ConstrainedRoute>,
+        constraints: &[QueryConstraint],
+        handler_id: u32,
+        priority: i32,
+        pattern: &str,
+    ) {
+        let route = ConstrainedRoute {
+            constraints: constraints.to_vec(),
+            handler_id,
+            priority,
+            pattern: pattern.to_string(),
+        };
+        if let Some(existing) = constrained
+            .iter_mut()
+            .find(|r| r.constraints == constraints)
+        {
+            *existing = route;
+        } else {
+            constrained.push(route);
         }
     }
 
@@ -155,6 +531,10 @@ impl Router {
     /// # Returns
     /// `Some(Match)` with handler_id and captured params, or `None` if no match
     ///
+    /// Lookup order: the exact method tree, then the GET tree for
+    /// HEAD requests when [`set_head_fallback`](Self::set_head_fallback)
+    /// is enabled, then the any-method (`*`) tree.
+    ///
     /// # Example
     /// ```
     /// use gust_router::Router;
@@ -167,30 +547,215 @@ impl Router {
     /// assert_eq!(m.params[0], ("id".to_string(), "42".to_string()));
     /// ```
     pub fn find(&self, method: &str, path: &str) -> Option<Match> {
-        let tree = self.trees.get(&method.to_uppercase())?;
-        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        self.find_with_query(method, path, None)
+    }
+
+    /// Find a matching route, checking query constraints
+    ///
+    /// Routes registered with a `?q=*` suffix only match when every
+    /// required query parameter is present in `query` (the raw query
+    /// string, without the leading `?`); exact-value constraints like
+    /// `format=json` compare the raw value byte-wise. A constrained
+    /// variant beats the plain route for the same path, and a request
+    /// failing the constraints falls through to less specific
+    /// candidates. With `query: None` (and via [`find`](Self::find))
+    /// constrained routes never match.
+    pub fn find_with_query(&self, method: &str, path: &str, query: Option<&str>) -> Option<Match> {
+        let method = method.to_uppercase();
+        let segments = Self::path_segments(path);
+        let pairs: Vec<(&str, &str)> = if self.has_constraints {
+            query.map(Self::parse_query_pairs).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        if let Some(m) = self.find_in_tree(&method, &segments, &pairs) {
+            return Some(m);
+        }
+        if self.head_fallback && method == "HEAD" {
+            if let Some(m) = self.find_in_tree("GET", &segments, &pairs) {
+                return Some(m);
+            }
+        }
+        if method != ANY_METHOD {
+            return self.find_in_tree(ANY_METHOD, &segments, &pairs);
+        }
+        None
+    }
+
+    /// Split a raw query string into (key, value) pairs; a bare key
+    /// without `=` gets an empty value
+    fn parse_query_pairs(query: &str) -> Vec<(&str, &str)> {
+        query
+            .split('&')
+            .filter(|s| !s.is_empty())
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+            .collect()
+    }
+
+    fn constraints_satisfied(constraints: &[QueryConstraint], query: &[(&str, &str)]) -> bool {
+        constraints.iter().all(|constraint| {
+            query.iter().any(|(k, v)| {
+                *k == constraint.name
+                    && constraint.value.as_deref().map(|cv| cv == *v).unwrap_or(true)
+            })
+        })
+    }
+
+    fn find_in_tree(
+        &self,
+        method: &str,
+        segments: &[Cow<'_, str>],
+        query: &[(&str, &str)],
+    ) -> Option<Match> {
+        let tree = self.trees.get(method)?;
         let mut params = Vec::new();
-        Self::find_node(tree, &segments, &mut params)
+        if !self.has_priorities {
+            return Self::find_node(tree, segments, &mut params, query);
+        }
+
+        // Priorities in play: enumerate every candidate in default
+        // order and keep the highest priority. A strict comparison
+        // makes ties deterministic (the first candidate in
+        // static > param > wildcard order wins).
+        let mut best: Option<(i32, Match)> = None;
+        Self::find_node_all(tree, segments, &mut params, query, &mut |priority, m| {
+            if best.as_ref().map(|(p, _)| priority > *p).unwrap_or(true) {
+                best = Some((priority, m));
+            }
+        });
+        best.map(|(_, m)| m)
+    }
+
+    /// Pick the first satisfied constrained variant, else the plain
+    /// handler; used by the early-return fast path
+    fn match_terminal(
+        constrained: &[ConstrainedRoute],
+        handler_id: Option<u32>,
+        pattern: &str,
+        params: &[(String, String)],
+        query: &[(&str, &str)],
+    ) -> Option<Match> {
+        for route in constrained {
+            if Self::constraints_satisfied(&route.constraints, query) {
+                return Some(Match {
+                    handler_id: route.handler_id,
+                    params: params.to_vec(),
+                    pattern: route.pattern.clone(),
+                });
+            }
+        }
+        handler_id.map(|id| Match {
+            handler_id: id,
+            params: params.to_vec(),
+            pattern: pattern.to_string(),
+        })
+    }
+
+    /// Visit every satisfied terminal variant (constrained first, so
+    /// on equal priority the more specific variant wins)
+    fn visit_terminal(
+        constrained: &[ConstrainedRoute],
+        handler_id: Option<u32>,
+        priority: i32,
+        pattern: &str,
+        params: &[(String, String)],
+        query: &[(&str, &str)],
+        visit: &mut dyn FnMut(i32, Match),
+    ) {
+        for route in constrained {
+            if Self::constraints_satisfied(&route.constraints, query) {
+                visit(
+                    route.priority,
+                    Match {
+                        handler_id: route.handler_id,
+                        params: params.to_vec(),
+                        pattern: route.pattern.clone(),
+                    },
+                );
+            }
+        }
+        if let Some(id) = handler_id {
+            visit(
+                priority,
+                Match {
+                    handler_id: id,
+                    params: params.to_vec(),
+                    pattern: pattern.to_string(),
+                },
+            );
+        }
+    }
+
+    /// Visit every matching terminal in default priority order
+    fn find_node_all(
+        node: &Node,
+        segments: &[Cow<'_, str>],
+        params: &mut Vec<(String, String)>,
+        query: &[(&str, &str)],
+        visit: &mut dyn FnMut(i32, Match),
+    ) {
+        if segments.is_empty() {
+            Self::visit_terminal(
+                &node.constrained,
+                node.handler_id,
+                node.priority,
+                &node.pattern,
+                params,
+                query,
+                visit,
+            );
+            return;
+        }
+
+        let segment = segments[0].as_ref();
+        let rest = &segments[1..];
+
+        if let Some(child) = node.children.get(segment) {
+            Self::find_node_all(child, rest, params, query, visit);
+        }
+        if let Some(ref param) = node.param_child {
+            params.push((param.name.clone(), segment.to_string()));
+            Self::find_node_all(&param.node, rest, params, query, visit);
+            params.pop();
+        }
+        if let Some(ref wildcard) = node.wildcard_child {
+            params.push((wildcard.name.clone(), segments.join("/")));
+            Self::visit_terminal(
+                &wildcard.constrained,
+                wildcard.handler_id,
+                wildcard.priority,
+                &wildcard.pattern,
+                params,
+                query,
+                visit,
+            );
+            params.pop();
+        }
     }
 
     fn find_node(
         node: &Node,
-        segments: &[&str],
+        segments: &[Cow<'_, str>],
         params: &mut Vec<(String, String)>,
+        query: &[(&str, &str)],
     ) -> Option<Match> {
         if segments.is_empty() {
-            return node.handler_id.map(|id| Match {
-                handler_id: id,
-                params: params.clone(),
-            });
+            return Self::match_terminal(
+                &node.constrained,
+                node.handler_id,
+                &node.pattern,
+                params,
+                query,
+            );
         }
 
-        let segment = segments[0];
+        let segment = segments[0].as_ref();
         let rest = &segments[1..];
 
         // Priority 1: Try exact static match (highest priority)
         if let Some(child) = node.children.get(segment) {
-            if let Some(m) = Self::find_node(child, rest, params) {
+            if let Some(m) = Self::find_node(child, rest, params, query) {
                 return Some(m);
             }
         }
@@ -198,7 +763,7 @@ impl Router {
         // Priority 2: Try parameter match
         if let Some(ref param) = node.param_child {
             params.push((param.name.clone(), segment.to_string()));
-            if let Some(m) = Self::find_node(&param.node, rest, params) {
+            if let Some(m) = Self::find_node(&param.node, rest, params, query) {
                 return Some(m);
             }
             params.pop();
@@ -208,15 +773,265 @@ impl Router {
         if let Some(ref wildcard) = node.wildcard_child {
             let rest_path = segments.join("/");
             params.push((wildcard.name.clone(), rest_path));
-            return Some(Match {
-                handler_id: wildcard.handler_id,
-                params: params.clone(),
-            });
+            let m = Self::match_terminal(
+                &wildcard.constrained,
+                wildcard.handler_id,
+                &wildcard.pattern,
+                params,
+                query,
+            );
+            if m.is_some() {
+                return m;
+            }
+            params.pop();
         }
 
         None
     }
 
+    /// Remove a route, returning its handler ID
+    ///
+    /// The path must be the pattern used at registration (param names
+    /// and query constraints included); request paths do not remove
+    /// routes. Emptied trie branches are pruned so lookups never walk
+    /// dead nodes. Together with [`replace`](Self::replace) and
+    /// [`merge`](Self::merge) this lets frameworks hot-reload
+    /// individual routes without rebuilding the whole trie.
+    pub fn remove(&mut self, method: &str, path: &str) -> Option<u32> {
+        let (path, spec) = match path.split_once('?') {
+            Some((p, s)) => (p, s),
+            None => (path, ""),
+        };
+        let constraints = Self::parse_constraint_spec(spec);
+        let segments = Self::route_segments(path);
+        let method = method.to_uppercase();
+        let tree = self.trees.get_mut(&method)?;
+        let removed = Self::remove_node(tree, &segments, &constraints);
+        if removed.is_some() && Self::node_is_empty(tree) {
+            self.trees.remove(&method);
+        }
+        removed
+    }
+
+    fn remove_node(
+        node: &mut Node,
+        segments: &[Cow<'_, str>],
+        constraints: &[QueryConstraint],
+    ) -> Option<u32> {
+        if segments.is_empty() {
+            return Self::remove_terminal(
+                &mut node.handler_id,
+                &mut node.priority,
+                &mut node.pattern,
+                &mut node.constrained,
+                constraints,
+            );
+        }
+
+        let segment = segments[0].as_ref();
+        let rest = &segments[1..];
+
+        if let Some(name) = segment.strip_prefix(':') {
+            let param = node.param_child.as_mut()?;
+            if param.name != name {
+                return None;
+            }
+            let removed = Self::remove_node(&mut param.node, rest, constraints)?;
+            if Self::node_is_empty(&param.node) {
+                node.param_child = None;
+            }
+            Some(removed)
+        } else if let Some(name) = segment.strip_prefix('*') {
+            // Wildcards are terminal, mirroring insert
+            let wildcard_name = if name.is_empty() { "*" } else { name };
+            let wildcard = node.wildcard_child.as_mut()?;
+            if wildcard.name != wildcard_name {
+                return None;
+            }
+            let removed = Self::remove_terminal(
+                &mut wildcard.handler_id,
+                &mut wildcard.priority,
+                &mut wildcard.pattern,
+                &mut wildcard.constrained,
+                constraints,
+            )?;
+            if wildcard.handler_id.is_none() && wildcard.constrained.is_empty() {
+                node.wildcard_child = None;
+            }
+            Some(removed)
+        } else {
+            let child = node.children.get_mut(segment)?;
+            let removed = Self::remove_node(child, rest, constraints)?;
+            if Self::node_is_empty(child) {
+                node.children.remove(segment);
+            }
+            Some(removed)
+        }
+    }
+
+    /// Clear the matching terminal (plain or constrained variant)
+    fn remove_terminal(
+        handler_id: &mut Option<u32>,
+        priority: &mut i32,
+        pattern: &mut String,
+        constrained: &mut Vec<ConstrainedRoute>,
+        constraints: &[QueryConstraint],
+    ) -> Option<u32> {
+        if constraints.is_empty() {
+            let removed = handler_id.take()?;
+            *priority = 0;
+            pattern.clear();
+            Some(removed)
+        } else {
+            let index = constrained
+                .iter()
+                .position(|r| r.constraints == constraints)?;
+            Some(constrained.remove(index).handler_id)
+        }
+    }
+
+    fn node_is_empty(node: &Node) -> bool {
+        node.handler_id.is_none()
+            && node.constrained.is_empty()
+            && node.children.is_empty()
+            && node.param_child.is_none()
+            && node.wildcard_child.is_none()
+    }
+
+    /// Insert a route, returning the handler ID it replaced (if any)
+    ///
+    /// Equivalent to [`insert`](Self::insert), which already
+    /// overwrites silently; the returned ID lets callers release the
+    /// old handler during a hot reload.
+    pub fn replace(&mut self, method: &str, path: &str, handler_id: u32) -> Option<u32> {
+        let previous = self.handler_at(method, path);
+        self.insert(method, path, handler_id);
+        previous
+    }
+
+    /// Handler registered at exactly this pattern, if any
+    ///
+    /// Unlike [`find`](Self::find), this does not match request paths:
+    /// `handler_at("GET", "/users/42")` does not see `/users/:id`.
+    pub fn handler_at(&self, method: &str, path: &str) -> Option<u32> {
+        let (path, spec) = match path.split_once('?') {
+            Some((p, s)) => (p, s),
+            None => (path, ""),
+        };
+        let constraints = Self::parse_constraint_spec(spec);
+        let segments = Self::route_segments(path);
+        let mut node = self.trees.get(&method.to_uppercase())?;
+        for (i, segment) in segments.iter().enumerate() {
+            let segment = segment.as_ref();
+            if let Some(name) = segment.strip_prefix(':') {
+                let param = node.param_child.as_deref()?;
+                if param.name != name {
+                    return None;
+                }
+                node = &param.node;
+            } else if let Some(name) = segment.strip_prefix('*') {
+                let wildcard_name = if name.is_empty() { "*" } else { name };
+                let wildcard = node.wildcard_child.as_deref()?;
+                if wildcard.name != wildcard_name || i != segments.len() - 1 {
+                    return None;
+                }
+                return Self::terminal_handler(
+                    wildcard.handler_id,
+                    &wildcard.constrained,
+                    &constraints,
+                );
+            } else {
+                node = node.children.get(segment)?;
+            }
+        }
+        Self::terminal_handler(node.handler_id, &node.constrained, &constraints)
+    }
+
+    fn terminal_handler(
+        handler_id: Option<u32>,
+        constrained: &[ConstrainedRoute],
+        constraints: &[QueryConstraint],
+    ) -> Option<u32> {
+        if constraints.is_empty() {
+            handler_id
+        } else {
+            constrained
+                .iter()
+                .find(|r| r.constraints == constraints)
+                .map(|r| r.handler_id)
+        }
+    }
+
+    /// All registered routes as (method, pattern, handler_id)
+    ///
+    /// Patterns come back in normalized form and round-trip through
+    /// [`insert`](Self::insert).
+    pub fn routes(&self) -> Vec<(String, String, u32)> {
+        self.entries()
+            .into_iter()
+            .map(|(method, pattern, handler_id, _)| (method, pattern, handler_id))
+            .collect()
+    }
+
+    /// Merge another router's routes into this one
+    ///
+    /// Routes sharing a method and pattern replace the existing
+    /// registration, everything else is added — so a framework can
+    /// build a small router with just the changed routes and merge it
+    /// into the live one.
+    pub fn merge(&mut self, other: &Router) {
+        for (method, pattern, handler_id, priority) in other.entries() {
+            self.insert_with_priority(&method, &pattern, handler_id, priority);
+        }
+    }
+
+    /// Every route with its priority, for routes() and merge()
+    fn entries(&self) -> Vec<(String, String, u32, i32)> {
+        let mut out = Vec::new();
+        for (method, tree) in &self.trees {
+            Self::collect_entries(tree, method, &mut out);
+        }
+        out
+    }
+
+    fn collect_entries(node: &Node, method: &str, out: &mut Vec<(String, String, u32, i32)>) {
+        if let Some(id) = node.handler_id {
+            out.push((method.to_string(), node.pattern.clone(), id, node.priority));
+        }
+        for route in &node.constrained {
+            out.push((
+                method.to_string(),
+                route.pattern.clone(),
+                route.handler_id,
+                route.priority,
+            ));
+        }
+        for child in node.children.values() {
+            Self::collect_entries(child, method, out);
+        }
+        if let Some(ref param) = node.param_child {
+            Self::collect_entries(&param.node, method, out);
+        }
+        if let Some(ref wildcard) = node.wildcard_child {
+            if let Some(id) = wildcard.handler_id {
+                out.push((
+                    method.to_string(),
+                    wildcard.pattern.clone(),
+                    id,
+                    wildcard.priority,
+                ));
+            }
+            for route in &wildcard.constrained {
+                out.push((
+                    method.to_string(),
+                    route.pattern.clone(),
+                    route.handler_id,
+                    route.priority,
+                ));
+            }
+        }
+    }
+
     /// Check if a method has any routes registered
     pub fn has_method(&self, method: &str) -> bool {
         self.trees.contains_key(&method.to_uppercase())
@@ -379,6 +1194,263 @@ mod tests {
         assert_eq!(router.find("Get", "/users").unwrap().handler_id, 1);
     }
 
+    #[test]
+    fn test_match_pattern() {
+        let mut router = Router::new();
+        router.insert("GET", "/users/:id", 1);
+        router.insert("GET", "/files/*path", 2);
+        router.insert("GET", "/health/", 3);
+        router.insert("GET", "/", 4);
+
+        // The registered pattern comes back, not the raw path
+        assert_eq!(router.find("GET", "/users/42").unwrap().pattern, "/users/:id");
+        assert_eq!(
+            router.find("GET", "/files/a/b.txt").unwrap().pattern,
+            "/files/*path"
+        );
+        // Patterns normalize trailing slashes
+        assert_eq!(router.find("GET", "/health").unwrap().pattern, "/health");
+        assert_eq!(router.find("GET", "/").unwrap().pattern, "/");
+    }
+
+    #[test]
+    fn test_priority_override() {
+        let mut router = Router::new();
+        router.insert("GET", "/docs/:page", 1);
+        router.insert_with_priority("GET", "/docs/*", 2, 1);
+
+        // The boosted wildcard wins over the param route
+        let m = router.find("GET", "/docs/intro").unwrap();
+        assert_eq!(m.handler_id, 2);
+        assert_eq!(m.params, vec![("*".to_string(), "intro".to_string())]);
+
+        // Routes the wildcard cannot match are unaffected
+        router.insert("GET", "/health", 3);
+        assert_eq!(router.find("GET", "/health").unwrap().handler_id, 3);
+    }
+
+    #[test]
+    fn test_priority_ties_keep_default_order() {
+        let mut router = Router::new();
+        router.insert_with_priority("GET", "/users/me", 1, 5);
+        router.insert_with_priority("GET", "/users/:id", 2, 5);
+
+        // Equal priorities fall back to static > param > wildcard
+        assert_eq!(router.find("GET", "/users/me").unwrap().handler_id, 1);
+        assert_eq!(router.find("GET", "/users/42").unwrap().handler_id, 2);
+    }
+
+    #[test]
+    fn test_negative_priority_demotes_route() {
+        let mut router = Router::new();
+        router.insert_with_priority("GET", "/api/v1", 1, -1);
+        router.insert("GET", "/api/:version", 2);
+
+        // The demoted static route loses to the param route
+        assert_eq!(router.find("GET", "/api/v1").unwrap().handler_id, 2);
+        // With no competing route it still matches
+        assert!(router.find("GET", "/api/v1/x").is_none());
+    }
+
+    #[test]
+    fn test_try_insert_duplicate_param() {
+        let mut router = Router::new();
+
+        assert_eq!(
+            router.try_insert("GET", "/a/:id/b/:id", 1),
+            Err(RouteError::DuplicateParam {
+                name: "id".to_string()
+            })
+        );
+        // A param sharing a name with the wildcard also collides
+        assert_eq!(
+            router.try_insert("GET", "/a/:path/*path", 2),
+            Err(RouteError::DuplicateParam {
+                name: "path".to_string()
+            })
+        );
+        // Nothing was registered
+        assert!(router.find("GET", "/a/1/b/2").is_none());
+
+        assert!(router.try_insert("GET", "/a/:id/b/:other", 3).is_ok());
+        assert_eq!(router.find("GET", "/a/1/b/2").unwrap().handler_id, 3);
+    }
+
+    #[test]
+    fn test_try_insert_limits() {
+        let mut router = Router::new();
+        router.set_max_params(2);
+        router.set_max_segments(4);
+
+        assert_eq!(
+            router.try_insert("GET", "/:a/:b/:c", 1),
+            Err(RouteError::TooManyParams { count: 3, max: 2 })
+        );
+        assert_eq!(
+            router.try_insert("GET", "/a/b/c/d/e", 2),
+            Err(RouteError::TooManySegments { count: 5, max: 4 })
+        );
+        assert!(router.try_insert("GET", "/x/:a/y/:b", 3).is_ok());
+    }
+
+    #[test]
+    fn test_any_method_tree() {
+        let mut router = Router::new();
+        router.insert("*", "/webhook", 1);
+        router.insert("GET", "/webhook", 2);
+        router.insert("*", "/files/*path", 3);
+
+        // Any method matches the * tree
+        assert_eq!(router.find("POST", "/webhook").unwrap().handler_id, 1);
+        assert_eq!(router.find("DELETE", "/webhook").unwrap().handler_id, 1);
+        // A method-specific route wins over the * tree
+        assert_eq!(router.find("GET", "/webhook").unwrap().handler_id, 2);
+        // Params and wildcards work in the * tree too
+        let m = router.find("PUT", "/files/a/b.txt").unwrap();
+        assert_eq!(m.handler_id, 3);
+        assert_eq!(m.params, vec![("path".to_string(), "a/b.txt".to_string())]);
+
+        assert!(router.find("POST", "/other").is_none());
+    }
+
+    #[test]
+    fn test_head_fallback() {
+        let mut router = Router::new();
+        router.insert("GET", "/users/:id", 1);
+        router.insert("HEAD", "/status", 2);
+
+        // Disabled by default
+        assert!(router.find("HEAD", "/users/42").is_none());
+
+        router.set_head_fallback(true);
+        let m = router.find("HEAD", "/users/42").unwrap();
+        assert_eq!(m.handler_id, 1);
+        assert_eq!(m.params, vec![("id".to_string(), "42".to_string())]);
+        // An explicit HEAD route still wins
+        assert_eq!(router.find("HEAD", "/status").unwrap().handler_id, 2);
+        // Only HEAD falls back
+        assert!(router.find("DELETE", "/users/42").is_none());
+    }
+
+    #[test]
+    fn test_query_constraints() {
+        let mut router = Router::new();
+        router.insert("GET", "/search?q=*", 1);
+        router.insert("GET", "/search", 2);
+        router.insert("GET", "/export?format=json", 3);
+
+        // The constrained variant wins when the param is present
+        let m = router.find_with_query("GET", "/search", Some("q=rust")).unwrap();
+        assert_eq!(m.handler_id, 1);
+        assert_eq!(m.pattern, "/search?q=*");
+        // Missing param falls through to the plain route
+        assert_eq!(
+            router.find_with_query("GET", "/search", Some("page=2")).unwrap().handler_id,
+            2
+        );
+        assert_eq!(router.find_with_query("GET", "/search", None).unwrap().handler_id, 2);
+        // `find` never sees a query, so constrained routes never match
+        assert_eq!(router.find("GET", "/search").unwrap().handler_id, 2);
+
+        // Exact-value constraints compare the raw value
+        assert_eq!(
+            router
+                .find_with_query("GET", "/export", Some("format=json"))
+                .unwrap()
+                .handler_id,
+            3
+        );
+        assert!(router.find_with_query("GET", "/export", Some("format=csv")).is_none());
+        assert!(router.find_with_query("GET", "/export", None).is_none());
+    }
+
+    #[test]
+    fn test_query_constraint_variants_coexist() {
+        let mut router = Router::new();
+        router.insert("GET", "/items?sort=*&filter=*", 1);
+        router.insert("GET", "/items?sort=*", 2);
+
+        // Variants are checked in insertion order
+        assert_eq!(
+            router
+                .find_with_query("GET", "/items", Some("sort=asc&filter=new"))
+                .unwrap()
+                .handler_id,
+            1
+        );
+        assert_eq!(
+            router.find_with_query("GET", "/items", Some("sort=asc")).unwrap().handler_id,
+            2
+        );
+        // No variant satisfied and no plain route: no match
+        assert!(router.find_with_query("GET", "/items", Some("page=1")).is_none());
+
+        // A bare key satisfies a presence constraint
+        assert_eq!(
+            router.find_with_query("GET", "/items", Some("sort")).unwrap().handler_id,
+            2
+        );
+    }
+
+    #[test]
+    fn test_query_constraints_on_param_route() {
+        let mut router = Router::new();
+        router.insert("GET", "/users/:id?expand=*", 1);
+        router.insert("GET", "/users/*rest", 2);
+
+        let m = router
+            .find_with_query("GET", "/users/42", Some("expand=posts"))
+            .unwrap();
+        assert_eq!(m.handler_id, 1);
+        assert_eq!(m.params, vec![("id".to_string(), "42".to_string())]);
+        // An unsatisfied param route falls through to the wildcard
+        let m = router.find_with_query("GET", "/users/42", None).unwrap();
+        assert_eq!(m.handler_id, 2);
+        assert_eq!(m.params, vec![("rest".to_string(), "42".to_string())]);
+    }
+
+    #[test]
+    fn test_percent_encoded_segments() {
+        let mut router = Router::new();
+        router.insert("GET", "/café", 1);
+        router.insert("GET", "/caf%C3%A9/menu", 2);
+
+        // Encoded and literal forms are the same route, both ways
+        assert_eq!(router.find("GET", "/caf%C3%A9").unwrap().handler_id, 1);
+        assert_eq!(router.find("GET", "/café").unwrap().handler_id, 1);
+        assert_eq!(router.find("GET", "/café/menu").unwrap().handler_id, 2);
+        // The pattern reports the decoded form
+        assert_eq!(router.find("GET", "/caf%C3%A9").unwrap().pattern, "/café");
+
+        // Invalid escapes match literally instead of erroring
+        router.insert("GET", "/odd%zz", 3);
+        assert_eq!(router.find("GET", "/odd%zz").unwrap().handler_id, 3);
+    }
+
+    #[test]
+    fn test_unicode_params_decode() {
+        let mut router = Router::new();
+        router.insert("GET", "/users/:name", 1);
+        router.insert("GET", "/files/*path", 2);
+
+        // CJK param value decodes to UTF-8
+        let m = router.find("GET", "/users/%E6%97%A5%E6%9C%AC%E8%AA%9E").unwrap();
+        assert_eq!(m.params, vec![("name".to_string(), "日本語".to_string())]);
+        // Emoji too
+        let m = router.find("GET", "/users/%F0%9F%A6%80").unwrap();
+        assert_eq!(m.params, vec![("name".to_string(), "🦀".to_string())]);
+
+        // An encoded slash stays inside its segment: it decodes into
+        // the captured value but cannot add a path separator
+        let m = router.find("GET", "/users/a%2Fb").unwrap();
+        assert_eq!(m.handler_id, 1);
+        assert_eq!(m.params, vec![("name".to_string(), "a/b".to_string())]);
+
+        // Wildcard captures decode per segment
+        let m = router.find("GET", "/files/docs/%E4%B8%AD%E6%96%87.md").unwrap();
+        assert_eq!(m.params, vec![("path".to_string(), "docs/中文.md".to_string())]);
+    }
+
     #[test]
     fn test_root_path() {
         let mut router = Router::new();
@@ -398,4 +1470,100 @@ mod tests {
         assert_eq!(router.find("GET", "/users").unwrap().handler_id, 1);
         assert_eq!(router.find("GET", "/users/").unwrap().handler_id, 1);
     }
+
+    #[test]
+    fn test_remove() {
+        let mut router = Router::new();
+        router.insert("GET", "/users", 1);
+        router.insert("GET", "/users/:id", 2);
+        router.insert("GET", "/static/*path", 3);
+
+        assert_eq!(router.remove("GET", "/users/:id"), Some(2));
+        assert!(router.find("GET", "/users/42").is_none());
+        // Siblings are untouched
+        assert_eq!(router.find("GET", "/users").unwrap().handler_id, 1);
+        assert_eq!(router.find("GET", "/static/a/b").unwrap().handler_id, 3);
+
+        // Unknown routes, wrong param names, wrong methods
+        assert_eq!(router.remove("GET", "/users/:id"), None);
+        assert_eq!(router.remove("GET", "/static/*other"), None);
+        assert_eq!(router.remove("POST", "/users"), None);
+
+        assert_eq!(router.remove("GET", "/static/*path"), Some(3));
+        assert_eq!(router.remove("GET", "/users"), Some(1));
+        assert!(!router.has_method("GET"));
+    }
+
+    #[test]
+    fn test_remove_constrained() {
+        let mut router = Router::new();
+        router.insert("GET", "/search?format=json", 1);
+        router.insert("GET", "/search", 2);
+
+        assert_eq!(router.remove("GET", "/search?format=json"), Some(1));
+        // The unconstrained fallback still matches
+        let m = router
+            .find_with_query("GET", "/search", Some("format=json"))
+            .unwrap();
+        assert_eq!(m.handler_id, 2);
+        assert_eq!(router.remove("GET", "/search?format=json"), None);
+    }
+
+    #[test]
+    fn test_remove_prunes_branches() {
+        let mut router = Router::new();
+        router.insert("GET", "/a/b/c/d", 1);
+        router.insert("GET", "/a/b", 2);
+
+        assert_eq!(router.remove("GET", "/a/b/c/d"), Some(1));
+        // /a/b survives; the emptied /c/d branch is gone
+        let tree = router.trees.get("GET").unwrap();
+        let b = &tree.children["a"].children["b"];
+        assert!(b.children.is_empty());
+        assert_eq!(router.find("GET", "/a/b").unwrap().handler_id, 2);
+    }
+
+    #[test]
+    fn test_replace_and_handler_at() {
+        let mut router = Router::new();
+        router.insert("GET", "/users/:id", 1);
+
+        assert_eq!(router.handler_at("GET", "/users/:id"), Some(1));
+        // Registration patterns, not request paths
+        assert_eq!(router.handler_at("GET", "/users/42"), None);
+
+        assert_eq!(router.replace("GET", "/users/:id", 5), Some(1));
+        assert_eq!(router.find("GET", "/users/42").unwrap().handler_id, 5);
+        // Replacing a new route is just an insert
+        assert_eq!(router.replace("GET", "/posts", 6), None);
+        assert_eq!(router.find("GET", "/posts").unwrap().handler_id, 6);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut live = Router::new();
+        live.insert("GET", "/users", 1);
+        live.insert("GET", "/users/:id", 2);
+
+        // Hot reload: patch one route, add another
+        let mut patch = Router::new();
+        patch.insert("GET", "/users/:id", 20);
+        patch.insert_with_priority("POST", "/users", 21, 5);
+
+        live.merge(&patch);
+        assert_eq!(live.find("GET", "/users").unwrap().handler_id, 1);
+        assert_eq!(live.find("GET", "/users/42").unwrap().handler_id, 20);
+        assert_eq!(live.find("POST", "/users").unwrap().handler_id, 21);
+
+        let mut routes = live.routes();
+        routes.sort();
+        assert_eq!(
+            routes,
+            vec![
+                ("GET".to_string(), "/users".to_string(), 1),
+                ("GET".to_string(), "/users/:id".to_string(), 20),
+                ("POST".to_string(), "/users".to_string(), 21),
+            ]
+        );
+    }
 }
@@ -48,6 +48,14 @@ pub fn generate_span_id() -> String {
     format!("{:016x}", id)
 }
 
+/// Fill a buffer from the seeded PRNG
+pub fn fill_random(buf: &mut [u8]) {
+    for chunk in buf.chunks_mut(8) {
+        let bytes = next_u64().to_be_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
 /// Generate a random 4-byte mask for WebSocket
 pub fn generate_mask() -> [u8; 4] {
     let r = next_u64();
@@ -402,3 +402,88 @@ pub fn generate_span_id() -> String {
 pub fn generate_websocket_mask() -> Vec<u8> {
     tracing::generate_mask().to_vec()
 }
+
+// ============================================================================
+// Tokens, UUIDs, and timing-safe comparison
+// ============================================================================
+
+/// Compare two byte slices in constant time
+///
+/// For secrets (API keys, signatures, session tokens): the run time
+/// depends only on the lengths, so timing does not leak how much of
+/// a guess matched.
+#[wasm_bindgen(js_name = timingSafeEqual)]
+pub fn timing_safe_equal(a: &[u8], b: &[u8]) -> bool {
+    gust_core::crypto::constant_time_eq(a, b)
+}
+
+/// `bytes` random bytes as lowercase hex
+/// Seed the RNG from JS entropy first (seed_rng)
+#[wasm_bindgen(js_name = randomTokenHex)]
+pub fn random_token_hex(bytes: u32) -> String {
+    random_vec(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// `bytes` random bytes as unpadded URL-safe Base64
+/// Seed the RNG from JS entropy first (seed_rng)
+#[wasm_bindgen(js_name = randomTokenBase64url)]
+pub fn random_token_base64url(bytes: u32) -> String {
+    gust_core::crypto::base64url_encode(&random_vec(bytes))
+}
+
+/// Generate a random UUIDv4 (RFC 9562)
+/// Seed the RNG from JS entropy first (seed_rng)
+#[wasm_bindgen(js_name = uuidV4)]
+pub fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+    tracing::fill_random(&mut bytes);
+    gust_core::crypto::uuid_v4_from_bytes(bytes)
+}
+
+/// Generate a time-ordered UUIDv7 (RFC 9562)
+///
+/// WASM has no clock, so pass `Date.now()` for the timestamp; IDs
+/// sort by creation time for better index locality than v4.
+#[wasm_bindgen(js_name = uuidV7)]
+pub fn uuid_v7(now_ms: f64) -> String {
+    let mut random = [0u8; 10];
+    tracing::fill_random(&mut random);
+    gust_core::crypto::uuid_v7_from_parts(now_ms as u64, random)
+}
+
+fn random_vec(bytes: u32) -> Vec<u8> {
+    let mut buf = vec![0u8; bytes as usize];
+    tracing::fill_random(&mut buf);
+    buf
+}
+
+// ============================================================================
+// Server-Sent Events
+// ============================================================================
+
+/// Encode a Server-Sent Event in `text/event-stream` wire format
+/// Uses gust_core::SseEvent (SSOT)
+///
+/// Data is split into one `data:` line per line; id/event values are
+/// sanitized so they cannot inject extra fields. Pass a comment for
+/// keep-alive (`: text`) events.
+#[wasm_bindgen(js_name = encodeSseEvent)]
+pub fn encode_sse_event(
+    data: Option<String>,
+    id: Option<String>,
+    event: Option<String>,
+    retry: Option<u32>,
+    comment: Option<String>,
+) -> String {
+    let sse = gust_core::SseEvent {
+        id,
+        event,
+        data: data.unwrap_or_default(),
+        retry,
+        comment,
+    };
+    sse.to_string()
+}
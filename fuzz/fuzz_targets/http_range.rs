@@ -0,0 +1,17 @@
+//! Fuzz the Range header parser with arbitrary header strings and sizes.
+
+#![no_main]
+
+use gust_core::pure::http_range::{is_range_satisfiable, parse_range};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, u64)| {
+    let (header, file_size) = input;
+    if let Some(parsed) = parse_range(header, file_size) {
+        // Every accepted range must be ordered and satisfiable
+        for range in &parsed.ranges {
+            assert!(range.start <= range.end);
+        }
+        assert!(is_range_satisfiable(&parsed.ranges, file_size));
+    }
+});
@@ -0,0 +1,16 @@
+//! Fuzz JWT decoding: arbitrary tokens must be rejected cleanly, and
+//! mutated-but-parseable tokens must never verify under a fixed secret.
+
+#![no_main]
+
+use gust_core::middleware::jwt::{Jwt, JwtConfig};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|token: &str| {
+    let jwt = Jwt::new(JwtConfig::new("fuzz-secret"));
+    if let Ok(claims) = jwt.decode(token) {
+        // Anything that verifies must have carried a valid HMAC, which a
+        // fuzzer cannot forge; reaching here means signature bypass
+        panic!("unsigned token verified: {:?}", claims);
+    }
+});
@@ -0,0 +1,14 @@
+//! Fuzz the body JSON parser: parse, then require that accepted
+//! documents survive a serialize/reparse round trip.
+
+#![no_main]
+
+use gust_core::pure::{parse_json, serialize_json};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(value) = parse_json(input) {
+        let serialized = serialize_json(&value);
+        parse_json(&serialized).expect("serialized JSON must reparse");
+    }
+});
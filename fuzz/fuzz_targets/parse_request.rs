@@ -0,0 +1,30 @@
+//! Fuzz the zero-copy HTTP/1.1 request parser and check that every
+//! offset it reports stays inside the input buffer.
+
+#![no_main]
+
+use gust_core::parser::{parse_request, HeaderOffsets, MAX_HEADERS};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut offsets: HeaderOffsets = [0; MAX_HEADERS * 4];
+    let parsed = parse_request(data, &mut offsets);
+    if parsed.state != 1 {
+        return;
+    }
+
+    let len = data.len() as u32;
+    assert!(parsed.path_start <= parsed.path_end && parsed.path_end <= len);
+    if parsed.query_start != 0 {
+        assert!(parsed.query_start <= parsed.query_end && parsed.query_end <= len);
+    }
+    assert!(parsed.body_start <= len);
+    assert!(parsed.headers_count as usize <= MAX_HEADERS);
+
+    for i in 0..parsed.headers_count as usize {
+        let [name_start, name_end, value_start, value_end] =
+            [offsets[i * 4], offsets[i * 4 + 1], offsets[i * 4 + 2], offsets[i * 4 + 3]];
+        assert!(name_start <= name_end && name_end <= len);
+        assert!(value_start <= value_end && value_end <= len);
+    }
+});
@@ -0,0 +1,12 @@
+//! Fuzz query-string parsing, including malformed percent escapes and
+//! multi-byte UTF-8 in keys and values.
+
+#![no_main]
+
+use gust_core::{Method, RequestBuilder};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|query: &str| {
+    let request = RequestBuilder::new(Method::Get, "/").query(query).build();
+    let _ = request.query_params();
+});
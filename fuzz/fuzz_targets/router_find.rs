@@ -0,0 +1,25 @@
+//! Fuzz the radix-trie router: arbitrary route patterns inserted and
+//! arbitrary paths matched against them.
+
+#![no_main]
+
+use gust_router::Router;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, &str, &str)| {
+    let (method, pattern, path) = input;
+
+    let mut router = Router::new();
+    router.insert(method, pattern, 1);
+    router.insert("GET", "/users/:id", 2);
+    router.insert("GET", "/files/*rest", 3);
+
+    for (m, p) in [(method, path), ("GET", path), (method, pattern)] {
+        if let Some(found) = router.find(m, p) {
+            // Captured params must all come from the matched path
+            for (_, value) in &found.params {
+                assert!(value.len() <= p.len());
+            }
+        }
+    }
+});
@@ -0,0 +1,18 @@
+//! Fuzz the W3C traceparent/tracestate parsers.
+
+#![no_main]
+
+use gust_core::tracing::{format_traceparent, parse_traceparent, parse_tracestate};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|header: &str| {
+    if let Some(ctx) = parse_traceparent(header) {
+        // Accepted contexts must round-trip through the formatter
+        let formatted = format_traceparent(&ctx);
+        let reparsed = parse_traceparent(&formatted).expect("formatted traceparent must parse");
+        assert_eq!(reparsed.trace_id, ctx.trace_id);
+        assert_eq!(reparsed.span_id, ctx.span_id);
+        assert_eq!(reparsed.trace_flags, ctx.trace_flags);
+    }
+    let _ = parse_tracestate(header);
+});
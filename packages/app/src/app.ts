@@ -151,10 +151,47 @@ export interface RouteManifest {
 export interface NativeHandlerContext {
 	readonly method: string
 	readonly path: string
+	/** Matched route pattern (e.g. "/users/:id"), a low-cardinality telemetry label */
+	readonly routePattern?: string
 	readonly query: string
 	readonly headers: Record<string, string>
 	readonly params: Record<string, string>
 	readonly body: Uint8Array
+	/**
+	 * Headers as a single length-prefixed buffer (batched transfer).
+	 * Layout per header: u32 LE name length, name, u32 LE value length,
+	 * value. Set instead of `headers` when `batchedHeaders` is enabled.
+	 */
+	readonly headersBuffer?: Uint8Array
+	/** Route params as alternating name/value pairs (batched transfer) */
+	readonly paramsFlat?: string[]
+	/** Client IP (socket address, or forwarded IP behind a trusted proxy) */
+	readonly ip?: string
+	/** Request protocol ("http"/"https") */
+	readonly protocol?: string
+	/** Request host (Host header or trusted X-Forwarded-Host) */
+	readonly host?: string
+}
+
+/**
+ * Decode the length-prefixed header buffer produced by the native server
+ */
+const decodeHeadersBuffer = (buf: Uint8Array): Record<string, string> => {
+	const headers: Record<string, string> = {}
+	const view = new DataView(buf.buffer, buf.byteOffset, buf.byteLength)
+	const decoder = new TextDecoder()
+	let offset = 0
+	while (offset + 4 <= buf.byteLength) {
+		const nameLen = view.getUint32(offset, true)
+		offset += 4
+		const name = decoder.decode(buf.subarray(offset, offset + nameLen))
+		offset += nameLen
+		const valueLen = view.getUint32(offset, true)
+		offset += 4
+		headers[name] = decoder.decode(buf.subarray(offset, offset + valueLen))
+		offset += valueLen
+	}
+	return headers
 }
 
 /**
@@ -510,13 +547,38 @@ export const createApp = <App = Record<string, never>>(config: AppConfig<App>):
 
 		try {
 			// Convert NativeHandlerContext to RawContext
+			// Headers/params arrive as flat buffers in batched mode and are
+			// only materialized when a handler actually reads them
 			const body = Buffer.from(nativeCtx.body)
+			let headersCache: Record<string, string> | undefined
+			let paramsCache: Record<string, string> | undefined
 			const raw: RawContext = {
 				method: nativeCtx.method,
 				path: nativeCtx.path,
+				routePattern: nativeCtx.routePattern,
 				query: nativeCtx.query,
-				headers: nativeCtx.headers,
-				params: nativeCtx.params,
+				get headers() {
+					if (headersCache === undefined) {
+						headersCache = nativeCtx.headersBuffer
+							? decodeHeadersBuffer(nativeCtx.headersBuffer)
+							: nativeCtx.headers
+					}
+					return headersCache
+				},
+				get params() {
+					if (paramsCache === undefined) {
+						const flat = nativeCtx.paramsFlat
+						if (flat) {
+							paramsCache = {}
+							for (let i = 0; i + 1 < flat.length; i += 2) {
+								paramsCache[flat[i] as string] = flat[i + 1] as string
+							}
+						} else {
+							paramsCache = nativeCtx.params
+						}
+					}
+					return paramsCache
+				},
 				body,
 				json: <T>() => {
 					try {
@@ -527,11 +589,18 @@ export const createApp = <App = Record<string, never>>(config: AppConfig<App>):
 				},
 				raw: body,
 				socket: null, // Not available in native path
+				ip: nativeCtx.ip,
+				protocol: nativeCtx.protocol,
+				host: nativeCtx.host,
 			}
 
 			// Create app context
+			// Note: prototype-based so the lazy header/param getters on
+			// `raw` survive (a spread would force them immediately)
 			const app = contextProvider ? await contextProvider(raw) : ({} as App)
-			const ctx = withApp(raw, app)
+			const ctx = Object.create(raw, {
+				app: { value: app, enumerable: true },
+			}) as Context<App>
 
 			// Create handler that just calls the matched handler
 			const directHandler: Handler<typeof ctx> = async (c) => {
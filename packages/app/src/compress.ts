@@ -151,8 +151,11 @@ export const compress = (options: CompressionOptions = {}): Middleware => {
 				return response
 			}
 
-			// Check content type
-			const contentType = response.headers['content-type']
+			// Check content type (array values never apply to content-type)
+			const contentTypeValue = response.headers['content-type']
+			const contentType = Array.isArray(contentTypeValue)
+				? contentTypeValue[0]
+				: (contentTypeValue as string | undefined)
 			if (!isCompressible(contentType, mimeTypes)) {
 				return response
 			}
@@ -30,6 +30,8 @@ import { type MethodCode, MethodNames } from '@sylphx/gust-core'
 export type Context<App = Record<string, never>> = {
 	readonly method: string
 	readonly path: string
+	/** Matched route pattern (e.g. "/users/:id"), when routing ran in Rust */
+	readonly routePattern?: string
 	readonly query: string
 	readonly headers: Readonly<Record<string, string>>
 	readonly params: Readonly<Record<string, string>>
@@ -38,6 +40,18 @@ export type Context<App = Record<string, never>> = {
 	readonly raw: Buffer
 	readonly socket: Socket | null
 	readonly app: App
+	/**
+	 * Client IP address
+	 *
+	 * The direct socket address, or the forwarded client IP when the
+	 * server is configured with trustProxy and the peer is trusted.
+	 * Undefined when the transport does not expose it.
+	 */
+	readonly ip?: string
+	/** Request protocol ("http"/"https"), respecting trusted X-Forwarded-Proto */
+	readonly protocol?: string
+	/** Request host (Host header, or trusted X-Forwarded-Host) */
+	readonly host?: string
 	/**
 	 * Original Fetch Request (available when using app.fetch)
 	 *
@@ -121,6 +135,8 @@ export const createRawContext = (
 		json: <T>() => JSON.parse(body.toString()) as T,
 		raw,
 		socket,
+		ip: socket?.remoteAddress,
+		host: headers.host,
 	}
 }
 
@@ -242,6 +258,9 @@ export const requestToRawContext = async (request: Request): Promise<RawContext>
 		},
 		raw: body,
 		socket: null, // Not available in Fetch API
+		ip: undefined, // Fetch API does not expose the peer address
+		protocol: url.protocol.replace(':', ''),
+		host: url.host,
 		request, // Store original request for delegation to other handlers
 	}
 }
@@ -267,11 +286,16 @@ export const requestToRawContext = async (request: Request): Promise<RawContext>
 export const serverResponseToResponse = (
 	response: import('@sylphx/gust-core').ServerResponse
 ): Response => {
-	// Convert headers
+	// Convert headers (array values become repeated headers)
 	const headers = new Headers()
 	if (response.headers) {
 		for (const [key, value] of Object.entries(response.headers)) {
-			if (value !== undefined) {
+			if (value === undefined) continue
+			if (Array.isArray(value)) {
+				for (const v of value) {
+					headers.append(key, String(v))
+				}
+			} else {
 				headers.set(key, String(value))
 			}
 		}
@@ -321,11 +345,18 @@ export const serverResponseToResponse = (
 export const responseToServerResponse = async (
 	response: Response
 ): Promise<import('@sylphx/gust-core').ServerResponse> => {
-	// Convert headers
-	const headers: Record<string, string> = {}
+	// Convert headers; Set-Cookie is the one header the Headers class
+	// refuses to join, so recover the individual values as an array
+	const headers: Record<string, import('@sylphx/gust-core').HeaderValue> = {}
 	response.headers.forEach((value, key) => {
 		headers[key] = value
 	})
+	if (typeof response.headers.getSetCookie === 'function') {
+		const cookies = response.headers.getSetCookie()
+		if (cookies.length > 1) {
+			headers['set-cookie'] = cookies
+		}
+	}
 
 	// Handle streaming vs buffered body
 	if (response.body) {
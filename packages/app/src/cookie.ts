@@ -153,3 +153,35 @@ export const setCookie = (name: string, value: string, options?: CookieOptions):
 export const setCookies = (cookies: Cookie[]): string[] => {
 	return cookies.map((cookie) => serializeCookie(cookie.name, cookie.value, cookie))
 }
+
+/**
+ * Merge Set-Cookie values into response headers without clobbering
+ *
+ * A new cookie replaces an existing Set-Cookie for the same cookie
+ * name; cookies for other names (from the handler or earlier
+ * middleware) are kept. Returns a new headers object.
+ */
+export const mergeSetCookie = (
+	headers: Readonly<Record<string, import('@sylphx/gust-core').HeaderValue>>,
+	...cookies: string[]
+): Record<string, import('@sylphx/gust-core').HeaderValue> => {
+	const existing = headers['set-cookie'] ?? headers['Set-Cookie']
+	const jar: string[] = existing === undefined ? [] : Array.isArray(existing) ? [...existing] : [existing as string]
+
+	const cookieName = (value: string): string => value.split('=', 1)[0]?.trim() ?? value
+
+	for (const cookie of cookies) {
+		const name = cookieName(cookie)
+		const index = jar.findIndex((c) => cookieName(c) === name)
+		if (index === -1) {
+			jar.push(cookie)
+		} else {
+			jar[index] = cookie
+		}
+	}
+
+	const merged: Record<string, import('@sylphx/gust-core').HeaderValue> = { ...headers }
+	delete merged['Set-Cookie']
+	merged['set-cookie'] = jar.length === 1 ? (jar[0] as string) : jar
+	return merged
+}
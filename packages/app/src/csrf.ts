@@ -10,7 +10,7 @@ import { createHmac, timingSafeEqual } from 'node:crypto'
 import type { Handler, ServerResponse, Wrapper } from '@sylphx/gust-core'
 import { forbidden } from '@sylphx/gust-core'
 import type { Context } from './context'
-import { type CookieOptions, parseCookies, serializeCookie } from './cookie'
+import { type CookieOptions, mergeSetCookie, parseCookies, serializeCookie } from './cookie'
 import { wasmGenerateTraceId } from './wasm-loader'
 
 // ============================================================================
@@ -211,10 +211,7 @@ export const csrf = (options: CsrfOptions): Wrapper<Context> => {
 				const setCookie = serializeCookie(cookie, csrfSecret, finalCookieOptions)
 				return {
 					...res,
-					headers: {
-						...res.headers,
-						'set-cookie': setCookie,
-					},
+					headers: mergeSetCookie(res.headers, setCookie),
 				}
 			}
 
@@ -281,10 +278,7 @@ export const csrfDoubleSubmit = (options: Omit<CsrfOptions, 'secret'> = {}): Wra
 				const setCookie = serializeCookie(cookie, csrfToken, finalCookieOptions)
 				return {
 					...res,
-					headers: {
-						...res.headers,
-						'set-cookie': setCookie,
-					},
+					headers: mergeSetCookie(res.headers, setCookie),
 				}
 			}
 
@@ -190,6 +190,7 @@ export {
 	deleteCookie,
 	getCookie,
 	getCookies,
+	mergeSetCookie,
 	parseCookies,
 	serializeCookie,
 	setCookie,
@@ -259,7 +260,9 @@ export {
 	getDefaultSessionStore,
 	getSession,
 	MemoryStore,
+	revokeSessions,
 	session,
+	SESSION_USER_KEY,
 } from './session'
 
 // Request ID / Tracing
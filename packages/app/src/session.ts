@@ -8,7 +8,7 @@
 
 import type { Handler, ServerResponse, Wrapper } from '@sylphx/gust-core'
 import type { Context } from './context'
-import { type CookieOptions, parseCookies, serializeCookie } from './cookie'
+import { type CookieOptions, mergeSetCookie, parseCookies, serializeCookie } from './cookie'
 import { wasmGenerateTraceId } from './wasm-loader'
 
 // ============================================================================
@@ -17,6 +17,12 @@ import { wasmGenerateTraceId } from './wasm-loader'
 
 export type SessionData = Record<string, unknown>
 
+/**
+ * Reserved session data key that binds a session to a user (principal).
+ * Stores indexing sessions by user read this key from the saved data.
+ */
+export const SESSION_USER_KEY = '_principal'
+
 export type Session<T extends SessionData = SessionData> = {
 	/** Session ID */
 	readonly id: string
@@ -45,6 +51,10 @@ export type SessionStore = {
 	destroy: (id: string) => Promise<void>
 	/** Touch session (update expiry) */
 	touch: (id: string, maxAge: number) => Promise<void>
+	/** Session IDs bound to a user, oldest first (stores with a user index) */
+	sessionsForUser?: (userId: string) => Promise<string[]>
+	/** Destroy every session bound to a user, returning the count */
+	revokeSessions?: (userId: string) => Promise<number>
 	/** Close and cleanup resources (intervals, connections) */
 	close?: () => void
 }
@@ -130,19 +140,44 @@ const verifySessionId = (signed: string, secret: string): string | null => {
 export class MemoryStore implements SessionStore {
 	private sessions = new Map<string, { data: SessionData; expires: number }>()
 	private cleanupInterval: ReturnType<typeof setInterval> | null = null
+	/** Session IDs per user, oldest first */
+	private byUser = new Map<string, string[]>()
+	/** Max concurrent sessions per user (0 = unlimited) */
+	private maxSessionsPerUser: number
+
+	constructor(options: { maxSessionsPerUser?: number } = {}) {
+		this.maxSessionsPerUser = options.maxSessionsPerUser ?? 0
 
-	constructor() {
 		// Cleanup expired sessions every minute
 		this.cleanupInterval = setInterval(() => {
 			const now = Date.now()
 			for (const [id, session] of this.sessions) {
 				if (session.expires < now) {
 					this.sessions.delete(id)
+					this.unindex(session.data, id)
 				}
 			}
 		}, 60000)
 	}
 
+	private userOf(data: SessionData): string | null {
+		const user = data[SESSION_USER_KEY]
+		return typeof user === 'string' ? user : null
+	}
+
+	private unindex(data: SessionData, id: string): void {
+		const user = this.userOf(data)
+		if (!user) return
+		const ids = this.byUser.get(user)
+		if (!ids) return
+		const remaining = ids.filter((existing) => existing !== id)
+		if (remaining.length === 0) {
+			this.byUser.delete(user)
+		} else {
+			this.byUser.set(user, remaining)
+		}
+	}
+
 	async get(id: string): Promise<SessionData | null> {
 		const session = this.sessions.get(id)
 		if (!session) return null
@@ -154,14 +189,38 @@ export class MemoryStore implements SessionStore {
 	}
 
 	async set(id: string, data: SessionData, maxAge: number): Promise<void> {
+		// Re-binding a session to a different user drops the old index entry
+		const previous = this.sessions.get(id)
+		if (previous && this.userOf(previous.data) !== this.userOf(data)) {
+			this.unindex(previous.data, id)
+		}
+
 		this.sessions.set(id, {
 			data,
 			expires: Date.now() + maxAge,
 		})
+
+		const user = this.userOf(data)
+		if (!user) return
+
+		const ids = this.byUser.get(user) ?? []
+		if (!ids.includes(id)) {
+			ids.push(id)
+		}
+		// Evict oldest sessions over the per-user limit
+		while (this.maxSessionsPerUser > 0 && ids.length > this.maxSessionsPerUser) {
+			const oldest = ids.shift()
+			if (oldest) this.sessions.delete(oldest)
+		}
+		this.byUser.set(user, ids)
 	}
 
 	async destroy(id: string): Promise<void> {
+		const session = this.sessions.get(id)
 		this.sessions.delete(id)
+		if (session) {
+			this.unindex(session.data, id)
+		}
 	}
 
 	async touch(id: string, maxAge: number): Promise<void> {
@@ -178,11 +237,33 @@ export class MemoryStore implements SessionStore {
 		return this.sessions
 	}
 
+	/**
+	 * Session IDs bound to a user, oldest first
+	 */
+	async sessionsForUser(userId: string): Promise<string[]> {
+		return [...(this.byUser.get(userId) ?? [])]
+	}
+
+	/**
+	 * Destroy every session bound to a user
+	 * Returns the number of sessions removed.
+	 */
+	async revokeSessions(userId: string): Promise<number> {
+		const ids = this.byUser.get(userId) ?? []
+		this.byUser.delete(userId)
+		let removed = 0
+		for (const id of ids) {
+			if (this.sessions.delete(id)) removed++
+		}
+		return removed
+	}
+
 	/**
 	 * Clear all sessions
 	 */
 	clear(): void {
 		this.sessions.clear()
+		this.byUser.clear()
 	}
 
 	/**
@@ -356,10 +437,7 @@ export const session = (options: SessionOptions): Wrapper<Context> => {
 			if (setCookieHeader) {
 				return {
 					...res,
-					headers: {
-						...res.headers,
-						'set-cookie': setCookieHeader,
-					},
+					headers: mergeSetCookie(res.headers, setCookieHeader),
 				}
 			}
 
@@ -402,6 +480,17 @@ export const flash = <T = string>(ctx: Context, key: string, value?: T): T | T[]
  */
 export const getDefaultSessionStore = (): MemoryStore | null => defaultStore
 
+/**
+ * Revoke all sessions for a user
+ * Uses the default memory store unless a store is given; stores
+ * without a user index revoke nothing.
+ */
+export const revokeSessions = async (userId: string, store?: SessionStore): Promise<number> => {
+	const target = store ?? defaultStore
+	if (!target?.revokeSessions) return 0
+	return target.revokeSessions(userId)
+}
+
 /**
  * Close and cleanup the default session store
  * Call this on server shutdown to prevent memory leaks
@@ -22,12 +22,13 @@ export {
 	MethodNames,
 	Methods,
 } from './core'
-export type { ErrorResponseBody, ResponseBody, ServerResponse } from './response'
+export type { ErrorResponseBody, HeaderValue, ResponseBody, ServerResponse } from './response'
 // Response helpers (pure, no I/O)
 export {
 	badRequest,
 	errorResponse,
 	forbidden,
+	headerPairs,
 	html,
 	isStreamingBody,
 	json,
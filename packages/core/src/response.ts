@@ -11,9 +11,15 @@
  */
 export type ResponseBody = string | Buffer | AsyncIterable<Uint8Array> | null
 
+/**
+ * Response header value
+ * An array produces repeated headers on the wire (e.g. multiple Set-Cookie)
+ */
+export type HeaderValue = string | readonly string[]
+
 export type ServerResponse = {
 	readonly status: number
-	readonly headers: Readonly<Record<string, string>>
+	readonly headers: Readonly<Record<string, HeaderValue>>
 	readonly body: ResponseBody
 }
 
@@ -26,10 +32,31 @@ export const isStreamingBody = (body: ResponseBody): body is AsyncIterable<Uint8
 	!Buffer.isBuffer(body) &&
 	Symbol.asyncIterator in body
 
+/**
+ * Flatten headers to alternating name/value pairs
+ *
+ * Array values repeat the name, preserving duplicate headers (e.g.
+ * multiple Set-Cookie) across boundaries that can't carry arrays.
+ */
+export const headerPairs = (headers: Readonly<Record<string, HeaderValue>>): string[] => {
+	const pairs: string[] = []
+	for (const [name, value] of Object.entries(headers)) {
+		if (value === undefined) continue
+		if (typeof value === 'string') {
+			pairs.push(name, value)
+		} else {
+			for (const v of value) {
+				pairs.push(name, v)
+			}
+		}
+	}
+	return pairs
+}
+
 // Response constructors
 export const response = (
 	body: string | Buffer | null = null,
-	init: { status?: number; headers?: Record<string, string> } = {}
+	init: { status?: number; headers?: Record<string, HeaderValue> } = {}
 ): ServerResponse => ({
 	status: init.status ?? 200,
 	headers: init.headers ?? {},
@@ -38,7 +65,7 @@ export const response = (
 
 export const json = <T>(
 	data: T,
-	init: { status?: number; headers?: Record<string, string> } = {}
+	init: { status?: number; headers?: Record<string, HeaderValue> } = {}
 ): ServerResponse => ({
 	status: init.status ?? 200,
 	headers: {
@@ -50,7 +77,7 @@ export const json = <T>(
 
 export const text = (
 	data: string,
-	init: { status?: number; headers?: Record<string, string> } = {}
+	init: { status?: number; headers?: Record<string, HeaderValue> } = {}
 ): ServerResponse => ({
 	status: init.status ?? 200,
 	headers: {
@@ -62,7 +89,7 @@ export const text = (
 
 export const html = (
 	data: string,
-	init: { status?: number; headers?: Record<string, string> } = {}
+	init: { status?: number; headers?: Record<string, HeaderValue> } = {}
 ): ServerResponse => ({
 	status: init.status ?? 200,
 	headers: {
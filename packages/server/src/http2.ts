@@ -99,7 +99,7 @@ const nativeRequiredError = (): Error => {
 
 const toResponseData = (
 	response: ServerResponse
-): { status: number; headers: Record<string, string>; body: string } => {
+): { status: number; headers: Record<string, string>; body: string; bodyBuffer?: Uint8Array } => {
 	const headers: Record<string, string> = {}
 	if (response.headers) {
 		for (const key in response.headers) {
@@ -107,19 +107,25 @@ const toResponseData = (
 		}
 	}
 
-	const body =
-		response.body === null
-			? ''
-			: typeof response.body === 'string'
-				? response.body
-				: Buffer.isBuffer(response.body)
-					? response.body.toString()
-					: String(response.body)
+	// Binary bodies cross the boundary as bytes (bodyBuffer takes
+	// precedence over body on the native side)
+	let body = ''
+	let bodyBuffer: Uint8Array | undefined
+	if (response.body !== null && response.body !== undefined) {
+		if (typeof response.body === 'string') {
+			body = response.body
+		} else if (response.body instanceof Uint8Array) {
+			bodyBuffer = response.body
+		} else {
+			body = String(response.body)
+		}
+	}
 
 	return {
 		status: response.status,
 		headers,
 		body,
+		bodyBuffer,
 	}
 }
 
@@ -177,7 +183,13 @@ export const serveHttp2 = async (options: Http2Options): Promise<Http2ServerInst
 
 		server.setFallback(async (ctx) => {
 			try {
-				const bodyBuffer = ctx.body ? Buffer.from(ctx.body) : Buffer.alloc(0)
+				// Prefer the binary-safe bytes; ctx.body is empty for
+				// non-UTF-8 payloads
+				const bodyBuffer = ctx.bodyBuffer
+					? Buffer.from(ctx.bodyBuffer)
+					: ctx.body
+						? Buffer.from(ctx.body)
+						: Buffer.alloc(0)
 				const authority = ctx.headers.host ?? ctx.headers[':authority'] ?? ''
 
 				const http2Ctx: Http2Context = {
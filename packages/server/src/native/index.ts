@@ -41,9 +41,11 @@ export { getNativeLoadError, isNativeAvailable, loadNativeBinding } from './load
 export {
 	createNativeMetricsCollector,
 	createNativeTracer,
+	nativeFormatBaggage,
 	nativeFormatTraceparent,
 	nativeGenerateSpanId,
 	nativeGenerateTraceId,
+	nativeParseBaggage,
 	nativeParseTraceparent,
 } from './otel'
 // ----------------------------------------------------------------------------
@@ -57,6 +59,7 @@ export {
 	nativeContentRange,
 	nativeGenerateEtag,
 	nativeGetMimeType,
+	nativeMultipartByteranges,
 	nativeParseRange,
 } from './range'
 
@@ -65,10 +68,15 @@ export {
 // ----------------------------------------------------------------------------
 export { createNativeBulkhead, createNativeCircuitBreaker } from './resilience'
 // ----------------------------------------------------------------------------
+// Refresh tokens
+// ----------------------------------------------------------------------------
+export { createNativeRefreshTokens } from './refresh'
+// ----------------------------------------------------------------------------
 // Native server wrappers
 // ----------------------------------------------------------------------------
 export {
 	createNativeServer,
+	createNativeServerFromConfigFile,
 	createNativeServerWithConfig,
 	type NativeServeOptions,
 	type NativeServerHandle,
@@ -92,6 +100,7 @@ export {
 // Shared types
 // ----------------------------------------------------------------------------
 export type {
+	NativeAdminListenerConfig,
 	NativeBinding,
 	NativeBulkhead,
 	NativeBulkheadConfig,
@@ -101,15 +110,22 @@ export type {
 	NativeCompressionConfig,
 	NativeCorsConfig,
 	NativeInvokeHandlerInput,
+	NativeIssuedRefreshToken,
 	NativeMetricsCollector,
+	NativeMiddlewareUpdate,
+	NativeMultipartRangeResponse,
 	NativeParsedRange,
 	NativeProxyInfo,
 	NativeRateLimitConfig,
+	NativeRefreshTokenConfig,
+	NativeRefreshTokens,
+	NativeRefreshTokenStats,
 	NativeRouteEntry,
 	NativeRouteManifest,
 	NativeSchemaType,
 	NativeSecurityConfig,
 	NativeServer,
+	NativeServerAddress,
 	NativeServerConfig,
 	NativeSpan,
 	NativeSpanContext,
@@ -117,6 +133,7 @@ export type {
 	NativeStringFormat,
 	NativeTlsConfig,
 	NativeTracer,
+	NativeTracingConfig,
 	NativeTrustProxy,
 	NativeValidationError,
 	NativeValidationResult,
@@ -98,6 +98,42 @@ export const nativeFormatTraceparent = (
 	return wasmFormatTraceparent(traceId, spanId, traceFlags)
 }
 
+/**
+ * Parse W3C baggage header into key/value entries
+ *
+ * Entry properties are dropped and values are percent-decoded.
+ * Native only - returns null if the binding is unavailable.
+ */
+export const nativeParseBaggage = (header: string): Record<string, string> | null => {
+	const binding = loadNative()
+	if (binding?.parseBaggage) {
+		try {
+			return binding.parseBaggage(header)
+		} catch {
+			return null
+		}
+	}
+	return null
+}
+
+/**
+ * Format W3C baggage header from key/value entries
+ *
+ * Values are percent-encoded where the spec requires it.
+ * Native only - returns null if the binding is unavailable.
+ */
+export const nativeFormatBaggage = (entries: Record<string, string>): string | null => {
+	const binding = loadNative()
+	if (binding?.formatBaggage) {
+		try {
+			return binding.formatBaggage(entries)
+		} catch {
+			return null
+		}
+	}
+	return null
+}
+
 /**
  * Create a native tracer
  *
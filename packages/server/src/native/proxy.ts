@@ -16,7 +16,7 @@ import type { NativeProxyInfo, NativeTrustProxy } from './types'
  * Extract proxy information from headers using native Rust implementation
  */
 export const nativeExtractProxyInfo = (
-	trust: NativeTrustProxy,
+	trust: NativeTrustProxy | readonly string[],
 	socketIp: string,
 	headers?: {
 		forwardedFor?: string
@@ -28,8 +28,20 @@ export const nativeExtractProxyInfo = (
 ): NativeProxyInfo | null => {
 	const binding = loadNative()
 	if (!binding) return null
+	if (Array.isArray(trust)) {
+		// Explicit IP/CIDR list: per-hop X-Forwarded-For evaluation
+		return binding.extractProxyInfoWithAddresses(
+			[...trust],
+			socketIp,
+			headers?.forwardedFor,
+			headers?.forwardedHost,
+			headers?.forwardedProto,
+			headers?.forwardedPort,
+			headers?.host
+		)
+	}
 	return binding.extractProxyInfo(
-		trust,
+		trust as NativeTrustProxy,
 		socketIp,
 		headers?.forwardedFor,
 		headers?.forwardedHost,
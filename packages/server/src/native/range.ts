@@ -6,7 +6,7 @@
  */
 
 import { loadNative } from './loader'
-import type { NativeParsedRange } from './types'
+import type { NativeMultipartRangeResponse, NativeParsedRange } from './types'
 
 // ============================================================================
 // Native Range Requests
@@ -39,6 +39,22 @@ export const nativeGetMimeType = (extension: string): string | null => {
 	return binding.getMimeType(extension)
 }
 
+/**
+ * Build a multipart/byteranges body for a multi-range request
+ *
+ * Returns null when the header doesn't parse against the body size or
+ * names only one range (serve that as a plain 206).
+ */
+export const nativeMultipartByteranges = (
+	header: string,
+	body: Uint8Array,
+	contentType: string
+): NativeMultipartRangeResponse | null => {
+	const binding = loadNative()
+	if (!binding) return null
+	return binding.buildMultipartByteranges(header, body, contentType)
+}
+
 /**
  * Generate ETag from file metadata
  */
@@ -0,0 +1,38 @@
+/**
+ * Native Refresh Tokens
+ *
+ * Constructor for the native refresh token issuer (opaque tokens with
+ * hashed storage, rotation on use, and replay detection that revokes
+ * the whole token family). Returns null when the native binding is
+ * unavailable.
+ */
+
+import { loadNative } from './loader'
+import type { NativeRefreshTokenConfig, NativeRefreshTokens } from './types'
+
+/**
+ * Create a native refresh token issuer
+ *
+ * @example
+ * ```ts
+ * const tokens = createNativeRefreshTokens({ ttlSeconds: 30 * 24 * 3600 })
+ * const issued = tokens?.issue('user-42')
+ * // later, on refresh:
+ * try {
+ *   const next = tokens?.rotate(presented)
+ * } catch {
+ *   // unknown, expired, or replayed (family revoked) — force re-login
+ * }
+ * ```
+ */
+export const createNativeRefreshTokens = (
+	config?: NativeRefreshTokenConfig
+): NativeRefreshTokens | null => {
+	const binding = loadNative()
+	if (!binding?.RefreshTokens) return null
+	try {
+		return new binding.RefreshTokens(config)
+	} catch {
+		return null
+	}
+}
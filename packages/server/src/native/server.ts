@@ -189,3 +189,19 @@ export const createNativeServerWithConfig = async (
 	if (!binding) return null
 	return binding.GustServerWithConfig(config)
 }
+
+/**
+ * Create a native server from a TOML or JSON configuration file
+ *
+ * File keys map to NativeServerConfig (snake_case in TOML, camelCase
+ * in JSON); GUST_* environment variables (GUST_PORT, GUST_HOSTNAME,
+ * ...) override the file. Throws on unknown keys or invalid values,
+ * naming the offending key.
+ */
+export const createNativeServerFromConfigFile = async (
+	path: string
+): Promise<NativeServer | null> => {
+	const binding = loadNative()
+	if (!binding) return null
+	return binding.GustServerFromConfigFile(path)
+}
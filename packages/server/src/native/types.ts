@@ -51,6 +51,50 @@ export interface NativeSecurityConfig {
 	referrerPolicy?: string
 }
 
+/** Request tracing configuration for native server */
+export interface NativeTracingConfig {
+	/** Header name for the request ID (default: X-Request-ID) */
+	headerName?: string
+	/** Generate a request ID when the header is absent (default: true) */
+	generateId?: boolean
+	/** ID generator: "uuid", "uuidv7", "ulid", "snowflake", "nanoid", "shortid", or "counter" (default: nanoid) */
+	idGenerator?: string
+	/** Machine ID (0-1023) for the snowflake generator (default: 0) */
+	machineId?: number
+	/** Parse/generate W3C trace context — trace + span IDs (default: true) */
+	propagateTrace?: boolean
+	/** Log requests to stderr */
+	logRequests?: boolean
+	/** Log responses to stderr */
+	logResponses?: boolean
+}
+
+/** Tokio runtime metrics snapshot from runtimeStats() */
+export interface NativeRuntimeStats {
+	/** Worker threads in the runtime */
+	workers: number
+	/** Tasks currently alive (running or suspended) */
+	aliveTasks: number
+	/** Tasks waiting on the global injection queue */
+	globalQueueDepth: number
+	/** Total time all workers spent executing tasks, in ms */
+	busyMs: number
+	/** Times workers went to sleep waiting for work */
+	parkCount: number
+}
+
+/** Replacement config for updateMiddleware; set the field matching the name */
+export interface NativeMiddlewareUpdate {
+	/** New config when updating "cors" */
+	cors?: NativeCorsConfig
+	/** New config when updating "rateLimit" */
+	rateLimit?: NativeRateLimitConfig
+	/** New config when updating "security" */
+	security?: NativeSecurityConfig
+	/** New config when updating "tracing" */
+	tracing?: NativeTracingConfig
+}
+
 /** Compression configuration for native server */
 export interface NativeCompressionConfig {
 	/** Enable gzip */
@@ -64,6 +108,45 @@ export interface NativeCompressionConfig {
 }
 
 /** TLS/HTTPS configuration for native server */
+/** Per-content-type body limit, applied via setBodyLimits */
+export interface NativeBodyLimit {
+	/** Media type ('application/json') or prefix ending in '/' ('multipart/') */
+	contentType: string
+	/** Maximum body size in bytes for matching requests */
+	maxBytes: number
+}
+
+/**
+ * Per-phase timeout overrides in milliseconds, applied via setTimeouts
+ * (global) or setRouteTimeouts (one route pattern). Unset phases keep
+ * their current value (global) or inherit the global (per-route);
+ * 0 disables a phase.
+ */
+export interface NativeTimeoutsConfig {
+	/** Connection accept / TLS handshake deadline */
+	connectMs?: number
+	/** Request line + header read deadline */
+	headerMs?: number
+	/** Body read deadline */
+	bodyMs?: number
+	/** Handler execution deadline */
+	handlerMs?: number
+	/** Response write deadline */
+	writeMs?: number
+	/** Whole-request wall-clock deadline */
+	totalMs?: number
+}
+
+/** Timeout counts per enforced phase, from timeoutStats */
+export interface NativeTimeoutStats {
+	/** Requests that timed out reading the body (408) */
+	body: number
+	/** Requests whose handler exceeded its deadline (504) */
+	handler: number
+	/** Requests that exceeded the total deadline (504) */
+	total: number
+}
+
 export interface NativeTlsConfig {
 	/** Path to certificate file (PEM format) */
 	certPath?: string
@@ -89,6 +172,8 @@ export interface NativeServerConfig {
 	rateLimit?: NativeRateLimitConfig
 	/** Security headers configuration */
 	security?: NativeSecurityConfig
+	/** Request tracing configuration */
+	tracing?: NativeTracingConfig
 	/** Compression configuration */
 	compression?: NativeCompressionConfig
 	/** TLS/HTTPS configuration */
@@ -103,6 +188,46 @@ export interface NativeServerConfig {
 	keepAliveTimeoutMs?: number
 	/** Maximum header size in bytes (default: 8KB) */
 	maxHeaderSize?: number
+	/** Which peers to trust for X-Forwarded-* headers (default: None) */
+	trustProxy?: NativeTrustProxy
+	/** Explicit IP/CIDR list of trusted proxies (takes precedence over trustProxy) */
+	trustProxyAddresses?: string[]
+	/** Separate admin listener for health/metrics/debug endpoints */
+	admin?: NativeAdminListenerConfig
+}
+
+/**
+ * Admin listener configuration: a second listener exposing only
+ * /health, /metrics, and /debug, kept off the public port. Bind it
+ * to loopback (the default) or a unix socket.
+ */
+export interface NativeAdminListenerConfig {
+	/** TCP port for the admin listener */
+	port?: number
+	/** Bind address (default '127.0.0.1'; keep it loopback) */
+	hostname?: string
+	/** Unix socket path instead of TCP (takes precedence over port) */
+	socketPath?: string
+}
+
+/** The address a server is actually bound to, from address() */
+export interface NativeServerAddress {
+	/** Bound IP address (e.g. '127.0.0.1', '::') */
+	host: string
+	/** Bound TCP port */
+	port: number
+	/** Address family: 'IPv4' or 'IPv6' */
+	family: string
+}
+
+/** One issue found by validateConfig */
+export interface NativeConfigIssue {
+	/** 'error' (the server cannot start correctly) or 'warning' */
+	severity: string
+	/** The offending config field, dotted for nested keys */
+	field: string
+	/** What is wrong and how to fix it */
+	message: string
 }
 
 // ============================================================================
@@ -162,6 +287,58 @@ export interface NativeBulkhead {
 	queued(): number
 }
 
+// ============================================================================
+// Refresh Token Types
+// ============================================================================
+
+/** Refresh token configuration */
+export interface NativeRefreshTokenConfig {
+	/** Lifetime of each issued token in seconds (default: 30 days) */
+	ttlSeconds?: number
+	/** Lifetime cap for a whole token family in seconds (default: 90 days) */
+	familyTtlSeconds?: number
+}
+
+/** A freshly issued refresh token */
+export interface NativeIssuedRefreshToken {
+	/** The opaque token to hand to the client (storage keeps a hash) */
+	token: string
+	/** Family this token belongs to (stable across rotations) */
+	family: string
+	/** Subject the token was issued to */
+	subject: string
+	/** Milliseconds until the token expires */
+	expiresInMs: number
+}
+
+/** Refresh token counters */
+export interface NativeRefreshTokenStats {
+	issued: number
+	rotated: number
+	reuseDetected: number
+	revoked: number
+}
+
+/** Native refresh token issuer with rotation and replay detection */
+export interface NativeRefreshTokens {
+	/** Issue a new token in a new family (e.g. at login) */
+	issue(subject: string): NativeIssuedRefreshToken
+	/** Exchange a token for its successor; throws on unknown/expired tokens and on reuse (family revoked) */
+	rotate(token: string): NativeIssuedRefreshToken
+	/** Check a token without rotating it, returning its subject */
+	validate(token: string): string
+	/** Revoke a single token */
+	revoke(token: string): boolean
+	/** Revoke every token in a family, returning the count */
+	revokeFamily(family: string): number
+	/** Revoke every token issued to a subject, returning the count */
+	revokeSubject(subject: string): number
+	/** Drop expired tokens */
+	cleanup(): void
+	/** Get counters */
+	stats(): NativeRefreshTokenStats
+}
+
 // ============================================================================
 // Validation Types
 // ============================================================================
@@ -195,6 +372,16 @@ export interface NativeParsedRange {
 	end: number
 }
 
+/** Multipart/byteranges response built for a multi-range request */
+export interface NativeMultipartRangeResponse {
+	/** Content-Type carrying the generated boundary */
+	contentType: string
+	/** Content-Length of the multipart body */
+	contentLength: number
+	/** The multipart/byteranges body (RFC 7233 §4.1) */
+	body: Uint8Array
+}
+
 // ============================================================================
 // Proxy Types
 // ============================================================================
@@ -230,6 +417,8 @@ export interface NativeSpan {
 	context(): NativeSpanContext | null
 	setAttribute(key: string, value: string): void
 	setAttributeNumber(key: string, value: number): void
+	addEvent(name: string, attributes?: Record<string, string>): void
+	addLink(traceId: string, spanId: string, attributes?: Record<string, string>): void
 	end(): void
 	endWithStatus(status: NativeSpanStatus): void
 	durationMs(): number | null
@@ -295,6 +484,8 @@ export interface NativeBinding {
 	// Server
 	GustServer: new () => NativeServer
 	GustServerWithConfig: (config: NativeServerConfig) => Promise<NativeServer>
+	GustServerFromConfigFile: (path: string) => Promise<NativeServer>
+	validateConfig: (config: NativeServerConfig) => NativeConfigIssue[]
 	isIoUringAvailable: () => boolean
 	isTlsAvailable: () => boolean
 	isHttp2Available: () => boolean
@@ -309,6 +500,8 @@ export interface NativeBinding {
 		config: NativeCircuitBreakerConfig
 	) => NativeCircuitBreaker
 	Bulkhead: new (config: NativeBulkheadConfig) => NativeBulkhead
+	// Refresh Tokens
+	RefreshTokens: new (config?: NativeRefreshTokenConfig) => NativeRefreshTokens
 	// Validation
 	validateJson: (
 		jsonStr: string,
@@ -324,6 +517,11 @@ export interface NativeBinding {
 	// Range Requests
 	parseRangeHeader: (header: string, fileSize: number) => NativeParsedRange | null
 	contentRangeHeader: (start: number, end: number, total: number) => string
+	buildMultipartByteranges: (
+		header: string,
+		body: Uint8Array,
+		contentType: string
+	) => NativeMultipartRangeResponse | null
 	getMimeType: (extension: string) => string
 	generateEtag: (mtimeMs: number, size: number) => string
 	// Proxy
@@ -336,11 +534,22 @@ export interface NativeBinding {
 		forwardedPort?: string,
 		hostHeader?: string
 	) => NativeProxyInfo
+	extractProxyInfoWithAddresses: (
+		trustedAddresses: string[],
+		socketIp: string,
+		forwardedFor?: string,
+		forwardedHost?: string,
+		forwardedProto?: string,
+		forwardedPort?: string,
+		hostHeader?: string
+	) => NativeProxyInfo
 	// OpenTelemetry
 	generateTraceId: () => string
 	generateSpanId: () => string
 	parseTraceparent: (header: string) => NativeSpanContext | null
 	formatTraceparent: (traceId: string, spanId: string, traceFlags: number) => string
+	parseBaggage: (header: string) => Record<string, string>
+	formatBaggage: (entries: Record<string, string>) => string
 	Tracer: new (serviceName: string, sampleRate?: number) => NativeTracer
 	MetricsCollector: new () => NativeMetricsCollector
 	// WebSocket
@@ -368,17 +577,41 @@ export interface NativeBinding {
 export interface RequestContext {
 	method: string
 	path: string
+	/** Matched route pattern (e.g. "/users/:id"); unset when no route matched */
+	routePattern?: string
 	params: Record<string, string>
 	query?: string
 	headers: Record<string, string>
 	body: string
+	/**
+	 * Request body as raw bytes; set whenever the request has a body so
+	 * binary payloads survive the native boundary. `body` is empty when
+	 * the bytes are not valid UTF-8.
+	 */
+	bodyBuffer?: Uint8Array
+	/** Client IP (socket address, or forwarded IP behind a trusted proxy) */
+	ip?: string
+	/** Request protocol ("http"/"https") */
+	protocol?: string
+	/** Request host (Host header or trusted X-Forwarded-Host) */
+	host?: string
 }
 
 /** Response data returned from JS handlers */
 export interface ResponseData {
 	status: number
 	headers: Record<string, string>
+	/**
+	 * Headers as alternating name/value pairs; allows duplicates
+	 * (e.g. multiple Set-Cookie). Takes precedence over `headers`.
+	 */
+	headersFlat?: string[]
 	body: string
+	/**
+	 * Body as raw bytes; takes precedence over `body` when set so
+	 * binary responses round-trip without UTF-8 conversion.
+	 */
+	bodyBuffer?: Uint8Array
 }
 
 /** Route entry for Rust router registration (napi-rs auto-converts to camelCase) */
@@ -406,6 +639,9 @@ export interface NativeInvokeHandlerInput {
 		headers: Record<string, string>
 		params: Record<string, string>
 		body: Uint8Array
+		ip?: string
+		protocol?: string
+		host?: string
 	}
 }
 
@@ -439,6 +675,12 @@ export interface NativeServer {
 	enableRateLimit(config: NativeRateLimitConfig): Promise<void>
 	/** Enable security headers middleware */
 	enableSecurity(config: NativeSecurityConfig): Promise<void>
+	/** Enable request tracing middleware (IDs injected into ctx.params) */
+	enableTracing(config: NativeTracingConfig): Promise<void>
+	/** Replace a middleware's config and atomically swap the chain (no restart) */
+	updateMiddleware(name: string, config: NativeMiddlewareUpdate): Promise<void>
+	/** Remove a middleware from the chain by name; true if anything was removed */
+	removeMiddleware(name: string): Promise<boolean>
 	/** Enable compression middleware */
 	enableCompression(config: NativeCompressionConfig): Promise<void>
 	/** Enable TLS/HTTPS */
@@ -447,16 +689,44 @@ export interface NativeServer {
 	enableHttp2(): Promise<void>
 	/** Set request timeout in milliseconds */
 	setRequestTimeout(timeoutMs: number): Promise<void>
+	/** Configure the per-phase timeout hierarchy (header/body/handler/total enforced) */
+	setTimeouts(config: NativeTimeoutsConfig): Promise<void>
+	/** Override timeouts for one route pattern; unset phases inherit the global */
+	setRouteTimeouts(route: string, config: NativeTimeoutsConfig): Promise<void>
+	/** Timeout counts per enforced phase since the server started */
+	timeoutStats(): NativeTimeoutStats
+	/** Enable the token-gated profiling endpoint (/_gust/profile, folded stacks) */
+	enableProfiling(token: string): Promise<void>
+	/** Tokio runtime metrics (workers, queue depth, busy/park counters) */
+	runtimeStats(): Promise<NativeRuntimeStats>
 	/** Set maximum body size in bytes */
 	setMaxBodySize(maxBytes: number): Promise<void>
+	/** Set per-content-type body limits (first matching rule wins) */
+	setBodyLimits(limits: NativeBodyLimit[]): Promise<void>
 	/** Set keep-alive timeout in milliseconds */
 	setKeepAliveTimeout(timeoutMs: number): Promise<void>
 	/** Set maximum header size in bytes */
 	setMaxHeaderSize(maxBytes: number): Promise<void>
-	/** Start server on port */
+	/** Set which peers to trust for X-Forwarded-* headers */
+	setTrustProxy(trust: NativeTrustProxy): void
+	/** Trust an explicit IP/CIDR list with per-hop X-Forwarded-For evaluation */
+	setTrustProxyAddresses(cidrs: string[]): void
+	/** Set the diagnostic log level ('error' | 'warn' | 'info' | 'debug' | 'trace') at runtime */
+	setLogLevel(level: string): void
+	/** Set the fraction of requests eligible for debug logging (0.0-1.0) */
+	setSampleRate(rate: number): void
+	/** Warn about requests slower than this threshold in ms (0 disables) */
+	setSlowRequestThreshold(thresholdMs: number): void
+	/** Mount an admin endpoint for runtime observability control (unprotected) */
+	enableAdminEndpoint(path: string): Promise<void>
+	/** Start a separate admin listener serving only /health, /metrics, and /debug */
+	enableAdminListener(config: NativeAdminListenerConfig): Promise<void>
+	/** Start server on port (0 binds an OS-assigned ephemeral port) */
 	serve(port: number): Promise<void>
 	/** Start server with custom hostname */
 	serveWithHostname(port: number, hostname: string): Promise<void>
+	/** Actual bound address, or null before serve() / after shutdown */
+	address(): Promise<NativeServerAddress | null>
 	/** Shutdown the server immediately */
 	shutdown(): Promise<void>
 	/** Graceful shutdown - waits for connections to drain
@@ -472,7 +472,7 @@ export const otel = (options: OtelOptions): Wrapper<Context> => {
 				// Capture response headers
 				for (const header of captureResponseHeadersLower) {
 					if (res.headers[header]) {
-						span.attributes[`http.response.header.${header}`] = res.headers[header]
+						span.attributes[`http.response.header.${header}`] = String(res.headers[header])
 					}
 				}
 
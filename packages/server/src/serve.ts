@@ -14,16 +14,76 @@ import {
 	type RawContext,
 	type Route,
 } from '@sylphx/gust-app'
-import type { Handler } from '@sylphx/gust-core'
+import { type Handler, headerPairs, type ServerResponse } from '@sylphx/gust-core'
 import {
 	getNativeLoadError,
 	isHttp2Available,
 	isNativeAvailable,
 	isTlsAvailable,
 	loadNativeBinding,
+	type NativeAdminListenerConfig,
 	type NativeInvokeHandlerInput,
+	type NativeMiddlewareUpdate,
+	type NativeServerAddress,
+	type NativeTracingConfig,
+	type NativeTrustProxy,
 } from './native'
 
+/**
+ * Convert a ServerResponse to the napi ResponseData shape
+ *
+ * Array header values are sent as flat name/value pairs so duplicate
+ * headers (e.g. multiple Set-Cookie) survive the native boundary.
+ */
+const toNativeResponse = (
+	response: ServerResponse
+): {
+	status: number
+	headers: Record<string, string>
+	headersFlat?: string[]
+	body: string
+	bodyBuffer?: Uint8Array
+} => {
+	const headers: Record<string, string> = {}
+	let hasArray = false
+	if (response.headers) {
+		for (const key in response.headers) {
+			const value = response.headers[key]
+			if (Array.isArray(value)) {
+				hasArray = true
+			} else {
+				headers[key] = String(value)
+			}
+		}
+	}
+
+	// Binary bodies cross the boundary as bytes (bodyBuffer takes
+	// precedence over body on the native side)
+	let body = ''
+	let bodyBuffer: Uint8Array | undefined
+	if (response.body !== null && response.body !== undefined) {
+		if (typeof response.body === 'string') {
+			body = response.body
+		} else if (response.body instanceof Uint8Array) {
+			bodyBuffer = response.body
+		} else {
+			body = String(response.body)
+		}
+	}
+
+	if (hasArray) {
+		return {
+			status: response.status,
+			headers: {},
+			headersFlat: headerPairs(response.headers),
+			body,
+			bodyBuffer,
+		}
+	}
+
+	return { status: response.status, headers, body, bodyBuffer }
+}
+
 export type TlsOptions = {
 	/** TLS certificate (PEM format) */
 	readonly cert: string | Buffer
@@ -73,6 +133,76 @@ export type ServeOptions<App = Record<string, never>> = {
 	readonly maxHeaderSize?: number
 	/** Maximum body size in bytes (default: 1MB) */
 	readonly maxBodySize?: number
+	/**
+	 * Per-content-type body limits, e.g.
+	 * [{ contentType: 'application/json', maxBytes: 100_000 },
+	 *  { contentType: 'multipart/', maxBytes: 50_000_000 }].
+	 * The first matching rule wins; unmatched types use maxBodySize.
+	 */
+	readonly bodyLimits?: readonly { contentType: string; maxBytes: number }[]
+	/**
+	 * Per-phase timeouts in milliseconds (header read, body read,
+	 * handler execution, total). Phases left unset fall back to
+	 * requestTimeout for body and handler; 0 disables a phase.
+	 */
+	readonly timeouts?: {
+		readonly connectMs?: number
+		readonly headerMs?: number
+		readonly bodyMs?: number
+		readonly handlerMs?: number
+		readonly writeMs?: number
+		readonly totalMs?: number
+	}
+	/**
+	 * Per-route timeout overrides keyed by route pattern, e.g.
+	 * { '/upload': { bodyMs: 300_000 } }. Unset phases inherit the
+	 * global timeouts.
+	 */
+	readonly routeTimeouts?: Readonly<
+		Record<
+			string,
+			{
+				readonly connectMs?: number
+				readonly headerMs?: number
+				readonly bodyMs?: number
+				readonly handlerMs?: number
+				readonly writeMs?: number
+				readonly totalMs?: number
+			}
+		>
+	>
+	/**
+	 * Separate admin listener exposing only /health, /metrics, and
+	 * /debug, kept off the public port. Bind it to loopback (the
+	 * default) or a unix socket.
+	 */
+	readonly admin?: NativeAdminListenerConfig
+	/**
+	 * Bearer token enabling GET /_gust/profile?seconds=10 (and
+	 * /profile on the admin listener): captures a wall-clock profile
+	 * of handler execution as flamegraph-compatible folded stacks.
+	 */
+	readonly profileToken?: string
+	/**
+	 * Which peers to trust for X-Forwarded-* headers (default: 'None')
+	 *
+	 * Controls how ctx.ip, ctx.protocol, and ctx.host are computed:
+	 * behind a trusted proxy they reflect the forwarded headers,
+	 * otherwise the direct socket address and listener scheme.
+	 *
+	 * Pass an array of IPs/CIDR ranges (e.g. ['10.0.0.0/8']) to trust
+	 * specific proxies with per-hop X-Forwarded-For evaluation.
+	 */
+	readonly trustProxy?: NativeTrustProxy | readonly string[]
+	/**
+	 * Request tracing (runs in Rust)
+	 *
+	 * Each request gets an ID (echoed on the response header) and,
+	 * with propagateTrace, W3C trace/span IDs. The IDs arrive in
+	 * ctx.params as _request_id, _trace_id, and _span_id so pino or
+	 * winston log lines can be correlated with native spans.
+	 */
+	readonly tracing?: NativeTracingConfig
 	/** TLS configuration for HTTPS */
 	readonly tls?: TlsOptions
 	/** Enable HTTP/2 (only with TLS) */
@@ -80,9 +210,16 @@ export type ServeOptions<App = Record<string, never>> = {
 }
 
 export type Server = {
+	/** Actual bound port (resolved when serving on port 0) */
 	readonly port: number
 	readonly hostname: string
 	readonly tls: boolean
+	/** Actual bound address, or null after stop */
+	readonly address: () => Promise<NativeServerAddress | null>
+	/** Replace a middleware's config in place (cors/rateLimit/security/tracing) */
+	readonly updateMiddleware: (name: string, config: NativeMiddlewareUpdate) => Promise<void>
+	/** Remove a middleware from the chain; true if anything was removed */
+	readonly removeMiddleware: (name: string) => Promise<boolean>
 	/** Stop server immediately */
 	readonly stop: () => Promise<void>
 	/** Graceful shutdown - wait for active requests to complete */
@@ -221,12 +358,39 @@ const serveNative = async <App>(
 		if (options.maxBodySize !== undefined) {
 			await server.setMaxBodySize(options.maxBodySize)
 		}
+		if (options.bodyLimits !== undefined) {
+			await server.setBodyLimits([...options.bodyLimits])
+		}
+		if (options.timeouts !== undefined) {
+			await server.setTimeouts(options.timeouts)
+		}
+		if (options.routeTimeouts !== undefined) {
+			for (const [route, config] of Object.entries(options.routeTimeouts)) {
+				await server.setRouteTimeouts(route, config)
+			}
+		}
+		if (options.admin !== undefined) {
+			await server.enableAdminListener(options.admin)
+		}
+		if (options.profileToken !== undefined) {
+			await server.enableProfiling(options.profileToken)
+		}
 		if (options.keepAliveTimeout !== undefined) {
 			await server.setKeepAliveTimeout(options.keepAliveTimeout)
 		}
 		if (options.maxHeaderSize !== undefined) {
 			await server.setMaxHeaderSize(options.maxHeaderSize)
 		}
+		if (options.tracing !== undefined) {
+			await server.enableTracing(options.tracing)
+		}
+		if (options.trustProxy !== undefined) {
+			if (Array.isArray(options.trustProxy)) {
+				server.setTrustProxyAddresses([...options.trustProxy])
+			} else {
+				server.setTrustProxy(options.trustProxy as NativeTrustProxy)
+			}
+		}
 
 		if (options.app) {
 			const nativeManifest = {
@@ -247,27 +411,7 @@ const serveNative = async <App>(
 					// biome-ignore lint/style/noNonNullAssertion: app is checked above
 					const response = await options.app!.invokeHandler(input.handlerId, input.ctx)
 
-					const headers: Record<string, string> = {}
-					if (response.headers) {
-						for (const key in response.headers) {
-							headers[key] = String(response.headers[key])
-						}
-					}
-
-					const body =
-						response.body === null
-							? ''
-							: typeof response.body === 'string'
-								? response.body
-								: Buffer.isBuffer(response.body)
-									? response.body.toString()
-									: String(response.body)
-
-					return {
-						status: response.status,
-						headers,
-						body,
-					}
+					return toNativeResponse(response)
 				} catch (err) {
 					options.onError?.(err as Error)
 					return {
@@ -280,7 +424,13 @@ const serveNative = async <App>(
 		} else {
 			server.setFallback(async (ctx) => {
 				try {
-					const bodyBuffer = ctx.body ? Buffer.from(ctx.body) : Buffer.alloc(0)
+					// Prefer the binary-safe bytes; ctx.body is empty for
+					// non-UTF-8 payloads
+					const bodyBuffer = ctx.bodyBuffer
+						? Buffer.from(ctx.bodyBuffer)
+						: ctx.body
+							? Buffer.from(ctx.body)
+							: Buffer.alloc(0)
 
 					const rawCtx: RawContext = {
 						method: ctx.method,
@@ -298,31 +448,14 @@ const serveNative = async <App>(
 						},
 						raw: bodyBuffer,
 						socket: null,
+						ip: ctx.ip,
+						protocol: ctx.protocol,
+						host: ctx.host,
 					}
 
 					const response = await handler(rawCtx)
 
-					const headers: Record<string, string> = {}
-					if (response.headers) {
-						for (const key in response.headers) {
-							headers[key] = String(response.headers[key])
-						}
-					}
-
-					const body =
-						response.body === null
-							? ''
-							: typeof response.body === 'string'
-								? response.body
-								: Buffer.isBuffer(response.body)
-									? response.body.toString()
-									: String(response.body)
-
-					return {
-						status: response.status,
-						headers,
-						body,
-					}
+					return toNativeResponse(response)
 				} catch (err) {
 					options.onError?.(err as Error)
 					return {
@@ -336,12 +469,19 @@ const serveNative = async <App>(
 
 		await server.serveWithHostname(port, hostname)
 
-		options.onListen?.({ port, hostname, tls: useTls })
+		// Resolve the actual port: with port 0 the OS assigns one
+		const bound = await server.address()
+		const boundPort = bound?.port ?? port
+
+		options.onListen?.({ port: boundPort, hostname, tls: useTls })
 
 		return {
-			port,
+			port: boundPort,
 			hostname,
 			tls: useTls,
+			address: () => server.address(),
+			updateMiddleware: (name, config) => server.updateMiddleware(name, config),
+			removeMiddleware: (name) => server.removeMiddleware(name),
 			connections: () => server.activeConnections(),
 			stop: async () => {
 				await server.shutdown()